@@ -1,8 +1,14 @@
 use ark_ff::Field;
 
-use crate::{constraint_builder::ConstraintBuilder, error::Error, gate::Gate};
+use crate::{
+    circuit_graph::CircuitGraph,
+    constraint_builder::ConstraintBuilder,
+    error::Error,
+    gate::{Gate, GateType},
+};
 
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Circuit {
     pub gates: Vec<Gate>,
     pub number_of_inputs: usize,
@@ -18,6 +24,120 @@ impl Circuit {
         }
     }
 
+    /// Build a circuit whose outputs are the values on the given wires, in that order. Gates are reordered so that
+    /// outputs are computed by the last gates, as required by the t-FT encoding.
+    pub fn with_outputs(
+        gates: &[Gate],
+        number_of_inputs: usize,
+        outputs: &[usize],
+    ) -> Result<Self, Error> {
+        let (gates, _) = place_outputs_last(gates, number_of_inputs, outputs)?;
+        Ok(Self::new(gates, number_of_inputs, outputs.len()))
+    }
+
+    /// The circuit computing g(f(x)), where the outputs of `f` feed the inputs of `g` in order. Both circuits are
+    /// laid out in canonical order and the gates of g follow those of f, so the wires of f keep their indices and
+    /// every wire of g but the constant moves up by [`Circuit::composition_offset`] of f.
+    pub fn compose(f: &Circuit, g: &Circuit) -> Result<Self, Error> {
+        if f.number_of_outputs != g.number_of_inputs {
+            return Err(Error::IncompatibleCircuits(format!(
+                "The first circuit has {} outputs but the second one has {} inputs",
+                f.number_of_outputs, g.number_of_inputs
+            )));
+        }
+
+        let f = CircuitGraph::from_circuit(f)?.to_canonical_circuit()?;
+        let g = CircuitGraph::from_circuit(g)?.to_canonical_circuit()?;
+
+        // input i of g lands on the i-th output of f, which is computed by one of the last gates of f
+        let offset = f.composition_offset();
+        let wire = |w: usize| if w == 0 { 0 } else { w + offset };
+        let gates = f
+            .gates
+            .iter()
+            .cloned()
+            .chain(g.gates.iter().map(|gate| {
+                Gate::new(
+                    wire(gate.left_index),
+                    wire(gate.right_index),
+                    gate.symbol.clone(),
+                )
+            }))
+            .collect();
+
+        Ok(Self::new(gates, f.number_of_inputs, g.number_of_outputs))
+    }
+
+    /// Check that every gate refers to an existing wire, that the gates form a DAG and that no gate consumes an
+    /// output, which [`CircuitCompiler::ac2tft`](crate::circuit_compiler::CircuitCompiler::ac2tft) relies on
+    pub fn validate(&self) -> Result<(), Error> {
+        CircuitGraph::from_circuit(self)?.validate()
+    }
+
+    /// Number of rows and columns of the t-FT encoding of the circuit: one for the constant 1, then one per input
+    /// and one per gate
+    pub fn number_of_constraints(&self) -> usize {
+        1 + self.number_of_inputs + self.gates.len()
+    }
+
+    /// Pad the circuit with dummy gates computing `1 * 1` so that its t-FT encoding has `number_of_constraints`
+    /// rows, typically the size of the domain H. The dummy gates are placed right before the output gates, which
+    /// keeps the outputs last and is the padding applied by [`ConstraintBuilder::finalize`]. The circuit must not
+    /// consume its outputs, see [`Circuit::validate`].
+    pub fn pad(&self, number_of_constraints: usize) -> Result<Self, Error> {
+        let number_of_dummy_gates = self.number_of_dummy_gates(number_of_constraints)?;
+
+        let mut gates = self.gates.clone();
+        let output_gates = gates.split_off(gates.len() - self.number_of_outputs);
+        gates.extend(vec![Gate::new(0, 0, GateType::Mul); number_of_dummy_gates]);
+        gates.extend(output_gates);
+
+        Ok(Self::new(
+            gates,
+            self.number_of_inputs,
+            self.number_of_outputs,
+        ))
+    }
+
+    /// Pad an assignment of this circuit into one of [`Circuit::pad`]: the dummy gates all evaluate to 1
+    pub fn pad_assignment<F: Field>(
+        &self,
+        assignment: &[F],
+        number_of_constraints: usize,
+    ) -> Result<Vec<F>, Error> {
+        let number_of_dummy_gates = self.number_of_dummy_gates(number_of_constraints)?;
+        if assignment.len() != self.number_of_constraints() {
+            return Err(Error::InvalidWire(format!(
+                "The circuit has {} wires but the assignment has {} values",
+                self.number_of_constraints(),
+                assignment.len()
+            )));
+        }
+
+        let first_output = assignment.len() - self.number_of_outputs;
+        let mut padded = assignment[..first_output].to_vec();
+        padded.extend(vec![F::one(); number_of_dummy_gates]);
+        padded.extend_from_slice(&assignment[first_output..]);
+        Ok(padded)
+    }
+
+    fn number_of_dummy_gates(&self, number_of_constraints: usize) -> Result<usize, Error> {
+        number_of_constraints
+            .checked_sub(self.number_of_constraints())
+            .ok_or_else(|| {
+                Error::CircuitTooLarge(format!(
+                    "The circuit has {} constraints, more than the {} it should be padded to",
+                    self.number_of_constraints(),
+                    number_of_constraints
+                ))
+            })
+    }
+
+    /// How far [`Circuit::compose`] moves the wires of a circuit fed by this one
+    pub fn composition_offset(&self) -> usize {
+        self.number_of_inputs + self.gates.len() - self.number_of_outputs
+    }
+
     pub fn from_constraint_builder<F: Field>(cb: &ConstraintBuilder<F>) -> Self {
         Self {
             gates: cb.gates.clone(),
@@ -36,3 +156,80 @@ impl Circuit {
         Ok(Self::from_constraint_builder(&cb))
     }
 }
+
+/// Reorder `gates` so that the wires in `outputs` are computed by the last gates, in the order given. Output rows
+/// must not be referenced by any other row, so an output which is also consumed by a gate (or which is an input)
+/// is copied into a new gate computing `w * 1`. Returns the new gates together with, for each new gate, the wire
+/// of the original circuit holding the same value.
+pub(crate) fn place_outputs_last(
+    gates: &[Gate],
+    number_of_inputs: usize,
+    outputs: &[usize],
+) -> Result<(Vec<Gate>, Vec<usize>), Error> {
+    let number_of_wires = 1 + number_of_inputs + gates.len();
+    let gate_wire = |gate_index: usize| 1 + number_of_inputs + gate_index;
+
+    let mut consumed = vec![false; number_of_wires];
+    for gate in gates {
+        for wire in [gate.left_index, gate.right_index] {
+            if wire >= number_of_wires {
+                return Err(Error::InvalidWire(format!(
+                    "Gate refers to wire {} but the circuit only has {} wires",
+                    wire, number_of_wires
+                )));
+            }
+            consumed[wire] = true;
+        }
+    }
+
+    // for each output, whether its gate is moved to the end or its value is copied
+    let mut moved = vec![false; gates.len()];
+    let mut output_is_moved = Vec::with_capacity(outputs.len());
+    for &wire in outputs {
+        if wire >= number_of_wires {
+            return Err(Error::InvalidWire(format!(
+                "Output wire {} does not exist, the circuit only has {} wires",
+                wire, number_of_wires
+            )));
+        }
+        let can_move =
+            wire > number_of_inputs && !consumed[wire] && !moved[wire - 1 - number_of_inputs];
+        if can_move {
+            moved[wire - 1 - number_of_inputs] = true;
+        }
+        output_is_moved.push(can_move);
+    }
+
+    let body: Vec<usize> = (0..gates.len()).filter(|&i| !moved[i]).collect();
+    let mut new_wire: Vec<usize> = (0..number_of_wires).collect();
+    for (position, &i) in body.iter().enumerate() {
+        new_wire[gate_wire(i)] = gate_wire(position);
+    }
+    for (offset, (&wire, &is_moved)) in outputs.iter().zip(output_is_moved.iter()).enumerate() {
+        if is_moved {
+            new_wire[wire] = gate_wire(body.len() + offset);
+        }
+    }
+
+    let remap = |gate: &Gate| {
+        Gate::new(
+            new_wire[gate.left_index],
+            new_wire[gate.right_index],
+            gate.symbol.clone(),
+        )
+    };
+
+    let mut new_gates: Vec<Gate> = body.iter().map(|&i| remap(&gates[i])).collect();
+    let mut sources: Vec<usize> = body.iter().map(|&i| gate_wire(i)).collect();
+    for (&wire, &is_moved) in outputs.iter().zip(output_is_moved.iter()) {
+        if is_moved {
+            new_gates.push(remap(&gates[wire - 1 - number_of_inputs]));
+        } else {
+            // wire 0 is the constant 1
+            new_gates.push(Gate::new(new_wire[wire], 0, GateType::Mul));
+        }
+        sources.push(wire);
+    }
+
+    Ok((new_gates, sources))
+}
@@ -0,0 +1,148 @@
+use ark_ff::Field;
+
+use crate::{
+    circuit::Circuit,
+    component::Component,
+    error::Error,
+    gate::{Gate, GateType},
+};
+
+/// A handle to a wire of a circuit under construction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wire {
+    /// The constant 1
+    One,
+    Input(usize),
+    Gate(usize),
+}
+
+/// Builds a circuit gate by gate from handles, without tracking wire indices or values by hand.
+///
+/// ```ignore
+/// let mut cb = CircuitBuilder::<F>::new();
+/// let x = cb.input("x");
+/// let x2 = cb.mul(x, x);
+/// let five = cb.constant(5u64);
+/// let y = cb.add(x2, five);
+/// cb.output(y);
+/// let (component, constants) = cb.build("square_plus_five")?;
+/// ```
+///
+/// Inputs are numbered in the order they are declared, and the gates follow the order of the calls. Constants other
+/// than 1 are inputs labelled `const_i`, whose values are returned along with the circuit.
+pub struct CircuitBuilder<F: Field> {
+    gates: Vec<(Wire, Wire, GateType)>,
+    inputs: Vec<String>,
+    /// Position among the inputs and value of every constant
+    constants: Vec<(usize, F)>,
+    outputs: Vec<(String, Wire)>,
+}
+
+impl<F: Field> CircuitBuilder<F> {
+    pub fn new() -> Self {
+        Self {
+            gates: Vec::new(),
+            inputs: Vec::new(),
+            constants: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    pub fn input(&mut self, label: &str) -> Wire {
+        self.inputs.push(label.into());
+        Wire::Input(self.inputs.len() - 1)
+    }
+
+    /// The constant wire for 1, or an input holding `value`. Inputs are shared by constants of the same value.
+    pub fn constant<V: Into<F>>(&mut self, value: V) -> Wire {
+        let value = value.into();
+        if value.is_one() {
+            return Wire::One;
+        }
+        if let Some(&(position, _)) = self.constants.iter().find(|(_, v)| *v == value) {
+            return Wire::Input(position);
+        }
+
+        let wire = self.input(&format!("const_{}", self.constants.len()));
+        self.constants.push((self.inputs.len() - 1, value));
+        wire
+    }
+
+    pub fn add(&mut self, left: Wire, right: Wire) -> Wire {
+        self.gate(left, right, GateType::Add)
+    }
+
+    pub fn mul(&mut self, left: Wire, right: Wire) -> Wire {
+        self.gate(left, right, GateType::Mul)
+    }
+
+    fn gate(&mut self, left: Wire, right: Wire, symbol: GateType) -> Wire {
+        self.gates.push((left, right, symbol));
+        Wire::Gate(self.gates.len() - 1)
+    }
+
+    /// Make `wire` the next output, labelled `out_i`
+    pub fn output(&mut self, wire: Wire) {
+        let label = format!("out_{}", self.outputs.len());
+        self.output_as(&label, wire);
+    }
+
+    pub fn output_as(&mut self, label: &str, wire: Wire) {
+        self.outputs.push((label.into(), wire));
+    }
+
+    /// The circuit, with the label and value of every constant input
+    pub fn build(self, name: &str) -> Result<(Component, Vec<(String, F)>), Error> {
+        let number_of_inputs = self.inputs.len();
+        // a gate can only refer to the gates built before it
+        let index = |wire: Wire, gates_before: usize| match wire {
+            Wire::One => Ok(0),
+            Wire::Input(i) if i < number_of_inputs => Ok(1 + i),
+            Wire::Gate(i) if i < gates_before => Ok(1 + number_of_inputs + i),
+            _ => Err(Error::InvalidWire(format!(
+                "{:?} belongs to another circuit builder",
+                wire
+            ))),
+        };
+
+        let gates = self
+            .gates
+            .iter()
+            .enumerate()
+            .map(|(k, (left, right, symbol))| {
+                Ok(Gate::new(
+                    index(*left, k)?,
+                    index(*right, k)?,
+                    symbol.clone(),
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|(_, wire)| index(*wire, self.gates.len()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let circuit = Circuit::with_outputs(&gates, number_of_inputs, &outputs)?;
+
+        let output_labels = self
+            .outputs
+            .iter()
+            .map(|(label, _)| label.as_str())
+            .collect::<Vec<_>>();
+        let inputs = self.inputs.iter().map(String::as_str).collect::<Vec<_>>();
+        let component = Component::new(name, circuit, &inputs, &output_labels)?;
+
+        let constants = self
+            .constants
+            .into_iter()
+            .map(|(position, value)| (self.inputs[position].clone(), value))
+            .collect();
+        Ok((component, constants))
+    }
+}
+
+impl<F: Field> Default for CircuitBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
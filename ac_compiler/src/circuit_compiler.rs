@@ -3,12 +3,35 @@ use ark_ff::PrimeField;
 // use ark_marlin::ahp::indexer::Matrix;
 use std::{cmp::max, marker::PhantomData};
 
-use crate::{circuit::Circuit, empty_matrix, gate::GateType};
+use crate::{
+    circuit::Circuit, circuit_graph::CircuitGraph, empty_matrix, error::Error, gate::GateType,
+};
 
 /// Given: an arithmetic circuit with ng gates, ni inputs, and no <= ng outputs, where gates are triples of (left_input_index, right_input_index, (add/mul))
 /// Produces: An index for R_R1CS-f(ng + ni + 1, ni + 1, no)
 pub trait CircuitCompiler<F: PrimeField> {
+    /// Panics if the circuit is not valid, see [`Circuit::validate`]
     fn ac2tft(circuit: &Circuit) -> (R1CSfIndex, Matrix<F>, Matrix<F>, Matrix<F>);
+
+    /// Validate the circuit before compiling it
+    #[allow(clippy::type_complexity)]
+    fn try_ac2tft(
+        circuit: &Circuit,
+    ) -> Result<(R1CSfIndex, Matrix<F>, Matrix<F>, Matrix<F>), Error> {
+        circuit.validate()?;
+        Ok(Self::ac2tft(circuit))
+    }
+
+    /// Validate the circuit and compile it padded to `number_of_constraints` rows, see [`Circuit::pad`]. The
+    /// t-FT, t-Diag and discrete log comparison proofs expect the matrices to have exactly |H| rows.
+    #[allow(clippy::type_complexity)]
+    fn ac2tft_padded(
+        circuit: &Circuit,
+        number_of_constraints: usize,
+    ) -> Result<(R1CSfIndex, Matrix<F>, Matrix<F>, Matrix<F>), Error> {
+        circuit.validate()?;
+        Self::try_ac2tft(&circuit.pad(number_of_constraints)?)
+    }
 }
 
 pub struct VanillaCompiler<F: PrimeField> {
@@ -17,6 +40,14 @@ pub struct VanillaCompiler<F: PrimeField> {
 
 impl<F: PrimeField> CircuitCompiler<F> for VanillaCompiler<F> {
     fn ac2tft(circuit: &Circuit) -> (R1CSfIndex, Matrix<F>, Matrix<F>, Matrix<F>) {
+        // gates are laid out in canonical topological order, which leaves already ordered circuits untouched
+        let circuit = &CircuitGraph::from_circuit(circuit)
+            .and_then(|graph| {
+                graph.validate()?;
+                graph.to_canonical_circuit()
+            })
+            .unwrap_or_else(|e| panic!("invalid circuit: {:?}", e));
+
         let number_of_constraints = circuit.gates.len() + circuit.number_of_inputs + 1;
         let number_of_input_rows = circuit.number_of_inputs + 1; // this is the `t` value in a t-functional triple
         let number_of_outputs = circuit.number_of_outputs;
@@ -0,0 +1,234 @@
+use std::{
+    cmp::{max, Reverse},
+    collections::{BTreeMap, BinaryHeap},
+};
+
+use crate::{
+    circuit::Circuit,
+    error::Error,
+    gate::{Gate, GateType},
+};
+
+/// The DAG formed by the gates of a circuit. Wires follow the indexing used by the compiler: wire 0 is the
+/// constant 1, wires 1..=number_of_inputs are the inputs and wire `1 + number_of_inputs + i` is the output of gate i.
+/// The last `number_of_outputs` gates are the circuit outputs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CircuitGraph {
+    gates: Vec<Gate>,
+    number_of_inputs: usize,
+    number_of_outputs: usize,
+    /// For each gate, the gates that consume its output
+    successors: Vec<Vec<usize>>,
+}
+
+impl CircuitGraph {
+    pub fn from_gates(
+        gates: &[Gate],
+        number_of_inputs: usize,
+        number_of_outputs: usize,
+    ) -> Result<Self, Error> {
+        if number_of_outputs > gates.len() {
+            return Err(Error::InvalidWire(format!(
+                "Circuit has {} outputs but only {} gates",
+                number_of_outputs,
+                gates.len()
+            )));
+        }
+
+        let number_of_wires = 1 + number_of_inputs + gates.len();
+        let mut successors = vec![vec![]; gates.len()];
+        for (i, gate) in gates.iter().enumerate() {
+            for wire in [gate.left_index, gate.right_index] {
+                if wire >= number_of_wires {
+                    return Err(Error::InvalidWire(format!(
+                        "Gate {} refers to wire {} but the circuit only has {} wires",
+                        i, wire, number_of_wires
+                    )));
+                }
+                if wire > number_of_inputs {
+                    successors[wire - 1 - number_of_inputs].push(i);
+                }
+            }
+        }
+
+        Ok(Self {
+            gates: gates.to_vec(),
+            number_of_inputs,
+            number_of_outputs,
+            successors,
+        })
+    }
+
+    pub fn from_circuit(circuit: &Circuit) -> Result<Self, Error> {
+        Self::from_gates(
+            &circuit.gates,
+            circuit.number_of_inputs,
+            circuit.number_of_outputs,
+        )
+    }
+
+    /// Index of the gate driving `wire`, or None if the wire is the constant or an input
+    fn gate_of_wire(&self, wire: usize) -> Option<usize> {
+        if wire > self.number_of_inputs {
+            Some(wire - 1 - self.number_of_inputs)
+        } else {
+            None
+        }
+    }
+
+    fn is_output(&self, gate_index: usize) -> bool {
+        gate_index >= self.gates.len() - self.number_of_outputs
+    }
+
+    /// Number of gates consuming each wire, indexed by wire. A gate using the same wire twice counts twice.
+    pub fn fan_out(&self) -> Vec<usize> {
+        let mut fan_out = vec![0; 1 + self.number_of_inputs + self.gates.len()];
+        for gate in &self.gates {
+            fan_out[gate.left_index] += 1;
+            fan_out[gate.right_index] += 1;
+        }
+        fan_out
+    }
+
+    /// Canonical topological order of the gates: among the gates whose operands are available, non-output gates
+    /// are scheduled first, then the one with the smallest original index. A circuit whose gates only refer to
+    /// earlier wires is therefore left in its original order.
+    pub fn topological_order(&self) -> Result<Vec<usize>, Error> {
+        let mut in_degree: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|gate| {
+                [gate.left_index, gate.right_index]
+                    .iter()
+                    .filter(|&&wire| self.gate_of_wire(wire).is_some())
+                    .count()
+            })
+            .collect();
+
+        let mut ready: BinaryHeap<Reverse<(bool, usize)>> = in_degree
+            .iter()
+            .enumerate()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(i, _)| Reverse((self.is_output(i), i)))
+            .collect();
+
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(Reverse((_, i))) = ready.pop() {
+            order.push(i);
+            for &successor in &self.successors[i] {
+                in_degree[successor] -= 1;
+                if in_degree[successor] == 0 {
+                    ready.push(Reverse((self.is_output(successor), successor)));
+                }
+            }
+        }
+
+        if order.len() != self.gates.len() {
+            return Err(Error::CyclicCircuit(format!(
+                "{} gates are part of a cycle",
+                self.gates.len() - order.len()
+            )));
+        }
+
+        Ok(order)
+    }
+
+    /// Depth of the circuit: the largest number of gates on a path from an input to a gate output
+    pub fn depth(&self) -> Result<usize, Error> {
+        let mut depths = vec![0; self.gates.len()];
+        let wire_depth = |depths: &[usize], wire: usize| match self.gate_of_wire(wire) {
+            Some(gate_index) => depths[gate_index],
+            None => 0,
+        };
+
+        for i in self.topological_order()? {
+            let gate = &self.gates[i];
+            depths[i] = 1 + max(
+                wire_depth(&depths, gate.left_index),
+                wire_depth(&depths, gate.right_index),
+            );
+        }
+
+        Ok(depths.into_iter().max().unwrap_or(0))
+    }
+
+    /// Check that the circuit can be compiled: the gates form a DAG and no gate consumes an output, whose rows must
+    /// not be referenced in the t-FT encoding. Wires are already checked when building the graph.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.topological_order()?;
+
+        let first_output = self.gates.len() - self.number_of_outputs;
+        for (i, successors) in self.successors.iter().enumerate().skip(first_output) {
+            if let Some(successor) = successors.first() {
+                return Err(Error::InvalidWire(format!(
+                    "Output wire {} is consumed by gate {}",
+                    1 + self.number_of_inputs + i,
+                    successor
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rebuild the circuit with its gates in canonical topological order
+    pub fn to_canonical_circuit(&self) -> Result<Circuit, Error> {
+        let order = self.topological_order()?;
+        let identity: Vec<usize> = (0..1 + self.number_of_inputs + self.gates.len()).collect();
+        Ok(self.rebuild(&order, &identity))
+    }
+
+    /// Merge gates computing the same operation on the same operands. Both gate types are commutative, so operands
+    /// are compared unordered. Output gates are never removed. The returned circuit is in canonical order and
+    /// its wires are renumbered, so any assignment has to be recomputed for it.
+    pub fn common_subexpression_elimination(&self) -> Result<Circuit, Error> {
+        let order = self.topological_order()?;
+
+        // maps each wire to the wire of the first gate computing the same value
+        let mut representative: Vec<usize> =
+            (0..1 + self.number_of_inputs + self.gates.len()).collect();
+        let mut seen: BTreeMap<(GateType, usize, usize), usize> = BTreeMap::new();
+        let mut kept = Vec::with_capacity(order.len());
+
+        for &i in &order {
+            let gate = &self.gates[i];
+            let left = representative[gate.left_index];
+            let right = representative[gate.right_index];
+            let key = (gate.symbol.clone(), left.min(right), left.max(right));
+            let wire = 1 + self.number_of_inputs + i;
+
+            match seen.get(&key) {
+                Some(&existing) if !self.is_output(i) => representative[wire] = existing,
+                _ => {
+                    seen.entry(key).or_insert(wire);
+                    kept.push(i);
+                }
+            }
+        }
+
+        Ok(self.rebuild(&kept, &representative))
+    }
+
+    /// Build a circuit made of the gates in `order`, with every operand replaced by its representative and
+    /// renumbered to follow the new gate positions
+    fn rebuild(&self, order: &[usize], representative: &[usize]) -> Circuit {
+        let mut new_wire: Vec<usize> = (0..1 + self.number_of_inputs + self.gates.len()).collect();
+        for (position, &i) in order.iter().enumerate() {
+            new_wire[1 + self.number_of_inputs + i] = 1 + self.number_of_inputs + position;
+        }
+
+        let gates = order
+            .iter()
+            .map(|&i| {
+                let gate = &self.gates[i];
+                Gate::new(
+                    new_wire[representative[gate.left_index]],
+                    new_wire[representative[gate.right_index]],
+                    gate.symbol.clone(),
+                )
+            })
+            .collect();
+
+        Circuit::new(gates, self.number_of_inputs, self.number_of_outputs)
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::BTreeSet;
+
+use crate::{circuit::Circuit, circuit_graph::CircuitGraph, error::Error, gate::Gate};
+
+/// A circuit whose inputs and outputs are labelled, so that it can be wired to other components. Outside of the
+/// component its labels are namespaced by its name: the input `x` of the component `adder` is `adder.x`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Component {
+    pub name: String,
+    pub circuit: Circuit,
+    /// Labels of the inputs, in order
+    pub inputs: Vec<String>,
+    /// Labels of the outputs, in order
+    pub outputs: Vec<String>,
+}
+
+impl Component {
+    pub fn new<S: AsRef<str>>(
+        name: &str,
+        circuit: Circuit,
+        inputs: &[S],
+        outputs: &[S],
+    ) -> Result<Self, Error> {
+        if inputs.len() != circuit.number_of_inputs || outputs.len() != circuit.number_of_outputs {
+            return Err(Error::InvalidWire(format!(
+                "Component {} has {} inputs and {} outputs but {} and {} labels were given",
+                name,
+                circuit.number_of_inputs,
+                circuit.number_of_outputs,
+                inputs.len(),
+                outputs.len()
+            )));
+        }
+
+        let mut seen = BTreeSet::new();
+        for label in inputs.iter().chain(outputs.iter()) {
+            if !seen.insert(label.as_ref()) {
+                return Err(Error::VarAlreadyExists(format!(
+                    "Label {} is used twice in component {}",
+                    label.as_ref(),
+                    name
+                )));
+            }
+        }
+
+        Ok(Self {
+            name: name.into(),
+            circuit,
+            inputs: inputs.iter().map(|label| label.as_ref().into()).collect(),
+            outputs: outputs.iter().map(|label| label.as_ref().into()).collect(),
+        })
+    }
+
+    /// The label as seen from outside of the component
+    pub fn namespaced(&self, label: &str) -> String {
+        format!("{}.{}", self.name, label)
+    }
+
+    /// Position of a namespaced label among `labels`
+    fn position(&self, labels: &[String], namespaced: &str) -> Option<usize> {
+        let label = namespaced
+            .strip_prefix(self.name.as_str())?
+            .strip_prefix('.')?;
+        labels.iter().position(|l| l == label)
+    }
+
+    /// Index of the gate computing the i-th output: outputs are computed by the last gates
+    fn output_gate(&self, i: usize) -> usize {
+        self.circuit.gates.len() - self.circuit.number_of_outputs + i
+    }
+}
+
+/// Stack two components into one named `name`, feeding outputs into inputs as given by `wiring`, a list of
+/// (output, input) pairs of namespaced labels. Wiring may go both ways between the components.
+///
+/// The inputs of the result are the unwired inputs of `a` then those of `b`, and its outputs are the unwired outputs
+/// of `a` then those of `b`, all keeping their namespaced labels. An output can feed several inputs, but every input
+/// is fed at most once. Wiring that makes a gate depend on its own output fails with [`Error::CyclicCircuit`].
+pub fn concat_circuits(
+    name: &str,
+    a: &Component,
+    b: &Component,
+    wiring: &[(&str, &str)],
+) -> Result<Component, Error> {
+    if a.name == b.name {
+        return Err(Error::IncompatibleCircuits(format!(
+            "Both components are named {}",
+            a.name
+        )));
+    }
+    let components = [a, b];
+
+    // for every input, the (component, output) feeding it
+    let mut sources: [Vec<Option<(usize, usize)>>; 2] =
+        [vec![None; a.inputs.len()], vec![None; b.inputs.len()]];
+    let mut wired_outputs = [vec![false; a.outputs.len()], vec![false; b.outputs.len()]];
+    for &(output, input) in wiring {
+        let find = |label: &str, labels: fn(&Component) -> &[String]| {
+            components.iter().enumerate().find_map(|(c, component)| {
+                component
+                    .position(labels(component), label)
+                    .map(|position| (c, position))
+            })
+        };
+
+        let source = find(output, |component| &component.outputs).ok_or_else(|| {
+            Error::VarMissing(format!("No component has an output labelled {}", output))
+        })?;
+        let (c, i) = find(input, |component| &component.inputs).ok_or_else(|| {
+            Error::VarMissing(format!("No component has an input labelled {}", input))
+        })?;
+
+        if sources[c][i].replace(source).is_some() {
+            return Err(Error::VarAlreadyExists(format!(
+                "Input {} is wired twice",
+                input
+            )));
+        }
+        wired_outputs[source.0][source.1] = true;
+    }
+
+    // inputs and outputs of the result, with the wire of each in its own component
+    let unwired = |labels: &[String], wired: &dyn Fn(usize) -> bool| {
+        (0..labels.len()).filter(|&i| !wired(i)).collect::<Vec<_>>()
+    };
+    let free_inputs = [0, 1].map(|c| unwired(&components[c].inputs, &|i| sources[c][i].is_some()));
+    let free_outputs = [0, 1].map(|c| unwired(&components[c].outputs, &|i| wired_outputs[c][i]));
+
+    let number_of_inputs = free_inputs[0].len() + free_inputs[1].len();
+    let mut input_wire = [vec![0; a.inputs.len()], vec![0; b.inputs.len()]];
+    for (k, (c, i)) in [0, 1]
+        .iter()
+        .flat_map(|&c| free_inputs[c].iter().map(move |&i| (c, i)))
+        .enumerate()
+    {
+        input_wire[c][i] = 1 + k;
+    }
+
+    // the gates of a come first, then those of b
+    let gate_base = [0, a.circuit.gates.len()];
+    let gate_wire = |c: usize, gate: usize| 1 + number_of_inputs + gate_base[c] + gate;
+    let wire = |c: usize, w: usize| {
+        let component = components[c];
+        if w == 0 {
+            0
+        } else if w <= component.circuit.number_of_inputs {
+            match sources[c][w - 1] {
+                Some((d, i)) => gate_wire(d, components[d].output_gate(i)),
+                None => input_wire[c][w - 1],
+            }
+        } else {
+            gate_wire(c, w - 1 - component.circuit.number_of_inputs)
+        }
+    };
+
+    let gates = [0, 1]
+        .iter()
+        .flat_map(|&c| {
+            components[c].circuit.gates.iter().map(move |gate| {
+                Gate::new(
+                    wire(c, gate.left_index),
+                    wire(c, gate.right_index),
+                    gate.symbol.clone(),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let outputs = [0, 1]
+        .iter()
+        .flat_map(|&c| {
+            free_outputs[c]
+                .iter()
+                .map(move |&i| gate_wire(c, components[c].output_gate(i)))
+        })
+        .collect::<Vec<_>>();
+
+    let circuit = Circuit::with_outputs(&gates, number_of_inputs, &outputs)?;
+    let circuit = CircuitGraph::from_circuit(&circuit)?.to_canonical_circuit()?;
+
+    let labels = |free: &[Vec<usize>; 2], labels: fn(&Component) -> &[String]| {
+        [0, 1]
+            .iter()
+            .flat_map(|&c| {
+                let component = components[c];
+                free[c]
+                    .iter()
+                    .map(move |&i| component.namespaced(&labels(component)[i]))
+            })
+            .collect::<Vec<_>>()
+    };
+    let inputs = labels(&free_inputs, |component| &component.inputs);
+    let outputs = labels(&free_outputs, |component| &component.outputs);
+
+    Ok(Component {
+        name: name.into(),
+        circuit,
+        inputs,
+        outputs,
+    })
+}
@@ -1,6 +1,7 @@
 use std::{collections::BTreeMap, marker::PhantomData};
 
 use crate::{
+    circuit::{place_outputs_last, Circuit},
     error::Error,
     gate::{Gate, GateType},
     variable::{Variable, VariableType},
@@ -13,6 +14,8 @@ pub struct ConstraintBuilder<F: Field> {
     curr_index: usize,
     pub(crate) number_of_inputs: usize,
     pub(crate) number_of_outputs: usize,
+    /// Wires of the output variables, in the order they were declared
+    outputs: Vec<usize>,
     pub assignment: Vec<F>,
     _f: PhantomData<F>,
 }
@@ -25,6 +28,7 @@ impl<F: Field> ConstraintBuilder<F> {
             curr_index: 1, // we reserve first input to be dummy selector constraint for addition
             number_of_inputs: 0,
             number_of_outputs: 0,
+            outputs: Vec::new(),
             assignment: vec![F::one()], // we add 1 to be the value of dummy selector
             _f: PhantomData,
         }
@@ -53,7 +57,7 @@ impl<F: Field> ConstraintBuilder<F> {
             Some(index) => Ok(*index),
             None => Err(Error::VarMissing(format!(
                 "Var with label {} doesn't exists",
-                lhs.label
+                rhs.label
             ))),
         }?;
 
@@ -88,7 +92,7 @@ impl<F: Field> ConstraintBuilder<F> {
         variable_type: VariableType,
     ) -> Result<Variable<F>, Error> {
         // we don't allow vars with same labels
-        if self.label_to_var_index.contains_key(label.into()) {
+        if self.label_to_var_index.contains_key(label) {
             return Err(Error::VarAlreadyExists(format!(
                 "Var with label {} already exists",
                 label
@@ -114,6 +118,7 @@ impl<F: Field> ConstraintBuilder<F> {
             }
             VariableType::Witness => {}
             VariableType::Output => {
+                self.outputs.push(self.curr_index - 1);
                 self.number_of_outputs += 1;
             }
         };
@@ -137,23 +142,22 @@ impl<F: Field> ConstraintBuilder<F> {
             }
         };
 
-        let unnormalized_num_of_constraints = self.gates.len() + self.number_of_inputs + 1;
-        let number_of_constraints = next_power_of_2(unnormalized_num_of_constraints);
-        let number_of_dummy_constraints = number_of_constraints - unnormalized_num_of_constraints;
+        // outputs are moved to the last gates, copying those which are consumed by other gates
+        let (gates, sources) =
+            place_outputs_last(&self.gates, self.number_of_inputs, &self.outputs)
+                .expect("outputs are always registered wires");
+        let mut assignment = self.assignment[..1 + self.number_of_inputs].to_vec();
+        assignment.extend(sources.iter().map(|&wire| self.assignment[wire]));
 
         // dummy constraint is represented as 1 * 1 = 1 where we refer to dummy variable at first position
-        let output_gates = self
-            .gates
-            .split_off(self.gates.len() - self.number_of_outputs);
-        let dummy_gates = vec![Gate::new(0, 0, GateType::Mul); number_of_dummy_constraints];
-        self.gates.extend(dummy_gates);
-        self.gates.extend(output_gates);
-
-        let outputs = self
-            .assignment
-            .split_off(unnormalized_num_of_constraints - self.number_of_outputs);
-        let dummy_assignment = vec![F::one(); number_of_dummy_constraints];
-        self.assignment.extend(&dummy_assignment);
-        self.assignment.extend(&outputs);
+        let circuit = Circuit::new(gates, self.number_of_inputs, self.number_of_outputs);
+        let number_of_constraints = next_power_of_2(circuit.number_of_constraints());
+        self.assignment = circuit
+            .pad_assignment(&assignment, number_of_constraints)
+            .expect("the assignment covers every wire");
+        self.gates = circuit
+            .pad(number_of_constraints)
+            .expect("padding to the next power of 2 never shrinks the circuit")
+            .gates;
     }
 }
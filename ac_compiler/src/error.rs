@@ -2,4 +2,9 @@
 pub enum Error {
     VarAlreadyExists(String),
     VarMissing(String),
+    InvalidWire(String),
+    CyclicCircuit(String),
+    IncompatibleCircuits(String),
+    InvalidEncoding(String),
+    CircuitTooLarge(String),
 }
@@ -1,34 +1,39 @@
-use crate::{
-    circuit::Circuit,
-    gate::{Gate, GateType},
-};
+use ark_ff::Field;
 
-/// Encode the circuit x^2 + 5
-pub fn sample_circuit_1() -> Circuit {
-    // index 0: x [public]
-    // index 1: 5 [public]
-    // index 2: x^2 [witness]
-    let gate_0 = Gate::new(0, 0, GateType::Mul);
-    let gate_1 = Gate::new(2, 1, GateType::Add);
+use crate::{circuit::Circuit, circuit_builder::CircuitBuilder};
 
-    Circuit::new(vec![gate_0, gate_1], 2, 1)
+/// Encode the circuit x^2 + 5, with inputs x and the constant 5
+pub fn sample_circuit_1<F: Field>() -> Circuit {
+    let mut cb = CircuitBuilder::<F>::new();
+    let x = cb.input("x");
+    let five = cb.constant(5u64);
+
+    let x2 = cb.mul(x, x);
+    let y = cb.add(x2, five);
+    cb.output(y);
+
+    let (component, _) = cb
+        .build("sample_circuit_1")
+        .expect("the circuit is well formed");
+    component.circuit
 }
 
-/// Encode the circuit x^3 + 2x + 5
-pub fn sample_circuit_2() -> Circuit {
-    // index 0: 2 [public]
-    // index 1: 5 [public]
-    // index 2: x [public]
-    // index 3: x^2 [witness]
-    // index 4: x^3 [witness]
-    // index 5: 2x [witness]
-    // index 6: x^3 + 2x [witness]
+/// Encode the circuit x^3 + 2x + 5, with inputs the constants 2 and 5 and x
+pub fn sample_circuit_2<F: Field>() -> Circuit {
+    let mut cb = CircuitBuilder::<F>::new();
+    let two = cb.constant(2u64);
+    let five = cb.constant(5u64);
+    let x = cb.input("x");
 
-    let gate_0 = Gate::new(2, 2, GateType::Mul); // produce x^2
-    let gate_1 = Gate::new(3, 2, GateType::Mul); // produce x^3
-    let gate_2 = Gate::new(0, 2, GateType::Mul); // produce 2x
-    let gate_3 = Gate::new(4, 5, GateType::Add); // produce x^3 + 2x
-    let gate_4 = Gate::new(6, 1, GateType::Add); // produce the output
+    let x2 = cb.mul(x, x);
+    let x3 = cb.mul(x2, x);
+    let two_x = cb.mul(two, x);
+    let sum = cb.add(x3, two_x);
+    let y = cb.add(sum, five);
+    cb.output(y);
 
-    Circuit::new(vec![gate_0, gate_1, gate_2, gate_3, gate_4], 3, 1)
+    let (component, _) = cb
+        .build("sample_circuit_2")
+        .expect("the circuit is well formed");
+    component.circuit
 }
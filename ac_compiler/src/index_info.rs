@@ -0,0 +1,63 @@
+use std::collections::BTreeSet;
+
+use ark_ff::Field;
+
+use crate::Matrix;
+
+/// Dimensions and sparsity of the matrices of a t-functional triple, as needed to choose the domains H and K
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IndexInfo {
+    /// Number of rows of the largest of A, B and C, which are square
+    pub dims: usize,
+    /// Number of leading rows without entries, reserved for the constant 1 and the inputs
+    pub t: usize,
+    /// Number of positions at which at least one of A, B and C has an entry
+    pub joint_non_zero: usize,
+    /// The largest number of non-zero entries among A, B and C
+    pub max_non_zero: usize,
+}
+
+/// Compute the [`IndexInfo`] of a t-functional triple. `t` is read from the matrices: it is the index of the first
+/// row with an entry in any of them, or the number of rows if they are all empty.
+pub fn index_info<F: Field>(a: &Matrix<F>, b: &Matrix<F>, c: &Matrix<F>) -> IndexInfo {
+    let dims = a.len().max(b.len()).max(c.len());
+    let row_is_empty = |i: usize| {
+        [a, b, c]
+            .iter()
+            .all(|matrix| matrix.get(i).into_iter().all(|row| row.is_empty()))
+    };
+    let t = (0..dims).find(|&i| !row_is_empty(i)).unwrap_or(dims);
+
+    IndexInfo {
+        dims,
+        t,
+        joint_non_zero: num_non_zero(&joint_matrix(a, b, c)),
+        max_non_zero: [a, b, c]
+            .iter()
+            .map(|matrix| num_non_zero(matrix))
+            .max()
+            .unwrap_or(0),
+    }
+}
+
+/// Count the non-zero entries of a matrix
+pub fn num_non_zero<F: Field>(matrix: &Matrix<F>) -> usize {
+    matrix.iter().map(|row| row.len()).sum()
+}
+
+/// Compute the joint sparsity pattern of three matrices: the result has a 1 at every position where at least one
+/// of `a`, `b` or `c` has an entry
+pub fn joint_matrix<F: Field>(a: &Matrix<F>, b: &Matrix<F>, c: &Matrix<F>) -> Matrix<F> {
+    let number_of_rows = a.len().max(b.len()).max(c.len());
+    (0..number_of_rows)
+        .map(|i| {
+            let mut columns = BTreeSet::new();
+            for matrix in [a, b, c] {
+                if let Some(row) = matrix.get(i) {
+                    columns.extend(row.iter().map(|(_, col)| *col));
+                }
+            }
+            columns.into_iter().map(|col| (F::one(), col)).collect()
+        })
+        .collect()
+}
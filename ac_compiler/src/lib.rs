@@ -3,11 +3,16 @@ use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 
 pub mod circuit;
+pub mod circuit_builder;
 pub mod circuit_compiler;
+pub mod circuit_graph;
+pub mod component;
 pub mod constraint_builder;
 pub mod error;
 pub mod example_circuits;
 pub mod gate;
+pub mod index_info;
+pub mod serialization;
 pub mod tests;
 pub mod variable;
 
@@ -0,0 +1,285 @@
+//! A compact binary encoding of circuits, so that they can be stored and shipped without the code that builds them.
+//!
+//! Every encoding starts with the magic bytes `ac2t` and a version byte, followed by a flag byte telling whether
+//! labels follow the circuit. Integers are unsigned LEB128 varints and strings are a varint length followed by UTF-8
+//! bytes:
+//!
+//! ```text
+//! circuit   := inputs outputs number_of_gates gate*
+//! gate      := symbol left right              symbol is 0 for Add and 1 for Mul
+//! labels    := name input_label* output_label*
+//! ```
+//!
+//! Wire 0 is the constant 1, which is the only constant a circuit refers to: other constants are inputs. Decoding
+//! checks that every gate refers to an existing wire. With the `serde` feature, [`Versioned`] wraps a circuit or a
+//! component with the same version for text formats such as JSON.
+
+use crate::{
+    circuit::Circuit,
+    circuit_graph::CircuitGraph,
+    component::Component,
+    error::Error,
+    gate::{Gate, GateType},
+};
+
+pub const MAGIC: &[u8; 4] = b"ac2t";
+
+/// Version of the encoding, bumped on every incompatible change
+pub const FORMAT_VERSION: u8 = 1;
+
+const WITHOUT_LABELS: u8 = 0;
+const WITH_LABELS: u8 = 1;
+
+impl Circuit {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::header(WITHOUT_LABELS);
+        writer.circuit(self);
+        writer.bytes
+    }
+
+    /// Decode a circuit, ignoring the labels of an encoded [`Component`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        let flags = reader.header()?;
+        let circuit = reader.circuit()?;
+        if flags == WITH_LABELS {
+            reader.labels(&circuit)?;
+        }
+        reader.finish()?;
+        Ok(circuit)
+    }
+}
+
+impl Component {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::header(WITH_LABELS);
+        writer.circuit(&self.circuit);
+        writer.string(&self.name);
+        for label in self.inputs.iter().chain(self.outputs.iter()) {
+            writer.string(label);
+        }
+        writer.bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        if reader.header()? != WITH_LABELS {
+            return Err(Error::InvalidEncoding(String::from(
+                "The encoded circuit has no labels",
+            )));
+        }
+        let circuit = reader.circuit()?;
+        let (name, inputs, outputs) = reader.labels(&circuit)?;
+        reader.finish()?;
+
+        Component::new(&name, circuit, &inputs, &outputs)
+    }
+}
+
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn header(flags: u8) -> Self {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+        bytes.push(flags);
+        Self { bytes }
+    }
+
+    fn varint(&mut self, mut value: usize) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    fn string(&mut self, value: &str) {
+        self.varint(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    fn circuit(&mut self, circuit: &Circuit) {
+        self.varint(circuit.number_of_inputs);
+        self.varint(circuit.number_of_outputs);
+        self.varint(circuit.gates.len());
+        for gate in &circuit.gates {
+            self.bytes.push(match gate.symbol {
+                GateType::Add => 0,
+                GateType::Mul => 1,
+            });
+            self.varint(gate.left_index);
+            self.varint(gate.right_index);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < length {
+            return Err(Error::InvalidEncoding(String::from(
+                "Unexpected end of the encoding",
+            )));
+        }
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<usize, Error> {
+        let mut value = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as usize;
+            if bits.checked_shl(shift).map(|shifted| shifted >> shift) != Some(bits) {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::InvalidEncoding(String::from(
+            "Integer does not fit in a usize",
+        )))
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let length = self.varint()?;
+        String::from_utf8(self.take(length)?.to_vec())
+            .map_err(|_| Error::InvalidEncoding(String::from("Label is not valid UTF-8")))
+    }
+
+    /// Check the magic bytes and version, returning the flags
+    fn header(&mut self) -> Result<u8, Error> {
+        if self.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+            return Err(Error::InvalidEncoding(String::from(
+                "Not an encoded circuit",
+            )));
+        }
+        check_version(self.byte()?)?;
+        match self.byte()? {
+            flags @ (WITHOUT_LABELS | WITH_LABELS) => Ok(flags),
+            flags => Err(Error::InvalidEncoding(format!("Unknown flags {}", flags))),
+        }
+    }
+
+    fn circuit(&mut self) -> Result<Circuit, Error> {
+        let number_of_inputs = self.varint()?;
+        let number_of_outputs = self.varint()?;
+        let number_of_gates = self.varint()?;
+
+        // every gate takes at least 3 bytes, which bounds the allocation by the length of the input
+        let mut gates = Vec::with_capacity(number_of_gates.min(self.bytes.len() / 3));
+        for _ in 0..number_of_gates {
+            let symbol = match self.byte()? {
+                0 => GateType::Add,
+                1 => GateType::Mul,
+                symbol => {
+                    return Err(Error::InvalidEncoding(format!(
+                        "Unknown gate symbol {}",
+                        symbol
+                    )))
+                }
+            };
+            gates.push(Gate::new(self.varint()?, self.varint()?, symbol));
+        }
+
+        let circuit = Circuit::new(gates, number_of_inputs, number_of_outputs);
+        CircuitGraph::from_circuit(&circuit)?;
+        Ok(circuit)
+    }
+
+    /// The name of the component, then the labels of its inputs and outputs
+    #[allow(clippy::type_complexity)]
+    fn labels(&mut self, circuit: &Circuit) -> Result<(String, Vec<String>, Vec<String>), Error> {
+        let name = self.string()?;
+        let inputs = (0..circuit.number_of_inputs)
+            .map(|_| self.string())
+            .collect::<Result<_, _>>()?;
+        let outputs = (0..circuit.number_of_outputs)
+            .map(|_| self.string())
+            .collect::<Result<_, _>>()?;
+        Ok((name, inputs, outputs))
+    }
+
+    fn finish(&self) -> Result<(), Error> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidEncoding(format!(
+                "{} trailing bytes",
+                self.bytes.len()
+            )))
+        }
+    }
+}
+
+/// A circuit or component tagged with the version of the encoding, for text formats
+#[cfg(feature = "serde")]
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Versioned<T> {
+    pub version: u8,
+    pub circuit: T,
+}
+
+#[cfg(feature = "serde")]
+impl<T> Versioned<T> {
+    pub fn new(circuit: T) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            circuit,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Versioned<Circuit> {
+    /// Check the version and the wires of the circuit
+    pub fn into_circuit(self) -> Result<Circuit, Error> {
+        check_version(self.version)?;
+        CircuitGraph::from_circuit(&self.circuit)?;
+        Ok(self.circuit)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Versioned<Component> {
+    /// Check the version, the wires of the circuit and the labels
+    pub fn into_component(self) -> Result<Component, Error> {
+        check_version(self.version)?;
+        CircuitGraph::from_circuit(&self.circuit.circuit)?;
+        let component = self.circuit;
+        Component::new(
+            &component.name,
+            component.circuit,
+            &component.inputs,
+            &component.outputs,
+        )
+    }
+}
+
+fn check_version(version: u8) -> Result<(), Error> {
+    if version == FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(Error::InvalidEncoding(format!(
+            "Unsupported version {}, expected {}",
+            version, FORMAT_VERSION
+        )))
+    }
+}
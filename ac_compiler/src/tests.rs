@@ -3,11 +3,17 @@ mod tests {
     use crate::variable::VariableType;
     use crate::{
         circuit::Circuit,
+        circuit_builder::{CircuitBuilder, Wire},
         circuit_compiler::{CircuitCompiler, VanillaCompiler},
+        circuit_graph::CircuitGraph,
+        component::{concat_circuits, Component},
         constraint_builder::ConstraintBuilder,
         diag_test,
         error::Error,
-        gate::GateType,
+        example_circuits::sample_circuit_2,
+        gate::{Gate, GateType},
+        index_info::{index_info, joint_matrix, num_non_zero},
+        serialization::FORMAT_VERSION,
         slt_test,
     };
     use ark_bn254::Fr;
@@ -76,4 +82,545 @@ mod tests {
 
         circuit_test_template(constraints)
     }
+
+    #[test]
+    fn test_multiple_outputs() {
+        let constraints = |cb: &mut ConstraintBuilder<F>| -> Result<(), Error> {
+            let x = cb.new_input_variable("x", F::from(3u64))?;
+            let y = cb.new_input_variable("y", F::from(4u64))?;
+
+            // x * y is both an output and consumed by the next gate
+            let xy = cb.enforce_constraint(&x, &y, GateType::Mul, VariableType::Output)?;
+            let xy_plus_x = cb.enforce_constraint(&xy, &x, GateType::Add, VariableType::Witness)?;
+            let _ = cb.enforce_constraint(&xy_plus_x, &y, GateType::Mul, VariableType::Output)?;
+
+            Ok(())
+        };
+
+        let mut cb = ConstraintBuilder::<F>::new();
+        let circuit = Circuit::synthesize(constraints, &mut cb).unwrap();
+        assert_eq!(circuit.number_of_outputs, 2);
+
+        let (index_info, _, _, _) = VanillaCompiler::<F>::ac2tft(&circuit);
+        assert_eq!(index_info.number_of_outputs, 2);
+        assert_eq!(
+            cb.assignment[cb.assignment.len() - 2..],
+            [F::from(12u64), F::from(60u64)]
+        );
+
+        circuit_test_template(constraints)
+    }
+
+    #[test]
+    fn test_circuit_with_outputs() {
+        // inputs x (1) and y (2)
+        // gate 0 (wire 3): x * y
+        // gate 1 (wire 4): (x * y) + x
+        let gates = vec![
+            Gate::new(1, 2, GateType::Mul),
+            Gate::new(3, 1, GateType::Add),
+        ];
+
+        // wire 4 is moved to the end as-is, wire 3 is consumed by gate 1 and gets copied, input y is copied
+        let circuit = Circuit::with_outputs(&gates, 2, &[4, 3, 2]).unwrap();
+        assert_eq!(
+            circuit.gates,
+            vec![
+                Gate::new(1, 2, GateType::Mul),
+                Gate::new(3, 1, GateType::Add),
+                Gate::new(3, 0, GateType::Mul),
+                Gate::new(2, 0, GateType::Mul),
+            ]
+        );
+        assert_eq!(circuit.number_of_outputs, 3);
+
+        assert!(matches!(
+            Circuit::with_outputs(&gates, 2, &[7]),
+            Err(Error::InvalidWire(_))
+        ));
+    }
+
+    #[test]
+    fn test_circuit_graph() {
+        // inputs x (1) and y (2)
+        // gate 0 (wire 3): x * y
+        // gate 1 (wire 4): y * x
+        // gate 2 (wire 5): (x * y) + (y * x) [output]
+        let gates = vec![
+            Gate::new(1, 2, GateType::Mul),
+            Gate::new(2, 1, GateType::Mul),
+            Gate::new(3, 4, GateType::Add),
+        ];
+        let graph = CircuitGraph::from_gates(&gates, 2, 1).unwrap();
+
+        assert_eq!(graph.depth().unwrap(), 2);
+        assert_eq!(graph.topological_order().unwrap(), vec![0, 1, 2]);
+        assert_eq!(graph.fan_out(), vec![0, 2, 2, 1, 1, 0]);
+
+        let reduced = graph.common_subexpression_elimination().unwrap();
+        assert_eq!(
+            reduced.gates,
+            vec![
+                Gate::new(1, 2, GateType::Mul),
+                Gate::new(3, 3, GateType::Add)
+            ]
+        );
+        assert_eq!(reduced.number_of_outputs, 1);
+    }
+
+    #[test]
+    fn test_canonical_order() {
+        // gate 0 (wire 2) consumes gate 1 (wire 3), so gate 1 has to come first
+        let gates = vec![
+            Gate::new(3, 1, GateType::Add),
+            Gate::new(1, 1, GateType::Mul),
+            Gate::new(2, 2, GateType::Mul),
+        ];
+        let graph = CircuitGraph::from_gates(&gates, 1, 1).unwrap();
+        assert_eq!(graph.topological_order().unwrap(), vec![1, 0, 2]);
+
+        let canonical = graph.to_canonical_circuit().unwrap();
+        assert_eq!(
+            canonical.gates,
+            vec![
+                Gate::new(1, 1, GateType::Mul),
+                Gate::new(2, 1, GateType::Add),
+                Gate::new(3, 3, GateType::Mul),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        // f: inputs x (1) and y (2), outputs x * y (3) and x + y (4)
+        let f = Circuit::new(
+            vec![
+                Gate::new(1, 2, GateType::Mul),
+                Gate::new(1, 2, GateType::Add),
+            ],
+            2,
+            2,
+        );
+        // g: inputs a (1) and b (2), output a * b + 1
+        let g = Circuit::new(
+            vec![
+                Gate::new(1, 2, GateType::Mul),
+                Gate::new(3, 0, GateType::Add),
+            ],
+            2,
+            1,
+        );
+
+        let composed = Circuit::compose(&f, &g).unwrap();
+        assert_eq!(f.composition_offset(), 2);
+        assert_eq!(
+            composed,
+            Circuit::new(
+                vec![
+                    Gate::new(1, 2, GateType::Mul),
+                    Gate::new(1, 2, GateType::Add),
+                    Gate::new(3, 4, GateType::Mul),
+                    Gate::new(5, 0, GateType::Add),
+                ],
+                2,
+                1
+            )
+        );
+
+        // the composition is already in canonical order
+        assert_eq!(
+            CircuitGraph::from_circuit(&composed)
+                .unwrap()
+                .to_canonical_circuit()
+                .unwrap(),
+            composed
+        );
+
+        assert!(matches!(
+            Circuit::compose(&g, &g),
+            Err(Error::IncompatibleCircuits(_))
+        ));
+    }
+
+    // inputs x and y, outputs x * y and x + y
+    fn product_and_sum(name: &str) -> Component {
+        let circuit = Circuit::new(
+            vec![
+                Gate::new(1, 2, GateType::Mul),
+                Gate::new(1, 2, GateType::Add),
+            ],
+            2,
+            2,
+        );
+        Component::new(name, circuit, &["x", "y"], &["product", "sum"]).unwrap()
+    }
+
+    #[test]
+    fn test_concat_circuits() {
+        let f = product_and_sum("f");
+        let g = product_and_sum("g");
+
+        // feeding every output of f to g is composition
+        let stacked =
+            concat_circuits("gf", &f, &g, &[("f.product", "g.x"), ("f.sum", "g.y")]).unwrap();
+        assert_eq!(
+            stacked.circuit,
+            Circuit::compose(&f.circuit, &g.circuit).unwrap()
+        );
+        assert_eq!(stacked.inputs, vec!["f.x", "f.y"]);
+        assert_eq!(stacked.outputs, vec!["g.product", "g.sum"]);
+
+        // unwired labels are kept, under their namespace: g(f(x, y).sum, z)
+        let stacked = concat_circuits("gf", &f, &g, &[("f.sum", "g.x")]).unwrap();
+        assert_eq!(stacked.inputs, vec!["f.x", "f.y", "g.y"]);
+        assert_eq!(stacked.outputs, vec!["f.product", "g.product", "g.sum"]);
+        assert_eq!(
+            stacked.circuit,
+            Circuit::new(
+                vec![
+                    Gate::new(1, 2, GateType::Add),
+                    Gate::new(1, 2, GateType::Mul),
+                    Gate::new(4, 3, GateType::Mul),
+                    Gate::new(4, 3, GateType::Add),
+                ],
+                3,
+                3
+            )
+        );
+
+        // the result can be stacked again, with labels namespaced once more
+        let h = product_and_sum("h");
+        let stacked = concat_circuits("top", &stacked, &h, &[("gf.g.sum", "h.x")]).unwrap();
+        assert_eq!(stacked.inputs, vec!["gf.f.x", "gf.f.y", "gf.g.y", "h.y"]);
+        assert_eq!(
+            stacked.outputs,
+            vec!["gf.f.product", "gf.g.product", "h.product", "h.sum"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_concatenation() {
+        let f = product_and_sum("f");
+        let g = product_and_sum("g");
+
+        assert!(matches!(
+            concat_circuits("gf", &f, &f, &[]),
+            Err(Error::IncompatibleCircuits(_))
+        ));
+        assert!(matches!(
+            concat_circuits("gf", &f, &g, &[("f.x", "g.x")]),
+            Err(Error::VarMissing(_))
+        ));
+        assert!(matches!(
+            concat_circuits("gf", &f, &g, &[("f.sum", "g.z")]),
+            Err(Error::VarMissing(_))
+        ));
+        assert!(matches!(
+            concat_circuits("gf", &f, &g, &[("f.sum", "g.x"), ("f.product", "g.x")]),
+            Err(Error::VarAlreadyExists(_))
+        ));
+
+        // g.x depends on f.y, which depends on g.sum
+        assert!(matches!(
+            concat_circuits("gf", &f, &g, &[("f.sum", "g.x"), ("g.sum", "f.y")]),
+            Err(Error::CyclicCircuit(_))
+        ));
+
+        assert!(matches!(
+            Component::new("f", f.circuit.clone(), &["x", "x"], &["product", "sum"]),
+            Err(Error::VarAlreadyExists(_))
+        ));
+        assert!(matches!(
+            Component::new("f", f.circuit, &["x"], &["product", "sum"]),
+            Err(Error::InvalidWire(_))
+        ));
+    }
+
+    #[test]
+    fn test_circuit_serialization() {
+        let component = product_and_sum("f");
+        let circuit = &component.circuit;
+
+        let bytes = circuit.to_bytes();
+        assert_eq!(
+            bytes,
+            [
+                b'a',
+                b'c',
+                b'2',
+                b't',
+                FORMAT_VERSION,
+                0,
+                2,
+                2,
+                2,
+                1,
+                1,
+                2,
+                0,
+                1,
+                2
+            ]
+        );
+        assert_eq!(Circuit::from_bytes(&bytes).as_ref(), Ok(circuit));
+
+        let bytes = component.to_bytes();
+        assert_eq!(Component::from_bytes(&bytes), Ok(component.clone()));
+        assert_eq!(Circuit::from_bytes(&bytes).as_ref(), Ok(circuit));
+
+        // wires beyond 127 take two bytes
+        let large = Circuit::new(
+            (0..200).map(|i| Gate::new(i, 1, GateType::Add)).collect(),
+            1,
+            1,
+        );
+        assert_eq!(Circuit::from_bytes(&large.to_bytes()), Ok(large));
+    }
+
+    #[test]
+    fn test_invalid_circuit_encoding() {
+        let component = product_and_sum("f");
+        let bytes = component.circuit.to_bytes();
+        let decode = |edit: &dyn Fn(&mut Vec<u8>)| {
+            let mut bytes = bytes.clone();
+            edit(&mut bytes);
+            Circuit::from_bytes(&bytes)
+        };
+
+        for edit in [
+            &(|bytes: &mut Vec<u8>| bytes[0] = b'x') as &dyn Fn(&mut Vec<u8>),
+            &|bytes| bytes[4] = FORMAT_VERSION + 1,
+            &|bytes| bytes[5] = 2,
+            &|bytes| bytes.truncate(bytes.len() - 1),
+            &|bytes| bytes.push(0),
+            &|bytes| bytes[9] = 2,
+            &|bytes| bytes.extend([0xff; 11]),
+        ] {
+            assert!(matches!(decode(edit), Err(Error::InvalidEncoding(_))));
+        }
+
+        // gate 0 refers to wire 5
+        assert!(matches!(
+            decode(&|bytes| bytes[10] = 5),
+            Err(Error::InvalidWire(_))
+        ));
+
+        // a circuit has no labels
+        assert!(matches!(
+            Component::from_bytes(&bytes),
+            Err(Error::InvalidEncoding(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_circuit_json() {
+        use crate::serialization::Versioned;
+
+        let component = product_and_sum("f");
+        let json = serde_json::to_string(&Versioned::new(component.clone())).unwrap();
+        let decoded: Versioned<Component> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.into_component(), Ok(component.clone()));
+
+        let json = serde_json::to_string(&Versioned::new(component.circuit.clone())).unwrap();
+        let mut decoded: Versioned<Circuit> = serde_json::from_str(&json).unwrap();
+        decoded.version += 1;
+        assert!(matches!(
+            decoded.into_circuit(),
+            Err(Error::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_circuit_builder() {
+        assert_eq!(
+            sample_circuit_2::<F>(),
+            Circuit::new(
+                vec![
+                    Gate::new(3, 3, GateType::Mul),
+                    Gate::new(4, 3, GateType::Mul),
+                    Gate::new(1, 3, GateType::Mul),
+                    Gate::new(5, 6, GateType::Add),
+                    Gate::new(7, 2, GateType::Add),
+                ],
+                3,
+                1
+            )
+        );
+
+        // (x + 1) * 3, with the input and the constant as extra outputs
+        let mut cb = CircuitBuilder::<F>::new();
+        let x = cb.input("x");
+        let one = cb.constant(1u64);
+        assert_eq!(one, Wire::One);
+        let three = cb.constant(3u64);
+        assert_eq!(cb.constant(3u64), three);
+        let x_plus_one = cb.add(x, one);
+        let y = cb.mul(x_plus_one, three);
+        cb.output_as("y", y);
+        cb.output(x);
+        cb.output(three);
+
+        let (component, constants) = cb.build("affine").unwrap();
+        assert_eq!(component.inputs, vec!["x", "const_0"]);
+        assert_eq!(component.outputs, vec!["y", "out_1", "out_2"]);
+        assert_eq!(constants, vec![(String::from("const_0"), F::from(3u64))]);
+
+        // inputs used as outputs are copied into gates computing w * 1
+        assert_eq!(
+            component.circuit,
+            Circuit::new(
+                vec![
+                    Gate::new(1, 0, GateType::Add),
+                    Gate::new(3, 2, GateType::Mul),
+                    Gate::new(1, 0, GateType::Mul),
+                    Gate::new(2, 0, GateType::Mul),
+                ],
+                2,
+                3
+            )
+        );
+
+        let (_, a, b, c) = VanillaCompiler::<F>::ac2tft(&component.circuit);
+        slt_test!(a, 3);
+        slt_test!(b, 3);
+        diag_test!(c);
+
+        // handles are only valid in their own builder
+        let mut cb = CircuitBuilder::<F>::new();
+        let y = cb.mul(Wire::Gate(0), Wire::One);
+        cb.output(y);
+        assert!(matches!(cb.build("foreign"), Err(Error::InvalidWire(_))));
+    }
+
+    #[test]
+    fn test_validate_circuit() {
+        let f = Circuit::new(
+            vec![
+                Gate::new(1, 2, GateType::Mul),
+                Gate::new(1, 2, GateType::Add),
+            ],
+            2,
+            2,
+        );
+        assert_eq!(f.validate(), Ok(()));
+        assert!(VanillaCompiler::<F>::try_ac2tft(&f).is_ok());
+
+        let invalid = [
+            // wire 5 does not exist
+            (Gate::new(1, 5, GateType::Add), "InvalidWire"),
+            // gate 1 consumes itself
+            (Gate::new(4, 1, GateType::Add), "CyclicCircuit"),
+            // gate 1 consumes the output of gate 0
+            (Gate::new(3, 1, GateType::Add), "InvalidWire"),
+        ];
+        for (gate, error) in invalid {
+            let circuit = Circuit::new(vec![Gate::new(1, 2, GateType::Mul), gate], 2, 2);
+            let result = VanillaCompiler::<F>::try_ac2tft(&circuit).map(|_| ());
+            assert_eq!(result, circuit.validate());
+            assert!(format!("{:?}", result).contains(error));
+        }
+
+        // the missing operand is reported
+        let mut cb = ConstraintBuilder::<F>::new();
+        let x = cb.new_input_variable("x", F::zero()).unwrap();
+        let mut y = x.clone();
+        y.label = String::from("y");
+        assert_eq!(
+            cb.enforce_constraint(&x, &y, GateType::Add, VariableType::Output)
+                .err(),
+            Some(Error::VarMissing(String::from(
+                "Var with label y doesn't exists"
+            )))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "CyclicCircuit")]
+    fn test_compile_invalid_circuit() {
+        let circuit = Circuit::new(vec![Gate::new(2, 1, GateType::Add)], 1, 1);
+        VanillaCompiler::<F>::ac2tft(&circuit);
+    }
+
+    #[test]
+    fn test_invalid_circuit_graph() {
+        let cyclic = vec![Gate::new(2, 1, GateType::Add)];
+        let graph = CircuitGraph::from_gates(&cyclic, 1, 1).unwrap();
+        assert!(matches!(
+            graph.topological_order(),
+            Err(Error::CyclicCircuit(_))
+        ));
+
+        let out_of_range = vec![Gate::new(5, 1, GateType::Add)];
+        assert!(matches!(
+            CircuitGraph::from_gates(&out_of_range, 1, 1),
+            Err(Error::InvalidWire(_))
+        ));
+    }
+
+    #[test]
+    fn test_index_info() {
+        let (r1csf_index, a, b, c) = VanillaCompiler::<F>::ac2tft(&sample_circuit_2::<F>());
+
+        let info = index_info(&a, &b, &c);
+        assert_eq!(info.dims, r1csf_index.number_of_constraints);
+        assert_eq!(info.t, r1csf_index.number_of_input_rows);
+        assert_eq!(info.max_non_zero, r1csf_index.number_of_non_zero_entries);
+        assert!(info.joint_non_zero >= info.max_non_zero);
+
+        let a = vec![vec![], vec![(F::from(2u64), 1)], vec![]];
+        let b = vec![vec![], vec![(F::from(3u64), 0), (F::from(4u64), 1)], vec![]];
+        let c = vec![vec![], vec![], vec![(F::from(5u64), 0)]];
+        assert_eq!(
+            joint_matrix(&a, &b, &c),
+            vec![
+                vec![],
+                vec![(F::from(1u64), 0), (F::from(1u64), 1)],
+                vec![(F::from(1u64), 0)]
+            ]
+        );
+        let info = index_info(&a, &b, &c);
+        assert_eq!((info.dims, info.t), (3, 1));
+        assert_eq!((info.joint_non_zero, info.max_non_zero), (3, 2));
+        assert_eq!(num_non_zero(&b), 2);
+    }
+
+    #[test]
+    fn test_pad_circuit() {
+        let mut cb = ConstraintBuilder::<F>::new();
+        let circuit = Circuit::synthesize(
+            |cb| {
+                let x = cb.new_input_variable("x", F::from(3u64))?;
+                let y = cb.new_input_variable("y", F::from(4u64))?;
+                let _ = cb.enforce_constraint(&x, &y, GateType::Mul, VariableType::Output)?;
+                Ok(())
+            },
+            &mut cb,
+        )
+        .unwrap();
+        assert_eq!(circuit.number_of_constraints(), 4);
+
+        let padded = circuit.pad(8).unwrap();
+        assert_eq!(padded.number_of_constraints(), 8);
+        assert_eq!(padded.gates.last(), circuit.gates.last());
+        let assignment = circuit.pad_assignment(&cb.assignment, 8).unwrap();
+        assert_eq!(assignment[7], F::from(12u64));
+
+        let (index, a, b, c) = VanillaCompiler::<F>::ac2tft_padded(&circuit, 8).unwrap();
+        assert_eq!(index.number_of_constraints, 8);
+        assert_eq!(index.number_of_input_rows, 3);
+        slt_test!(a, index.number_of_input_rows);
+        slt_test!(b, index.number_of_input_rows);
+        diag_test!(c);
+        // every row after the inputs is a constraint
+        assert!(c.iter().skip(3).all(|row| row.len() == 1));
+
+        let row_value =
+            |row: &Vec<(F, usize)>| row.iter().map(|&(v, i)| v * assignment[i]).sum::<F>();
+        for i in 3..8 {
+            assert_eq!(row_value(&a[i]) * row_value(&b[i]), row_value(&c[i]));
+        }
+
+        assert!(matches!(circuit.pad(2), Err(Error::CircuitTooLarge(_))));
+    }
 }
@@ -2,14 +2,34 @@ use ark_ff::{FromBytes, ToBytes};
 use ark_std::convert::From;
 use ark_std::marker::PhantomData;
 use ark_std::rand::{RngCore, SeedableRng};
+use ark_std::UniformRand;
 use digest::Digest;
 
 use ark_std::vec::Vec;
 
+#[cfg(feature = "sponge")]
+pub mod sponge;
+mod tests;
+pub mod transcript;
+
+#[cfg(feature = "sponge")]
+pub use ark_sponge;
+#[cfg(feature = "sponge")]
+pub use sponge::{SpongeConfig, SpongeFiatShamirRng};
+pub use transcript::{
+    FiatShamirTranscript, ProtocolRng, RewindableTranscript, Transcript, TranscriptCheckpoint,
+    TranscriptError,
+};
+
 /// An RNG suitable for Fiat-Shamir transforms
-pub trait FiatShamirRng: RngCore {
+pub trait FiatShamirRng: Absorb {
     /// Create a new `Self` with an initial input
     fn initialize<'a, T: 'a + ToBytes>(initial_input: &'a T) -> Self;
+}
+
+/// What a protocol does with its Fiat-Shamir RNG once it runs: absorb its messages and draw challenges. Unlike
+/// [`FiatShamirRng`] it says nothing of how the RNG was seeded, so that it can be backed by a [`Transcript`].
+pub trait Absorb: RngCore {
     /// Absorb new inputs into state
     fn absorb<'a, T: 'a + ToBytes>(&mut self, new_input: &'a T);
 }
@@ -45,6 +65,16 @@ impl<D: Digest, R: RngCore + SeedableRng> RngCore for SimpleHashFiatShamirRng<D,
     }
 }
 
+impl<D: Digest, R: RngCore + SeedableRng + Clone> Clone for SimpleHashFiatShamirRng<D, R> {
+    fn clone(&self) -> Self {
+        Self {
+            r: self.r.clone(),
+            seed: self.seed,
+            digest: PhantomData,
+        }
+    }
+}
+
 impl<D: Digest, R: RngCore + SeedableRng> FiatShamirRng for SimpleHashFiatShamirRng<D, R>
 where
     R::Seed: From<[u8; 32]>,
@@ -65,7 +95,12 @@ where
             digest: PhantomData,
         }
     }
+}
 
+impl<D: Digest, R: RngCore + SeedableRng> Absorb for SimpleHashFiatShamirRng<D, R>
+where
+    R::Seed: From<[u8; 32]>,
+{
     /// Refresh `self.seed` with new material. Achieved by setting
     /// `self.seed = H(new_input || self.seed)`.
     #[inline]
@@ -79,3 +114,111 @@ where
         self.r = R::from_seed(<R::Seed>::from(self.seed));
     }
 }
+
+/// A domain separation context identifying one protocol instance. Every protocol absorbs its context before any
+/// other input, and sub-protocols run under a child context derived from their parent's, so that no two protocol
+/// instances share a transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Context {
+    /// The labels from the root down, each prefixed with its length so that no label can be mistaken for several
+    path: Vec<u8>,
+}
+
+impl Context {
+    pub fn new(label: &[u8]) -> Self {
+        Self {
+            path: Self::segment(label),
+        }
+    }
+
+    /// Derive the context of a sub-protocol run under `self`
+    pub fn child(&self, label: &[u8]) -> Self {
+        let mut path = self.path.clone();
+        path.extend(Self::segment(label));
+        Self { path }
+    }
+
+    /// Derive the context of a sub-protocol that runs in its own transcript, bound to the `challenges` of `self` it
+    /// depends on, e.g. those its virtual oracle uses. The path holds a `D` digest of the challenges under `label`.
+    pub fn bound_child<D: Digest, T: ToBytes>(&self, label: &[u8], challenges: &[T]) -> Self {
+        let mut bytes = Vec::new();
+        for challenge in challenges {
+            challenge
+                .write(&mut bytes)
+                .expect("failed to convert to bytes");
+        }
+        self.child(label).child(&D::digest(&bytes))
+    }
+
+    fn segment(label: &[u8]) -> Vec<u8> {
+        let mut segment = (label.len() as u64).to_le_bytes().to_vec();
+        segment.extend_from_slice(label);
+        segment
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.path
+    }
+
+    /// The context whose path is `bytes`, as returned by [`Self::as_bytes`], or `None` if they are not a sequence of
+    /// length-prefixed labels
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut rest = bytes;
+        while !rest.is_empty() {
+            let (length, tail) = (rest.get(..8)?, &rest[8..]);
+            let length = usize::try_from(u64::from_le_bytes(length.try_into().ok()?)).ok()?;
+            rest = tail.get(length..)?;
+        }
+        Some(Self {
+            path: bytes.to_vec(),
+        })
+    }
+}
+
+impl ToBytes for Context {
+    fn write<W: ark_std::io::Write>(&self, mut writer: W) -> ark_std::io::Result<()> {
+        // length-prefixed so that a context cannot be confused with the data absorbed after it
+        (self.path.len() as u64).write(&mut writer)?;
+        self.path.write(&mut writer)
+    }
+}
+
+/// A length-prefixed label, so that consecutive labels cannot be confused with each other
+struct Label<'a>(&'a [u8]);
+
+impl ToBytes for Label<'_> {
+    fn write<W: ark_std::io::Write>(&self, mut writer: W) -> ark_std::io::Result<()> {
+        (self.0.len() as u64).write(&mut writer)?;
+        self.0.write(&mut writer)
+    }
+}
+
+/// Derives the challenges of one protocol round from the transcript. The label of the round is absorbed first, then
+/// every challenge absorbs its own label before it is sampled, so challenges of different rounds or with different
+/// names are always domain separated, and the derivation spells out the challenge structure of the protocol:
+///
+/// ```ignore
+/// let mut round = ChallengeSet::new(&mut fs_rng, b"round_1");
+/// let beta_1: F = round.challenge(b"beta_1");
+/// let c: F = round.challenge(b"c");
+/// ```
+pub struct ChallengeSet<'a, FS: Absorb> {
+    fs_rng: &'a mut FS,
+}
+
+impl<'a, FS: Absorb> ChallengeSet<'a, FS> {
+    pub fn new(fs_rng: &'a mut FS, round: &[u8]) -> Self {
+        fs_rng.absorb(&Label(round));
+        Self { fs_rng }
+    }
+
+    pub fn challenge<T: UniformRand>(&mut self, label: &[u8]) -> T {
+        self.challenge_with(label, |fs_rng| T::rand(fs_rng))
+    }
+
+    /// Derive a challenge with a custom sampler, e.g. rejection sampling of a point outside of a domain
+    pub fn challenge_with<T>(&mut self, label: &[u8], sample: impl FnOnce(&mut FS) -> T) -> T {
+        self.fs_rng.absorb(&Label(label));
+        sample(self.fs_rng)
+    }
+}
@@ -0,0 +1,96 @@
+//! Fiat-Shamir RNGs over an arkworks [`CryptographicSponge`], alongside the [`Digest`](digest::Digest)-based
+//! [`SimpleHashFiatShamirRng`](crate::SimpleHashFiatShamirRng).
+//!
+//! Upstream Marlin moved from hashing with a `Digest` to a `FiatShamirRng` over a sponge, so that challenges can be
+//! derived with an algebraic hash such as Poseidon and recomputed cheaply in a circuit. Every protocol here is
+//! generic over its [`FiatShamirRng`], so running one over a sponge only takes picking [`SpongeFiatShamirRng`]:
+//!
+//! ```ignore
+//! struct PoseidonConfig;
+//!
+//! impl SpongeConfig for PoseidonConfig {
+//!     type Sponge = PoseidonSponge<Fr>;
+//!
+//!     fn parameters() -> PoseidonParameters<Fr> {
+//!         PoseidonParameters::new(8, 31, 17, mds, ark)
+//!     }
+//! }
+//!
+//! let proof = ZeroOverK::<F, PC, SpongeFiatShamirRng<PoseidonConfig>>::prove(...)?;
+//! ```
+//!
+//! Challenges are still sampled from squeezed bytes, as through any RNG, so both paths derive them the same way.
+
+use crate::{Absorb, FiatShamirRng};
+use ark_ff::{to_bytes, ToBytes};
+use ark_sponge::CryptographicSponge;
+use ark_std::rand::RngCore;
+
+/// A sponge with parameters fixed by the type, so that [`FiatShamirRng::initialize`] can create it from its first
+/// input alone
+pub trait SpongeConfig {
+    type Sponge: CryptographicSponge;
+
+    fn parameters() -> <Self::Sponge as CryptographicSponge>::Parameters;
+}
+
+/// A [`FiatShamirRng`] that absorbs the serialization of its inputs into a sponge and squeezes its output from it
+pub struct SpongeFiatShamirRng<C: SpongeConfig> {
+    sponge: C::Sponge,
+}
+
+impl<C: SpongeConfig> SpongeFiatShamirRng<C> {
+    pub fn into_inner(self) -> C::Sponge {
+        self.sponge
+    }
+}
+
+impl<C: SpongeConfig> Clone for SpongeFiatShamirRng<C> {
+    fn clone(&self) -> Self {
+        Self {
+            sponge: self.sponge.clone(),
+        }
+    }
+}
+
+impl<C: SpongeConfig> RngCore for SpongeFiatShamirRng<C> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.copy_from_slice(&self.sponge.squeeze_bytes(dest.len()));
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ark_std::rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<C: SpongeConfig> FiatShamirRng for SpongeFiatShamirRng<C> {
+    /// Create a sponge with the parameters of `C` and absorb `initial_input`
+    fn initialize<'a, T: 'a + ToBytes>(initial_input: &'a T) -> Self {
+        let mut rng = Self {
+            sponge: C::Sponge::new(&C::parameters()),
+        };
+        rng.absorb(initial_input);
+        rng
+    }
+}
+
+impl<C: SpongeConfig> Absorb for SpongeFiatShamirRng<C> {
+    /// Absorb the serialization of `new_input`
+    fn absorb<'a, T: 'a + ToBytes>(&mut self, new_input: &'a T) {
+        let bytes = to_bytes![new_input].expect("failed to convert to bytes");
+        self.sponge.absorb(&bytes);
+    }
+}
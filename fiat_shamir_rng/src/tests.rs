@@ -0,0 +1,29 @@
+#[cfg(test)]
+mod test {
+    use crate::Context;
+
+    #[test]
+    fn test_child_contexts_are_distinct() {
+        let root = Context::new(b"test");
+
+        // a label containing the former separator is a single segment
+        assert_ne!(root.child(b"a/b"), root.child(b"a").child(b"b"));
+        assert_ne!(root.child(b"ab"), root.child(b"a").child(b"b"));
+        assert_ne!(Context::new(b"test/a"), root.child(b"a"));
+        assert_eq!(root.child(b"a").child(b"b"), root.child(b"a").child(b"b"));
+    }
+
+    #[test]
+    fn test_context_from_bytes() {
+        let context = Context::new(b"test").child(b"a");
+        assert_eq!(
+            Context::from_bytes(context.as_bytes()),
+            Some(context.clone())
+        );
+
+        // a truncated segment or length prefix is rejected
+        let bytes = context.as_bytes();
+        assert_eq!(Context::from_bytes(&bytes[..bytes.len() - 1]), None);
+        assert_eq!(Context::from_bytes(&bytes[..4]), None);
+    }
+}
@@ -0,0 +1,292 @@
+//! Fiat-Shamir transcripts managed by the caller, for running our protocols inside a larger one.
+//!
+//! A protocol seeds its own [`FiatShamirRng`] from its statement. Given a [`Transcript`] instead, it appends its
+//! statement and messages to it and draws its challenges from it, without ever constructing or seeding an RNG, so
+//! that its challenges also depend on whatever the larger protocol absorbed before:
+//!
+//! ```ignore
+//! let mut transcript = merlin::Transcript::new(b"my protocol");
+//! transcript.append_message(b"statement", &statement);
+//! let proof = ZeroOverK::<F, PC, FS>::prove_with_transcript(..., &context, &mut transcript, rng)?;
+//! ```
+//!
+//! Sub-protocols run on the transcript of their parent, one after the other, so the verifier must check them in the
+//! order the prover created them.
+//!
+//! Only ZeroOverK, NonZeroOverK, GeoSeqTest and ProofOfKnowledge run on a caller's transcript. The composite protocols
+//! of `proof_of_function_relation` (DLComparison, t-SLT, t-Diag, t-FT and the matrix tests) absorb into the
+//! `FiatShamirRng` their caller passes, but still run each of their sub-protocols on a transcript that sub-protocol
+//! seeds from its own statement. Their proofs can be embedded in a larger protocol by appending them to its
+//! transcript, but their challenges do not depend on what the larger protocol absorbed before.
+//!
+//! A prover whose sub-protocol fails, e.g. on unlucky masking randomness, can roll the transcript back and retry the
+//! sub-protocol without restarting the whole composed proof. [`RewindableTranscript`] saves checkpoints of a transcript
+//! it wraps, and restoring one discards every message appended and every challenge drawn since, together:
+//!
+//! ```ignore
+//! let mut transcript = RewindableTranscript::new(merlin::Transcript::new(b"my protocol"));
+//! let checkpoint = transcript.checkpoint()?;
+//! let proof = loop {
+//!     match ZeroOverK::<F, PC, FS>::prove_with_transcript(..., &mut transcript, rng) {
+//!         Ok(proof) => break proof,
+//!         Err(_) => transcript.restore(&checkpoint)?,
+//!     }
+//! };
+//! // the proof is final, what was appended so far can no longer be rolled back
+//! transcript.seal();
+//! ```
+
+use crate::{Absorb, FiatShamirRng, Label};
+use ark_ff::{to_bytes, ToBytes};
+use ark_std::rand::RngCore;
+use ark_std::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Label of the statement of a protocol appended to a transcript
+pub const STATEMENT_LABEL: &[u8] = b"statement";
+
+/// Label of the messages of a protocol appended to a transcript
+pub const MESSAGE_LABEL: &[u8] = b"message";
+
+/// Label of the challenges of a protocol drawn from a transcript
+pub const CHALLENGE_LABEL: &[u8] = b"challenge";
+
+/// A transcript of a public-coin protocol, with the interface of Merlin: labeled messages go in, labeled challenges
+/// come out. It is object safe, so that protocols can take it as `&mut dyn Transcript`.
+pub trait Transcript {
+    /// Append a message to the transcript
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+    /// Fill `dest` with a challenge depending on everything appended so far
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]);
+
+    /// Save the current state of the transcript, to roll back to with [`Self::restore`]. Transcripts are not
+    /// rewindable unless wrapped in a [`RewindableTranscript`].
+    fn checkpoint(&mut self) -> Result<TranscriptCheckpoint, TranscriptError> {
+        Err(TranscriptError::NotRewindable)
+    }
+
+    /// Roll back to the state saved by `checkpoint`, discarding the messages and challenges since and the
+    /// checkpoints saved after it. The checkpoint itself can be restored again.
+    fn restore(&mut self, _checkpoint: &TranscriptCheckpoint) -> Result<(), TranscriptError> {
+        Err(TranscriptError::NotRewindable)
+    }
+}
+
+/// Why a transcript could not be rolled back
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptError {
+    /// The transcript does not save checkpoints
+    NotRewindable,
+    /// The checkpoint was saved by another transcript
+    ForeignCheckpoint,
+    /// The checkpoint was discarded, by sealing the transcript or restoring an earlier checkpoint. Restoring it
+    /// would splice messages of an abandoned attempt with challenges that do not depend on them.
+    DiscardedCheckpoint,
+}
+
+/// A state of a [`RewindableTranscript`] to roll back to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TranscriptCheckpoint {
+    transcript: u64,
+    serial: u64,
+}
+
+#[cfg(feature = "merlin")]
+impl Transcript for merlin::Transcript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        merlin::Transcript::append_message(self, label, message)
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        merlin::Transcript::challenge_bytes(self, label, dest)
+    }
+}
+
+/// A [`FiatShamirRng`] as a transcript: labels and messages are absorbed length-prefixed, and challenges drawn from
+/// the RNG after absorbing their label
+pub struct FiatShamirTranscript<FS: FiatShamirRng> {
+    fs_rng: FS,
+}
+
+impl<FS: FiatShamirRng> FiatShamirTranscript<FS> {
+    /// A transcript seeded with `label` by the caller
+    pub fn new(label: &[u8]) -> Self {
+        Self {
+            fs_rng: FS::initialize(&Label(label)),
+        }
+    }
+
+    pub fn into_inner(self) -> FS {
+        self.fs_rng
+    }
+}
+
+impl<FS: FiatShamirRng> Transcript for FiatShamirTranscript<FS> {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.fs_rng.absorb(&Label(label));
+        self.fs_rng.absorb(&Label(message));
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.fs_rng.absorb(&Label(label));
+        self.fs_rng.fill_bytes(dest);
+    }
+}
+
+impl<FS: FiatShamirRng + Clone> Clone for FiatShamirTranscript<FS> {
+    fn clone(&self) -> Self {
+        Self {
+            fs_rng: self.fs_rng.clone(),
+        }
+    }
+}
+
+/// Source of the identifiers that tie checkpoints to the transcript that saved them
+static NEXT_TRANSCRIPT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A transcript that saves checkpoints to roll back to, see the [module documentation](self).
+///
+/// Every checkpoint saves the whole state, so that restoring one can never change a message that a challenge drawn
+/// before the checkpoint was derived from: everything before a checkpoint is kept as is, everything after it is
+/// discarded along with the challenges derived from it. Once its messages are final, e.g. because a challenge
+/// derived from them went into the proof of an enclosing protocol, a prover seals the transcript, after which
+/// none of the earlier checkpoints can be restored.
+pub struct RewindableTranscript<T: Transcript + Clone> {
+    id: u64,
+    transcript: T,
+    checkpoints: Vec<(u64, T)>,
+    next_serial: u64,
+}
+
+impl<T: Transcript + Clone> RewindableTranscript<T> {
+    pub fn new(transcript: T) -> Self {
+        Self {
+            id: NEXT_TRANSCRIPT_ID.fetch_add(1, Ordering::Relaxed),
+            transcript,
+            checkpoints: Vec::new(),
+            next_serial: 0,
+        }
+    }
+
+    /// Discard every checkpoint, so that what was appended so far is final
+    pub fn seal(&mut self) {
+        self.checkpoints.clear();
+    }
+
+    pub fn into_inner(self) -> T {
+        self.transcript
+    }
+}
+
+impl<T: Transcript + Clone> Transcript for RewindableTranscript<T> {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.transcript.append_message(label, message)
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.transcript.challenge_bytes(label, dest)
+    }
+
+    fn checkpoint(&mut self) -> Result<TranscriptCheckpoint, TranscriptError> {
+        let serial = self.next_serial;
+        self.next_serial += 1;
+        self.checkpoints.push((serial, self.transcript.clone()));
+
+        Ok(TranscriptCheckpoint {
+            transcript: self.id,
+            serial,
+        })
+    }
+
+    fn restore(&mut self, checkpoint: &TranscriptCheckpoint) -> Result<(), TranscriptError> {
+        if checkpoint.transcript != self.id {
+            return Err(TranscriptError::ForeignCheckpoint);
+        }
+        let position = self
+            .checkpoints
+            .iter()
+            .position(|(serial, _)| *serial == checkpoint.serial)
+            .ok_or(TranscriptError::DiscardedCheckpoint)?;
+
+        self.checkpoints.truncate(position + 1);
+        self.transcript = self.checkpoints[position].1.clone();
+        Ok(())
+    }
+}
+
+/// The Fiat-Shamir RNG a protocol runs with: one it seeded itself from its statement, or the transcript of the
+/// caller, to which the statement was appended
+pub enum ProtocolRng<'a, FS: FiatShamirRng> {
+    Seeded(FS),
+    External(&'a mut dyn Transcript),
+}
+
+impl<'a, FS: FiatShamirRng> ProtocolRng<'a, FS> {
+    /// Seed a fresh `FS` with `statement`, or append it to `transcript` if the caller manages one
+    pub fn new(transcript: Option<&'a mut dyn Transcript>, statement: &Vec<u8>) -> Self {
+        match transcript {
+            None => Self::Seeded(FS::initialize(statement)),
+            Some(transcript) => {
+                transcript.append_message(STATEMENT_LABEL, statement);
+                Self::External(transcript)
+            }
+        }
+    }
+
+    /// The transcript sub-protocols run on: none when seeded, as they seed their own, and the transcript of the
+    /// caller otherwise
+    pub fn sub_transcript(&mut self) -> Option<&mut dyn Transcript> {
+        match self {
+            Self::Seeded(_) => None,
+            Self::External(transcript) => Some(&mut **transcript),
+        }
+    }
+}
+
+impl<FS: FiatShamirRng> RngCore for ProtocolRng<'_, FS> {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Self::Seeded(fs_rng) => fs_rng.next_u32(),
+            Self::External(_) => {
+                let mut bytes = [0u8; 4];
+                self.fill_bytes(&mut bytes);
+                u32::from_le_bytes(bytes)
+            }
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Self::Seeded(fs_rng) => fs_rng.next_u64(),
+            Self::External(_) => {
+                let mut bytes = [0u8; 8];
+                self.fill_bytes(&mut bytes);
+                u64::from_le_bytes(bytes)
+            }
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Self::Seeded(fs_rng) => fs_rng.fill_bytes(dest),
+            Self::External(transcript) => transcript.challenge_bytes(CHALLENGE_LABEL, dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ark_std::rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<FS: FiatShamirRng> Absorb for ProtocolRng<'_, FS> {
+    fn absorb<'a, T: 'a + ToBytes>(&mut self, new_input: &'a T) {
+        match self {
+            Self::Seeded(fs_rng) => fs_rng.absorb(new_input),
+            Self::External(transcript) => transcript.append_message(
+                MESSAGE_LABEL,
+                &to_bytes![new_input].expect("failed to convert to bytes"),
+            ),
+        }
+    }
+}
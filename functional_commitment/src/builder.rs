@@ -0,0 +1,960 @@
+//! A fluent entry point to the proofs of function relation.
+//!
+//! ```ignore
+//! let (prover, verifier) = FunctionalCommitment::<C>::setup(max_degree, rng)?
+//!     .index(Matrices::from_circuit(&circuit))?
+//!     .commit(rng)?;
+//!
+//! let proof = prover.prove_tft(rng)?;
+//! verifier.verify_tft(proof)?;
+//! ```
+//!
+//! The builder picks the domains H and K, arithmetizes and commits to the index matrices, and derives the
+//! Fiat-Shamir RNG of every proof from the context, so that prover and verifier only exchange proofs. The protocols
+//! it wraps remain available for finer control.
+
+use crate::{
+    marlin,
+    options::ProtocolOptions,
+    proof_cache::{ProofCache, ProofCacheStats},
+    property::{FunctionProperty, PropertyProof, PropertyRegistry},
+};
+use ac_compiler::{
+    circuit::Circuit,
+    circuit_compiler::{CircuitCompiler, VanillaCompiler},
+    index_info::{index_info, IndexInfo},
+    Matrix, R1CSfIndex,
+};
+use ark_ff::{to_bytes, PrimeField, SquareRootField};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{Rng, RngCore};
+use derivative::Derivative;
+use fiat_shamir_rng::{Context, FiatShamirRng};
+use homomorphic_poly_commit::commit_cache::CommitCache;
+use proof_of_function_relation::{
+    checked_key::{CheckedCommitterKey, RequiredDegreeBounds},
+    discrete_log_comparison::{proof::Proof as DLComparisonProof, DLComparison},
+    domains::{choose_domains, DomainH, DomainK},
+    error::{to_pc_error, Error},
+    indexer::{arithmetize_matrix, MatrixArith},
+    t_diag::{proof::Proof as TDiagProof, TDiag},
+    t_functional_triple::TFT,
+    t_strictly_lower_triangular_test::{proof::Proof as TSLTProof, TStrictlyLowerTriangular},
+};
+use std::{
+    io::BufReader,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    cancellation::{checkpoint, CancellationToken},
+    zero_over_k::ZeroOverK,
+};
+
+/// The matrices A, B and C of a t-functional triple, with the number t of input rows
+#[derive(Clone, Debug)]
+pub struct Matrices<F: PrimeField> {
+    pub t: usize,
+    pub a: Matrix<F>,
+    pub b: Matrix<F>,
+    pub c: Matrix<F>,
+}
+
+impl<F: PrimeField> Matrices<F> {
+    /// Compile a circuit into its t-functional triple
+    pub fn from_circuit(circuit: &Circuit) -> Self {
+        VanillaCompiler::<F>::ac2tft(circuit).into()
+    }
+
+    /// Validate and compile a circuit into its t-functional triple
+    pub fn try_from_circuit(circuit: &Circuit) -> Result<Self, Error> {
+        VanillaCompiler::<F>::try_ac2tft(circuit)
+            .map(Self::from)
+            .map_err(|e| Error::InvalidCircuit(format!("{:?}", e)))
+    }
+
+    /// Validate and compile a circuit padded to the size of its domain H, see [`Circuit::pad`]. The t-FT and t-Diag
+    /// proofs require C to be diagonal on every row of H after the first t, so a circuit whose number of constraints
+    /// is not the size of a domain H has to be indexed this way.
+    pub fn padded_from_circuit(circuit: &Circuit) -> Result<Self, Error>
+    where
+        F: SquareRootField,
+    {
+        // H only depends on the number of constraints, which padding brings up to |H|
+        let (domain_h, _) = choose_domains::<F>(circuit.number_of_constraints(), 0)?;
+        VanillaCompiler::<F>::ac2tft_padded(circuit, domain_h.size())
+            .map(Self::from)
+            .map_err(|e| Error::InvalidCircuit(format!("{:?}", e)))
+    }
+
+    /// Dimensions and sparsity of A, B and C
+    pub fn info(&self) -> IndexInfo {
+        index_info(&self.a, &self.b, &self.c)
+    }
+
+    /// The number of rows of the largest of A, B and C
+    pub fn number_of_constraints(&self) -> usize {
+        self.info().dims
+    }
+
+    /// The largest number of non-zero entries among A, B and C
+    pub fn number_of_non_zero(&self) -> usize {
+        self.info().max_non_zero
+    }
+
+    /// The smallest domains H and K the matrices can be indexed over
+    pub fn domains(&self) -> Result<(DomainH<F>, DomainK<F>), Error>
+    where
+        F: SquareRootField,
+    {
+        let info = self.info();
+        choose_domains::<F>(info.dims, info.max_non_zero)
+    }
+}
+
+impl<F: PrimeField> From<(R1CSfIndex, Matrix<F>, Matrix<F>, Matrix<F>)> for Matrices<F> {
+    fn from((index_info, a, b, c): (R1CSfIndex, Matrix<F>, Matrix<F>, Matrix<F>)) -> Self {
+        Self {
+            t: index_info.number_of_input_rows,
+            a,
+            b,
+            c,
+        }
+    }
+}
+
+/// One of the two matrices of a t-functional triple that must be t-strictly lower triangular
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SltMatrix {
+    A,
+    B,
+}
+
+/// A polynomial committed to by the prover, along with its commitment randomness
+pub struct CommittedOracle<C: ProtocolConfig> {
+    pub polynomial: LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    pub commitment: LabeledCommitment<pc::Commitment<C>>,
+    pub randomness: pc::Randomness<C>,
+}
+
+/// Entry point of the builder
+pub struct FunctionalCommitment<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> FunctionalCommitment<C>
+where
+    C::F: SquareRootField,
+{
+    pub const DEFAULT_CONTEXT: &'static [u8] = b"functional_commitment";
+
+    /// Sample universal parameters supporting polynomials of degree up to `max_degree`
+    pub fn setup<R: Rng>(max_degree: usize, rng: &mut R) -> Result<UniversalSetup<C>, Error> {
+        let pp = C::PC::setup(max_degree, None, rng).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        Ok(UniversalSetup {
+            max_degree,
+            pp,
+            context: Context::new(Self::DEFAULT_CONTEXT),
+            options: ProtocolOptions::default(),
+            properties: PropertyRegistry::new(),
+        })
+    }
+}
+
+/// Universal parameters, before any index is fixed
+pub struct UniversalSetup<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    max_degree: usize,
+    pp: pc::UniversalParams<C>,
+    pub(crate) context: Context,
+    options: ProtocolOptions,
+    pub(crate) properties: PropertyRegistry<C>,
+}
+
+impl<C: ProtocolConfig> UniversalSetup<C>
+where
+    C::F: SquareRootField,
+{
+    /// Run every proof under `context` instead of the default one
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Run every proof with `options` instead of the default ones
+    pub fn with_options(mut self, options: ProtocolOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Make the properties of `registry` available to the prover and verifier of the index
+    pub fn with_properties(mut self, registry: PropertyRegistry<C>) -> Self {
+        self.properties = registry;
+        self
+    }
+
+    /// Choose the domains for `matrices`, trim the keys to them and arithmetize the matrices
+    pub fn index(mut self, matrices: Matrices<C::F>) -> Result<IndexedSetup<C>, Error> {
+        let (domain_h, domain_k) = matrices.domains()?;
+        let properties = Arc::new(std::mem::take(&mut self.properties));
+        self.index_over(
+            &matrices,
+            domain_h,
+            domain_k,
+            self.context.clone(),
+            &properties,
+        )
+    }
+
+    /// Trim the keys to the given domains and arithmetize the matrices over them
+    pub(crate) fn index_over(
+        &self,
+        matrices: &Matrices<C::F>,
+        domain_h: DomainH<C::F>,
+        domain_k: DomainK<C::F>,
+        context: Context,
+        properties: &Arc<PropertyRegistry<C>>,
+    ) -> Result<IndexedSetup<C>, Error> {
+        // masking polynomials in zero over k have degree |K| + 1
+        let enforced_degree_bound = domain_k.size() + 1;
+        let index = Index {
+            t: matrices.t,
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            context,
+            options: self.options,
+        };
+
+        let mut degree_bounds = vec![2, enforced_degree_bound];
+        degree_bounds.extend(marlin::degree_bounds(&index));
+        degree_bounds.extend(properties.degree_bounds(&index));
+        degree_bounds.sort_unstable();
+        degree_bounds.dedup();
+        let (ck, vk) = C::PC::trim(&self.pp, self.max_degree, 1, Some(&degree_bounds))
+            .map_err(to_pc_error::<C::F, C::PC>)?;
+        #[cfg(feature = "parallel")]
+        self.options.configure_thread_pool()?;
+
+        let hiding_bound = index.hiding_bound();
+        let arithmetize = |matrix: &Matrix<C::F>, label, padding| {
+            arithmetize_matrix(matrix, domain_h, domain_k, padding)
+                .map(|arith| arith.with_bounds(label, Some(enforced_degree_bound), hiding_bound))
+        };
+
+        Ok(IndexedSetup {
+            index,
+            ck,
+            vk,
+            a: arithmetize(&matrices.a, "a", TFT::<C>::AB_PADDING)?,
+            b: arithmetize(&matrices.b, "b", TFT::<C>::AB_PADDING)?,
+            c: arithmetize(&matrices.c, "c", TFT::<C>::C_PADDING)?,
+            properties: properties.clone(),
+        })
+    }
+}
+
+/// The public parameters of an index, shared by prover and verifier
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Index<F: PrimeField> {
+    pub t: usize,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<F>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_h: DomainH<F>,
+    pub enforced_degree_bound: usize,
+    #[cfg_attr(feature = "serde", serde(with = "serde_context"))]
+    pub context: Context,
+    pub options: ProtocolOptions,
+}
+
+/// The statement that proofs about an index are checked against: its public parameters and the commitments to its
+/// matrices
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    pub index: Index<C::F>,
+    /// row, col and val of A, B and C, in this order
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::vec::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::vec::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitments")
+    )]
+    pub commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+}
+
+/// A context is encoded as the hex string of its path
+#[cfg(feature = "serde")]
+mod serde_context {
+    use fiat_shamir_rng::Context;
+    use homomorphic_poly_commit::serde_hex;
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(context: &Context, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_hex::encode(&context.as_bytes().to_vec())
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Context, D::Error> {
+        let path: Vec<u8> =
+            serde_hex::decode(&String::deserialize(deserializer)?).map_err(de::Error::custom)?;
+        Context::from_bytes(&path).ok_or_else(|| de::Error::custom("malformed context path"))
+    }
+}
+
+/// Keys trimmed to an index and the arithmetized matrices, before they are committed to
+pub struct IndexedSetup<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    index: Index<C::F>,
+    ck: pc::CommitterKey<C>,
+    vk: pc::VerifierKey<C>,
+    a: MatrixArith<C::F>,
+    b: MatrixArith<C::F>,
+    c: MatrixArith<C::F>,
+    properties: Arc<PropertyRegistry<C>>,
+}
+
+impl<C: ProtocolConfig> IndexedSetup<C>
+where
+    C::F: SquareRootField,
+{
+    /// Commit to the row, col and val polynomials of A, B and C
+    #[allow(clippy::type_complexity)]
+    pub fn commit<R: Rng>(
+        self,
+        rng: &mut R,
+    ) -> Result<(ProverContext<C>, VerifierContext<C>), Error> {
+        let polys = [&self.a, &self.b, &self.c]
+            .iter()
+            .flat_map(|arith| [&arith.row, &arith.col, &arith.val])
+            .collect::<Vec<_>>();
+        let rng = self.index.options.hiding.then_some(rng as &mut dyn RngCore);
+        let (commitments, rands) =
+            C::PC::commit(&self.ck, polys, rng).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let verifier = VerifierContext {
+            index: self.index.clone(),
+            vk: self.vk.clone(),
+            ck: self.ck.clone(),
+            commitments: commitments.clone(),
+            properties: self.properties.clone(),
+        };
+        let prover = ProverContext {
+            index: self.index,
+            ck: self.ck,
+            vk: self.vk,
+            matrices: [self.a, self.b, self.c],
+            commitments,
+            rands,
+            commit_cache: None,
+            proof_cache: None,
+            cancellation: None,
+            properties: self.properties,
+        };
+
+        Ok((prover, verifier))
+    }
+}
+
+impl<F: PrimeField> Index<F> {
+    /// Both parties seed a fresh Fiat-Shamir RNG with the context of the index for every proof, and absorb the
+    /// options the proof is made under
+    pub(crate) fn fs_rng<FS: FiatShamirRng>(&self) -> FS {
+        let mut fs_rng = FS::initialize(&self.context);
+        fs_rng.absorb(&self.options.to_bytes().expect("Options are serializable"));
+        fs_rng
+    }
+
+    /// The Fiat-Shamir RNG of one of the repetitions of a proof
+    fn repetition_fs_rng<FS: FiatShamirRng>(&self, repetition: usize) -> FS {
+        let mut fs_rng = self.fs_rng::<FS>();
+        if self.options.repetitions > 1 {
+            fs_rng.absorb(&(repetition as u64).to_le_bytes());
+        }
+        fs_rng
+    }
+
+    /// The proof of every repetition, serialized together unless there is a single one
+    fn join_repetitions(&self, mut proofs: Vec<Vec<u8>>) -> Result<Vec<u8>, Error> {
+        if self.options.repetitions == 1 {
+            return Ok(proofs.remove(0));
+        }
+        let mut writer = Vec::new();
+        proofs
+            .serialize(&mut writer)
+            .map_err(|_| Error::ProofSerializationError)?;
+        Ok(writer)
+    }
+
+    /// Inverse of [`Self::join_repetitions`], checking the number of repetitions
+    fn split_repetitions(&self, proof: Vec<u8>) -> Result<Vec<Vec<u8>>, Error> {
+        if self.options.repetitions == 1 {
+            return Ok(vec![proof]);
+        }
+        let proofs = Vec::<Vec<u8>>::deserialize(BufReader::new(proof.as_slice()))
+            .map_err(|_| Error::ProofDeserializationError)?;
+        if proofs.len() != self.options.repetitions {
+            return Err(Error::ProofDeserializationError);
+        }
+        Ok(proofs)
+    }
+
+    /// The hiding bound of the polynomials committed to over the index
+    fn hiding_bound(&self) -> Option<usize> {
+        self.options.hiding.then_some(1)
+    }
+}
+
+impl SltMatrix {
+    /// Position of the matrix among A, B and C
+    fn position(&self) -> usize {
+        match self {
+            Self::A => 0,
+            Self::B => 1,
+        }
+    }
+
+    /// Position of the row commitment of the matrix among the index commitments, followed by col and val
+    fn commitment_offset(&self) -> usize {
+        3 * self.position()
+    }
+}
+
+/// Position of the row commitment of C among the index commitments
+const C_OFFSET: usize = 6;
+
+/// Everything the prover needs to prove statements about an index
+pub struct ProverContext<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    index: Index<C::F>,
+    pub(crate) ck: pc::CommitterKey<C>,
+    pub(crate) vk: pc::VerifierKey<C>,
+    pub(crate) matrices: [MatrixArith<C::F>; 3],
+    /// row, col and val of A, B and C, in this order
+    pub(crate) commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+    pub(crate) rands: Vec<pc::Randomness<C>>,
+    /// Commitments made by [`ProverContext::commit`], when caching is enabled
+    commit_cache: Option<Mutex<CommitCache<C::F, C::PC>>>,
+    /// Proofs made by the `prove_*` methods, when caching is enabled
+    proof_cache: Option<Mutex<ProofCache>>,
+    /// Polled by the `prove_*` methods, when set
+    cancellation: Option<CancellationToken>,
+    properties: Arc<PropertyRegistry<C>>,
+}
+
+impl<C: ProtocolConfig> ProverContext<C>
+where
+    C::F: SquareRootField,
+{
+    pub fn index(&self) -> &Index<C::F> {
+        &self.index
+    }
+
+    /// Reuse the commitments of [`ProverContext::commit`] to polynomials it already committed to, keeping up to
+    /// `capacity` of them. Commitments to equal polynomials are then equal, see [`CommitCache`]. Hiding commitments are
+    /// never reused, so the cache only takes effect with `hiding=false`.
+    pub fn with_commit_cache(mut self, capacity: usize) -> Self {
+        self.commit_cache = Some(Mutex::new(CommitCache::new(capacity)));
+        self
+    }
+
+    /// Number of commitments served from and missed by the cache, if caching is enabled
+    pub fn commit_cache_stats(&self) -> Option<(usize, usize)> {
+        self.commit_cache.as_ref().map(|cache| {
+            let cache = cache.lock().expect("commit cache lock poisoned");
+            (cache.hits(), cache.misses())
+        })
+    }
+
+    /// Return the earlier proof when a `prove_*` method is called again on the same statement and committed witness,
+    /// keeping up to `capacity` proofs. Repeated calls then return the same proof, see [`ProofCache`].
+    pub fn with_proof_cache(mut self, capacity: usize) -> Self {
+        self.proof_cache = Some(Mutex::new(ProofCache::new(capacity)));
+        self
+    }
+
+    /// Counters of the proof cache, if caching is enabled
+    pub fn proof_cache_stats(&self) -> Option<ProofCacheStats> {
+        self.proof_cache
+            .as_ref()
+            .map(|cache| cache.lock().expect("proof cache lock poisoned").stats())
+    }
+
+    /// Have the `prove_*` methods fail with [`Error::Aborted`] once `token`, or any of its clones, is cancelled. The
+    /// token is passed to the provers along with the committer key, and they poll it between their rounds and before
+    /// their batches of FFTs, see [`zero_over_k::cancellation`]. [`Self::prove_property`] only polls it before the
+    /// property is proven, as [`FunctionProperty::prove`] does not take a token.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Run `prove`, or return the cached proof of the protocol about the index and the `witness` commitments. The
+    /// lock is not held while proving, so concurrent calls on a new statement may both run the prover.
+    fn cached<P: CanonicalSerialize + CanonicalDeserialize>(
+        &self,
+        protocol_name: &[u8],
+        witness: &[&LabeledCommitment<pc::Commitment<C>>],
+        prove: impl FnOnce() -> Result<P, Error>,
+    ) -> Result<P, Error> {
+        let cache = match &self.proof_cache {
+            Some(cache) => cache,
+            None => return prove(),
+        };
+
+        let statement = to_bytes![
+            self.index.context,
+            self.index.options.to_bytes()?,
+            self.index.t as u64,
+            self.index.domain_k.size() as u64,
+            self.index.domain_h.size() as u64,
+            self.index.enforced_degree_bound as u64,
+            self.commitments,
+            witness
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        let key = ProofCache::key(protocol_name, &statement);
+
+        if let Some(proof) = cache.lock().expect("proof cache lock poisoned").get(&key) {
+            return P::deserialize(proof.as_slice()).map_err(|_| Error::ProofDeserializationError);
+        }
+        let proof = prove()?;
+        let mut bytes = Vec::new();
+        proof
+            .serialize(&mut bytes)
+            .map_err(|_| Error::ProofSerializationError)?;
+        cache
+            .lock()
+            .expect("proof cache lock poisoned")
+            .insert(key, bytes);
+        Ok(proof)
+    }
+
+    /// Commit to an oracle over K, with the degree bound enforced by the index
+    pub fn commit<R: Rng>(
+        &self,
+        label: &str,
+        polynomial: DensePolynomial<C::F>,
+        rng: &mut R,
+    ) -> Result<CommittedOracle<C>, Error> {
+        let polynomial = LabeledPolynomial::new(
+            String::from(label),
+            polynomial,
+            Some(self.index.enforced_degree_bound),
+            self.index.hiding_bound(),
+        );
+        let (mut commitments, mut rands) = match &self.commit_cache {
+            Some(cache) => cache.lock().expect("commit cache lock poisoned").commit(
+                &self.ck,
+                [&polynomial],
+                rng,
+            )?,
+            None => {
+                let rng = self.index.options.hiding.then_some(rng as &mut dyn RngCore);
+                C::PC::commit(&self.ck, [&polynomial], rng).map_err(to_pc_error::<C::F, C::PC>)?
+            }
+        };
+
+        Ok(CommittedOracle {
+            polynomial,
+            commitment: commitments.remove(0),
+            randomness: rands.remove(0),
+        })
+    }
+
+    /// The committer key, checked against the degree bounds `P` enforces over the index
+    fn checked_ck<P: RequiredDegreeBounds>(&self) -> Result<CheckedCommitterKey<'_, C, P>, Error> {
+        let ck = CheckedCommitterKey::new(&self.ck, Some(self.index.enforced_degree_bound))?;
+        Ok(match &self.cancellation {
+            Some(token) => ck.with_cancellation(token),
+            None => ck,
+        })
+    }
+
+    /// With [`strict_labels`](ProtocolOptions::strict_labels), check that the oracles match their commitments in
+    /// label and degree bound
+    fn check_labels(
+        &self,
+        oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        commitments: &[LabeledCommitment<pc::Commitment<C>>],
+    ) -> Result<(), Error> {
+        if self.index.options.strict_labels {
+            ZeroOverK::<C>::check_oracle_labels(oracles, commitments)?;
+        }
+        Ok(())
+    }
+
+    /// [`Self::check_labels`] on the row, col and val polynomials of A, B and C
+    fn check_index_labels(&self) -> Result<(), Error> {
+        let polys = self
+            .matrices
+            .iter()
+            .flat_map(|arith| [&arith.row, &arith.col, &arith.val])
+            .collect::<Vec<_>>();
+        self.check_labels(&polys, &self.commitments)
+    }
+
+    /// Prove a registered property of the function
+    pub fn prove_property<P: FunctionProperty<C>, R: Rng>(
+        &self,
+        statement: &P::Statement,
+        witness: &P::Witness,
+        rng: &mut R,
+    ) -> Result<PropertyProof, Error> {
+        checkpoint(
+            self.cancellation.as_ref(),
+            "functional_commitment::prove_property",
+        )?;
+        self.properties
+            .prove::<P, R>(&self.ck, &self.index, statement, witness, rng)
+    }
+
+    /// Prove that A or B is t-strictly lower triangular
+    pub fn prove_t_slt<R: Rng>(
+        &self,
+        matrix: SltMatrix,
+        rng: &mut R,
+    ) -> Result<TSLTProof<C>, Error> {
+        let arith = &self.matrices[matrix.position()];
+        let offset = matrix.commitment_offset();
+        self.check_index_labels()?;
+
+        let protocol_name = [
+            TStrictlyLowerTriangular::<C>::PROTOCOL_NAME,
+            &[matrix.position() as u8],
+        ]
+        .concat();
+        self.cached(&protocol_name, &[], || {
+            TStrictlyLowerTriangular::<C>::prove(
+                &self.checked_ck()?,
+                self.index.t,
+                &self.index.domain_k,
+                &self.index.domain_h,
+                &arith.row,
+                &self.commitments[offset],
+                &self.rands[offset],
+                &arith.col,
+                &self.commitments[offset + 1],
+                &self.rands[offset + 1],
+                Some(self.index.enforced_degree_bound),
+                TFT::<C>::AB_PADDING,
+                &self.index.context,
+                &mut self.index.fs_rng(),
+                rng,
+            )
+        })
+    }
+
+    /// Prove that C is t-diagonal
+    pub fn prove_t_diag<R: Rng>(&self, rng: &mut R) -> Result<TDiagProof<C>, Error> {
+        let c = &self.matrices[2];
+        self.check_index_labels()?;
+
+        self.cached(TDiag::<C>::PROTOCOL_NAME, &[], || {
+            TDiag::<C>::prove(
+                &self.checked_ck()?,
+                self.index.t,
+                &c.row,
+                &c.col,
+                &c.val,
+                &self.commitments[C_OFFSET],
+                &self.commitments[C_OFFSET + 1],
+                &self.commitments[C_OFFSET + 2],
+                &self.rands[C_OFFSET],
+                &self.rands[C_OFFSET + 1],
+                &self.rands[C_OFFSET + 2],
+                Some(self.index.enforced_degree_bound),
+                &self.index.domain_k,
+                &self.index.domain_h,
+                self.index.domain_h.size(),
+                TFT::<C>::C_PADDING,
+                &self.index.context,
+                rng,
+            )
+        })
+    }
+
+    /// Prove that (A, B, C) is a t-functional triple, returning the serialized proof. With several
+    /// [`repetitions`](ProtocolOptions::repetitions), the proofs of the repetitions are serialized together.
+    pub fn prove_tft<R: Rng>(&self, rng: &mut R) -> Result<Vec<u8>, Error> {
+        self.check_index_labels()?;
+        self.cached(TFT::<C>::PROTOCOL_NAME, &[], || {
+            let proofs = (0..self.index.options.repetitions)
+                .map(|repetition| {
+                    self.prove_tft_repetition(&mut self.index.repetition_fs_rng(repetition), rng)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            self.index.join_repetitions(proofs)
+        })
+    }
+
+    /// One t-FT proof, amortized with [`batching`](ProtocolOptions::batching)
+    fn prove_tft_repetition<R: Rng>(
+        &self,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error> {
+        let [a, b, c] = &self.matrices;
+        let (cm, rands) = (&self.commitments, &self.rands);
+        let prove = if self.index.options.batching {
+            TFT::<C>::prove_amortized
+        } else {
+            TFT::<C>::prove
+        };
+
+        prove(
+            &self.checked_ck()?,
+            self.index.t,
+            &self.index.domain_k,
+            &self.index.domain_h,
+            Some(self.index.enforced_degree_bound),
+            &a.row,
+            &a.col,
+            &cm[0],
+            &cm[1],
+            &rands[0],
+            &rands[1],
+            &b.row,
+            &b.col,
+            &cm[3],
+            &cm[4],
+            &rands[3],
+            &rands[4],
+            &c.row,
+            &c.col,
+            &c.val,
+            &cm[C_OFFSET],
+            &cm[C_OFFSET + 1],
+            &cm[C_OFFSET + 2],
+            &rands[C_OFFSET],
+            &rands[C_OFFSET + 1],
+            &rands[C_OFFSET + 2],
+            &self.index.context,
+            fs_rng,
+            rng,
+        )
+    }
+
+    /// Prove that the discrete log of f is greater than that of g everywhere over K
+    pub fn prove_dl_comparison<R: Rng>(
+        &self,
+        f: &CommittedOracle<C>,
+        g: &CommittedOracle<C>,
+        rng: &mut R,
+    ) -> Result<DLComparisonProof<C>, Error> {
+        self.check_labels(
+            &[&f.polynomial, &g.polynomial],
+            &[f.commitment.clone(), g.commitment.clone()],
+        )?;
+
+        self.cached(
+            DLComparison::<C>::PROTOCOL_NAME,
+            &[&f.commitment, &g.commitment],
+            || {
+                DLComparison::<C>::prove(
+                    &self.checked_ck()?,
+                    &self.index.domain_k,
+                    &self.index.domain_h,
+                    &f.polynomial,
+                    &f.commitment,
+                    &f.randomness,
+                    &g.polynomial,
+                    &g.commitment,
+                    &g.randomness,
+                    Some(self.index.enforced_degree_bound),
+                    &self.index.context,
+                    &mut self.index.fs_rng(),
+                    rng,
+                )
+            },
+        )
+    }
+}
+
+/// Everything the verifier needs to check statements about an index
+pub struct VerifierContext<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    index: Index<C::F>,
+    pub(crate) vk: pc::VerifierKey<C>,
+    pub(crate) ck: pc::CommitterKey<C>,
+    /// row, col and val of A, B and C, in this order
+    commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+    properties: Arc<PropertyRegistry<C>>,
+}
+
+impl<C: ProtocolConfig> VerifierContext<C>
+where
+    C::F: SquareRootField,
+{
+    pub fn index(&self) -> &Index<C::F> {
+        &self.index
+    }
+
+    /// The commitments to the row, col and val polynomials of A, B and C, in this order
+    pub fn commitments(&self) -> &[LabeledCommitment<pc::Commitment<C>>] {
+        &self.commitments
+    }
+
+    /// The public statement checked by this verifier, e.g. to hand it to a remote verifier
+    pub fn statement(&self) -> Statement<C> {
+        Statement {
+            index: self.index.clone(),
+            commitments: self.commitments.clone(),
+        }
+    }
+
+    /// Verify the proof of a registered property of the function
+    pub fn verify_property<P: FunctionProperty<C>>(
+        &self,
+        statement: &P::Statement,
+        proof: &PropertyProof,
+    ) -> Result<(), Error> {
+        self.properties
+            .verify::<P>(&self.vk, &self.ck, &self.index, statement, proof)
+    }
+
+    /// With [`strict_labels`](ProtocolOptions::strict_labels), check that the labels of the commitments are distinct
+    /// and not reserved by the protocols
+    fn check_labels(
+        &self,
+        commitments: &[LabeledCommitment<pc::Commitment<C>>],
+    ) -> Result<(), Error> {
+        if self.index.options.strict_labels {
+            ZeroOverK::<C>::check_commitment_labels(commitments)?;
+        }
+        Ok(())
+    }
+
+    pub fn verify_t_slt(&self, matrix: SltMatrix, proof: TSLTProof<C>) -> Result<(), Error> {
+        let offset = matrix.commitment_offset();
+        self.check_labels(&self.commitments)?;
+
+        TStrictlyLowerTriangular::<C>::verify(
+            &self.vk,
+            &self.ck,
+            self.index.t,
+            &self.index.domain_k,
+            &self.index.domain_h,
+            &self.commitments[offset],
+            &self.commitments[offset + 1],
+            Some(self.index.enforced_degree_bound),
+            TFT::<C>::AB_PADDING,
+            proof,
+            &self.index.context,
+            &mut self.index.fs_rng(),
+        )
+    }
+
+    pub fn verify_t_diag(&self, proof: TDiagProof<C>) -> Result<(), Error> {
+        self.check_labels(&self.commitments)?;
+
+        TDiag::<C>::verify(
+            &self.vk,
+            self.index.t,
+            &self.commitments[C_OFFSET],
+            &self.commitments[C_OFFSET + 1],
+            &self.commitments[C_OFFSET + 2],
+            Some(self.index.enforced_degree_bound),
+            &self.index.domain_h,
+            &self.index.domain_k,
+            self.index.domain_h.size(),
+            TFT::<C>::C_PADDING,
+            proof,
+            &self.index.context,
+        )
+    }
+
+    pub fn verify_tft(&self, proof: Vec<u8>) -> Result<(), Error> {
+        self.check_labels(&self.commitments)?;
+        for (repetition, proof) in self.index.split_repetitions(proof)?.into_iter().enumerate() {
+            self.verify_tft_repetition(proof, &mut self.index.repetition_fs_rng(repetition))?;
+        }
+        Ok(())
+    }
+
+    /// One t-FT proof, amortized with [`batching`](ProtocolOptions::batching)
+    fn verify_tft_repetition(&self, proof: Vec<u8>, fs_rng: &mut C::FS) -> Result<(), Error> {
+        let cm = &self.commitments;
+        let verify = if self.index.options.batching {
+            TFT::<C>::verify_amortized
+        } else {
+            TFT::<C>::verify_with_report
+        };
+
+        verify(
+            &self.vk,
+            &self.ck,
+            self.index.t,
+            &cm[0],
+            &cm[1],
+            &cm[3],
+            &cm[4],
+            &cm[C_OFFSET],
+            &cm[C_OFFSET + 1],
+            &cm[C_OFFSET + 2],
+            Some(self.index.enforced_degree_bound),
+            &self.index.domain_h,
+            &self.index.domain_k,
+            proof,
+            &self.index.context,
+            fs_rng,
+        )
+        .into_result()
+    }
+
+    pub fn verify_dl_comparison(
+        &self,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        proof: DLComparisonProof<C>,
+    ) -> Result<(), Error> {
+        self.check_labels(&[f_commit.clone(), g_commit.clone()])?;
+
+        DLComparison::<C>::verify(
+            &self.vk,
+            &self.ck,
+            &self.index.domain_k,
+            &self.index.domain_h,
+            f_commit,
+            g_commit,
+            Some(self.index.enforced_degree_bound),
+            proof,
+            &self.index.context,
+            &mut self.index.fs_rng(),
+        )
+    }
+}
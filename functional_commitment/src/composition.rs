@@ -0,0 +1,551 @@
+//! Commitments to the composition g∘f of two committed functions.
+//!
+//! ```ignore
+//! let (prover, verifier) = FunctionalCommitment::<C>::setup(max_degree, rng)?
+//!     .index_composition(&f, &g)?
+//!     .commit(rng)?;
+//!
+//! let proof = prover.prove_composition(rng)?;
+//! verifier.verify_composition(proof)?;
+//! ```
+//!
+//! The circuits are stitched with [`Circuit::compose`]: the wires of f keep their indices and every wire of g but the
+//! constant moves up by a public offset s. Each of the matrices A, B and C of g∘f is then the matrix of f plus that of
+//! g embedded at this offset, M = M_f + P * M_g * Q, where P moves row i > 0 of M_g to row i + s and Q moves column
+//! j > 0 to column j + s. P and Q are public, so the consistency proof consists of two matrix product tests and one
+//! matrix sum test per matrix, over hiding commitments to P * M_g and P * M_g * Q.
+//!
+//! f, g and g∘f are all indexed over the domains of g∘f, and the three indices can be used on their own.
+
+use crate::builder::{
+    Index, IndexedSetup, Matrices, ProverContext, UniversalSetup, VerifierContext,
+};
+use ac_compiler::{circuit::Circuit, Matrix};
+use ark_ff::{PrimeField, SquareRootField};
+use ark_poly_commit::{LabeledCommitment, PCCommitment, PolynomialCommitment};
+use ark_std::rand::{Rng, RngCore};
+use derivative::Derivative;
+use proof_of_function_relation::{
+    domains::choose_domains,
+    error::{to_pc_error, Error},
+    indexer::{arithmetize_matrix, CommittedEncoding, PaddingStrategy, SparseMatrixEncoding},
+    matrix_product_test::{
+        proof::Proof as MatrixProductProof, MatrixProductTest, Statement as ProductStatement,
+    },
+    matrix_sum_test::{proof::Proof as MatrixSumProof, MatrixSumTest, Statement as SumStatement},
+};
+use std::sync::Arc;
+use zero_over_k::config::{pc, ProtocolConfig};
+
+/// Labels of A, B and C in the contexts of the consistency proof
+const MATRIX_NAMES: [&[u8]; 3] = [b"a", b"b", b"c"];
+
+/// Where the wires of g land in g∘f
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Embedding {
+    /// How far every wire of g but the constant moves up
+    pub offset: usize,
+    /// The number of rows of the matrices of g
+    pub rows: usize,
+}
+
+impl Embedding {
+    /// The number of rows of the matrices of g∘f
+    pub fn size(&self) -> usize {
+        self.offset + self.rows
+    }
+
+    fn wire(&self, w: usize) -> usize {
+        if w == 0 {
+            0
+        } else {
+            w + self.offset
+        }
+    }
+
+    /// P, moving every row of a matrix of g but the constant one to its row in g∘f
+    fn rows_matrix<F: PrimeField>(&self) -> Matrix<F> {
+        let mut matrix = vec![vec![]; self.size()];
+        for i in 1..self.rows {
+            matrix[self.wire(i)].push((F::one(), i));
+        }
+        matrix
+    }
+
+    /// Q, moving every column of a matrix of g to its column in g∘f
+    fn cols_matrix<F: PrimeField>(&self) -> Matrix<F> {
+        let mut matrix = vec![vec![]; self.size()];
+        for (j, row) in matrix.iter_mut().enumerate().take(self.rows) {
+            row.push((F::one(), self.wire(j)));
+        }
+        matrix
+    }
+
+    /// P * M
+    fn shift_rows<F: PrimeField>(&self, matrix: &Matrix<F>) -> Matrix<F> {
+        let mut shifted = vec![vec![]; self.size()];
+        for (i, row) in matrix.iter().enumerate().skip(1) {
+            shifted[self.wire(i)] = row.clone();
+        }
+        shifted
+    }
+
+    /// M * Q
+    fn shift_cols<F: PrimeField>(&self, matrix: &Matrix<F>) -> Matrix<F> {
+        matrix
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&(val, col)| (val, self.wire(col)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A matrix encoding along with its commitments and their randomness
+struct CommittedMatrix<C: ProtocolConfig> {
+    encoding: SparseMatrixEncoding<C::F>,
+    commitment: CommittedEncoding<pc::Commitment<C>>,
+    rands: Vec<pc::Randomness<C>>,
+}
+
+impl<C: ProtocolConfig> CommittedMatrix<C> {
+    /// Arithmetize `matrix` over the domains of `index` and commit to it. Without `rng` the commitments are not hiding
+    /// and anyone can recompute them.
+    fn new(
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        label: &str,
+        matrix: &Matrix<C::F>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self, Error> {
+        let hiding_bound = rng.as_ref().map(|_| 1);
+        let encoding: SparseMatrixEncoding<C::F> = arithmetize_matrix(
+            matrix,
+            index.domain_h,
+            index.domain_k,
+            PaddingStrategy::RepeatLast,
+        )?
+        .with_bounds(label, Some(index.enforced_degree_bound), hiding_bound)
+        .into();
+        let (commitments, rands) =
+            C::PC::commit(ck, encoding.iter(), rng).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        Ok(Self {
+            encoding,
+            commitment: committed_encoding(&commitments),
+            rands,
+        })
+    }
+}
+
+/// The commitments to row, col and val, in this order
+fn committed_encoding<C: PCCommitment>(
+    commitments: &[LabeledCommitment<C>],
+) -> CommittedEncoding<C> {
+    CommittedEncoding {
+        row: commitments[0].clone(),
+        col: commitments[1].clone(),
+        val: commitments[2].clone(),
+    }
+}
+
+fn product_statement<C: ProtocolConfig>(
+    index: &Index<C::F>,
+    a: &CommittedEncoding<pc::Commitment<C>>,
+    b: &CommittedEncoding<pc::Commitment<C>>,
+    m: &CommittedEncoding<pc::Commitment<C>>,
+) -> ProductStatement<C> {
+    ProductStatement {
+        domain_k: index.domain_k,
+        domain_h: index.domain_h,
+        a: a.clone(),
+        b: b.clone(),
+        m: m.clone(),
+        enforced_degree_bound: Some(index.enforced_degree_bound),
+    }
+}
+
+fn sum_statement<C: ProtocolConfig>(
+    index: &Index<C::F>,
+    m: &CommittedEncoding<pc::Commitment<C>>,
+    m1: &CommittedEncoding<pc::Commitment<C>>,
+    m2: &CommittedEncoding<pc::Commitment<C>>,
+) -> SumStatement<C> {
+    SumStatement {
+        domain_k: index.domain_k,
+        domain_h: index.domain_h,
+        m: m.clone(),
+        m1: m1.clone(),
+        m2: m2.clone(),
+        enforced_degree_bound: Some(index.enforced_degree_bound),
+    }
+}
+
+impl<C: ProtocolConfig> UniversalSetup<C>
+where
+    C::F: SquareRootField,
+{
+    /// Index f, g and their composition g∘f, in which the outputs of `f` feed the inputs of `g`, over the domains of
+    /// g∘f
+    pub fn index_composition(
+        mut self,
+        f: &Circuit,
+        g: &Circuit,
+    ) -> Result<CompositionSetup<C>, Error> {
+        let composite =
+            Circuit::compose(f, g).map_err(|e| Error::InvalidCircuit(format!("{:?}", e)))?;
+        let composite_matrices = Matrices::<C::F>::from_circuit(&composite);
+        let g_matrices = Matrices::<C::F>::try_from_circuit(g)?;
+        let embedding = Embedding {
+            offset: f.composition_offset(),
+            rows: g_matrices.number_of_constraints(),
+        };
+
+        // Q has one entry per row of g, which may be more than the entries of any matrix of g∘f
+        let (domain_h, domain_k) = choose_domains::<C::F>(
+            composite_matrices.number_of_constraints(),
+            composite_matrices.number_of_non_zero().max(embedding.rows),
+        )?;
+        let properties = Arc::new(std::mem::take(&mut self.properties));
+        let index = |matrices: &Matrices<C::F>, context| {
+            self.index_over(matrices, domain_h, domain_k, context, &properties)
+        };
+
+        Ok(CompositionSetup {
+            f: index(&Matrices::try_from_circuit(f)?, self.context.child(b"f"))?,
+            g: index(&g_matrices, self.context.child(b"g"))?,
+            composite: index(&composite_matrices, self.context.clone())?,
+            g_matrices,
+            embedding,
+        })
+    }
+}
+
+/// The indices of f, g and g∘f, before they are committed to
+pub struct CompositionSetup<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    f: IndexedSetup<C>,
+    g: IndexedSetup<C>,
+    composite: IndexedSetup<C>,
+    g_matrices: Matrices<C::F>,
+    embedding: Embedding,
+}
+
+impl<C: ProtocolConfig> CompositionSetup<C>
+where
+    C::F: SquareRootField,
+{
+    /// Commit to the matrices of f, g and g∘f, and to the public matrices P and Q
+    #[allow(clippy::type_complexity)]
+    pub fn commit<R: Rng>(
+        self,
+        rng: &mut R,
+    ) -> Result<(CompositionProver<C>, CompositionVerifier<C>), Error> {
+        let (f_prover, f_verifier) = self.f.commit(rng)?;
+        let (g_prover, g_verifier) = self.g.commit(rng)?;
+        let (prover, verifier) = self.composite.commit(rng)?;
+
+        let rows = CommittedMatrix::new(
+            &prover.ck,
+            prover.index(),
+            "shift_rows",
+            &self.embedding.rows_matrix(),
+            None,
+        )?;
+        let cols = CommittedMatrix::new(
+            &prover.ck,
+            prover.index(),
+            "shift_cols",
+            &self.embedding.cols_matrix(),
+            None,
+        )?;
+
+        let verifier = CompositionVerifier {
+            f: f_verifier,
+            g: g_verifier,
+            composite: verifier,
+            embedding: self.embedding,
+            rows: rows.commitment.clone(),
+            cols: cols.commitment.clone(),
+        };
+        let prover = CompositionProver {
+            f: f_prover,
+            g: g_prover,
+            composite: prover,
+            g_matrices: self.g_matrices,
+            embedding: self.embedding,
+            rows,
+            cols,
+        };
+
+        Ok((prover, verifier))
+    }
+}
+
+/// The proof that one of A, B and C of g∘f is the matrix of f plus that of g embedded at the offset of the composition
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct MatrixEmbeddingProof<C: ProtocolConfig> {
+    /// Commitment to P * M_g
+    pub shifted_rows: CommittedEncoding<pc::Commitment<C>>,
+    /// Commitment to P * M_g * Q
+    pub embedded: CommittedEncoding<pc::Commitment<C>>,
+    pub shift_rows_proof: MatrixProductProof<C>,
+    pub shift_cols_proof: MatrixProductProof<C>,
+    pub sum_proof: MatrixSumProof<C>,
+}
+
+/// The proof that the index of g∘f is consistent with those of f and g
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct CompositionProof<C: ProtocolConfig> {
+    pub a: MatrixEmbeddingProof<C>,
+    pub b: MatrixEmbeddingProof<C>,
+    pub c: MatrixEmbeddingProof<C>,
+}
+
+impl<C: ProtocolConfig> ProverContext<C>
+where
+    C::F: SquareRootField,
+{
+    /// A, B or C as committed to in the index
+    fn committed_matrix(&self, position: usize) -> CommittedMatrix<C> {
+        let offset = 3 * position;
+        CommittedMatrix {
+            encoding: self.matrices[position].clone().into(),
+            commitment: committed_encoding(&self.commitments[offset..offset + 3]),
+            rands: self.rands[offset..offset + 3].to_vec(),
+        }
+    }
+}
+
+/// Everything the prover needs to prove that g∘f is consistent with f and g
+pub struct CompositionProver<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    pub f: ProverContext<C>,
+    pub g: ProverContext<C>,
+    pub composite: ProverContext<C>,
+    g_matrices: Matrices<C::F>,
+    embedding: Embedding,
+    /// P
+    rows: CommittedMatrix<C>,
+    /// Q
+    cols: CommittedMatrix<C>,
+}
+
+impl<C: ProtocolConfig> CompositionProver<C>
+where
+    C::F: SquareRootField,
+{
+    pub fn embedding(&self) -> Embedding {
+        self.embedding
+    }
+
+    /// Prove that each of A, B and C of g∘f is that of f plus that of g embedded at the offset of the composition
+    pub fn prove_composition<R: Rng>(&self, rng: &mut R) -> Result<CompositionProof<C>, Error> {
+        let mut fs_rng = self.composite.index().fs_rng::<C::FS>();
+
+        Ok(CompositionProof {
+            a: self.prove_embedding(0, &mut fs_rng, rng)?,
+            b: self.prove_embedding(1, &mut fs_rng, rng)?,
+            c: self.prove_embedding(2, &mut fs_rng, rng)?,
+        })
+    }
+
+    fn prove_embedding<R: Rng>(
+        &self,
+        position: usize,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<MatrixEmbeddingProof<C>, Error> {
+        let ck = &self.composite.ck;
+        let index = self.composite.index();
+        let context = index
+            .context
+            .child(b"composition")
+            .child(MATRIX_NAMES[position]);
+
+        let g_matrix = [&self.g_matrices.a, &self.g_matrices.b, &self.g_matrices.c][position];
+        let shifted_rows = self.embedding.shift_rows(g_matrix);
+        let embedded = self.embedding.shift_cols(&shifted_rows);
+        let shifted_rows =
+            CommittedMatrix::<C>::new(ck, index, "shifted_rows", &shifted_rows, Some(rng))?;
+        let embedded = CommittedMatrix::<C>::new(ck, index, "embedded", &embedded, Some(rng))?;
+
+        let f = self.f.committed_matrix(position);
+        let g = self.g.committed_matrix(position);
+        let composite = self.composite.committed_matrix(position);
+
+        // Step 1: P * M_g
+        let shift_rows_proof = MatrixProductTest::<C>::prove(
+            ck,
+            &product_statement(
+                index,
+                &self.rows.commitment,
+                &g.commitment,
+                &shifted_rows.commitment,
+            ),
+            &self.rows.encoding,
+            &self.rows.rands,
+            &g.encoding,
+            &g.rands,
+            &shifted_rows.encoding,
+            &shifted_rows.rands,
+            &context.child(b"rows"),
+            fs_rng,
+            rng,
+        )?;
+
+        // Step 2: (P * M_g) * Q
+        let shift_cols_proof = MatrixProductTest::<C>::prove(
+            ck,
+            &product_statement(
+                index,
+                &shifted_rows.commitment,
+                &self.cols.commitment,
+                &embedded.commitment,
+            ),
+            &shifted_rows.encoding,
+            &shifted_rows.rands,
+            &self.cols.encoding,
+            &self.cols.rands,
+            &embedded.encoding,
+            &embedded.rands,
+            &context.child(b"cols"),
+            fs_rng,
+            rng,
+        )?;
+
+        // Step 3: M_f + P * M_g * Q
+        let sum_proof = MatrixSumTest::<C>::prove(
+            ck,
+            &sum_statement(
+                index,
+                &composite.commitment,
+                &f.commitment,
+                &embedded.commitment,
+            ),
+            &composite.encoding,
+            &composite.rands,
+            &f.encoding,
+            &f.rands,
+            &embedded.encoding,
+            &embedded.rands,
+            &context.child(b"sum"),
+            fs_rng,
+            rng,
+        )?;
+
+        Ok(MatrixEmbeddingProof {
+            shifted_rows: shifted_rows.commitment,
+            embedded: embedded.commitment,
+            shift_rows_proof,
+            shift_cols_proof,
+            sum_proof,
+        })
+    }
+}
+
+/// Everything the verifier needs to check that g∘f is consistent with f and g
+pub struct CompositionVerifier<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    pub f: VerifierContext<C>,
+    pub g: VerifierContext<C>,
+    pub composite: VerifierContext<C>,
+    embedding: Embedding,
+    /// P
+    rows: CommittedEncoding<pc::Commitment<C>>,
+    /// Q
+    cols: CommittedEncoding<pc::Commitment<C>>,
+}
+
+impl<C: ProtocolConfig> CompositionVerifier<C>
+where
+    C::F: SquareRootField,
+{
+    pub fn embedding(&self) -> Embedding {
+        self.embedding
+    }
+
+    pub fn verify_composition(&self, proof: CompositionProof<C>) -> Result<(), Error> {
+        let mut fs_rng = self.composite.index().fs_rng::<C::FS>();
+
+        self.verify_embedding(0, proof.a, &mut fs_rng)?;
+        self.verify_embedding(1, proof.b, &mut fs_rng)?;
+        self.verify_embedding(2, proof.c, &mut fs_rng)
+    }
+
+    fn verify_embedding(
+        &self,
+        position: usize,
+        proof: MatrixEmbeddingProof<C>,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        let vk = &self.composite.vk;
+        let index = self.composite.index();
+        let context = index
+            .context
+            .child(b"composition")
+            .child(MATRIX_NAMES[position]);
+
+        let offset = 3 * position;
+        let matrix = |verifier: &VerifierContext<C>| {
+            committed_encoding(&verifier.commitments()[offset..offset + 3])
+        };
+
+        MatrixProductTest::<C>::verify(
+            vk,
+            &product_statement(index, &self.rows, &matrix(&self.g), &proof.shifted_rows),
+            proof.shift_rows_proof,
+            &context.child(b"rows"),
+            fs_rng,
+        )?;
+        MatrixProductTest::<C>::verify(
+            vk,
+            &product_statement(index, &proof.shifted_rows, &self.cols, &proof.embedded),
+            proof.shift_cols_proof,
+            &context.child(b"cols"),
+            fs_rng,
+        )?;
+        MatrixSumTest::<C>::verify(
+            vk,
+            &sum_statement(
+                index,
+                &matrix(&self.composite),
+                &matrix(&self.f),
+                &proof.embedded,
+            ),
+            proof.sum_proof,
+            &context.child(b"sum"),
+            fs_rng,
+        )
+    }
+}
@@ -1,3 +1,9 @@
+pub mod builder;
+pub mod composition;
+pub mod marlin;
+pub mod options;
+pub mod proof_cache;
+pub mod property;
 mod tests;
 
 #[macro_export]
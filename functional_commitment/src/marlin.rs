@@ -0,0 +1,198 @@
+//! Proofs of evaluation y = f(x) with index private Marlin over the committed index, so that the proofs of function
+//! relation and the proofs of evaluation refer to the same commitments.
+//!
+//! ```ignore
+//! let (pk, _) = prover.marlin_keys(&index_info, &matrices)?;
+//! let proof = prover.prove_evaluation(&pk, assignment, rng)?;
+//!
+//! let vk = verifier.marlin_verifier_key(&index_info)?;
+//! assert!(verifier.verify_evaluation(&vk, &inputs, &outputs, proof, rng)?);
+//! ```
+//!
+//! Marlin arithmetizes the transpose of each matrix and divides its entries by u_H(c, c). The index is committed
+//! without either: its row and col polynomials are the col and row polynomials of Marlin, and Marlin reads its val
+//! polynomials with [`ValEncoding::Raw`]. The keys built here reuse the commitments and randomness of the index as
+//! they are, and the index is trimmed to the degree bounds of Marlin so that no new commitment to it is needed.
+
+use crate::builder::{Index, Matrices, ProverContext, VerifierContext};
+use ac_compiler::R1CSfIndex;
+use ark_ff::{PrimeField, SquareRootField};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::LabeledPolynomial;
+use ark_std::rand::Rng;
+use index_private_marlin::{
+    ahp::{
+        constraint_systems::{MatrixArithmetization, MatrixEvals, ValEncoding},
+        indexer::Index as MarlinIndex,
+        AHPForR1CS,
+    },
+    data_structures::{Proof, ProverKey, VerifierKey},
+    Error, Marlin,
+};
+use proof_of_function_relation::indexer::MatrixArith;
+use zero_over_k::config::{pc, ProtocolConfig};
+
+/// Marlin proofs about an index are created under this child of its context
+const MARLIN_CONTEXT: &[u8] = b"marlin";
+
+/// The degree bounds of the Marlin prover over the domains of `index`, beyond those the index enforces anyway
+pub(crate) fn degree_bounds<F: PrimeField>(index: &Index<F>) -> [usize; 2] {
+    let index_info = R1CSfIndex {
+        number_of_constraints: index.domain_h.size(),
+        number_of_input_rows: index.t,
+        number_of_outputs: 0,
+        number_of_non_zero_entries: index.domain_k.size(),
+    };
+    let [g_1, g_2, ..] = AHPForR1CS::<F>::get_degree_bounds(&index_info);
+    [g_1, g_2]
+}
+
+/// The information about `index` as Marlin sees it, given that of the circuit it was compiled from. Marlin derives K
+/// from the number of non-zero entries, which the index may have enlarged to |H|.
+fn marlin_index_info<F: PrimeField, E>(
+    index: &Index<F>,
+    index_info: &R1CSfIndex,
+) -> Result<R1CSfIndex, Error<E>> {
+    let domain_h_size =
+        GeneralEvaluationDomain::<F>::compute_size_of_domain(index_info.number_of_constraints);
+    if index_info.number_of_input_rows != index.t || domain_h_size != Some(index.domain_h.size()) {
+        return Err(Error::IndexMismatch(format!(
+            "A circuit with {} input rows and {} constraints was not compiled to an index with t = {} over a domain \
+             H of size {}",
+            index_info.number_of_input_rows,
+            index_info.number_of_constraints,
+            index.t,
+            index.domain_h.size()
+        )));
+    }
+
+    Ok(R1CSfIndex {
+        number_of_non_zero_entries: index.domain_k.size(),
+        ..index_info.clone()
+    })
+}
+
+/// Row, col and val of A, B and C in the order of Marlin, where row and col are swapped
+fn marlin_order<T: Clone>(items: &[T]) -> Vec<T> {
+    items
+        .chunks(3)
+        .flat_map(|matrix| [&matrix[1], &matrix[0], &matrix[2]])
+        .cloned()
+        .collect()
+}
+
+/// The Marlin arithmetization of the transpose of the matrix encoded by `arith`
+fn transpose<F: PrimeField>(arith: &MatrixArith<F>, prefix: &str) -> MatrixArithmetization<F> {
+    let relabel = |name: &str, poly: &LabeledPolynomial<F, DensePolynomial<F>>| {
+        LabeledPolynomial::new(
+            format!("{}_{}", prefix, name),
+            poly.polynomial().clone(),
+            poly.degree_bound(),
+            poly.hiding_bound(),
+        )
+    };
+
+    MatrixArithmetization {
+        row: relabel("row", &arith.col),
+        col: relabel("col", &arith.row),
+        val: relabel("val", &arith.val),
+        evals_on_k: MatrixEvals {
+            row: arith.evals_on_k.col.clone(),
+            col: arith.evals_on_k.row.clone(),
+            val: arith.evals_on_k.val.clone(),
+        },
+    }
+}
+
+impl<C: ProtocolConfig> ProverContext<C>
+where
+    C::F: SquareRootField,
+{
+    /// The Marlin keys of the committed index, which must have been compiled to `index_info` and `matrices`
+    #[allow(clippy::type_complexity)]
+    pub fn marlin_keys(
+        &self,
+        index_info: &R1CSfIndex,
+        matrices: &Matrices<C::F>,
+    ) -> Result<(ProverKey<C>, VerifierKey<C>), Error<pc::Error<C>>> {
+        let [a, b, c] = &self.matrices;
+        let index = MarlinIndex {
+            index_info: marlin_index_info(self.index(), index_info)?,
+            a_arith: transpose(a, "a"),
+            b_arith: transpose(b, "b"),
+            c_arith: transpose(c, "c"),
+            val_encoding: ValEncoding::Raw,
+            a: matrices.a.clone(),
+            b: matrices.b.clone(),
+            c: matrices.c.clone(),
+        };
+        let commits = marlin_order(&self.commitments)
+            .into_iter()
+            .map(|commitment| commitment.commitment().clone())
+            .collect();
+
+        Marlin::<C>::index_from_commitments(
+            self.ck.clone(),
+            self.vk.clone(),
+            index,
+            commits,
+            marlin_order(&self.rands),
+        )
+    }
+
+    /// Prove that the outputs in `assignment` are the evaluation of the function at its inputs
+    pub fn prove_evaluation<R: Rng>(
+        &self,
+        pk: &ProverKey<C>,
+        assignment: Vec<C::F>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error<pc::Error<C>>> {
+        Marlin::<C>::prove(
+            pk,
+            assignment,
+            &self.index().context.child(MARLIN_CONTEXT),
+            rng,
+        )
+    }
+}
+
+impl<C: ProtocolConfig> VerifierContext<C>
+where
+    C::F: SquareRootField,
+{
+    /// The Marlin verifier key of the committed index, built from the commitments of this verifier
+    pub fn marlin_verifier_key(
+        &self,
+        index_info: &R1CSfIndex,
+    ) -> Result<VerifierKey<C>, Error<pc::Error<C>>> {
+        Ok(VerifierKey {
+            commits: marlin_order(self.commitments())
+                .into_iter()
+                .map(|commitment| commitment.commitment().clone())
+                .collect(),
+            verifier_key: self.vk.clone(),
+            index_info: marlin_index_info(self.index(), index_info)?,
+            val_encoding: ValEncoding::Raw,
+        })
+    }
+
+    /// Verify that `outputs` are the evaluation of the function at `inputs`, the first of which is the constant 1
+    pub fn verify_evaluation<R: Rng>(
+        &self,
+        vk: &VerifierKey<C>,
+        inputs: &[C::F],
+        outputs: &[C::F],
+        proof: Proof<C>,
+        rng: &mut R,
+    ) -> Result<bool, Error<pc::Error<C>>> {
+        Marlin::<C>::verify(
+            vk,
+            &inputs.to_vec(),
+            &outputs.to_vec(),
+            proof,
+            &self.index().context.child(MARLIN_CONTEXT),
+            rng,
+            &self.ck,
+        )
+    }
+}
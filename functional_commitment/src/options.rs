@@ -0,0 +1,128 @@
+//! Features of the proofs about an index that integrators toggle at runtime, as opposed to the cargo features of the
+//! protocol crates, which are fixed at build time.
+//!
+//! ```ignore
+//! let options = "hiding=false, batching=true, repetitions=2".parse::<ProtocolOptions>()?;
+//! let (prover, verifier) = FunctionalCommitment::<C>::setup(max_degree, rng)?
+//!     .with_options(options)
+//!     .index(matrices)?
+//!     .commit(rng)?;
+//! ```
+//!
+//! The options are part of the [`Index`](crate::builder::Index), and both parties absorb them into the transcript of
+//! every proof, so a proof made under some options is rejected by a verifier holding others.
+
+use ark_ff::to_bytes;
+use proof_of_function_relation::error::Error;
+use std::str::FromStr;
+
+/// Runtime options of the provers and verifiers of the builder
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolOptions {
+    /// Commit to the index matrices and to the oracles of [`ProverContext::commit`] with hiding randomness. The
+    /// masking polynomials of the protocols themselves are not affected.
+    ///
+    /// [`ProverContext::commit`]: crate::builder::ProverContext::commit
+    pub hiding: bool,
+    /// Prove the t-SLT tests on A and B of a t-FT with a single h and geometric sequence test, see
+    /// [`TFT::prove_amortized`](proof_of_function_relation::t_functional_triple::TFT::prove_amortized)
+    pub batching: bool,
+    /// Number of independent t-FT proofs, each with its own challenges, in a proof of
+    /// [`ProverContext::prove_tft`](crate::builder::ProverContext::prove_tft)
+    pub repetitions: usize,
+    /// Number of threads of the global rayon pool the provers run on, or its default size for `None`. Only with the
+    /// `parallel` feature, and not part of the transcript since it does not change the proofs.
+    pub parallelism: Option<usize>,
+    /// Check that the oracles and commitments handed to the provers and verifiers carry labels and degree bounds fit
+    /// for the protocols, as the `strict-labels` feature does within each ZeroOverK sub-proof
+    pub strict_labels: bool,
+}
+
+impl Default for ProtocolOptions {
+    fn default() -> Self {
+        Self {
+            hiding: true,
+            batching: false,
+            repetitions: 1,
+            parallelism: None,
+            strict_labels: false,
+        }
+    }
+}
+
+impl ProtocolOptions {
+    /// The options as absorbed into the transcript
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_bytes![
+            self.hiding,
+            self.batching,
+            self.repetitions as u64,
+            self.strict_labels
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+
+    /// Build the global rayon pool the provers run on with [`Self::parallelism`] threads. The pool is built once per
+    /// process, so this fails if it already runs on another number of threads.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn configure_thread_pool(&self) -> Result<(), Error> {
+        let threads = match self.parallelism {
+            Some(threads) => threads,
+            None => return Ok(()),
+        };
+        if rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .is_err()
+            && rayon::current_num_threads() != threads
+        {
+            return Err(Error::InvalidOption(format!(
+                "The thread pool already runs on {} threads",
+                rayon::current_num_threads()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Set the option named `key` from its string value, e.g. `set("repetitions", "2")`. `parallelism` takes a
+    /// number of threads or `auto`.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let invalid = || Error::InvalidOption(format!("Invalid value {} for {}", value, key));
+        let flag = || value.parse::<bool>().map_err(|_| invalid());
+
+        match key {
+            "hiding" => self.hiding = flag()?,
+            "batching" => self.batching = flag()?,
+            "strict_labels" => self.strict_labels = flag()?,
+            "repetitions" => match value.parse::<usize>() {
+                Ok(repetitions) if repetitions > 0 => self.repetitions = repetitions,
+                _ => return Err(invalid()),
+            },
+            "parallelism" => {
+                self.parallelism = match value {
+                    "auto" => None,
+                    threads => Some(threads.parse::<usize>().map_err(|_| invalid())?),
+                }
+            }
+            _ => return Err(Error::InvalidOption(format!("Unknown option {}", key))),
+        }
+        Ok(())
+    }
+}
+
+/// Comma-separated `key=value` pairs, see [`ProtocolOptions::set`], with the defaults for the options not listed
+impl FromStr for ProtocolOptions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut options = Self::default();
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| Error::InvalidOption(format!("Expected key=value, got {}", pair)))?;
+            options.set(key.trim(), value.trim())?;
+        }
+        Ok(options)
+    }
+}
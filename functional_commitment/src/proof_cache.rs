@@ -0,0 +1,182 @@
+use blake2::{Blake2s, Digest};
+use std::collections::BTreeMap;
+
+/// The key of a proof: a digest of the protocol name, the statement and the commitments to the witness
+pub type ProofKey = [u8; 32];
+
+struct Entry {
+    proof: Vec<u8>,
+    last_used: u64,
+}
+
+/// Counters of a [`ProofCache`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProofCacheStats {
+    /// Number of proofs served from the cache
+    pub hits: usize,
+    /// Number of proofs computed
+    pub misses: usize,
+    /// Number of proofs evicted to stay within the capacity
+    pub evictions: usize,
+    /// Number of proofs currently cached
+    pub len: usize,
+}
+
+/// Remembers the serialized proofs made within a process, so that proving the same statement about the same
+/// committed witness again returns the earlier proof instead of running the prover. This suits services that re-prove
+/// identical statements under load.
+///
+/// A cached proof is a valid proof of the statement, but it is the same proof every time: a verifier that sees two
+/// proofs of a statement learns that they come from the same cache. Once `capacity` proofs are cached, the least
+/// recently used one is evicted.
+pub struct ProofCache {
+    capacity: usize,
+    entries: BTreeMap<ProofKey, Entry>,
+    clock: u64,
+    hits: usize,
+    misses: usize,
+    evictions: usize,
+}
+
+impl ProofCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: BTreeMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    /// The key of the proof of a protocol about a statement, both given as bytes
+    pub fn key(protocol_name: &[u8], statement: &[u8]) -> ProofKey {
+        let mut hasher = Blake2s::new();
+        hasher.update((protocol_name.len() as u64).to_le_bytes());
+        hasher.update(protocol_name);
+        hasher.update(statement);
+        hasher.finalize().into()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity, evicting the least recently used proofs that no longer fit
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > self.capacity {
+            self.evict_least_recently_used();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> ProofCacheStats {
+        ProofCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            len: self.entries.len(),
+        }
+    }
+
+    /// Forget every proof. The counters are kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The cached proof under `key`, counted as a hit, or `None`, counted as a miss
+    pub fn get(&mut self, key: &ProofKey) -> Option<Vec<u8>> {
+        self.clock += 1;
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = self.clock;
+                self.hits += 1;
+                Some(entry.proof.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Cache a proof under `key`, evicting the least recently used proof if the cache is full
+    pub fn insert(&mut self, key: ProofKey, proof: Vec<u8>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+        self.entries.insert(
+            key,
+            Entry {
+                proof,
+                last_used: self.clock,
+            },
+        );
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        if let Some(key) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| *key)
+        {
+            self.entries.remove(&key);
+            self.evictions += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::proof_cache::{ProofCache, ProofCacheStats};
+
+    #[test]
+    fn test_proof_cache() {
+        let key = |statement: &[u8]| ProofCache::key(b"protocol", statement);
+        assert_ne!(ProofCache::key(b"a", b"bc"), ProofCache::key(b"ab", b"c"));
+
+        let mut cache = ProofCache::new(2);
+        assert_eq!(cache.get(&key(b"1")), None);
+        cache.insert(key(b"1"), vec![1]);
+        cache.insert(key(b"2"), vec![2]);
+        assert_eq!(cache.get(&key(b"1")), Some(vec![1]));
+
+        // the least recently used proof is evicted first
+        cache.insert(key(b"3"), vec![3]);
+        assert_eq!(cache.get(&key(b"2")), None);
+        assert_eq!(cache.get(&key(b"1")), Some(vec![1]));
+        assert_eq!(
+            cache.stats(),
+            ProofCacheStats {
+                hits: 2,
+                misses: 2,
+                evictions: 1,
+                len: 2,
+            }
+        );
+
+        cache.set_capacity(1);
+        assert_eq!(cache.get(&key(b"3")), None);
+        cache.clear();
+        assert!(cache.is_empty());
+
+        // nothing is cached without capacity
+        cache.set_capacity(0);
+        cache.insert(key(b"1"), vec![1]);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,265 @@
+//! Properties of the committed function, beyond those built into the builder, that downstream crates can prove.
+//!
+//! A property implements `FunctionProperty` and is registered once in a `PropertyRegistry` handed to the builder.
+//! The builder then trims its keys to the degree bounds the property needs, and both contexts run it under a child
+//! of the index context, so that its transcript is bound to the same index as the built-in proofs.
+
+use crate::builder::Index;
+use ark_ff::SquareRootField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    any::Any,
+    rand::{Rng, RngCore},
+};
+use fiat_shamir_rng::Context;
+use proof_of_function_relation::error::Error;
+use std::collections::BTreeMap;
+use zero_over_k::config::{pc, ProtocolConfig};
+
+/// A property of the committed function, together with its proof system
+pub trait FunctionProperty<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    /// Unique name of the property, which also labels its transcript
+    const NAME: &'static str;
+
+    /// Public data the property is checked against, on top of the index
+    type Statement: 'static;
+
+    /// Data only known to the prover
+    type Witness: 'static;
+
+    type Proof: CanonicalSerialize + CanonicalDeserialize;
+
+    /// Degree bounds the keys must support for this property, on top of those enforced by every index
+    fn degree_bounds(&self, index: &Index<C::F>) -> Vec<usize>;
+
+    fn prove<R: Rng>(
+        &self,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Self::Proof, Error>;
+
+    fn verify(
+        &self,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &Self::Statement,
+        proof: Self::Proof,
+        context: &Context,
+    ) -> Result<(), Error>;
+}
+
+/// The proof of a registered property, tagged with the name of the property
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyProof {
+    pub name: String,
+    pub proof: Vec<u8>,
+}
+
+/// `FunctionProperty` with its types erased, so that properties of different types can share a registry
+trait ErasedProperty<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    fn degree_bounds(&self, index: &Index<C::F>) -> Vec<usize>;
+
+    fn prove(
+        &self,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &dyn Any,
+        witness: &dyn Any,
+        context: &Context,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u8>, Error>;
+
+    fn verify(
+        &self,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &dyn Any,
+        proof: &[u8],
+        context: &Context,
+    ) -> Result<(), Error>;
+}
+
+impl<C: ProtocolConfig, P> ErasedProperty<C> for P
+where
+    C::F: SquareRootField,
+    P: FunctionProperty<C>,
+{
+    fn degree_bounds(&self, index: &Index<C::F>) -> Vec<usize> {
+        FunctionProperty::degree_bounds(self, index)
+    }
+
+    fn prove(
+        &self,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &dyn Any,
+        witness: &dyn Any,
+        context: &Context,
+        mut rng: &mut dyn RngCore,
+    ) -> Result<Vec<u8>, Error> {
+        let statement = downcast::<P::Statement>(P::NAME, "statement", statement)?;
+        let witness = downcast::<P::Witness>(P::NAME, "witness", witness)?;
+        let proof =
+            FunctionProperty::prove(self, ck, index, statement, witness, context, &mut rng)?;
+
+        let mut bytes = Vec::new();
+        proof
+            .serialize(&mut bytes)
+            .map_err(|_| Error::ProofSerializationError)?;
+        Ok(bytes)
+    }
+
+    fn verify(
+        &self,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &dyn Any,
+        proof: &[u8],
+        context: &Context,
+    ) -> Result<(), Error> {
+        let statement = downcast::<P::Statement>(P::NAME, "statement", statement)?;
+        let proof = P::Proof::deserialize(proof).map_err(|_| Error::ProofDeserializationError)?;
+        FunctionProperty::verify(self, vk, ck, index, statement, proof, context)
+    }
+}
+
+fn downcast<'a, T: 'static>(name: &str, what: &str, value: &'a dyn Any) -> Result<&'a T, Error> {
+    value.downcast_ref::<T>().ok_or_else(|| {
+        Error::InvalidProperty(format!(
+            "The {} given to property {} is not of type {}",
+            what,
+            name,
+            ark_std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// The properties available to the prover and verifier of an index, by name
+pub struct PropertyRegistry<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    properties: BTreeMap<&'static str, Box<dyn ErasedProperty<C>>>,
+}
+
+impl<C: ProtocolConfig> Default for PropertyRegistry<C>
+where
+    C::F: SquareRootField,
+{
+    fn default() -> Self {
+        Self {
+            properties: BTreeMap::new(),
+        }
+    }
+}
+
+impl<C: ProtocolConfig> PropertyRegistry<C>
+where
+    C::F: SquareRootField,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a property, failing if another one is registered under the same name
+    pub fn register<P: FunctionProperty<C> + 'static>(
+        mut self,
+        property: P,
+    ) -> Result<Self, Error> {
+        if self.properties.contains_key(P::NAME) {
+            return Err(Error::InvalidProperty(format!(
+                "A property named {} is already registered",
+                P::NAME
+            )));
+        }
+        self.properties.insert(P::NAME, Box::new(property));
+        Ok(self)
+    }
+
+    /// Names of the registered properties, in lexicographic order
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.properties.keys().copied()
+    }
+
+    /// Degree bounds required by all the registered properties
+    pub(crate) fn degree_bounds(&self, index: &Index<C::F>) -> Vec<usize> {
+        self.properties
+            .values()
+            .flat_map(|property| property.degree_bounds(index))
+            .collect()
+    }
+
+    /// Every property runs under its own child of the index context
+    fn context(index: &Index<C::F>, name: &str) -> Context {
+        index.context.child(b"property").child(name.as_bytes())
+    }
+
+    fn get(&self, name: &str) -> Result<&dyn ErasedProperty<C>, Error> {
+        self.properties
+            .get(name)
+            .map(|property| property.as_ref())
+            .ok_or_else(|| Error::InvalidProperty(format!("No property named {}", name)))
+    }
+
+    pub(crate) fn prove<P: FunctionProperty<C>, R: Rng>(
+        &self,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &P::Statement,
+        witness: &P::Witness,
+        rng: &mut R,
+    ) -> Result<PropertyProof, Error> {
+        let proof = self.get(P::NAME)?.prove(
+            ck,
+            index,
+            statement,
+            witness,
+            &Self::context(index, P::NAME),
+            rng,
+        )?;
+
+        Ok(PropertyProof {
+            name: String::from(P::NAME),
+            proof,
+        })
+    }
+
+    pub(crate) fn verify<P: FunctionProperty<C>>(
+        &self,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        index: &Index<C::F>,
+        statement: &P::Statement,
+        proof: &PropertyProof,
+    ) -> Result<(), Error> {
+        if proof.name != P::NAME {
+            return Err(Error::InvalidProperty(format!(
+                "Got a proof of property {} but expected one of {}",
+                proof.name,
+                P::NAME
+            )));
+        }
+
+        self.get(P::NAME)?.verify(
+            vk,
+            ck,
+            index,
+            statement,
+            &proof.proof,
+            &Self::context(index, P::NAME),
+        )
+    }
+}
@@ -2,32 +2,58 @@
 mod tests {
     use ac_compiler::constraint_builder::ConstraintBuilder;
     use ac_compiler::error::Error;
-    use ac_compiler::gate::GateType;
+    use ac_compiler::gate::{Gate, GateType};
     use ac_compiler::variable::VariableType;
+    use ac_compiler::R1CSfIndex;
     use ac_compiler::{circuit::Circuit, variable::Variable};
     use ark_bn254::{Bn254, Fr};
     use ark_ff::bytes::ToBytes;
     use ark_ff::PrimeField;
     use ark_ff::{to_bytes, Field, One};
-    use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+    use ark_poly::{univariate::DensePolynomial, EvaluationDomain, UVPolynomial};
     use ark_poly_commit::LabeledCommitment;
+    use ark_poly_commit::{PCCommitterKey, PolynomialCommitment};
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::rand::Rng;
     use ark_std::test_rng;
     use blake2::Blake2s;
-    use fiat_shamir_rng::{FiatShamirRng, SimpleHashFiatShamirRng};
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
     use homomorphic_poly_commit::marlin_kzg::KZG10;
-    use index_private_marlin::Marlin;
-    use proof_of_function_relation::t_functional_triple::TFT;
+    use index_private_marlin::{ahp::AHPForR1CS, Error as MarlinError, Marlin};
+    use proof_of_function_relation::{
+        checked_key::CheckedCommitterKey,
+        domains::{DomainH, DomainK},
+        equal_on_subdomain::{proof::Proof as EqualOnSubdomainProof, EqualOnSubdomain},
+        error::Error as PoFRError,
+        pre_verified::PreVerified,
+        report::VerificationReport,
+        t_functional_triple::TFT,
+    };
     use rand_chacha::ChaChaRng;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use zero_over_k::cancellation::CancellationToken;
+    use zero_over_k::config::Config;
 
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
     use ac_compiler::circuit_compiler::{CircuitCompiler, VanillaCompiler};
 
-    use crate::{diag_test, slt_test};
+    use crate::{
+        builder::{CommittedOracle, FunctionalCommitment, Index, Matrices, SltMatrix},
+        composition::Embedding,
+        diag_test,
+        options::ProtocolOptions,
+        property::{FunctionProperty, PropertyProof, PropertyRegistry},
+        slt_test,
+    };
 
     type F = Fr;
     type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
 
-    type MarlinInst = Marlin<Fr, PC, FS>;
+    type MarlinInst = Marlin<C>;
 
     pub fn build_mux1_circuit<F: Field>(
         cb: &mut ConstraintBuilder<F>,
@@ -140,9 +166,8 @@ mod tests {
 
         assert_eq!(true, index_info.check_domains_sizes::<F>());
 
-        let domain_k =
-            GeneralEvaluationDomain::<F>::new(index_info.number_of_non_zero_entries).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(index_info.number_of_constraints).unwrap();
+        let domain_k = DomainK::<F>::new(index_info.number_of_non_zero_entries).unwrap();
+        let domain_h = DomainH::<F>::new(index_info.number_of_constraints).unwrap();
 
         slt_test!(a, index_info.number_of_input_rows);
         slt_test!(b, index_info.number_of_input_rows);
@@ -155,9 +180,18 @@ mod tests {
         let (pk, vk) = MarlinInst::index(&universal_srs, &index_info, a, b, c, rng).unwrap();
 
         // TEST MARLIN
-        let proof = MarlinInst::prove(&pk, cb.assignment, rng).unwrap();
+        let proof = MarlinInst::prove(&pk, cb.assignment, &Context::new(b"test"), rng).unwrap();
 
-        assert!(MarlinInst::verify(&vk, inputs, outputs, proof, rng, &pk.committer_key).unwrap());
+        assert!(MarlinInst::verify(
+            &vk,
+            inputs,
+            outputs,
+            proof,
+            &Context::new(b"test"),
+            rng,
+            &pk.committer_key
+        )
+        .unwrap());
 
         // TEST PROOF OF FUNCTION
         let labels = vec![
@@ -175,8 +209,8 @@ mod tests {
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
         //JUST REVERSE ROW A AND COL A TO GET STRICTLY UPPER TRIANGULAR
-        let tft_proof = TFT::<F, PC, FS>::prove(
-            &pk.committer_key,
+        let tft_proof = TFT::<C>::prove(
+            &CheckedCommitterKey::new(&pk.committer_key, Some(domain_k.size() + 1)).unwrap(),
             index_info.number_of_input_rows,
             &domain_k,
             &domain_h,
@@ -202,14 +236,28 @@ mod tests {
             &pk.rands[6],              // row_c_random,
             &pk.rands[7],              // col_c_random,
             &pk.rands[8],              // val_c_random,
+            &Context::new(b"test"),    // context,
             &mut fs_rng,               // fs_rng,
             rng,                       // rng,
         )
         .unwrap();
 
+        let tft_proof_size = tft_proof.len();
+        let supported_degree = pk.committer_key.supported_degree();
+        assert_eq!(
+            TFT::<C>::proof_size(
+                index_info.number_of_input_rows,
+                &domain_k,
+                &domain_h,
+                Some(domain_k.size() + 1),
+                supported_degree,
+            )
+            .unwrap(),
+            tft_proof_size
+        );
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let is_valid = TFT::<F, PC, FS>::verify(
+        let is_valid = TFT::<C>::verify(
             &vk.verifier_key,
             &pk.committer_key,
             index_info.number_of_input_rows,
@@ -223,11 +271,192 @@ mod tests {
             Some(domain_k.size() + 1),
             &domain_h,
             &domain_k,
-            tft_proof,
+            tft_proof.clone(),
+            &Context::new(b"test"),
             &mut fs_rng,
         );
 
         assert!(is_valid.is_ok());
+
+        // the proof does not verify against a same-shaped statement with the commitments of A and B swapped
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        let report = TFT::<C>::verify_with_report(
+            &vk.verifier_key,
+            &pk.committer_key,
+            index_info.number_of_input_rows,
+            &commits[4],
+            &commits[3],
+            &commits[1],
+            &commits[0],
+            &commits[6],
+            &commits[7],
+            &commits[8],
+            Some(domain_k.size() + 1),
+            &domain_h,
+            &domain_k,
+            tft_proof.clone(),
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+
+        assert!(report.failures().contains(&(
+            String::from("t_functional_triple/statement_digest"),
+            PoFRError::StatementMismatch
+        )));
+
+        // the t-SLT test on A and the t-Diag test on C were verified elsewhere: their checks are skipped, and the
+        // transcript of the test on A is replayed so that the test on B still verifies
+        let digests = TFT::<C>::sub_proof_digests(&tft_proof, &Context::new(b"test")).unwrap();
+        let pre_verified = vec![digests[0].clone(), digests[2].clone()]
+            .into_iter()
+            .collect::<PreVerified>();
+        let skipped = |report: &VerificationReport| {
+            report
+                .sub_reports
+                .iter()
+                .filter(|sub_report| {
+                    sub_report
+                        .sub_reports
+                        .iter()
+                        .any(|check| check.name == "pre_verified")
+                })
+                .map(|sub_report| sub_report.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        let report = TFT::<C>::verify_with_pre_verified(
+            &vk.verifier_key,
+            &pk.committer_key,
+            index_info.number_of_input_rows,
+            &commits[1],
+            &commits[0],
+            &commits[4],
+            &commits[3],
+            &commits[6],
+            &commits[7],
+            &commits[8],
+            Some(domain_k.size() + 1),
+            &domain_h,
+            &domain_k,
+            tft_proof.clone(),
+            &pre_verified,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+
+        assert!(report.is_ok());
+        assert_eq!(
+            skipped(&report),
+            vec![
+                String::from("t_strictly_lower_triangular_test"),
+                String::from("t_diag")
+            ]
+        );
+
+        // digests computed under another context do not match, so every sub-proof is checked
+        let pre_verified = TFT::<C>::sub_proof_digests(&tft_proof, &Context::new(b"other"))
+            .unwrap()
+            .into_iter()
+            .collect::<PreVerified>();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        let report = TFT::<C>::verify_with_pre_verified(
+            &vk.verifier_key,
+            &pk.committer_key,
+            index_info.number_of_input_rows,
+            &commits[1],
+            &commits[0],
+            &commits[4],
+            &commits[3],
+            &commits[6],
+            &commits[7],
+            &commits[8],
+            Some(domain_k.size() + 1),
+            &domain_h,
+            &domain_k,
+            tft_proof,
+            &pre_verified,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+
+        assert!(report.is_ok());
+        assert!(skipped(&report).is_empty());
+
+        // the t-SLT tests on A and B share a single h and geometric sequence test
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        let amortized_proof = TFT::<C>::prove_amortized(
+            &CheckedCommitterKey::new(&pk.committer_key, Some(domain_k.size() + 1)).unwrap(),
+            index_info.number_of_input_rows,
+            &domain_k,
+            &domain_h,
+            Some(domain_k.size() + 1),
+            &pk.index.a_arith.col,
+            &pk.index.a_arith.row,
+            &commits[1],
+            &commits[0],
+            &pk.rands[1],
+            &pk.rands[0],
+            &pk.index.b_arith.col,
+            &pk.index.b_arith.row,
+            &commits[4],
+            &commits[3],
+            &pk.rands[4],
+            &pk.rands[3],
+            &pk.index.c_arith.row,
+            &pk.index.c_arith.col,
+            &pk.index.c_arith.val,
+            &commits[6],
+            &commits[7],
+            &commits[8],
+            &pk.rands[6],
+            &pk.rands[7],
+            &pk.rands[8],
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )
+        .unwrap();
+        assert!(amortized_proof.len() < tft_proof_size);
+        assert_eq!(
+            TFT::<C>::amortized_proof_size(
+                index_info.number_of_input_rows,
+                &domain_k,
+                &domain_h,
+                Some(domain_k.size() + 1),
+                supported_degree,
+            )
+            .unwrap(),
+            amortized_proof.len()
+        );
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        let report = TFT::<C>::verify_amortized(
+            &vk.verifier_key,
+            &pk.committer_key,
+            index_info.number_of_input_rows,
+            &commits[1],
+            &commits[0],
+            &commits[4],
+            &commits[3],
+            &commits[6],
+            &commits[7],
+            &commits[8],
+            Some(domain_k.size() + 1),
+            &domain_h,
+            &domain_k,
+            amortized_proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+
+        assert!(report.is_ok());
     }
 
     #[test]
@@ -375,4 +604,597 @@ mod tests {
         };
         circuit_test_template(constraints, &inputs, &outputs);
     }
+
+    #[test]
+    fn test_builder() {
+        let rng = &mut test_rng();
+
+        let x_val = F::from(2u64);
+        let mut cb = ConstraintBuilder::<F>::new();
+        let circuit = Circuit::synthesize(|cb| build_x4_circuit::<Fr>(cb, x_val), &mut cb).unwrap();
+
+        let (prover, verifier) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index(Matrices::from_circuit(&circuit))
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+
+        let proof = prover.prove_tft(rng).unwrap();
+        assert_eq!(verifier.verify_tft(proof), Ok(()));
+
+        let proof = prover.prove_t_slt(SltMatrix::B, rng).unwrap();
+        assert_eq!(verifier.verify_t_slt(SltMatrix::B, proof), Ok(()));
+
+        // a proof about B is not a proof about A
+        let proof = prover.prove_t_slt(SltMatrix::B, rng).unwrap();
+        assert!(verifier.verify_t_slt(SltMatrix::A, proof).is_err());
+
+        let proof = prover.prove_t_diag(rng).unwrap();
+        assert_eq!(verifier.verify_t_diag(proof), Ok(()));
+
+        // f = omega^(1 + i mod (|H| - 1)) and g = 1 over K
+        let domain_k = prover.index().domain_k;
+        let domain_h = prover.index().domain_h;
+        let to_poly = |exponents: Vec<usize>| {
+            let evals = exponents
+                .into_iter()
+                .map(|e| domain_h.element(e))
+                .collect::<Vec<_>>();
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&evals))
+        };
+        let f = to_poly(
+            (0..domain_k.size())
+                .map(|i| 1 + i % (domain_h.size() - 1))
+                .collect(),
+        );
+        let g = to_poly(vec![0; domain_k.size()]);
+
+        let f = prover.commit("f", f, rng).unwrap();
+        let g = prover.commit("g", g, rng).unwrap();
+        let proof = prover.prove_dl_comparison(&f, &g, rng).unwrap();
+        assert_eq!(
+            verifier.verify_dl_comparison(&f.commitment, &g.commitment, proof),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_marlin_over_committed_index() {
+        let rng = &mut test_rng();
+
+        let x_val = F::from(2u64);
+        let mut cb = ConstraintBuilder::<F>::new();
+        let circuit = Circuit::synthesize(|cb| build_x4_circuit::<Fr>(cb, x_val), &mut cb).unwrap();
+        let (index_info, a, b, c) = VanillaCompiler::<F>::ac2tft(&circuit);
+        let matrices = Matrices::from((index_info.clone(), a, b, c));
+
+        let (_, domain_k) = matrices.domains().unwrap();
+        let max_degree = AHPForR1CS::<F>::max_degree(&R1CSfIndex {
+            number_of_non_zero_entries: domain_k.size(),
+            ..index_info.clone()
+        })
+        .unwrap();
+
+        let (prover, verifier) = FunctionalCommitment::<C>::setup(max_degree, rng)
+            .unwrap()
+            .index(matrices.clone())
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+
+        let (pk, _) = prover.marlin_keys(&index_info, &matrices).unwrap();
+        let vk = verifier.marlin_verifier_key(&index_info).unwrap();
+
+        // Marlin commits to nothing new: its index commitments are those of the statement
+        let statement = verifier.statement();
+        assert_eq!(vk.commits, pk.vk.commits);
+        for commitment in &vk.commits {
+            assert!(statement
+                .commitments
+                .iter()
+                .any(|cm| cm.commitment() == commitment));
+        }
+
+        let inputs = vec![F::one(), F::one(), x_val];
+        let proof = prover
+            .prove_evaluation(&pk, cb.assignment.clone(), rng)
+            .unwrap();
+        assert!(verifier
+            .verify_evaluation(&vk, &inputs, &[F::from(16u64)], proof, rng)
+            .unwrap());
+
+        // the proofs of function relation refer to the same commitments
+        let proof = prover.prove_tft(rng).unwrap();
+        assert_eq!(verifier.verify_tft(proof), Ok(()));
+
+        let proof = prover.prove_evaluation(&pk, cb.assignment, rng).unwrap();
+        assert!(!matches!(
+            verifier.verify_evaluation(&vk, &inputs, &[F::from(15u64)], proof, rng),
+            Ok(true)
+        ));
+
+        // a circuit with another number of inputs was not compiled to this index
+        let other_info = R1CSfIndex {
+            number_of_input_rows: index_info.number_of_input_rows + 1,
+            ..index_info
+        };
+        assert!(matches!(
+            verifier.marlin_verifier_key(&other_info),
+            Err(MarlinError::IndexMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_padded_circuit() {
+        let rng = &mut test_rng();
+        // 1 + 2 inputs + 2 gates = 5 constraints, padded to |H| = 8
+        let circuit = composition_f();
+        let matrices = Matrices::<F>::padded_from_circuit(&circuit).unwrap();
+        assert_eq!(matrices.t, 3);
+
+        let (prover, verifier) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index(matrices)
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+        assert_eq!(prover.index().domain_h.size(), 8);
+
+        let proof = prover.prove_t_slt(SltMatrix::A, rng).unwrap();
+        assert_eq!(verifier.verify_t_slt(SltMatrix::A, proof), Ok(()));
+        let proof = prover.prove_t_diag(rng).unwrap();
+        assert_eq!(verifier.verify_t_diag(proof), Ok(()));
+        let proof = prover.prove_tft(rng).unwrap();
+        assert_eq!(verifier.verify_tft(proof), Ok(()));
+
+        // without padding C is not diagonal on the last rows of H
+        let (prover, _) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index(Matrices::from_circuit(&circuit))
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+        assert!(prover.prove_t_diag(rng).is_err());
+    }
+
+    #[test]
+    fn test_commit_cache() {
+        let rng = &mut test_rng();
+        let (prover, _) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .with_options("hiding=false".parse().unwrap())
+            .index(Matrices::from_circuit(&composition_f()))
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+        assert_eq!(prover.commit_cache_stats(), None);
+
+        let prover = prover.with_commit_cache(4);
+        let one = DensePolynomial::from_coefficients_vec(vec![F::one()]);
+        let first = prover.commit("one", one.clone(), rng).unwrap();
+        let second = prover.commit("other_one", one, rng).unwrap();
+        assert_eq!(prover.commit_cache_stats(), Some((1, 1)));
+        assert_eq!(
+            first.commitment.commitment(),
+            second.commitment.commitment()
+        );
+        assert_eq!(second.commitment.label(), "other_one");
+
+        // hiding commitments are never reused
+        let (prover, _) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index(Matrices::from_circuit(&composition_f()))
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+        let prover = prover.with_commit_cache(4);
+        let one = DensePolynomial::from_coefficients_vec(vec![F::one()]);
+        let first = prover.commit("one", one.clone(), rng).unwrap();
+        let second = prover.commit("one", one, rng).unwrap();
+        assert_eq!(prover.commit_cache_stats(), Some((0, 2)));
+        assert_ne!(
+            first.commitment.commitment(),
+            second.commitment.commitment()
+        );
+    }
+
+    #[test]
+    fn test_proof_cache() {
+        let rng = &mut test_rng();
+        let (prover, verifier) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index(Matrices::padded_from_circuit(&composition_f()).unwrap())
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+        assert_eq!(prover.proof_cache_stats(), None);
+
+        let prover = prover.with_proof_cache(2);
+        let proof = prover.prove_tft(rng).unwrap();
+        assert_eq!(prover.prove_tft(rng).unwrap(), proof);
+        assert_eq!(verifier.verify_tft(proof), Ok(()));
+
+        // the t-SLT proofs of A and B are cached apart, evicting the t-FT proof
+        let a_proof = prover.prove_t_slt(SltMatrix::A, rng).unwrap();
+        prover.prove_t_slt(SltMatrix::B, rng).unwrap();
+        assert_eq!(prover.prove_t_slt(SltMatrix::A, rng).unwrap(), a_proof);
+        assert_eq!(verifier.verify_t_slt(SltMatrix::A, a_proof), Ok(()));
+
+        let stats = prover.proof_cache_stats().unwrap();
+        assert_eq!(
+            (stats.hits, stats.misses, stats.evictions, stats.len),
+            (2, 3, 1, 2)
+        );
+    }
+
+    #[test]
+    fn test_deterministic_proofs() {
+        // the same seeds give the same bytes, whether or not the openings of the sub-proofs are batched
+        for options in ["batching=false", "batching=true"] {
+            let prove = || {
+                let rng = &mut test_rng();
+                let (prover, verifier) = FunctionalCommitment::<C>::setup(64, rng)
+                    .unwrap()
+                    .with_options(options.parse().unwrap())
+                    .index(Matrices::padded_from_circuit(&composition_f()).unwrap())
+                    .unwrap()
+                    .commit(rng)
+                    .unwrap();
+
+                let tft_proof = prover.prove_tft(rng).unwrap();
+                let slt_proof = prover.prove_t_slt(SltMatrix::A, rng).unwrap();
+                let diag_proof = prover.prove_t_diag(rng).unwrap();
+                let mut bytes = Vec::new();
+                tft_proof.serialize(&mut bytes).unwrap();
+                slt_proof.serialize(&mut bytes).unwrap();
+                diag_proof.serialize(&mut bytes).unwrap();
+
+                assert_eq!(verifier.verify_tft(tft_proof), Ok(()));
+                assert_eq!(verifier.verify_t_slt(SltMatrix::A, slt_proof), Ok(()));
+                assert_eq!(verifier.verify_t_diag(diag_proof), Ok(()));
+                bytes
+            };
+            assert_eq!(prove(), prove(), "{}", options);
+        }
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let rng = &mut test_rng();
+        let (prover, _) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index(Matrices::padded_from_circuit(&composition_f()).unwrap())
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+
+        // the token fires at the fifth checkpoint, and the prover stops there rather than at the end of the proof
+        let checkpoints = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+        let token = token.clone().with_progress({
+            let checkpoints = checkpoints.clone();
+            move |_| {
+                if checkpoints.fetch_add(1, Ordering::SeqCst) + 1 == 5 {
+                    token.cancel();
+                }
+            }
+        });
+
+        let prover = prover.with_cancellation(token);
+        assert!(matches!(prover.prove_tft(rng), Err(PoFRError::Aborted(_))));
+        assert_eq!(checkpoints.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_protocol_options() {
+        assert_eq!(
+            "repetitions=3, parallelism=auto".parse::<ProtocolOptions>(),
+            Ok(ProtocolOptions {
+                repetitions: 3,
+                ..ProtocolOptions::default()
+            })
+        );
+        assert!(matches!(
+            "hiding=maybe".parse::<ProtocolOptions>(),
+            Err(PoFRError::InvalidOption(_))
+        ));
+        assert!(matches!(
+            "repetitions=0".parse::<ProtocolOptions>(),
+            Err(PoFRError::InvalidOption(_))
+        ));
+        assert!(matches!(
+            "masking=true".parse::<ProtocolOptions>(),
+            Err(PoFRError::InvalidOption(_))
+        ));
+
+        // without hiding, the same setup and index give the same commitments whatever the other options
+        let setup = |options: &str| {
+            let rng = &mut test_rng();
+            FunctionalCommitment::<C>::setup(64, rng)
+                .unwrap()
+                .with_options(options.parse().unwrap())
+                .index(Matrices::padded_from_circuit(&composition_f()).unwrap())
+                .unwrap()
+                .commit(rng)
+                .unwrap()
+        };
+        let rng = &mut test_rng();
+        let (prover, verifier) = setup("hiding=false, batching=true, repetitions=2");
+        let proof = prover.prove_tft(rng).unwrap();
+        assert_eq!(verifier.verify_tft(proof.clone()), Ok(()));
+
+        // the options are part of the transcript
+        let (_, other_verifier) =
+            setup("hiding=false, batching=true, repetitions=2, strict_labels=true");
+        let commitments = |commitments: &[LabeledCommitment<
+            <PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment,
+        >]| {
+            commitments
+                .iter()
+                .map(|c| *c.commitment())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            commitments(verifier.commitments()),
+            commitments(other_verifier.commitments())
+        );
+        assert!(other_verifier.verify_tft(proof.clone()).is_err());
+
+        // and a proof with two repetitions is not one with a single one
+        let (_, other_verifier) = setup("hiding=false, batching=true");
+        assert!(other_verifier.verify_tft(proof).is_err());
+    }
+
+    // f: inputs x and y, outputs x * y and x + y
+    fn composition_f() -> Circuit {
+        Circuit::new(
+            vec![
+                Gate::new(1, 2, GateType::Mul),
+                Gate::new(1, 2, GateType::Add),
+            ],
+            2,
+            2,
+        )
+    }
+
+    // g: inputs a and b, output a * b + 1, or a + b + 1 with `gate` set to Add
+    fn composition_g(gate: GateType) -> Circuit {
+        Circuit::new(
+            vec![Gate::new(1, 2, gate), Gate::new(3, 0, GateType::Add)],
+            2,
+            1,
+        )
+    }
+
+    #[test]
+    fn test_composition() {
+        let rng = &mut test_rng();
+        let (f, g) = (composition_f(), composition_g(GateType::Mul));
+
+        let (prover, verifier) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index_composition(&f, &g)
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+        assert_eq!(verifier.embedding(), Embedding { offset: 2, rows: 5 });
+
+        let proof = prover.prove_composition(rng).unwrap();
+        assert_eq!(verifier.verify_composition(proof), Ok(()));
+
+        // the composite index can be used on its own
+        let proof = prover.composite.prove_t_slt(SltMatrix::B, rng).unwrap();
+        assert_eq!(verifier.composite.verify_t_slt(SltMatrix::B, proof), Ok(()));
+
+        assert!(matches!(
+            FunctionalCommitment::<C>::setup(64, rng)
+                .unwrap()
+                .index_composition(&g, &g),
+            Err(PoFRError::InvalidCircuit(_))
+        ));
+    }
+
+    #[test]
+    fn test_composition_with_other_function() {
+        let rng = &mut test_rng();
+        let f = composition_f();
+        let commit = |g: &Circuit, rng: &mut _| {
+            FunctionalCommitment::<C>::setup(64, rng)
+                .unwrap()
+                .index_composition(&f, g)
+                .unwrap()
+                .commit(rng)
+                .unwrap()
+        };
+
+        let (prover, mut verifier) = commit(&composition_g(GateType::Mul), rng);
+        let (_, other) = commit(&composition_g(GateType::Add), rng);
+
+        // g∘f is not consistent with the index of another g of the same shape
+        verifier.g = other.g;
+        let proof = prover.prove_composition(rng).unwrap();
+        assert!(verifier.verify_composition(proof).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        use crate::builder::Statement;
+        use proof_of_function_relation::{
+            t_diag::proof::Proof as TDiagProof,
+            t_strictly_lower_triangular_test::proof::Proof as TSLTProof,
+        };
+
+        let rng = &mut test_rng();
+
+        let mut cb = ConstraintBuilder::<F>::new();
+        let circuit =
+            Circuit::synthesize(|cb| build_x4_circuit::<Fr>(cb, F::from(2u64)), &mut cb).unwrap();
+        let (prover, verifier) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .index(Matrices::from_circuit(&circuit))
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+
+        // the statement survives the trip through JSON
+        let json = serde_json::to_string(&verifier.statement()).unwrap();
+        let statement: Statement<C> = serde_json::from_str(&json).unwrap();
+        let index = verifier.index();
+        assert_eq!(statement.index.t, index.t);
+        assert_eq!(statement.index.domain_k, index.domain_k);
+        assert_eq!(statement.index.domain_h, index.domain_h);
+        assert_eq!(
+            statement.index.enforced_degree_bound,
+            index.enforced_degree_bound
+        );
+        assert_eq!(statement.index.context, index.context);
+        let parts = |commitments: &[LabeledCommitment<_>]| {
+            commitments
+                .iter()
+                .map(|c| (c.label().clone(), *c.commitment(), c.degree_bound()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(parts(&statement.commitments), parts(verifier.commitments()));
+
+        // and so do proofs, which still verify afterwards
+        let proof = prover.prove_t_diag(rng).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let proof: TDiagProof<C> = serde_json::from_str(&json).unwrap();
+        assert_eq!(verifier.verify_t_diag(proof), Ok(()));
+
+        let proof = prover.prove_t_slt(SltMatrix::A, rng).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let proof: TSLTProof<C> = serde_json::from_str(&json).unwrap();
+        assert_eq!(verifier.verify_t_slt(SltMatrix::A, proof), Ok(()));
+    }
+
+    /// A downstream property: two committed oracles agree on all of K
+    struct EqualOnK;
+
+    impl FunctionProperty<C> for EqualOnK {
+        const NAME: &'static str = "equal_on_k";
+
+        type Statement =
+            [LabeledCommitment<<PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment>; 2];
+        type Witness = [CommittedOracle<C>; 2];
+        type Proof = EqualOnSubdomainProof<C>;
+
+        fn degree_bounds(&self, index: &Index<F>) -> Vec<usize> {
+            vec![index.enforced_degree_bound]
+        }
+
+        fn prove<R: Rng>(
+            &self,
+            ck: &<PC as PolynomialCommitment<F, DensePolynomial<F>>>::CommitterKey,
+            index: &Index<F>,
+            statement: &Self::Statement,
+            witness: &Self::Witness,
+            context: &Context,
+            rng: &mut R,
+        ) -> Result<Self::Proof, PoFRError> {
+            let [f, g] = witness;
+            EqualOnSubdomain::<C>::prove(
+                ck,
+                &index.domain_k,
+                &(0..index.domain_k.size()).collect::<Vec<_>>(),
+                &f.polynomial,
+                &statement[0],
+                &f.randomness,
+                &g.polynomial,
+                &statement[1],
+                &g.randomness,
+                Some(index.enforced_degree_bound),
+                context,
+                rng,
+            )
+        }
+
+        fn verify(
+            &self,
+            vk: &<PC as PolynomialCommitment<F, DensePolynomial<F>>>::VerifierKey,
+            ck: &<PC as PolynomialCommitment<F, DensePolynomial<F>>>::CommitterKey,
+            index: &Index<F>,
+            statement: &Self::Statement,
+            proof: Self::Proof,
+            context: &Context,
+        ) -> Result<(), PoFRError> {
+            EqualOnSubdomain::<C>::verify(
+                vk,
+                ck,
+                &index.domain_k,
+                &(0..index.domain_k.size()).collect::<Vec<_>>(),
+                &statement[0],
+                &statement[1],
+                Some(index.enforced_degree_bound),
+                proof,
+                context,
+            )
+        }
+    }
+
+    #[test]
+    fn test_function_property() {
+        let rng = &mut test_rng();
+
+        let registry = PropertyRegistry::new().register(EqualOnK).unwrap();
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["equal_on_k"]);
+        assert!(matches!(
+            PropertyRegistry::<C>::new()
+                .register(EqualOnK)
+                .unwrap()
+                .register(EqualOnK),
+            Err(PoFRError::InvalidProperty(_))
+        ));
+
+        let mut cb = ConstraintBuilder::<F>::new();
+        let circuit =
+            Circuit::synthesize(|cb| build_x4_circuit::<Fr>(cb, F::from(2u64)), &mut cb).unwrap();
+        let (prover, verifier) = FunctionalCommitment::<C>::setup(64, rng)
+            .unwrap()
+            .with_properties(registry)
+            .index(Matrices::from_circuit(&circuit))
+            .unwrap()
+            .commit(rng)
+            .unwrap();
+
+        let domain_k = prover.index().domain_k;
+        let evals = (0..domain_k.size())
+            .map(|i| F::from(i as u64))
+            .collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(domain_k.ifft(&evals));
+        let f = prover.commit("f", poly.clone(), rng).unwrap();
+        let g = prover.commit("g", poly.clone(), rng).unwrap();
+        let h = prover
+            .commit(
+                "h",
+                &poly + &DensePolynomial::from_coefficients_vec(vec![F::one()]),
+                rng,
+            )
+            .unwrap();
+
+        let statement = [f.commitment.clone(), g.commitment.clone()];
+        let proof = prover
+            .prove_property::<EqualOnK, _>(&statement, &[f, g], rng)
+            .unwrap();
+        assert_eq!(
+            verifier.verify_property::<EqualOnK>(&statement, &proof),
+            Ok(())
+        );
+
+        // the proof is bound to its statement and to its property
+        let other_statement = [statement[0].clone(), h.commitment.clone()];
+        assert!(verifier
+            .verify_property::<EqualOnK>(&other_statement, &proof)
+            .is_err());
+        let renamed = PropertyProof {
+            name: String::from("monotone_on_k"),
+            proof: proof.proof.clone(),
+        };
+        assert!(matches!(
+            verifier.verify_property::<EqualOnK>(&statement, &renamed),
+            Err(PoFRError::InvalidProperty(_))
+        ));
+    }
 }
@@ -0,0 +1,107 @@
+//! Peak memory of committing with a loaded committer key versus a memory-mapped one.
+//!
+//! Run with `cargo bench -p homomorphic_poly_commit --features mmap --bench mapped_key -- <log2 of degree>`. The
+//! benchmark writes a key both serialized and as a `MappedCommitterKey` file, then commits to the same polynomial in
+//! two child processes, one loading the former and one mapping the latter, so that each reports the resident set size
+//! high-water mark (VmHWM, only available on Linux) of that path alone. Pages of the mapped file count as resident
+//! while they are read, but the OS may drop them under memory pressure, unlike the loaded key, so the anonymous part
+//! of the resident set (RssAnon) is reported alongside.
+
+use std::{env, fs, path::PathBuf, process::Command, time::Instant};
+
+use ark_bn254::{Bn254, Fr};
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use ark_poly_commit::{marlin_pc, LabeledPolynomial, PolynomialCommitment};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::test_rng;
+use homomorphic_poly_commit::{mapped_key::MappedCommitterKey, marlin_kzg::KZG10};
+
+type F = Fr;
+type PC = KZG10<Bn254>;
+
+/// A field of /proc/self/status, in KiB
+fn status_kib(field: &str) -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find(|line| line.starts_with(field))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kib| kib.parse().ok())
+}
+
+fn key_path(mode: &str) -> PathBuf {
+    env::temp_dir().join(format!("mapped_key_bench.{}", mode))
+}
+
+/// Commit with the key written for `mode`, in this process
+fn commit(mode: &str, degree: usize) {
+    let polynomial = LabeledPolynomial::new(
+        String::from("p"),
+        DensePolynomial::<F>::rand(degree, &mut test_rng()),
+        None,
+        None,
+    );
+
+    let start = Instant::now();
+    let commitment = match mode {
+        "loaded" => {
+            let bytes = fs::read(key_path(mode)).unwrap();
+            let ck =
+                marlin_pc::CommitterKey::<Bn254>::deserialize_unchecked(bytes.as_slice()).unwrap();
+            drop(bytes);
+            *PC::commit(&ck, [&polynomial], None).unwrap().0[0].commitment()
+        }
+        _ => {
+            let ck = MappedCommitterKey::<Bn254>::open(key_path(mode)).unwrap();
+            *ck.commit([&polynomial], None).unwrap().0[0].commitment()
+        }
+    };
+    let elapsed = start.elapsed();
+
+    match (status_kib("VmHWM:"), status_kib("RssAnon:")) {
+        (Some(peak), Some(anon)) => println!(
+            "{} key: {:?}, peak RSS {} KiB, anonymous RSS {} KiB",
+            mode, elapsed, peak, anon
+        ),
+        _ => println!(
+            "{} key: {:?}, RSS unavailable on this platform",
+            mode, elapsed
+        ),
+    }
+    println!("{:?}", commitment);
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let log_size = args
+        .iter()
+        .find_map(|arg| arg.parse::<u32>().ok())
+        .unwrap_or(18);
+    let degree = (1usize << log_size) - 1;
+    if let Some(mode) = args.iter().find(|arg| *arg == "loaded" || *arg == "mapped") {
+        return commit(mode, degree);
+    }
+
+    let rng = &mut test_rng();
+    let pp = PC::setup(degree, None, rng).unwrap();
+    let (ck, _) = PC::trim(&pp, degree, 0, None).unwrap();
+    let mut bytes = Vec::new();
+    ck.serialize_unchecked(&mut bytes).unwrap();
+    fs::write(key_path("loaded"), bytes).unwrap();
+    MappedCommitterKey::write(&ck, key_path("mapped")).unwrap();
+    drop((pp, ck));
+
+    println!("degree 2^{} - 1", log_size);
+    let commitments = ["loaded", "mapped"].map(|mode| {
+        let output = Command::new(env::current_exe().unwrap())
+            .args([log_size.to_string(), String::from(mode)])
+            .output()
+            .unwrap();
+        let output = String::from_utf8(output.stdout).unwrap();
+        let (report, commitment) = output.trim_end().rsplit_once('\n').unwrap();
+        println!("{}", report);
+        fs::remove_file(key_path(mode)).unwrap();
+        commitment.to_string()
+    });
+    assert_eq!(commitments[0], commitments[1]);
+}
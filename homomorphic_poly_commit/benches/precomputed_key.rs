@@ -0,0 +1,74 @@
+//! Repeated commitments with a committer key versus one with fixed-base precomputation.
+//!
+//! Run with `cargo bench -p homomorphic_poly_commit --bench precomputed_key -- <log2 of degree> <window bits>...`. The
+//! benchmark commits to a batch of polynomials with `commit`, then precomputes the tables of the key for each number of
+//! window bits and commits to the same batch with `PrecomputedCommitterKey::commit`, checking that the commitments
+//! agree. The precomputation pays off once it is amortized over enough commitments.
+
+use std::{env, time::Instant};
+
+use ark_bn254::{Bn254, Fr};
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+use ark_std::test_rng;
+use homomorphic_poly_commit::{marlin_kzg::KZG10, precomputed_key::Precompute};
+
+type F = Fr;
+type PC = KZG10<Bn254>;
+
+/// Number of polynomials committed to under the same key
+const NUM_POLYNOMIALS: usize = 16;
+
+fn main() {
+    let mut args = env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse::<usize>().ok());
+    let log_size = args.next().unwrap_or(14);
+    let window_bits = match args.collect::<Vec<_>>() {
+        window_bits if window_bits.is_empty() => vec![8, 12, 16],
+        window_bits => window_bits,
+    };
+    let rng = &mut test_rng();
+    let n = 1usize << log_size;
+
+    let pp = PC::setup(n - 1, None, rng).unwrap();
+    let (ck, _) = PC::trim(&pp, n - 1, 0, None).unwrap();
+    let polynomials = (0..NUM_POLYNOMIALS)
+        .map(|i| {
+            LabeledPolynomial::new(
+                format!("p_{}", i),
+                DensePolynomial::<F>::rand(n - 1, rng),
+                None,
+                None,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    println!(
+        "{} polynomials of degree < 2^{} ({} coefficients)",
+        NUM_POLYNOMIALS, log_size, n
+    );
+    let start = Instant::now();
+    let (expected, _) = PC::commit(&ck, &polynomials, None).unwrap();
+    println!("committer key: {:?}", start.elapsed());
+
+    for window_bits in window_bits {
+        let start = Instant::now();
+        let precomputed = ck.clone().precompute(window_bits);
+        let precompute_time = start.elapsed();
+
+        let start = Instant::now();
+        let (commitments, _) = precomputed.commit(&polynomials, None).unwrap();
+        let commit_time = start.elapsed();
+
+        for (commitment, expected) in commitments.iter().zip(&expected) {
+            assert_eq!(commitment.commitment(), expected.commitment());
+        }
+        println!(
+            "{} window bits: {:?} to precompute, {:?} to commit",
+            precomputed.window_bits(),
+            precompute_time,
+            commit_time
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Commitments to sparse polynomials, through a dense MSM versus the sparse path.
+//!
+//! Run with `cargo bench -p homomorphic_poly_commit --bench sparse_commit -- <log2 of degree>`. The benchmark commits
+//! to polynomials of 1% and 0.1% density, as the coefficients of matrices with that many non-zero entries, first with
+//! `commit`, then with `commit_sparse` and `commit_with_density_heuristic`, and checks that all three agree.
+
+use std::{env, time::Instant};
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{UniformRand, Zero};
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+use ark_std::{rand::Rng, test_rng};
+use homomorphic_poly_commit::{marlin_kzg::KZG10, sparse::to_sparse, AdditivelyHomomorphicPCS};
+
+type F = Fr;
+type PC = KZG10<Bn254>;
+
+/// Densities in thousandths
+const DENSITIES: [usize; 2] = [10, 1];
+
+fn main() {
+    let log_size = env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<u32>().ok())
+        .unwrap_or(16);
+    let rng = &mut test_rng();
+    let n = 1usize << log_size;
+
+    let pp = PC::setup(n - 1, None, rng).unwrap();
+    let (ck, _) = PC::trim(&pp, n - 1, 0, None).unwrap();
+
+    println!("degree < 2^{} ({} coefficients)", log_size, n);
+    for density in DENSITIES {
+        let mut coeffs = vec![F::zero(); n];
+        for _ in 0..(n * density / 1000).max(1) {
+            coeffs[rng.gen_range(0..n)] = F::rand(rng);
+        }
+        let polynomial = LabeledPolynomial::new(
+            String::from("val"),
+            DensePolynomial::from_coefficients_vec(coeffs),
+            None,
+            None,
+        );
+
+        let start = Instant::now();
+        let (dense, _) = PC::commit(&ck, [&polynomial], None).unwrap();
+        let dense_time = start.elapsed();
+
+        let sparse_polynomial = to_sparse(&polynomial);
+        let start = Instant::now();
+        let (sparse, _) = PC::commit_sparse(&ck, &[sparse_polynomial], None).unwrap();
+        let sparse_time = start.elapsed();
+
+        let start = Instant::now();
+        let (heuristic, _) = PC::commit_with_density_heuristic(&ck, [&polynomial], None).unwrap();
+        let heuristic_time = start.elapsed();
+
+        assert_eq!(dense[0].commitment(), sparse[0].commitment());
+        assert_eq!(dense[0].commitment(), heuristic[0].commitment());
+        println!(
+            "density {}.{}%: {:?} dense, {:?} sparse, {:?} with the density heuristic",
+            density / 10,
+            density % 10,
+            dense_time,
+            sparse_time,
+            heuristic_time
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+};
+
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use ark_std::rand::RngCore;
+
+use crate::error::{to_pc_error, Error};
+
+struct Entry<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>> {
+    polynomial: DensePolynomial<F>,
+    degree_bound: Option<usize>,
+    hiding_bound: Option<usize>,
+    commitment: PC::Commitment,
+    randomness: PC::Randomness,
+    last_used: u64,
+}
+
+/// Remembers the commitments made during a prover session, so that committing again to a polynomial with the same
+/// coefficients, degree bound and hiding bound reuses the commitment and its randomness instead of running another
+/// MSM. Labels do not matter: a hit is returned under the requested label.
+///
+/// A cache must only be used with a single committer key. The commitments to equal polynomials are equal, which
+/// reveals that they are equal: only cache polynomials for which this is public, such as sequences determined by the
+/// index. Polynomials with a hiding bound bypass the cache, as reusing their randomness would break hiding. Once
+/// `capacity` commitments are cached, the least recently used one is evicted.
+pub struct CommitCache<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>> {
+    capacity: usize,
+    entries: BTreeMap<u64, Entry<F, PC>>,
+    clock: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>> CommitCache<F, PC> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: BTreeMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity, evicting the least recently used commitments that no longer fit
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > self.capacity {
+            self.evict_least_recently_used();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of commitments served from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of commitments computed
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Forget every commitment, e.g. at the end of a prover session or before switching committer keys
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Commit to the polynomials as `PC::commit` does, reusing the cached commitments to polynomials without a hiding
+    /// bound
+    #[allow(clippy::type_complexity)]
+    pub fn commit<'a, R: RngCore>(
+        &mut self,
+        ck: &PC::CommitterKey,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+        rng: &mut R,
+    ) -> Result<(Vec<LabeledCommitment<PC::Commitment>>, Vec<PC::Randomness>), Error> {
+        let mut commitments = Vec::new();
+        let mut rands = Vec::new();
+        for polynomial in polynomials {
+            let (commitment, randomness) = self.commit_one(ck, polynomial, rng)?;
+            commitments.push(LabeledCommitment::new(
+                polynomial.label().clone(),
+                commitment,
+                polynomial.degree_bound(),
+            ));
+            rands.push(randomness);
+        }
+        Ok((commitments, rands))
+    }
+
+    fn commit_one<R: RngCore>(
+        &mut self,
+        ck: &PC::CommitterKey,
+        polynomial: &LabeledPolynomial<F, DensePolynomial<F>>,
+        rng: &mut R,
+    ) -> Result<(PC::Commitment, PC::Randomness), Error> {
+        // a hiding commitment is always computed with fresh randomness
+        if polynomial.hiding_bound().is_some() {
+            self.misses += 1;
+            return Self::commit_fresh(ck, polynomial, rng);
+        }
+
+        self.clock += 1;
+        let key = Self::key(polynomial);
+
+        // the hash only selects the entry, the polynomial is compared in full
+        if let Some(entry) = self.entries.get_mut(&key) {
+            if entry.polynomial == *polynomial.polynomial()
+                && entry.degree_bound == polynomial.degree_bound()
+                && entry.hiding_bound == polynomial.hiding_bound()
+            {
+                entry.last_used = self.clock;
+                self.hits += 1;
+                return Ok((entry.commitment.clone(), entry.randomness.clone()));
+            }
+        }
+
+        self.misses += 1;
+        let (commitment, randomness) = Self::commit_fresh(ck, polynomial, rng)?;
+
+        if self.capacity > 0 {
+            if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+                self.evict_least_recently_used();
+            }
+            self.entries.insert(
+                key,
+                Entry {
+                    polynomial: polynomial.polynomial().clone(),
+                    degree_bound: polynomial.degree_bound(),
+                    hiding_bound: polynomial.hiding_bound(),
+                    commitment: commitment.clone(),
+                    randomness: randomness.clone(),
+                    last_used: self.clock,
+                },
+            );
+        }
+
+        Ok((commitment, randomness))
+    }
+
+    fn commit_fresh<R: RngCore>(
+        ck: &PC::CommitterKey,
+        polynomial: &LabeledPolynomial<F, DensePolynomial<F>>,
+        rng: &mut R,
+    ) -> Result<(PC::Commitment, PC::Randomness), Error> {
+        let (mut commitments, mut rands) =
+            PC::commit(ck, [polynomial], Some(rng)).map_err(to_pc_error::<F, PC>)?;
+        Ok((commitments.remove(0).commitment().clone(), rands.remove(0)))
+    }
+
+    fn key(polynomial: &LabeledPolynomial<F, DensePolynomial<F>>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        polynomial.polynomial().coeffs.hash(&mut hasher);
+        polynomial.degree_bound().hash(&mut hasher);
+        polynomial.hiding_bound().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        if let Some(key) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| *key)
+        {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{commit_cache::CommitCache, marlin_kzg::KZG10};
+    use ark_bn254::{Bn254, Fr};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    #[test]
+    fn test_commit_cache() {
+        let rng = &mut thread_rng();
+        let pp = PC::setup(16, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 16, 1, Some(&[10])).unwrap();
+
+        let poly = DensePolynomial::<F>::rand(7, rng);
+        let labeled = |label: &str, degree_bound| {
+            LabeledPolynomial::new(label.into(), poly.clone(), degree_bound, None)
+        };
+
+        let mut cache = CommitCache::<F, PC>::new(2);
+        let (first, first_rands) = cache.commit(&ck, [&labeled("a", None)], rng).unwrap();
+        let (second, second_rands) = cache.commit(&ck, [&labeled("b", None)], rng).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert_eq!(first[0].commitment(), second[0].commitment());
+        assert_eq!(first_rands[0], second_rands[0]);
+        assert_eq!(second[0].label(), "b");
+
+        // a different degree bound is a different commitment
+        cache.commit(&ck, [&labeled("a", Some(10))], rng).unwrap();
+        assert_eq!((cache.len(), cache.misses()), (2, 2));
+
+        // the least recently used commitment is evicted first
+        cache.commit(&ck, [&labeled("a", None)], rng).unwrap();
+        let other = LabeledPolynomial::new("c".into(), DensePolynomial::rand(3, rng), None, None);
+        cache.commit(&ck, [&other], rng).unwrap();
+        assert_eq!(cache.len(), 2);
+        cache.commit(&ck, [&labeled("a", None)], rng).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (3, 3));
+        cache.commit(&ck, [&labeled("a", Some(10))], rng).unwrap();
+        assert_eq!(cache.misses(), 4);
+
+        cache.set_capacity(1);
+        assert_eq!(cache.len(), 1);
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_hiding_bypasses_cache() {
+        let rng = &mut thread_rng();
+        let pp = PC::setup(16, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 16, 1, None).unwrap();
+
+        let hiding = LabeledPolynomial::new(
+            "a".into(),
+            DensePolynomial::<F>::rand(7, rng),
+            None,
+            Some(1),
+        );
+        let mut cache = CommitCache::<F, PC>::new(2);
+        let (first, first_rands) = cache.commit(&ck, [&hiding], rng).unwrap();
+        let (second, second_rands) = cache.commit(&ck, [&hiding], rng).unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+        assert!(cache.is_empty());
+        assert_ne!(first[0].commitment(), second[0].commitment());
+        assert_ne!(first_rands[0], second_rands[0]);
+    }
+}
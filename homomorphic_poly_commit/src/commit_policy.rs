@@ -0,0 +1,72 @@
+use ark_ff::{PrimeField, ToBytes};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::io::{Read, Write};
+
+use crate::{error::Error, AdditivelyHomomorphicPCS};
+
+/// Whether the commitments made by a prover must hide the committed polynomials. Under `Hiding`, committing to a
+/// prover oracle with empty randomness is a hard error rather than a silent loss of zero knowledge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommitPolicy {
+    Hiding,
+    NonHiding,
+}
+
+impl CommitPolicy {
+    /// The hiding bound to give to the oracles that a prover commits to itself
+    pub fn hiding_bound(&self) -> Option<usize> {
+        match self {
+            Self::Hiding => Some(1),
+            Self::NonHiding => None,
+        }
+    }
+
+    /// Check that the randomness of the commitment to `label` complies with the policy
+    pub fn check<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+        &self,
+        label: &str,
+        randomness: &PC::Randomness,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Hiding if !PC::is_hiding(randomness) => Err(Error::EmptyRandomness(format!(
+                "The commitment to {} does not hide it, but the policy requires hiding",
+                label
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Hiding => 0,
+            Self::NonHiding => 1,
+        }
+    }
+}
+
+impl ToBytes for CommitPolicy {
+    fn write<W: Write>(&self, writer: W) -> ark_std::io::Result<()> {
+        self.to_u8().write(writer)
+    }
+}
+
+impl CanonicalSerialize for CommitPolicy {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.to_u8().serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.to_u8().serialized_size()
+    }
+}
+
+impl CanonicalDeserialize for CommitPolicy {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        match u8::deserialize(reader)? {
+            0 => Ok(Self::Hiding),
+            1 => Ok(Self::NonHiding),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
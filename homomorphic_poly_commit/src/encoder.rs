@@ -0,0 +1,264 @@
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Evaluations as EvaluationsOnDomain,
+    GeneralEvaluationDomain,
+};
+use ark_poly_commit::LabeledPolynomial;
+
+use crate::error::Error;
+
+/// Describes how a byte blob was laid out over an evaluation domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlobMetadata {
+    /// Length of the blob in bytes
+    pub byte_len: usize,
+    /// Number of bytes packed into each field element
+    pub bytes_per_element: usize,
+    /// Size of the domain the blob is encoded over
+    pub domain_size: usize,
+}
+
+/// A byte blob encoded as the evaluations of a polynomial over a domain. The first evaluation holds the length of
+/// the blob, the following ones hold consecutive chunks of `bytes_per_element` bytes read in little-endian order,
+/// and the remaining evaluations are 0. Committing to the polynomial gives a commitment to the blob whose chunks can
+/// be opened individually at the corresponding domain elements.
+#[derive(Clone, Debug)]
+pub struct EncodedBlob<F: PrimeField> {
+    pub polynomial: LabeledPolynomial<F, DensePolynomial<F>>,
+    pub evaluations: EvaluationsOnDomain<F>,
+    pub metadata: BlobMetadata,
+}
+
+/// Number of bytes that fit in a field element without reduction modulo the field characteristic
+pub fn bytes_per_element<F: PrimeField>() -> usize {
+    (F::size_in_bits() - 1) / 8
+}
+
+/// Number of evaluations needed to encode `byte_len` bytes, including the length frame
+pub fn num_elements<F: PrimeField>(byte_len: usize) -> usize {
+    1 + byte_len.div_ceil(bytes_per_element::<F>())
+}
+
+/// Encode `data` over the smallest domain that holds it
+pub fn encode<F: PrimeField>(
+    label: &str,
+    data: &[u8],
+    degree_bound: Option<usize>,
+    hiding_bound: Option<usize>,
+) -> Result<EncodedBlob<F>, Error> {
+    let num_elements = num_elements::<F>(data.len());
+    let domain = GeneralEvaluationDomain::<F>::new(num_elements).ok_or_else(|| {
+        Error::InputLengthError(format!(
+            "No evaluation domain can hold {} field elements",
+            num_elements
+        ))
+    })?;
+
+    let mut evals = Vec::with_capacity(domain.size());
+    evals.push(F::from(data.len() as u64));
+    evals.extend(
+        data.chunks(bytes_per_element::<F>())
+            .map(F::from_le_bytes_mod_order),
+    );
+    evals.resize(domain.size(), F::zero());
+
+    let evaluations = EvaluationsOnDomain::from_vec_and_domain(evals, domain);
+    let polynomial = LabeledPolynomial::new(
+        label.to_string(),
+        evaluations.clone().interpolate(),
+        degree_bound,
+        hiding_bound,
+    );
+
+    Ok(EncodedBlob {
+        polynomial,
+        evaluations,
+        metadata: BlobMetadata {
+            byte_len: data.len(),
+            bytes_per_element: bytes_per_element::<F>(),
+            domain_size: domain.size(),
+        },
+    })
+}
+
+/// Recover the blob from its evaluations, rejecting anything that is not a canonical encoding
+pub fn decode<F: PrimeField>(evaluations: &[F]) -> Result<Vec<u8>, Error> {
+    let chunk_size = bytes_per_element::<F>();
+    let (length, chunks) = evaluations
+        .split_first()
+        .ok_or_else(|| Error::EncodingError(String::from("Missing length frame")))?;
+
+    let byte_len = to_bytes_checked(length, 8)?;
+    let byte_len = u64::from_le_bytes(byte_len.try_into().unwrap()) as usize;
+    let num_chunks = num_elements::<F>(byte_len) - 1;
+    if num_chunks > chunks.len() {
+        return Err(Error::EncodingError(format!(
+            "Blob of {} bytes does not fit in {} evaluations",
+            byte_len,
+            evaluations.len()
+        )));
+    }
+
+    let mut data = Vec::with_capacity(num_chunks * chunk_size);
+    for chunk in &chunks[..num_chunks] {
+        data.extend(to_bytes_checked(chunk, chunk_size)?);
+    }
+    if data[byte_len..].iter().any(|byte| *byte != 0) {
+        return Err(Error::EncodingError(String::from(
+            "Non-zero bytes after the end of the blob",
+        )));
+    }
+    data.truncate(byte_len);
+
+    if chunks[num_chunks..].iter().any(|chunk| !chunk.is_zero()) {
+        return Err(Error::EncodingError(String::from(
+            "Non-zero evaluations after the end of the blob",
+        )));
+    }
+
+    Ok(data)
+}
+
+/// Recover the blob from the polynomial encoding it, given the metadata produced by [`encode`]
+pub fn decode_polynomial<F: PrimeField>(
+    polynomial: &DensePolynomial<F>,
+    metadata: &BlobMetadata,
+) -> Result<Vec<u8>, Error> {
+    let domain = GeneralEvaluationDomain::<F>::new(metadata.domain_size)
+        .filter(|domain| domain.size() == metadata.domain_size)
+        .ok_or_else(|| {
+            Error::EncodingError(format!("Invalid domain size {}", metadata.domain_size))
+        })?;
+    if polynomial.coeffs.len() > domain.size() {
+        return Err(Error::EncodingError(format!(
+            "Polynomial of degree {} is too large for a domain of size {}",
+            polynomial.coeffs.len() - 1,
+            domain.size()
+        )));
+    }
+
+    let data = decode(&domain.fft(&polynomial.coeffs))?;
+    if data.len() != metadata.byte_len {
+        return Err(Error::EncodingError(format!(
+            "Expected a blob of {} bytes but decoded {} bytes",
+            metadata.byte_len,
+            data.len()
+        )));
+    }
+
+    Ok(data)
+}
+
+/// Little-endian bytes of a field element, which must fit in `len` bytes
+fn to_bytes_checked<F: PrimeField>(element: &F, len: usize) -> Result<Vec<u8>, Error> {
+    let mut bytes = element.into_repr().to_bytes_le();
+    if bytes[len..].iter().any(|byte| *byte != 0) {
+        return Err(Error::EncodingError(format!(
+            "Field element does not fit in {} bytes",
+            len
+        )));
+    }
+    bytes.truncate(len);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        encoder::{bytes_per_element, decode, decode_polynomial, encode},
+        error::Error,
+        marlin_kzg::KZG10,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{One, PrimeField, UniformRand};
+    use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::{thread_rng, RngCore};
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    #[test]
+    fn test_roundtrip() {
+        let rng = &mut thread_rng();
+        assert_eq!(bytes_per_element::<F>(), 31);
+
+        for len in [0, 1, 30, 31, 32, 100, 200] {
+            let mut data = vec![0u8; len];
+            rng.fill_bytes(&mut data);
+
+            let blob = encode::<F>("blob", &data, None, None).unwrap();
+            assert_eq!(blob.metadata.byte_len, len);
+            assert!(blob.metadata.domain_size > len.div_ceil(31));
+
+            assert_eq!(decode(&blob.evaluations.evals).unwrap(), data);
+            assert_eq!(
+                decode_polynomial(blob.polynomial.polynomial(), &blob.metadata).unwrap(),
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_chunk() {
+        let rng = &mut thread_rng();
+        let data: Vec<u8> = (0..100).collect();
+        let blob = encode::<F>("blob", &data, None, None).unwrap();
+
+        let max_degree = 16;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 0, None).unwrap();
+        let polynomials = [blob.polynomial];
+        let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+
+        // the second chunk lives at the third element of the domain
+        let domain = GeneralEvaluationDomain::<F>::new(blob.metadata.domain_size).unwrap();
+        let point = domain.element(2);
+        let opening_challenge = F::rand(rng);
+        let proof = PC::open(
+            &ck,
+            &polynomials,
+            &commitments,
+            &point,
+            opening_challenge,
+            &rands,
+            Some(rng),
+        )
+        .unwrap();
+
+        let chunk = F::from_le_bytes_mod_order(&data[31..62]);
+        let res = PC::check(
+            &vk,
+            &commitments,
+            &point,
+            vec![chunk],
+            &proof,
+            opening_challenge,
+            Some(rng),
+        )
+        .unwrap();
+        assert!(res);
+    }
+
+    #[test]
+    fn test_reject_non_canonical() {
+        let data = [7u8; 40];
+        let blob = encode::<F>("blob", &data, None, None).unwrap();
+
+        // a chunk that does not fit in 31 bytes
+        let mut evals = blob.evaluations.evals.clone();
+        evals[1] = -F::one();
+        assert!(matches!(decode(&evals), Err(Error::EncodingError(_))));
+
+        // trailing data after the end of the blob
+        let mut evals = blob.evaluations.evals.clone();
+        let last = evals.len() - 1;
+        evals[last] = F::one();
+        assert!(matches!(decode(&evals), Err(Error::EncodingError(_))));
+
+        // a length frame larger than the evaluations
+        let mut evals = blob.evaluations.evals.clone();
+        evals[0] = F::from(1000u64);
+        assert!(matches!(decode(&evals), Err(Error::EncodingError(_))));
+    }
+}
@@ -9,6 +9,18 @@ pub enum Error {
     InputLengthError(String),
     MismatchedDegreeBounds(String),
     ConstantTermInAggregation,
+
+    /// A commitment made with empty randomness where the commit policy requires hiding
+    EmptyRandomness(String),
+
+    /// Field elements that are not a valid encoding of a byte blob
+    EncodingError(String),
+
+    /// A commitment that differs from the one recomputed from its polynomial and randomness
+    CommitmentMismatch(String),
+
+    /// A key file cannot be written, read or mapped, or is not laid out as expected
+    KeyFileError(String),
 }
 
 /// Convert an ark_poly_commit error
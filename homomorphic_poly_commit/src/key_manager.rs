@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{PCUniversalParams, PolynomialCommitment};
+use ark_std::rand::RngCore;
+
+use crate::error::{to_pc_error, Error};
+
+/// The parameters a pair of keys was trimmed for: supported degree and sorted enforced degree bounds
+type KeySpec = (usize, Vec<usize>);
+
+/// Holds the universal parameters of a polynomial commitment scheme and derives committer and verifier keys on
+/// demand, so that protocol code can ask for the degree bounds it needs instead of trimming keys up front.
+/// Derived keys are cached and reused for identical requests.
+pub struct KeyManager<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>> {
+    pp: PC::UniversalParams,
+    hiding_bound: usize,
+    cache: BTreeMap<KeySpec, (PC::CommitterKey, PC::VerifierKey)>,
+}
+
+impl<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>> KeyManager<F, PC> {
+    pub fn new(pp: PC::UniversalParams, hiding_bound: usize) -> Self {
+        Self {
+            pp,
+            hiding_bound,
+            cache: BTreeMap::new(),
+        }
+    }
+
+    /// Run the setup of the commitment scheme and manage the resulting universal parameters
+    pub fn setup<R: RngCore>(
+        max_degree: usize,
+        hiding_bound: usize,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        let pp = PC::setup(max_degree, None, rng).map_err(to_pc_error::<F, PC>)?;
+        Ok(Self::new(pp, hiding_bound))
+    }
+
+    /// The maximum degree supported by the universal parameters
+    pub fn max_degree(&self) -> usize {
+        self.pp.max_degree()
+    }
+
+    pub fn hiding_bound(&self) -> usize {
+        self.hiding_bound
+    }
+
+    /// Number of distinct key pairs derived so far
+    pub fn num_cached(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Committer and verifier keys supporting polynomials up to `supported_degree` and the given enforced degree
+    /// bounds. The order and multiplicity of the bounds do not matter.
+    pub fn keys(
+        &mut self,
+        supported_degree: usize,
+        enforced_degree_bounds: &[usize],
+    ) -> Result<(PC::CommitterKey, PC::VerifierKey), Error> {
+        let mut degree_bounds = enforced_degree_bounds.to_vec();
+        degree_bounds.sort_unstable();
+        degree_bounds.dedup();
+
+        let spec = (supported_degree, degree_bounds);
+        if let Some(keys) = self.cache.get(&spec) {
+            return Ok(keys.clone());
+        }
+
+        if supported_degree > self.max_degree() {
+            return Err(Error::InputLengthError(format!(
+                "Requested keys for degree {}, but the universal parameters only support degree {}",
+                supported_degree,
+                self.max_degree()
+            )));
+        }
+
+        let bounds = match spec.1.is_empty() {
+            true => None,
+            false => Some(spec.1.as_slice()),
+        };
+        let keys = PC::trim(&self.pp, supported_degree, self.hiding_bound, bounds)
+            .map_err(to_pc_error::<F, PC>)?;
+
+        self.cache.insert(spec, keys.clone());
+        Ok(keys)
+    }
+
+    pub fn committer_key(
+        &mut self,
+        supported_degree: usize,
+        enforced_degree_bounds: &[usize],
+    ) -> Result<PC::CommitterKey, Error> {
+        self.keys(supported_degree, enforced_degree_bounds)
+            .map(|(ck, _)| ck)
+    }
+
+    pub fn verifier_key(
+        &mut self,
+        supported_degree: usize,
+        enforced_degree_bounds: &[usize],
+    ) -> Result<PC::VerifierKey, Error> {
+        self.keys(supported_degree, enforced_degree_bounds)
+            .map(|(_, vk)| vk)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{error::Error, key_manager::KeyManager, marlin_kzg::KZG10};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::UniformRand;
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PCCommitterKey, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    #[test]
+    fn test_keys_are_derived_lazily() {
+        let rng = &mut thread_rng();
+        let mut key_manager = KeyManager::<F, PC>::setup(32, 1, rng).unwrap();
+        assert_eq!(key_manager.num_cached(), 0);
+
+        let (ck, vk) = key_manager.keys(16, &[10, 2]).unwrap();
+        assert_eq!(ck.supported_degree(), 16);
+        assert_eq!(key_manager.num_cached(), 1);
+
+        // the same bounds in a different order are served from the cache
+        key_manager.keys(16, &[2, 10, 10]).unwrap();
+        assert_eq!(key_manager.num_cached(), 1);
+
+        key_manager.keys(20, &[10]).unwrap();
+        assert_eq!(key_manager.num_cached(), 2);
+
+        // the derived keys commit and open as trimmed keys would
+        let polynomials = [LabeledPolynomial::new(
+            String::from("a"),
+            DensePolynomial::rand(7, rng),
+            Some(10),
+            Some(1),
+        )];
+        let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+
+        let point = F::rand(rng);
+        let opening_challenge = F::rand(rng);
+        let proof = PC::open(
+            &ck,
+            &polynomials,
+            &commitments,
+            &point,
+            opening_challenge,
+            &rands,
+            Some(rng),
+        )
+        .unwrap();
+        let res = PC::check(
+            &vk,
+            &commitments,
+            &point,
+            vec![polynomials[0].evaluate(&point)],
+            &proof,
+            opening_challenge,
+            Some(rng),
+        )
+        .unwrap();
+        assert!(res);
+    }
+
+    #[test]
+    fn test_degree_too_large() {
+        let rng = &mut thread_rng();
+        let mut key_manager = KeyManager::<F, PC>::setup(16, 1, rng).unwrap();
+
+        assert!(matches!(
+            key_manager.keys(17, &[]),
+            Err(Error::InputLengthError(_))
+        ));
+        assert_eq!(key_manager.num_cached(), 0);
+    }
+}
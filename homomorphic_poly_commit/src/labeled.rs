@@ -0,0 +1,137 @@
+//! [`Debug`] and [`PartialEq`] for labeled commitments, which ark-poly-commit only makes `Clone`, and [`PartialEq`]
+//! for labeled polynomials. Types holding them derive these traits with `derivative`, pointing `format_with` and
+//! `compare_with` at the functions here, e.g. `#[derivative(Debug(format_with = "homomorphic_poly_commit::labeled::fmt"))]`.
+
+use ark_ff::Field;
+use ark_poly::Polynomial;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PCCommitment};
+use ark_std::fmt::{self, Debug, Formatter};
+
+/// Format the label, commitment and degree bound of `commitment`
+pub fn fmt<C: PCCommitment + Debug>(
+    commitment: &LabeledCommitment<C>,
+    f: &mut Formatter,
+) -> fmt::Result {
+    f.debug_struct("LabeledCommitment")
+        .field("label", commitment.label())
+        .field("commitment", commitment.commitment())
+        .field("degree_bound", &commitment.degree_bound())
+        .finish()
+}
+
+/// Two labeled commitments are equal if they agree on label, commitment and degree bound
+pub fn eq<C: PCCommitment + PartialEq>(a: &LabeledCommitment<C>, b: &LabeledCommitment<C>) -> bool {
+    a.label() == b.label()
+        && a.commitment() == b.commitment()
+        && a.degree_bound() == b.degree_bound()
+}
+
+/// [`fmt`] and [`eq`] for vectors of labeled commitments
+pub mod vec {
+    use super::*;
+
+    struct Labeled<'a, C: PCCommitment>(&'a LabeledCommitment<C>);
+
+    impl<C: PCCommitment + Debug> Debug for Labeled<'_, C> {
+        fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+            super::fmt(self.0, f)
+        }
+    }
+
+    pub fn fmt<C: PCCommitment + Debug>(
+        commitments: &[LabeledCommitment<C>],
+        f: &mut Formatter,
+    ) -> fmt::Result {
+        f.debug_list()
+            .entries(commitments.iter().map(Labeled))
+            .finish()
+    }
+
+    pub fn eq<C: PCCommitment + PartialEq>(
+        a: &[LabeledCommitment<C>],
+        b: &[LabeledCommitment<C>],
+    ) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| super::eq(a, b))
+    }
+}
+
+/// [`eq`] for labeled polynomials, which also have to agree on their hiding bound
+pub mod polynomial {
+    use super::*;
+
+    pub fn eq<F: Field, P: Polynomial<F>>(
+        a: &LabeledPolynomial<F, P>,
+        b: &LabeledPolynomial<F, P>,
+    ) -> bool {
+        a.label() == b.label()
+            && a.polynomial() == b.polynomial()
+            && a.degree_bound() == b.degree_bound()
+            && a.hiding_bound() == b.hiding_bound()
+    }
+
+    pub mod vec {
+        use super::*;
+
+        pub fn eq<F: Field, P: Polynomial<F>>(
+            a: &[LabeledPolynomial<F, P>],
+            b: &[LabeledPolynomial<F, P>],
+        ) -> bool {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| super::eq(a, b))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{labeled, marlin_kzg::KZG10};
+    use ark_bn254::{Bn254, Fr};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+    use ark_std::{
+        fmt::{self, Debug, Formatter},
+        rand::thread_rng,
+    };
+
+    type PC = KZG10<Bn254>;
+    type Commitment = <PC as PolynomialCommitment<Fr, DensePolynomial<Fr>>>::Commitment;
+
+    #[test]
+    fn test_labeled_commitments() {
+        let rng = &mut thread_rng();
+        let pp = PC::setup(8, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 8, 0, Some(&[4])).unwrap();
+
+        let poly = DensePolynomial::<Fr>::rand(3, rng);
+        let labeled = |label: &str, degree_bound| {
+            LabeledPolynomial::new(String::from(label), poly.clone(), degree_bound, None)
+        };
+        let (commitments, _) = PC::commit(
+            &ck,
+            &[
+                labeled("p", None),
+                labeled("q", None),
+                labeled("p", Some(4)),
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert!(labeled::eq(&commitments[0], &commitments[0].clone()));
+        // the same polynomial under another label or degree bound
+        assert!(!labeled::eq(&commitments[0], &commitments[1]));
+        assert!(!labeled::eq(&commitments[0], &commitments[2]));
+
+        assert!(labeled::vec::eq(&commitments, &commitments.clone()));
+        assert!(!labeled::vec::eq(&commitments, &commitments[..2]));
+
+        struct Formatted<'a>(&'a [LabeledCommitment<Commitment>]);
+        impl Debug for Formatted<'_> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                labeled::vec::fmt(self.0, f)
+            }
+        }
+        let formatted = format!("{:?}", Formatted(&commitments));
+        assert!(formatted.contains("label: \"q\""));
+        assert!(formatted.contains("degree_bound: Some(4)"));
+    }
+}
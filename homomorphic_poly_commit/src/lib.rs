@@ -1,15 +1,45 @@
-use ark_ff::PrimeField;
-use ark_poly::univariate::DensePolynomial;
-use ark_poly_commit::{LabeledCommitment, LinearCombination, PolynomialCommitment};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::univariate::{DensePolynomial, SparsePolynomial};
+use ark_poly_commit::{
+    LabeledCommitment, LabeledPolynomial, LinearCombination, PolynomialCommitment,
+};
+use ark_std::{fmt::Debug, rand::RngCore};
 
-use crate::error::Error;
+use crate::{
+    error::{to_pc_error, Error},
+    multi_point::MultiPointProof,
+};
 
+pub mod commit_cache;
+pub mod commit_policy;
+pub mod encoder;
 pub mod error;
+pub mod key_manager;
+pub mod labeled;
+#[cfg(feature = "mmap")]
+pub mod mapped_key;
 pub mod marlin_kzg;
+pub mod multi_point;
+pub mod opening_accumulator;
+pub mod opening_hint;
+#[cfg(feature = "pedersen")]
+pub mod pedersen;
+pub mod precomputed_key;
+#[cfg(feature = "serde")]
+pub mod serde_hex;
+pub mod shared_randomness;
 pub mod sonic_kzg;
+pub mod sparse;
+mod tests;
+pub mod vector_commitment;
 
-/// An additively homomorphic polynomial commitment scheme
-pub trait AdditivelyHomomorphicPCS<F>: PolynomialCommitment<F, DensePolynomial<F>>
+/// Serialized size in bytes of the length prefix of a vector
+pub const LENGTH_PREFIX_SIZE: usize = 8;
+
+/// An additively homomorphic polynomial commitment scheme. Its commitments and proofs are `Debug`, and its
+/// commitments `Eq`, so that the proofs and statements of the protocols built on it can be printed and compared.
+pub trait AdditivelyHomomorphicPCS<F>:
+    PolynomialCommitment<F, DensePolynomial<F>, Commitment: Debug + Eq, Proof: Debug, BatchProof: Debug>
 where
     F: PrimeField,
 {
@@ -20,4 +50,166 @@ where
         randomness: Option<Vec<Self::Randomness>>,
         lc: &LinearCombination<F>,
     ) -> Result<(LabeledCommitment<Self::Commitment>, Self::Randomness), Error>;
+
+    /// Compute the commitment `sum_i scalars[i] * commitments[i]` with a single multi-scalar multiplication. This is
+    /// the fast path for long linear combinations, e.g. on the verifier side.
+    fn msm_commitments(
+        commitments: &[Self::Commitment],
+        scalars: &[F],
+    ) -> Result<Self::Commitment, Error>;
+
+    /// Compute `c1 + c2` on raw commitments, without labels or a linear combination
+    fn add(c1: &Self::Commitment, c2: &Self::Commitment) -> Result<Self::Commitment, Error> {
+        Self::msm_commitments(&[c1.clone(), c2.clone()], &[F::one(), F::one()])
+    }
+
+    /// Compute `c1 - c2` on raw commitments, without labels or a linear combination
+    fn sub(c1: &Self::Commitment, c2: &Self::Commitment) -> Result<Self::Commitment, Error> {
+        Self::msm_commitments(&[c1.clone(), c2.clone()], &[F::one(), -F::one()])
+    }
+
+    /// Compute `scalar * c` on a raw commitment
+    fn scale(c: &Self::Commitment, scalar: F) -> Result<Self::Commitment, Error> {
+        Self::msm_commitments(ark_std::slice::from_ref(c), &[scalar])
+    }
+
+    /// Commit to polynomials given by their non-zero coefficients, see [`sparse`]. The commitments and randomness are
+    /// those of the dense polynomials. The default implementation commits to the dense polynomials, schemes that can
+    /// skip the zero coefficients in their MSM override it.
+    #[allow(clippy::type_complexity)]
+    fn commit_sparse(
+        ck: &Self::CommitterKey,
+        polynomials: &[LabeledPolynomial<F, SparsePolynomial<F>>],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::Randomness>,
+        ),
+        Error,
+    > {
+        sparse::commit_as_dense::<F, Self>(ck, polynomials, rng)
+    }
+
+    /// Commit to polynomials like `commit`, taking the path of [`Self::commit_sparse`] for those with at most
+    /// [`sparse::MAX_SPARSE_DENSITY_PERCENT`] percent of non-zero coefficients
+    #[allow(clippy::type_complexity)]
+    fn commit_with_density_heuristic<'a>(
+        ck: &Self::CommitterKey,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::Randomness>,
+        ),
+        Error,
+    > {
+        sparse::commit_with_density_heuristic::<F, Self>(ck, polynomials, rng)
+    }
+
+    /// The commitment to the zero polynomial under `randomness`, for a polynomial with degree bound `degree_bound`.
+    /// Adding it to the commitment made with empty randomness gives the commitment made with `randomness`, see
+    /// [`shared_randomness`].
+    fn commit_randomness(
+        ck: &Self::CommitterKey,
+        degree_bound: Option<usize>,
+        randomness: &Self::Randomness,
+    ) -> Result<Self::Commitment, Error>;
+
+    /// Commit to polynomials with randomness given by the caller rather than sampled, one per polynomial, see
+    /// [`shared_randomness`]
+    fn commit_with_rands<'a>(
+        ck: &Self::CommitterKey,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+        rands: &[Self::Randomness],
+    ) -> Result<Vec<LabeledCommitment<Self::Commitment>>, Error> {
+        shared_randomness::commit_with_rands::<F, Self>(ck, polynomials, rands)
+    }
+
+    /// Whether the randomness of a commitment hides the committed polynomial, i.e. it is not empty
+    fn is_hiding(randomness: &Self::Randomness) -> bool;
+
+    /// Serialized size in bytes of a commitment, which carries a shifted commitment when it enforces a degree bound
+    /// in some schemes
+    fn commitment_size(degree_bounded: bool) -> usize;
+
+    /// Serialized size in bytes of the proof of an opening at a single point with keys of degree `supported_degree`,
+    /// which carries more randomness when one of the opened polynomials is hiding
+    fn opening_proof_size(supported_degree: usize, hiding: bool) -> usize;
+
+    /// Serialized size in bytes of a batch opening proof, given whether one of the polynomials opened at each of its
+    /// distinct points is hiding. The batch proofs of the schemes of this crate are vectors of one proof per point.
+    fn batch_proof_size(supported_degree: usize, points_hiding: &[bool]) -> usize {
+        LENGTH_PREFIX_SIZE
+            + points_hiding
+                .iter()
+                .map(|&hiding| Self::opening_proof_size(supported_degree, hiding))
+                .sum::<usize>()
+    }
+
+    /// Re-randomize a hiding commitment by adding a fresh hiding commitment to the zero polynomial. The result commits
+    /// to the same polynomial but is unlinkable to the original; openings against it use the original randomness
+    /// plus the returned randomness delta.
+    fn rerandomize<R: RngCore>(
+        ck: &Self::CommitterKey,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        hiding_bound: usize,
+        rng: &mut R,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::Randomness), Error> {
+        let zero_label = format!("{}_rerandomization", commitment.label());
+        let zero = LabeledPolynomial::new(
+            zero_label.clone(),
+            DensePolynomial::zero(),
+            commitment.degree_bound(),
+            Some(hiding_bound),
+        );
+        let (zero_commitments, zero_rands) =
+            Self::commit(ck, &[zero], Some(rng)).map_err(to_pc_error::<F, Self>)?;
+
+        let lc = LinearCombination::new(
+            commitment.label().clone(),
+            vec![
+                (F::one(), commitment.label().clone()),
+                (F::one(), zero_label),
+            ],
+        );
+        let (rerandomized, _) = Self::aggregate_commitments(
+            &[commitment.clone(), zero_commitments[0].clone()],
+            None,
+            &lc,
+        )?;
+
+        Ok((rerandomized, zero_rands[0].clone()))
+    }
+
+    /// Open one polynomial at several distinct points with a single proof, see [`multi_point`]. `challenge` derives
+    /// the evaluation point of the reduction from the commitment to the quotient, typically by absorbing it into a
+    /// Fiat-Shamir transcript.
+    fn open_multi_point<R: RngCore>(
+        ck: &Self::CommitterKey,
+        polynomial: &LabeledPolynomial<F, DensePolynomial<F>>,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        randomness: &Self::Randomness,
+        points: &[F],
+        challenge: impl FnOnce(&Self::Commitment) -> F,
+        rng: &mut R,
+    ) -> Result<MultiPointProof<F, Self>, Error> {
+        multi_point::open(
+            ck, polynomial, commitment, randomness, points, challenge, rng,
+        )
+    }
+
+    /// Check that the committed polynomial takes `values` at `points`, deriving the challenge as the prover did
+    fn check_multi_point<R: RngCore>(
+        vk: &Self::VerifierKey,
+        commitment: &LabeledCommitment<Self::Commitment>,
+        points: &[F],
+        values: &[F],
+        proof: &MultiPointProof<F, Self>,
+        challenge: impl FnOnce(&Self::Commitment) -> F,
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        multi_point::check(vk, commitment, points, values, proof, challenge, rng)
+    }
 }
@@ -0,0 +1,495 @@
+//! Committer keys of the KZG backend ([`KZG10`](crate::marlin_kzg::KZG10)) memory-mapped from disk. For max degrees
+//! in the tens of millions, a loaded key takes gigabytes, almost all of it in the powers of g and their shifted
+//! counterparts, which only the MSMs of a commitment read. [`MappedCommitterKey::write`] lays these bases out at fixed
+//! offsets in a file, and [`MappedCommitterKey::open`] maps the file instead of loading it. Committing then decodes
+//! one window of bases at a time straight from the mapping, while the OS pages the rest of the key in and out as
+//! needed.
+//!
+//! ```ignore
+//! MappedCommitterKey::write(&ck, "ck.bin")?;
+//! drop(ck);
+//!
+//! let ck = MappedCommitterKey::<Bn254>::open("ck.bin")?;
+//! let (commitments, rands) = ck.commit(&polynomials, Some(rng))?;
+//! ```
+//!
+//! The commitments and randomness are those of [`PolynomialCommitment::commit`](ark_poly_commit::PolynomialCommitment::commit) with the loaded key and the same rng.
+//! Bases are decoded from their uncompressed encoding without curve checks, rather than reinterpreted in place, which
+//! would tie the file to the in-memory layout of the points; like any serialized key, the file is trusted. Opening
+//! reads the whole key, see [`MappedCommitterKey::to_committer_key`].
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use ark_ec::{msm::VariableBaseMSM, PairingEngine};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{marlin_pc, LabeledCommitment, LabeledPolynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+use memmap2::Mmap;
+
+use crate::{
+    error::Error,
+    marlin_kzg::{commit_with_bases, CommitterBases, KZGRandomness},
+};
+
+/// First bytes of a key file, versioning its layout
+const MAGIC: &[u8; 8] = b"FCKZGCK\x01";
+
+/// Number of bases decoded at once by default
+pub const DEFAULT_WINDOW_SIZE: usize = 1 << 16;
+
+/// Points stored back to back from an offset of the file
+#[derive(Clone, Copy, Debug)]
+struct Section {
+    offset: usize,
+    len: usize,
+}
+
+/// A committer key of [`KZG10`](crate::marlin_kzg::KZG10) whose powers of g, shifted or not, are read from a
+/// memory-mapped file
+pub struct MappedCommitterKey<E: PairingEngine> {
+    map: Mmap,
+    /// Size of an uncompressed point
+    point_size: usize,
+    powers: Section,
+    shifted_powers: Option<Section>,
+    powers_of_gamma_g: Vec<E::G1Affine>,
+    enforced_degree_bounds: Option<Vec<usize>>,
+    max_degree: usize,
+    window_size: usize,
+}
+
+fn key_file_error(error: impl std::fmt::Debug) -> Error {
+    Error::KeyFileError(format!("{:?}", error))
+}
+
+impl<E: PairingEngine> MappedCommitterKey<E> {
+    /// Write `ck` to `path`: a header with the powers of gamma g, the degree bounds and the number of powers,
+    /// followed by the uncompressed powers of g and then the shifted powers
+    pub fn write<P: AsRef<Path>>(ck: &marlin_pc::CommitterKey<E>, path: P) -> Result<(), Error> {
+        let mut header = Vec::new();
+        ck.max_degree
+            .serialize(&mut header)
+            .and_then(|_| ck.enforced_degree_bounds.serialize(&mut header))
+            .and_then(|_| ck.powers_of_gamma_g.serialize(&mut header))
+            .and_then(|_| ck.powers.len().serialize(&mut header))
+            .and_then(|_| {
+                ck.shifted_powers
+                    .as_ref()
+                    .map(Vec::len)
+                    .serialize(&mut header)
+            })
+            .map_err(key_file_error)?;
+
+        let mut file = BufWriter::new(File::create(path).map_err(key_file_error)?);
+        file.write_all(MAGIC)
+            .and_then(|_| file.write_all(&(header.len() as u64).to_le_bytes()))
+            .and_then(|_| file.write_all(&header))
+            .map_err(key_file_error)?;
+        for point in ck.powers.iter().chain(ck.shifted_powers.iter().flatten()) {
+            point
+                .serialize_uncompressed(&mut file)
+                .map_err(key_file_error)?;
+        }
+        file.flush().map_err(key_file_error)
+    }
+
+    /// Map a key file written by [`Self::write`], checking its header and length
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path).map_err(key_file_error)?;
+        // SAFETY: the mapping is only ever read, and as for any memory-mapped file, the key file must not be modified
+        // while mapped
+        let map = unsafe { Mmap::map(&file) }.map_err(key_file_error)?;
+
+        if map.len() < MAGIC.len() + 8 || &map[..MAGIC.len()] != MAGIC {
+            return Err(Error::KeyFileError(String::from(
+                "Not a committer key file, or one of an unsupported version",
+            )));
+        }
+        // the sizes come from the file, so none of the arithmetic on them may wrap around
+        let overflow = || Error::KeyFileError(String::from("Section sizes overflow"));
+        let header_len = u64::from_le_bytes(map[MAGIC.len()..MAGIC.len() + 8].try_into().unwrap());
+        let header_start = MAGIC.len() + 8;
+        let header_end = usize::try_from(header_len)
+            .ok()
+            .and_then(|header_len| header_start.checked_add(header_len))
+            .ok_or_else(overflow)?;
+        let mut header = map
+            .get(header_start..header_end)
+            .ok_or_else(|| Error::KeyFileError(String::from("Truncated header")))?;
+
+        let max_degree = usize::deserialize(&mut header).map_err(key_file_error)?;
+        let enforced_degree_bounds =
+            Option::<Vec<usize>>::deserialize(&mut header).map_err(key_file_error)?;
+        let powers_of_gamma_g =
+            Vec::<E::G1Affine>::deserialize(&mut header).map_err(key_file_error)?;
+        let powers_len = usize::deserialize(&mut header).map_err(key_file_error)?;
+        let shifted_len = Option::<usize>::deserialize(&mut header).map_err(key_file_error)?;
+
+        let point_size = E::G1Affine::zero().uncompressed_size();
+        let powers = Section {
+            offset: header_end,
+            len: powers_len,
+        };
+        let shifted_offset = powers_len
+            .checked_mul(point_size)
+            .and_then(|size| header_end.checked_add(size))
+            .ok_or_else(overflow)?;
+        let shifted_powers = shifted_len.map(|len| Section {
+            offset: shifted_offset,
+            len,
+        });
+        let expected_len = shifted_len
+            .unwrap_or(0)
+            .checked_mul(point_size)
+            .and_then(|size| shifted_offset.checked_add(size))
+            .ok_or_else(overflow)?;
+        if map.len() != expected_len {
+            return Err(Error::KeyFileError(format!(
+                "Expected {} bytes for {} powers and {:?} shifted powers, found {}",
+                expected_len,
+                powers_len,
+                shifted_len,
+                map.len()
+            )));
+        }
+
+        Ok(Self {
+            map,
+            point_size,
+            powers,
+            shifted_powers,
+            powers_of_gamma_g,
+            enforced_degree_bounds,
+            max_degree,
+            window_size: DEFAULT_WINDOW_SIZE,
+        })
+    }
+
+    /// Decode `window_size` bases at a time when committing, trading memory for fewer, larger MSMs
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(1);
+        self
+    }
+
+    pub fn supported_degree(&self) -> usize {
+        self.powers.len.saturating_sub(1)
+    }
+
+    /// The maximum degree supported by the universal parameters the key was trimmed from
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    pub fn enforced_degree_bounds(&self) -> Option<&[usize]> {
+        self.enforced_degree_bounds.as_deref()
+    }
+
+    /// Load the whole key, e.g. to open commitments
+    pub fn to_committer_key(&self) -> Result<marlin_pc::CommitterKey<E>, Error> {
+        Ok(marlin_pc::CommitterKey {
+            powers: self.bases(self.powers, 0, self.powers.len)?,
+            shifted_powers: self
+                .shifted_powers
+                .map(|section| self.bases(section, 0, section.len))
+                .transpose()?,
+            powers_of_gamma_g: self.powers_of_gamma_g.clone(),
+            enforced_degree_bounds: self.enforced_degree_bounds.clone(),
+            max_degree: self.max_degree,
+        })
+    }
+
+    /// Commit to `polynomials` as [`PolynomialCommitment::commit`](ark_poly_commit::PolynomialCommitment::commit) does with the loaded key
+    #[allow(clippy::type_complexity)]
+    pub fn commit<'a>(
+        &self,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<E::Fr, DensePolynomial<E::Fr>>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<marlin_pc::Commitment<E>>>,
+            Vec<KZGRandomness<E>>,
+        ),
+        Error,
+    > {
+        commit_with_bases(self, polynomials, rng)
+    }
+
+    /// The powers of g, or the shifted powers if `shifted`
+    fn section(&self, shifted: bool) -> Option<Section> {
+        if shifted {
+            self.shifted_powers
+        } else {
+            Some(self.powers)
+        }
+    }
+
+    /// `sum_i scalars[i] * bases[shift + i]` over the bases of `section`, decoding one window of bases and converting
+    /// one window of scalars at a time
+    fn windowed_msm(
+        &self,
+        section: Section,
+        shift: usize,
+        scalars: &[E::Fr],
+    ) -> Result<E::G1Projective, Error> {
+        let mut sum = E::G1Projective::zero();
+        for (i, window) in scalars.chunks(self.window_size).enumerate() {
+            let bases = self.bases(section, shift + i * self.window_size, window.len())?;
+            let window = window
+                .iter()
+                .map(|scalar| scalar.into_repr())
+                .collect::<Vec<_>>();
+            sum += VariableBaseMSM::multi_scalar_mul(&bases, &window);
+        }
+        Ok(sum)
+    }
+
+    /// Decode `len` bases of `section` from the index `start`
+    fn bases(&self, section: Section, start: usize, len: usize) -> Result<Vec<E::G1Affine>, Error> {
+        debug_assert!(start + len <= section.len);
+        let offset = section.offset + start * self.point_size;
+        self.map[offset..offset + len * self.point_size]
+            .chunks_exact(self.point_size)
+            .map(|bytes| E::G1Affine::deserialize_unchecked(bytes).map_err(key_file_error))
+            .collect()
+    }
+}
+
+impl<E: PairingEngine> CommitterBases<E> for MappedCommitterKey<E> {
+    fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    fn enforced_degree_bounds(&self) -> Option<&[usize]> {
+        self.enforced_degree_bounds.as_deref()
+    }
+
+    fn powers_of_gamma_g(&self) -> &[E::G1Affine] {
+        &self.powers_of_gamma_g
+    }
+
+    fn num_powers(&self, shifted: bool) -> Option<usize> {
+        self.section(shifted).map(|section| section.len)
+    }
+
+    fn msm(
+        &self,
+        shifted: bool,
+        shift: usize,
+        scalars: &[E::Fr],
+    ) -> Result<E::G1Projective, Error> {
+        match self.section(shifted) {
+            Some(section) => self.windowed_msm(section, shift, scalars),
+            None => Ok(E::G1Projective::zero()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{error::Error, mapped_key::MappedCommitterKey, marlin_kzg::KZG10};
+    use ark_bn254::{Bn254, Fr, G1Affine};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        test_rng,
+    };
+    use std::{fs, path::PathBuf};
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    /// A file in the temporary directory, removed when dropped
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!("{}_{}.ck", name, std::process::id())))
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_commit_matches_loaded_key() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 16, 2, Some(&[4, 9])).unwrap();
+
+        let file = TempFile::new("test_commit_matches_loaded_key");
+        MappedCommitterKey::write(&ck, &file.0).unwrap();
+        // a small window so that the MSMs span several of them
+        let mapped = MappedCommitterKey::<Bn254>::open(&file.0)
+            .unwrap()
+            .with_window_size(3);
+        assert_eq!(mapped.supported_degree(), 16);
+        assert_eq!(mapped.enforced_degree_bounds(), Some(&[4, 9][..]));
+
+        let polynomials = [
+            LabeledPolynomial::new(
+                String::from("plain"),
+                DensePolynomial::rand(16, rng),
+                None,
+                None,
+            ),
+            LabeledPolynomial::new(
+                String::from("hiding"),
+                DensePolynomial::rand(10, rng),
+                None,
+                Some(2),
+            ),
+            LabeledPolynomial::new(
+                String::from("bounded"),
+                DensePolynomial::rand(9, rng),
+                Some(9),
+                Some(1),
+            ),
+            LabeledPolynomial::new(
+                String::from("lower_bound"),
+                DensePolynomial::rand(3, rng),
+                Some(4),
+                None,
+            ),
+        ];
+
+        let (expected, expected_rands) =
+            PC::commit(&ck, &polynomials, Some(&mut StdRng::seed_from_u64(7))).unwrap();
+        let (commitments, rands) = mapped
+            .commit(&polynomials, Some(&mut StdRng::seed_from_u64(7)))
+            .unwrap();
+        for (commitment, expected) in commitments.iter().zip(&expected) {
+            assert_eq!(commitment.label(), expected.label());
+            assert_eq!(commitment.degree_bound(), expected.degree_bound());
+            assert_eq!(commitment.commitment(), expected.commitment());
+        }
+        assert_eq!(rands, expected_rands);
+
+        // the loaded key opens the commitments
+        let loaded = mapped.to_committer_key().unwrap();
+        assert_eq!(loaded.powers, ck.powers);
+        assert_eq!(loaded.shifted_powers, ck.shifted_powers);
+        let point = F::from(5u64);
+        let proof = PC::open(
+            &loaded,
+            &polynomials,
+            &commitments,
+            &point,
+            F::from(3u64),
+            &rands,
+            None,
+        )
+        .unwrap();
+        let values = polynomials
+            .iter()
+            .map(|p| p.evaluate(&point))
+            .collect::<Vec<_>>();
+        assert!(PC::check(
+            &vk,
+            &commitments,
+            &point,
+            values,
+            &proof,
+            F::from(3u64),
+            None
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_rejects_unsupported_polynomials() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 8, 1, Some(&[4])).unwrap();
+        let file = TempFile::new("test_rejects_unsupported_polynomials");
+        MappedCommitterKey::write(&ck, &file.0).unwrap();
+        let mapped = MappedCommitterKey::<Bn254>::open(&file.0).unwrap();
+
+        let commit = |degree: usize, degree_bound: Option<usize>| {
+            let p = LabeledPolynomial::new(
+                String::from("p"),
+                DensePolynomial::<F>::rand(degree, &mut test_rng()),
+                degree_bound,
+                None,
+            );
+            mapped.commit(&[p], None).map(|_| ())
+        };
+        assert_eq!(commit(8, None), Ok(()));
+        assert!(matches!(commit(9, None), Err(Error::PCError { .. })));
+        assert!(matches!(commit(3, Some(5)), Err(Error::PCError { .. })));
+        assert!(matches!(commit(5, Some(4)), Err(Error::PCError { .. })));
+
+        // hiding needs an rng
+        let p = LabeledPolynomial::new(
+            String::from("p"),
+            DensePolynomial::<F>::rand(3, rng),
+            None,
+            Some(1),
+        );
+        assert!(matches!(
+            mapped.commit(&[p], None),
+            Err(Error::PCError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_malformed_files() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 8, 1, Some(&[4])).unwrap();
+        let file = TempFile::new("test_rejects_malformed_files");
+        MappedCommitterKey::write(&ck, &file.0).unwrap();
+
+        let bytes = fs::read(&file.0).unwrap();
+        fs::write(&file.0, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(matches!(
+            MappedCommitterKey::<Bn254>::open(&file.0),
+            Err(Error::KeyFileError(_))
+        ));
+
+        // sizes in the header that overflow the offsets of the sections
+        let header_with_powers = |powers_len: usize| {
+            let mut header = Vec::new();
+            8usize.serialize(&mut header).unwrap();
+            None::<Vec<usize>>.serialize(&mut header).unwrap();
+            Vec::<G1Affine>::new().serialize(&mut header).unwrap();
+            powers_len.serialize(&mut header).unwrap();
+            Some(powers_len).serialize(&mut header).unwrap();
+            header
+        };
+        for (header_len, header) in [
+            (u64::MAX, header_with_powers(1)),
+            (
+                header_with_powers(0).len() as u64,
+                header_with_powers(usize::MAX),
+            ),
+            (
+                header_with_powers(0).len() as u64,
+                header_with_powers(usize::MAX / 64),
+            ),
+        ] {
+            let bytes = [&bytes[..8], &header_len.to_le_bytes(), &header].concat();
+            fs::write(&file.0, bytes).unwrap();
+            assert!(matches!(
+                MappedCommitterKey::<Bn254>::open(&file.0),
+                Err(Error::KeyFileError(_))
+            ));
+        }
+
+        fs::write(&file.0, b"not a key").unwrap();
+        assert!(matches!(
+            MappedCommitterKey::<Bn254>::open(&file.0),
+            Err(Error::KeyFileError(_))
+        ));
+    }
+}
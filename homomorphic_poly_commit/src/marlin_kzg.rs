@@ -1,13 +1,25 @@
 use std::collections::BTreeMap;
 
-use ark_ec::PairingEngine;
-use ark_poly::univariate::DensePolynomial;
+use ark_ec::{msm::VariableBaseMSM, PairingEngine, ProjectiveCurve};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::{
+    univariate::{DensePolynomial, SparsePolynomial},
+    Polynomial,
+};
 use ark_poly_commit::{
-    kzg10, marlin_pc::MarlinKZG10, LCTerm, LabeledCommitment, LinearCombination, PCCommitment,
+    kzg10,
+    marlin_pc::{self, MarlinKZG10},
+    LCTerm, LabeledCommitment, LabeledPolynomial, LinearCombination, PCCommitment, PCCommitterKey,
     PCRandomness, PolynomialCommitment,
 };
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::RngCore;
 
-use crate::{error::Error, AdditivelyHomomorphicPCS};
+use crate::{
+    error::{to_pc_error, Error},
+    shared_randomness::kzg10_hiding_commitment,
+    AdditivelyHomomorphicPCS,
+};
 
 /// The Default KZG-style commitment scheme
 pub type KZG10<E> = MarlinKZG10<E, DensePolynomial<<E as PairingEngine>::Fr>>;
@@ -64,8 +76,8 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for MarlinKZG10<E, DenseP
             .collect::<Result<BTreeMap<_, (_, _)>, Error>>()?;
 
         // initial values
-        let mut aggregate_commitment = kzg10::Commitment::empty();
-        let mut aggregate_shifted_commitment = kzg10::Commitment::empty();
+        let mut terms = Vec::with_capacity(lc.len());
+        let mut coefficients = Vec::with_capacity(lc.len());
         let mut aggregate_randomness = kzg10::Randomness::empty();
         let mut aggregate_shifted_randomness = kzg10::Randomness::empty();
 
@@ -78,12 +90,6 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for MarlinKZG10<E, DenseP
                 LCTerm::PolyLabel(label) => match label_comm_mapping.get(label) {
                     Some((comm, rand)) => {
                         if degree_bound.is_some() {
-                            aggregate_shifted_commitment += (
-                                *coef,
-                                &comm.shifted_comm.expect(
-                                    "Degree bounded polynomial must have shifted commitment",
-                                ),
-                            );
                             aggregate_shifted_randomness += (
                                 *coef,
                                 &rand.shifted_rand.clone().expect(
@@ -92,7 +98,12 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for MarlinKZG10<E, DenseP
                             );
                         }
 
-                        aggregate_commitment += (*coef, &comm.comm);
+                        // shifted parts only take part in the aggregation under a degree bound
+                        terms.push(Self::Commitment {
+                            comm: comm.comm,
+                            shifted_comm: degree_bound.and(comm.shifted_comm),
+                        });
+                        coefficients.push(*coef);
                         aggregate_randomness += (*coef, &rand.rand);
                     }
                     None => {
@@ -106,16 +117,26 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for MarlinKZG10<E, DenseP
             }
         }
 
-        let (shifted_comm, shifted_rand) = match degree_bound.is_some() {
-            true => (
-                Some(aggregate_shifted_commitment),
+        let aggregate_commitment = Self::msm_commitments(&terms, &coefficients)?;
+
+        let (shifted_comm, shifted_rand) = match degree_bound {
+            Some(_) if terms.is_empty() => (
+                Some(kzg10::Commitment::empty()),
                 Some(aggregate_shifted_randomness),
             ),
-            false => (None, None),
+            Some(_) => (
+                Some(
+                    aggregate_commitment
+                        .shifted_comm
+                        .expect("Degree bounded polynomial must have shifted commitment"),
+                ),
+                Some(aggregate_shifted_randomness),
+            ),
+            None => (None, None),
         };
 
         let commitment = Self::Commitment {
-            comm: aggregate_commitment,
+            comm: aggregate_commitment.comm,
             shifted_comm,
         };
 
@@ -129,18 +150,363 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for MarlinKZG10<E, DenseP
             randomness,
         ))
     }
+
+    fn msm_commitments(
+        commitments: &[Self::Commitment],
+        scalars: &[E::Fr],
+    ) -> Result<Self::Commitment, Error> {
+        if commitments.len() != scalars.len() {
+            return Err(Error::InputLengthError(format!(
+                "Got {} commitments but {} scalars",
+                commitments.len(),
+                scalars.len()
+            )));
+        }
+
+        let scalars = scalars
+            .iter()
+            .map(|scalar| scalar.into_repr())
+            .collect::<Vec<_>>();
+        let bases = commitments
+            .iter()
+            .map(|commitment| commitment.comm.0)
+            .collect::<Vec<_>>();
+        let comm = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+
+        // either every commitment has a shifted part or none of them do
+        if commitments
+            .iter()
+            .all(|commitment| commitment.shifted_comm.is_none())
+        {
+            return Ok(Self::Commitment {
+                comm: kzg10::Commitment(comm.into_affine()),
+                shifted_comm: None,
+            });
+        }
+        let shifted_bases = commitments
+            .iter()
+            .map(|commitment| commitment.shifted_comm.map(|shifted| shifted.0))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                Error::MismatchedDegreeBounds(String::from(
+                    "Cannot combine commitments with and without a shifted commitment",
+                ))
+            })?;
+        let shifted_comm = VariableBaseMSM::multi_scalar_mul(&shifted_bases, &scalars);
+
+        // normalize both accumulators with a single inversion
+        let affine = E::G1Projective::batch_normalization_into_affine(&[comm, shifted_comm]);
+        Ok(Self::Commitment {
+            comm: kzg10::Commitment(affine[0]),
+            shifted_comm: Some(kzg10::Commitment(affine[1])),
+        })
+    }
+
+    fn commit_sparse(
+        ck: &Self::CommitterKey,
+        polynomials: &[LabeledPolynomial<E::Fr, SparsePolynomial<E::Fr>>],
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::Randomness>,
+        ),
+        Error,
+    > {
+        // A hiding commitment to p is the commitment to the coefficients of p plus a hiding commitment to 0 with the
+        // same bounds. The latter comes from the dense path, which also checks the bounds against the key, so that
+        // only the MSMs over the coefficients of p are sparse.
+        let zeros = polynomials
+            .iter()
+            .map(|p| {
+                LabeledPolynomial::new(
+                    p.label().clone(),
+                    DensePolynomial::zero(),
+                    p.degree_bound(),
+                    p.hiding_bound(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let (zero_commitments, rands) =
+            Self::commit(ck, &zeros, rng).map_err(to_pc_error::<E::Fr, Self>)?;
+
+        let sparse_msm = |powers: &[E::G1Affine], p: &SparsePolynomial<E::Fr>| {
+            let (bases, scalars): (Vec<_>, Vec<_>) = p
+                .iter()
+                .map(|(i, coeff)| (powers[*i], coeff.into_repr()))
+                .unzip();
+            VariableBaseMSM::multi_scalar_mul(&bases, &scalars)
+        };
+
+        let commitments = polynomials
+            .iter()
+            .zip(zero_commitments)
+            .map(|(p, zero)| {
+                let degree = p.polynomial().degree();
+                if degree > ck.supported_degree() {
+                    return Err(to_pc_error::<E::Fr, Self>(
+                        ark_poly_commit::Error::TooManyCoefficients {
+                            num_coefficients: degree + 1,
+                            num_powers: ck.powers.len(),
+                        },
+                    ));
+                }
+
+                let mut comm = sparse_msm(&ck.powers, p.polynomial());
+                comm.add_assign_mixed(&zero.commitment().comm.0);
+
+                let shifted_comm = match (p.degree_bound(), zero.commitment().shifted_comm) {
+                    (Some(degree_bound), Some(zero_shifted)) => {
+                        if degree > degree_bound {
+                            return Err(to_pc_error::<E::Fr, Self>(
+                                ark_poly_commit::Error::IncorrectDegreeBound {
+                                    poly_degree: degree,
+                                    degree_bound,
+                                    supported_degree: ck.supported_degree(),
+                                    label: p.label().clone(),
+                                },
+                            ));
+                        }
+                        let shifted_powers = ck.shifted_powers(degree_bound).ok_or_else(|| {
+                            to_pc_error::<E::Fr, Self>(
+                                ark_poly_commit::Error::UnsupportedDegreeBound(degree_bound),
+                            )
+                        })?;
+                        let mut shifted = sparse_msm(&shifted_powers.powers_of_g, p.polynomial());
+                        shifted.add_assign_mixed(&zero_shifted.0);
+                        Some(kzg10::Commitment(shifted.into_affine()))
+                    }
+                    _ => None,
+                };
+
+                Ok(LabeledCommitment::new(
+                    p.label().clone(),
+                    Self::Commitment {
+                        comm: kzg10::Commitment(comm.into_affine()),
+                        shifted_comm,
+                    },
+                    p.degree_bound(),
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok((commitments, rands))
+    }
+
+    fn commit_randomness(
+        ck: &Self::CommitterKey,
+        degree_bound: Option<usize>,
+        randomness: &Self::Randomness,
+    ) -> Result<Self::Commitment, Error> {
+        let comm = kzg10_hiding_commitment(&ck.powers(), &randomness.rand)?;
+
+        // the shifted commitment is blinded with the same powers of gamma_g as the unshifted one
+        let shifted_comm = match (degree_bound, &randomness.shifted_rand) {
+            (Some(_), Some(shifted_rand)) => {
+                Some(kzg10_hiding_commitment(&ck.powers(), shifted_rand)?)
+            }
+            (Some(_), None) => Some(kzg10::Commitment::empty()),
+            (None, _) => None,
+        };
+
+        Ok(marlin_pc::Commitment { comm, shifted_comm })
+    }
+
+    fn is_hiding(randomness: &Self::Randomness) -> bool {
+        !randomness.rand.blinding_polynomial.is_zero()
+    }
+
+    fn commitment_size(degree_bounded: bool) -> usize {
+        let comm = kzg10::Commitment::<E>::empty();
+        marlin_pc::Commitment {
+            comm,
+            shifted_comm: degree_bounded.then_some(comm),
+        }
+        .serialized_size()
+    }
+
+    fn opening_proof_size(_supported_degree: usize, hiding: bool) -> usize {
+        kzg10::Proof::<E> {
+            w: E::G1Affine::zero(),
+            random_v: hiding.then(E::Fr::zero),
+        }
+        .serialized_size()
+    }
+}
+
+/// A committer key of [`KZG10`] held other than as a [`marlin_pc::CommitterKey`], which computes the MSMs of a
+/// commitment over its powers of g itself
+pub(crate) trait CommitterBases<E: PairingEngine> {
+    fn max_degree(&self) -> usize;
+
+    fn enforced_degree_bounds(&self) -> Option<&[usize]>;
+
+    fn powers_of_gamma_g(&self) -> &[E::G1Affine];
+
+    /// Number of powers of g, or of shifted powers if `shifted`, which the key may lack
+    fn num_powers(&self, shifted: bool) -> Option<usize>;
+
+    /// `sum_i scalars[i] * powers[shift + i]` over the powers of g, or over the shifted powers if `shifted`
+    fn msm(&self, shifted: bool, shift: usize, scalars: &[E::Fr])
+        -> Result<E::G1Projective, Error>;
+}
+
+/// Commit to `polynomials` with `key` as [`PolynomialCommitment::commit`] does with the equivalent
+/// [`marlin_pc::CommitterKey`], so that the commitments and randomness are the same for the same rng
+#[allow(clippy::type_complexity)]
+pub(crate) fn commit_with_bases<'a, E: PairingEngine, K: CommitterBases<E>>(
+    key: &K,
+    polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<E::Fr, DensePolynomial<E::Fr>>>,
+    mut rng: Option<&mut dyn RngCore>,
+) -> Result<
+    (
+        Vec<LabeledCommitment<marlin_pc::Commitment<E>>>,
+        Vec<KZGRandomness<E>>,
+    ),
+    Error,
+> {
+    let mut commitments = Vec::new();
+    let mut rands = Vec::new();
+    for p in polynomials {
+        check_degree_bound(key, p)?;
+        let coeffs = &p.polynomial().coeffs;
+
+        let (comm, rand) = kzg10_commit(key, false, 0, coeffs, p.hiding_bound(), &mut rng)?;
+        let (shifted_comm, shifted_rand) = match p.degree_bound() {
+            Some(degree_bound) => {
+                // the shifted powers start at the largest enforced bound, which check_degree_bound made sure exists
+                let max_bound = key
+                    .enforced_degree_bounds()
+                    .and_then(|bounds| bounds.last().copied())
+                    .unwrap_or(degree_bound);
+                let (shifted_comm, shifted_rand) = kzg10_commit(
+                    key,
+                    true,
+                    max_bound - degree_bound,
+                    coeffs,
+                    p.hiding_bound(),
+                    &mut rng,
+                )?;
+                (Some(shifted_comm), Some(shifted_rand))
+            }
+            None => (None, None),
+        };
+
+        commitments.push(LabeledCommitment::new(
+            p.label().clone(),
+            marlin_pc::Commitment { comm, shifted_comm },
+            p.degree_bound(),
+        ));
+        rands.push(marlin_pc::Randomness { rand, shifted_rand });
+    }
+    Ok((commitments, rands))
+}
+
+/// The degree bound of `p` must be enforced by the key and lie between the degree of `p` and the max degree
+fn check_degree_bound<E: PairingEngine, K: CommitterBases<E>>(
+    key: &K,
+    p: &LabeledPolynomial<E::Fr, DensePolynomial<E::Fr>>,
+) -> Result<(), Error> {
+    let degree_bound = match p.degree_bound() {
+        Some(degree_bound) => degree_bound,
+        None => return Ok(()),
+    };
+    let pc_error = match key.enforced_degree_bounds() {
+        Some(bounds)
+            if bounds.binary_search(&degree_bound).is_ok() && key.num_powers(true).is_some() =>
+        {
+            if degree_bound >= p.degree() && degree_bound <= key.max_degree() {
+                return Ok(());
+            }
+            ark_poly_commit::Error::IncorrectDegreeBound {
+                poly_degree: p.degree(),
+                degree_bound,
+                supported_degree: key.num_powers(false).unwrap_or(0).saturating_sub(1),
+                label: p.label().clone(),
+            }
+        }
+        _ => ark_poly_commit::Error::UnsupportedDegreeBound(degree_bound),
+    };
+    Err(to_pc_error::<E::Fr, KZG10<E>>(pc_error))
+}
+
+/// A KZG10 commitment to the polynomial with coefficients `coeffs`, over the powers of g from `shift`, or over the
+/// shifted powers if `shifted`
+#[allow(clippy::type_complexity)]
+fn kzg10_commit<E: PairingEngine, K: CommitterBases<E>>(
+    key: &K,
+    shifted: bool,
+    shift: usize,
+    coeffs: &[E::Fr],
+    hiding_bound: Option<usize>,
+    rng: &mut Option<&mut dyn RngCore>,
+) -> Result<
+    (
+        kzg10::Commitment<E>,
+        kzg10::Randomness<E::Fr, DensePolynomial<E::Fr>>,
+    ),
+    Error,
+> {
+    let num_powers = key.num_powers(shifted).unwrap_or(0).saturating_sub(shift);
+    if coeffs.len() > num_powers {
+        return Err(to_pc_error::<E::Fr, KZG10<E>>(
+            ark_poly_commit::Error::TooManyCoefficients {
+                num_coefficients: coeffs.len(),
+                num_powers,
+            },
+        ));
+    }
+    let mut commitment = key.msm(shifted, shift, coeffs)?;
+
+    let powers_of_gamma_g = key.powers_of_gamma_g();
+    let randomness = match hiding_bound {
+        Some(hiding_bound) => {
+            let rng = rng.as_deref_mut().ok_or_else(|| {
+                to_pc_error::<E::Fr, KZG10<E>>(ark_poly_commit::Error::MissingRng)
+            })?;
+            let randomness = kzg10::Randomness::<E::Fr, DensePolynomial<E::Fr>>::rand(
+                hiding_bound,
+                false,
+                None,
+                &mut &mut *rng,
+            );
+            let hiding_poly_degree = randomness.blinding_polynomial.degree();
+            if hiding_poly_degree == 0 {
+                return Err(to_pc_error::<E::Fr, KZG10<E>>(
+                    ark_poly_commit::Error::HidingBoundIsZero,
+                ));
+            }
+            if hiding_poly_degree >= powers_of_gamma_g.len() {
+                return Err(to_pc_error::<E::Fr, KZG10<E>>(
+                    ark_poly_commit::Error::HidingBoundToolarge {
+                        hiding_poly_degree,
+                        num_powers: powers_of_gamma_g.len(),
+                    },
+                ));
+            }
+            randomness
+        }
+        None => kzg10::Randomness::empty(),
+    };
+    let powers = kzg10::Powers::<E> {
+        powers_of_g: (&[][..]).into(),
+        powers_of_gamma_g: powers_of_gamma_g.into(),
+    };
+    commitment.add_assign_mixed(&kzg10_hiding_commitment(&powers, &randomness)?.0);
+
+    Ok((kzg10::Commitment(commitment.into_affine()), randomness))
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{marlin_kzg::KZG10, AdditivelyHomomorphicPCS};
+    use crate::{error::Error, marlin_kzg::KZG10, AdditivelyHomomorphicPCS};
     use ark_bn254::{Bn254, Fr};
     use ark_ff::One;
     use ark_ff::UniformRand;
     use ark_poly::univariate::DensePolynomial;
     use ark_poly::UVPolynomial;
     use ark_poly_commit::LinearCombination;
-    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_poly_commit::{kzg10, LabeledPolynomial, PCCommitment, PolynomialCommitment};
     use ark_std::rand::thread_rng;
     use rand_core::OsRng;
 
@@ -255,4 +621,177 @@ mod test {
 
         assert_eq!(true, res)
     }
+
+    #[test]
+    fn test_msm_commitments() {
+        let rng = &mut thread_rng();
+        let maximum_degree: usize = 16;
+        let degree_bound = 10;
+
+        let pp = PC::setup(maximum_degree, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&pp, maximum_degree, 0, Some(&[degree_bound])).unwrap();
+
+        let bounded = (0..20)
+            .map(|i| {
+                LabeledPolynomial::new(
+                    format!("p_{}", i),
+                    DensePolynomial::rand(7, rng),
+                    Some(degree_bound),
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (commitments, _) = PC::commit(&ck, &bounded, None).unwrap();
+        let commitments = commitments
+            .iter()
+            .map(|comm| *comm.commitment())
+            .collect::<Vec<_>>();
+        let scalars = (0..20).map(|_| F::rand(rng)).collect::<Vec<_>>();
+
+        // naive accumulation in affine form
+        let mut expected_comm = kzg10::Commitment::empty();
+        let mut expected_shifted_comm = kzg10::Commitment::empty();
+        for (comm, scalar) in commitments.iter().zip(scalars.iter()) {
+            expected_comm += (*scalar, &comm.comm);
+            expected_shifted_comm += (*scalar, &comm.shifted_comm.unwrap());
+        }
+
+        let msm = PC::msm_commitments(&commitments, &scalars).unwrap();
+        assert_eq!(msm.comm, expected_comm);
+        assert_eq!(msm.shifted_comm, Some(expected_shifted_comm));
+
+        // commitments with and without a shifted part cannot be combined
+        let unbounded = [LabeledPolynomial::new(
+            String::from("q"),
+            DensePolynomial::rand(7, rng),
+            None,
+            None,
+        )];
+        let (unbounded_commitments, _) = PC::commit(&ck, &unbounded, None).unwrap();
+        let mixed = [commitments[0], *unbounded_commitments[0].commitment()];
+        assert!(matches!(
+            PC::msm_commitments(&mixed, &scalars[..2]),
+            Err(Error::MismatchedDegreeBounds(_))
+        ));
+        assert!(matches!(
+            PC::msm_commitments(&commitments, &scalars[..2]),
+            Err(Error::InputLengthError(_))
+        ));
+    }
+
+    #[test]
+    fn test_commitment_arithmetic() {
+        let rng = &mut thread_rng();
+        let maximum_degree: usize = 16;
+        let degree_bound = 10;
+
+        let pp = PC::setup(maximum_degree, None, &mut OsRng).unwrap();
+        let (ck, _) = PC::trim(&pp, maximum_degree, 0, Some(&[degree_bound])).unwrap();
+
+        let a = DensePolynomial::rand(7, rng);
+        let b = DensePolynomial::rand(5, rng);
+        let k = F::rand(rng);
+        let polynomials = [a.clone(), b.clone(), &a + &b, &a - &b, &a * k]
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                LabeledPolynomial::new(format!("p_{}", i), p.clone(), Some(degree_bound), None)
+            })
+            .collect::<Vec<_>>();
+
+        // without hiding, commitments are deterministic and can be compared directly
+        let (commitments, _) = PC::commit(&ck, &polynomials, None).unwrap();
+        let commitments = commitments
+            .iter()
+            .map(|comm| *comm.commitment())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            PC::add(&commitments[0], &commitments[1]).unwrap(),
+            commitments[2]
+        );
+        assert_eq!(
+            PC::sub(&commitments[0], &commitments[1]).unwrap(),
+            commitments[3]
+        );
+        assert_eq!(PC::scale(&commitments[0], k).unwrap(), commitments[4]);
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let rng = &mut thread_rng();
+        let maximum_degree: usize = 16;
+        let hiding_bound = 1;
+        let degree_bound = 10;
+
+        let pp = PC::setup(maximum_degree, None, &mut OsRng).unwrap();
+        let (ck, vk) = PC::trim(&pp, maximum_degree, hiding_bound, Some(&[degree_bound])).unwrap();
+
+        let polynomials = [LabeledPolynomial::new(
+            String::from("a"),
+            DensePolynomial::rand(7, rng),
+            Some(degree_bound),
+            Some(hiding_bound),
+        )];
+
+        let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+        let (rerandomized, delta_rand) =
+            PC::rerandomize(&ck, &commitments[0], hiding_bound, rng).unwrap();
+
+        assert_eq!(rerandomized.label(), commitments[0].label());
+        assert_eq!(rerandomized.degree_bound(), Some(degree_bound));
+        assert_ne!(rerandomized.commitment(), commitments[0].commitment());
+        let rerandomized = [rerandomized];
+
+        let evaluation_point = F::rand(rng);
+        let eval = polynomials[0].evaluate(&evaluation_point);
+        let opening_challenge = F::rand(rng);
+
+        // open against the re-randomized commitment with the accumulated randomness
+        let accumulated_rand = rands[0].clone() + &delta_rand;
+        let proof = PC::open(
+            &ck,
+            &polynomials,
+            &rerandomized,
+            &evaluation_point,
+            opening_challenge,
+            &[accumulated_rand],
+            Some(rng),
+        )
+        .unwrap();
+        let res = PC::check(
+            &vk,
+            &rerandomized,
+            &evaluation_point,
+            vec![eval],
+            &proof,
+            opening_challenge,
+            Some(rng),
+        )
+        .unwrap();
+        assert!(res);
+
+        // the original randomness alone no longer opens the re-randomized commitment
+        let proof = PC::open(
+            &ck,
+            &polynomials,
+            &rerandomized,
+            &evaluation_point,
+            opening_challenge,
+            &rands,
+            Some(rng),
+        )
+        .unwrap();
+        let res = PC::check(
+            &vk,
+            &rerandomized,
+            &evaluation_point,
+            vec![eval],
+            &proof,
+            opening_challenge,
+            Some(rng),
+        )
+        .unwrap();
+        assert!(!res);
+    }
 }
@@ -0,0 +1,348 @@
+//! Opening one committed polynomial at several points with a proof whose size does not depend on the number of
+//! points, following the single-point reduction of Boneh, Drake, Fisch and Gabizon (ePrint 2020/081).
+//!
+//! To show that `p(z_i) = v_i` for the points `S = {z_1, ..., z_k}`, the prover commits to the quotient
+//! `q = (p - I) / Z_S`, where `I` interpolates the values over `S` and `Z_S` vanishes on `S`. Given a challenge `z`
+//! derived from that commitment, the polynomial `L = p - Z_S(z) * q` evaluates to `I(z)` at `z`. Its commitment is
+//! derived homomorphically from those of `p` and `q`, so a single opening of `L` at `z` convinces the verifier. Unlike
+//! the textbook KZG multi-point check, this needs no powers of the trapdoor in G2.
+
+use ark_ff::{batch_inversion, PrimeField, Zero};
+use ark_poly::{
+    univariate::{DenseOrSparsePolynomial, DensePolynomial},
+    Polynomial, UVPolynomial,
+};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, LinearCombination};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::{
+    io::{Read, Write},
+    rand::RngCore,
+};
+use derivative::Derivative;
+
+use crate::{
+    error::{to_pc_error, Error},
+    AdditivelyHomomorphicPCS,
+};
+
+/// Proof that a committed polynomial takes given values at several points: a commitment to the quotient and a single
+/// opening proof, whatever the number of points
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "PC::Proof: PartialEq"),
+    Eq(bound = "PC::Proof: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct MultiPointProof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub quotient_commitment: PC::Commitment,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub opening: PC::Proof,
+}
+
+pub(crate) fn open<F, PC, R>(
+    ck: &PC::CommitterKey,
+    polynomial: &LabeledPolynomial<F, DensePolynomial<F>>,
+    commitment: &LabeledCommitment<PC::Commitment>,
+    randomness: &PC::Randomness,
+    points: &[F],
+    challenge: impl FnOnce(&PC::Commitment) -> F,
+    rng: &mut R,
+) -> Result<MultiPointProof<F, PC>, Error>
+where
+    F: PrimeField,
+    PC: AdditivelyHomomorphicPCS<F>,
+    R: RngCore,
+{
+    check_points(points)?;
+    let values = points
+        .iter()
+        .map(|point| polynomial.evaluate(point))
+        .collect::<Vec<_>>();
+
+    // q = (p - I) / Z_S, which is exact since p - I vanishes on S
+    let numerator = polynomial.polynomial() - &interpolate(points, &values);
+    let (quotient, _) = DenseOrSparsePolynomial::from(numerator)
+        .divide_with_q_and_r(&DenseOrSparsePolynomial::from(vanishing_polynomial(points)))
+        .ok_or_else(|| Error::InputLengthError(String::from("Cannot divide by Z_S")))?;
+    let quotient = LabeledPolynomial::new(
+        quotient_label(commitment.label()),
+        quotient,
+        polynomial.degree_bound(),
+        polynomial.hiding_bound(),
+    );
+    let (quotient_commitments, quotient_rands) =
+        PC::commit(ck, [&quotient], Some(rng)).map_err(to_pc_error::<F, PC>)?;
+
+    let z = challenge(quotient_commitments[0].commitment());
+    let scale = evaluate_vanishing(points, z);
+
+    // L = p - Z_S(z) * q, with its commitment and randomness derived homomorphically
+    let combination = LabeledPolynomial::new(
+        combination_label(commitment.label()),
+        polynomial.polynomial() - &(quotient.polynomial() * scale),
+        polynomial.degree_bound(),
+        polynomial.hiding_bound(),
+    );
+    let (combination_commitment, combination_rand) = PC::aggregate_commitments(
+        &[commitment.clone(), quotient_commitments[0].clone()],
+        Some(vec![randomness.clone(), quotient_rands[0].clone()]),
+        &combination_lc(commitment.label(), scale),
+    )?;
+
+    let opening = PC::open(
+        ck,
+        [&combination],
+        [&combination_commitment],
+        &z,
+        F::one(),
+        [&combination_rand],
+        Some(rng),
+    )
+    .map_err(to_pc_error::<F, PC>)?;
+
+    Ok(MultiPointProof {
+        quotient_commitment: quotient_commitments[0].commitment().clone(),
+        opening,
+    })
+}
+
+pub(crate) fn check<F, PC, R>(
+    vk: &PC::VerifierKey,
+    commitment: &LabeledCommitment<PC::Commitment>,
+    points: &[F],
+    values: &[F],
+    proof: &MultiPointProof<F, PC>,
+    challenge: impl FnOnce(&PC::Commitment) -> F,
+    rng: &mut R,
+) -> Result<bool, Error>
+where
+    F: PrimeField,
+    PC: AdditivelyHomomorphicPCS<F>,
+    R: RngCore,
+{
+    check_points(points)?;
+    if points.len() != values.len() {
+        return Err(Error::InputLengthError(format!(
+            "Got {} points but {} values",
+            points.len(),
+            values.len()
+        )));
+    }
+
+    let z = challenge(&proof.quotient_commitment);
+    let scale = evaluate_vanishing(points, z);
+    let quotient_commitment = LabeledCommitment::new(
+        quotient_label(commitment.label()),
+        proof.quotient_commitment.clone(),
+        commitment.degree_bound(),
+    );
+    let (combination_commitment, _) = PC::aggregate_commitments(
+        &[commitment.clone(), quotient_commitment],
+        None,
+        &combination_lc(commitment.label(), scale),
+    )?;
+
+    PC::check(
+        vk,
+        [&combination_commitment],
+        &z,
+        [interpolate(points, values).evaluate(&z)],
+        &proof.opening,
+        F::one(),
+        Some(rng),
+    )
+    .map_err(to_pc_error::<F, PC>)
+}
+
+fn check_points<F: PrimeField>(points: &[F]) -> Result<(), Error> {
+    if points.is_empty() {
+        return Err(Error::InputLengthError(String::from(
+            "A multi-point opening needs at least one point",
+        )));
+    }
+    for (i, point) in points.iter().enumerate() {
+        if points[..i].contains(point) {
+            return Err(Error::InputLengthError(format!(
+                "Point {} is given twice",
+                point
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn quotient_label(label: &str) -> String {
+    format!("{}_multi_point_quotient", label)
+}
+
+fn combination_label(label: &str) -> String {
+    format!("{}_multi_point_combination", label)
+}
+
+/// The linear combination p - scale * q
+fn combination_lc<F: PrimeField>(label: &str, scale: F) -> LinearCombination<F> {
+    LinearCombination::new(
+        combination_label(label),
+        vec![
+            (F::one(), label.to_string()),
+            (-scale, quotient_label(label)),
+        ],
+    )
+}
+
+/// Z_S(X) = (X - z_1) * ... * (X - z_k)
+fn vanishing_polynomial<F: PrimeField>(points: &[F]) -> DensePolynomial<F> {
+    points.iter().fold(
+        DensePolynomial::from_coefficients_vec(vec![F::one()]),
+        |acc, point| &acc * &DensePolynomial::from_coefficients_vec(vec![-*point, F::one()]),
+    )
+}
+
+fn evaluate_vanishing<F: PrimeField>(points: &[F], x: F) -> F {
+    points.iter().map(|point| x - point).product()
+}
+
+/// The polynomial of degree less than k taking `values[i]` at `points[i]`, in Lagrange form
+fn interpolate<F: PrimeField>(points: &[F], values: &[F]) -> DensePolynomial<F> {
+    let vanishing = vanishing_polynomial(points);
+
+    // the barycentric weights 1 / prod_{j != i} (z_i - z_j)
+    let mut weights = points
+        .iter()
+        .enumerate()
+        .map(|(i, z_i)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, z_j)| *z_i - z_j)
+                .product::<F>()
+        })
+        .collect::<Vec<_>>();
+    batch_inversion(&mut weights);
+
+    points.iter().zip(values).zip(weights).fold(
+        DensePolynomial::zero(),
+        |acc, ((point, value), weight)| {
+            // Z_S / (X - z_i) is exact
+            let (basis, _) = DenseOrSparsePolynomial::from(&vanishing)
+                .divide_with_q_and_r(&DenseOrSparsePolynomial::from(
+                    DensePolynomial::from_coefficients_vec(vec![-*point, F::one()]),
+                ))
+                .expect("X - z_i is not zero");
+            acc + &basis * (*value * weight)
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::interpolate;
+    use crate::{marlin_kzg, sonic_kzg, AdditivelyHomomorphicPCS};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::UniformRand;
+    use ark_poly::{univariate::DensePolynomial, Polynomial, UVPolynomial};
+    use ark_poly_commit::LabeledPolynomial;
+    use ark_std::rand::thread_rng;
+
+    type F = Fr;
+
+    /// Open a random polynomial at 4 points and check the opening, then check it against wrong values
+    fn multi_point_template<PC: AdditivelyHomomorphicPCS<F>>(degree_bound: Option<usize>) {
+        let rng = &mut thread_rng();
+        let maximum_degree = 16;
+        let pp = PC::setup(maximum_degree, None, rng).unwrap();
+        let degree_bounds = degree_bound.map(|bound| vec![bound]);
+        let (ck, vk) = PC::trim(&pp, maximum_degree, 1, degree_bounds.as_deref()).unwrap();
+
+        let polynomial = LabeledPolynomial::new(
+            String::from("p"),
+            DensePolynomial::rand(7, rng),
+            degree_bound,
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&polynomial], Some(rng)).unwrap();
+
+        let points = (0..4).map(|_| F::rand(rng)).collect::<Vec<_>>();
+        let values = points
+            .iter()
+            .map(|point| polynomial.evaluate(point))
+            .collect::<Vec<_>>();
+        let z = F::rand(rng);
+
+        let proof = PC::open_multi_point(
+            &ck,
+            &polynomial,
+            &commitments[0],
+            &rands[0],
+            &points,
+            |_| z,
+            rng,
+        )
+        .unwrap();
+        assert!(
+            PC::check_multi_point(&vk, &commitments[0], &points, &values, &proof, |_| z, rng)
+                .unwrap()
+        );
+
+        let mut wrong_values = values.clone();
+        wrong_values[2] += F::from(1u64);
+        assert!(!PC::check_multi_point(
+            &vk,
+            &commitments[0],
+            &points,
+            &wrong_values,
+            &proof,
+            |_| z,
+            rng
+        )
+        .unwrap());
+
+        // the opening only holds at the challenge it was produced for
+        assert!(!PC::check_multi_point(
+            &vk,
+            &commitments[0],
+            &points,
+            &values,
+            &proof,
+            |_| z + F::from(1u64),
+            rng
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_multi_point_marlin_kzg() {
+        multi_point_template::<marlin_kzg::KZG10<Bn254>>(None);
+    }
+
+    #[test]
+    fn test_multi_point_marlin_kzg_with_degree_bound() {
+        multi_point_template::<marlin_kzg::KZG10<Bn254>>(Some(10));
+    }
+
+    #[test]
+    fn test_multi_point_sonic_kzg() {
+        multi_point_template::<sonic_kzg::KZG10<Bn254>>(None);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let rng = &mut thread_rng();
+        let points = (0..5).map(|_| F::rand(rng)).collect::<Vec<_>>();
+        let values = (0..5).map(|_| F::rand(rng)).collect::<Vec<_>>();
+
+        let interpolation = interpolate(&points, &values);
+        assert!(interpolation.degree() < points.len());
+        for (point, value) in points.iter().zip(values) {
+            assert_eq!(interpolation.evaluate(point), value);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Openings deferred by the sub-proofs of a composed protocol, to be proven and checked in a single batch.
+//!
+//! Each sub-proof adds the polynomials it would open and the query set it would open them at. Its queries may be at
+//! points outside of any domain the other sub-proofs use, e.g. one sub-proof over K and another over H: the batch
+//! opening only groups queries by point. Labels are prefixed with the index of the sub-proof, so that sub-proofs may
+//! reuse the labels of their internal polynomials and points. Once every sub-proof has bound its evaluations to the
+//! transcript, the caller draws one opening challenge for the whole batch, which costs the verifier a single batch
+//! check (a single pairing check with KZG) instead of one per sub-proof.
+
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{Evaluations, LabeledCommitment, LabeledPolynomial, QuerySet};
+use ark_std::rand::RngCore;
+
+use crate::{
+    error::{to_pc_error, Error},
+    AdditivelyHomomorphicPCS,
+};
+
+fn namespaced(sub_proof: usize, label: &str) -> String {
+    format!("{}/{}", sub_proof, label)
+}
+
+fn namespaced_query_set<F: PrimeField>(sub_proof: usize, query_set: &QuerySet<F>) -> QuerySet<F> {
+    query_set
+        .iter()
+        .map(|(label, (point_label, point))| {
+            (
+                namespaced(sub_proof, label),
+                (namespaced(sub_proof, point_label), *point),
+            )
+        })
+        .collect()
+}
+
+/// The openings a prover defers, see the [module documentation](self)
+pub struct ProverAccumulator<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+    polynomials: Vec<LabeledPolynomial<F, DensePolynomial<F>>>,
+    commitments: Vec<LabeledCommitment<PC::Commitment>>,
+    rands: Vec<PC::Randomness>,
+    query_set: QuerySet<F>,
+    sub_proofs: usize,
+}
+
+impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> Default for ProverAccumulator<F, PC> {
+    fn default() -> Self {
+        Self {
+            polynomials: Vec::new(),
+            commitments: Vec::new(),
+            rands: Vec::new(),
+            query_set: QuerySet::new(),
+            sub_proofs: 0,
+        }
+    }
+}
+
+impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> ProverAccumulator<F, PC> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of sub-proofs added so far
+    pub fn len(&self) -> usize {
+        self.sub_proofs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sub_proofs == 0
+    }
+
+    /// Defer the openings of a sub-proof: the polynomials, with their commitments and randomness in the same order,
+    /// at the points of `query_set`
+    pub fn add<'a>(
+        &mut self,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<PC::Commitment>>,
+        rands: impl IntoIterator<Item = &'a PC::Randomness>,
+        query_set: &QuerySet<F>,
+    ) where
+        PC::Commitment: 'a,
+        PC::Randomness: 'a,
+    {
+        let sub_proof = self.sub_proofs;
+        self.polynomials
+            .extend(polynomials.into_iter().map(|polynomial| {
+                LabeledPolynomial::new(
+                    namespaced(sub_proof, polynomial.label()),
+                    polynomial.polynomial().clone(),
+                    polynomial.degree_bound(),
+                    polynomial.hiding_bound(),
+                )
+            }));
+        self.commitments
+            .extend(commitments.into_iter().map(|commitment| {
+                LabeledCommitment::new(
+                    namespaced(sub_proof, commitment.label()),
+                    commitment.commitment().clone(),
+                    commitment.degree_bound(),
+                )
+            }));
+        self.rands.extend(rands.into_iter().cloned());
+        self.query_set
+            .extend(namespaced_query_set(sub_proof, query_set));
+        self.sub_proofs += 1;
+    }
+
+    /// Prove every deferred opening at once, with an opening challenge drawn after all the sub-proofs
+    pub fn open<R: RngCore>(
+        &self,
+        ck: &PC::CommitterKey,
+        opening_challenge: F,
+        rng: &mut R,
+    ) -> Result<PC::BatchProof, Error> {
+        PC::batch_open(
+            ck,
+            &self.polynomials,
+            &self.commitments,
+            &self.query_set,
+            opening_challenge,
+            &self.rands,
+            Some(rng),
+        )
+        .map_err(to_pc_error::<F, PC>)
+    }
+}
+
+/// The openings a verifier defers, see the [module documentation](self). Sub-proofs must be added in the order the
+/// prover added them.
+pub struct VerifierAccumulator<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+    commitments: Vec<LabeledCommitment<PC::Commitment>>,
+    query_set: QuerySet<F>,
+    evaluations: Evaluations<F, F>,
+    sub_proofs: usize,
+}
+
+impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> Default for VerifierAccumulator<F, PC> {
+    fn default() -> Self {
+        Self {
+            commitments: Vec::new(),
+            query_set: QuerySet::new(),
+            evaluations: Evaluations::new(),
+            sub_proofs: 0,
+        }
+    }
+}
+
+impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> VerifierAccumulator<F, PC> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of sub-proofs added so far
+    pub fn len(&self) -> usize {
+        self.sub_proofs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sub_proofs == 0
+    }
+
+    /// Defer the check of the evaluations a sub-proof claims for the commitments at the points of `query_set`
+    pub fn add<'a>(
+        &mut self,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<PC::Commitment>>,
+        query_set: &QuerySet<F>,
+        evaluations: &Evaluations<F, F>,
+    ) where
+        PC::Commitment: 'a,
+    {
+        let sub_proof = self.sub_proofs;
+        self.commitments
+            .extend(commitments.into_iter().map(|commitment| {
+                LabeledCommitment::new(
+                    namespaced(sub_proof, commitment.label()),
+                    commitment.commitment().clone(),
+                    commitment.degree_bound(),
+                )
+            }));
+        self.query_set
+            .extend(namespaced_query_set(sub_proof, query_set));
+        self.evaluations.extend(
+            evaluations
+                .iter()
+                .map(|((label, point), value)| ((namespaced(sub_proof, label), *point), *value)),
+        );
+        self.sub_proofs += 1;
+    }
+
+    /// Check every deferred opening at once against the proof of [`ProverAccumulator::open`]
+    pub fn check<R: RngCore>(
+        &self,
+        vk: &PC::VerifierKey,
+        proof: &PC::BatchProof,
+        opening_challenge: F,
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        PC::batch_check(
+            vk,
+            &self.commitments,
+            &self.query_set,
+            &self.evaluations,
+            proof,
+            opening_challenge,
+            rng,
+        )
+        .map_err(to_pc_error::<F, PC>)
+    }
+}
@@ -0,0 +1,225 @@
+//! Opening hints, for a split prover where the owner of committed polynomials delegates proving about them.
+//!
+//! The owner commits to its polynomials and publishes the commitments, then hands the delegate one [`OpeningHint`]
+//! per polynomial: its evaluations over the smallest domain that determines it, with its label and bounds and the
+//! randomness of its commitment. The delegate rebuilds the oracles that provers take with [`from_hints`] and proves
+//! against the published commitments.
+//!
+//! The evaluations determine the polynomial, so a delegate learns it: hints spare the owner from shipping its own
+//! representation of the polynomials, not from trusting the delegate with them. A hint that does not match its
+//! commitment only yields a proof that fails to verify.
+//!
+//! ```ignore
+//! // owner
+//! let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng))?;
+//! let hints = opening_hint::hints(&polynomials, &rands)?;
+//! // delegate
+//! let (oracles, rands) = opening_hint::from_hints(&hints)?;
+//! ```
+
+use ark_ff::PrimeField;
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+};
+use ark_poly_commit::LabeledPolynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::io::{Read, Write};
+use derivative::Derivative;
+
+use crate::{error::Error, AdditivelyHomomorphicPCS};
+
+/// What a delegate needs to know of one committed polynomial to prove about it, see the
+/// [module documentation](self)
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(Clone(bound = ""), PartialEq(bound = "PC::Randomness: PartialEq"))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct OpeningHint<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+    pub label: String,
+    pub degree_bound: Option<usize>,
+    pub hiding_bound: Option<usize>,
+    /// Evaluations of the polynomial over the domain of as many elements
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub evaluations: Vec<F>,
+    /// Randomness of the commitment to the polynomial
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub randomness: PC::Randomness,
+}
+
+impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> OpeningHint<F, PC> {
+    /// The hint to a polynomial committed to with `randomness`
+    pub fn new(
+        polynomial: &LabeledPolynomial<F, DensePolynomial<F>>,
+        randomness: &PC::Randomness,
+    ) -> Result<Self, Error> {
+        let coeffs = polynomial.coeffs();
+        let domain = GeneralEvaluationDomain::<F>::new(coeffs.len().max(1)).ok_or_else(|| {
+            Error::InputLengthError(format!(
+                "No domain of size at least {} to evaluate {} over",
+                coeffs.len(),
+                polynomial.label()
+            ))
+        })?;
+
+        Ok(Self {
+            label: polynomial.label().clone(),
+            degree_bound: polynomial.degree_bound(),
+            hiding_bound: polynomial.hiding_bound(),
+            evaluations: domain.fft(coeffs),
+            randomness: randomness.clone(),
+        })
+    }
+
+    /// The polynomial interpolating the evaluations of the hint, with its label and bounds
+    pub fn to_oracle(&self) -> Result<LabeledPolynomial<F, DensePolynomial<F>>, Error> {
+        let domain = GeneralEvaluationDomain::<F>::new(self.evaluations.len())
+            .filter(|domain| domain.size() == self.evaluations.len())
+            .ok_or_else(|| {
+                Error::InputLengthError(format!(
+                    "The hint to {} has {} evaluations, which is not the size of a domain",
+                    self.label,
+                    self.evaluations.len()
+                ))
+            })?;
+
+        Ok(LabeledPolynomial::new(
+            self.label.clone(),
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.evaluations)),
+            self.degree_bound,
+            self.hiding_bound,
+        ))
+    }
+}
+
+/// The hints to polynomials committed to with `rands`, in the same order
+pub fn hints<'a, F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+    rands: &[PC::Randomness],
+) -> Result<Vec<OpeningHint<F, PC>>, Error> {
+    let polynomials = polynomials.into_iter().collect::<Vec<_>>();
+    if polynomials.len() != rands.len() {
+        return Err(Error::InputLengthError(format!(
+            "{} polynomials but {} randomness",
+            polynomials.len(),
+            rands.len()
+        )));
+    }
+
+    polynomials
+        .into_iter()
+        .zip(rands)
+        .map(|(polynomial, rand)| OpeningHint::new(polynomial, rand))
+        .collect()
+}
+
+/// The oracles rebuilt from hints and the randomness of their commitments, in the order of the hints
+#[allow(clippy::type_complexity)]
+pub fn from_hints<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    hints: &[OpeningHint<F, PC>],
+) -> Result<
+    (
+        Vec<LabeledPolynomial<F, DensePolynomial<F>>>,
+        Vec<PC::Randomness>,
+    ),
+    Error,
+> {
+    let oracles = hints
+        .iter()
+        .map(OpeningHint::to_oracle)
+        .collect::<Result<Vec<_>, _>>()?;
+    let rands = hints.iter().map(|hint| hint.randomness.clone()).collect();
+    Ok((oracles, rands))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        marlin_kzg::KZG10,
+        opening_hint::{from_hints, hints, OpeningHint},
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::UniformRand;
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PCRandomness, PolynomialCommitment};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::rand::thread_rng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    #[test]
+    fn test_hint_round_trip() {
+        let rng = &mut thread_rng();
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[15])).unwrap();
+
+        // degrees that are and are not one less than the size of a domain
+        let polynomials = [(6, None, None), (7, Some(15), Some(1)), (0, None, Some(1))]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (degree, degree_bound, hiding_bound))| {
+                LabeledPolynomial::new(
+                    format!("p_{}", i),
+                    DensePolynomial::rand(degree, rng),
+                    degree_bound,
+                    hiding_bound,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+
+        let hints = hints::<F, PC>(&polynomials, &rands).unwrap();
+        let mut bytes = Vec::new();
+        hints.serialize(&mut bytes).unwrap();
+        let hints = Vec::<OpeningHint<F, PC>>::deserialize(&bytes[..]).unwrap();
+
+        // the delegate opens the commitments of the owner with the rebuilt oracles
+        let (oracles, rands) = from_hints(&hints).unwrap();
+        for (oracle, polynomial) in oracles.iter().zip(&polynomials) {
+            assert_eq!(oracle.label(), polynomial.label());
+            assert_eq!(oracle.polynomial(), polynomial.polynomial());
+            assert_eq!(oracle.degree_bound(), polynomial.degree_bound());
+            assert_eq!(oracle.hiding_bound(), polynomial.hiding_bound());
+        }
+
+        let point = F::rand(rng);
+        let separation_challenge = F::rand(rng);
+        let proof = PC::open(
+            &ck,
+            &oracles,
+            &commitments,
+            &point,
+            separation_challenge,
+            &rands,
+            Some(rng),
+        )
+        .unwrap();
+        let evaluations = oracles.iter().map(|oracle| oracle.evaluate(&point));
+        assert!(PC::check(
+            &vk,
+            &commitments,
+            &point,
+            evaluations,
+            &proof,
+            separation_challenge,
+            Some(rng)
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_malformed_hint() {
+        let rng = &mut thread_rng();
+        let polynomial =
+            LabeledPolynomial::new("p".into(), DensePolynomial::rand(4, rng), None, None);
+        let mut hint = OpeningHint::<F, PC>::new(&polynomial, &PCRandomness::empty()).unwrap();
+        assert!(hints::<F, PC>([&polynomial], &[]).is_err());
+
+        hint.evaluations.pop();
+        assert!(hint.to_oracle().is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, ProjectiveCurve};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{
+    ipa_pc::{self, InnerProductArgPC},
+    LCTerm, LabeledCommitment, LinearCombination, PCRandomness,
+};
+use ark_serialize::CanonicalSerialize;
+use digest::Digest;
+
+use crate::{error::Error, AdditivelyHomomorphicPCS};
+
+/// A Pedersen vector commitment scheme with openings via an inner product argument. It needs no pairing and no
+/// trusted setup, at the cost of openings that are linear in the degree to verify, which makes it a cheap backend
+/// for prototypes and tests.
+pub type PedersenPC<G, D> =
+    InnerProductArgPC<G, D, DensePolynomial<<G as AffineCurve>::ScalarField>>;
+
+impl<G: AffineCurve, D: Digest> AdditivelyHomomorphicPCS<G::ScalarField>
+    for InnerProductArgPC<G, D, DensePolynomial<G::ScalarField>>
+{
+    fn aggregate_commitments(
+        commitments: &[LabeledCommitment<Self::Commitment>],
+        randomness: Option<Vec<Self::Randomness>>,
+        lc: &LinearCombination<G::ScalarField>,
+    ) -> Result<(LabeledCommitment<Self::Commitment>, Self::Randomness), Error> {
+        let degree_bound = commitments[0].degree_bound();
+
+        let randomness = randomness.map_or(
+            vec![
+                ipa_pc::Randomness {
+                    rand: G::ScalarField::zero(),
+                    shifted_rand: degree_bound.map(|_| G::ScalarField::zero()),
+                };
+                commitments.len()
+            ],
+            |rands| rands,
+        );
+
+        // create mapping of label -> commitment and fail if all degree bounds are not the same
+        let label_comm_mapping = commitments
+            .iter()
+            .zip(randomness.iter())
+            .map(|(comm, rand)| {
+                if comm.degree_bound() != degree_bound {
+                    // Can only accumulate commitments that have the same degree bound
+                    return Err(Error::MismatchedDegreeBounds(format!(
+                        "{} has degree bound {:?}, but {} has degree bound {:?}",
+                        commitments[0].label(),
+                        degree_bound,
+                        comm.label(),
+                        comm.degree_bound()
+                    )));
+                }
+                Ok((comm.label().clone(), (*comm.commitment(), rand.clone())))
+            })
+            .collect::<Result<BTreeMap<_, (_, _)>, Error>>()?;
+
+        // initial values
+        let mut terms = Vec::with_capacity(lc.len());
+        let mut coefficients = Vec::with_capacity(lc.len());
+        let mut aggregate_randomness = Self::Randomness::empty();
+        let mut aggregate_shifted_randomness = G::ScalarField::zero();
+
+        for (coef, term) in lc.iter() {
+            match term {
+                // No support for constant terms
+                LCTerm::One => return Err(Error::ConstantTermInAggregation),
+
+                // Find the corresponding commitment and randomness in our map; aggregate.
+                LCTerm::PolyLabel(label) => match label_comm_mapping.get(label) {
+                    Some((comm, rand)) => {
+                        // commitments without hiding carry no shifted randomness
+                        if degree_bound.is_some() {
+                            aggregate_shifted_randomness +=
+                                *coef * rand.shifted_rand.unwrap_or_else(G::ScalarField::zero);
+                        }
+
+                        // shifted parts only take part in the aggregation under a degree bound
+                        terms.push(Self::Commitment {
+                            comm: comm.comm,
+                            shifted_comm: degree_bound.and(comm.shifted_comm),
+                        });
+                        coefficients.push(*coef);
+                        aggregate_randomness.rand += *coef * rand.rand;
+                    }
+                    None => {
+                        return Err(Error::MissingCommitment(format!(
+                            "Could not find object with label '{}' when computing '{}'",
+                            label,
+                            lc.label()
+                        )))
+                    }
+                },
+            }
+        }
+
+        let mut aggregate_commitment = Self::msm_commitments(&terms, &coefficients)?;
+        if degree_bound.is_some() {
+            aggregate_commitment.shifted_comm = match terms.is_empty() {
+                true => Some(G::zero()),
+                false => Some(
+                    aggregate_commitment
+                        .shifted_comm
+                        .expect("Degree bounded polynomial must have shifted commitment"),
+                ),
+            };
+            aggregate_randomness.shifted_rand = Some(aggregate_shifted_randomness);
+        }
+
+        Ok((
+            LabeledCommitment::new(lc.label().clone(), aggregate_commitment, degree_bound),
+            aggregate_randomness,
+        ))
+    }
+
+    fn msm_commitments(
+        commitments: &[Self::Commitment],
+        scalars: &[G::ScalarField],
+    ) -> Result<Self::Commitment, Error> {
+        if commitments.len() != scalars.len() {
+            return Err(Error::InputLengthError(format!(
+                "Got {} commitments but {} scalars",
+                commitments.len(),
+                scalars.len()
+            )));
+        }
+
+        let scalars = scalars
+            .iter()
+            .map(|scalar| scalar.into_repr())
+            .collect::<Vec<_>>();
+        let bases = commitments
+            .iter()
+            .map(|commitment| commitment.comm)
+            .collect::<Vec<_>>();
+        let comm = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+
+        // either every commitment has a shifted part or none of them do
+        if commitments
+            .iter()
+            .all(|commitment| commitment.shifted_comm.is_none())
+        {
+            return Ok(Self::Commitment {
+                comm: comm.into_affine(),
+                shifted_comm: None,
+            });
+        }
+        let shifted_bases = commitments
+            .iter()
+            .map(|commitment| commitment.shifted_comm)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                Error::MismatchedDegreeBounds(String::from(
+                    "Cannot combine commitments with and without a shifted commitment",
+                ))
+            })?;
+        let shifted_comm = VariableBaseMSM::multi_scalar_mul(&shifted_bases, &scalars);
+
+        // normalize both accumulators with a single inversion
+        let affine = G::Projective::batch_normalization_into_affine(&[comm, shifted_comm]);
+        Ok(Self::Commitment {
+            comm: affine[0],
+            shifted_comm: Some(affine[1]),
+        })
+    }
+
+    fn commit_randomness(
+        ck: &Self::CommitterKey,
+        degree_bound: Option<usize>,
+        randomness: &Self::Randomness,
+    ) -> Result<Self::Commitment, Error> {
+        let comm = ck.s.mul(randomness.rand).into_affine();
+        let shifted_comm = degree_bound.map(|_| {
+            ck.s.mul(randomness.shifted_rand.unwrap_or_else(G::ScalarField::zero))
+                .into_affine()
+        });
+
+        Ok(ipa_pc::Commitment { comm, shifted_comm })
+    }
+
+    fn is_hiding(randomness: &Self::Randomness) -> bool {
+        !randomness.rand.is_zero()
+    }
+
+    fn commitment_size(degree_bounded: bool) -> usize {
+        ipa_pc::Commitment {
+            comm: G::zero(),
+            shifted_comm: degree_bounded.then(G::zero),
+        }
+        .serialized_size()
+    }
+
+    /// The inner product argument takes one round per halving of the key, whose size is rounded up to a power of two
+    fn opening_proof_size(supported_degree: usize, hiding: bool) -> usize {
+        let rounds = ark_std::log2((supported_degree + 1).next_power_of_two()) as usize;
+        ipa_pc::Proof {
+            l_vec: vec![G::zero(); rounds],
+            r_vec: vec![G::zero(); rounds],
+            final_comm_key: G::zero(),
+            c: G::ScalarField::zero(),
+            hiding_comm: hiding.then(G::zero),
+            rand: hiding.then(G::ScalarField::zero),
+        }
+        .serialized_size()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{pedersen::PedersenPC, AdditivelyHomomorphicPCS};
+    use ark_bn254::{Fr, G1Affine};
+    use ark_ff::{One, UniformRand};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, LinearCombination, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+
+    type F = Fr;
+    type PC = PedersenPC<G1Affine, Blake2s>;
+
+    #[test]
+    fn test_aggregate_comm_with_rand() {
+        let rng = &mut thread_rng();
+        let maximum_degree: usize = 15;
+        let hiding_bound = 1;
+        let degree_bound = 10;
+
+        let pp = PC::setup(maximum_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, maximum_degree, hiding_bound, Some(&[degree_bound])).unwrap();
+
+        let a_poly = LabeledPolynomial::new(
+            String::from("a"),
+            DensePolynomial::rand(7, rng),
+            Some(degree_bound),
+            Some(hiding_bound),
+        );
+        let b_poly = LabeledPolynomial::new(
+            String::from("b"),
+            DensePolynomial::rand(5, rng),
+            Some(degree_bound),
+            Some(hiding_bound),
+        );
+        let a_plus_2b_poly = LabeledPolynomial::new(
+            String::from("a_plus_2b"),
+            a_poly.polynomial().clone() + (b_poly.polynomial() * F::from(2u64)),
+            Some(degree_bound),
+            Some(hiding_bound),
+        );
+
+        let linear_combination =
+            LinearCombination::new("a_plus_2b", vec![(F::one(), "a"), (F::from(2u64), "b")]);
+
+        let (commitments, rands) = PC::commit(&ck, &[a_poly, b_poly], Some(rng)).unwrap();
+        let (test_commitment, test_rand) =
+            PC::aggregate_commitments(&commitments, Some(rands.to_vec()), &linear_combination)
+                .unwrap();
+
+        // the verifier aggregates without randomness and gets the same commitment
+        let (verifier_commitment, _) =
+            PC::aggregate_commitments(&commitments, None, &linear_combination).unwrap();
+        assert_eq!(
+            verifier_commitment.commitment(),
+            test_commitment.commitment()
+        );
+
+        let evaluation_point = F::rand(rng);
+        let eval = a_plus_2b_poly.evaluate(&evaluation_point);
+        let opening_challenge = F::rand(rng);
+
+        let test_commitment = [test_commitment];
+        let proof = PC::open(
+            &ck,
+            &[a_plus_2b_poly],
+            &test_commitment,
+            &evaluation_point,
+            opening_challenge,
+            &[test_rand],
+            Some(rng),
+        )
+        .unwrap();
+
+        let res = PC::check(
+            &vk,
+            &test_commitment,
+            &evaluation_point,
+            vec![eval],
+            &proof,
+            opening_challenge,
+            Some(rng),
+        )
+        .unwrap();
+        assert!(res);
+    }
+}
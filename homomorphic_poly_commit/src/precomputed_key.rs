@@ -0,0 +1,318 @@
+//! Committer keys of the KZG backend ([`KZG10`](crate::marlin_kzg::KZG10)) with fixed-base precomputation. A prover
+//! committing to many polynomials under the same key runs an MSM over the same powers of g each time. Splitting the
+//! scalars into windows of `w` bits, [`Precompute::precompute`] stores `2^(w * j) * g_i` for every power `g_i` and
+//! window `j`, so that an MSM only sorts the bases into buckets by the digits of the scalars and sums the buckets
+//! once, instead of once per window as the variable-base MSM does, and needs no doublings.
+//!
+//! ```ignore
+//! let ck = ck.precompute(16);
+//! for polynomials in batches {
+//!     let (commitments, rands) = ck.commit(&polynomials, Some(rng))?;
+//! }
+//! let proof = PC::open(ck.committer_key(), ...)?;
+//! ```
+//!
+//! The commitments and randomness are those of
+//! [`PolynomialCommitment::commit`](ark_poly_commit::PolynomialCommitment::commit) with the same rng. The tables take
+//! `ceil(254 / w)` times the memory of the powers for BN254, e.g. 22 times for `w = 12`, and each MSM sums `2^w`
+//! buckets, so windows of a few bits less than the log of the degree do best. With 12 bits, 16 commitments to
+//! polynomials of degree 2^14 take about 20% less time than with the committer key, and of degree 2^16 about 10%, on
+//! top of a precomputation costing half as much as the commitments, see `benches/precomputed_key.rs`.
+
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{marlin_pc, LabeledCommitment, LabeledPolynomial};
+use ark_std::rand::RngCore;
+
+use crate::{
+    error::Error,
+    marlin_kzg::{commit_with_bases, CommitterBases, KZGRandomness},
+};
+
+/// Largest number of bits of a window, bounding the buckets of an MSM to 2^20
+pub const MAX_WINDOW_BITS: usize = 20;
+
+/// Number of powers whose multiples are normalized at once while precomputing
+const CHUNK_SIZE: usize = 1 << 12;
+
+/// Precompute the fixed-base tables of a committer key
+pub trait Precompute<E: PairingEngine> {
+    /// Precompute the multiples of the powers of g, shifted or not, for windows of `window_bits` bits, clamped between
+    /// 1 and [`MAX_WINDOW_BITS`]
+    fn precompute(self, window_bits: usize) -> PrecomputedCommitterKey<E>;
+}
+
+impl<E: PairingEngine> Precompute<E> for marlin_pc::CommitterKey<E> {
+    fn precompute(self, window_bits: usize) -> PrecomputedCommitterKey<E> {
+        PrecomputedCommitterKey::new(self, window_bits)
+    }
+}
+
+/// A committer key of [`KZG10`](crate::marlin_kzg::KZG10) stored alongside the multiples of its powers of g for every
+/// window of a scalar
+pub struct PrecomputedCommitterKey<E: PairingEngine> {
+    ck: marlin_pc::CommitterKey<E>,
+    window_bits: usize,
+    /// Number of windows of a scalar
+    num_windows: usize,
+    /// `2^(window_bits * j) * ck.powers[i]` at `i * num_windows + j`
+    powers: Vec<E::G1Affine>,
+    /// The same for `ck.shifted_powers`
+    shifted_powers: Option<Vec<E::G1Affine>>,
+}
+
+impl<E: PairingEngine> PrecomputedCommitterKey<E> {
+    pub fn new(ck: marlin_pc::CommitterKey<E>, window_bits: usize) -> Self {
+        let window_bits = window_bits.clamp(1, MAX_WINDOW_BITS);
+        let num_windows = E::Fr::size_in_bits().div_ceil(window_bits);
+        let powers = Self::table(&ck.powers, window_bits, num_windows);
+        let shifted_powers = ck
+            .shifted_powers
+            .as_ref()
+            .map(|shifted_powers| Self::table(shifted_powers, window_bits, num_windows));
+        Self {
+            ck,
+            window_bits,
+            num_windows,
+            powers,
+            shifted_powers,
+        }
+    }
+
+    pub fn window_bits(&self) -> usize {
+        self.window_bits
+    }
+
+    /// The key the tables were precomputed from, e.g. to open commitments
+    pub fn committer_key(&self) -> &marlin_pc::CommitterKey<E> {
+        &self.ck
+    }
+
+    /// Drop the tables and return the key they were precomputed from
+    pub fn into_committer_key(self) -> marlin_pc::CommitterKey<E> {
+        self.ck
+    }
+
+    /// Commit to `polynomials` as [`PolynomialCommitment::commit`](ark_poly_commit::PolynomialCommitment::commit) does
+    /// with the committer key
+    #[allow(clippy::type_complexity)]
+    pub fn commit<'a>(
+        &self,
+        polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<E::Fr, DensePolynomial<E::Fr>>>,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<marlin_pc::Commitment<E>>>,
+            Vec<KZGRandomness<E>>,
+        ),
+        Error,
+    > {
+        commit_with_bases(self, polynomials, rng)
+    }
+
+    /// `2^(window_bits * j) * bases[i]` at `i * num_windows + j`, doubling each base in projective coordinates and
+    /// normalizing a chunk of bases at a time
+    fn table(bases: &[E::G1Affine], window_bits: usize, num_windows: usize) -> Vec<E::G1Affine> {
+        let mut table = Vec::with_capacity(bases.len() * num_windows);
+        for chunk in bases.chunks(CHUNK_SIZE) {
+            let mut multiples = Vec::with_capacity(chunk.len() * num_windows);
+            for base in chunk {
+                let mut multiple = base.into_projective();
+                for _ in 0..num_windows {
+                    multiples.push(multiple);
+                    for _ in 0..window_bits {
+                        multiple.double_in_place();
+                    }
+                }
+            }
+            table.extend(E::G1Projective::batch_normalization_into_affine(&multiples));
+        }
+        table
+    }
+
+    /// `sum_i scalars[i] * bases[shift + i]` with the multiples of the bases in `table`: each window of each scalar
+    /// adds the multiple of its base for that window to the bucket of its digit, and the buckets are summed weighted
+    /// by their digit
+    fn fixed_base_msm(
+        &self,
+        table: &[E::G1Affine],
+        shift: usize,
+        scalars: &[E::Fr],
+    ) -> E::G1Projective {
+        let mut buckets = vec![E::G1Projective::zero(); (1 << self.window_bits) - 1];
+        for (i, scalar) in scalars.iter().enumerate() {
+            if scalar.is_zero() {
+                continue;
+            }
+            let scalar = scalar.into_repr();
+            let multiples = &table[(shift + i) * self.num_windows..][..self.num_windows];
+            for (j, multiple) in multiples.iter().enumerate() {
+                let digit = digit(scalar.as_ref(), j * self.window_bits, self.window_bits);
+                if digit != 0 {
+                    buckets[digit - 1].add_assign_mixed(multiple);
+                }
+            }
+        }
+
+        // sum_d d * buckets[d - 1] as the sum of the running sums from the largest digit down
+        let mut running_sum = E::G1Projective::zero();
+        let mut sum = E::G1Projective::zero();
+        for bucket in buckets.into_iter().rev() {
+            running_sum += bucket;
+            sum += running_sum;
+        }
+        sum
+    }
+}
+
+/// The `bits` bits of the little-endian `limbs` from the bit `start`
+fn digit(limbs: &[u64], start: usize, bits: usize) -> usize {
+    let (limb, offset) = (start / 64, start % 64);
+    let mut value = limbs.get(limb).map_or(0, |limb| limb >> offset);
+    if offset + bits > 64 {
+        value |= limbs.get(limb + 1).map_or(0, |limb| limb << (64 - offset));
+    }
+    (value & ((1 << bits) - 1)) as usize
+}
+
+impl<E: PairingEngine> CommitterBases<E> for PrecomputedCommitterKey<E> {
+    fn max_degree(&self) -> usize {
+        self.ck.max_degree
+    }
+
+    fn enforced_degree_bounds(&self) -> Option<&[usize]> {
+        self.ck.enforced_degree_bounds.as_deref()
+    }
+
+    fn powers_of_gamma_g(&self) -> &[E::G1Affine] {
+        &self.ck.powers_of_gamma_g
+    }
+
+    fn num_powers(&self, shifted: bool) -> Option<usize> {
+        if shifted {
+            self.ck.shifted_powers.as_ref().map(Vec::len)
+        } else {
+            Some(self.ck.powers.len())
+        }
+    }
+
+    fn msm(
+        &self,
+        shifted: bool,
+        shift: usize,
+        scalars: &[E::Fr],
+    ) -> Result<E::G1Projective, Error> {
+        let table = if shifted {
+            self.shifted_powers.as_deref().unwrap_or(&[])
+        } else {
+            &self.powers
+        };
+        Ok(self.fixed_base_msm(table, shift, scalars))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{error::Error, marlin_kzg::KZG10, precomputed_key::Precompute};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{One, Zero};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        test_rng,
+    };
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    #[test]
+    fn test_commit_matches_committer_key() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 16, 2, Some(&[4, 9])).unwrap();
+
+        let mut edge_coeffs = vec![F::zero(); 17];
+        edge_coeffs[0] = -F::one();
+        edge_coeffs[16] = F::one();
+        let polynomials = [
+            LabeledPolynomial::new(
+                String::from("plain"),
+                DensePolynomial::rand(16, rng),
+                None,
+                None,
+            ),
+            LabeledPolynomial::new(
+                String::from("edge"),
+                DensePolynomial::from_coefficients_vec(edge_coeffs),
+                None,
+                None,
+            ),
+            LabeledPolynomial::new(
+                String::from("bounded"),
+                DensePolynomial::rand(7, rng),
+                Some(9),
+                None,
+            ),
+            LabeledPolynomial::new(
+                String::from("hiding"),
+                DensePolynomial::rand(4, rng),
+                Some(4),
+                Some(2),
+            ),
+        ];
+        let (expected, expected_rands) =
+            PC::commit(&ck, &polynomials, Some(&mut StdRng::seed_from_u64(7))).unwrap();
+
+        // windows that do and do not divide the scalar size, spanning several limbs or a single bit
+        for window_bits in [1, 5, 13, 16] {
+            let precomputed = ck.clone().precompute(window_bits);
+            assert_eq!(precomputed.window_bits(), window_bits);
+            let (commitments, rands) = precomputed
+                .commit(&polynomials, Some(&mut StdRng::seed_from_u64(7)))
+                .unwrap();
+
+            for (commitment, expected) in commitments.iter().zip(&expected) {
+                assert_eq!(commitment.label(), expected.label());
+                assert_eq!(commitment.degree_bound(), expected.degree_bound());
+                assert_eq!(commitment.commitment(), expected.commitment());
+            }
+            assert_eq!(rands, expected_rands);
+        }
+    }
+
+    #[test]
+    fn test_rejects_unsupported_polynomials() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 8, 1, Some(&[4])).unwrap();
+        let precomputed = ck.precompute(8);
+
+        let commit = |degree: usize, degree_bound: Option<usize>| {
+            let p = LabeledPolynomial::new(
+                String::from("p"),
+                DensePolynomial::<F>::rand(degree, &mut test_rng()),
+                degree_bound,
+                None,
+            );
+            precomputed.commit(&[p], None).map(|_| ())
+        };
+        assert_eq!(commit(8, None), Ok(()));
+        assert!(matches!(commit(9, None), Err(Error::PCError { .. })));
+        assert!(matches!(commit(3, Some(5)), Err(Error::PCError { .. })));
+        assert!(matches!(commit(5, Some(4)), Err(Error::PCError { .. })));
+
+        // hiding needs an rng
+        let p = LabeledPolynomial::new(
+            String::from("p"),
+            DensePolynomial::<F>::rand(3, rng),
+            None,
+            Some(1),
+        );
+        assert!(matches!(
+            precomputed.commit(&[p], None),
+            Err(Error::PCError { .. })
+        ));
+    }
+}
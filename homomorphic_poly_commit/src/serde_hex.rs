@@ -0,0 +1,215 @@
+//! Serde support for arkworks types, which only implement `CanonicalSerialize`. A value is encoded as the hex string
+//! of its canonical serialization, so that group and field elements stay readable in JSON. The functions of this
+//! module and of its sub-modules are meant for `#[serde(with = "...")]` field attributes.
+
+use ark_poly_commit::{LabeledCommitment, PCCommitment};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Hex encoding of the canonical serialization of `value`
+pub fn encode<T: CanonicalSerialize>(value: &T) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(value.serialized_size());
+    value
+        .serialize(&mut bytes)
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Inverse of `encode`
+pub fn decode<T: CanonicalDeserialize>(hex: &str) -> Result<T, String> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(format!("Hex string of odd length {}", hex.len()));
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| format!("Invalid hex digits at position {}", i))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    T::deserialize(&bytes[..]).map_err(|e| format!("{:?}", e))
+}
+
+pub fn serialize<T: CanonicalSerialize, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Serialize::serialize(&encode(value).map_err(ser::Error::custom)?, serializer)
+}
+
+pub fn deserialize<'de, T: CanonicalDeserialize, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    decode(&<String as Deserialize>::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
+/// A vector of values, encoded as an array of hex strings
+pub mod vec {
+    use super::{decode, encode};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: CanonicalSerialize, S: Serializer>(
+        values: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let hex = values
+            .iter()
+            .map(encode)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ser::Error::custom)?;
+        Serialize::serialize(&hex, serializer)
+    }
+
+    pub fn deserialize<'de, T: CanonicalDeserialize, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        <Vec<String> as Deserialize>::deserialize(deserializer)?
+            .iter()
+            .map(|hex| decode(hex))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The JSON shape of a labeled commitment
+#[derive(Serialize, Deserialize)]
+struct LabeledCommitmentRepr {
+    label: String,
+    commitment: String,
+    degree_bound: Option<usize>,
+}
+
+impl LabeledCommitmentRepr {
+    fn encode<C: PCCommitment>(commitment: &LabeledCommitment<C>) -> Result<Self, String> {
+        Ok(Self {
+            label: commitment.label().clone(),
+            commitment: encode(commitment.commitment())?,
+            degree_bound: commitment.degree_bound(),
+        })
+    }
+
+    fn decode<C: PCCommitment>(self) -> Result<LabeledCommitment<C>, String> {
+        Ok(LabeledCommitment::new(
+            self.label,
+            decode(&self.commitment)?,
+            self.degree_bound,
+        ))
+    }
+}
+
+/// A labeled commitment, encoded as its label, hex commitment and degree bound
+pub mod labeled_commitment {
+    use super::LabeledCommitmentRepr;
+    use ark_poly_commit::{LabeledCommitment, PCCommitment};
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<C: PCCommitment, S: Serializer>(
+        commitment: &LabeledCommitment<C>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        LabeledCommitmentRepr::encode(commitment)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, C: PCCommitment, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<LabeledCommitment<C>, D::Error> {
+        LabeledCommitmentRepr::deserialize(deserializer)?
+            .decode()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// A vector of labeled commitments
+pub mod labeled_commitments {
+    use super::LabeledCommitmentRepr;
+    use ark_poly_commit::{LabeledCommitment, PCCommitment};
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<C: PCCommitment, S: Serializer>(
+        commitments: &[LabeledCommitment<C>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        commitments
+            .iter()
+            .map(LabeledCommitmentRepr::encode)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, C: PCCommitment, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<LabeledCommitment<C>>, D::Error> {
+        Vec::<LabeledCommitmentRepr>::deserialize(deserializer)?
+            .into_iter()
+            .map(LabeledCommitmentRepr::decode)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{marlin_kzg::KZG10, serde_hex};
+    use ark_bn254::{Bn254, Fr};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+    use ark_std::test_rng;
+    use serde::{Deserialize, Serialize};
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type Commitment = <PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment;
+
+    #[derive(Serialize, Deserialize)]
+    struct Statement {
+        #[serde(with = "serde_hex")]
+        point: F,
+        #[serde(with = "serde_hex::labeled_commitments")]
+        commitments: Vec<LabeledCommitment<Commitment>>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(8, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 8, 0, Some(&[6])).unwrap();
+
+        let polynomials = [
+            LabeledPolynomial::new(String::from("a"), DensePolynomial::rand(4, rng), None, None),
+            LabeledPolynomial::new(
+                String::from("b"),
+                DensePolynomial::rand(4, rng),
+                Some(6),
+                None,
+            ),
+        ];
+        let (commitments, _) = PC::commit(&ck, &polynomials, None).unwrap();
+        let statement = Statement {
+            point: F::from(42u64),
+            commitments,
+        };
+
+        let json = serde_json::to_string(&statement).unwrap();
+        let decoded: Statement = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.point, statement.point);
+        let parts = |commitments: &[LabeledCommitment<Commitment>]| {
+            commitments
+                .iter()
+                .map(|c| (c.label().clone(), *c.commitment(), c.degree_bound()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(parts(&decoded.commitments), parts(&statement.commitments));
+
+        assert_eq!(
+            serde_hex::encode(&F::from(1u64)).unwrap(),
+            format!("01{}", "00".repeat(31))
+        );
+        assert!(serde_hex::decode::<F>("0").is_err());
+        assert!(serde_hex::decode::<F>("zz").is_err());
+    }
+}
@@ -0,0 +1,366 @@
+//! Commitments with randomness supplied by the caller, for protocols driven by an MPC engine.
+//!
+//! The parties of an MPC agree on the randomness of every commitment instead of sampling it locally, typically as
+//! additive shares: each party holds one share of the randomness of each polynomial and the randomness is their sum.
+//! A commitment is the commitment to the polynomial with empty randomness plus the commitment to the zero polynomial
+//! with its randomness, see [`AdditivelyHomomorphicPCS::commit_randomness`], so that the shares can be committed to
+//! separately and summed with [`combine_shares`].
+//!
+//! Whoever then holds the polynomials and the agreed randomness reproduces the commitments with
+//! [`AdditivelyHomomorphicPCS::commit_with_rands`], and checks the commitments and aggregations it receives with
+//! [`check_commitments_with_rands`] and [`check_aggregation_with_rands`].
+//!
+//! ```ignore
+//! // each party commits to its shares of the randomness
+//! let (_, rand) = shared_randomness::combine_shares::<F, PC>(&ck, degree_bound, &shares)?;
+//! let commitments = PC::commit_with_rands(&ck, &polynomials, &rands)?;
+//! shared_randomness::check_aggregation_with_rands::<F, PC>(&ck, &commitments, &rands, &lc, &combined)?;
+//! ```
+
+use ark_ec::{msm::VariableBaseMSM, PairingEngine};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{kzg10, LabeledCommitment, LabeledPolynomial, LinearCombination};
+
+use crate::{
+    error::{to_pc_error, Error},
+    AdditivelyHomomorphicPCS,
+};
+
+/// Commit to polynomials with one given randomness each, in the same order. A polynomial without hiding bound must
+/// come with empty randomness, since openings ignore the randomness of such polynomials.
+pub fn commit_with_rands<'a, F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    ck: &PC::CommitterKey,
+    polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+    rands: &[PC::Randomness],
+) -> Result<Vec<LabeledCommitment<PC::Commitment>>, Error> {
+    let polynomials = polynomials.into_iter().collect::<Vec<_>>();
+    if polynomials.len() != rands.len() {
+        return Err(Error::InputLengthError(format!(
+            "{} polynomials but {} randomness",
+            polynomials.len(),
+            rands.len()
+        )));
+    }
+    if let Some(p) = polynomials
+        .iter()
+        .zip(rands)
+        .find_map(|(p, rand)| (p.hiding_bound().is_none() && PC::is_hiding(rand)).then_some(p))
+    {
+        return Err(Error::PCError {
+            error: format!(
+                "Hiding randomness for {}, which has no hiding bound",
+                p.label()
+            ),
+        });
+    }
+
+    // the bounds are checked against the key by the commitment with empty randomness
+    let unhidden = polynomials
+        .iter()
+        .map(|p| {
+            LabeledPolynomial::new(
+                p.label().clone(),
+                p.polynomial().clone(),
+                p.degree_bound(),
+                None,
+            )
+        })
+        .collect::<Vec<_>>();
+    let (commitments, _) = PC::commit(ck, &unhidden, None).map_err(to_pc_error::<F, PC>)?;
+
+    commitments
+        .into_iter()
+        .zip(rands)
+        .map(|(commitment, rand)| {
+            let hiding = PC::commit_randomness(ck, commitment.degree_bound(), rand)?;
+            Ok(LabeledCommitment::new(
+                commitment.label().clone(),
+                PC::add(commitment.commitment(), &hiding)?,
+                commitment.degree_bound(),
+            ))
+        })
+        .collect()
+}
+
+/// The randomness that is the sum of `shares`, with the commitment to the zero polynomial under it, the sum of the
+/// commitments to the shares. Every share must be for a polynomial with degree bound `degree_bound`.
+pub fn combine_shares<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    ck: &PC::CommitterKey,
+    degree_bound: Option<usize>,
+    shares: &[PC::Randomness],
+) -> Result<(PC::Commitment, PC::Randomness), Error> {
+    if shares.is_empty() {
+        return Err(Error::InputLengthError(
+            "No shares of randomness to combine".into(),
+        ));
+    }
+
+    let commitments = shares
+        .iter()
+        .enumerate()
+        .map(|(i, share)| {
+            Ok(LabeledCommitment::new(
+                format!("share_{}", i),
+                PC::commit_randomness(ck, degree_bound, share)?,
+                degree_bound,
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let lc = LinearCombination::new(
+        "shares",
+        commitments
+            .iter()
+            .map(|commitment| (F::one(), commitment.label().clone()))
+            .collect::<Vec<_>>(),
+    );
+    let (commitment, rand) = PC::aggregate_commitments(&commitments, Some(shares.to_vec()), &lc)?;
+
+    Ok((commitment.commitment().clone(), rand))
+}
+
+/// Check that `commitments` are the commitments to `polynomials` with `rands`
+pub fn check_commitments_with_rands<'a, F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    ck: &PC::CommitterKey,
+    polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+    commitments: &[LabeledCommitment<PC::Commitment>],
+    rands: &[PC::Randomness],
+) -> Result<(), Error> {
+    let expected = commit_with_rands::<F, PC>(ck, polynomials, rands)?;
+    if expected.len() != commitments.len() {
+        return Err(Error::InputLengthError(format!(
+            "{} polynomials but {} commitments",
+            expected.len(),
+            commitments.len()
+        )));
+    }
+
+    match expected
+        .iter()
+        .zip(commitments)
+        .find(|(expected, commitment)| {
+            expected.label() != commitment.label()
+                || expected.degree_bound() != commitment.degree_bound()
+                || expected.commitment() != commitment.commitment()
+        }) {
+        Some((expected, _)) => Err(Error::CommitmentMismatch(expected.label().clone())),
+        None => Ok(()),
+    }
+}
+
+/// Check that aggregating `commitments` made with `rands` according to `lc` gives the commitment to `combined`, the
+/// linear combination of their polynomials, under the randomness aggregated alongside. Returns that randomness, which
+/// opens the aggregated commitment.
+pub fn check_aggregation_with_rands<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    ck: &PC::CommitterKey,
+    commitments: &[LabeledCommitment<PC::Commitment>],
+    rands: &[PC::Randomness],
+    lc: &LinearCombination<F>,
+    combined: &LabeledPolynomial<F, DensePolynomial<F>>,
+) -> Result<PC::Randomness, Error> {
+    let (aggregated, rand) = PC::aggregate_commitments(commitments, Some(rands.to_vec()), lc)?;
+
+    // the combined randomness hides exactly when one of the aggregated randomness does
+    let combined = LabeledPolynomial::new(
+        combined.label().clone(),
+        combined.polynomial().clone(),
+        combined.degree_bound(),
+        combined
+            .hiding_bound()
+            .or_else(|| PC::is_hiding(&rand).then_some(1)),
+    );
+    let expected = commit_with_rands::<F, PC>(ck, [&combined], ark_std::slice::from_ref(&rand))?;
+    if expected[0].commitment() != aggregated.commitment() {
+        return Err(Error::CommitmentMismatch(lc.label().clone()));
+    }
+
+    Ok(rand)
+}
+
+/// The hiding part of a KZG10 commitment, i.e. the commitment to the zero polynomial under `randomness`
+pub(crate) fn kzg10_hiding_commitment<E: PairingEngine>(
+    powers: &kzg10::Powers<E>,
+    randomness: &kzg10::Randomness<E::Fr, DensePolynomial<E::Fr>>,
+) -> Result<kzg10::Commitment<E>, Error> {
+    let coeffs = &randomness.blinding_polynomial.coeffs;
+    if coeffs.len() > powers.powers_of_gamma_g.len() {
+        return Err(Error::InputLengthError(format!(
+            "Blinding polynomial with {} coefficients but only {} powers of gamma_g",
+            coeffs.len(),
+            powers.powers_of_gamma_g.len()
+        )));
+    }
+    if coeffs.iter().all(Zero::is_zero) {
+        return Ok(kzg10::Commitment(E::G1Affine::zero()));
+    }
+
+    let scalars = coeffs.iter().map(|c| c.into_repr()).collect::<Vec<_>>();
+    Ok(kzg10::Commitment(
+        VariableBaseMSM::multi_scalar_mul(&powers.powers_of_gamma_g[..coeffs.len()], &scalars)
+            .into(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        error::Error,
+        marlin_kzg,
+        shared_randomness::{
+            check_aggregation_with_rands, check_commitments_with_rands, combine_shares,
+        },
+        sonic_kzg, AdditivelyHomomorphicPCS,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{One, UniformRand, Zero};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, LinearCombination, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+
+    type F = Fr;
+    type PC = marlin_kzg::KZG10<Bn254>;
+
+    /// Reproduce the commitments of `commit` from its randomness, then open a commitment made with randomness
+    /// combined from shares
+    fn commit_with_rands_template<PC: AdditivelyHomomorphicPCS<F>>() {
+        let rng = &mut thread_rng();
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 2, Some(&[15])).unwrap();
+
+        let polynomials = [(None, None), (Some(15), Some(2)), (None, Some(1))]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (degree_bound, hiding_bound))| {
+                LabeledPolynomial::new(
+                    format!("p_{}", i),
+                    DensePolynomial::rand(7, rng),
+                    degree_bound,
+                    hiding_bound,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // the randomness sampled by commit reproduces its commitments
+        let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+        for (recommitted, commitment) in PC::commit_with_rands(&ck, &polynomials, &rands)
+            .unwrap()
+            .iter()
+            .zip(&commitments)
+        {
+            assert_eq!(recommitted.commitment(), commitment.commitment());
+        }
+        check_commitments_with_rands::<F, PC>(&ck, &polynomials, &commitments, &rands).unwrap();
+        assert!(matches!(
+            check_commitments_with_rands::<F, PC>(
+                &ck,
+                &polynomials,
+                &commitments,
+                &[rands[0].clone(), rands[2].clone(), rands[1].clone()]
+            ),
+            Err(Error::CommitmentMismatch(_))
+        ));
+
+        // randomness agreed as shares opens the commitment made with it
+        let bounded = &polynomials[1];
+        let shares = (0..3)
+            .map(|_| {
+                let zero = LabeledPolynomial::new(
+                    "zero".into(),
+                    DensePolynomial::zero(),
+                    bounded.degree_bound(),
+                    bounded.hiding_bound(),
+                );
+                PC::commit(&ck, &[zero], Some(rng)).unwrap().1.remove(0)
+            })
+            .collect::<Vec<_>>();
+        let (_, rand) = combine_shares::<F, PC>(&ck, bounded.degree_bound(), &shares).unwrap();
+        let commitments =
+            PC::commit_with_rands(&ck, [bounded], ark_std::slice::from_ref(&rand)).unwrap();
+
+        let point = F::rand(rng);
+        let separation_challenge = F::rand(rng);
+        let proof = PC::open(
+            &ck,
+            [bounded],
+            &commitments,
+            &point,
+            separation_challenge,
+            [&rand],
+            Some(rng),
+        )
+        .unwrap();
+        assert!(PC::check(
+            &vk,
+            &commitments,
+            &point,
+            [bounded.evaluate(&point)],
+            &proof,
+            separation_challenge,
+            Some(rng)
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_commit_with_rands_marlin_kzg() {
+        commit_with_rands_template::<marlin_kzg::KZG10<Bn254>>();
+    }
+
+    #[test]
+    fn test_commit_with_rands_sonic_kzg() {
+        commit_with_rands_template::<sonic_kzg::KZG10<Bn254>>();
+    }
+
+    #[cfg(feature = "pedersen")]
+    #[test]
+    fn test_commit_with_rands_pedersen() {
+        commit_with_rands_template::<
+            crate::pedersen::PedersenPC<ark_bn254::G1Affine, blake2::Blake2s>,
+        >();
+    }
+
+    #[test]
+    fn test_check_aggregation_with_rands() {
+        let rng = &mut thread_rng();
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, max_degree, 1, None).unwrap();
+
+        let polynomials = (0..2)
+            .map(|i| {
+                LabeledPolynomial::new(
+                    format!("p_{}", i),
+                    DensePolynomial::rand(7, rng),
+                    None,
+                    Some(1),
+                )
+            })
+            .collect::<Vec<_>>();
+        let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+
+        let a = F::rand(rng);
+        let lc = LinearCombination::new(
+            "combined",
+            vec![(F::one(), "p_0".to_string()), (a, "p_1".to_string())],
+        );
+        let combined = LabeledPolynomial::new(
+            "combined".into(),
+            polynomials[0].polynomial() + &(polynomials[1].polynomial() * a),
+            None,
+            Some(1),
+        );
+        check_aggregation_with_rands::<F, PC>(&ck, &commitments, &rands, &lc, &combined).unwrap();
+
+        let wrong = LabeledPolynomial::new(
+            "combined".into(),
+            polynomials[0].polynomial() + polynomials[1].polynomial(),
+            None,
+            Some(1),
+        );
+        assert!(matches!(
+            check_aggregation_with_rands::<F, PC>(&ck, &commitments, &rands, &lc, &wrong),
+            Err(Error::CommitmentMismatch(_))
+        ));
+    }
+}
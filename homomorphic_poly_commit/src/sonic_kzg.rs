@@ -1,12 +1,15 @@
 use std::collections::BTreeMap;
 
-use ark_ec::PairingEngine;
+use ark_ec::{msm::VariableBaseMSM, PairingEngine, ProjectiveCurve};
+use ark_ff::{PrimeField, Zero};
 use ark_poly::univariate::DensePolynomial;
 use ark_poly_commit::{
-    sonic_pc::SonicKZG10, LCTerm, LabeledCommitment, LinearCombination, PCCommitment, PCRandomness,
+    kzg10, sonic_pc::SonicKZG10, LCTerm, LabeledCommitment, LinearCombination, PCCommitment,
+    PCRandomness,
 };
+use ark_serialize::CanonicalSerialize;
 
-use crate::{error::Error, AdditivelyHomomorphicPCS};
+use crate::{error::Error, shared_randomness::kzg10_hiding_commitment, AdditivelyHomomorphicPCS};
 
 /// The Default KZG-style commitment scheme
 pub type KZG10<E> = SonicKZG10<E, DensePolynomial<<E as PairingEngine>::Fr>>;
@@ -58,7 +61,8 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for SonicKZG10<E, DensePo
             .collect::<Result<BTreeMap<_, (_, _)>, Error>>()?;
 
         // initial values
-        let mut aggregate_commitment = Self::Commitment::empty();
+        let mut terms = Vec::with_capacity(lc.len());
+        let mut coefficients = Vec::with_capacity(lc.len());
         let mut aggregate_randomness = Self::Randomness::empty();
 
         for (coef, term) in lc.iter() {
@@ -69,7 +73,8 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for SonicKZG10<E, DensePo
                 // Find the corresponding commitment and randomness in our map; aggregate.
                 LCTerm::PolyLabel(label) => match label_comm_mapping.get(label) {
                     Some((comm, rand)) => {
-                        aggregate_commitment += (*coef, &comm);
+                        terms.push(*comm);
+                        coefficients.push(*coef);
                         aggregate_randomness += (*coef, rand);
                     }
                     None => {
@@ -83,11 +88,83 @@ impl<E: PairingEngine> AdditivelyHomomorphicPCS<E::Fr> for SonicKZG10<E, DensePo
             }
         }
 
+        let aggregate_commitment = Self::msm_commitments(&terms, &coefficients)?;
+
         Ok((
             LabeledCommitment::new(lc.label().clone(), aggregate_commitment, degree_bound),
             aggregate_randomness,
         ))
     }
+
+    fn msm_commitments(
+        commitments: &[Self::Commitment],
+        scalars: &[E::Fr],
+    ) -> Result<Self::Commitment, Error> {
+        if commitments.len() != scalars.len() {
+            return Err(Error::InputLengthError(format!(
+                "Got {} commitments but {} scalars",
+                commitments.len(),
+                scalars.len()
+            )));
+        }
+
+        let scalars = scalars
+            .iter()
+            .map(|scalar| scalar.into_repr())
+            .collect::<Vec<_>>();
+        let bases = commitments
+            .iter()
+            .map(|commitment| commitment.0)
+            .collect::<Vec<_>>();
+
+        Ok(kzg10::Commitment(
+            VariableBaseMSM::multi_scalar_mul(&bases, &scalars).into_affine(),
+        ))
+    }
+
+    fn commit_randomness(
+        ck: &Self::CommitterKey,
+        degree_bound: Option<usize>,
+        randomness: &Self::Randomness,
+    ) -> Result<Self::Commitment, Error> {
+        let powers = match degree_bound {
+            Some(degree_bound) => {
+                let enforced = ck
+                    .enforced_degree_bounds
+                    .as_ref()
+                    .is_some_and(|bounds| bounds.contains(&degree_bound));
+                enforced
+                    .then(|| ck.shifted_powers(degree_bound))
+                    .flatten()
+                    .ok_or_else(|| {
+                        Error::MismatchedDegreeBounds(format!(
+                            "Degree bound {} is not enforced by the committer key",
+                            degree_bound
+                        ))
+                    })?
+            }
+            None => ck.powers(),
+        };
+
+        kzg10_hiding_commitment(&powers, randomness)
+    }
+
+    fn is_hiding(randomness: &Self::Randomness) -> bool {
+        !randomness.blinding_polynomial.is_zero()
+    }
+
+    /// Degree bounds are enforced at opening time, so that commitments are a single group element either way
+    fn commitment_size(_degree_bounded: bool) -> usize {
+        kzg10::Commitment::<E>::empty().serialized_size()
+    }
+
+    fn opening_proof_size(_supported_degree: usize, hiding: bool) -> usize {
+        kzg10::Proof::<E> {
+            w: E::G1Affine::zero(),
+            random_v: hiding.then(E::Fr::zero),
+        }
+        .serialized_size()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,221 @@
+//! Commitments to polynomials with few non-zero coefficients. A dense MSM over the coefficients of such a polynomial
+//! spends most of its time on zero scalars, as its window size is chosen for the full length.
+//!
+//! [`AdditivelyHomomorphicPCS::commit_sparse`] commits to [`SparsePolynomial`]s, with an MSM over the non-zero
+//! coefficients only where the scheme allows it, and [`AdditivelyHomomorphicPCS::commit_with_density_heuristic`]
+//! takes that path for each dense polynomial whose density is at most [`MAX_SPARSE_DENSITY_PERCENT`]. Density is
+//! measured in the coefficient basis: an index polynomial that is zero over most of a domain is not sparse in general.
+//!
+//! Run `cargo bench -p homomorphic_poly_commit --bench sparse_commit -- <log2 of degree>` to compare the two paths on
+//! polynomials of 1% and 0.1% density.
+
+use ark_ff::PrimeField;
+use ark_poly::univariate::{DensePolynomial, SparsePolynomial};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use ark_std::rand::RngCore;
+
+use crate::{
+    error::{to_pc_error, Error},
+    AdditivelyHomomorphicPCS,
+};
+
+/// Polynomials with at most this percentage of non-zero coefficients are committed to as sparse polynomials by
+/// [`commit_with_density_heuristic`]
+pub const MAX_SPARSE_DENSITY_PERCENT: usize = 10;
+
+/// Number of non-zero coefficients of a polynomial
+pub fn num_non_zero<F: PrimeField>(polynomial: &DensePolynomial<F>) -> usize {
+    polynomial.coeffs.iter().filter(|c| !c.is_zero()).count()
+}
+
+/// Whether a polynomial has at most [`MAX_SPARSE_DENSITY_PERCENT`] percent of non-zero coefficients
+pub fn is_sparse<F: PrimeField>(polynomial: &DensePolynomial<F>) -> bool {
+    num_non_zero(polynomial) * 100 <= polynomial.coeffs.len() * MAX_SPARSE_DENSITY_PERCENT
+}
+
+/// The same labeled polynomial, with its non-zero coefficients only
+pub fn to_sparse<F: PrimeField>(
+    polynomial: &LabeledPolynomial<F, DensePolynomial<F>>,
+) -> LabeledPolynomial<F, SparsePolynomial<F>> {
+    LabeledPolynomial::new(
+        polynomial.label().clone(),
+        polynomial.polynomial().clone().into(),
+        polynomial.degree_bound(),
+        polynomial.hiding_bound(),
+    )
+}
+
+/// The same labeled polynomial, with all of its coefficients
+pub fn to_dense<F: PrimeField>(
+    polynomial: &LabeledPolynomial<F, SparsePolynomial<F>>,
+) -> LabeledPolynomial<F, DensePolynomial<F>> {
+    LabeledPolynomial::new(
+        polynomial.label().clone(),
+        polynomial.polynomial().clone().into(),
+        polynomial.degree_bound(),
+        polynomial.hiding_bound(),
+    )
+}
+
+/// Commit to sparse polynomials through their dense form, for schemes without a sparse MSM
+#[allow(clippy::type_complexity)]
+pub fn commit_as_dense<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    ck: &PC::CommitterKey,
+    polynomials: &[LabeledPolynomial<F, SparsePolynomial<F>>],
+    rng: Option<&mut dyn RngCore>,
+) -> Result<(Vec<LabeledCommitment<PC::Commitment>>, Vec<PC::Randomness>), Error> {
+    let dense = polynomials.iter().map(to_dense).collect::<Vec<_>>();
+    PC::commit(ck, &dense, rng).map_err(to_pc_error::<F, PC>)
+}
+
+/// Commit to the sparse polynomials with [`AdditivelyHomomorphicPCS::commit_sparse`] and to the others with
+/// `PC::commit`, returning the commitments and randomness in the order of `polynomials`
+#[allow(clippy::type_complexity)]
+pub fn commit_with_density_heuristic<'a, F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    ck: &PC::CommitterKey,
+    polynomials: impl IntoIterator<Item = &'a LabeledPolynomial<F, DensePolynomial<F>>>,
+    mut rng: Option<&mut dyn RngCore>,
+) -> Result<(Vec<LabeledCommitment<PC::Commitment>>, Vec<PC::Randomness>), Error> {
+    let polynomials = polynomials.into_iter().collect::<Vec<_>>();
+    let (sparse, dense): (Vec<_>, Vec<_>) = polynomials
+        .iter()
+        .copied()
+        .partition(|polynomial| is_sparse(polynomial.polynomial()));
+
+    let sparse_polynomials = sparse.iter().map(|p| to_sparse(p)).collect::<Vec<_>>();
+    let (sparse_commitments, sparse_rands) = PC::commit_sparse(
+        ck,
+        &sparse_polynomials,
+        rng.as_mut().map(|rng| &mut **rng as &mut dyn RngCore),
+    )?;
+    let (dense_commitments, dense_rands) =
+        PC::commit(ck, dense.iter().copied(), rng).map_err(to_pc_error::<F, PC>)?;
+
+    // merge both batches back in the order of the polynomials
+    let mut sparse = sparse_commitments.into_iter().zip(sparse_rands);
+    let mut dense = dense_commitments.into_iter().zip(dense_rands);
+    Ok(polynomials
+        .iter()
+        .map(|polynomial| {
+            let batch = if is_sparse(polynomial.polynomial()) {
+                &mut sparse
+            } else {
+                &mut dense
+            };
+            batch.next().expect("one commitment per polynomial")
+        })
+        .unzip())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        marlin_kzg::KZG10,
+        sparse::{is_sparse, to_sparse},
+        AdditivelyHomomorphicPCS,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{One, UniformRand, Zero};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    fn sparse_poly(degree: usize, non_zero: &[usize]) -> DensePolynomial<F> {
+        let rng = &mut thread_rng();
+        let mut coeffs = vec![F::zero(); degree + 1];
+        for &i in non_zero {
+            coeffs[i] = F::rand(rng);
+        }
+        DensePolynomial::from_coefficients_vec(coeffs)
+    }
+
+    #[test]
+    fn test_is_sparse() {
+        assert!(is_sparse(&sparse_poly(99, &[0, 50, 99])));
+        assert!(!is_sparse(&sparse_poly(9, &[0, 5, 9])));
+        assert!(!is_sparse(&DensePolynomial::from_coefficients_vec(vec![
+            F::one()
+        ])));
+    }
+
+    #[test]
+    fn test_commit_sparse() {
+        let rng = &mut thread_rng();
+        let max_degree = 40;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[35])).unwrap();
+
+        let p = sparse_poly(30, &[3, 17, 30]);
+        let point = F::rand(rng);
+        for (degree_bound, hiding_bound) in [(None, None), (Some(35), None), (Some(35), Some(1))] {
+            let labeled = LabeledPolynomial::new("p".into(), p.clone(), degree_bound, hiding_bound);
+
+            let (commitments, rands) =
+                PC::commit_sparse(&ck, &[to_sparse(&labeled)], Some(rng)).unwrap();
+            if hiding_bound.is_none() {
+                let (dense_commitments, _) = PC::commit(&ck, [&labeled], None).unwrap();
+                assert_eq!(
+                    commitments[0].commitment(),
+                    dense_commitments[0].commitment()
+                );
+            }
+
+            // the commitment opens with its randomness like a dense one
+            let proof = PC::open(
+                &ck,
+                [&labeled],
+                &commitments,
+                &point,
+                F::one(),
+                &rands,
+                Some(rng),
+            )
+            .unwrap();
+            assert!(PC::check(
+                &vk,
+                &commitments,
+                &point,
+                [labeled.evaluate(&point)],
+                &proof,
+                F::one(),
+                Some(rng)
+            )
+            .unwrap());
+        }
+
+        // coefficients beyond the degree bound are rejected
+        let labeled = LabeledPolynomial::new("p".into(), sparse_poly(36, &[36]), Some(35), None);
+        assert!(PC::commit_sparse(&ck, &[to_sparse(&labeled)], None).is_err());
+    }
+
+    #[test]
+    fn test_commit_with_density_heuristic() {
+        let rng = &mut thread_rng();
+        let max_degree = 100;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, max_degree, 1, None).unwrap();
+
+        let polynomials = [
+            sparse_poly(99, &[1, 98]),
+            DensePolynomial::rand(99, rng),
+            sparse_poly(99, &[]),
+            DensePolynomial::rand(20, rng),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| LabeledPolynomial::new(format!("p_{}", i), p, None, None))
+        .collect::<Vec<_>>();
+
+        let (commitments, rands) =
+            PC::commit_with_density_heuristic(&ck, &polynomials, Some(rng)).unwrap();
+        let (dense_commitments, _) = PC::commit(&ck, &polynomials, None).unwrap();
+        for (commitment, dense_commitment) in commitments.iter().zip(dense_commitments) {
+            assert_eq!(commitment.label(), dense_commitment.label());
+            assert_eq!(commitment.commitment(), dense_commitment.commitment());
+        }
+        assert_eq!(rands.len(), polynomials.len());
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod test {
+    //! Properties of the additive homomorphism that every scheme of this crate must satisfy, checked on random
+    //! polynomials and linear combinations: aggregating commitments commits to the combined polynomial, with or
+    //! without hiding randomness, and the combination opens to the combined evaluations, shifted ones included.
+
+    use crate::{marlin_kzg, sonic_kzg, AdditivelyHomomorphicPCS};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{PrimeField, Zero};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LCTerm, LabeledPolynomial, LinearCombination};
+    use ark_std::rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Supported degree of the keys, one less than a power of two for the inner product argument
+    const MAX_DEGREE: usize = 15;
+    const HIDING_BOUND: usize = 1;
+    const ENFORCED_DEGREE_BOUNDS: [usize; 2] = [7, 10];
+    const NUM_TRIALS: usize = 16;
+    /// Most polynomials and terms of a linear combination
+    const MAX_TERMS: usize = 4;
+
+    /// Random polynomials sharing a degree bound, hiding or not, and a random linear combination of them, possibly
+    /// with repeated terms, along with the combined polynomial
+    #[allow(clippy::type_complexity)]
+    fn random_instance<F: PrimeField, R: Rng>(
+        degree_bound: Option<usize>,
+        hiding: bool,
+        rng: &mut R,
+    ) -> (
+        Vec<LabeledPolynomial<F, DensePolynomial<F>>>,
+        LinearCombination<F>,
+        LabeledPolynomial<F, DensePolynomial<F>>,
+    ) {
+        let hiding_bound = hiding.then_some(HIDING_BOUND);
+        let polynomials = (0..rng.gen_range(1..=MAX_TERMS))
+            .map(|i| {
+                let degree = rng.gen_range(0..=degree_bound.unwrap_or(MAX_DEGREE));
+                LabeledPolynomial::new(
+                    format!("p_{}", i),
+                    DensePolynomial::rand(degree, rng),
+                    degree_bound,
+                    hiding_bound,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let terms = (0..rng.gen_range(1..=MAX_TERMS))
+            .map(|_| {
+                let index = rng.gen_range(0..polynomials.len());
+                (F::rand(rng), index)
+            })
+            .collect::<Vec<_>>();
+        let combined = terms
+            .iter()
+            .fold(DensePolynomial::zero(), |sum, (coeff, index)| {
+                sum + polynomials[*index].polynomial() * *coeff
+            });
+        let lc = LinearCombination::new(
+            "lc",
+            terms
+                .iter()
+                .map(|(coeff, index)| (*coeff, polynomials[*index].label().clone()))
+                .collect::<Vec<_>>(),
+        );
+        let combined =
+            LabeledPolynomial::new(String::from("lc"), combined, degree_bound, hiding_bound);
+
+        (polynomials, lc, combined)
+    }
+
+    fn check_homomorphism<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(seed: u64) {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        let pp = PC::setup(MAX_DEGREE, None, rng).unwrap();
+        let (ck, vk) =
+            PC::trim(&pp, MAX_DEGREE, HIDING_BOUND, Some(&ENFORCED_DEGREE_BOUNDS)).unwrap();
+
+        for trial in 0..NUM_TRIALS {
+            let degree_bound = match rng.gen_range(0..=ENFORCED_DEGREE_BOUNDS.len()) {
+                0 => None,
+                i => Some(ENFORCED_DEGREE_BOUNDS[i - 1]),
+            };
+            let hiding = rng.gen_bool(0.5);
+            let (polynomials, lc, combined) = random_instance::<F, _>(degree_bound, hiding, rng);
+            let case = format!(
+                "trial {} with seed {}: degree bound {:?}, hiding {}, {} polynomials, {} terms",
+                trial,
+                seed,
+                degree_bound,
+                hiding,
+                polynomials.len(),
+                lc.len()
+            );
+
+            // Aggregated commitments commit to the combined polynomial
+            let (commitments, rands) = PC::commit(&ck, &polynomials, Some(rng)).unwrap();
+            let (aggregate, aggregate_rand) =
+                PC::aggregate_commitments(&commitments, Some(rands.clone()), &lc).unwrap();
+            let expected =
+                PC::commit_with_rands(&ck, [&combined], std::slice::from_ref(&aggregate_rand))
+                    .unwrap()
+                    .remove(0);
+            assert_eq!(aggregate.commitment(), expected.commitment(), "{}", case);
+            assert_eq!(aggregate.degree_bound(), degree_bound, "{}", case);
+            assert_eq!(PC::is_hiding(&aggregate_rand), hiding, "{}", case);
+
+            // Without randomness, as a verifier aggregates, the commitment is the same
+            let (verifier_aggregate, _) =
+                PC::aggregate_commitments(&commitments, None, &lc).unwrap();
+            assert_eq!(
+                verifier_aggregate.commitment(),
+                aggregate.commitment(),
+                "{}",
+                case
+            );
+            if !hiding {
+                let (expected, _) = PC::commit(&ck, [&combined], None).unwrap();
+                assert_eq!(aggregate.commitment(), expected[0].commitment(), "{}", case);
+            }
+
+            // The aggregate opens to the combination of the evaluations of the polynomials
+            let point = F::rand(rng);
+            let value = lc.iter().fold(F::zero(), |sum, (coeff, term)| {
+                let p = polynomials
+                    .iter()
+                    .find(|p| matches!(term, LCTerm::PolyLabel(label) if label == p.label()))
+                    .unwrap();
+                sum + *coeff * p.evaluate(&point)
+            });
+            assert_eq!(value, combined.evaluate(&point), "{}", case);
+
+            let opening_challenge = F::rand(rng);
+            let proof = PC::open(
+                &ck,
+                [&combined],
+                [&aggregate],
+                &point,
+                opening_challenge,
+                [&aggregate_rand],
+                Some(rng),
+            )
+            .unwrap();
+            assert!(
+                PC::check(
+                    &vk,
+                    [&verifier_aggregate],
+                    &point,
+                    [value],
+                    &proof,
+                    opening_challenge,
+                    Some(rng),
+                )
+                .unwrap(),
+                "{}",
+                case
+            );
+            assert!(
+                !PC::check(
+                    &vk,
+                    [&verifier_aggregate],
+                    &point,
+                    [value + F::one()],
+                    &proof,
+                    opening_challenge,
+                    Some(rng),
+                )
+                .unwrap(),
+                "{}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn test_marlin_kzg_homomorphism() {
+        check_homomorphism::<Fr, marlin_kzg::KZG10<Bn254>>(0);
+    }
+
+    #[test]
+    fn test_sonic_kzg_homomorphism() {
+        check_homomorphism::<Fr, sonic_kzg::KZG10<Bn254>>(1);
+    }
+
+    #[cfg(feature = "pedersen")]
+    #[test]
+    fn test_pedersen_homomorphism() {
+        check_homomorphism::<Fr, crate::pedersen::PedersenPC<ark_bn254::G1Affine, blake2::Blake2s>>(
+            2,
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::BTreeMap;
+
+use ark_ff::PrimeField;
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+};
+use ark_poly_commit::{
+    Evaluations, LabeledCommitment, LabeledPolynomial, LinearCombination, PCRandomness, QuerySet,
+};
+use ark_std::{marker::PhantomData, rand::RngCore};
+
+use crate::{
+    error::{to_pc_error, Error},
+    AdditivelyHomomorphicPCS,
+};
+
+/// A vector committed to through its interpolating polynomial, together with the data needed to open and update it
+pub struct CommittedVector<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+    pub values: Vec<F>,
+    pub polynomial: LabeledPolynomial<F, DensePolynomial<F>>,
+    pub commitment: LabeledCommitment<PC::Commitment>,
+    pub randomness: PC::Randomness,
+}
+
+/// A vector commitment over an additively homomorphic polynomial commitment scheme. A vector v is committed to as the
+/// polynomial interpolating v over a domain K, so that position i is opened with an evaluation proof at kappa^i.
+/// Commitments to the Lagrange polynomials of K are computed once, which lets anyone update a commitment to v into a
+/// commitment to v + delta * e_i.
+pub struct VectorCommitment<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+    domain: GeneralEvaluationDomain<F>,
+    lagrange_commitments: Vec<LabeledCommitment<PC::Commitment>>,
+    _pc: PhantomData<PC>,
+}
+
+impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> VectorCommitment<F, PC> {
+    /// Set up commitments to vectors of up to `size` elements. Shorter vectors are padded with zeros.
+    pub fn new(ck: &PC::CommitterKey, size: usize) -> Result<Self, Error> {
+        let domain = GeneralEvaluationDomain::<F>::new(size).ok_or_else(|| {
+            Error::InputLengthError(format!("No evaluation domain of size {}", size))
+        })?;
+
+        let lagrange_polynomials = (0..domain.size())
+            .map(|i| {
+                let mut evals = vec![F::zero(); domain.size()];
+                evals[i] = F::one();
+                LabeledPolynomial::new(
+                    Self::lagrange_label(i),
+                    DensePolynomial::from_coefficients_vec(domain.ifft(&evals)),
+                    None,
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (lagrange_commitments, _) =
+            PC::commit(ck, &lagrange_polynomials, None).map_err(to_pc_error::<F, PC>)?;
+
+        Ok(Self {
+            domain,
+            lagrange_commitments,
+            _pc: PhantomData,
+        })
+    }
+
+    /// Maximum length of a committed vector
+    pub fn size(&self) -> usize {
+        self.domain.size()
+    }
+
+    pub fn domain(&self) -> &GeneralEvaluationDomain<F> {
+        &self.domain
+    }
+
+    /// Commitments to the Lagrange polynomials of K, i.e. to the unit vectors
+    pub fn lagrange_commitments(&self) -> &[LabeledCommitment<PC::Commitment>] {
+        &self.lagrange_commitments
+    }
+
+    pub fn commit<R: RngCore>(
+        &self,
+        ck: &PC::CommitterKey,
+        label: &str,
+        values: &[F],
+        hiding_bound: Option<usize>,
+        rng: &mut R,
+    ) -> Result<CommittedVector<F, PC>, Error> {
+        if values.len() > self.size() {
+            return Err(Error::InputLengthError(format!(
+                "Cannot commit to {} values with a vector commitment of size {}",
+                values.len(),
+                self.size()
+            )));
+        }
+
+        let mut values = values.to_vec();
+        values.resize(self.size(), F::zero());
+
+        let polynomial = LabeledPolynomial::new(
+            label.to_string(),
+            DensePolynomial::from_coefficients_vec(self.domain.ifft(&values)),
+            None,
+            hiding_bound,
+        );
+        let (commitments, rands) =
+            PC::commit(ck, [&polynomial], Some(rng)).map_err(to_pc_error::<F, PC>)?;
+
+        Ok(CommittedVector {
+            values,
+            polynomial,
+            commitment: commitments[0].clone(),
+            randomness: rands[0].clone(),
+        })
+    }
+
+    /// Prove the value at position `index`
+    pub fn open<R: RngCore>(
+        &self,
+        ck: &PC::CommitterKey,
+        committed: &CommittedVector<F, PC>,
+        index: usize,
+        rng: &mut R,
+    ) -> Result<PC::Proof, Error> {
+        self.check_index(index)?;
+        PC::open(
+            ck,
+            [&committed.polynomial],
+            [&committed.commitment],
+            &self.domain.element(index),
+            F::one(),
+            [&committed.randomness],
+            Some(rng),
+        )
+        .map_err(to_pc_error::<F, PC>)
+    }
+
+    /// Check that the committed vector has `value` at position `index`
+    pub fn verify(
+        &self,
+        vk: &PC::VerifierKey,
+        commitment: &LabeledCommitment<PC::Commitment>,
+        index: usize,
+        value: F,
+        proof: &PC::Proof,
+    ) -> Result<bool, Error> {
+        self.check_index(index)?;
+        PC::check(
+            vk,
+            [commitment],
+            &self.domain.element(index),
+            [value],
+            proof,
+            F::one(),
+            None,
+        )
+        .map_err(to_pc_error::<F, PC>)
+    }
+
+    /// Prove the values at several positions at once
+    pub fn batch_open<R: RngCore>(
+        &self,
+        ck: &PC::CommitterKey,
+        committed: &CommittedVector<F, PC>,
+        indices: &[usize],
+        rng: &mut R,
+    ) -> Result<PC::BatchProof, Error> {
+        let query_set = self.query_set(committed.commitment.label(), indices)?;
+        PC::batch_open(
+            ck,
+            [&committed.polynomial],
+            [&committed.commitment],
+            &query_set,
+            F::one(),
+            [&committed.randomness],
+            Some(rng),
+        )
+        .map_err(to_pc_error::<F, PC>)
+    }
+
+    /// Check a batch opening of the (position, value) pairs in `openings`
+    pub fn batch_verify<R: RngCore>(
+        &self,
+        vk: &PC::VerifierKey,
+        commitment: &LabeledCommitment<PC::Commitment>,
+        openings: &[(usize, F)],
+        proof: &PC::BatchProof,
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        let indices = openings.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        let query_set = self.query_set(commitment.label(), &indices)?;
+        let evaluations: Evaluations<F, F> = openings
+            .iter()
+            .map(|(i, value)| {
+                (
+                    (commitment.label().clone(), self.domain.element(*i)),
+                    *value,
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        PC::batch_check(
+            vk,
+            [commitment],
+            &query_set,
+            &evaluations,
+            proof,
+            F::one(),
+            rng,
+        )
+        .map_err(to_pc_error::<F, PC>)
+    }
+
+    /// Set position `index` to `value`. The commitment is updated homomorphically and keeps its randomness.
+    pub fn update(
+        &self,
+        committed: &CommittedVector<F, PC>,
+        index: usize,
+        value: F,
+    ) -> Result<CommittedVector<F, PC>, Error> {
+        self.check_index(index)?;
+        let delta = value - committed.values[index];
+
+        let mut values = committed.values.clone();
+        values[index] = value;
+
+        let mut delta_evals = vec![F::zero(); self.size()];
+        delta_evals[index] = delta;
+        let delta_polynomial =
+            DensePolynomial::from_coefficients_vec(self.domain.ifft(&delta_evals));
+        let polynomial = LabeledPolynomial::new(
+            committed.polynomial.label().clone(),
+            committed.polynomial.polynomial() + &delta_polynomial,
+            committed.polynomial.degree_bound(),
+            committed.polynomial.hiding_bound(),
+        );
+
+        let (commitment, randomness) = PC::aggregate_commitments(
+            &[
+                committed.commitment.clone(),
+                self.lagrange_commitments[index].clone(),
+            ],
+            Some(vec![committed.randomness.clone(), PC::Randomness::empty()]),
+            &self.update_linear_combination(committed.commitment.label(), index, delta),
+        )?;
+
+        Ok(CommittedVector {
+            values,
+            polynomial,
+            commitment,
+            randomness,
+        })
+    }
+
+    /// Turn a commitment to v into a commitment to v + delta * e_index, without knowledge of v
+    pub fn update_commitment(
+        &self,
+        commitment: &LabeledCommitment<PC::Commitment>,
+        index: usize,
+        delta: F,
+    ) -> Result<LabeledCommitment<PC::Commitment>, Error> {
+        self.check_index(index)?;
+        let (commitment, _) = PC::aggregate_commitments(
+            &[commitment.clone(), self.lagrange_commitments[index].clone()],
+            None,
+            &self.update_linear_combination(commitment.label(), index, delta),
+        )?;
+
+        Ok(commitment)
+    }
+
+    fn update_linear_combination(
+        &self,
+        label: &str,
+        index: usize,
+        delta: F,
+    ) -> LinearCombination<F> {
+        LinearCombination::new(
+            label,
+            vec![
+                (F::one(), label.to_string()),
+                (delta, Self::lagrange_label(index)),
+            ],
+        )
+    }
+
+    fn query_set(&self, label: &str, indices: &[usize]) -> Result<QuerySet<F>, Error> {
+        indices
+            .iter()
+            .map(|&i| {
+                self.check_index(i)?;
+                Ok((
+                    label.to_string(),
+                    (format!("kappa_{}", i), self.domain.element(i)),
+                ))
+            })
+            .collect()
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), Error> {
+        if index >= self.size() {
+            return Err(Error::InputLengthError(format!(
+                "Position {} is out of range for a vector commitment of size {}",
+                index,
+                self.size()
+            )));
+        }
+        Ok(())
+    }
+
+    fn lagrange_label(index: usize) -> String {
+        format!("lagrange_{}", index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{error::Error, marlin_kzg::KZG10, vector_commitment::VectorCommitment};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::UniformRand;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+
+    #[test]
+    fn test_open_positions() {
+        let rng = &mut thread_rng();
+        let pp = PC::setup(16, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 16, 1, None).unwrap();
+
+        let vc = VectorCommitment::<F, PC>::new(&ck, 6).unwrap();
+        assert_eq!(vc.size(), 8);
+
+        let values = (0..6).map(|_| F::rand(rng)).collect::<Vec<_>>();
+        let committed = vc.commit(&ck, "v", &values, Some(1), rng).unwrap();
+
+        let proof = vc.open(&ck, &committed, 3, rng).unwrap();
+        assert!(vc
+            .verify(&vk, &committed.commitment, 3, values[3], &proof)
+            .unwrap());
+        assert!(!vc
+            .verify(&vk, &committed.commitment, 3, values[2], &proof)
+            .unwrap());
+
+        // padding positions open to zero
+        let proof = vc.open(&ck, &committed, 7, rng).unwrap();
+        assert!(vc
+            .verify(&vk, &committed.commitment, 7, F::from(0u64), &proof)
+            .unwrap());
+
+        let indices = [0, 2, 5];
+        let proof = vc.batch_open(&ck, &committed, &indices, rng).unwrap();
+        let openings = indices.map(|i| (i, values[i]));
+        assert!(vc
+            .batch_verify(&vk, &committed.commitment, &openings, &proof, rng)
+            .unwrap());
+
+        let wrong_openings = [(0, values[0]), (2, values[2]), (5, values[4])];
+        assert!(!vc
+            .batch_verify(&vk, &committed.commitment, &wrong_openings, &proof, rng)
+            .unwrap());
+
+        assert!(matches!(
+            vc.open(&ck, &committed, 8, rng),
+            Err(Error::InputLengthError(_))
+        ));
+    }
+
+    #[test]
+    fn test_update() {
+        let rng = &mut thread_rng();
+        let pp = PC::setup(16, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 16, 1, None).unwrap();
+
+        let vc = VectorCommitment::<F, PC>::new(&ck, 4).unwrap();
+        let values = (0..4).map(|_| F::rand(rng)).collect::<Vec<_>>();
+        let committed = vc.commit(&ck, "v", &values, Some(1), rng).unwrap();
+
+        let new_value = F::rand(rng);
+        let updated = vc.update(&committed, 1, new_value).unwrap();
+        assert_eq!(updated.values[1], new_value);
+
+        // the verifier can derive the same commitment from the difference alone
+        let derived = vc
+            .update_commitment(&committed.commitment, 1, new_value - values[1])
+            .unwrap();
+        assert_eq!(derived.commitment(), updated.commitment.commitment());
+
+        let proof = vc.open(&ck, &updated, 1, rng).unwrap();
+        assert!(vc.verify(&vk, &derived, 1, new_value, &proof).unwrap());
+
+        let proof = vc.open(&ck, &updated, 2, rng).unwrap();
+        assert!(vc.verify(&vk, &derived, 2, values[2], &proof).unwrap());
+    }
+}
@@ -1,9 +1,9 @@
 use std::collections::BTreeMap;
 
-use ark_ff::PrimeField;
+use ark_ff::{PrimeField, ToBytes};
 use ark_poly::{
     univariate::DensePolynomial, EvaluationDomain, Evaluations as EvaluationsOnDomain,
-    GeneralEvaluationDomain,
+    GeneralEvaluationDomain, UVPolynomial,
 };
 use ark_relations::r1cs::Matrix;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
@@ -44,6 +44,71 @@ pub struct MatrixArithmetization<F: PrimeField> {
     pub evals_on_k: MatrixEvals<F>,
 }
 
+/// How the `val` polynomial of a [`MatrixArithmetization`] encodes the entries of the matrix
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValEncoding {
+    /// val(k) = M[r][c] / u_H(c, c), as produced by [`arithmetize_matrix`]
+    #[default]
+    Normalized,
+    /// val(k) = M[r][c], as in the index of a functional commitment. Since u_H(x, x) = |H| / x over H and `row`
+    /// encodes the columns of M, the normalized value is recovered as val * row / |H| over K.
+    Raw,
+}
+
+impl ValEncoding {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Normalized => 0,
+            Self::Raw => 1,
+        }
+    }
+
+    /// The normalized val polynomial of a matrix given its `val` and `row` polynomials in this encoding
+    pub fn normalized_val<F: PrimeField>(
+        &self,
+        val: &DensePolynomial<F>,
+        row: &DensePolynomial<F>,
+        domain_h: &GeneralEvaluationDomain<F>,
+    ) -> DensePolynomial<F> {
+        match self {
+            Self::Normalized => val.clone(),
+            Self::Raw => {
+                let h_inv = DensePolynomial::from_coefficients_slice(&[domain_h
+                    .size_as_field_element()
+                    .inverse()
+                    .unwrap()]);
+                &(val * row) * &h_inv
+            }
+        }
+    }
+}
+
+impl ToBytes for ValEncoding {
+    fn write<W: Write>(&self, writer: W) -> ark_std::io::Result<()> {
+        self.tag().write(writer)
+    }
+}
+
+impl CanonicalSerialize for ValEncoding {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.tag().serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.tag().serialized_size()
+    }
+}
+
+impl CanonicalDeserialize for ValEncoding {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        match u8::deserialize(reader)? {
+            0 => Ok(Self::Normalized),
+            1 => Ok(Self::Raw),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
 pub fn arithmetize_matrix<F: PrimeField>(
     m: &Matrix<F>,
     interpolation_domain: GeneralEvaluationDomain<F>,
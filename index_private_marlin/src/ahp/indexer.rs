@@ -5,7 +5,7 @@ use derivative::Derivative;
 
 use ark_std::io::{Read, Write};
 
-use super::constraint_systems::MatrixArithmetization;
+use super::constraint_systems::{MatrixArithmetization, ValEncoding};
 
 /// Represents a matrix.
 pub type Matrix<F> = Vec<Vec<(F, usize)>>;
@@ -27,6 +27,9 @@ pub struct Index<F: PrimeField> {
     /// The C matrix arithmetization
     pub c_arith: MatrixArithmetization<F>,
 
+    /// How the val polynomials of the arithmetizations encode the entries of the matrices
+    pub val_encoding: ValEncoding,
+
     /// tmp store matrices
     pub a: Matrix<F>,
     pub b: Matrix<F>,
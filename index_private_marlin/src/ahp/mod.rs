@@ -8,7 +8,7 @@ use ark_std::cfg_iter_mut;
 
 use self::constraint_systems::LabeledPolynomial;
 
-pub(crate) mod constraint_systems;
+pub mod constraint_systems;
 
 pub mod indexer;
 pub mod prover;
@@ -4,6 +4,7 @@ use ark_poly::{
     GeneralEvaluationDomain, Polynomial, UVPolynomial,
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use derivative::Derivative;
 
 use ac_compiler::R1CSfIndex;
 use ark_std::{cfg_iter_mut, end_timer, rand::RngCore, start_timer};
@@ -11,7 +12,7 @@ use ark_std::{cfg_iter_mut, end_timer, rand::RngCore, start_timer};
 use crate::ahp::UnnormalizedBivariateLagrangePoly;
 
 use super::{
-    constraint_systems::LabeledPolynomial,
+    constraint_systems::{LabeledPolynomial, MatrixArithmetization},
     indexer::{Index, Matrix},
     verifier::{VerifierFirstMsg, VerifierSecondMsg},
     AHPForR1CS, Error,
@@ -69,7 +70,7 @@ impl<'a, F: PrimeField> ProverState<'a, F> {
 }
 
 /// Each prover message that is not a list of oracles is a list of field elements.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ProverMsg<F: Field> {
     /// Some rounds, the prover sends only oracles. (This is actually the case for all
     /// rounds in Marlin.)
@@ -161,17 +162,24 @@ impl<F: Field> CanonicalDeserialize for ProverMsg<F> {
 }
 
 /// The first set of prover oracles.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
 pub struct ProverFirstOracles<F: Field> {
     /// The LDE of `z`.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub z: LabeledPolynomial<F>,
     /// The LDE of `Az`.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub z_a: LabeledPolynomial<F>,
     /// The LDE of `Bz`.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub z_b: LabeledPolynomial<F>,
     /// The sum-check hiding polynomial.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub mask_poly: LabeledPolynomial<F>,
 
     /// The inner sum-check hiding polynomial.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub inner_mask_poly: LabeledPolynomial<F>,
 }
 
@@ -194,12 +202,17 @@ impl<F: Field> ProverFirstOracles<F> {
 }
 
 /// The second set of prover oracles.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
 pub struct ProverSecondOracles<F: Field> {
     /// The polynomial `t` that is produced in the first round.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub t: LabeledPolynomial<F>,
     /// The polynomial `g` resulting from the first sumcheck.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub g_1: LabeledPolynomial<F>,
     /// The polynomial `h` resulting from the first sumcheck.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub h_1: LabeledPolynomial<F>,
 }
 
@@ -211,10 +224,14 @@ impl<F: Field> ProverSecondOracles<F> {
 }
 
 /// The third set of prover oracles.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
 pub struct ProverThirdOracles<F: Field> {
     /// The polynomial `f` resulting from the a / b
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub f: LabeledPolynomial<F>,
     /// The polynomial `g` resulting from the second sumcheck.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub g_2: LabeledPolynomial<F>,
     // The polynomial `h` resulting from the second sumcheck.
     // pub h_2: LabeledPolynomial<F>, //TODO NOT H2
@@ -561,17 +578,25 @@ impl<F: PrimeField> AHPForR1CS<F> {
         let eta_b_times_v_h_alpha_v_h_beta = eta_b * v_h_alpha_v_h_beta;
         let eta_c_times_v_h_alpha_v_h_beta = eta_c * v_h_alpha_v_h_beta;
 
+        let normalized_val = |arith: &MatrixArithmetization<F>| {
+            index.val_encoding.normalized_val(
+                arith.val.polynomial(),
+                arith.row.polynomial(),
+                &domain_h,
+            )
+        };
+
         let a_row = index.a_arith.row.polynomial();
         let a_col = index.a_arith.col.polynomial();
-        let a_val = index.a_arith.val.polynomial();
+        let a_val = &normalized_val(&index.a_arith);
 
         let b_row = index.b_arith.row.polynomial();
         let b_col = index.b_arith.col.polynomial();
-        let b_val = index.b_arith.val.polynomial();
+        let b_val = &normalized_val(&index.b_arith);
 
         let c_row = index.c_arith.row.polynomial();
         let c_col = index.c_arith.col.polynomial();
-        let c_val = index.c_arith.val.polynomial();
+        let c_val = &normalized_val(&index.c_arith);
 
         let a_part_denom = &(&(DensePolynomial::from_coefficients_slice(&[beta])) - a_row)
             * &(&(DensePolynomial::from_coefficients_slice(&[alpha])) - a_col);
@@ -9,6 +9,7 @@ use ark_poly::EvaluationDomain;
 use ark_poly::GeneralEvaluationDomain;
 use ark_poly_commit::QuerySet;
 use ark_std::rand::RngCore;
+use derivative::Derivative;
 
 use super::AHPForR1CS;
 
@@ -44,13 +45,19 @@ pub struct VerifierSecondMsg<F> {
 }
 
 /// The first set of prover oracles.
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
 pub struct VerifierWellFormationOracles<F: PrimeField> {
     /// The LDE of `pi`.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub x: LabeledPolynomial<F>,
     /// The LDE of `output`.
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub y: LabeledPolynomial<F>,
 
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub vh_gt_x: LabeledPolynomial<F>,
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub vh_lt_y: LabeledPolynomial<F>,
 }
 
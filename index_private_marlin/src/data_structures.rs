@@ -1,12 +1,18 @@
+use crate::ahp::constraint_systems::ValEncoding;
 use crate::ahp::indexer::*;
 use crate::ahp::prover::ProverMsg;
 use crate::Vec;
 use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
-use ark_poly_commit::{BatchLCProof, PolynomialCommitment};
+use ark_poly_commit::BatchLCProof;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
-use ark_std::io::{Read, Write};
+use ark_std::{
+    fmt,
+    io::{Read, Write},
+};
+use derivative::Derivative;
 
+use ::zero_over_k::config::{pc, ProtocolConfig};
 use ::zero_over_k::zero_over_k::proof::Proof as ZeroOverKProof;
 use ac_compiler::R1CSfIndex;
 use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
@@ -16,7 +22,7 @@ use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
 /* ************************************************************************* */
 
 /// The universal public parameters for the argument system.
-pub type UniversalSRS<F, PC> = <PC as PolynomialCommitment<F, DensePolynomial<F>>>::UniversalParams;
+pub type UniversalSRS<C> = pc::UniversalParams<C>;
 
 /* ************************************************************************* */
 /* ************************************************************************* */
@@ -24,20 +30,20 @@ pub type UniversalSRS<F, PC> = <PC as PolynomialCommitment<F, DensePolynomial<F>
 
 /// Proving key for a specific index (i.e., R1CS matrices).
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct ProverKey<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+pub struct ProverKey<C: ProtocolConfig> {
     /// The index verifier key.
-    pub vk: VerifierKey<F, PC>,
+    pub vk: VerifierKey<C>,
     /// The randomness used for hiding matrix ldes
-    pub rands: Vec<PC::Randomness>,
+    pub rands: Vec<pc::Randomness<C>>,
     /// The index itself.
-    pub index: Index<F>,
+    pub index: Index<C::F>,
     /// The committer key for this index, trimmed from the universal SRS.
-    pub committer_key: PC::CommitterKey,
+    pub committer_key: pc::CommitterKey<C>,
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> Clone for ProverKey<F, PC>
+impl<C: ProtocolConfig> Clone for ProverKey<C>
 where
-    PC::Commitment: Clone,
+    pc::Commitment<C>: Clone,
 {
     fn clone(&self) -> Self {
         Self {
@@ -49,8 +55,8 @@ where
     }
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> ProverKey<F, PC> {
-    pub fn get_rands(&self) -> Vec<PC::Randomness> {
+impl<C: ProtocolConfig> ProverKey<C> {
+    pub fn get_rands(&self) -> Vec<pc::Randomness<C>> {
         self.rands.clone()
     }
 }
@@ -59,7 +65,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> ProverKey<F, PC> {
 /// Prover will commit to matrix arithmetizations and this data will be used for
 /// slt and diag testing
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct VerifierKey<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+pub struct VerifierKey<C: ProtocolConfig> {
     // /// matrix a row commitment
     // pub a_row_commit: PC::Commitment,
 
@@ -89,36 +95,41 @@ pub struct VerifierKey<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
     /// a(row, col, val), b(row, col, val), c(row, col, val),
 
     /// commitments of (row, col, val) for each matrix
-    pub commits: Vec<PC::Commitment>,
+    pub commits: Vec<pc::Commitment<C>>,
 
     /// verifier key
-    pub verifier_key: PC::VerifierKey,
+    pub verifier_key: pc::VerifierKey<C>,
 
     /// Stores information about the size of the index, as well as its field of
     /// definition.
     pub index_info: R1CSfIndex,
+
+    /// How the committed val polynomials encode the entries of the matrices
+    pub val_encoding: ValEncoding,
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> Clone for VerifierKey<F, PC> {
+impl<C: ProtocolConfig> Clone for VerifierKey<C> {
     fn clone(&self) -> Self {
         Self {
             commits: self.commits.clone(),
             index_info: self.index_info.clone(),
             verifier_key: self.verifier_key.clone(),
+            val_encoding: self.val_encoding,
         }
     }
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> ark_ff::ToBytes for VerifierKey<F, PC> {
+impl<C: ProtocolConfig> ark_ff::ToBytes for VerifierKey<C> {
     fn write<W: Write>(&self, mut w: W) -> ark_std::io::Result<()> {
         self.index_info.write(&mut w)?;
-        self.commits.write(&mut w)
+        self.commits.write(&mut w)?;
+        self.val_encoding.write(&mut w)
     }
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> VerifierKey<F, PC> {
+impl<C: ProtocolConfig> VerifierKey<C> {
     /// Iterate over the commitments to indexed polynomials in `self`.
-    pub fn iter(&self) -> impl Iterator<Item = &PC::Commitment> {
+    pub fn iter(&self) -> impl Iterator<Item = &pc::Commitment<C>> {
         self.commits.iter()
     }
 }
@@ -128,30 +139,41 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> VerifierKey<F, PC> {
 /* ************************************************************************* */
 
 /// A zkSNARK index private proof.
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+pub struct Proof<C: ProtocolConfig> {
     /// Commitments to the polynomials produced by the AHP prover.
-    pub commitments: Vec<Vec<PC::Commitment>>,
+    pub commitments: Vec<Vec<pc::Commitment<C>>>,
     /// Evaluations of these polynomials.
-    pub evaluations: Vec<F>,
+    pub evaluations: Vec<C::F>,
     /// The field elements sent by the prover.
-    pub prover_messages: Vec<ProverMsg<F>>,
+    pub prover_messages: Vec<ProverMsg<C::F>>,
     /// An evaluation proof from the polynomial commitment.
-    pub pc_proof: BatchLCProof<F, DensePolynomial<F>, PC>,
-
-    pub rational_sumcheck_zero_over_k_proof: ZeroOverKProof<F, PC>,
-    pub well_formation_proof: ZeroOverKProof<F, PC>,
+    #[derivative(
+        Clone(clone_with = "clone_batch_lc_proof"),
+        Debug(format_with = "fmt_batch_lc_proof"),
+        PartialEq(compare_with = "eq_batch_lc_proof")
+    )]
+    pub pc_proof: BatchLCProof<C::F, DensePolynomial<C::F>, C::PC>,
+
+    pub rational_sumcheck_zero_over_k_proof: ZeroOverKProof<C>,
+    pub well_formation_proof: ZeroOverKProof<C>,
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> Proof<F, PC> {
+impl<C: ProtocolConfig> Proof<C> {
     /// Construct a new proof.
     pub fn new(
-        commitments: Vec<Vec<PC::Commitment>>,
-        evaluations: Vec<F>,
-        prover_messages: Vec<ProverMsg<F>>,
-        pc_proof: BatchLCProof<F, DensePolynomial<F>, PC>,
-        rational_sumcheck_zero_over_k_proof: ZeroOverKProof<F, PC>,
-        well_formation_proof: ZeroOverKProof<F, PC>,
+        commitments: Vec<Vec<pc::Commitment<C>>>,
+        evaluations: Vec<C::F>,
+        prover_messages: Vec<ProverMsg<C::F>>,
+        pc_proof: BatchLCProof<C::F, DensePolynomial<C::F>, C::PC>,
+        rational_sumcheck_zero_over_k_proof: ZeroOverKProof<C>,
+        well_formation_proof: ZeroOverKProof<C>,
     ) -> Self {
         Self {
             commitments,
@@ -163,3 +185,34 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> Proof<F, PC> {
         }
     }
 }
+
+// `BatchLCProof` derives `Clone` only for a `Clone` scheme, and neither `Debug` nor `PartialEq`
+
+fn clone_batch_lc_proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    proof: &BatchLCProof<F, DensePolynomial<F>, PC>,
+) -> BatchLCProof<F, DensePolynomial<F>, PC> {
+    BatchLCProof {
+        proof: proof.proof.clone(),
+        evals: proof.evals.clone(),
+    }
+}
+
+fn fmt_batch_lc_proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    proof: &BatchLCProof<F, DensePolynomial<F>, PC>,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    f.debug_struct("BatchLCProof")
+        .field("proof", &proof.proof)
+        .field("evals", &proof.evals)
+        .finish()
+}
+
+fn eq_batch_lc_proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    a: &BatchLCProof<F, DensePolynomial<F>, PC>,
+    b: &BatchLCProof<F, DensePolynomial<F>, PC>,
+) -> bool
+where
+    PC::BatchProof: PartialEq,
+{
+    a.proof == b.proof && a.evals == b.evals
+}
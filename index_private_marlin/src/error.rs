@@ -17,6 +17,9 @@ pub enum Error<E> {
     /// Number of constraints is larger than number of non zero elements (we don't allow this because Discrete-log Comparison in proof of function fails)
     DomainHLargerThanDomainK,
     DomainTooLarge,
+
+    /// The arithmetized index does not match its commitments or the labels and domains of the protocol
+    IndexMismatch(String),
 }
 
 impl<E> From<AHPError> for Error<E> {
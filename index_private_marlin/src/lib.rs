@@ -3,25 +3,25 @@ pub mod data_structures;
 pub mod error;
 pub mod well_formation;
 
-use crate::ahp::{constraint_systems::LabeledPolynomial, AHPForR1CS, EvaluationsProvider};
+use crate::ahp::{AHPForR1CS, EvaluationsProvider};
 use ::zero_over_k::{
+    config::{pc, ProtocolConfig},
     virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
     vo_constant,
     zero_over_k::ZeroOverK,
 };
 use ac_compiler::R1CSfIndex;
 use ahp::{
-    constraint_systems::arithmetize_matrix,
+    constraint_systems::{arithmetize_matrix, ValEncoding},
     indexer::{Index, Matrix},
 };
-use ark_ff::{to_bytes, PrimeField, UniformRand};
+use ark_ff::{to_bytes, Field, One, UniformRand, Zero};
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
-use ark_poly_commit::Evaluations;
 use ark_poly_commit::LabeledCommitment;
+use ark_poly_commit::{Evaluations, PolynomialCommitment};
 use ark_std::{iter, rand::RngCore};
 use data_structures::{Proof, ProverKey, UniversalSRS, VerifierKey};
-use fiat_shamir_rng::FiatShamirRng;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use fiat_shamir_rng::{Absorb, Context, FiatShamirRng};
 
 #[macro_use]
 extern crate ark_std;
@@ -33,13 +33,9 @@ pub use error::*;
 // pub use ahp::constraint_systems::arithmetize_matrix; //TODO: for
 
 /// The compiled argument system.
-pub struct Marlin<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng>(
-    PhantomData<F>,
-    PhantomData<PC>,
-    PhantomData<FS>,
-);
+pub struct Marlin<C: ProtocolConfig>(PhantomData<C>);
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F, PC, FS> {
+impl<C: ProtocolConfig> Marlin<C> {
     pub const PROTOCOL_NAME: &'static [u8] = b"INDEX-PRIVATE-MARLIN";
 
     /// Generate the universal prover and verifier keys for the
@@ -47,8 +43,8 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
     pub fn universal_setup<R: RngCore>(
         index_info: &R1CSfIndex,
         rng: &mut R,
-    ) -> Result<UniversalSRS<F, PC>, Error<PC::Error>> {
-        let max_degree = AHPForR1CS::<F>::max_degree(index_info)?;
+    ) -> Result<UniversalSRS<C>, Error<pc::Error<C>>> {
+        let max_degree = AHPForR1CS::<C::F>::max_degree(index_info)?;
         let setup_time = start_timer!(|| {
             format!(
             "Marlin::UniversalSetup with max_degree {}, computed for a maximum of {} constraints, {} vars, {} non_zero",
@@ -56,33 +52,33 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         )
         });
 
-        let srs = PC::setup(max_degree, None, rng).map_err(Error::from_pc_err);
+        let srs = C::PC::setup(max_degree, None, rng).map_err(Error::from_pc_err);
         end_timer!(setup_time);
         srs
     }
 
     pub fn index<R: RngCore>(
-        srs: &UniversalSRS<F, PC>,
+        srs: &UniversalSRS<C>,
         index_info: &R1CSfIndex,
-        a: Matrix<F>,
-        b: Matrix<F>,
-        c: Matrix<F>,
+        a: Matrix<C::F>,
+        b: Matrix<C::F>,
+        c: Matrix<C::F>,
         rng: &mut R,
-    ) -> Result<(ProverKey<F, PC>, VerifierKey<F, PC>), Error<PC::Error>> {
-        if !index_info.check_domains_sizes::<F>() {
+    ) -> Result<(ProverKey<C>, VerifierKey<C>), Error<pc::Error<C>>> {
+        if !index_info.check_domains_sizes::<C::F>() {
             return Err(Error::DomainHLargerThanDomainK);
         }
-        let domain_k = GeneralEvaluationDomain::<F>::new(index_info.number_of_non_zero_entries)
+        let domain_k = GeneralEvaluationDomain::<C::F>::new(index_info.number_of_non_zero_entries)
             .ok_or(Error::DomainTooLarge)?;
-        let domain_h = GeneralEvaluationDomain::<F>::new(index_info.number_of_constraints)
+        let domain_h = GeneralEvaluationDomain::<C::F>::new(index_info.number_of_constraints)
             .ok_or(Error::DomainTooLarge)?;
 
         let supported_hiding_bound = 1;
 
-        let max_degree = AHPForR1CS::<F>::max_degree(index_info)?;
-        let degree_bounds = AHPForR1CS::<F>::get_degree_bounds(index_info);
+        let max_degree = AHPForR1CS::<C::F>::max_degree(index_info)?;
+        let degree_bounds = AHPForR1CS::<C::F>::get_degree_bounds(index_info);
 
-        let (committer_key, verifier_key) = PC::trim(
+        let (committer_key, verifier_key) = C::PC::trim(
             &srs,
             max_degree,
             supported_hiding_bound,
@@ -133,53 +129,117 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         ];
 
         let (matrix_poly_commits, matrix_poly_rands): (_, _) =
-            PC::commit(&committer_key, &polys, Some(rng)).map_err(Error::from_pc_err)?;
+            C::PC::commit(&committer_key, &polys, Some(rng)).map_err(Error::from_pc_err)?;
 
         let matrix_poly_commits = matrix_poly_commits
             .iter()
             .map(|c| c.commitment().clone())
             .collect::<Vec<_>>();
 
-        let vk: VerifierKey<F, PC> = VerifierKey {
-            commits: matrix_poly_commits,
-            verifier_key,
-            index_info: index_info.clone(),
-        };
-
-        let index = Index::<F> {
+        let index = Index::<C::F> {
             index_info: index_info.clone(),
             a_arith,
             b_arith,
             c_arith,
+            val_encoding: ValEncoding::Normalized,
 
             a,
             b,
             c,
         };
 
+        Self::index_from_commitments(
+            committer_key,
+            verifier_key,
+            index,
+            matrix_poly_commits,
+            matrix_poly_rands,
+        )
+    }
+
+    /// Build the keys of an index whose matrices were arithmetized and committed to outside of [`Marlin::index`],
+    /// e.g. by a functional commitment, so that proofs refer to these very commitments. The arithmetizations must
+    /// encode the transposes of the matrices as [`arithmetize_matrix`] does, up to the encoding of their values given
+    /// by `index.val_encoding`, and be labelled like it. `commits` and `rands` are those of row, col and val of A, B
+    /// and C, in this order. The committer key must support the degree bounds of
+    /// [`AHPForR1CS::get_degree_bounds`].
+    #[allow(clippy::type_complexity)]
+    pub fn index_from_commitments(
+        committer_key: pc::CommitterKey<C>,
+        verifier_key: pc::VerifierKey<C>,
+        index: Index<C::F>,
+        commits: Vec<pc::Commitment<C>>,
+        rands: Vec<pc::Randomness<C>>,
+    ) -> Result<(ProverKey<C>, VerifierKey<C>), Error<pc::Error<C>>> {
+        if !index.index_info.check_domains_sizes::<C::F>() {
+            return Err(Error::DomainHLargerThanDomainK);
+        }
+        let domain_k =
+            GeneralEvaluationDomain::<C::F>::new(index.index_info.number_of_non_zero_entries)
+                .ok_or(Error::DomainTooLarge)?;
+
+        let num_polys = AHPForR1CS::<C::F>::MATRIX_POLY_LABELS.len();
+        if commits.len() != num_polys || rands.len() != num_polys {
+            return Err(Error::IndexMismatch(format!(
+                "Expected {} matrix commitments and randomness, got {} and {}",
+                num_polys,
+                commits.len(),
+                rands.len()
+            )));
+        }
+
+        let polys = [&index.a_arith, &index.b_arith, &index.c_arith]
+            .into_iter()
+            .flat_map(|arith| [&arith.row, &arith.col, &arith.val]);
+        for (poly, label) in polys.zip(AHPForR1CS::<C::F>::MATRIX_POLY_LABELS) {
+            if poly.label() != label {
+                return Err(Error::IndexMismatch(format!(
+                    "Expected a polynomial labelled {}, got {}",
+                    label,
+                    poly.label()
+                )));
+            }
+            if poly.degree() >= domain_k.size() {
+                return Err(Error::IndexMismatch(format!(
+                    "Polynomial {} of degree {} is not defined over a domain K of size {}",
+                    label,
+                    poly.degree(),
+                    domain_k.size()
+                )));
+            }
+        }
+
+        let vk: VerifierKey<C> = VerifierKey {
+            commits,
+            verifier_key,
+            index_info: index.index_info.clone(),
+            val_encoding: index.val_encoding,
+        };
+
         let pk = ProverKey {
-            index: index.clone(),
-            rands: matrix_poly_rands.clone(),
+            index,
+            rands,
             vk: vk.clone(),
-            committer_key: committer_key.clone(),
+            committer_key,
         };
 
         Ok((pk, vk))
-        // arith matrices
     }
 
     pub fn prove<R: RngCore>(
-        pk: &ProverKey<F, PC>,
-        assignment: Vec<F>,
+        pk: &ProverKey<C>,
+        assignment: Vec<C::F>,
+        context: &Context,
         zk_rng: &mut R,
-    ) -> Result<Proof<F, PC>, Error<PC::Error>> {
+    ) -> Result<Proof<C>, Error<pc::Error<C>>> {
         let prover_time = start_timer!(|| "Marlin::Prover");
 
         let prover_init_state = AHPForR1CS::prover_init(&pk.index, assignment)?;
         let public_input = prover_init_state.public_input();
 
-        let mut fs_rng =
-            FS::initialize(&to_bytes![&Self::PROTOCOL_NAME, &pk.vk, &public_input].unwrap());
+        let mut fs_rng = C::FS::initialize(
+            &to_bytes![&Self::PROTOCOL_NAME, context, &pk.vk, &public_input].unwrap(),
+        );
 
         // --------------------------------------------------------------------
         // First round
@@ -189,7 +249,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 
         let first_round_comm_time = start_timer!(|| "Committing to first round polys");
         let (first_comms, first_comm_rands) =
-            PC::commit(&pk.committer_key, prover_first_oracles.iter(), Some(zk_rng))
+            C::PC::commit(&pk.committer_key, prover_first_oracles.iter(), Some(zk_rng))
                 .map_err(Error::from_pc_err)?;
         end_timer!(first_round_comm_time);
 
@@ -209,7 +269,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         let domain_h = prover_state.domain_h.clone();
 
         let second_round_comm_time = start_timer!(|| "Committing to second round polys");
-        let (second_comms, second_comm_rands) = PC::commit(
+        let (second_comms, second_comm_rands) = C::PC::commit(
             &pk.committer_key,
             prover_second_oracles.iter(),
             Some(zk_rng),
@@ -230,7 +290,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 
         let third_round_comm_time = start_timer!(|| "Committing to third round polys");
         let (third_comms, third_comm_rands) =
-            PC::commit(&pk.committer_key, prover_third_oracles.iter(), Some(zk_rng))
+            C::PC::commit(&pk.committer_key, prover_third_oracles.iter(), Some(zk_rng))
                 .map_err(Error::from_pc_err)?;
         end_timer!(third_round_comm_time);
 
@@ -264,7 +324,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
             .collect();
 
         // Gather commitment randomness together.
-        let comm_rands: Vec<PC::Randomness> = first_comm_rands
+        let comm_rands: Vec<pc::Randomness<C>> = first_comm_rands
             .into_iter()
             .chain(second_comm_rands)
             .chain(third_comm_rands)
@@ -287,19 +347,19 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
                 .find(|lc| &lc.label == label)
                 .ok_or(ahp::Error::MissingEval(label.to_string()))?;
             let eval = polynomials.get_lc_eval(&lc, *point)?;
-            if !AHPForR1CS::<F>::LC_WITH_ZERO_EVAL.contains(&lc.label.as_ref()) {
+            if !AHPForR1CS::<C::F>::LC_WITH_ZERO_EVAL.contains(&lc.label.as_ref()) {
                 evaluations.push((label.to_string(), eval));
             }
         }
 
         evaluations.sort_by(|a, b| a.0.cmp(&b.0));
-        let evaluations = evaluations.into_iter().map(|x| x.1).collect::<Vec<F>>();
+        let evaluations = evaluations.into_iter().map(|x| x.1).collect::<Vec<C::F>>();
         end_timer!(eval_time);
 
         fs_rng.absorb(&evaluations);
-        let opening_challenge: F = u128::rand(&mut fs_rng).into();
+        let opening_challenge: C::F = u128::rand(&mut fs_rng).into();
 
-        let pc_proof = PC::open_combinations(
+        let pc_proof = C::PC::open_combinations(
             &pk.committer_key,
             &lc_s,
             polynomials.clone(),
@@ -312,25 +372,30 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         .map_err(Error::from_pc_err)?;
 
         let concrete_oracles = [
-            pk.index.a_arith.row.clone(),
-            pk.index.a_arith.col.clone(),
-            pk.index.a_arith.val.clone(),
-            pk.index.b_arith.row.clone(),
-            pk.index.b_arith.col.clone(),
-            pk.index.b_arith.val.clone(),
-            pk.index.c_arith.row.clone(),
-            pk.index.c_arith.col.clone(),
-            pk.index.c_arith.val.clone(),
-            prover_third_oracles.f.clone(), // f
+            &pk.index.a_arith.row,
+            &pk.index.a_arith.col,
+            &pk.index.a_arith.val,
+            &pk.index.b_arith.row,
+            &pk.index.b_arith.col,
+            &pk.index.b_arith.val,
+            &pk.index.c_arith.row,
+            &pk.index.c_arith.col,
+            &pk.index.c_arith.val,
+            &prover_third_oracles.f, // f
         ];
 
         let rational_sumcheck_vo = GenericShiftingVO::new(
             &vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
-            &vec![F::one(); 10],
-            rational_sumcheck_oracle!(verifier_first_msg, verifier_second_msg, domain_k),
+            &vec![C::F::one(); 10],
+            rational_sumcheck_oracle!(
+                verifier_first_msg,
+                verifier_second_msg,
+                domain_h,
+                pk.index.val_encoding
+            ),
         )?;
 
-        let labels = AHPForR1CS::<F>::matrix_poly_labels();
+        let labels = AHPForR1CS::<C::F>::matrix_poly_labels();
         let mut rational_sumcheck_commitments = pk
             .vk
             .commits
@@ -351,9 +416,9 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
             .get_rands()
             .into_iter()
             .chain(iter::once(f_rand))
-            .collect::<Vec<PC::Randomness>>();
+            .collect::<Vec<pc::Randomness<C>>>();
 
-        let rational_sumcheck_proof = ZeroOverK::<F, PC, FS>::prove(
+        let rational_sumcheck_proof = ZeroOverK::<C>::prove(
             &concrete_oracles,
             &rational_sumcheck_commitments,
             matrix_poly_rands.as_slice(),
@@ -361,6 +426,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
             &rational_sumcheck_vo,
             &domain_k,
             &pk.committer_key,
+            &context.child(b"rational_sumcheck"),
             zk_rng,
         )?;
 
@@ -370,7 +436,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         */
 
         fs_rng.absorb(&opening_challenge);
-        let separation_challenge: F = u128::rand(&mut fs_rng).into();
+        let separation_challenge: C::F = u128::rand(&mut fs_rng).into();
         let well_formation_oracles = AHPForR1CS::verifier_well_formation_oracles(
             &pk.index.index_info,
             &prover_state.public_input(),
@@ -380,12 +446,12 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 
         // TODO: this polys should not be committed to
         let (well_formation_commits, well_formation_rands) =
-            PC::commit(&pk.committer_key, well_formation_oracles.iter(), None)
+            C::PC::commit(&pk.committer_key, well_formation_oracles.iter(), None)
                 .map_err(Error::from_pc_err)?;
 
         let well_formation_vo = GenericShiftingVO::new(
             &vec![0, 1, 2, 0, 3, 4],
-            &vec![F::one(); 6],
+            &vec![C::F::one(); 6],
             well_formation_vo!(separation_challenge),
         )?;
 
@@ -395,8 +461,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 
         let well_formation_concrete_oracles = iter::once(&z_poly)
             .chain(well_formation_oracles.iter())
-            .map(|oracle| oracle.clone())
-            .collect::<Vec<LabeledPolynomial<_>>>();
+            .collect::<Vec<_>>();
         let well_formation_commits = iter::once(&z_commit)
             .chain(well_formation_commits.iter())
             .map(|commit| commit.clone())
@@ -404,9 +469,9 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         let well_formation_rands = iter::once(&z_rand)
             .chain(well_formation_rands.iter())
             .map(|rand| rand.clone())
-            .collect::<Vec<PC::Randomness>>();
+            .collect::<Vec<pc::Randomness<C>>>();
 
-        let well_formation_proof = ZeroOverK::<F, PC, FS>::prove(
+        let well_formation_proof = ZeroOverK::<C>::prove(
             &well_formation_concrete_oracles,
             &well_formation_commits,
             &well_formation_rands,
@@ -414,6 +479,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
             &well_formation_vo,
             &domain_h,
             &pk.committer_key,
+            &context.child(b"well_formation"),
             zk_rng,
         )?;
 
@@ -436,17 +502,19 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
     /// Verify that a proof for the constrain system defined by `C` asserts that
     /// all constraints are satisfied where circuit is private.
     pub fn verify<R: RngCore>(
-        vk: &data_structures::VerifierKey<F, PC>,
-        public_input: &Vec<F>,
-        output: &Vec<F>,
-        proof: Proof<F, PC>,
+        vk: &data_structures::VerifierKey<C>,
+        public_input: &Vec<C::F>,
+        output: &Vec<C::F>,
+        proof: Proof<C>,
+        context: &Context,
         rng: &mut R,
-        ck: &PC::CommitterKey, //TODO: make sure to remove this once we introduce instance oracles in virtual oracle
-    ) -> Result<bool, Error<PC::Error>> {
+        ck: &pc::CommitterKey<C>, //TODO: make sure to remove this once we introduce instance oracles in virtual oracle
+    ) -> Result<bool, Error<pc::Error<C>>> {
         let verifier_time = start_timer!(|| "Marlin::Verify");
 
-        let mut fs_rng =
-            FS::initialize(&to_bytes![&Self::PROTOCOL_NAME, &vk, &public_input].unwrap());
+        let mut fs_rng = C::FS::initialize(
+            &to_bytes![&Self::PROTOCOL_NAME, context, &vk, &public_input].unwrap(),
+        );
 
         // --------------------------------------------------------------------
         // First round
@@ -479,11 +547,11 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         // degree bounds because we know the committed index polynomial has the
         // correct degree.
         let index_info = vk.index_info.clone();
-        let degree_bounds = AHPForR1CS::<F>::prover_first_round_degree_bounds(&index_info)
-            .chain(AHPForR1CS::<F>::prover_second_round_degree_bounds(
+        let degree_bounds = AHPForR1CS::<C::F>::prover_first_round_degree_bounds(&index_info)
+            .chain(AHPForR1CS::<C::F>::prover_second_round_degree_bounds(
                 &index_info,
             ))
-            .chain(AHPForR1CS::<F>::prover_third_round_degree_bounds(
+            .chain(AHPForR1CS::<C::F>::prover_third_round_degree_bounds(
                 &index_info,
             ))
             .collect::<Vec<_>>();
@@ -494,7 +562,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
             .chain(second_comms)
             .chain(third_comms)
             .cloned()
-            .zip(AHPForR1CS::<F>::polynomial_labels())
+            .zip(AHPForR1CS::<C::F>::polynomial_labels())
             .zip(degree_bounds)
             .map(|((c, l), d)| LabeledCommitment::new(l, c, d))
             .collect();
@@ -503,13 +571,13 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
             AHPForR1CS::verifier_query_set(verifier_state, &mut fs_rng);
 
         fs_rng.absorb(&proof.evaluations);
-        let opening_challenge: F = u128::rand(&mut fs_rng).into();
+        let opening_challenge: C::F = u128::rand(&mut fs_rng).into();
 
         let mut evaluations = Evaluations::new();
         let mut evaluation_labels = Vec::new();
         for (poly_label, (_, point)) in query_set.iter().cloned() {
-            if AHPForR1CS::<F>::LC_WITH_ZERO_EVAL.contains(&poly_label.as_ref()) {
-                evaluations.insert((poly_label, point), F::zero());
+            if AHPForR1CS::<C::F>::LC_WITH_ZERO_EVAL.contains(&poly_label.as_ref()) {
+                evaluations.insert((poly_label, point), C::F::zero());
             } else {
                 evaluation_labels.push((poly_label, point));
             }
@@ -521,7 +589,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 
         let lc_s = AHPForR1CS::construct_linear_combinations(&evaluations, &verifier_state)?;
 
-        let evaluations_are_correct = PC::check_combinations(
+        let evaluations_are_correct = C::PC::check_combinations(
             &vk.verifier_key,
             &lc_s,
             &commitments,
@@ -538,11 +606,16 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 
         let rational_sumcheck_vo = GenericShiftingVO::new(
             &vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
-            &vec![F::one(); 10],
-            rational_sumcheck_oracle!(verifier_first_msg, verifier_second_msg, domain_k),
+            &vec![C::F::one(); 10],
+            rational_sumcheck_oracle!(
+                verifier_first_msg,
+                verifier_second_msg,
+                domain_h,
+                vk.val_encoding
+            ),
         )?;
 
-        let labels = AHPForR1CS::<F>::matrix_poly_labels();
+        let labels = AHPForR1CS::<C::F>::matrix_poly_labels();
         let mut rational_sumcheck_commitments = vk
             .commits
             .iter()
@@ -558,21 +631,22 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
             None,
         ));
 
-        ZeroOverK::<F, PC, FS>::verify(
+        ZeroOverK::<C>::verify(
             proof.rational_sumcheck_zero_over_k_proof,
             &rational_sumcheck_commitments,
             None,
             &rational_sumcheck_vo,
             &domain_k,
             &vk.verifier_key,
+            &context.child(b"rational_sumcheck"),
         )?;
 
         fs_rng.absorb(&opening_challenge);
-        let separation_challenge: F = u128::rand(&mut fs_rng).into();
+        let separation_challenge: C::F = u128::rand(&mut fs_rng).into();
 
         let well_formation_vo = GenericShiftingVO::new(
             &vec![0, 1, 2, 0, 3, 4],
-            &vec![F::one(); 6],
+            &vec![C::F::one(); 6],
             well_formation_vo!(separation_challenge),
         )?;
 
@@ -584,9 +658,9 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
         );
 
         let (verifier_well_formation_commits, _) =
-            PC::commit(ck, well_formation_oracles.iter(), None).map_err(Error::from_pc_err)?;
+            C::PC::commit(ck, well_formation_oracles.iter(), None).map_err(Error::from_pc_err)?;
 
-        let labels = AHPForR1CS::<F>::well_formation_labels();
+        let labels = AHPForR1CS::<C::F>::well_formation_labels();
         let mut verifier_well_formation_commits: Vec<LabeledCommitment<_>> =
             verifier_well_formation_commits
                 .iter()
@@ -602,13 +676,14 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 
         // let well_formation_commits = iter::once(&z_commit).chain(well_formation_commits.iter()).map(|commit| commit.clone()).collect::<Vec<LabeledCommitment<_>>>();
 
-        ZeroOverK::<F, PC, FS>::verify(
+        ZeroOverK::<C>::verify(
             proof.well_formation_proof,
             &well_formation_commits,
             None,
             &well_formation_vo,
             &domain_h,
             &vk.verifier_key,
+            &context.child(b"well_formation"),
         )?;
 
         end_timer!(verifier_time, || format!(
@@ -633,16 +708,17 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> Marlin<F
 /// terms[8]: c_col(X)
 /// terms[9]: c_val(X)
 /// terms[10]: f(X)
+/// The val terms are normalized according to the given [`ValEncoding`](crate::ahp::constraint_systems::ValEncoding).
 #[macro_export]
 macro_rules! rational_sumcheck_oracle {
-    ($verifier_first_msg:expr, $verifier_second_msg:expr, $domain_k:expr) => {
-        |terms: &[VOTerm<F>]| {
+    ($verifier_first_msg:expr, $verifier_second_msg:expr, $domain_h:expr, $val_encoding:expr) => {
+        |terms: &[VOTerm<_>]| {
             // define consts
             let alpha = vo_constant!($verifier_first_msg.alpha);
             let beta = vo_constant!($verifier_second_msg.beta);
 
-            let vh_alpha = $domain_k.evaluate_vanishing_polynomial($verifier_first_msg.alpha);
-            let vh_beta = $domain_k.evaluate_vanishing_polynomial($verifier_second_msg.beta);
+            let vh_alpha = $domain_h.evaluate_vanishing_polynomial($verifier_first_msg.alpha);
+            let vh_beta = $domain_h.evaluate_vanishing_polynomial($verifier_second_msg.beta);
             let v_h_alpha_v_h_beta = vh_alpha * vh_beta;
 
             let eta_a_times_v_h_alpha_v_h_beta =
@@ -655,15 +731,22 @@ macro_rules! rational_sumcheck_oracle {
             let alpha_beta = alpha.clone() * beta.clone();
 
             // define terms
+            let normalized_val = |val: VOTerm<_>, row: VOTerm<_>| match $val_encoding {
+                $crate::ahp::constraint_systems::ValEncoding::Normalized => val,
+                $crate::ahp::constraint_systems::ValEncoding::Raw => {
+                    vo_constant!($domain_h.size_as_field_element().inverse().unwrap()) * val * row
+                }
+            };
+
             let a_row = terms[1].clone();
             let a_col = terms[2].clone();
-            let a_val = terms[3].clone();
+            let a_val = normalized_val(terms[3].clone(), a_row.clone());
             let b_row = terms[4].clone();
             let b_col = terms[5].clone();
-            let b_val = terms[6].clone();
+            let b_val = normalized_val(terms[6].clone(), b_row.clone());
             let c_row = terms[7].clone();
             let c_col = terms[8].clone();
-            let c_val = terms[9].clone();
+            let c_val = normalized_val(terms[9].clone(), c_row.clone());
             let f = terms[10].clone();
 
             // begin logic
@@ -705,7 +788,7 @@ macro_rules! rational_sumcheck_oracle {
 #[macro_export]
 macro_rules! well_formation_vo {
     ($separation_challenge:expr) => {
-        |terms: &[VOTerm<F>]| {
+        |terms: &[VOTerm<_>]| {
             (terms[1].clone() - terms[2].clone()) * terms[3].clone()
                 + vo_constant!($separation_challenge)
                     * (terms[4].clone() - terms[5].clone())
@@ -0,0 +1,323 @@
+use crate::{
+    band_matrix_test::proof::Proof,
+    discrete_log_comparison::{piop::PIOPforDLComparison, DLComparison},
+    domains::{DomainH, DomainK},
+    error::{Error, WitnessError},
+    report::VerificationReport,
+    util::scale_commitment,
+};
+use ark_ff::{to_bytes, SquareRootField};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, Context};
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::trace_span;
+#[cfg(feature = "prover")]
+use {
+    crate::{checked_key::CheckedCommitterKey, util::scale_poly},
+    rand::Rng,
+};
+
+pub mod proof;
+mod tests;
+
+/// The public data of a band matrix test: the committed row and col polynomials of an encoding over K, and the
+/// bandwidth b that every entry must lie within
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    pub bandwidth: usize,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<C::F>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_h: DomainH<C::F>,
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub row_commit: LabeledCommitment<pc::Commitment<C>>,
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub col_commit: LabeledCommitment<pc::Commitment<C>>,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+impl<C: ProtocolConfig> Statement<C> {
+    /// omega^(b + 1), which moves an index of H up by b + 1
+    fn shift(&self) -> C::F {
+        self.domain_h.element(self.bandwidth + 1)
+    }
+
+    fn check_bandwidth(&self) -> Result<(), Error> {
+        if self.bandwidth >= self.domain_h.size() - 1 {
+            return Err(Error::BandwidthTooLarge(format!(
+                "A bandwidth of {} leaves no room in domain H of size {}",
+                self.bandwidth,
+                self.domain_h.size()
+            )));
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_bytes![
+            self.bandwidth as u64,
+            self.domain_k.size() as u64,
+            self.domain_h.size() as u64,
+            self.row_commit,
+            self.col_commit
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+}
+
+/// Proves that every entry of the encoded matrix lies within a band of width b around the diagonal: |r - c| <= b for
+/// row(κ) = ω^r and col(κ) = ω^c. This generalises the t-SLT test with two discrete-log comparisons: ω^(b + 1) * col >
+/// row gives r <= c + b, and ω^(b + 1) * row > col gives c <= r + b.
+///
+/// Shifting an index close to the end of H wraps around, which can only make the comparisons fail, so the test is
+/// sound for any encoding. It is complete for encodings whose rows and columns all stay below |H| - b - 1, i.e.
+/// domain H must be chosen with |H| > n + b for an n x n matrix.
+pub struct BandMatrixTest<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> BandMatrixTest<C>
+where
+    C::F: SquareRootField,
+{
+    pub const PROTOCOL_NAME: &'static [u8] = b"Band Matrix Test";
+
+    /// Check that every entry lies within the band and far enough from the end of H to be shifted, failing on the
+    /// first offending element of K before any work is done. With the `validate-witness` feature the prover runs this
+    /// check itself.
+    pub fn validate_witness(
+        statement: &Statement<C>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    ) -> Result<(), Error> {
+        statement.check_bandwidth()?;
+
+        let bandwidth = statement.bandwidth;
+        let domain_k = &statement.domain_k;
+        let domain_h = &statement.domain_h;
+        let row_exponents = PIOPforDLComparison::exponents(domain_k, domain_h, row_poly)?;
+        let col_exponents = PIOPforDLComparison::exponents(domain_k, domain_h, col_poly)?;
+
+        for (index, (&row_exponent, &col_exponent)) in
+            row_exponents.iter().zip(col_exponents.iter()).enumerate()
+        {
+            if row_exponent > col_exponent + bandwidth || col_exponent > row_exponent + bandwidth {
+                return Err(Error::InvalidWitness(WitnessError::OutsideBand {
+                    index,
+                    row_exponent,
+                    col_exponent,
+                    bandwidth,
+                }));
+            }
+
+            let exponent = row_exponent.max(col_exponent);
+            if exponent + bandwidth + 1 >= domain_h.size() {
+                return Err(Error::InvalidWitness(WitnessError::BandOverflow {
+                    index,
+                    exponent,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_random: &pc::Randomness<C>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_random: &pc::Randomness<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("band_matrix_test::prove");
+        statement.check_bandwidth()?;
+
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(statement, row_poly, col_poly)?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        // Step 1: shift row and col up by b + 1
+        let shift = statement.shift();
+        let bound = statement.enforced_degree_bound;
+        let shifted_row = scale_poly("shifted_row", row_poly, shift, bound);
+        let (shifted_row_commit, shifted_row_random) = scale_commitment::<C::F, C::PC>(
+            "shifted_row",
+            &statement.row_commit,
+            Some(row_random),
+            shift,
+            bound,
+        )?;
+        let shifted_col = scale_poly("shifted_col", col_poly, shift, bound);
+        let (shifted_col_commit, shifted_col_random) = scale_commitment::<C::F, C::PC>(
+            "shifted_col",
+            &statement.col_commit,
+            Some(col_random),
+            shift,
+            bound,
+        )?;
+
+        // Step 2: Discrete Log Comparison between ω^(b + 1) * col and row
+        let dl_ck = CheckedCommitterKey::<C, DLComparison<C>>::new(ck, bound)?;
+        let upper_proof = DLComparison::<C>::prove(
+            &dl_ck,
+            &statement.domain_k,
+            &statement.domain_h,
+            &shifted_col,
+            &shifted_col_commit,
+            &shifted_col_random,
+            row_poly,
+            &statement.row_commit,
+            row_random,
+            statement.enforced_degree_bound,
+            &context.child(b"upper"),
+            fs_rng,
+            rng,
+        )?;
+
+        // Step 3: Discrete Log Comparison between ω^(b + 1) * row and col
+        let lower_proof = DLComparison::<C>::prove(
+            &dl_ck,
+            &statement.domain_k,
+            &statement.domain_h,
+            &shifted_row,
+            &shifted_row_commit,
+            &shifted_row_random,
+            col_poly,
+            &statement.col_commit,
+            col_random,
+            statement.enforced_degree_bound,
+            &context.child(b"lower"),
+            fs_rng,
+            rng,
+        )?;
+
+        Ok(Proof {
+            upper_proof,
+            lower_proof,
+        })
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(vk, ck, statement, proof, context, fs_rng).into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("band_matrix_test::verify");
+        let mut report = VerificationReport::builder("band_matrix_test");
+
+        report.check("bandwidth", || statement.check_bandwidth());
+
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        let shift_commitment = |label, commit| {
+            scale_commitment::<C::F, C::PC>(
+                label,
+                commit,
+                None,
+                statement.shift(),
+                statement.enforced_degree_bound,
+            )
+            .map(|(shifted_commit, _)| shifted_commit)
+        };
+        let shifted_commits =
+            shift_commitment("shifted_row", &statement.row_commit).and_then(|shifted_row| {
+                shift_commitment("shifted_col", &statement.col_commit)
+                    .map(|shifted_col| (shifted_row, shifted_col))
+            });
+        let (shifted_row_commit, shifted_col_commit) = match shifted_commits {
+            Ok(shifted_commits) => shifted_commits,
+            Err(e) => {
+                report.check("shifted_commitments", || Err(e));
+                return report.finish();
+            }
+        };
+
+        report.add(DLComparison::<C>::verify_with_report(
+            vk,
+            ck,
+            &statement.domain_k,
+            &statement.domain_h,
+            &shifted_col_commit,
+            &statement.row_commit,
+            statement.enforced_degree_bound,
+            proof.upper_proof,
+            &context.child(b"upper"),
+            fs_rng,
+        ));
+
+        report.add(DLComparison::<C>::verify_with_report(
+            vk,
+            ck,
+            &statement.domain_k,
+            &statement.domain_h,
+            &shifted_row_commit,
+            &statement.col_commit,
+            statement.enforced_degree_bound,
+            proof.lower_proof,
+            &context.child(b"lower"),
+            fs_rng,
+        ));
+
+        report.finish()
+    }
+}
@@ -0,0 +1,28 @@
+use crate::discrete_log_comparison::proof::Proof as DLProof;
+use ark_ff::SquareRootField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::io::{Read, Write};
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
+
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    /// row < omega^(b + 1) * col in the discrete-log sense
+    pub upper_proof: DLProof<C>,
+    /// col < omega^(b + 1) * row in the discrete-log sense
+    pub lower_proof: DLProof<C>,
+}
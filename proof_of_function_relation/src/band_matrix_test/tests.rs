@@ -0,0 +1,183 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        band_matrix_test::{BandMatrixTest, Statement},
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{domains, encode},
+            PaddingStrategy,
+        },
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // M values, with a bandwidth of 1
+    /*
+        1, 2, 0, 0
+        3, 4, 5, 0
+        0, 6, 7, 8
+        0, 0, 9, 1
+    */
+    fn tridiagonal_entries() -> Vec<(usize, usize, F)> {
+        [
+            (0, 0),
+            (0, 1),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (2, 1),
+            (2, 2),
+            (2, 3),
+            (3, 2),
+            (3, 3),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &(row, col))| (row, col, F::from(i as u64 + 1)))
+        .collect()
+    }
+
+    fn run_band_matrix_test(entries: &[(usize, usize, F)], bandwidth: usize) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(16, 8);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let encoding = encode(entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast);
+
+        let max_degree = 40;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) =
+            PC::commit(&ck, [&encoding.row, &encoding.col], Some(rng)).unwrap();
+
+        let statement = Statement::<C> {
+            bandwidth,
+            domain_k,
+            domain_h,
+            row_commit: commitments[0].clone(),
+            col_commit: commitments[1].clone(),
+            enforced_degree_bound: Some(enforced_degree_bound),
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = BandMatrixTest::<C>::prove(
+            &ck,
+            &statement,
+            &encoding.row,
+            &rands[0],
+            &encoding.col,
+            &rands[1],
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        BandMatrixTest::<C>::verify(
+            &vk,
+            &ck,
+            &statement,
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )
+    }
+
+    #[test]
+    fn test_band_matrix() {
+        assert_eq!(run_band_matrix_test(&tridiagonal_entries(), 1), Ok(()));
+        assert_eq!(run_band_matrix_test(&tridiagonal_entries(), 2), Ok(()));
+    }
+
+    #[test]
+    fn test_diagonal_matrix() {
+        let entries = (0..4)
+            .map(|i| (i, i, F::from(i as u64 + 1)))
+            .collect::<Vec<_>>();
+        assert_eq!(run_band_matrix_test(&entries, 0), Ok(()));
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_outside_of_band() {
+        // above the band
+        let mut entries = tridiagonal_entries();
+        entries[1].1 = 2;
+        assert!(run_band_matrix_test(&entries, 1).is_err());
+
+        // below the band
+        let mut entries = tridiagonal_entries();
+        entries[8].1 = 0;
+        assert!(run_band_matrix_test(&entries, 1).is_err());
+    }
+
+    #[test]
+    fn test_bandwidth_too_large() {
+        assert!(matches!(
+            run_band_matrix_test(&tridiagonal_entries(), 7),
+            Err(Error::BandwidthTooLarge(_))
+        ));
+        assert!(matches!(
+            run_band_matrix_test(&tridiagonal_entries(), usize::MAX),
+            Err(Error::BandwidthTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(16, 8);
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[domain_k.size() + 1])).unwrap();
+
+        let validate = |entries: &[(usize, usize, F)], bandwidth| {
+            let encoding = encode(entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast);
+            let (commitments, _) =
+                PC::commit(&ck, [&encoding.row, &encoding.col], Some(&mut thread_rng())).unwrap();
+            let statement = Statement::<C> {
+                bandwidth,
+                domain_k,
+                domain_h,
+                row_commit: commitments[0].clone(),
+                col_commit: commitments[1].clone(),
+                enforced_degree_bound: None,
+            };
+            BandMatrixTest::<C>::validate_witness(&statement, &encoding.row, &encoding.col)
+        };
+
+        assert_eq!(validate(&tridiagonal_entries(), 1), Ok(()));
+        assert_eq!(
+            validate(&tridiagonal_entries(), 0),
+            Err(Error::InvalidWitness(WitnessError::OutsideBand {
+                index: 1,
+                row_exponent: 0,
+                col_exponent: 1,
+                bandwidth: 0
+            }))
+        );
+
+        // shifting column 3 by 4 + 1 reaches the end of H
+        assert_eq!(
+            validate(&tridiagonal_entries(), 4),
+            Err(Error::InvalidWitness(WitnessError::BandOverflow {
+                index: 7,
+                exponent: 3
+            }))
+        );
+    }
+}
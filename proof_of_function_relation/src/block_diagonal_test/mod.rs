@@ -0,0 +1,522 @@
+use crate::{
+    block_diagonal_test::proof::Proof,
+    discrete_log_comparison::{piop::PIOPforDLComparison, DLComparison},
+    domains::{DomainH, DomainK},
+    error::{Error, WitnessError},
+    report::VerificationReport,
+    util::scale_commitment,
+};
+use ark_ff::{to_bytes, One, SquareRootField, Zero};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use blake2::{Blake2s, Digest};
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {
+    crate::{checked_key::CheckedCommitterKey, error::to_pc_error, util::scale_poly},
+    ark_poly::UVPolynomial,
+    rand::Rng,
+};
+
+pub mod proof;
+mod tests;
+
+/// The public data of a block-diagonal test: the committed row and col polynomials of an encoding over K, and the
+/// sizes of the diagonal blocks, in order from the top-left corner
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    pub blocks: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<C::F>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_h: DomainH<C::F>,
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub row_commit: LabeledCommitment<pc::Commitment<C>>,
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub col_commit: LabeledCommitment<pc::Commitment<C>>,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+impl<C: ProtocolConfig> Statement<C> {
+    /// The [start, end) range of indices of H covered by each block
+    fn boundaries(&self) -> Vec<(usize, usize)> {
+        self.blocks
+            .iter()
+            .scan(0, |start, &size| {
+                let boundary = (*start, *start + size);
+                *start += size;
+                Some(boundary)
+            })
+            .collect()
+    }
+
+    /// The index of the block that covers an index of H
+    fn block_of(&self, exponent: usize) -> Option<usize> {
+        self.boundaries()
+            .iter()
+            .position(|&(start, end)| start <= exponent && exponent < end)
+    }
+
+    /// The blocks as pairs (ω^start, ω^end) of elements of H
+    fn block_elements(&self) -> Vec<(C::F, C::F)> {
+        self.boundaries()
+            .iter()
+            .map(|&(start, end)| (self.domain_h.element(start), self.domain_h.element(end)))
+            .collect()
+    }
+
+    fn check_blocks(&self) -> Result<(), Error> {
+        if self.blocks.is_empty() || self.blocks.contains(&0) {
+            return Err(Error::InvalidBlocks(String::from(
+                "Every declared block must cover at least one index",
+            )));
+        }
+        let size = self.blocks.iter().sum::<usize>();
+        if size >= self.domain_h.size() {
+            return Err(Error::InvalidBlocks(format!(
+                "Blocks covering {} indices leave no room in domain H of size {}",
+                size,
+                self.domain_h.size()
+            )));
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|&size| size as u64)
+            .collect::<Vec<_>>();
+        to_bytes![
+            blocks.len() as u64,
+            blocks,
+            self.domain_k.size() as u64,
+            self.domain_h.size() as u64,
+            self.row_commit,
+            self.col_commit
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+}
+
+/// Proves that the matrix encoded over K is block diagonal with the declared blocks: the row and the column of every
+/// entry lie in the same block. The prover commits to block_start and block_end, which map each element of K to the
+/// first index of H in the block of its entry and to the first index past it. Then:
+///
+/// 1. a subset check shows that (block_start, block_end) is one of the declared blocks over K, with a ZeroOverK of
+///    prod_j ((block_start - ω^start_j) + gamma * (block_end - ω^end_j)) for a verifier challenge gamma;
+/// 2. four discrete-log comparisons show block_start <= row < block_end and block_start <= col < block_end, where
+///    block_start <= row is proven as block_start < ω * row.
+///
+/// The blocks must cover fewer indices than |H|, so that ω * row and block_end never wrap around H. The degree of the
+/// subset check grows with the number of blocks, which makes the test best suited to a few independent components.
+pub struct BlockDiagonalTest<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> BlockDiagonalTest<C>
+where
+    C::F: SquareRootField,
+{
+    pub const PROTOCOL_NAME: &'static [u8] = b"Block Diagonal Test";
+
+    /// Check that the row and column of every entry lie in the same declared block, failing on the first offending
+    /// element of K before any work is done. With the `validate-witness` feature the prover runs this check itself.
+    pub fn validate_witness(
+        statement: &Statement<C>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    ) -> Result<(), Error> {
+        statement.check_blocks()?;
+
+        let row_exponents =
+            PIOPforDLComparison::exponents(&statement.domain_k, &statement.domain_h, row_poly)?;
+        let col_exponents =
+            PIOPforDLComparison::exponents(&statement.domain_k, &statement.domain_h, col_poly)?;
+
+        for (index, (&row_exponent, &col_exponent)) in
+            row_exponents.iter().zip(col_exponents.iter()).enumerate()
+        {
+            let row_block = statement.block_of(row_exponent);
+            if row_block.is_none() || row_block != statement.block_of(col_exponent) {
+                return Err(Error::InvalidWitness(WitnessError::OffBlockDiagonal {
+                    index,
+                    row_exponent,
+                    col_exponent,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_random: &pc::Randomness<C>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_random: &pc::Randomness<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("block_diagonal_test::prove");
+        statement.check_blocks()?;
+
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(statement, row_poly, col_poly)?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let domain_k = &statement.domain_k;
+        let domain_h = &statement.domain_h;
+        let bound = statement.enforced_degree_bound;
+
+        // Step 1: commit to the boundaries of the block of every entry. An entry whose row lies outside of every
+        // block is assigned the first one, for which the comparisons below fail.
+        let block_elements = statement.block_elements();
+        let (start_evals, end_evals): (Vec<C::F>, Vec<C::F>) =
+            PIOPforDLComparison::exponents(domain_k, domain_h, row_poly)?
+                .into_iter()
+                .map(|exponent| block_elements[statement.block_of(exponent).unwrap_or(0)])
+                .unzip();
+
+        let block_start = LabeledPolynomial::new(
+            String::from("block_start"),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&start_evals)),
+            bound,
+            Some(1),
+        );
+        let block_end = LabeledPolynomial::new(
+            String::from("block_end"),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&end_evals)),
+            bound,
+            Some(1),
+        );
+        let (commitments, rands) = C::PC::commit(ck, [&block_start, &block_end], Some(rng))
+            .map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![commitments].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+        let gamma: C::F = ChallengeSet::new(&mut *fs_rng, b"block_membership").challenge(b"gamma");
+
+        // Step 2: Zero over K for the membership of (block_start, block_end) in the declared blocks
+        let membership_vo = GenericShiftingVO::new(
+            &[0, 1],
+            &[C::F::one(), C::F::one()],
+            Self::membership_check(block_elements, gamma),
+        )?
+        .with_scaling_factor(statement.blocks.len());
+        let membership_proof = ZeroOverK::<C>::prove(
+            &[&block_start, &block_end],
+            &commitments,
+            &rands,
+            bound,
+            &membership_vo,
+            domain_k,
+            ck,
+            &Self::membership_context(context, gamma)?,
+            rng,
+        )?;
+
+        // Step 3: shift row and col up by one, turning block_start <= row into a strict comparison
+        let omega = domain_h.element(1);
+        let next_row = scale_poly("next_row", row_poly, omega, bound);
+        let (next_row_commit, next_row_random) = scale_commitment::<C::F, C::PC>(
+            "next_row",
+            &statement.row_commit,
+            Some(row_random),
+            omega,
+            bound,
+        )?;
+        let next_col = scale_poly("next_col", col_poly, omega, bound);
+        let (next_col_commit, next_col_random) = scale_commitment::<C::F, C::PC>(
+            "next_col",
+            &statement.col_commit,
+            Some(col_random),
+            omega,
+            bound,
+        )?;
+
+        // Step 4: Discrete Log Comparisons of row and col against the boundaries of their block
+        let dl_ck = CheckedCommitterKey::<C, DLComparison<C>>::new(ck, bound)?;
+        let row_start_proof = DLComparison::<C>::prove(
+            &dl_ck,
+            domain_k,
+            domain_h,
+            &next_row,
+            &next_row_commit,
+            &next_row_random,
+            &block_start,
+            &commitments[0],
+            &rands[0],
+            bound,
+            &context.child(b"row_start"),
+            fs_rng,
+            rng,
+        )?;
+        let row_end_proof = DLComparison::<C>::prove(
+            &dl_ck,
+            domain_k,
+            domain_h,
+            &block_end,
+            &commitments[1],
+            &rands[1],
+            row_poly,
+            &statement.row_commit,
+            row_random,
+            bound,
+            &context.child(b"row_end"),
+            fs_rng,
+            rng,
+        )?;
+        let col_start_proof = DLComparison::<C>::prove(
+            &dl_ck,
+            domain_k,
+            domain_h,
+            &next_col,
+            &next_col_commit,
+            &next_col_random,
+            &block_start,
+            &commitments[0],
+            &rands[0],
+            bound,
+            &context.child(b"col_start"),
+            fs_rng,
+            rng,
+        )?;
+        let col_end_proof = DLComparison::<C>::prove(
+            &dl_ck,
+            domain_k,
+            domain_h,
+            &block_end,
+            &commitments[1],
+            &rands[1],
+            col_poly,
+            &statement.col_commit,
+            col_random,
+            bound,
+            &context.child(b"col_end"),
+            fs_rng,
+            rng,
+        )?;
+
+        Ok(Proof {
+            block_start_commit: commitments[0].commitment().clone(),
+            block_end_commit: commitments[1].commitment().clone(),
+            membership_proof,
+            row_start_proof,
+            row_end_proof,
+            col_start_proof,
+            col_end_proof,
+        })
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(vk, ck, statement, proof, context, fs_rng).into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("block_diagonal_test::verify");
+        let mut report = VerificationReport::builder("block_diagonal_test");
+        let domain_k = &statement.domain_k;
+        let domain_h = &statement.domain_h;
+        let bound = statement.enforced_degree_bound;
+
+        report.check("blocks", || statement.check_blocks());
+
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        let commitments = [
+            LabeledCommitment::new(String::from("block_start"), proof.block_start_commit, bound),
+            LabeledCommitment::new(String::from("block_end"), proof.block_end_commit, bound),
+        ];
+
+        let mut gamma = C::F::zero();
+        report.check("block_commits", || {
+            let fs_bytes = &to_bytes![commitments.to_vec()].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            gamma = ChallengeSet::new(&mut *fs_rng, b"block_membership").challenge(b"gamma");
+            Ok::<(), Error>(())
+        });
+
+        report.check("membership", || {
+            let membership_vo = GenericShiftingVO::new(
+                &[0, 1],
+                &[C::F::one(), C::F::one()],
+                Self::membership_check(statement.block_elements(), gamma),
+            )?
+            .with_scaling_factor(statement.blocks.len());
+
+            ZeroOverK::<C>::verify(
+                proof.membership_proof,
+                &commitments,
+                bound,
+                &membership_vo,
+                domain_k,
+                vk,
+                &Self::membership_context(context, gamma)?,
+            )
+            .map_err(Error::from)
+        });
+
+        let shift_commitment = |label, commit| {
+            scale_commitment::<C::F, C::PC>(label, commit, None, domain_h.element(1), bound)
+                .map(|(shifted_commit, _)| shifted_commit)
+        };
+        let shifted_commits =
+            shift_commitment("next_row", &statement.row_commit).and_then(|next_row| {
+                shift_commitment("next_col", &statement.col_commit)
+                    .map(|next_col| (next_row, next_col))
+            });
+        let (next_row_commit, next_col_commit) = match shifted_commits {
+            Ok(shifted_commits) => shifted_commits,
+            Err(e) => {
+                report.check("shifted_commitments", || Err(e));
+                return report.finish();
+            }
+        };
+
+        report.add(DLComparison::<C>::verify_with_report(
+            vk,
+            ck,
+            domain_k,
+            domain_h,
+            &next_row_commit,
+            &commitments[0],
+            bound,
+            proof.row_start_proof,
+            &context.child(b"row_start"),
+            fs_rng,
+        ));
+
+        report.add(DLComparison::<C>::verify_with_report(
+            vk,
+            ck,
+            domain_k,
+            domain_h,
+            &commitments[1],
+            &statement.row_commit,
+            bound,
+            proof.row_end_proof,
+            &context.child(b"row_end"),
+            fs_rng,
+        ));
+
+        report.add(DLComparison::<C>::verify_with_report(
+            vk,
+            ck,
+            domain_k,
+            domain_h,
+            &next_col_commit,
+            &commitments[0],
+            bound,
+            proof.col_start_proof,
+            &context.child(b"col_start"),
+            fs_rng,
+        ));
+
+        report.add(DLComparison::<C>::verify_with_report(
+            vk,
+            ck,
+            domain_k,
+            domain_h,
+            &commitments[1],
+            &statement.col_commit,
+            bound,
+            proof.col_end_proof,
+            &context.child(b"col_end"),
+            fs_rng,
+        ));
+
+        report.finish()
+    }
+
+    /// prod_j ((block_start - ω^start_j) + gamma * (block_end - ω^end_j)), over the terms
+    /// [X, block_start(X), block_end(X)]
+    fn membership_check(
+        block_elements: Vec<(C::F, C::F)>,
+        gamma: C::F,
+    ) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        move |terms: &[VOTerm<C::F>]| {
+            block_elements
+                .iter()
+                .fold(vo_constant!(C::F::one()), |acc, &(start, end)| {
+                    acc * ((terms[1].clone() - vo_constant!(start))
+                        + vo_constant!(gamma) * (terms[2].clone() - vo_constant!(end)))
+                })
+        }
+    }
+
+    fn membership_context(context: &Context, gamma: C::F) -> Result<Context, Error> {
+        let challenge = to_bytes![gamma].map_err(|_| Error::ToBytesError)?;
+        Ok(context
+            .child(b"membership")
+            .child(&Blake2s::digest(&challenge)))
+    }
+}
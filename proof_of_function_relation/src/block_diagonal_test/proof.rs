@@ -0,0 +1,39 @@
+use crate::discrete_log_comparison::proof::Proof as DLProof;
+use ark_ff::SquareRootField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::io::{Read, Write};
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::zero_over_k::proof::Proof as ZeroProof;
+
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub block_start_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub block_end_commit: pc::Commitment<C>,
+    /// (block_start, block_end) is one of the declared blocks over K
+    pub membership_proof: ZeroProof<C>,
+    /// block_start < omega * row in the discrete-log sense
+    pub row_start_proof: DLProof<C>,
+    /// row < block_end in the discrete-log sense
+    pub row_end_proof: DLProof<C>,
+    /// block_start < omega * col in the discrete-log sense
+    pub col_start_proof: DLProof<C>,
+    /// col < block_end in the discrete-log sense
+    pub col_end_proof: DLProof<C>,
+}
@@ -0,0 +1,180 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        block_diagonal_test::{BlockDiagonalTest, Statement},
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{domains, encode},
+            PaddingStrategy,
+        },
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // M values, with blocks of sizes 2, 3 and 1
+    /*
+        1, 2, 0, 0, 0, 0
+        0, 3, 0, 0, 0, 0
+        0, 0, 4, 0, 5, 0
+        0, 0, 0, 6, 0, 0
+        0, 0, 7, 0, 8, 0
+        0, 0, 0, 0, 0, 9
+    */
+    fn block_diagonal_entries() -> Vec<(usize, usize, F)> {
+        [
+            (0, 0),
+            (0, 1),
+            (1, 1),
+            (2, 2),
+            (2, 4),
+            (3, 3),
+            (4, 2),
+            (4, 4),
+            (5, 5),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, &(row, col))| (row, col, F::from(i as u64 + 1)))
+        .collect()
+    }
+
+    fn run_block_diagonal_test(
+        entries: &[(usize, usize, F)],
+        blocks: &[usize],
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(16, 8);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let encoding = encode(entries, &domain_k, &domain_h, PaddingStrategy::Origin);
+
+        let max_degree = 80;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) =
+            PC::commit(&ck, [&encoding.row, &encoding.col], Some(rng)).unwrap();
+
+        let statement = Statement::<C> {
+            blocks: blocks.to_vec(),
+            domain_k,
+            domain_h,
+            row_commit: commitments[0].clone(),
+            col_commit: commitments[1].clone(),
+            enforced_degree_bound: Some(enforced_degree_bound),
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = BlockDiagonalTest::<C>::prove(
+            &ck,
+            &statement,
+            &encoding.row,
+            &rands[0],
+            &encoding.col,
+            &rands[1],
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        BlockDiagonalTest::<C>::verify(
+            &vk,
+            &ck,
+            &statement,
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )
+    }
+
+    #[test]
+    fn test_block_diagonal_matrix() {
+        assert_eq!(
+            run_block_diagonal_test(&block_diagonal_entries(), &[2, 3, 1]),
+            Ok(())
+        );
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_entry_across_blocks() {
+        let mut entries = block_diagonal_entries();
+        entries[2].1 = 2;
+        assert!(run_block_diagonal_test(&entries, &[2, 3, 1]).is_err());
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_wrong_blocks() {
+        // the entries are block diagonal, but not with these blocks
+        assert!(run_block_diagonal_test(&block_diagonal_entries(), &[3, 3]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_blocks() {
+        for blocks in [vec![], vec![2, 0, 4], vec![2, 3, 3]] {
+            assert!(matches!(
+                run_block_diagonal_test(&block_diagonal_entries(), &blocks),
+                Err(Error::InvalidBlocks(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(16, 8);
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[domain_k.size() + 1])).unwrap();
+
+        let validate = |entries: &[(usize, usize, F)], blocks: &[usize]| {
+            let encoding = encode(entries, &domain_k, &domain_h, PaddingStrategy::Origin);
+            let (commitments, _) =
+                PC::commit(&ck, [&encoding.row, &encoding.col], Some(&mut thread_rng())).unwrap();
+            let statement = Statement::<C> {
+                blocks: blocks.to_vec(),
+                domain_k,
+                domain_h,
+                row_commit: commitments[0].clone(),
+                col_commit: commitments[1].clone(),
+                enforced_degree_bound: None,
+            };
+            BlockDiagonalTest::<C>::validate_witness(&statement, &encoding.row, &encoding.col)
+        };
+
+        assert_eq!(validate(&block_diagonal_entries(), &[2, 3, 1]), Ok(()));
+        assert_eq!(
+            validate(&block_diagonal_entries(), &[3, 3]),
+            Err(Error::InvalidWitness(WitnessError::OffBlockDiagonal {
+                index: 4,
+                row_exponent: 2,
+                col_exponent: 4
+            }))
+        );
+
+        // the last entry is outside of every block
+        assert_eq!(
+            validate(&block_diagonal_entries(), &[2, 3]),
+            Err(Error::InvalidWitness(WitnessError::OffBlockDiagonal {
+                index: 8,
+                row_exponent: 5,
+                col_exponent: 5
+            }))
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Committer keys checked against the degree bounds a protocol enforces. A key trimmed without one of them only fails
+//! deep inside the commitment scheme, when the prover commits to a polynomial under that bound. The provers of the
+//! protocols below take a [`CheckedCommitterKey`] instead, which can only be obtained by checking the key up front.
+//!
+//! ```ignore
+//! let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&DLComparison::<C>::required_degree_bounds(bound)))?;
+//! let ck = CheckedCommitterKey::<C, DLComparison<C>>::new(&ck, bound)?;
+//! let proof = DLComparison::<C>::prove(&ck, &domain_k, &domain_h, ..., bound, &context, fs_rng, rng)?;
+//! ```
+//!
+//! The key also carries the [`CancellationToken`] of the proof, if any, which the provers pass on to their
+//! sub-protocols so that they abort with [`Error::Aborted`] once it is cancelled.
+
+use crate::{
+    discrete_log_comparison::DLComparison, t_diag::TDiag, t_functional_triple::TFT,
+    t_strictly_lower_triangular_test::TStrictlyLowerTriangular,
+};
+use ark_ff::SquareRootField;
+use zero_over_k::config::ProtocolConfig;
+use zero_over_k::zero_over_k::MASKING_DEGREE_BOUND;
+#[cfg(feature = "prover")]
+use {
+    crate::error::Error,
+    ark_ff::Zero,
+    ark_poly::univariate::DensePolynomial,
+    ark_poly_commit::{LabeledPolynomial, PolynomialCommitment},
+    std::marker::PhantomData,
+    zero_over_k::cancellation::CancellationToken,
+    zero_over_k::config::pc,
+};
+
+/// A protocol whose prover commits to polynomials under enforced degree bounds
+pub trait RequiredDegreeBounds {
+    /// The degree bounds a committer key must be trimmed with to prove under `enforced_degree_bound`
+    fn required_degree_bounds(enforced_degree_bound: Option<usize>) -> Vec<usize>;
+}
+
+/// The bounds of the ZeroOverK sub-proofs: that of the masking polynomials and the one enforced on the oracles
+fn zero_over_k_bounds(enforced_degree_bound: Option<usize>) -> Vec<usize> {
+    let mut bounds = vec![MASKING_DEGREE_BOUND];
+    bounds.extend(enforced_degree_bound);
+    bounds
+}
+
+/// A committer key known to support the degree bounds required by the protocol `P` under an enforced degree bound
+#[cfg(feature = "prover")]
+pub struct CheckedCommitterKey<'a, C: ProtocolConfig, P> {
+    ck: &'a pc::CommitterKey<C>,
+    enforced_degree_bound: Option<usize>,
+    cancellation: Option<&'a CancellationToken>,
+    _protocol: PhantomData<P>,
+}
+
+#[cfg(feature = "prover")]
+impl<'a, C: ProtocolConfig, P> CheckedCommitterKey<'a, C, P>
+where
+    P: RequiredDegreeBounds,
+{
+    /// Check that `ck` supports every degree bound `P` requires under `enforced_degree_bound`, by committing to the
+    /// zero polynomial under each of them
+    pub fn new(
+        ck: &'a pc::CommitterKey<C>,
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<Self, Error> {
+        for bound in P::required_degree_bounds(enforced_degree_bound) {
+            let probe = LabeledPolynomial::new(
+                String::from("degree_bound_probe"),
+                DensePolynomial::zero(),
+                Some(bound),
+                None,
+            );
+            C::PC::commit(ck, [&probe], None).map_err(|error| {
+                Error::UnsupportedDegreeBound(format!(
+                    "The committer key does not support the degree bound {} required by {}: {:?}",
+                    bound,
+                    ark_std::any::type_name::<P>(),
+                    error
+                ))
+            })?;
+        }
+
+        Ok(Self {
+            ck,
+            enforced_degree_bound,
+            cancellation: None,
+            _protocol: PhantomData,
+        })
+    }
+
+    /// Have the provers taking this key abort once `token` is cancelled
+    pub fn with_cancellation(mut self, token: &'a CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn ck(&self) -> &'a pc::CommitterKey<C> {
+        self.ck
+    }
+
+    /// The degree bound the key was checked for
+    pub fn enforced_degree_bound(&self) -> Option<usize> {
+        self.enforced_degree_bound
+    }
+
+    /// The cancellation token of the key, if any
+    pub fn cancellation(&self) -> Option<&'a CancellationToken> {
+        self.cancellation
+    }
+
+    /// Fail unless the key was checked for `enforced_degree_bound`
+    pub fn check_bound(&self, enforced_degree_bound: Option<usize>) -> Result<(), Error> {
+        if enforced_degree_bound != self.enforced_degree_bound {
+            return Err(Error::UnsupportedDegreeBound(format!(
+                "The committer key was checked for the enforced degree bound {:?}, not {:?}",
+                self.enforced_degree_bound, enforced_degree_bound
+            )));
+        }
+        Ok(())
+    }
+
+    /// The same key for a sub-protocol `Q` of `P`, whose bounds are among those of `P`
+    pub(crate) fn sub_protocol<Q: RequiredDegreeBounds>(&self) -> CheckedCommitterKey<'a, C, Q> {
+        debug_assert!(Q::required_degree_bounds(self.enforced_degree_bound)
+            .iter()
+            .all(|bound| P::required_degree_bounds(self.enforced_degree_bound).contains(bound)));
+
+        CheckedCommitterKey {
+            ck: self.ck,
+            enforced_degree_bound: self.enforced_degree_bound,
+            cancellation: self.cancellation,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "prover")]
+impl<'a, C: ProtocolConfig, P> Clone for CheckedCommitterKey<'a, C, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "prover")]
+impl<'a, C: ProtocolConfig, P> Copy for CheckedCommitterKey<'a, C, P> {}
+
+impl<C: ProtocolConfig> RequiredDegreeBounds for DLComparison<C>
+where
+    C::F: SquareRootField,
+{
+    fn required_degree_bounds(enforced_degree_bound: Option<usize>) -> Vec<usize> {
+        zero_over_k_bounds(enforced_degree_bound)
+    }
+}
+
+impl<C: ProtocolConfig> RequiredDegreeBounds for TStrictlyLowerTriangular<C>
+where
+    C::F: SquareRootField,
+{
+    fn required_degree_bounds(enforced_degree_bound: Option<usize>) -> Vec<usize> {
+        zero_over_k_bounds(enforced_degree_bound)
+    }
+}
+
+impl<C: ProtocolConfig> RequiredDegreeBounds for TDiag<C>
+where
+    C::F: SquareRootField,
+{
+    fn required_degree_bounds(enforced_degree_bound: Option<usize>) -> Vec<usize> {
+        zero_over_k_bounds(enforced_degree_bound)
+    }
+}
+
+impl<C: ProtocolConfig> RequiredDegreeBounds for TFT<C>
+where
+    C::F: SquareRootField,
+{
+    fn required_degree_bounds(enforced_degree_bound: Option<usize>) -> Vec<usize> {
+        zero_over_k_bounds(enforced_degree_bound)
+    }
+}
@@ -0,0 +1,3 @@
+//! The [`ProtocolConfig`] the protocols of this crate are parameterized by
+
+pub use zero_over_k::config::{pc, Config, ProtocolConfig};
@@ -0,0 +1,365 @@
+//! Estimated costs of the protocols of this crate, to choose domains and features before proving anything.
+//!
+//! Costs are counted rather than measured: proof sizes in commitments, opening proofs and field elements, prover work
+//! in FFTs and commitments (MSMs) over K, and verifier work in opening checks and pairings, with the homomorphic KZG
+//! scheme in mind where checking a batched opening takes one product of two pairings. The counts follow the
+//! structure of the proofs, so they are exact for the elements of a proof, leaving out fixed-size headers such as
+//! statement digests, and for pairings, and a first-order estimate of prover time.
+//!
+//! ```ignore
+//! const TFT_PAIRINGS: usize = Protocol::TFT.cost().pairings;
+//!
+//! let report = CostReport::new(domain_h.size(), domain_k.size(), &[Protocol::TFT], ElementSizes::new(65, 33, 32));
+//! println!("{}", report);
+//! ```
+
+use std::fmt;
+
+mod tests;
+
+/// Pairings computed to check one batched opening
+pub const PAIRINGS_PER_OPENING_CHECK: usize = 2;
+
+/// Size of K from which running the FFTs of a prover round on separate threads pays off
+pub const PARALLEL_THRESHOLD: usize = 1 << 12;
+
+/// Number of points a polynomial is opened at from which a constant-size multi-point opening is smaller than a
+/// batched opening with one proof per point
+pub const MULTI_POINT_THRESHOLD: usize = 3;
+
+/// Operation counts of a proof
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cost {
+    /// Commitments in the proof
+    pub commitments: usize,
+    /// Opening proofs in the proof, one per distinct point of each batched opening
+    pub opening_proofs: usize,
+    /// Evaluations and other field elements in the proof
+    pub field_elements: usize,
+    /// FFTs over K, or a small multiple of it, run by the prover
+    pub ffts: usize,
+    /// Commitments computed by the prover, each an MSM of size at most |K|
+    pub msms: usize,
+    /// Batched openings checked by the verifier
+    pub opening_checks: usize,
+    /// Pairings computed by the verifier
+    pub pairings: usize,
+    /// Largest number of points a single polynomial is opened at
+    pub max_opening_points: usize,
+}
+
+impl Cost {
+    pub const ZERO: Self = Self {
+        commitments: 0,
+        opening_proofs: 0,
+        field_elements: 0,
+        ffts: 0,
+        msms: 0,
+        opening_checks: 0,
+        pairings: 0,
+        max_opening_points: 0,
+    };
+
+    /// The cost of running both protocols
+    pub const fn plus(self, other: Self) -> Self {
+        Self {
+            commitments: self.commitments + other.commitments,
+            opening_proofs: self.opening_proofs + other.opening_proofs,
+            field_elements: self.field_elements + other.field_elements,
+            ffts: self.ffts + other.ffts,
+            msms: self.msms + other.msms,
+            opening_checks: self.opening_checks + other.opening_checks,
+            pairings: self.pairings + other.pairings,
+            max_opening_points: if self.max_opening_points > other.max_opening_points {
+                self.max_opening_points
+            } else {
+                other.max_opening_points
+            },
+        }
+    }
+
+    /// The cost of running the protocol `n` times
+    pub const fn times(self, n: usize) -> Self {
+        Self {
+            commitments: self.commitments * n,
+            opening_proofs: self.opening_proofs * n,
+            field_elements: self.field_elements * n,
+            ffts: self.ffts * n,
+            msms: self.msms * n,
+            opening_checks: self.opening_checks * n,
+            pairings: self.pairings * n,
+            max_opening_points: if n == 0 { 0 } else { self.max_opening_points },
+        }
+    }
+
+    /// A batched opening at `points` distinct points, each polynomial being opened at a single one of them
+    const fn opening(points: usize) -> Self {
+        Self {
+            opening_proofs: points,
+            opening_checks: 1,
+            pairings: PAIRINGS_PER_OPENING_CHECK,
+            max_opening_points: 1,
+            ..Self::ZERO
+        }
+    }
+
+    /// An opening of a single polynomial at `points` distinct points, with one opening proof per point
+    const fn multi_point_opening(points: usize) -> Self {
+        Self {
+            max_opening_points: points,
+            ..Self::opening(points)
+        }
+    }
+
+    /// `n` polynomials interpolated over K and committed to by the prover
+    const fn committed(n: usize) -> Self {
+        Self {
+            commitments: n,
+            ffts: n,
+            msms: n,
+            ..Self::ZERO
+        }
+    }
+
+    /// Size of the proof in bytes
+    pub const fn proof_size(&self, sizes: &ElementSizes) -> usize {
+        self.commitments * sizes.commitment
+            + self.opening_proofs * sizes.opening_proof
+            + self.field_elements * sizes.field_element
+    }
+
+    /// Field multiplications of the prover FFTs over a domain K of size `domain_k_size`
+    pub const fn fft_multiplications(&self, domain_k_size: usize) -> usize {
+        self.ffts * domain_k_size / 2 * log2(domain_k_size)
+    }
+
+    /// Scalar multiplications of the prover MSMs over a domain K of size `domain_k_size`
+    pub const fn msm_terms(&self, domain_k_size: usize) -> usize {
+        self.msms * domain_k_size
+    }
+}
+
+const fn log2(n: usize) -> usize {
+    if n <= 1 {
+        0
+    } else {
+        (usize::BITS - (n - 1).leading_zeros()) as usize
+    }
+}
+
+/// Serialized sizes in bytes of the elements of a proof, which depend on the curve and commitment scheme
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElementSizes {
+    pub commitment: usize,
+    pub opening_proof: usize,
+    pub field_element: usize,
+}
+
+impl ElementSizes {
+    pub const fn new(commitment: usize, opening_proof: usize, field_element: usize) -> Self {
+        Self {
+            commitment,
+            opening_proof,
+            field_element,
+        }
+    }
+}
+
+/// A protocol whose cost is estimated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    /// A ZeroOverK over a virtual oracle with `terms` variable terms and `shifts` distinct shifts, one of which is 1
+    ZeroOverK {
+        terms: usize,
+        shifts: usize,
+    },
+    NonZeroOverK,
+    /// A GeoSeqTest for a sequence made of `sequences` geometric sequences
+    GeoSeq {
+        sequences: usize,
+    },
+    DLComparison,
+    /// DLComparison with the square checks and the product check batched into one ZeroOverK
+    DLComparisonV2,
+    SubsetOverK,
+    TStrictlyLowerTriangular,
+    TDiag,
+    TFT,
+}
+
+impl Protocol {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::ZeroOverK { .. } => "zero_over_k",
+            Self::NonZeroOverK => "non_zero_over_k",
+            Self::GeoSeq { .. } => "geo_seq",
+            Self::DLComparison => "discrete_log_comparison",
+            Self::DLComparisonV2 => "discrete_log_comparison_v2",
+            Self::SubsetOverK => "subset_over_k",
+            Self::TStrictlyLowerTriangular => "t_strictly_lower_triangular",
+            Self::TDiag => "t_diag",
+            Self::TFT => "t_functional_triple",
+        }
+    }
+
+    pub const fn cost(&self) -> Cost {
+        match *self {
+            // r_i and m_i for each term and q_1, with h'_i and m_i opened at alpha_i * beta_1 and alpha_i * beta_2
+            Self::ZeroOverK { terms, shifts } => Cost {
+                field_elements: 2 * terms + 2,
+                ffts: terms + 1,
+                ..Cost::committed(2 * terms + 1).plus(Cost::opening(2 * shifts))
+            },
+            // g = 1 / f, checked by f * g - 1 over K
+            Self::NonZeroOverK => Cost::committed(1).plus(Self::zero_over_k(2, 1)),
+            // f(gamma * X) - r * f(X) over K and the openings of f at the start of each sequence
+            Self::GeoSeq { sequences } => {
+                Self::zero_over_k(2, 2).plus(Cost::multi_point_opening(sequences))
+            }
+            // s, f', g', s' and h, the evaluation of h, three square checks, the product check, the geometric
+            // sequence of h, four subset checks and four non-zero checks
+            Self::DLComparison => Cost {
+                field_elements: 1,
+                ..Cost::committed(5)
+            }
+            .plus(Cost::opening(1))
+            .plus(Self::zero_over_k(2, 1).times(3))
+            .plus(Self::zero_over_k(3, 1))
+            .plus(Self::GeoSeq { sequences: 2 }.cost())
+            .plus(Self::SubsetOverK.cost().times(4))
+            .plus(Self::NonZeroOverK.cost().times(4)),
+            // as DLComparison, with a single check over f, f', g, g', s and s'
+            Self::DLComparisonV2 => Cost {
+                field_elements: 1,
+                ..Cost::committed(5)
+            }
+            .plus(Cost::opening(1))
+            .plus(Self::zero_over_k(6, 1))
+            .plus(Self::GeoSeq { sequences: 2 }.cost())
+            .plus(Self::SubsetOverK.cost().times(4))
+            .plus(Self::NonZeroOverK.cost().times(4)),
+            // m, a, b and z, checked by a single ZeroOverK over z shifted and unshifted, a, b, m, f and h
+            Self::SubsetOverK => Cost::committed(4).plus(Self::zero_over_k(7, 2)),
+            Self::TStrictlyLowerTriangular => Cost::committed(1)
+                .plus(Self::SubsetOverK.cost())
+                .plus(Self::DLComparison.cost())
+                .plus(Self::GeoSeq { sequences: 2 }.cost()),
+            Self::TDiag => Cost::committed(2)
+                .plus(Self::GeoSeq { sequences: 2 }.cost().times(2))
+                .plus(Self::zero_over_k(2, 1).times(3))
+                .plus(Self::NonZeroOverK.cost()),
+            Self::TFT => Self::TStrictlyLowerTriangular
+                .cost()
+                .times(2)
+                .plus(Self::TDiag.cost()),
+        }
+    }
+
+    const fn zero_over_k(terms: usize, shifts: usize) -> Cost {
+        Self::ZeroOverK { terms, shifts }.cost()
+    }
+}
+
+/// Whether the optional features of the crates reduce the cost of the selected protocols
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Advice {
+    /// Enable the `parallel` feature, which runs the FFTs of a prover round on separate threads
+    pub parallel: bool,
+    /// Open polynomials at many points with `AdditivelyHomomorphicPCS::open_multi_point`, whose proof size does not
+    /// depend on the number of points
+    pub multi_point_openings: bool,
+}
+
+impl Advice {
+    pub const fn new(domain_k_size: usize, cost: &Cost) -> Self {
+        Self {
+            parallel: domain_k_size >= PARALLEL_THRESHOLD && cost.ffts > 1,
+            multi_point_openings: cost.max_opening_points >= MULTI_POINT_THRESHOLD,
+        }
+    }
+}
+
+/// Estimated costs of a selection of protocols over given domains
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CostReport {
+    pub domain_h_size: usize,
+    pub domain_k_size: usize,
+    pub sizes: ElementSizes,
+    pub protocols: Vec<(Protocol, Cost)>,
+    pub total: Cost,
+    pub advice: Advice,
+}
+
+impl CostReport {
+    pub fn new(
+        domain_h_size: usize,
+        domain_k_size: usize,
+        protocols: &[Protocol],
+        sizes: ElementSizes,
+    ) -> Self {
+        let protocols = protocols
+            .iter()
+            .map(|protocol| (*protocol, protocol.cost()))
+            .collect::<Vec<_>>();
+        let total = protocols
+            .iter()
+            .fold(Cost::ZERO, |total, (_, cost)| total.plus(*cost));
+
+        Self {
+            domain_h_size,
+            domain_k_size,
+            sizes,
+            protocols,
+            total,
+            advice: Advice::new(domain_k_size, &total),
+        }
+    }
+
+    /// Size in bytes of all the proofs
+    pub fn proof_size(&self) -> usize {
+        self.total.proof_size(&self.sizes)
+    }
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "|H| = {}, |K| = {}",
+            self.domain_h_size, self.domain_k_size
+        )?;
+        let rows = self
+            .protocols
+            .iter()
+            .map(|(protocol, cost)| (protocol.name(), cost))
+            .chain(std::iter::once(("total", &self.total)));
+        for (name, cost) in rows {
+            writeln!(
+                f,
+                "{}: {} bytes, prover {} FFTs and {} MSMs ({} field and {} group multiplications), verifier {} \
+                 pairings",
+                name,
+                cost.proof_size(&self.sizes),
+                cost.ffts,
+                cost.msms,
+                cost.fft_multiplications(self.domain_k_size),
+                cost.msm_terms(self.domain_k_size),
+                cost.pairings
+            )?;
+        }
+        write!(
+            f,
+            "parallel: {}, multi-point openings: {}",
+            recommendation(self.advice.parallel),
+            recommendation(self.advice.multi_point_openings)
+        )
+    }
+}
+
+fn recommendation(enable: bool) -> &'static str {
+    if enable {
+        "recommended"
+    } else {
+        "not needed"
+    }
+}
@@ -0,0 +1,226 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        cost_model::{Advice, Cost, CostReport, ElementSizes, Protocol, PARALLEL_THRESHOLD},
+        discrete_log_comparison::DLComparison,
+        domains::{DomainH, DomainK},
+        geo_seq::GeoSeqTest,
+        non_zero_over_k::NonZeroOverK,
+        subset_over_k::{SubsetOverK, Table},
+        t_diag::TDiag,
+        t_functional_triple::TFT,
+        t_strictly_lower_triangular_test::TStrictlyLowerTriangular,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::One;
+    use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::{test_rng, UniformRand};
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+    use zero_over_k::{
+        proof_shape::ProofShape,
+        virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
+        zero_over_k::ZeroOverK,
+    };
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
+
+    // costs are available at compile time
+    const TFT_COST: Cost = Protocol::TFT.cost();
+
+    #[test]
+    fn test_composition() {
+        let slt = Protocol::TStrictlyLowerTriangular.cost();
+        let diag = Protocol::TDiag.cost();
+        assert_eq!(TFT_COST, slt.plus(slt).plus(diag));
+        assert_eq!(TFT_COST.pairings, 2 * TFT_COST.opening_checks);
+
+        // a ZeroOverK is checked with a single batched opening at beta_1 and beta_2, shifted or not
+        let zero_over_k = Protocol::ZeroOverK {
+            terms: 2,
+            shifts: 2,
+        }
+        .cost();
+        assert_eq!(zero_over_k.opening_checks, 1);
+        assert_eq!(zero_over_k.opening_proofs, 4);
+        assert_eq!(zero_over_k.commitments, 5);
+
+        assert_eq!(Cost::ZERO.plus(diag), diag);
+        assert_eq!(diag.times(0), Cost::ZERO);
+    }
+
+    #[test]
+    fn test_matches_proof() {
+        let rng = &mut test_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        // f is non-zero over K with overwhelming probability
+        let evals = (0..domain_k.size())
+            .map(|_| F::rand(rng))
+            .collect::<Vec<_>>();
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial {
+                coeffs: domain_k.ifft(&evals),
+            },
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (f_commit, f_rand) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        let proof = NonZeroOverK::<C>::prove(
+            &ck,
+            &domain_k,
+            &f,
+            &f_commit[0],
+            &f_rand[0],
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        let cost = Protocol::NonZeroOverK.cost();
+        let zero_over_k = &proof.zero_over_k_proof;
+        assert_eq!(
+            cost.commitments,
+            1 + zero_over_k.m_commitments.len() + zero_over_k.r_commitments.len() + 1
+        );
+        assert_eq!(
+            cost.field_elements,
+            2 + zero_over_k.h_prime_evals.len() + zero_over_k.m_evals.len()
+        );
+        assert_eq!(cost.opening_proofs, zero_over_k.opening_proof.len());
+    }
+
+    #[test]
+    fn test_matches_dry_run() {
+        let domain_k = DomainK::<F>::new(16).unwrap();
+        let domain_h = DomainH::<F>::new(8).unwrap();
+        let bound = Some(20);
+        let t = 2;
+
+        let check = |protocol: Protocol, shape: ProofShape| {
+            assert_eq!(
+                protocol.cost().commitments,
+                shape.commitments.len(),
+                "{:?}",
+                protocol
+            );
+        };
+
+        let inverse_check =
+            GenericShiftingVO::new(&[0, 1], &[F::one(), F::one()], presets::inverse_check).unwrap();
+        check(
+            Protocol::ZeroOverK {
+                terms: 2,
+                shifts: 1,
+            },
+            ZeroOverK::<C>::prove_dry_run(bound, &inverse_check),
+        );
+        check(
+            Protocol::NonZeroOverK,
+            NonZeroOverK::<C>::prove_dry_run(bound).unwrap(),
+        );
+        check(
+            Protocol::GeoSeq { sequences: 2 },
+            GeoSeqTest::<C>::prove_dry_run(
+                domain_k.element(1),
+                "f",
+                bound,
+                &[F::one(), F::one()],
+                &[8, 8],
+                &domain_k,
+            )
+            .unwrap(),
+        );
+        check(
+            Protocol::DLComparison,
+            DLComparison::<C>::prove_dry_run(&domain_k, &domain_h, bound).unwrap(),
+        );
+        check(
+            Protocol::DLComparisonV2,
+            DLComparison::<C>::prove_v2_dry_run(&domain_k, &domain_h, bound).unwrap(),
+        );
+        check(
+            Protocol::SubsetOverK,
+            SubsetOverK::<C>::prove_dry_run(&domain_k, Table::Values, bound).unwrap(),
+        );
+        check(
+            Protocol::TStrictlyLowerTriangular,
+            TStrictlyLowerTriangular::<C>::prove_dry_run(
+                t,
+                &domain_k,
+                &domain_h,
+                bound,
+                TFT::<C>::AB_PADDING,
+            )
+            .unwrap(),
+        );
+        check(
+            Protocol::TDiag,
+            TDiag::<C>::prove_dry_run(
+                t,
+                bound,
+                &domain_k,
+                &domain_h,
+                domain_h.size(),
+                TFT::<C>::C_PADDING,
+            )
+            .unwrap(),
+        );
+        check(
+            Protocol::TFT,
+            TFT::<C>::prove_dry_run(t, &domain_k, &domain_h, bound).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let sizes = ElementSizes::new(65, 33, 32);
+        let report = CostReport::new(
+            4,
+            8,
+            &[Protocol::TStrictlyLowerTriangular, Protocol::TDiag],
+            sizes,
+        );
+
+        assert_eq!(
+            report.total,
+            Protocol::TStrictlyLowerTriangular
+                .cost()
+                .plus(Protocol::TDiag.cost())
+        );
+        assert_eq!(report.proof_size(), report.total.proof_size(&sizes));
+        assert_eq!(report.advice, Advice::default());
+
+        let printed = report.to_string();
+        assert!(printed.contains("t_diag: "));
+        assert!(printed.contains(&format!("{} pairings", report.total.pairings)));
+
+        // large domains benefit from parallel FFTs and geometric sequences with many pieces from multi-point openings
+        let report = CostReport::new(
+            PARALLEL_THRESHOLD,
+            PARALLEL_THRESHOLD,
+            &[Protocol::GeoSeq { sequences: 4 }],
+            sizes,
+        );
+        assert_eq!(
+            report.advice,
+            Advice {
+                parallel: true,
+                multi_point_openings: true,
+            }
+        );
+    }
+}
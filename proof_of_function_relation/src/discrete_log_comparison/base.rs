@@ -0,0 +1,103 @@
+use crate::{discrete_log_comparison::piop::PIOPforDLComparison, domains::DomainH, error::Error};
+use ark_ff::{PrimeField, SquareRootField};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use std::iter;
+
+/// The base of the discrete logs compared by [`DLComparison`](super::DLComparison): a public element g of order n with
+/// a square root of order 2n, which any g of odd order has. Over a domain H, g is the generator of H and n = |H|.
+pub trait DiscreteLogBase<F: PrimeField + SquareRootField> {
+    /// The element g
+    fn generator(&self) -> F;
+
+    /// The order n of g
+    fn order(&self) -> usize;
+
+    /// The powers g^0, ..., g^(n - 1), whose exponents are the discrete logs
+    fn powers(&self) -> Vec<F> {
+        let generator = self.generator();
+        iter::successors(Some(F::one()), |power| Some(*power * generator))
+            .take(self.order())
+            .collect()
+    }
+}
+
+impl<F: PrimeField + SquareRootField> DiscreteLogBase<F> for GeneralEvaluationDomain<F> {
+    fn generator(&self) -> F {
+        self.element(1)
+    }
+
+    fn order(&self) -> usize {
+        self.size()
+    }
+
+    fn powers(&self) -> Vec<F> {
+        self.elements().collect()
+    }
+}
+
+impl<F: PrimeField + SquareRootField> DiscreteLogBase<F> for DomainH<F> {
+    fn generator(&self) -> F {
+        self.0.generator()
+    }
+
+    fn order(&self) -> usize {
+        self.0.order()
+    }
+
+    fn powers(&self) -> Vec<F> {
+        self.0.powers()
+    }
+}
+
+/// A public element of known order, to compare discrete logs in a base other than the generator of a domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupElementBase<F: PrimeField + SquareRootField> {
+    generator: F,
+    order: usize,
+}
+
+impl<F: PrimeField + SquareRootField> GroupElementBase<F> {
+    /// Check that `generator` has exactly the given order, and a square root of twice that order
+    pub fn new(generator: F, order: usize) -> Result<Self, Error> {
+        let has_order = |order: usize| generator.pow([order as u64]).is_one();
+        if order == 0 || !has_order(order) || prime_factors(order).any(|p| has_order(order / p)) {
+            return Err(Error::InvalidDiscreteLogBase(format!(
+                "The base does not have order {}",
+                order
+            )));
+        }
+        let base = Self { generator, order };
+        PIOPforDLComparison::delta(&base)?;
+
+        Ok(base)
+    }
+}
+
+impl<F: PrimeField + SquareRootField> DiscreteLogBase<F> for GroupElementBase<F> {
+    fn generator(&self) -> F {
+        self.generator
+    }
+
+    fn order(&self) -> usize {
+        self.order
+    }
+}
+
+/// The distinct prime factors of n, by trial division
+fn prime_factors(mut n: usize) -> impl Iterator<Item = usize> {
+    let mut factors = vec![];
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors.into_iter()
+}
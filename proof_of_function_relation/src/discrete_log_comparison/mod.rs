@@ -1,168 +1,715 @@
 use crate::{
-    discrete_log_comparison::{piop::PIOPforDLComparison, proof::Proof},
-    error::{to_pc_error, Error},
-    geo_seq::GeoSeqTest,
-    non_zero_over_k::NonZeroOverK,
-    subset_over_k::SubsetOverK,
-};
-use ark_ff::{to_bytes, PrimeField, SquareRootField};
-use ark_poly::{
-    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    discrete_log_comparison::{
+        base::DiscreteLogBase,
+        piop::{oracles::OracleSet, PIOPforDLComparison},
+        proof::{Proof, ProofV2, VersionedProof},
+    },
+    domains::DomainK,
+    error::{to_pc_error, Error, WitnessError},
+    geo_seq::{proof::Proof as GeoProof, GeoSeqTest},
+    non_zero_over_k::{proof::Proof as NonZeroProof, NonZeroOverK},
+    report::{ReportBuilder, VerificationReport},
+    subset_over_k::{proof::Proof as SubsetProof, SubsetOverK, Table},
 };
-use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use ark_ff::{to_bytes, One, SquareRootField, Zero};
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use ark_serialize::CanonicalSerialize;
 use ark_std::marker::PhantomData;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
-use rand::Rng;
-use std::iter;
+use homomorphic_poly_commit::{commit_policy::CommitPolicy, AdditivelyHomomorphicPCS};
+use zero_over_k::config::{pc, ProtocolConfig};
 use zero_over_k::{
-    virtual_oracle::generic_shifting_vo::{
-        presets::{self, square_check},
-        GenericShiftingVO,
+    proof_shape::ProofShape,
+    square_and_product_check, trace_span,
+    virtual_oracle::{
+        generic_shifting_vo::{
+            presets::{self, square_check},
+            vo_term::VOTerm,
+            GenericShiftingVO,
+        },
+        VirtualOracle,
     },
+    vo_constant,
     zero_over_k::ZeroOverK,
 };
+#[cfg(feature = "prover")]
+use {
+    crate::{
+        checked_key::CheckedCommitterKey,
+        discrete_log_comparison::piop::prover::{ProverFirstOracles, ProverState},
+    },
+    rand::Rng,
+    std::iter,
+    zero_over_k::cancellation::CancellationToken,
+};
 
-use fiat_shamir_rng::FiatShamirRng;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
 
+pub mod base;
 pub mod piop;
 pub mod proof;
 mod tests;
 
-pub struct DLComparison<
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
-> {
-    _field: PhantomData<F>,
-    _polynomial_commitment_scheme: PhantomData<PC>,
-    _fs: PhantomData<FS>,
+/// Proves that for every element κ of K, with f(κ) = ω^a and g(κ) = ω^b for ω the generator of H and
+/// 0 <= a, b < |H|, the discrete logs compare strictly: a > b. The exponents are taken in [0, |H|) without wrapping,
+/// so at the boundaries:
+/// - g(κ) = ω^0 = 1 compares below any f(κ) other than 1, and the largest gap is f(κ) = ω^(|H| - 1), g(κ) = 1
+/// - f(κ) = 1 is never greater than g(κ); for g(κ) != 1 the product check f' = s' * g' fails
+/// - f(κ) = g(κ), including f(κ) = g(κ) = 1, is rejected by the check that s(X) - 1 is non-zero over K
+///
+/// More generally, ω can be any public base of order n with a square root of order 2n, in place of the generator of H
+/// and its order |H|, see [`DiscreteLogBase`]. The protocol then needs n <= |K|.
+pub struct DLComparison<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
 }
 
-impl<F, PC, FS> DLComparison<F, PC, FS>
+impl<C: ProtocolConfig> DLComparison<C>
 where
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
+    C::F: SquareRootField,
 {
     pub const PROTOCOL_NAME: &'static [u8] = b"Discrete-log Comparison";
 
-    pub fn prove<R: Rng>(
-        ck: &PC::CommitterKey,
-        domain_k: &GeneralEvaluationDomain<F>,
-        domain_h: &GeneralEvaluationDomain<F>,
-        f: &LabeledPolynomial<F, DensePolynomial<F>>,
-        f_commit: &LabeledCommitment<PC::Commitment>,
-        f_rand: &PC::Randomness,
-        g: &LabeledPolynomial<F, DensePolynomial<F>>,
-        g_commit: &LabeledCommitment<PC::Commitment>,
-        g_rand: &PC::Randomness,
+    /// Check that f and g satisfy the comparison over K, failing on the first offending element before any work is
+    /// done. With the `validate-witness` feature the prover runs this check itself.
+    pub fn validate_witness<B: DiscreteLogBase<C::F>>(
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        g: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    ) -> Result<(), Error> {
+        let f_exponents = PIOPforDLComparison::exponents(domain_k, base, f)?;
+        let g_exponents = PIOPforDLComparison::exponents(domain_k, base, g)?;
+
+        for (index, (&f_exponent, &g_exponent)) in
+            f_exponents.iter().zip(g_exponents.iter()).enumerate()
+        {
+            if f_exponent <= g_exponent {
+                return Err(Error::InvalidWitness(WitnessError::NotGreater {
+                    index,
+                    f_exponent,
+                    g_exponent,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The commitments and queries of a proof of [`Self::prove`] over K and H, without computing it
+    pub fn prove_dry_run<B: DiscreteLogBase<C::F>>(
+        domain_k: &DomainK<C::F>,
+        base: &B,
         enforced_degree_bound: Option<usize>,
-        fs_rng: &mut FS,
-        rng: &mut R,
-    ) -> Result<Proof<F, PC>, Error> {
-        let prover_initial_state =
-            PIOPforDLComparison::prover_init(domain_k, domain_h, f, g, enforced_degree_bound)?;
+    ) -> Result<ProofShape, Error> {
+        let mut shape = Self::first_round_dry_run(enforced_degree_bound);
 
-        //------------------------------------------------------------------
-        // First Round
-        let (_, prover_first_oracles, prover_state) =
-            PIOPforDLComparison::prover_first_round(prover_initial_state, rng)?;
+        let square_check_vo = Self::square_check_oracle()?;
+        for label in ["f_prime_square", "g_prime_square", "s_prime_square"] {
+            shape.extend(
+                label,
+                ZeroOverK::<C>::prove_dry_run(enforced_degree_bound, &square_check_vo),
+            );
+        }
+        shape.extend(
+            "f_prime_product",
+            ZeroOverK::<C>::prove_dry_run(enforced_degree_bound, &Self::product_check_oracle()?),
+        );
 
-        //------------------------------------------------------------------
-        // Commit Phase
+        Self::shared_dry_run(&mut shape, domain_k, base, enforced_degree_bound)?;
+        Ok(shape)
+    }
 
-        let one_poly = DensePolynomial::from_coefficients_vec(vec![F::one()]);
-        let one_poly =
-            LabeledPolynomial::new(String::from("one"), one_poly, enforced_degree_bound, None);
+    /// The commitments and queries of a proof of [`Self::prove_v2`] over K and H, without computing it
+    pub fn prove_v2_dry_run<B: DiscreteLogBase<C::F>>(
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<ProofShape, Error> {
+        let mut shape = Self::first_round_dry_run(enforced_degree_bound);
+        shape.challenge("combination");
+        shape.extend(
+            "square_and_product",
+            ZeroOverK::<C>::prove_dry_run(enforced_degree_bound, &Self::combined_check_oracle()?),
+        );
 
-        // commit to s, f_prime, g_prime, s_prime, h and the constant 1 polynomial
-        // order of commitments is: s, f_prime, g_prime, s_prime, h, one
-        let (commitments, rands) = PC::commit(
-            ck,
-            prover_first_oracles.iter().chain(iter::once(&one_poly)),
-            Some(rng),
+        Self::shared_dry_run(&mut shape, domain_k, base, enforced_degree_bound)?;
+        Ok(shape)
+    }
+
+    /// Serialized size in bytes of a proof of [`Self::prove`] over K and H, with keys of degree `supported_degree`
+    pub fn proof_size<B: DiscreteLogBase<C::F>>(
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        Ok(
+            Self::first_round_size(enforced_degree_bound, supported_degree)
+                + 3 * ZeroOverK::<C>::proof_size(
+                    enforced_degree_bound,
+                    &Self::square_check_oracle()?,
+                    CommitPolicy::NonHiding,
+                    supported_degree,
+                )
+                + ZeroOverK::<C>::proof_size(
+                    enforced_degree_bound,
+                    &Self::product_check_oracle()?,
+                    CommitPolicy::NonHiding,
+                    supported_degree,
+                )
+                + Self::shared_size(domain_k, base, enforced_degree_bound, supported_degree)?,
         )
-        .map_err(to_pc_error::<F, PC>)?;
+    }
 
-        let fs_bytes =
-            &to_bytes![Self::PROTOCOL_NAME, commitments].map_err(|_| Error::ToBytesError)?;
-        fs_rng.absorb(fs_bytes);
+    /// Serialized size in bytes of a proof of [`Self::prove_v2`] over K and H, with keys of degree
+    /// `supported_degree`
+    pub fn proof_v2_size<B: DiscreteLogBase<C::F>>(
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        Ok(
+            Self::first_round_size(enforced_degree_bound, supported_degree)
+                + ZeroOverK::<C>::proof_size(
+                    enforced_degree_bound,
+                    &Self::combined_check_oracle()?,
+                    CommitPolicy::NonHiding,
+                    supported_degree,
+                )
+                + Self::shared_size(domain_k, base, enforced_degree_bound, supported_degree)?,
+        )
+    }
+
+    fn square_check_oracle() -> Result<impl VirtualOracle<C::F>, Error> {
+        Ok(
+            GenericShiftingVO::new(&[0, 1], &[C::F::one(), C::F::one()], square_check)?
+                .with_scaling_factor(2),
+        )
+    }
+
+    fn product_check_oracle() -> Result<impl VirtualOracle<C::F>, Error> {
+        Ok(
+            GenericShiftingVO::new(&[0, 1, 2], &[C::F::one(); 3], presets::abc_product_check)?
+                .with_scaling_factor(2),
+        )
+    }
+
+    /// The check of [`Self::prove_v2`], whose combination challenge changes its evaluations, not its terms
+    fn combined_check_oracle() -> Result<impl VirtualOracle<C::F>, Error> {
+        Ok(GenericShiftingVO::new(
+            &[0, 1, 2, 3, 4, 5],
+            &[C::F::one(); 6],
+            square_and_product_check!(C::F::one()),
+        )?
+        .with_scaling_factor(2))
+    }
+
+    /// s, f', g', s' and h, hiding, and the opening of h
+    fn first_round_size(enforced_degree_bound: Option<usize>, supported_degree: usize) -> usize {
+        OracleSet::LABELS.iter().len() * C::PC::commitment_size(enforced_degree_bound.is_some())
+            + C::F::zero().serialized_size()
+            + C::PC::opening_proof_size(supported_degree, true)
+    }
+
+    /// The geometric sequence test on h, which is hiding, the subset checks and the non-zero checks
+    fn shared_size<B: DiscreteLogBase<C::F>>(
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        let delta = PIOPforDLComparison::<C::F>::delta(base)?;
+        let (a_s, c_s) = PIOPforDLComparison::<C::F>::h_sequence(base, domain_k)?;
+
+        Ok(GeoSeqTest::<C>::proof_size(
+            delta,
+            enforced_degree_bound,
+            true,
+            &a_s,
+            &c_s,
+            domain_k,
+            supported_degree,
+        )? + 3 * SubsetOverK::<C>::proof_size(
+            domain_k,
+            Table::Values,
+            enforced_degree_bound,
+            supported_degree,
+        )? + SubsetOverK::<C>::proof_size(
+            domain_k,
+            Table::Squares,
+            enforced_degree_bound,
+            supported_degree,
+        )? + 4 * NonZeroOverK::<C>::proof_size(enforced_degree_bound, supported_degree)?)
+    }
+
+    /// s, f', g', s' and h, with h opened at a random point
+    fn first_round_dry_run(enforced_degree_bound: Option<usize>) -> ProofShape {
+        let mut shape = ProofShape::new();
+        for label in OracleSet::LABELS {
+            shape.commit(label, enforced_degree_bound);
+        }
+        shape.query(OracleSet::LABELS.h, "h_point");
+        shape.challenge("h_point");
+        shape.check(
+            "h_degree_bound",
+            "opening of h at h_point, against its degree bound",
+        );
+        shape
+    }
+
+    /// The geometric sequence test on h, the subset checks and the non-zero checks, see [`Self::prove_shared`]
+    fn shared_dry_run<B: DiscreteLogBase<C::F>>(
+        shape: &mut ProofShape,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<(), Error> {
+        let delta = PIOPforDLComparison::<C::F>::delta(base)?;
+        let (a_s, c_s) = PIOPforDLComparison::<C::F>::h_sequence(base, domain_k)?;
+        shape.extend(
+            "h_geo_seq",
+            GeoSeqTest::<C>::prove_dry_run(
+                delta,
+                OracleSet::LABELS.h,
+                enforced_degree_bound,
+                &a_s,
+                &c_s,
+                domain_k,
+            )?,
+        );
+
+        for (label, table) in [
+            ("f_prime_subset", Table::Values),
+            ("g_prime_subset", Table::Values),
+            ("s_prime_subset", Table::Values),
+            ("s_h_subset", Table::Squares),
+        ] {
+            shape.extend(
+                label,
+                SubsetOverK::<C>::prove_dry_run(domain_k, table, enforced_degree_bound)?,
+            );
+        }
+
+        for label in [
+            "f_prime_non_zero",
+            "g_prime_non_zero",
+            "s_prime_non_zero",
+            "s_minus_one_non_zero",
+        ] {
+            shape.extend(
+                label,
+                NonZeroOverK::<C>::prove_dry_run(enforced_degree_bound)?,
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "prover")]
+    pub fn prove<B: DiscreteLogBase<C::F>, R: Rng>(
+        ck: &CheckedCommitterKey<C, Self>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        g: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_rand: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("discrete_log_comparison::prove");
+        ck.check_bound(enforced_degree_bound)?;
+        let cancellation = ck.cancellation();
+        let ck = ck.ck();
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(domain_k, base, f, g)?;
+
+        let prover_state = PIOPforDLComparison::prover_init(
+            domain_k,
+            base,
+            f,
+            g,
+            enforced_degree_bound,
+            cancellation,
+        )?;
+        let first_round = Self::prove_first_round(
+            ck,
+            prover_state,
+            f_commit,
+            g_commit,
+            enforced_degree_bound,
+            context,
+            fs_rng,
+            rng,
+        )?;
+        let oracles = &first_round.oracles;
+        let commitments = &first_round.commitments;
+        let rands = &first_round.rands;
 
-        let alphas = [F::one(), F::one()];
-        let square_check_vo = GenericShiftingVO::new(&[0, 1], &alphas, square_check)?;
+        let alphas = [C::F::one(), C::F::one()];
+        let square_check_vo =
+            GenericShiftingVO::new(&[0, 1], &alphas, square_check)?.with_scaling_factor(2);
 
         //------------------------------------------------------------------
         // Run sub-protocols
 
         // Step 4a: Zero over K for f = (f')^2
-        let f_prime_square_proof = ZeroOverK::<F, PC, FS>::prove(
-            &[f.clone(), prover_first_oracles.f_prime.clone()],
-            &[f_commit.clone(), commitments[1].clone()], // f and f'
-            &[f_rand.clone(), rands[1].clone()],
+        let f_prime_square_proof = ZeroOverK::<C>::prove_with_cancellation(
+            &[f, &oracles.f_prime],
+            &[f_commit.clone(), commitments.f_prime.clone()],
+            &[f_rand.clone(), rands.f_prime.clone()],
             enforced_degree_bound,
             &square_check_vo,
             &domain_k,
             &ck,
+            &context.child(b"f_prime_square"),
+            cancellation,
             rng,
         )?;
 
         // Step 4b: Zero over K for g = (g')^2
-        let g_prime_square_proof = ZeroOverK::<F, PC, FS>::prove(
-            &[g.clone(), prover_first_oracles.g_prime.clone()],
-            &[g_commit.clone(), commitments[2].clone()], // g and g'
-            &[g_rand.clone(), rands[2].clone()],
+        let g_prime_square_proof = ZeroOverK::<C>::prove_with_cancellation(
+            &[g, &oracles.g_prime],
+            &[g_commit.clone(), commitments.g_prime.clone()],
+            &[g_rand.clone(), rands.g_prime.clone()],
             enforced_degree_bound,
             &square_check_vo,
             &domain_k,
             &ck,
+            &context.child(b"g_prime_square"),
+            cancellation,
             rng,
         )?;
 
         // Step 4c: Zero over K for s = (s')^2
-        let s_prime_square_proof = ZeroOverK::<F, PC, FS>::prove(
-            &[
-                prover_first_oracles.s.clone(),
-                prover_first_oracles.s_prime.clone(),
-            ],
-            &[commitments[0].clone(), commitments[3].clone()], // s and s'
-            &[rands[0].clone(), rands[3].clone()],
+        let s_prime_square_proof = ZeroOverK::<C>::prove_with_cancellation(
+            &[&oracles.s, &oracles.s_prime],
+            &[commitments.s.clone(), commitments.s_prime.clone()],
+            &[rands.s.clone(), rands.s_prime.clone()],
             enforced_degree_bound,
             &square_check_vo,
             &domain_k,
             &ck,
+            &context.child(b"s_prime_square"),
+            cancellation,
             rng,
         )?;
 
-        // // SANITY CHECK
-        // for element in domain_k.elements() {
-        //     let eval = prover_first_oracles.f_prime.evaluate(&element)
-        //         - prover_first_oracles.s_prime.evaluate(&element)
-        //             * prover_first_oracles.g_prime.evaluate(&element);
-        //     assert_eq!(eval, F::zero());
-        // }
-
         // Step 4d: Zero over K for f' = (s')*(g')
-        let product_check_vo =
-            GenericShiftingVO::new(&[0, 1, 2], &vec![F::one(); 3], presets::abc_product_check)?;
-        let f_prime_product_proof = ZeroOverK::<F, PC, FS>::prove(
+        let product_check_vo = GenericShiftingVO::new(
+            &[0, 1, 2],
+            &vec![C::F::one(); 3],
+            presets::abc_product_check,
+        )?
+        .with_scaling_factor(2);
+        let f_prime_product_proof = ZeroOverK::<C>::prove_with_cancellation(
+            &[&oracles.f_prime, &oracles.s_prime, &oracles.g_prime],
             &[
-                prover_first_oracles.f_prime.clone(),
-                prover_first_oracles.s_prime.clone(),
-                prover_first_oracles.g_prime.clone(),
+                commitments.f_prime.clone(),
+                commitments.s_prime.clone(),
+                commitments.g_prime.clone(),
             ],
             &[
-                commitments[1].clone(),
-                commitments[3].clone(),
-                commitments[2].clone(),
-            ], // f', s' and g'
-            &[rands[1].clone(), rands[3].clone(), rands[2].clone()],
+                rands.f_prime.clone(),
+                rands.s_prime.clone(),
+                rands.g_prime.clone(),
+            ],
             enforced_degree_bound,
             &product_check_vo,
             &domain_k,
             &ck,
+            &context.child(b"f_prime_product"),
+            cancellation,
+            rng,
+        )?;
+
+        let shared = Self::prove_shared(
+            ck,
+            domain_k,
+            first_round,
+            enforced_degree_bound,
+            context,
+            fs_rng,
+            cancellation,
+            rng,
+        )?;
+
+        Ok(Proof {
+            // Commitments
+            s_commit: shared.commitments.s,
+            f_prime_commit: shared.commitments.f_prime,
+            g_prime_commit: shared.commitments.g_prime,
+            s_prime_commit: shared.commitments.s_prime,
+            h_commit: shared.commitments.h,
+
+            // Evaluations
+            h_eval: shared.h_eval,
+
+            // Proofs
+            f_prime_square_proof,
+            g_prime_square_proof,
+            s_prime_square_proof,
+            f_prime_product_proof,
+            h_proof: shared.h_proof,
+            h_opening_proof: shared.h_opening_proof,
+            f_prime_subset_proof: shared.f_prime_subset_proof,
+            g_prime_subset_proof: shared.g_prime_subset_proof,
+            s_prime_subset_proof: shared.s_prime_subset_proof,
+            s_subset_proof: shared.s_subset_proof,
+            nzk_f_prime_proof: shared.nzk_f_prime_proof,
+            nzk_g_prime_proof: shared.nzk_g_prime_proof,
+            nzk_s_prime_proof: shared.nzk_s_prime_proof,
+            nzk_s_minus_one_proof: shared.nzk_s_minus_one_proof,
+        })
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove the comparison with the square checks and the product check batched into a single ZeroOverK over f, f',
+    /// g, g', s and s' (see [`square_and_product_check`]). The proof is smaller than that of [`Self::prove`], with
+    /// one quotient, batched opening and masking of each oracle instead of four of each.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_v2<B: DiscreteLogBase<C::F>, R: Rng>(
+        ck: &CheckedCommitterKey<C, Self>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        g: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_rand: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<ProofV2<C>, Error> {
+        let _span = trace_span!("discrete_log_comparison::prove_v2");
+        ck.check_bound(enforced_degree_bound)?;
+        let cancellation = ck.cancellation();
+        let ck = ck.ck();
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(domain_k, base, f, g)?;
+
+        let prover_state = PIOPforDLComparison::prover_init(
+            domain_k,
+            base,
+            f,
+            g,
+            enforced_degree_bound,
+            cancellation,
+        )?;
+        let first_round = Self::prove_first_round(
+            ck,
+            prover_state,
+            f_commit,
+            g_commit,
+            enforced_degree_bound,
+            context,
+            fs_rng,
+            rng,
+        )?;
+        let oracles = &first_round.oracles;
+        let commitments = &first_round.commitments;
+        let rands = &first_round.rands;
+
+        // Step 4: Zero over K for f = (f')^2, g = (g')^2, s = (s')^2 and f' = (s')*(g') at once
+        let challenge = Self::combination_challenge(fs_rng);
+        let combined_check_vo = GenericShiftingVO::new(
+            &[0, 1, 2, 3, 4, 5],
+            &[C::F::one(); 6],
+            square_and_product_check!(challenge),
+        )?
+        .with_scaling_factor(2);
+        let square_and_product_proof = ZeroOverK::<C>::prove_with_cancellation(
+            &[
+                f,
+                &oracles.f_prime,
+                g,
+                &oracles.g_prime,
+                &oracles.s,
+                &oracles.s_prime,
+            ],
+            &[
+                f_commit.clone(),
+                commitments.f_prime.clone(),
+                g_commit.clone(),
+                commitments.g_prime.clone(),
+                commitments.s.clone(),
+                commitments.s_prime.clone(),
+            ],
+            &[
+                f_rand.clone(),
+                rands.f_prime.clone(),
+                g_rand.clone(),
+                rands.g_prime.clone(),
+                rands.s.clone(),
+                rands.s_prime.clone(),
+            ],
+            enforced_degree_bound,
+            &combined_check_vo,
+            domain_k,
+            ck,
+            &context.child(b"square_and_product"),
+            cancellation,
             rng,
         )?;
 
+        let shared = Self::prove_shared(
+            ck,
+            domain_k,
+            first_round,
+            enforced_degree_bound,
+            context,
+            fs_rng,
+            cancellation,
+            rng,
+        )?;
+
+        Ok(ProofV2 {
+            // Commitments
+            s_commit: shared.commitments.s,
+            f_prime_commit: shared.commitments.f_prime,
+            g_prime_commit: shared.commitments.g_prime,
+            s_prime_commit: shared.commitments.s_prime,
+            h_commit: shared.commitments.h,
+
+            // Evaluations
+            h_eval: shared.h_eval,
+
+            // Proofs
+            square_and_product_proof,
+            h_proof: shared.h_proof,
+            h_opening_proof: shared.h_opening_proof,
+            f_prime_subset_proof: shared.f_prime_subset_proof,
+            g_prime_subset_proof: shared.g_prime_subset_proof,
+            s_prime_subset_proof: shared.s_prime_subset_proof,
+            s_subset_proof: shared.s_subset_proof,
+            nzk_f_prime_proof: shared.nzk_f_prime_proof,
+            nzk_g_prime_proof: shared.nzk_g_prime_proof,
+            nzk_s_prime_proof: shared.nzk_s_prime_proof,
+            nzk_s_minus_one_proof: shared.nzk_s_minus_one_proof,
+        })
+    }
+
+    /// The challenge batching the four constraints of the combined check, derived once the prover committed to s,
+    /// f', g', s' and h
+    fn combination_challenge(fs_rng: &mut C::FS) -> C::F {
+        ChallengeSet::new(fs_rng, b"square_and_product").challenge(b"combination")
+    }
+
+    #[cfg(feature = "prover")]
+    /// Commit to s, f', g', s' and h, and open h at a random point so that its degree bound is enforced
+    #[allow(clippy::too_many_arguments)]
+    fn prove_first_round<'a, R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        prover_initial_state: ProverState<'a, C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<FirstRound<'a, C>, Error> {
+        let fs_bytes = &to_bytes![Self::PROTOCOL_NAME, context, f_commit, g_commit]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        //------------------------------------------------------------------
+        // First Round
+        let (_, prover_first_oracles, prover_state) =
+            PIOPforDLComparison::prover_first_round(prover_initial_state, rng)?;
+
+        //------------------------------------------------------------------
+        // Commit Phase
+
+        let one_poly = DensePolynomial::from_coefficients_vec(vec![C::F::one()]);
+        let one_poly =
+            LabeledPolynomial::new(String::from("one"), one_poly, enforced_degree_bound, None);
+
+        // commit to s, f_prime, g_prime, s_prime, h and then the constant 1 polynomial
+        let (mut commitments, mut rands) = C::PC::commit(
+            ck,
+            prover_first_oracles.iter().chain(iter::once(&one_poly)),
+            Some(rng),
+        )
+        .map_err(to_pc_error::<C::F, C::PC>)?;
+        let one_commitment = commitments.pop().expect("Committed to one");
+        let one_rand = rands.pop().expect("Committed to one");
+        let commitments = OracleSet::from_ordered(commitments).expect("One commitment per oracle");
+        let rands = OracleSet::from_ordered(rands).expect("One randomness per oracle");
+
+        let fs_bytes = &to_bytes![&commitments.to_vec()].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        // The verifier derives the commitment to s - 1 from s and its own commitment to one, which the transcript
+        // binds by absorbing the result
+        let (s_minus_one_commitment, s_minus_one_rand) = C::PC::aggregate_commitments(
+            &[commitments.s.clone(), one_commitment],
+            Some(vec![rands.s.clone(), one_rand]),
+            &PIOPforDLComparison::s_minus_one_linear_combination(),
+        )?;
+        Self::absorb_s_minus_one(s_minus_one_commitment.commitment(), fs_rng)?;
+
+        // open h at a random point against its commitment so that its degree bound is enforced
+        let h_point: C::F =
+            ChallengeSet::new(&mut *fs_rng, b"h_degree_bound").challenge(b"h_point");
+        let h_eval = prover_first_oracles.h.evaluate(&h_point);
+        let h_opening_proof = C::PC::open(
+            ck,
+            [&prover_first_oracles.h],
+            [&commitments.h],
+            &h_point,
+            C::F::one(),
+            [&rands.h],
+            Some(rng),
+        )
+        .map_err(to_pc_error::<C::F, C::PC>)?;
+
+        Ok(FirstRound {
+            oracles: prover_first_oracles,
+            state: prover_state,
+            commitments,
+            rands,
+            one_poly,
+            s_minus_one_commitment,
+            s_minus_one_rand,
+            h_eval,
+            h_opening_proof,
+        })
+    }
+
+    #[cfg(feature = "prover")]
+    /// The sub-proofs that follow the square and product checks in both versions of the protocol
+    #[allow(clippy::too_many_arguments)]
+    fn prove_shared<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        first_round: FirstRound<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<SharedProofs<C>, Error> {
+        let FirstRound {
+            oracles: prover_first_oracles,
+            state: prover_state,
+            commitments,
+            rands,
+            one_poly,
+            s_minus_one_commitment,
+            s_minus_one_rand,
+            h_eval,
+            h_opening_proof,
+        } = first_round;
+
         // Step 5: Geometric sequence test on h
         let delta = prover_state
             .delta
@@ -174,59 +721,108 @@ where
             .c_s
             .expect("\'c\' values should be computed in the prover's first round");
 
-        let h_proof = GeoSeqTest::<F, PC, FS>::prove(
+        let h_proof = GeoSeqTest::<C>::prove_with_cancellation(
             &ck,
             delta,
             &prover_first_oracles.h,
-            &commitments[4].clone(),
-            &rands[4],
+            &commitments.h,
+            &rands.h,
             &a_s,
             &c_s,
             &domain_k,
+            &context.child(b"h_geo_seq"),
+            cancellation,
             rng,
         )?;
 
-        // Step 6a: Subset over K for f'
-        let f_prime_subset_proof = SubsetOverK::<F, PC, FS>::prove();
-
-        // Step 6b: Subset over K for g'
-        let g_prime_subset_proof = SubsetOverK::<F, PC, FS>::prove();
+        // Steps 6a to 6c: Subset over K between each of f', g' and s' and h, i.e. they are powers of delta below the
+        // order of the base
+        let mut prove_subset = |f, f_commit, f_rand, table, label: &[u8]| {
+            SubsetOverK::<C>::prove_with_cancellation(
+                ck,
+                domain_k,
+                f,
+                f_commit,
+                f_rand,
+                &prover_first_oracles.h,
+                &commitments.h,
+                &rands.h,
+                table,
+                enforced_degree_bound,
+                &context.child(label),
+                fs_rng,
+                cancellation,
+                rng,
+            )
+        };
+        let f_prime_subset_proof = prove_subset(
+            &prover_first_oracles.f_prime,
+            &commitments.f_prime,
+            &rands.f_prime,
+            Table::Values,
+            b"f_prime_subset",
+        )?;
+        let g_prime_subset_proof = prove_subset(
+            &prover_first_oracles.g_prime,
+            &commitments.g_prime,
+            &rands.g_prime,
+            Table::Values,
+            b"g_prime_subset",
+        )?;
+        let s_prime_subset_proof = prove_subset(
+            &prover_first_oracles.s_prime,
+            &commitments.s_prime,
+            &rands.s_prime,
+            Table::Values,
+            b"s_prime_subset",
+        )?;
 
-        // Step 6c: Subset over K for s'
-        let s_prime_subset_proof = SubsetOverK::<F, PC, FS>::prove();
+        // Step 6d: Subset over K between s and h^2, i.e. the discrete logs of s are those encoded in h
+        let s_subset_proof = prove_subset(
+            &prover_first_oracles.s,
+            &commitments.s,
+            &rands.s,
+            Table::Squares,
+            b"s_h_subset",
+        )?;
 
         // Step 7a: Non-zero over K for f′
-        let nzk_f_prime_proof = NonZeroOverK::<F, PC, FS>::prove(
+        let nzk_f_prime_proof = NonZeroOverK::<C>::prove_with_cancellation(
             ck,
             domain_k,
             &prover_first_oracles.f_prime,
-            &commitments[1].clone(),
-            &rands[1].clone(),
+            &commitments.f_prime,
+            &rands.f_prime,
+            &context.child(b"f_prime_non_zero"),
+            cancellation,
             rng,
         )?;
 
         // Step 7b: Non-zero over K for g′
-        let nzk_g_prime_proof = NonZeroOverK::<F, PC, FS>::prove(
+        let nzk_g_prime_proof = NonZeroOverK::<C>::prove_with_cancellation(
             ck,
             domain_k,
             &prover_first_oracles.g_prime,
-            &commitments[2].clone(),
-            &rands[2].clone(),
+            &commitments.g_prime,
+            &rands.g_prime,
+            &context.child(b"g_prime_non_zero"),
+            cancellation,
             rng,
         )?;
 
         // Step 7c: Non-zero over K for s′
-        let nzk_s_prime_proof = NonZeroOverK::<F, PC, FS>::prove(
+        let nzk_s_prime_proof = NonZeroOverK::<C>::prove_with_cancellation(
             ck,
             domain_k,
             &prover_first_oracles.s_prime,
-            &commitments[3].clone(),
-            &rands[3].clone(),
+            &commitments.s_prime,
+            &rands.s_prime,
+            &context.child(b"s_prime_non_zero"),
+            cancellation,
             rng,
         )?;
 
-        // Step 7d: Non-zero over K for s(X) − 1
-        // The verifier is expected to derive a commitment to the constant one polynomial on their own
+        // Step 7d: Non-zero over K for s(X) − 1, against the commitment derived in the first round
         let s_minus_one = prover_first_oracles.s.polynomial() - one_poly.polynomial();
         let s_minus_one = LabeledPolynomial::new(
             String::from("s_minus_one"),
@@ -235,233 +831,728 @@ where
             Some(1),
         );
 
-        let (s_minus_one_commitment, s_minus_one_rand) = PC::aggregate_commitments(
-            &commitments,
-            Some(rands.to_vec()),
-            &PIOPforDLComparison::s_minus_one_linear_combination(),
-        )
-        .unwrap();
-
-        let nzk_s_minus_one_proof = NonZeroOverK::<F, PC, FS>::prove(
+        let nzk_s_minus_one_proof = NonZeroOverK::<C>::prove_with_cancellation(
             ck,
             domain_k,
             &s_minus_one,
             &s_minus_one_commitment,
             &s_minus_one_rand,
+            &context.child(b"s_minus_one_non_zero"),
+            cancellation,
             rng,
         )?;
 
-        let proof = Proof {
-            // Commitments
-            s_commit: commitments[0].commitment().clone(),
-            f_prime_commit: commitments[1].commitment().clone(),
-            g_prime_commit: commitments[2].commitment().clone(),
-            s_prime_commit: commitments[3].commitment().clone(),
-            h_commit: commitments[4].commitment().clone(),
-
-            // Proofs
-            f_prime_square_proof,
-            g_prime_square_proof,
-            s_prime_square_proof,
-            f_prime_product_proof,
+        Ok(SharedProofs {
+            commitments: commitments.map(|_, commitment| commitment.commitment().clone()),
+            h_eval,
+            h_opening_proof,
             h_proof,
             f_prime_subset_proof,
             g_prime_subset_proof,
             s_prime_subset_proof,
+            s_subset_proof,
             nzk_f_prime_proof,
             nzk_g_prime_proof,
             nzk_s_prime_proof,
             nzk_s_minus_one_proof,
-        };
-
-        Ok(proof)
+        })
     }
 
-    pub fn verify(
-        vk: &PC::VerifierKey,
-        ck: &PC::CommitterKey,
-        domain_k: &GeneralEvaluationDomain<F>,
-        domain_h: &GeneralEvaluationDomain<F>,
-        f_commit: &LabeledCommitment<PC::Commitment>,
-        g_commit: &LabeledCommitment<PC::Commitment>,
+    pub fn verify<B: DiscreteLogBase<C::F>>(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
         enforced_degree_bound: Option<usize>,
-        proof: Proof<F, PC>,
-        fs_rng: &mut FS,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
     ) -> Result<(), Error> {
-        // re-label f and g with the enforced degree bound
-        let f_commit = LabeledCommitment::new(
-            f_commit.label().clone(),
-            f_commit.commitment().clone(),
+        Self::verify_with_report(
+            vk,
+            ck,
+            domain_k,
+            base,
+            f_commit,
+            g_commit,
             enforced_degree_bound,
-        );
-        let g_commit = LabeledCommitment::new(
-            g_commit.label().clone(),
-            g_commit.commitment().clone(),
+            proof,
+            context,
+            fs_rng,
+        )
+        .into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report<B: DiscreteLogBase<C::F>>(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("discrete_log_comparison::verify");
+        let mut report = VerificationReport::builder("discrete_log_comparison");
+
+        let shared = SharedProofs {
+            commitments: OracleSet {
+                s: proof.s_commit,
+                f_prime: proof.f_prime_commit,
+                g_prime: proof.g_prime_commit,
+                s_prime: proof.s_prime_commit,
+                h: proof.h_commit,
+            },
+            h_eval: proof.h_eval,
+            h_opening_proof: proof.h_opening_proof,
+            h_proof: proof.h_proof,
+            f_prime_subset_proof: proof.f_prime_subset_proof,
+            g_prime_subset_proof: proof.g_prime_subset_proof,
+            s_prime_subset_proof: proof.s_prime_subset_proof,
+            s_subset_proof: proof.s_subset_proof,
+            nzk_f_prime_proof: proof.nzk_f_prime_proof,
+            nzk_g_prime_proof: proof.nzk_g_prime_proof,
+            nzk_s_prime_proof: proof.nzk_s_prime_proof,
+            nzk_s_minus_one_proof: proof.nzk_s_minus_one_proof,
+        };
+        let (f_commit, g_commit, commitments) = Self::verify_first_round(
+            &mut report,
+            vk,
+            ck,
+            f_commit,
+            g_commit,
+            &shared,
             enforced_degree_bound,
+            context,
+            fs_rng,
         );
 
-        let commitments = vec![
-            LabeledCommitment::new(String::from("s"), proof.s_commit, enforced_degree_bound),
-            LabeledCommitment::new(
-                String::from("f_prime"),
-                proof.f_prime_commit,
+        let alphas = [C::F::one(), C::F::one()];
+        let square_check_vo = GenericShiftingVO::new(&[0, 1], &alphas, square_check)
+            .unwrap()
+            .with_scaling_factor(2);
+
+        // Zero over K for f_prime
+        report.check("f_prime_square", || {
+            ZeroOverK::<C>::verify(
+                proof.f_prime_square_proof,
+                &[f_commit.clone(), commitments.f_prime.clone()],
                 enforced_degree_bound,
-            ),
-            LabeledCommitment::new(
-                String::from("g_prime"),
-                proof.g_prime_commit,
+                &square_check_vo,
+                &domain_k,
+                vk,
+                &context.child(b"f_prime_square"),
+            )
+        });
+
+        // Zero over K for g_prime
+        report.check("g_prime_square", || {
+            ZeroOverK::<C>::verify(
+                proof.g_prime_square_proof,
+                &[g_commit.clone(), commitments.g_prime.clone()],
                 enforced_degree_bound,
-            ),
-            LabeledCommitment::new(
-                String::from("s_prime"),
-                proof.s_prime_commit,
+                &square_check_vo,
+                &domain_k,
+                vk,
+                &context.child(b"g_prime_square"),
+            )
+        });
+
+        // Zero over K for s_prime
+        report.check("s_prime_square", || {
+            ZeroOverK::<C>::verify(
+                proof.s_prime_square_proof,
+                &[commitments.s.clone(), commitments.s_prime.clone()],
                 enforced_degree_bound,
-            ),
-            LabeledCommitment::new(String::from("h"), proof.h_commit, enforced_degree_bound),
-        ];
+                &square_check_vo,
+                &domain_k,
+                vk,
+                &context.child(b"s_prime_square"),
+            )
+        });
 
-        let fs_bytes =
-            &to_bytes![Self::PROTOCOL_NAME, commitments].map_err(|_| Error::ToBytesError)?;
-        fs_rng.absorb(fs_bytes);
+        let product_check_vo = GenericShiftingVO::new(
+            &[0, 1, 2],
+            &vec![C::F::one(); 3],
+            presets::abc_product_check,
+        )
+        .unwrap()
+        .with_scaling_factor(2);
 
-        let alphas = [F::one(), F::one()];
-        let square_check_vo = GenericShiftingVO::new(&[0, 1], &alphas, square_check)?;
+        // Zero over K for f' = (s')*(g')
+        report.check("f_prime_product", || {
+            ZeroOverK::<C>::verify(
+                proof.f_prime_product_proof,
+                &[
+                    commitments.f_prime.clone(),
+                    commitments.s_prime.clone(),
+                    commitments.g_prime.clone(),
+                ],
+                enforced_degree_bound,
+                &product_check_vo,
+                &domain_k,
+                vk,
+                &context.child(b"f_prime_product"),
+            )
+        });
 
-        // Zero over K for f_prime
-        ZeroOverK::<F, PC, FS>::verify(
-            proof.f_prime_square_proof,
-            &[f_commit.clone(), commitments[1].clone()],
-            enforced_degree_bound,
-            &square_check_vo,
-            &domain_k,
+        Self::verify_shared(
+            &mut report,
             vk,
-        )?;
-
-        // Zero over K for g_prime
-        ZeroOverK::<F, PC, FS>::verify(
-            proof.g_prime_square_proof,
-            &[g_commit.clone(), commitments[2].clone()],
+            ck,
+            domain_k,
+            base,
+            &commitments,
             enforced_degree_bound,
-            &square_check_vo,
-            &domain_k,
-            vk,
-        )?;
+            shared,
+            context,
+            fs_rng,
+        );
 
-        // Zero over K for s_prime
-        ZeroOverK::<F, PC, FS>::verify(
-            proof.s_prime_square_proof,
-            &[commitments[0].clone(), commitments[3].clone()],
+        report.finish()
+    }
+
+    /// Verify a proof created with [`Self::prove_v2`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_v2<B: DiscreteLogBase<C::F>>(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: ProofV2<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_v2_with_report(
+            vk,
+            ck,
+            domain_k,
+            base,
+            f_commit,
+            g_commit,
             enforced_degree_bound,
-            &square_check_vo,
-            &domain_k,
+            proof,
+            context,
+            fs_rng,
+        )
+        .into_result()
+    }
+
+    /// Verify every sub-protocol of a proof created with [`Self::prove_v2`] and report on each of them
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_v2_with_report<B: DiscreteLogBase<C::F>>(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: ProofV2<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("discrete_log_comparison::verify_v2");
+        let mut report = VerificationReport::builder("discrete_log_comparison_v2");
+
+        let shared = SharedProofs {
+            commitments: OracleSet {
+                s: proof.s_commit,
+                f_prime: proof.f_prime_commit,
+                g_prime: proof.g_prime_commit,
+                s_prime: proof.s_prime_commit,
+                h: proof.h_commit,
+            },
+            h_eval: proof.h_eval,
+            h_opening_proof: proof.h_opening_proof,
+            h_proof: proof.h_proof,
+            f_prime_subset_proof: proof.f_prime_subset_proof,
+            g_prime_subset_proof: proof.g_prime_subset_proof,
+            s_prime_subset_proof: proof.s_prime_subset_proof,
+            s_subset_proof: proof.s_subset_proof,
+            nzk_f_prime_proof: proof.nzk_f_prime_proof,
+            nzk_g_prime_proof: proof.nzk_g_prime_proof,
+            nzk_s_prime_proof: proof.nzk_s_prime_proof,
+            nzk_s_minus_one_proof: proof.nzk_s_minus_one_proof,
+        };
+        let (f_commit, g_commit, commitments) = Self::verify_first_round(
+            &mut report,
             vk,
-        )?;
+            ck,
+            f_commit,
+            g_commit,
+            &shared,
+            enforced_degree_bound,
+            context,
+            fs_rng,
+        );
 
-        let product_check_vo =
-            GenericShiftingVO::new(&[0, 1, 2], &vec![F::one(); 3], presets::abc_product_check)?;
+        // Zero over K for f = (f')^2, g = (g')^2, s = (s')^2 and f' = (s')*(g') at once
+        report.check("square_and_product", || {
+            let challenge = Self::combination_challenge(fs_rng);
+            let combined_check_vo = GenericShiftingVO::new(
+                &[0, 1, 2, 3, 4, 5],
+                &[C::F::one(); 6],
+                square_and_product_check!(challenge),
+            )?
+            .with_scaling_factor(2);
 
-        // Zero over K for f' = (s')*(g')
-        ZeroOverK::<F, PC, FS>::verify(
-            proof.f_prime_product_proof,
-            &[
-                commitments[1].clone(),
-                commitments[3].clone(),
-                commitments[2].clone(),
-            ],
+            ZeroOverK::<C>::verify(
+                proof.square_and_product_proof,
+                &[
+                    f_commit.clone(),
+                    commitments.f_prime.clone(),
+                    g_commit.clone(),
+                    commitments.g_prime.clone(),
+                    commitments.s.clone(),
+                    commitments.s_prime.clone(),
+                ],
+                enforced_degree_bound,
+                &combined_check_vo,
+                domain_k,
+                vk,
+                &context.child(b"square_and_product"),
+            )
+        });
+
+        Self::verify_shared(
+            &mut report,
+            vk,
+            ck,
+            domain_k,
+            base,
+            &commitments,
             enforced_degree_bound,
-            &product_check_vo,
-            &domain_k,
+            shared,
+            context,
+            fs_rng,
+        );
+
+        report.finish()
+    }
+
+    /// Verify a proof of any version with the verifier of its version, so that proofs created before a change of
+    /// format stay verifiable
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_versioned<B: DiscreteLogBase<C::F>>(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: VersionedProof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_versioned_with_report(
             vk,
-        )?;
+            ck,
+            domain_k,
+            base,
+            f_commit,
+            g_commit,
+            enforced_degree_bound,
+            proof,
+            context,
+            fs_rng,
+        )
+        .into_result()
+    }
 
-        // Geometric Sequence Test for h
-        let omega: F = domain_h.element(1);
-        let delta_r = omega.sqrt();
-        if delta_r.is_none() {
-            return Err(Error::OmegaSqrtError);
+    /// Verify every sub-protocol of a proof of any version and report on each of them
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_versioned_with_report<B: DiscreteLogBase<C::F>>(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: VersionedProof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        match proof {
+            VersionedProof::V1(proof) => Self::verify_with_report(
+                vk,
+                ck,
+                domain_k,
+                base,
+                f_commit,
+                g_commit,
+                enforced_degree_bound,
+                proof,
+                context,
+                fs_rng,
+            ),
+            VersionedProof::V2(proof) => Self::verify_v2_with_report(
+                vk,
+                ck,
+                domain_k,
+                base,
+                f_commit,
+                g_commit,
+                enforced_degree_bound,
+                proof,
+                context,
+                fs_rng,
+            ),
         }
+    }
 
-        let delta = delta_r.unwrap();
+    /// Absorb the commitments of the prover and check the opening of h. Returns f, g and the commitments of the
+    /// prover labeled with the enforced degree bound.
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    fn verify_first_round(
+        report: &mut ReportBuilder,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        shared: &SharedProofs<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> (
+        LabeledCommitment<pc::Commitment<C>>,
+        LabeledCommitment<pc::Commitment<C>>,
+        OracleSet<LabeledCommitment<pc::Commitment<C>>>,
+    ) {
+        // re-label f and g with the enforced degree bound
+        let f_commit = LabeledCommitment::new(
+            f_commit.label().clone(),
+            f_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
+        let g_commit = LabeledCommitment::new(
+            g_commit.label().clone(),
+            g_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
 
-        let mut a_s = vec![F::one()];
-        let mut c_s = vec![domain_h.size()];
+        let commitments = shared.commitments.clone().map(|label, commitment| {
+            LabeledCommitment::new(String::from(label), commitment, enforced_degree_bound)
+        });
 
-        let to_pad = domain_k.size() - domain_h.size();
-        if to_pad > 0 {
-            a_s.push(F::zero());
-            c_s.push(to_pad);
-        }
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![Self::PROTOCOL_NAME, context, f_commit, g_commit]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
 
-        GeoSeqTest::<F, PC, FS>::verify(
-            delta,
-            &mut a_s,
-            &mut c_s,
-            &domain_k,
-            &commitments[4],
-            enforced_degree_bound,
-            proof.h_proof,
-            &vk,
-        )?;
+            let fs_bytes = &to_bytes![commitments.to_vec()].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
 
-        // Subset over K for f'
-        SubsetOverK::<F, PC, FS>::verify(proof.f_prime_subset_proof)?;
+            let s_minus_one_commitment = Self::s_minus_one_commitment(
+                ck,
+                commitments.s.commitment(),
+                enforced_degree_bound,
+            )?;
+            Self::absorb_s_minus_one(&s_minus_one_commitment, fs_rng)
+        });
 
-        // Subset over K for g'
-        SubsetOverK::<F, PC, FS>::verify(proof.g_prime_subset_proof)?;
+        // Degree bound of h
+        report.check("h_degree_bound", || {
+            let h_point: C::F =
+                ChallengeSet::new(&mut *fs_rng, b"h_degree_bound").challenge(b"h_point");
+            match C::PC::check(
+                vk,
+                [&commitments.h],
+                &h_point,
+                vec![shared.h_eval],
+                &shared.h_opening_proof,
+                C::F::one(),
+                None,
+            ) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(Error::BatchCheckError),
+                Err(e) => Err(to_pc_error::<C::F, C::PC>(e)),
+            }
+        });
 
-        // Subset over K for s'
-        SubsetOverK::<F, PC, FS>::verify(proof.s_prime_subset_proof)?;
+        (f_commit, g_commit, commitments)
+    }
 
-        // Non-zero over K for f′
-        NonZeroOverK::<F, PC, FS>::verify(
-            &vk,
-            &domain_k,
-            commitments[1].commitment().clone(),
-            enforced_degree_bound,
-            proof.nzk_f_prime_proof,
-        )?;
+    /// Advance `fs_rng` as verifying `proof` would, without checking anything. For proofs verified elsewhere, so that
+    /// the challenges of the proofs that follow them in the transcript are still those of the prover.
+    pub(crate) fn replay_transcript(
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        proof: &Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        let fs_bytes = &to_bytes![Self::PROTOCOL_NAME, context, f_commit, g_commit]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
 
-        // Non-zero over K for g′
-        NonZeroOverK::<F, PC, FS>::verify(
-            &vk,
-            &domain_k,
-            commitments[2].commitment().clone(),
-            enforced_degree_bound,
-            proof.nzk_g_prime_proof,
-        )?;
+        let commitments = vec![
+            &proof.s_commit,
+            &proof.f_prime_commit,
+            &proof.g_prime_commit,
+            &proof.s_prime_commit,
+            &proof.h_commit,
+        ];
+        let fs_bytes = &to_bytes![commitments].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
 
-        // Non-zero over K for s′
-        NonZeroOverK::<F, PC, FS>::verify(
-            &vk,
-            &domain_k,
-            commitments[3].commitment().clone(),
-            enforced_degree_bound,
-            proof.nzk_s_prime_proof,
-        )?;
+        let s_minus_one_commitment =
+            Self::s_minus_one_commitment(ck, &proof.s_commit, f_commit.degree_bound())?;
+        Self::absorb_s_minus_one(&s_minus_one_commitment, fs_rng)?;
 
-        // Non-zero over K for s(X) − 1
+        let _: C::F = ChallengeSet::new(&mut *fs_rng, b"h_degree_bound").challenge(b"h_point");
+
+        let enforced_degree_bound = f_commit.degree_bound();
+        let commitment = |label: &str, commitment: &pc::Commitment<C>| {
+            LabeledCommitment::new(
+                String::from(label),
+                commitment.clone(),
+                enforced_degree_bound,
+            )
+        };
+        let h_commit = commitment(OracleSet::LABELS.h, &proof.h_commit);
+        for (label, f_commit, table, subset_proof) in [
+            (
+                "f_prime_subset",
+                commitment(OracleSet::LABELS.f_prime, &proof.f_prime_commit),
+                Table::Values,
+                &proof.f_prime_subset_proof,
+            ),
+            (
+                "g_prime_subset",
+                commitment(OracleSet::LABELS.g_prime, &proof.g_prime_commit),
+                Table::Values,
+                &proof.g_prime_subset_proof,
+            ),
+            (
+                "s_prime_subset",
+                commitment(OracleSet::LABELS.s_prime, &proof.s_prime_commit),
+                Table::Values,
+                &proof.s_prime_subset_proof,
+            ),
+            (
+                "s_h_subset",
+                commitment(OracleSet::LABELS.s, &proof.s_commit),
+                Table::Squares,
+                &proof.s_subset_proof,
+            ),
+        ] {
+            SubsetOverK::<C>::replay_transcript(
+                domain_k,
+                &f_commit,
+                &h_commit,
+                table,
+                enforced_degree_bound,
+                subset_proof,
+                &context.child(label.as_bytes()),
+                fs_rng,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The commitment to s - 1 the verifier derives from the commitment to s and its own commitment to one
+    fn s_minus_one_commitment(
+        ck: &pc::CommitterKey<C>,
+        s_commit: &pc::Commitment<C>,
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<pc::Commitment<C>, Error> {
         let one_poly = LabeledPolynomial::new(
             String::from("one"),
-            DensePolynomial::from_coefficients_slice(&[F::one()]),
+            DensePolynomial::from_coefficients_slice(&[C::F::one()]),
             enforced_degree_bound,
             None,
         );
-        let (commit_to_one, _) = PC::commit(ck, &[one_poly], None).map_err(to_pc_error::<F, PC>)?;
+        let (commit_to_one, _) =
+            C::PC::commit(ck, &[one_poly], None).map_err(to_pc_error::<C::F, C::PC>)?;
 
-        let (s_minus_one_commitment, _) = PC::aggregate_commitments(
-            &[commitments[0].clone(), commit_to_one[0].clone()],
-            None,
-            &PIOPforDLComparison::s_minus_one_linear_combination(),
-        )?;
-
-        NonZeroOverK::<F, PC, FS>::verify(
-            &vk,
-            &domain_k,
-            s_minus_one_commitment.commitment().clone(),
-            enforced_degree_bound,
-            proof.nzk_s_minus_one_proof,
-        )?;
+        Ok(C::PC::sub(s_commit, commit_to_one[0].commitment())?)
+    }
 
+    fn absorb_s_minus_one(
+        s_minus_one_commitment: &pc::Commitment<C>,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        let fs_bytes =
+            &to_bytes![b"s_minus_one", s_minus_one_commitment].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
         Ok(())
     }
+
+    /// Verify the sub-proofs that follow the square and product checks in both versions of the protocol
+    #[allow(clippy::too_many_arguments)]
+    fn verify_shared<B: DiscreteLogBase<C::F>>(
+        report: &mut ReportBuilder,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        base: &B,
+        commitments: &OracleSet<LabeledCommitment<pc::Commitment<C>>>,
+        enforced_degree_bound: Option<usize>,
+        proof: SharedProofs<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) {
+        // Geometric Sequence Test for h
+        report.check("h_geo_seq", || {
+            let delta = PIOPforDLComparison::delta(base)?;
+
+            let (a_s, c_s) = PIOPforDLComparison::h_sequence(base, domain_k)?;
+
+            GeoSeqTest::<C>::verify(
+                delta,
+                &a_s,
+                &c_s,
+                &domain_k,
+                &commitments.h,
+                enforced_degree_bound,
+                proof.h_proof,
+                &vk,
+                &context.child(b"h_geo_seq"),
+            )
+        });
+
+        // Subset over K between f', g', s' and h, and between s and h^2
+        for (label, f_commit, table, subset_proof) in [
+            (
+                "f_prime_subset",
+                &commitments.f_prime,
+                Table::Values,
+                proof.f_prime_subset_proof,
+            ),
+            (
+                "g_prime_subset",
+                &commitments.g_prime,
+                Table::Values,
+                proof.g_prime_subset_proof,
+            ),
+            (
+                "s_prime_subset",
+                &commitments.s_prime,
+                Table::Values,
+                proof.s_prime_subset_proof,
+            ),
+            (
+                "s_h_subset",
+                &commitments.s,
+                Table::Squares,
+                proof.s_subset_proof,
+            ),
+        ] {
+            report.check(label, || {
+                SubsetOverK::<C>::verify(
+                    vk,
+                    domain_k,
+                    f_commit,
+                    &commitments.h,
+                    table,
+                    enforced_degree_bound,
+                    subset_proof,
+                    &context.child(label.as_bytes()),
+                    fs_rng,
+                )
+            });
+        }
+
+        // Non-zero over K for f′
+        report.check("f_prime_non_zero", || {
+            NonZeroOverK::<C>::verify(
+                &vk,
+                &domain_k,
+                commitments.f_prime.commitment().clone(),
+                enforced_degree_bound,
+                proof.nzk_f_prime_proof,
+                &context.child(b"f_prime_non_zero"),
+            )
+        });
+
+        // Non-zero over K for g′
+        report.check("g_prime_non_zero", || {
+            NonZeroOverK::<C>::verify(
+                &vk,
+                &domain_k,
+                commitments.g_prime.commitment().clone(),
+                enforced_degree_bound,
+                proof.nzk_g_prime_proof,
+                &context.child(b"g_prime_non_zero"),
+            )
+        });
+
+        // Non-zero over K for s′
+        report.check("s_prime_non_zero", || {
+            NonZeroOverK::<C>::verify(
+                &vk,
+                &domain_k,
+                commitments.s_prime.commitment().clone(),
+                enforced_degree_bound,
+                proof.nzk_s_prime_proof,
+                &context.child(b"s_prime_non_zero"),
+            )
+        });
+
+        // Non-zero over K for s(X) − 1
+        report.check("s_minus_one_non_zero", || {
+            let s_minus_one_commitment = Self::s_minus_one_commitment(
+                ck,
+                commitments.s.commitment(),
+                enforced_degree_bound,
+            )?;
+
+            NonZeroOverK::<C>::verify(
+                &vk,
+                &domain_k,
+                s_minus_one_commitment,
+                enforced_degree_bound,
+                proof.nzk_s_minus_one_proof,
+                &context.child(b"s_minus_one_non_zero"),
+            )
+        });
+    }
+}
+
+/// The prover's commitments to s, f', g', s' and h and the opening of h, along with what the sub-proofs that follow
+/// need from its first round
+#[cfg(feature = "prover")]
+struct FirstRound<'a, C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    oracles: ProverFirstOracles<C::F>,
+    state: ProverState<'a, C::F>,
+    commitments: OracleSet<LabeledCommitment<pc::Commitment<C>>>,
+    rands: OracleSet<pc::Randomness<C>>,
+    one_poly: LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    s_minus_one_commitment: LabeledCommitment<pc::Commitment<C>>,
+    s_minus_one_rand: pc::Randomness<C>,
+    h_eval: C::F,
+    h_opening_proof: pc::Proof<C>,
+}
+
+/// The parts of a proof that both versions of the protocol share
+struct SharedProofs<C: ProtocolConfig> {
+    commitments: OracleSet<pc::Commitment<C>>,
+    h_eval: C::F,
+    h_opening_proof: pc::Proof<C>,
+    h_proof: GeoProof<C>,
+    f_prime_subset_proof: SubsetProof<C>,
+    g_prime_subset_proof: SubsetProof<C>,
+    s_prime_subset_proof: SubsetProof<C>,
+    s_subset_proof: SubsetProof<C>,
+    nzk_f_prime_proof: NonZeroProof<C>,
+    nzk_g_prime_proof: NonZeroProof<C>,
+    nzk_s_prime_proof: NonZeroProof<C>,
+    nzk_s_minus_one_proof: NonZeroProof<C>,
 }
@@ -1,7 +1,16 @@
+use crate::discrete_log_comparison::base::DiscreteLogBase;
+use crate::domains::DomainK;
+use crate::error::{Error, WitnessError};
+use crate::util::padded_sequence;
 use ark_ff::{PrimeField, SquareRootField};
-use ark_poly_commit::LinearCombination;
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+use ark_poly_commit::{LabeledPolynomial, LinearCombination};
 use ark_std::marker::PhantomData;
+use oracles::OracleSet;
+use std::collections::BTreeMap;
 
+pub mod oracles;
+#[cfg(feature = "prover")]
 pub mod prover;
 
 pub struct PIOPforDLComparison<F: PrimeField + SquareRootField> {
@@ -10,6 +19,71 @@ pub struct PIOPforDLComparison<F: PrimeField + SquareRootField> {
 
 impl<F: PrimeField + SquareRootField> PIOPforDLComparison<F> {
     pub fn s_minus_one_linear_combination() -> LinearCombination<F> {
-        LinearCombination::new("s_minus_one", vec![(F::one(), "s"), (-F::one(), "one")])
+        LinearCombination::new(
+            "s_minus_one",
+            vec![(F::one(), OracleSet::LABELS.s), (-F::one(), "one")],
+        )
+    }
+
+    /// The square root delta of the base g of order 2n, for n the order of g, so that products of two powers of delta
+    /// below n never wrap around. For an even n, g must be a quadratic residue and both of its square roots have order
+    /// 2n. For an odd n, g always has a square root of order n, and its negation -delta has order 2n.
+    pub fn delta<B: DiscreteLogBase<F> + ?Sized>(base: &B) -> Result<F, Error> {
+        let delta = base.generator().sqrt().ok_or(Error::OmegaSqrtError)?;
+        if delta.pow([base.order() as u64]).is_one() {
+            Ok(-delta)
+        } else {
+            Ok(delta)
+        }
+    }
+
+    /// The discrete logs, in the given base, of the evaluations of an oracle over K
+    pub fn exponents<B: DiscreteLogBase<F> + ?Sized>(
+        domain_k: &DomainK<F>,
+        base: &B,
+        oracle: &LabeledPolynomial<F, DensePolynomial<F>>,
+    ) -> Result<Vec<usize>, Error> {
+        Self::exponents_of_evals(
+            base,
+            oracle.label(),
+            &domain_k.fft(&oracle.polynomial().coeffs),
+        )
+    }
+
+    /// The discrete logs, in the given base, of the evaluations over K of the oracle labeled `label`
+    pub fn exponents_of_evals<B: DiscreteLogBase<F> + ?Sized>(
+        base: &B,
+        label: &str,
+        evals: &[F],
+    ) -> Result<Vec<usize>, Error> {
+        let omega_powers_mapping = base
+            .powers()
+            .into_iter()
+            .enumerate()
+            .map(|(power, omega)| (omega, power))
+            .collect::<BTreeMap<_, _>>();
+
+        evals
+            .iter()
+            .enumerate()
+            .map(|(index, eval)| {
+                omega_powers_mapping.get(eval).copied().ok_or_else(|| {
+                    Error::InvalidWitness(WitnessError::NotInDomain {
+                        oracle: String::from(label),
+                        index,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Initial values and lengths of the geometric sequence h over K: powers of delta on the first n elements, for n
+    /// the order of the base, followed by zeros on the remaining |K| - n elements. Fails when K is smaller than n,
+    /// e.g. smaller than H.
+    pub fn h_sequence<B: DiscreteLogBase<F> + ?Sized>(
+        base: &B,
+        domain_k: &DomainK<F>,
+    ) -> Result<(Vec<F>, Vec<usize>), Error> {
+        padded_sequence(F::one(), base.order(), F::zero, domain_k)
     }
 }
@@ -0,0 +1,93 @@
+use std::array;
+
+/// One item per oracle sent by the prover in the first round of the discrete-log comparison: the polynomials
+/// themselves, their commitments, randomness or labels. Items are looked up by name, and iterated in the stable order
+/// s, f', g', s', h which is the order they are committed to and absorbed in the transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OracleSet<T> {
+    pub s: T,
+    pub f_prime: T,
+    pub g_prime: T,
+    pub s_prime: T,
+    pub h: T,
+}
+
+impl OracleSet<&'static str> {
+    /// The labels of the oracles, shared by the prover and the verifier
+    pub const LABELS: Self = Self {
+        s: "s",
+        f_prime: "f_prime",
+        g_prime: "g_prime",
+        s_prime: "s_prime",
+        h: "h",
+    };
+}
+
+impl<T> OracleSet<T> {
+    /// Build a set from exactly five items given in iteration order
+    pub fn from_ordered(items: impl IntoIterator<Item = T>) -> Option<Self> {
+        let mut items = items.into_iter();
+        let set = Self {
+            s: items.next()?,
+            f_prime: items.next()?,
+            g_prime: items.next()?,
+            s_prime: items.next()?,
+            h: items.next()?,
+        };
+        match items.next() {
+            Some(_) => None,
+            None => Some(set),
+        }
+    }
+
+    /// Transform each item, given along with the label of its oracle
+    pub fn map<U>(self, mut f: impl FnMut(&'static str, T) -> U) -> OracleSet<U> {
+        let labels = OracleSet::LABELS;
+        OracleSet {
+            s: f(labels.s, self.s),
+            f_prime: f(labels.f_prime, self.f_prime),
+            g_prime: f(labels.g_prime, self.g_prime),
+            s_prime: f(labels.s_prime, self.s_prime),
+            h: f(labels.h, self.h),
+        }
+    }
+
+    pub fn as_ref(&self) -> OracleSet<&T> {
+        OracleSet {
+            s: &self.s,
+            f_prime: &self.f_prime,
+            g_prime: &self.g_prime,
+            s_prime: &self.s_prime,
+            h: &self.h,
+        }
+    }
+
+    pub fn iter(&self) -> array::IntoIter<&T, 5> {
+        self.as_ref().into_iter()
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> IntoIterator for OracleSet<T> {
+    type Item = T;
+    type IntoIter = array::IntoIter<T, 5>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        [self.s, self.f_prime, self.g_prime, self.s_prime, self.h].into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a OracleSet<T> {
+    type Item = &'a T;
+    type IntoIter = array::IntoIter<&'a T, 5>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -1,18 +1,24 @@
-use crate::discrete_log_comparison::piop::PIOPforDLComparison;
+use crate::discrete_log_comparison::{
+    base::DiscreteLogBase,
+    piop::{oracles::OracleSet, PIOPforDLComparison},
+};
+use crate::domains::DomainK;
 use crate::error::Error;
 use crate::util::*;
 use ark_ff::{PrimeField, SquareRootField};
 use ark_marlin::ahp::prover::ProverMsg;
-use ark_poly::{
-    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
-};
+use ark_poly::univariate::DensePolynomial;
 use ark_poly_commit::LabeledPolynomial;
 use ark_std::rand::Rng;
+use zero_over_k::{
+    cancellation::{checkpoint, CancellationToken},
+    util::{batch_fft, batch_ifft},
+};
 
 pub struct ProverState<'a, F: PrimeField + SquareRootField> {
-    domain_k: &'a GeneralEvaluationDomain<F>,
+    domain_k: &'a DomainK<F>,
 
-    domain_h: &'a GeneralEvaluationDomain<F>,
+    base: &'a dyn DiscreteLogBase<F>,
 
     f: &'a LabeledPolynomial<F, DensePolynomial<F>>,
 
@@ -20,6 +26,8 @@ pub struct ProverState<'a, F: PrimeField + SquareRootField> {
 
     enforced_degree_bound: Option<usize>,
 
+    cancellation: Option<&'a CancellationToken>,
+
     first_oracles: Option<ProverFirstOracles<F>>,
 
     pub a_s: Option<Vec<F>>,
@@ -30,47 +38,25 @@ pub struct ProverState<'a, F: PrimeField + SquareRootField> {
 }
 
 /// The first set of prover oracles
-#[derive(Clone)]
-pub struct ProverFirstOracles<F: PrimeField + SquareRootField> {
-    pub s: LabeledPolynomial<F, DensePolynomial<F>>,
-
-    pub f_prime: LabeledPolynomial<F, DensePolynomial<F>>,
-
-    pub g_prime: LabeledPolynomial<F, DensePolynomial<F>>,
-
-    pub s_prime: LabeledPolynomial<F, DensePolynomial<F>>,
-
-    pub h: LabeledPolynomial<F, DensePolynomial<F>>,
-}
-
-impl<F: PrimeField + SquareRootField> ProverFirstOracles<F> {
-    pub fn iter(&self) -> impl Iterator<Item = &LabeledPolynomial<F, DensePolynomial<F>>> {
-        vec![
-            &self.s,
-            &self.f_prime,
-            &self.g_prime,
-            &self.s_prime,
-            &self.h,
-        ]
-        .into_iter()
-    }
-}
+pub type ProverFirstOracles<F> = OracleSet<LabeledPolynomial<F, DensePolynomial<F>>>;
 
 #[allow(dead_code)]
 impl<F: PrimeField + SquareRootField> PIOPforDLComparison<F> {
     pub fn prover_init<'a>(
-        domain_k: &'a GeneralEvaluationDomain<F>,
-        domain_h: &'a GeneralEvaluationDomain<F>,
+        domain_k: &'a DomainK<F>,
+        base: &'a dyn DiscreteLogBase<F>,
         f: &'a LabeledPolynomial<F, DensePolynomial<F>>,
         g: &'a LabeledPolynomial<F, DensePolynomial<F>>,
         enforced_degree_bound: Option<usize>,
+        cancellation: Option<&'a CancellationToken>,
     ) -> Result<ProverState<'a, F>, Error> {
         Ok(ProverState {
             domain_k,
-            domain_h,
+            base,
             f,
             g,
             enforced_degree_bound,
+            cancellation,
             first_oracles: None,
             a_s: None,
             c_s: None,
@@ -82,103 +68,71 @@ impl<F: PrimeField + SquareRootField> PIOPforDLComparison<F> {
         mut state: ProverState<'a, F>,
         _rng: &mut R,
     ) -> Result<(ProverMsg<F>, ProverFirstOracles<F>, ProverState<'a, F>), Error> {
-        let m = state.domain_k.size();
-        let n = state.domain_h.size();
-        let delta = state.domain_h.element(1).sqrt().unwrap();
-
-        // Compute s
-        let f_evals = state.domain_k.fft(state.f.polynomial().coeffs());
-        let g_evals = state.domain_k.fft(state.g.polynomial().coeffs());
+        let delta = Self::delta(state.base)?;
+
+        // Evaluate f and g over K in one batch and locate them among the powers of the base before any division, so
+        // that other values fail with a precise error
+        checkpoint(state.cancellation, "discrete_log_comparison::fft")?;
+        let evals = batch_fft(
+            state.domain_k,
+            &[state.f.polynomial(), state.g.polynomial()],
+        );
+        let (f_evals, g_evals) = (&evals[0], &evals[1]);
+        let f_exponents = Self::exponents_of_evals(state.base, state.f.label(), f_evals)?;
+        let g_exponents = Self::exponents_of_evals(state.base, state.g.label(), g_evals)?;
 
+        // Compute the evaluations of s
         let s_evals: Vec<F> = f_evals
             .iter()
             .zip(g_evals.iter())
             .map(|(&f_eval, g_eval)| f_eval * g_eval.inverse().unwrap())
             .collect();
 
-        let s = DensePolynomial::<F>::from_coefficients_slice(&state.domain_k.ifft(&s_evals));
-        let s = LabeledPolynomial::new(String::from("s"), s, state.enforced_degree_bound, Some(1));
-
-        // For b in {f, g, s}, compute b_prime
-        let omegas = state.domain_h.elements();
-        let omega_powers_mapping = omegas
-            .enumerate()
-            .map(|(power, omega)| (omega, power))
-            .collect::<std::collections::HashMap<_, _>>();
-
-        let f_prime_evals = f_evals
+        // For b in {f, g, s}, compute the evaluations of b_prime. Since f and g are powers of the base, so is s, and
+        // its discrete log is the difference of those of f and g
+        let n = state.base.order();
+        let s_exponents = f_exponents
             .iter()
-            .map(|f| {
-                let power = omega_powers_mapping.get(f).expect("F evals are wrong");
-                delta.pow(&[(*power) as u64])
-            })
+            .zip(g_exponents.iter())
+            .map(|(f_exponent, g_exponent)| (f_exponent + n - g_exponent) % n)
             .collect::<Vec<_>>();
-
-        let f_prime =
-            DensePolynomial::<F>::from_coefficients_slice(&state.domain_k.ifft(&f_prime_evals));
-        let f_prime = LabeledPolynomial::new(
-            String::from("f_prime"),
-            f_prime,
-            state.enforced_degree_bound,
-            Some(1),
-        );
-
-        let g_prime_evals = g_evals
-            .iter()
-            .map(|g| {
-                let power = omega_powers_mapping.get(g).expect("G evals are wrong");
-                delta.pow(&[(*power) as u64])
-            })
-            .collect::<Vec<_>>();
-
-        let g_prime =
-            DensePolynomial::<F>::from_coefficients_slice(&state.domain_k.ifft(&g_prime_evals));
-        let g_prime = LabeledPolynomial::new(
-            String::from("g_prime"),
-            g_prime,
-            state.enforced_degree_bound,
-            Some(1),
-        );
-
-        let s_prime_evals = s_evals
-            .iter()
-            .map(|s| {
-                let power = omega_powers_mapping.get(s).expect("S evals are wrong");
-                delta.pow(&[(*power) as u64])
-            })
-            .collect::<Vec<_>>();
-
-        let s_prime =
-            DensePolynomial::<F>::from_coefficients_slice(&state.domain_k.ifft(&s_prime_evals));
-        let s_prime = LabeledPolynomial::new(
-            String::from("s_prime"),
-            s_prime,
-            state.enforced_degree_bound,
-            Some(1),
-        );
+        let to_delta_powers = |exponents: &[usize]| {
+            exponents
+                .iter()
+                .map(|power| delta.pow(&[*power as u64]))
+                .collect::<Vec<_>>()
+        };
+        let f_prime_evals = to_delta_powers(&f_exponents);
+        let g_prime_evals = to_delta_powers(&g_exponents);
+        let s_prime_evals = to_delta_powers(&s_exponents);
 
         // Compute the sequence h
-        let mut a_s = vec![F::one()];
-        let mut c_s = vec![n];
-
-        let to_pad = m - n;
-        if to_pad > 0 {
-            a_s.push(F::zero());
-            c_s.push(to_pad);
-        }
-
+        let (a_s, c_s) = Self::h_sequence(state.base, state.domain_k)?;
         let seq = generate_sequence(delta, &a_s, &c_s);
-        let h = DensePolynomial::<F>::from_coefficients_slice(&state.domain_k.ifft(&seq));
-        let h = LabeledPolynomial::new(String::from("h"), h, state.enforced_degree_bound, Some(1));
-
-        // create ProverFirstOracles struct
-        let prover_oracles = ProverFirstOracles {
-            s,
-            f_prime,
-            g_prime,
-            s_prime,
-            h,
+
+        // Interpolate all the oracles of this round in one batch
+        checkpoint(state.cancellation, "discrete_log_comparison::ifft")?;
+        let evals = OracleSet {
+            s: s_evals,
+            f_prime: f_prime_evals,
+            g_prime: g_prime_evals,
+            s_prime: s_prime_evals,
+            h: seq,
         };
+        let polys = batch_ifft(
+            state.domain_k,
+            &evals.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        );
+        let prover_oracles = OracleSet::from_ordered(polys)
+            .expect("One polynomial per oracle")
+            .map(|label, polynomial| {
+                LabeledPolynomial::new(
+                    String::from(label),
+                    polynomial,
+                    state.enforced_degree_bound,
+                    Some(1),
+                )
+            });
 
         // Prover message
         let msg = ProverMsg::EmptyMessage;
@@ -1,33 +1,194 @@
 use crate::geo_seq::proof::Proof as GeoProof;
 use crate::non_zero_over_k::proof::Proof as NonZeroProof;
+use crate::proof_version::ProofVersion;
 use crate::subset_over_k::proof::Proof as SubsetProof;
-use ark_ff::PrimeField;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
 use zero_over_k::zero_over_k::proof::Proof as ZeroProof;
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Write};
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig> {
     // Commitments
-    pub s_commit: PC::Commitment,
-    pub f_prime_commit: PC::Commitment,
-    pub g_prime_commit: PC::Commitment,
-    pub s_prime_commit: PC::Commitment,
-    pub h_commit: PC::Commitment,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub s_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub f_prime_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub g_prime_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub s_prime_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_commit: pc::Commitment<C>,
+
+    // Evaluations
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_eval: C::F,
+
+    // Proofs
+    pub f_prime_square_proof: ZeroProof<C>,
+    pub g_prime_square_proof: ZeroProof<C>,
+    pub s_prime_square_proof: ZeroProof<C>,
+    pub f_prime_product_proof: ZeroProof<C>,
+    pub f_prime_subset_proof: SubsetProof<C>,
+    pub g_prime_subset_proof: SubsetProof<C>,
+    pub s_prime_subset_proof: SubsetProof<C>,
+    pub s_subset_proof: SubsetProof<C>,
+    pub h_proof: GeoProof<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_opening_proof: pc::Proof<C>,
+    pub nzk_f_prime_proof: NonZeroProof<C>,
+    pub nzk_g_prime_proof: NonZeroProof<C>,
+    pub nzk_s_prime_proof: NonZeroProof<C>,
+    pub nzk_s_minus_one_proof: NonZeroProof<C>,
+}
+
+/// A proof of [`super::DLComparison::prove_v2`], where a single ZeroOverK replaces the three square checks and the
+/// product check
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct ProofV2<C: ProtocolConfig> {
+    // Commitments
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub s_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub f_prime_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub g_prime_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub s_prime_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_commit: pc::Commitment<C>,
+
+    // Evaluations
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_eval: C::F,
 
     // Proofs
-    pub f_prime_square_proof: ZeroProof<F, PC>,
-    pub g_prime_square_proof: ZeroProof<F, PC>,
-    pub s_prime_square_proof: ZeroProof<F, PC>,
-    pub f_prime_product_proof: ZeroProof<F, PC>,
-    pub f_prime_subset_proof: SubsetProof,
-    pub g_prime_subset_proof: SubsetProof,
-    pub s_prime_subset_proof: SubsetProof,
-    pub h_proof: GeoProof<F, PC>,
-    pub nzk_f_prime_proof: NonZeroProof<F, PC>,
-    pub nzk_g_prime_proof: NonZeroProof<F, PC>,
-    pub nzk_s_prime_proof: NonZeroProof<F, PC>,
-    pub nzk_s_minus_one_proof: NonZeroProof<F, PC>,
+    pub square_and_product_proof: ZeroProof<C>,
+    pub f_prime_subset_proof: SubsetProof<C>,
+    pub g_prime_subset_proof: SubsetProof<C>,
+    pub s_prime_subset_proof: SubsetProof<C>,
+    pub s_subset_proof: SubsetProof<C>,
+    pub h_proof: GeoProof<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_opening_proof: pc::Proof<C>,
+    pub nzk_f_prime_proof: NonZeroProof<C>,
+    pub nzk_g_prime_proof: NonZeroProof<C>,
+    pub nzk_s_prime_proof: NonZeroProof<C>,
+    pub nzk_s_minus_one_proof: NonZeroProof<C>,
+}
+
+/// A proof of any version, serialized after its [`ProofVersion`], which [`super::DLComparison::verify_versioned`]
+/// verifies with the verifier of that version
+#[allow(clippy::large_enum_variant)]
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub enum VersionedProof<C: ProtocolConfig> {
+    V1(Proof<C>),
+    V2(ProofV2<C>),
+}
+
+impl<C: ProtocolConfig> VersionedProof<C> {
+    pub fn version(&self) -> ProofVersion {
+        match self {
+            Self::V1(_) => ProofVersion::V1,
+            Self::V2(_) => ProofVersion::V2,
+        }
+    }
+}
+
+impl<C: ProtocolConfig> From<Proof<C>> for VersionedProof<C> {
+    fn from(proof: Proof<C>) -> Self {
+        Self::V1(proof)
+    }
+}
+
+impl<C: ProtocolConfig> From<ProofV2<C>> for VersionedProof<C> {
+    fn from(proof: ProofV2<C>) -> Self {
+        Self::V2(proof)
+    }
+}
+
+impl<C: ProtocolConfig> CanonicalSerialize for VersionedProof<C> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.version().serialize(&mut writer)?;
+        match self {
+            Self::V1(proof) => proof.serialize(&mut writer),
+            Self::V2(proof) => proof.serialize(&mut writer),
+        }
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.version().serialized_size()
+            + match self {
+                Self::V1(proof) => proof.serialized_size(),
+                Self::V2(proof) => proof.serialized_size(),
+            }
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.version().serialize_uncompressed(&mut writer)?;
+        match self {
+            Self::V1(proof) => proof.serialize_uncompressed(&mut writer),
+            Self::V2(proof) => proof.serialize_uncompressed(&mut writer),
+        }
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.version().uncompressed_size()
+            + match self {
+                Self::V1(proof) => proof.uncompressed_size(),
+                Self::V2(proof) => proof.uncompressed_size(),
+            }
+    }
+}
+
+impl<C: ProtocolConfig> CanonicalDeserialize for VersionedProof<C> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        match ProofVersion::deserialize(&mut reader)? {
+            ProofVersion::V1 => Proof::deserialize(&mut reader).map(Self::V1),
+            ProofVersion::V2 => ProofV2::deserialize(&mut reader).map(Self::V2),
+        }
+    }
+
+    fn deserialize_uncompressed<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        match ProofVersion::deserialize_uncompressed(&mut reader)? {
+            ProofVersion::V1 => Proof::deserialize_uncompressed(&mut reader).map(Self::V1),
+            ProofVersion::V2 => ProofV2::deserialize_uncompressed(&mut reader).map(Self::V2),
+        }
+    }
 }
@@ -1,22 +1,89 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod tests {
+    use crate::config::Config;
     use ark_bn254::{Bn254, Fr};
-    use ark_ff::{to_bytes, Field, SquareRootField};
+    use ark_ff::{to_bytes, FftField, FftParameters, Field, One, SquareRootField};
     use ark_poly::{
         univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
     };
-    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_poly_commit::{LabeledPolynomial, PCCommitterKey, PolynomialCommitment};
     use ark_std::rand::thread_rng;
     use blake2::Blake2s;
-    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use homomorphic_poly_commit::{marlin_kzg::KZG10, AdditivelyHomomorphicPCS};
 
-    use crate::{discrete_log_comparison::DLComparison, error::Error};
-    use fiat_shamir_rng::{FiatShamirRng, SimpleHashFiatShamirRng};
+    use crate::{
+        checked_key::{CheckedCommitterKey, RequiredDegreeBounds},
+        cost_model::{ElementSizes, Protocol},
+        discrete_log_comparison::{
+            base::{DiscreteLogBase, GroupElementBase},
+            piop::{oracles::OracleSet, PIOPforDLComparison},
+            proof::Proof,
+            DLComparison,
+        },
+        domains::{DomainH, DomainK},
+        error::{Error, WitnessError},
+    };
+    use ark_serialize::CanonicalSerialize;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
     use rand_chacha::ChaChaRng;
 
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
     type F = Fr;
     type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    #[test]
+    fn test_oracle_set() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+
+        let oracle = |label: &str, exponents: &[usize]| {
+            let evals = exponents
+                .iter()
+                .map(|&exponent| domain_h.element(exponent))
+                .collect::<Vec<_>>();
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::from_coefficients_vec(domain_k.ifft(&evals)),
+                None,
+                None,
+            )
+        };
+        let f = oracle("f", &[3, 2, 1, 3, 2, 1, 3, 2]);
+        let g = oracle("g", &[0, 1, 0, 2, 0, 0, 1, 1]);
+
+        // the prover labels its oracles with the labels of the set, in iteration order
+        let state =
+            PIOPforDLComparison::prover_init(&domain_k, &domain_h, &f, &g, None, None).unwrap();
+        let (_, oracles, _) = PIOPforDLComparison::prover_first_round(state, rng).unwrap();
+        assert_eq!(
+            oracles
+                .iter()
+                .map(|p| p.label().as_str())
+                .collect::<Vec<_>>(),
+            OracleSet::LABELS.to_vec()
+        );
+        assert_eq!(
+            OracleSet::LABELS.to_vec(),
+            vec!["s", "f_prime", "g_prime", "s_prime", "h"]
+        );
+
+        let set = OracleSet::from_ordered(0..5).unwrap();
+        assert_eq!((set.f_prime, set.h), (1, 4));
+        assert_eq!(
+            set.clone().into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            set.map(|label, i| format!("{}{}", label, i)).s_prime,
+            "s_prime3"
+        );
+
+        // exactly one item per oracle
+        assert_eq!(OracleSet::from_ordered(0..4), None);
+        assert_eq!(OracleSet::from_ordered(0..6), None);
+    }
 
     #[test]
     fn test_square_root_friendly() {
@@ -53,11 +120,11 @@ mod tests {
         let enforced_degree_bound = m + 1;
         let enforced_hiding_bound = Some(1);
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
-        // For the test to pass, each value in f_evals must be less than its corresponding value in
-        // g_evals, mod n (since we use domain_h)
+        // For the test to pass, the discrete log of each value in f_evals must be strictly greater than that of
+        // its corresponding value in g_evals
 
         let f_evals = vec![
             domain_h.element(1),
@@ -104,8 +171,8 @@ mod tests {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let proof = DLComparison::<F, PC, FS>::prove(
-            &ck,
+        let proof = DLComparison::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
             &domain_k,
             &domain_h,
             &f_poly,
@@ -115,11 +182,14 @@ mod tests {
             &commitments[1],
             &rands[1],
             Some(enforced_degree_bound),
+            &Context::new(b"test"),
             &mut fs_rng,
             rng,
         )
         .unwrap();
 
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
         let res = DLComparison::verify(
             &vk,
             &ck,
@@ -129,6 +199,7 @@ mod tests {
             &commitments[1],
             Some(enforced_degree_bound),
             proof,
+            &Context::new(b"test"),
             &mut fs_rng,
         )
         .unwrap();
@@ -136,6 +207,8 @@ mod tests {
         assert_eq!((), res)
     }
 
+    // an invalid witness is rejected by the prover before the verifier sees it
+    #[cfg(not(feature = "validate-witness"))]
     #[test]
     fn test_malicious_discrete_log_proof() {
         let rng = &mut thread_rng();
@@ -145,8 +218,8 @@ mod tests {
         let enforced_degree_bound = m + 1;
         let enforced_hiding_bound = Some(1);
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let f_evals = vec![
             domain_h.element(1),
@@ -193,8 +266,8 @@ mod tests {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let proof = DLComparison::<F, PC, FS>::prove(
-            &ck,
+        let proof = DLComparison::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
             &domain_k,
             &domain_h,
             &f_poly,
@@ -204,12 +277,15 @@ mod tests {
             &commitments[1],
             &rands[1],
             Some(enforced_degree_bound),
+            &Context::new(b"test"),
             &mut fs_rng,
             rng,
         )
         .unwrap();
 
-        let res = DLComparison::verify(
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        let report = DLComparison::verify_with_report(
             &vk,
             &ck,
             &domain_k,
@@ -218,8 +294,21 @@ mod tests {
             &commitments[1],
             Some(enforced_degree_bound),
             proof,
+            &Context::new(b"test"),
             &mut fs_rng,
         );
+
+        // only the product check fails, every other sub-protocol is still verified
+        assert_eq!(
+            report.failures(),
+            vec![(
+                String::from("discrete_log_comparison/f_prime_product"),
+                Error::ZeroOverKError(String::from("Check2Failed"))
+            )]
+        );
+        assert_eq!(report.sub_reports.len(), 15);
+
+        let res = report.into_result();
         assert!(res.is_err());
 
         // Test for a specific error
@@ -229,6 +318,484 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_discrete_log_proof_v2() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let to_poly = |label: &str, exponents: &[usize]| {
+            let evals = exponents
+                .iter()
+                .map(|&e| domain_h.element(e))
+                .collect::<Vec<_>>();
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::<F>::from_coefficients_vec(domain_k.ifft(&evals)),
+                Some(enforced_degree_bound),
+                Some(1),
+            )
+        };
+        let f_poly = to_poly("f_poly", &[1, 2, 3, 3, 1, 2, 3, 3]);
+        let g_poly = to_poly("g_poly", &[0, 1, 1, 2, 0, 1, 1, 2]);
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) =
+            PC::commit(&ck, &[f_poly.clone(), g_poly.clone()], Some(rng)).unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = DLComparison::<C>::prove_v2(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+            &domain_k,
+            &domain_h,
+            &f_poly,
+            &commitments[0],
+            &rands[0],
+            &g_poly,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )
+        .unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof_v1 = DLComparison::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+            &domain_k,
+            &domain_h,
+            &f_poly,
+            &commitments[0],
+            &rands[0],
+            &g_poly,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )
+        .unwrap();
+
+        // one ZeroOverK over six oracles instead of three over two and one over three. The cost model counts the
+        // elements saved, the serialized proofs also save the length prefixes of three ZeroOverK proofs.
+        let sizes = ElementSizes::new(
+            proof.s_commit.serialized_size(),
+            proof.h_opening_proof.serialized_size(),
+            proof.h_eval.serialized_size(),
+        );
+        let savings = Protocol::DLComparison.cost().proof_size(&sizes)
+            - Protocol::DLComparisonV2.cost().proof_size(&sizes);
+        assert!(proof_v1.serialized_size() - proof.serialized_size() >= savings);
+        assert!(savings > 0);
+
+        // both sizes are known before proving
+        let size = |v2: bool| {
+            let size = if v2 {
+                DLComparison::<C>::proof_v2_size
+            } else {
+                DLComparison::<C>::proof_size
+            };
+            size(
+                &domain_k,
+                &domain_h,
+                Some(enforced_degree_bound),
+                ck.supported_degree(),
+            )
+            .unwrap()
+        };
+        assert_eq!(size(false), proof_v1.serialized_size());
+        assert_eq!(size(true), proof.serialized_size());
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let res = DLComparison::verify_v2(
+            &vk,
+            &ck,
+            &domain_k,
+            &domain_h,
+            &commitments[0],
+            &commitments[1],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+        assert_eq!(res, Ok(()));
+    }
+
+    // a single constraint failing makes the combined check fail
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_malicious_discrete_log_proof_v2() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let to_poly = |label: &str, exponents: &[usize]| {
+            let evals = exponents
+                .iter()
+                .map(|&e| domain_h.element(e))
+                .collect::<Vec<_>>();
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::<F>::from_coefficients_vec(domain_k.ifft(&evals)),
+                Some(enforced_degree_bound),
+                Some(1),
+            )
+        };
+        // here log(g) > log(f) in the first position
+        let f_poly = to_poly("f_poly", &[1, 2, 3, 3, 1, 2, 3, 3]);
+        let g_poly = to_poly("g_poly", &[3, 1, 1, 2, 0, 1, 1, 2]);
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) =
+            PC::commit(&ck, &[f_poly.clone(), g_poly.clone()], Some(rng)).unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = DLComparison::<C>::prove_v2(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+            &domain_k,
+            &domain_h,
+            &f_poly,
+            &commitments[0],
+            &rands[0],
+            &g_poly,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )
+        .unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let report = DLComparison::verify_v2_with_report(
+            &vk,
+            &ck,
+            &domain_k,
+            &domain_h,
+            &commitments[0],
+            &commitments[1],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+
+        assert_eq!(
+            report.failures(),
+            vec![(
+                String::from("discrete_log_comparison_v2/square_and_product"),
+                Error::ZeroOverKError(String::from("Check2Failed"))
+            )]
+        );
+    }
+
+    /// Run the protocol over |K| = 8 and |H| = 4 on f and g taking the values omega^f_exponents[i] and
+    /// omega^g_exponents[i] over K
+    fn run_discrete_log_proof(f_exponents: &[usize], g_exponents: &[usize]) -> Result<(), Error> {
+        run_tampered_discrete_log_proof(f_exponents, g_exponents, |_| {})
+    }
+
+    /// As [`run_discrete_log_proof`], with the proof altered before it is verified
+    fn run_tampered_discrete_log_proof(
+        f_exponents: &[usize],
+        g_exponents: &[usize],
+        tamper: impl Fn(&mut Proof<C>),
+    ) -> Result<(), Error> {
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        run_discrete_log_proof_in_base(&domain_h, f_exponents, g_exponents, tamper)
+    }
+
+    /// As [`run_tampered_discrete_log_proof`], with f and g taking powers of another base than the generator of H
+    fn run_discrete_log_proof_in_base<B: DiscreteLogBase<F>>(
+        base: &B,
+        f_exponents: &[usize],
+        g_exponents: &[usize],
+        tamper: impl Fn(&mut Proof<C>),
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let powers = base.powers();
+        let to_poly = |label: &str, exponents: &[usize]| {
+            let evals = exponents.iter().map(|&e| powers[e]).collect::<Vec<_>>();
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::<F>::from_coefficients_vec(domain_k.ifft(&evals)),
+                Some(enforced_degree_bound),
+                Some(1),
+            )
+        };
+        let f_poly = to_poly("f_poly", f_exponents);
+        let g_poly = to_poly("g_poly", g_exponents);
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) =
+            PC::commit(&ck, &[f_poly.clone(), g_poly.clone()], Some(rng)).unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        let proof = DLComparison::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+            &domain_k,
+            base,
+            &f_poly,
+            &commitments[0],
+            &rands[0],
+            &g_poly,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+        let mut proof = proof;
+        tamper(&mut proof);
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+        DLComparison::verify(
+            &vk,
+            &ck,
+            &domain_k,
+            base,
+            &commitments[0],
+            &commitments[1],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )
+    }
+
+    #[test]
+    fn test_boundary_exponents() {
+        // g takes the identity value: every exponent of f above 0 compares strictly greater
+        assert_eq!(
+            run_discrete_log_proof(&[1, 2, 3, 1, 2, 3, 1, 2], &[0; 8]),
+            Ok(())
+        );
+
+        // the largest gap, from the identity to the last power of omega
+        assert_eq!(run_discrete_log_proof(&[3; 8], &[0; 8]), Ok(()));
+    }
+
+    #[test]
+    fn test_other_base() {
+        // omega^3 also generates H, and the discrete logs of omega^2 and omega^3 are 2 and 1 in its base
+        let omega = GeneralEvaluationDomain::<F>::new(4).unwrap().element(1);
+        let base = GroupElementBase::new(omega.pow([3]), 4).unwrap();
+        assert_eq!(
+            run_discrete_log_proof_in_base(&base, &[2; 8], &[1; 8], |_| {}),
+            Ok(())
+        );
+
+        // in base omega the same values compare the other way
+        assert!(run_discrete_log_proof(&[2; 8], &[3; 8]).is_err());
+    }
+
+    #[test]
+    fn test_odd_order_base() {
+        // a cube root of unity, (-1 + sqrt(-3)) / 2, has order 3 and a square root of order 6
+        let generator = (-F::one() + (-F::from(3u64)).sqrt().unwrap()) / F::from(2u64);
+        let base = GroupElementBase::new(generator, 3).unwrap();
+        assert_eq!(
+            run_discrete_log_proof_in_base(
+                &base,
+                &[1, 2, 2, 1, 2, 1, 2, 2],
+                &[0, 1, 0, 0, 1, 0, 0, 1],
+                |_| {}
+            ),
+            Ok(())
+        );
+
+        assert!(run_discrete_log_proof_in_base(
+            &base,
+            &[1, 2, 2, 1, 2, 1, 2, 2],
+            &[0, 2, 0, 0, 1, 0, 0, 1],
+            |_| {}
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_group_element_base() {
+        let omega = GeneralEvaluationDomain::<F>::new(4).unwrap().element(1);
+        assert!(GroupElementBase::new(omega, 4).is_ok());
+        assert!(GroupElementBase::new(-F::one(), 2).is_ok());
+
+        // the order must be exact, but need not be even
+        assert!(GroupElementBase::new(F::one(), 1).is_ok());
+        for (generator, order) in [(omega, 8), (omega, 2), (F::one(), 2), (F::one(), 0)] {
+            assert!(matches!(
+                GroupElementBase::new(generator, order),
+                Err(Error::InvalidDiscreteLogBase(_))
+            ));
+        }
+
+        // the generator of the largest 2-adic subgroup has no square root
+        let two_adicity = <F as FftField>::FftParams::TWO_ADICITY;
+        assert_eq!(
+            GroupElementBase::new(F::two_adic_root_of_unity(), 1 << two_adicity),
+            Err(Error::OmegaSqrtError)
+        );
+    }
+
+    #[test]
+    fn test_substituted_s_commitment() {
+        // the verifier derives the commitment to s - 1 from the one to s and binds it to the transcript, so another
+        // commitment to s sent once the challenges are drawn is rejected
+        let res = run_tampered_discrete_log_proof(&[3; 8], &[0; 8], |proof| {
+            proof.s_commit = PC::scale(&proof.s_commit, F::from(2u64)).unwrap();
+        });
+        assert!(res.is_err());
+    }
+
+    // an invalid witness is rejected by the prover before the verifier sees it
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_identity_is_never_greater() {
+        // f takes the identity value while g does not
+        assert_eq!(
+            run_discrete_log_proof(&[0, 2, 3, 3, 1, 2, 3, 3], &[1, 1, 1, 2, 0, 1, 1, 2]),
+            Err(Error::ZeroOverKError(String::from("Check2Failed")))
+        );
+
+        // f and g both take the identity value, so s - 1 vanishes and the prover cannot show it is non-zero
+        assert_eq!(
+            run_discrete_log_proof(&[0, 2, 3, 3, 1, 2, 3, 3], &[0, 1, 1, 2, 0, 1, 1, 2]),
+            Err(Error::FEvalIsZero)
+        );
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+
+        let to_poly = |label: &str, evals: Vec<F>| {
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::<F>::from_coefficients_vec(domain_k.ifft(&evals)),
+                None,
+                None,
+            )
+        };
+        let powers = |exponents: &[usize]| {
+            exponents
+                .iter()
+                .map(|&e| domain_h.element(e))
+                .collect::<Vec<_>>()
+        };
+
+        let f = to_poly("f", powers(&[1, 2, 3, 1, 2, 3, 1, 2]));
+        let g = to_poly("g", powers(&[0, 1, 2, 0, 1, 2, 0, 0]));
+        assert_eq!(
+            DLComparison::<C>::validate_witness(&domain_k, &domain_h, &f, &g),
+            Ok(())
+        );
+
+        // equal exponents are caught at the first offending index
+        let g = to_poly("g", powers(&[0, 1, 2, 0, 2, 2, 0, 0]));
+        assert_eq!(
+            DLComparison::<C>::validate_witness(&domain_k, &domain_h, &f, &g),
+            Err(Error::InvalidWitness(WitnessError::NotGreater {
+                index: 4,
+                f_exponent: 2,
+                g_exponent: 2
+            }))
+        );
+
+        // a value of g outside of H is reported with its oracle label
+        let mut g_evals = powers(&[0; 8]);
+        g_evals[5] = F::from(2u64);
+        let g = to_poly("g", g_evals);
+        assert_eq!(
+            DLComparison::<C>::validate_witness(&domain_k, &domain_h, &f, &g),
+            Err(Error::InvalidWitness(WitnessError::NotInDomain {
+                oracle: String::from("g"),
+                index: 5
+            }))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_degree_bound() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+
+        // a key trimmed without the enforced degree bound is rejected before proving anything
+        let (ck, _) = PC::trim(&pp, max_degree, 1, Some(&[2])).unwrap();
+        let checked =
+            CheckedCommitterKey::<C, DLComparison<C>>::new(&ck, Some(enforced_degree_bound));
+        assert!(matches!(checked, Err(Error::UnsupportedDegreeBound(_))));
+
+        // the bounds it was trimmed with are those the protocol requires
+        let bounds = DLComparison::<C>::required_degree_bounds(Some(enforced_degree_bound));
+        let (ck, _) = PC::trim(&pp, max_degree, 1, Some(&bounds)).unwrap();
+        let checked =
+            CheckedCommitterKey::<C, DLComparison<C>>::new(&ck, Some(enforced_degree_bound))
+                .unwrap();
+
+        // a checked key only proves under the degree bound it was checked for
+        let to_poly = |label: &str, exponents: &[usize]| {
+            let evals = exponents
+                .iter()
+                .map(|&e| domain_h.element(e))
+                .collect::<Vec<_>>();
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::<F>::from_coefficients_vec(domain_k.ifft(&evals)),
+                Some(enforced_degree_bound),
+                Some(1),
+            )
+        };
+        let f_poly = to_poly("f_poly", &[1, 2, 3, 3, 1, 2, 3, 3]);
+        let g_poly = to_poly("g_poly", &[0, 1, 1, 2, 0, 1, 1, 2]);
+        let (commitments, rands) =
+            PC::commit(&ck, &[f_poly.clone(), g_poly.clone()], Some(rng)).unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let res = DLComparison::<C>::prove(
+            &checked,
+            &domain_k,
+            &domain_h,
+            &f_poly,
+            &commitments[0],
+            &rands[0],
+            &g_poly,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound + 1),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        );
+        assert!(matches!(res, Err(Error::UnsupportedDegreeBound(_))));
+    }
+
     #[test]
     fn test_reject_large_degree() {
         let rng = &mut thread_rng();
@@ -239,11 +806,11 @@ mod tests {
         let other_degree_bound = m + 5;
         let enforced_hiding_bound = Some(1);
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
-        // For the test to pass, each value in f_evals must be less than its corresponding value in
-        // g_evals, mod n (since we use domain_h)
+        // For the test to pass, the discrete log of each value in f_evals must be strictly greater than that of
+        // its corresponding value in g_evals
 
         let f_evals = vec![
             domain_h.element(1),
@@ -296,8 +863,8 @@ mod tests {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let proof = DLComparison::<F, PC, FS>::prove(
-            &ck,
+        let proof = DLComparison::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(other_degree_bound)).unwrap(),
             &domain_k,
             &domain_h,
             &f_poly,
@@ -307,11 +874,14 @@ mod tests {
             &commitments[1],
             &rands[1],
             Some(other_degree_bound),
+            &Context::new(b"test"),
             &mut fs_rng,
             rng,
         )
         .unwrap();
 
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
         let res = DLComparison::verify(
             &vk,
             &ck,
@@ -321,15 +891,144 @@ mod tests {
             &commitments[1],
             Some(enforced_degree_bound),
             proof,
+            &Context::new(b"test"),
             &mut fs_rng,
         );
 
         assert!(res.is_err());
 
-        // Test for a specific error
-        assert_eq!(
-            res.err().unwrap(),
-            Error::ZeroOverKError(String::from("BatchCheckError"))
-        );
+        // Test for a specific error: the opening of h is the first check to enforce the degree bound
+        assert_eq!(res.err().unwrap(), Error::BatchCheckError);
+    }
+
+    /// Bn254 only supports radix-2 domains, so mixed-radix domains are tested over MNT6-298 whose scalar field has
+    /// multiplicative subgroups of order 7^a * 2^b
+    mod mixed_radix {
+        use super::{Config, FS};
+        use crate::{
+            checked_key::CheckedCommitterKey,
+            discrete_log_comparison::DLComparison,
+            domains::{DomainH, DomainK},
+            error::Error,
+        };
+        use ark_ff::to_bytes;
+        use ark_mnt6_298::{Fr, MNT6_298};
+        use ark_poly::{
+            univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
+            MixedRadixEvaluationDomain, UVPolynomial,
+        };
+        use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+        use ark_std::rand::thread_rng;
+        use fiat_shamir_rng::{Context, FiatShamirRng};
+        use homomorphic_poly_commit::marlin_kzg::KZG10;
+
+        type F = Fr;
+        type PC = KZG10<MNT6_298>;
+        type C = Config<F, PC, FS>;
+
+        fn mixed_radix_domain(size: usize) -> GeneralEvaluationDomain<F> {
+            let domain = MixedRadixEvaluationDomain::<F>::new(size).unwrap();
+            assert_eq!(domain.size(), size);
+            GeneralEvaluationDomain::MixedRadix(domain)
+        }
+
+        /// Run the protocol on f and g taking the values omega^f_exponents[i] and omega^g_exponents[i] over K
+        fn run_discrete_log_proof(
+            domain_k: DomainK<F>,
+            domain_h: DomainH<F>,
+            f_exponents: &[usize],
+            g_exponents: &[usize],
+        ) -> Result<(), Error> {
+            let rng = &mut thread_rng();
+            let enforced_degree_bound = domain_k.size() + 1;
+
+            let to_poly = |label: &str, exponents: &[usize]| {
+                let evals = exponents
+                    .iter()
+                    .map(|&e| domain_h.element(e))
+                    .collect::<Vec<_>>();
+                LabeledPolynomial::new(
+                    String::from(label),
+                    DensePolynomial::<F>::from_coefficients_vec(domain_k.ifft(&evals)),
+                    Some(enforced_degree_bound),
+                    Some(1),
+                )
+            };
+            let f_poly = to_poly("f_poly", f_exponents);
+            let g_poly = to_poly("g_poly", g_exponents);
+
+            let max_degree = 3 * domain_k.size();
+            let pp = PC::setup(max_degree, None, rng).unwrap();
+            let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+            let (commitments, rands) =
+                PC::commit(&ck, &[f_poly.clone(), g_poly.clone()], Some(rng)).unwrap();
+
+            let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+            let proof = DLComparison::<C>::prove(
+                &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+                &domain_k,
+                &domain_h,
+                &f_poly,
+                &commitments[0],
+                &rands[0],
+                &g_poly,
+                &commitments[1],
+                &rands[1],
+                Some(enforced_degree_bound),
+                &Context::new(b"test"),
+                &mut fs_rng,
+                rng,
+            )?;
+
+            let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+
+            DLComparison::verify(
+                &vk,
+                &ck,
+                &domain_k,
+                &domain_h,
+                &commitments[0],
+                &commitments[1],
+                Some(enforced_degree_bound),
+                proof,
+                &Context::new(b"test"),
+                &mut fs_rng,
+            )
+        }
+
+        #[test]
+        fn test_discrete_log_proof_mixed_radix() {
+            let domain_k = DomainK(mixed_radix_domain(7 * 4));
+            let domain_h = DomainH(mixed_radix_domain(7 * 2));
+
+            let g_exponents = (0..28).map(|i| i % 7).collect::<Vec<_>>();
+            let f_exponents = g_exponents
+                .iter()
+                .enumerate()
+                .map(|(i, g)| g + 1 + i % 6)
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                run_discrete_log_proof(domain_k, domain_h, &f_exponents, &g_exponents),
+                Ok(())
+            );
+        }
+
+        #[test]
+        fn test_discrete_log_proof_odd_domain() {
+            // the generator of H has order 7, delta is the square root of order 14
+            let domain_k = DomainK(mixed_radix_domain(7 * 4));
+            let domain_h = DomainH(mixed_radix_domain(7));
+
+            let g_exponents = (0..28).map(|i| i % 6).collect::<Vec<_>>();
+            let f_exponents = g_exponents.iter().map(|g| g + 1).collect::<Vec<_>>();
+
+            assert_eq!(
+                run_discrete_log_proof(domain_k, domain_h, &f_exponents, &g_exponents),
+                Ok(())
+            );
+        }
     }
 }
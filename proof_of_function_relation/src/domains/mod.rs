@@ -0,0 +1,90 @@
+//! The two domains of the protocols on matrices: H, which indexes the rows and columns of a matrix, and K, which
+//! indexes its non-zero entries. Both are [`GeneralEvaluationDomain`]s, wrapped in [`DomainH`] and [`DomainK`] so that
+//! the compiler rejects a call that swaps them. Both wrappers dereference to the underlying domain.
+//!
+//! ```ignore
+//! let (domain_h, domain_k) = choose_domains::<F>(num_constraints, num_non_zero)?;
+//! let proof = TFT::<C>::prove(&ck, t, &domain_k, &domain_h, ...)?;
+//! ```
+
+use crate::{discrete_log_comparison::piop::PIOPforDLComparison, error::Error};
+use ark_ff::{FftField, FftParameters, PrimeField, SquareRootField};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use std::ops::Deref;
+
+mod tests;
+
+/// The domain K, indexing the non-zero entries of a matrix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DomainK<F: FftField>(pub GeneralEvaluationDomain<F>);
+
+/// The domain H, indexing the rows and columns of a matrix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DomainH<F: FftField>(pub GeneralEvaluationDomain<F>);
+
+impl<F: FftField> DomainK<F> {
+    /// The smallest domain K of at least `size` elements
+    pub fn new(size: usize) -> Result<Self, Error> {
+        new_domain(size, "K").map(Self)
+    }
+}
+
+impl<F: FftField> DomainH<F> {
+    /// The smallest domain H of at least `size` elements
+    pub fn new(size: usize) -> Result<Self, Error> {
+        new_domain(size, "H").map(Self)
+    }
+}
+
+impl<F: FftField> Deref for DomainK<F> {
+    type Target = GeneralEvaluationDomain<F>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<F: FftField> Deref for DomainH<F> {
+    type Target = GeneralEvaluationDomain<F>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Choose the smallest domains H and K supported by the field for a matrix with `num_constraints` rows and columns
+/// and `num_non_zero` non-zero entries. H indexes rows and columns and K indexes the non-zero entries, so:
+/// - |H| >= num_constraints and |K| >= num_non_zero,
+/// - |K| >= |H|, as GeoSeqTest pads the sequence w^t, ..., w^(n-1) over K,
+/// - the generator of H has a square root of order 2|H|, as required by DLComparison.
+pub fn choose_domains<F: PrimeField + SquareRootField>(
+    num_constraints: usize,
+    num_non_zero: usize,
+) -> Result<(DomainH<F>, DomainK<F>), Error> {
+    // over a base of order 1 every discrete log is 0 and no comparison holds
+    let domain_h = DomainH::<F>::new(num_constraints.max(2))?;
+    PIOPforDLComparison::delta(&domain_h).map_err(|_| {
+        Error::UnsupportedDomain(format!(
+            "The generator of a domain H of size {} has no square root of order {} (two-adicity of the field is {})",
+            domain_h.size(),
+            2 * domain_h.size(),
+            <F as FftField>::FftParams::TWO_ADICITY
+        ))
+    })?;
+
+    let domain_k = DomainK::<F>::new(num_non_zero.max(domain_h.size()))?;
+
+    Ok((domain_h, domain_k))
+}
+
+fn new_domain<F: FftField>(size: usize, name: &str) -> Result<GeneralEvaluationDomain<F>, Error> {
+    GeneralEvaluationDomain::<F>::new(size).ok_or_else(|| {
+        Error::UnsupportedDomain(format!(
+            "No domain {} of size at least {} exists in the field (two-adicity is {})",
+            name,
+            size,
+            <F as FftField>::FftParams::TWO_ADICITY
+        ))
+    })
+}
@@ -0,0 +1,89 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::{
+        discrete_log_comparison::piop::PIOPforDLComparison,
+        domains::{choose_domains, DomainK},
+        error::Error,
+        util::{padded_sequence, rows_from_t},
+    };
+    use ark_bn254::Fr;
+    use ark_ff::{One, Zero};
+    use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+
+    type F = Fr;
+
+    #[test]
+    fn test_choose_domains() {
+        // sizes are rounded up to powers of two
+        let (domain_h, domain_k) = choose_domains::<F>(3, 6).unwrap();
+        assert_eq!(domain_h.size(), 4);
+        assert_eq!(domain_k.size(), 8);
+
+        // K is at least as large as H
+        let (domain_h, domain_k) = choose_domains::<F>(5, 2).unwrap();
+        assert_eq!(domain_h.size(), 8);
+        assert_eq!(domain_k.size(), 8);
+
+        // H has an even size
+        let (domain_h, domain_k) = choose_domains::<F>(1, 1).unwrap();
+        assert_eq!(domain_h.size(), 2);
+        assert_eq!(domain_k.size(), 2);
+    }
+
+    #[test]
+    fn test_insufficient_two_adicity() {
+        // the two-adicity of the bn254 scalar field is 28: a domain of size 2^28 exists, but its generator has no
+        // square root
+        let res = choose_domains::<F>(1 << 28, 1);
+        assert!(matches!(res, Err(Error::UnsupportedDomain(_))));
+
+        let res = choose_domains::<F>(4, 1 << 29);
+        assert!(matches!(res, Err(Error::UnsupportedDomain(_))));
+    }
+
+    #[test]
+    fn test_padding_near_two_adicity() {
+        // the largest domains of the bn254 scalar field: H of size 2^27, whose generator has a square root, and K of
+        // size 2^28. Only their sizes are used, no FFT is run over them.
+        let (domain_h, domain_k) = choose_domains::<F>(1 << 27, 1 << 28).unwrap();
+        assert_eq!((domain_h.size(), domain_k.size()), (1 << 27, 1 << 28));
+
+        let (a_s, c_s) = PIOPforDLComparison::h_sequence(&domain_h, &domain_k).unwrap();
+        assert_eq!(a_s, vec![F::one(), F::zero()]);
+        assert_eq!(c_s, vec![1 << 27, 1 << 27]);
+
+        // rows t..n-1 of H, padded over K
+        let rows = rows_from_t(3, domain_h.size()).unwrap();
+        let (_, c_s) = padded_sequence(F::one(), rows, F::zero, &domain_k).unwrap();
+        assert_eq!(c_s, vec![(1 << 27) - 3, (1 << 27) + 3]);
+
+        // a sequence filling K needs no padding, an empty one is all padding
+        let (_, c_s) = padded_sequence(F::one(), 1 << 28, F::zero, &domain_k).unwrap();
+        assert_eq!(c_s, vec![1 << 28]);
+        let rows = rows_from_t(domain_h.size(), domain_h.size()).unwrap();
+        let (a_s, c_s) = padded_sequence(F::one(), rows, F::zero, &domain_k).unwrap();
+        assert_eq!((a_s, c_s), (vec![F::zero()], vec![1 << 28]));
+
+        // a sequence longer than K, or a t past the last row, is an error rather than a wrapped-around length
+        assert!(matches!(
+            padded_sequence(F::one(), (1 << 28) + 1, F::zero, &domain_k),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+        assert!(matches!(
+            padded_sequence(F::one(), usize::MAX, F::zero, &domain_k),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+        assert_eq!(
+            rows_from_t(domain_h.size() + 1, domain_h.size()),
+            Err(Error::T2Large)
+        );
+        assert_eq!(rows_from_t(usize::MAX, 0), Err(Error::T2Large));
+
+        // H larger than K
+        let domain_small = DomainK(GeneralEvaluationDomain::<F>::new(1 << 26).unwrap());
+        assert!(matches!(
+            PIOPforDLComparison::h_sequence(&domain_h, &domain_small),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::equal_on_subdomain::proof::Proof;
+use crate::error::{to_pc_error, Error};
+use ark_ff::{One, Zero};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use fiat_shamir_rng::Context;
+#[cfg(feature = "prover")]
+use rand::Rng;
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
+    zero_over_k::ZeroOverK,
+};
+
+pub mod proof;
+mod tests;
+
+/// Proves that f(X) = g(X) for all X in a sub-domain S of K, where S is given by the indices of its elements in K.
+/// Values outside of S are unconstrained, so this shows that an update of f into g left the region S untouched.
+pub struct EqualOnSubdomain<C: ProtocolConfig> {
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> EqualOnSubdomain<C> {
+    /// The public selector polynomial, equal to 1 on S and 0 on the rest of K
+    pub fn selector(
+        domain: &GeneralEvaluationDomain<C::F>,
+        subdomain: &[usize],
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<LabeledPolynomial<C::F, DensePolynomial<C::F>>, Error> {
+        let mut evals = vec![C::F::zero(); domain.size()];
+        for &index in subdomain {
+            if index >= domain.size() {
+                return Err(Error::InvalidSubdomain(format!(
+                    "Index {} is out of a domain of size {}",
+                    index,
+                    domain.size()
+                )));
+            }
+            evals[index] = C::F::one();
+        }
+
+        Ok(LabeledPolynomial::new(
+            String::from("selector"),
+            DensePolynomial::from_coefficients_vec(domain.ifft(&evals)),
+            enforced_degree_bound,
+            None,
+        ))
+    }
+
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        subdomain: &[usize],
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        g: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_rand: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("equal_on_subdomain::prove");
+        // the selector is public, so its commitment carries no randomness
+        let selector = Self::selector(domain, subdomain, enforced_degree_bound)?;
+        let (selector_commit, selector_rand) =
+            C::PC::commit(ck, [&selector], None).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        // the virtual oracle s(X) * (f(X) - g(X))
+        let alphas = [C::F::one(), C::F::one(), C::F::one()];
+        let selected_equality_vo =
+            GenericShiftingVO::new(&[0, 1, 2], &alphas, presets::selected_equality_check)?
+                .with_scaling_factor(2);
+
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
+            &[&selector, f, g],
+            &[
+                selector_commit[0].clone(),
+                f_commit.clone(),
+                g_commit.clone(),
+            ],
+            &[selector_rand[0].clone(), f_rand.clone(), g_rand.clone()],
+            enforced_degree_bound,
+            &selected_equality_vo,
+            domain,
+            ck,
+            &context.child(b"selected_equality_check"),
+            rng,
+        )?;
+
+        Ok(Proof { zero_over_k_proof })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        subdomain: &[usize],
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        let _span = trace_span!("equal_on_subdomain::verify");
+        // the verifier derives the selector commitment itself
+        let selector = Self::selector(domain, subdomain, enforced_degree_bound)?;
+        let (selector_commit, _) =
+            C::PC::commit(ck, &[selector], None).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        // re-label the oracle commitments with the enforced degree bound
+        let f_commit = LabeledCommitment::new(
+            f_commit.label().clone(),
+            f_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
+        let g_commit = LabeledCommitment::new(
+            g_commit.label().clone(),
+            g_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
+
+        // the virtual oracle s(X) * (f(X) - g(X))
+        let alphas = [C::F::one(), C::F::one(), C::F::one()];
+        let selected_equality_vo =
+            GenericShiftingVO::new(&[0, 1, 2], &alphas, presets::selected_equality_check)?
+                .with_scaling_factor(2);
+
+        ZeroOverK::<C>::verify(
+            proof.zero_over_k_proof,
+            &[selector_commit[0].clone(), f_commit, g_commit],
+            enforced_degree_bound,
+            &selected_equality_vo,
+            domain,
+            vk,
+            &context.child(b"selected_equality_check"),
+        )
+        .map_err(Error::from)
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{equal_on_subdomain::EqualOnSubdomain, error::Error};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::One;
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    };
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+    use zero_over_k::util::sample_vector;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
+
+    /// Update f outside of the protected region and prove that f and its update agree on `subdomain`
+    fn run_equal_on_subdomain(updated: &[usize], subdomain: &[usize]) -> Result<(), Error> {
+        let m = 8;
+        let rng = &mut thread_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let enforced_degree_bound = m + 1;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let f_evals: Vec<F> = sample_vector(rng, m);
+        let mut g_evals = f_evals.clone();
+        for &index in updated {
+            g_evals[index] += F::one();
+        }
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&f_evals)),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&g_evals)),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+
+        let (commitments, rands) = PC::commit(&ck, &[f.clone(), g.clone()], Some(rng)).unwrap();
+
+        let proof = EqualOnSubdomain::<C>::prove(
+            &ck,
+            &domain_k,
+            subdomain,
+            &f,
+            &commitments[0],
+            &rands[0],
+            &g,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            rng,
+        )?;
+
+        EqualOnSubdomain::<C>::verify(
+            &vk,
+            &ck,
+            &domain_k,
+            subdomain,
+            &commitments[0],
+            &commitments[1],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+        )
+    }
+
+    #[test]
+    fn test_equal_on_subdomain() {
+        assert_eq!(run_equal_on_subdomain(&[1, 6, 7], &[0, 2, 3, 4, 5]), Ok(()));
+    }
+
+    #[test]
+    fn test_update_inside_subdomain() {
+        assert_eq!(
+            run_equal_on_subdomain(&[1, 3], &[0, 2, 3, 4, 5]),
+            Err(Error::ZeroOverKError(String::from("Check2Failed")))
+        );
+    }
+
+    #[test]
+    fn test_invalid_subdomain() {
+        assert!(matches!(
+            run_equal_on_subdomain(&[], &[0, 8]),
+            Err(Error::InvalidSubdomain(_))
+        ));
+    }
+}
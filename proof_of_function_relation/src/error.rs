@@ -1,4 +1,4 @@
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Error {
     // In zero_over_k and geo_seq
     BatchCheckError,
@@ -15,6 +15,27 @@ pub enum Error {
     /// Number of user inputs is too large
     T2Large,
 
+    /// The evaluation domain is not supported by the protocol
+    UnsupportedDomain(String),
+
+    /// The indices of a sub-domain do not all lie in the domain
+    InvalidSubdomain(String),
+
+    /// The initial values and lengths of a geometric sequence do not describe a sequence over the whole domain
+    InvalidSequenceDescription(String),
+
+    /// The bandwidth of a band matrix leaves no room in domain H to shift indices by it
+    BandwidthTooLarge(String),
+
+    /// The declared blocks of a block-diagonal matrix are empty or do not fit in domain H
+    InvalidBlocks(String),
+
+    /// The padding strategy of a matrix encoding is not supported by the protocol
+    UnsupportedPadding(String),
+
+    /// The proof was created for a different statement
+    StatementMismatch,
+
     ProofSerializationError,
     ProofDeserializationError,
 
@@ -23,6 +44,102 @@ pub enum Error {
     },
 
     ZeroOverKError(String),
+
+    /// The prover was cancelled through its [`CancellationToken`](zero_over_k::cancellation::CancellationToken), at
+    /// the given checkpoint
+    Aborted(String),
+
+    // In indexer
+    MatrixTooLarge(String),
+
+    /// The prover's witness does not satisfy the relation being proven
+    InvalidWitness(WitnessError),
+
+    /// A function property is unknown, registered twice, or given data of the wrong type
+    InvalidProperty(String),
+
+    /// An oracle committed to by a third party cannot be brought to the labels and degree bound of a protocol
+    IncompatibleExternalOracle(String),
+
+    /// A circuit cannot be compiled or composed with another one
+    InvalidCircuit(String),
+
+    /// A session fork label is malformed or was already used, so that two proofs would share their challenges
+    InvalidFork(String),
+
+    /// The committer key was not trimmed with a degree bound the protocol enforces
+    UnsupportedDegreeBound(String),
+
+    /// A runtime protocol option is unknown or given a malformed value
+    InvalidOption(String),
+
+    /// A function into an extension field is not given by one oracle per coordinate
+    InvalidExtensionFunction(String),
+
+    /// A base of discrete logs does not have the claimed order
+    InvalidDiscreteLogBase(String),
+}
+
+/// Why a witness fails the relation, pointing at the first offending element of K
+#[derive(Clone, Debug, PartialEq)]
+pub enum WitnessError {
+    /// The oracle does not evaluate to an element of H at the given index of K
+    NotInDomain { oracle: String, index: usize },
+
+    /// The discrete log of f is not strictly greater than that of g at the given index of K
+    NotGreater {
+        index: usize,
+        f_exponent: usize,
+        g_exponent: usize,
+    },
+
+    /// The row at the given index of K lies among the first t rows
+    RowBelowT {
+        index: usize,
+        row_exponent: usize,
+        t: usize,
+    },
+
+    /// The entry at the given index of K has no mirrored entry (col, row, val) to pair with
+    NoMirrorEntry { index: usize },
+
+    /// The value of f at the given index of K is not a value of h
+    NotInSubset { index: usize },
+
+    /// The entry of M at the given index of K has no transposed entry (col, row, val) in the encoding of M^T to pair
+    /// with
+    NoTransposedEntry { index: usize },
+
+    /// The entries of the given row or column do not add up to the committed sum of that line
+    WrongLineSum { line: usize },
+
+    /// The value at the given index of K is neither 0 nor 1
+    NotBoolean { index: usize },
+
+    /// The entry at the given index of K lies outside of the band
+    OutsideBand {
+        index: usize,
+        row_exponent: usize,
+        col_exponent: usize,
+        bandwidth: usize,
+    },
+
+    /// The row or column at the given index of K lies within the bandwidth of the end of H, where shifting it by the
+    /// bandwidth wraps around
+    BandOverflow { index: usize, exponent: usize },
+
+    /// The row and column of the entry at the given index of K do not lie in the same declared block
+    OffBlockDiagonal {
+        index: usize,
+        row_exponent: usize,
+        col_exponent: usize,
+    },
+
+    /// The entries of M at the given row and column do not add up to those of M1 and M2
+    SumMismatch { row: usize, col: usize },
+
+    /// The entry of M at the given row and column is not that of the product A * B
+    ProductMismatch { row: usize, col: usize },
 }
 
 /// Convert an ark_poly_commit error
@@ -39,7 +156,10 @@ where
 
 impl From<zero_over_k::error::Error> for Error {
     fn from(err: zero_over_k::error::Error) -> Self {
-        Self::ZeroOverKError(format!("{:?}", err))
+        match err {
+            zero_over_k::error::Error::Aborted(stage) => Self::Aborted(stage),
+            err => Self::ZeroOverKError(format!("{:?}", err)),
+        }
     }
 }
 
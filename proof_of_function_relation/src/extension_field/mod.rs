@@ -0,0 +1,339 @@
+use crate::{
+    error::{to_pc_error, Error},
+    extension_field::proof::Proof,
+};
+use ark_ff::{
+    to_bytes, CubicExtField, CubicExtParameters, Field, One, PrimeField, QuadExtField,
+    QuadExtParameters,
+};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial,
+    UVPolynomial,
+};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PCCommitment};
+use blake2::{Blake2s, Digest};
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use rand::Rng;
+use std::{fmt::Debug, marker::PhantomData};
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    extension_product_check, trace_span,
+    virtual_oracle::{
+        generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+        VirtualOracle,
+    },
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+
+pub mod proof;
+mod tests;
+
+/// A binomial extension E = F[u]/(u^d - β) of the scalar field F of a polynomial commitment scheme, whose elements
+/// are given by their d coordinates in the basis 1, u, ..., u^(d - 1)
+pub trait BinomialExtension<F: PrimeField>: Field {
+    /// The degree d of the extension
+    const DEGREE: usize;
+
+    /// The non-residue β = u^d
+    fn nonresidue() -> F;
+
+    fn coordinates(&self) -> Vec<F>;
+
+    /// The element with the given d coordinates
+    fn from_coordinates(coordinates: &[F]) -> Self;
+}
+
+impl<F: PrimeField, P: QuadExtParameters<BaseField = F>> BinomialExtension<F> for QuadExtField<P> {
+    const DEGREE: usize = 2;
+
+    fn nonresidue() -> F {
+        P::NONRESIDUE
+    }
+
+    fn coordinates(&self) -> Vec<F> {
+        vec![self.c0, self.c1]
+    }
+
+    fn from_coordinates(coordinates: &[F]) -> Self {
+        Self::new(coordinates[0], coordinates[1])
+    }
+}
+
+impl<F: PrimeField, P: CubicExtParameters<BaseField = F>> BinomialExtension<F>
+    for CubicExtField<P>
+{
+    const DEGREE: usize = 3;
+
+    fn nonresidue() -> F {
+        P::NONRESIDUE
+    }
+
+    fn coordinates(&self) -> Vec<F> {
+        vec![self.c0, self.c1, self.c2]
+    }
+
+    fn from_coordinates(coordinates: &[F]) -> Self {
+        Self::new(coordinates[0], coordinates[1], coordinates[2])
+    }
+}
+
+/// A function from a domain K of F into an extension E, encoded as the d polynomials over F that interpolate its
+/// coordinates over K. The PCS commits to each of them, under the labels `label_0` to `label_(d - 1)`.
+#[derive(Clone, Debug)]
+pub struct ExtensionFunction<F: PrimeField> {
+    pub coordinates: Vec<LabeledPolynomial<F, DensePolynomial<F>>>,
+}
+
+impl<F: PrimeField> ExtensionFunction<F> {
+    /// Encode the function with the given evaluations over K
+    pub fn from_evals<E: BinomialExtension<F>>(
+        label: &str,
+        evals: &[E],
+        domain: &GeneralEvaluationDomain<F>,
+        degree_bound: Option<usize>,
+        hiding_bound: Option<usize>,
+    ) -> Self {
+        let coordinates = (0..E::DEGREE)
+            .map(|i| {
+                let coordinate_evals = evals
+                    .iter()
+                    .map(|eval| eval.coordinates()[i])
+                    .collect::<Vec<_>>();
+                LabeledPolynomial::new(
+                    format!("{}_{}", label, i),
+                    DensePolynomial::from_coefficients_vec(domain.ifft(&coordinate_evals)),
+                    degree_bound,
+                    hiding_bound,
+                )
+            })
+            .collect();
+
+        Self { coordinates }
+    }
+
+    /// The value of the function at a point of F
+    pub fn evaluate<E: BinomialExtension<F>>(&self, point: &F) -> E {
+        let coordinates = self
+            .coordinates
+            .iter()
+            .map(|coordinate| coordinate.polynomial().evaluate(point))
+            .collect::<Vec<_>>();
+        E::from_coordinates(&coordinates)
+    }
+
+    /// Commit to the coordinate polynomials
+    #[allow(clippy::type_complexity)]
+    pub fn commit<PC: AdditivelyHomomorphicPCS<F>, R: Rng>(
+        &self,
+        ck: &PC::CommitterKey,
+        rng: Option<&mut R>,
+    ) -> Result<
+        (
+            CommittedExtensionFunction<PC::Commitment>,
+            Vec<PC::Randomness>,
+        ),
+        Error,
+    > {
+        let rng = rng.map(|rng| rng as &mut dyn rand::RngCore);
+        let (coordinates, rands) =
+            PC::commit(ck, &self.coordinates, rng).map_err(to_pc_error::<F, PC>)?;
+        Ok((CommittedExtensionFunction { coordinates }, rands))
+    }
+}
+
+/// The commitments to the coordinates of an [`ExtensionFunction`]
+#[derive(Clone, Derivative)]
+#[derivative(
+    Debug(bound = "C: Debug"),
+    PartialEq(bound = "C: PartialEq"),
+    Eq(bound = "C: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct CommittedExtensionFunction<C: PCCommitment> {
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::vec::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::vec::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitments")
+    )]
+    pub coordinates: Vec<LabeledCommitment<C>>,
+}
+
+/// Proves that h = f * g over K for functions f, g and h from K into an extension E of F, each committed to as its
+/// d coordinates over F.
+///
+/// The product in E gives d constraints on the coordinates, h_k = sum_{i + j = k} f_i * g_j + β * sum_{i + j = k + d}
+/// f_i * g_j, which a single ZeroOverK covers once batched with the powers of a challenge, see
+/// [`extension_product_check!`].
+pub struct ExtensionProduct<C: ProtocolConfig, E>
+where
+    E: BinomialExtension<C::F>,
+{
+    _config: PhantomData<C>,
+    _extension: PhantomData<E>,
+}
+
+impl<C: ProtocolConfig, E> ExtensionProduct<C, E>
+where
+    E: BinomialExtension<C::F>,
+{
+    pub const PROTOCOL_NAME: &'static [u8] = b"Extension Product";
+
+    #[cfg(feature = "prover")]
+    /// The randomness of each function is given in the order of its coordinates
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        h: (
+            &ExtensionFunction<C::F>,
+            &CommittedExtensionFunction<pc::Commitment<C>>,
+        ),
+        f: (
+            &ExtensionFunction<C::F>,
+            &CommittedExtensionFunction<pc::Commitment<C>>,
+        ),
+        g: (
+            &ExtensionFunction<C::F>,
+            &CommittedExtensionFunction<pc::Commitment<C>>,
+        ),
+        rands: [&[pc::Randomness<C>]; 3],
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("extension_product::prove");
+        let commitments = [h.1, f.1, g.1];
+        let challenge = Self::product_challenge(domain, &commitments, context, fs_rng)?;
+
+        let oracles = [h.0, f.0, g.0]
+            .iter()
+            .flat_map(|function| &function.coordinates)
+            .collect::<Vec<_>>();
+        let rands = rands.concat();
+        let product_check_proof = ZeroOverK::<C>::prove(
+            &oracles,
+            &Self::coordinate_commitments(&commitments, enforced_degree_bound),
+            &rands,
+            enforced_degree_bound,
+            &Self::product_check_vo(challenge)?,
+            domain,
+            ck,
+            &Self::product_check_context(context, challenge)?,
+            rng,
+        )?;
+
+        Ok(Proof {
+            product_check_proof,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        h_commit: &CommittedExtensionFunction<pc::Commitment<C>>,
+        f_commit: &CommittedExtensionFunction<pc::Commitment<C>>,
+        g_commit: &CommittedExtensionFunction<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        let _span = trace_span!("extension_product::verify");
+        let commitments = [h_commit, f_commit, g_commit];
+        let challenge = Self::product_challenge(domain, &commitments, context, fs_rng)?;
+
+        ZeroOverK::<C>::verify(
+            proof.product_check_proof,
+            &Self::coordinate_commitments(&commitments, enforced_degree_bound),
+            enforced_degree_bound,
+            &Self::product_check_vo(challenge)?,
+            domain,
+            vk,
+            &Self::product_check_context(context, challenge)?,
+        )
+        .map_err(Error::from)
+    }
+
+    /// The coordinates of the functions, relabeled with the enforced degree bound
+    fn coordinate_commitments(
+        commitments: &[&CommittedExtensionFunction<pc::Commitment<C>>; 3],
+        enforced_degree_bound: Option<usize>,
+    ) -> Vec<LabeledCommitment<pc::Commitment<C>>> {
+        commitments
+            .iter()
+            .flat_map(|function| &function.coordinates)
+            .map(|commitment| {
+                LabeledCommitment::new(
+                    commitment.label().clone(),
+                    commitment.commitment().clone(),
+                    enforced_degree_bound,
+                )
+            })
+            .collect()
+    }
+
+    fn product_challenge(
+        domain: &GeneralEvaluationDomain<C::F>,
+        commitments: &[&CommittedExtensionFunction<pc::Commitment<C>>; 3],
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<C::F, Error> {
+        if let Some(function) = commitments
+            .iter()
+            .find(|function| function.coordinates.len() != E::DEGREE)
+        {
+            return Err(Error::InvalidExtensionFunction(format!(
+                "Expected {} coordinates, got {}",
+                E::DEGREE,
+                function.coordinates.len()
+            )));
+        }
+
+        let fs_bytes = &to_bytes![
+            &Self::PROTOCOL_NAME,
+            context,
+            domain.size() as u64,
+            E::DEGREE as u64,
+            E::nonresidue(),
+            commitments
+                .iter()
+                .flat_map(|function| &function.coordinates)
+                .collect::<Vec<_>>()
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        Ok(ChallengeSet::new(&mut *fs_rng, b"extension_product").challenge(b"batching"))
+    }
+
+    /// The product check over the terms [X, h_0, ..., h_(d - 1), f_0, ..., f_(d - 1), g_0, ..., g_(d - 1)]
+    fn product_check_vo(challenge: C::F) -> Result<impl VirtualOracle<C::F>, Error> {
+        let nonresidue = E::nonresidue();
+        let mapping = (0..3 * E::DEGREE).collect::<Vec<_>>();
+        Ok(GenericShiftingVO::new(
+            &mapping,
+            &vec![C::F::one(); mapping.len()],
+            extension_product_check!(nonresidue, E::DEGREE, challenge),
+        )?
+        .with_scaling_factor(2))
+    }
+
+    fn product_check_context(context: &Context, challenge: C::F) -> Result<Context, Error> {
+        let challenge = to_bytes![challenge].map_err(|_| Error::ToBytesError)?;
+        Ok(context
+            .child(b"product_check")
+            .child(&Blake2s::digest(&challenge)))
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::{
+        config::{Config, ProtocolConfig},
+        error::Error,
+        extension_field::{BinomialExtension, ExtensionFunction, ExtensionProduct},
+    };
+    use ark_ff::{to_bytes, One, UniformRand};
+    use ark_mnt4_298::{Fq2, MNT4_298};
+    use ark_mnt6_298::{Fq3, MNT6_298};
+    use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type Mnt6Kzg = Config<ark_mnt6_298::Fr, KZG10<MNT6_298>, FS>;
+    type Mnt4Kzg = Config<ark_mnt4_298::Fr, KZG10<MNT4_298>, FS>;
+
+    /// Prove and verify h = f * g over K for random f and g, with h(gamma^i) multiplied by `tamper(i)`
+    fn run_extension_product<C: ProtocolConfig, E>(tamper: impl Fn(usize) -> E) -> Result<(), Error>
+    where
+        E: BinomialExtension<C::F> + UniformRand,
+    {
+        let rng = &mut thread_rng();
+        let m = 8;
+        let domain_k = GeneralEvaluationDomain::<C::F>::new(m).unwrap();
+        let enforced_degree_bound = m + 1;
+
+        let max_degree = 3 * m;
+        let pp = C::PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = C::PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let f_evals = (0..m).map(|_| E::rand(rng)).collect::<Vec<_>>();
+        let g_evals = (0..m).map(|_| E::rand(rng)).collect::<Vec<_>>();
+        let h_evals = (0..m)
+            .map(|i| f_evals[i] * g_evals[i] * tamper(i))
+            .collect::<Vec<_>>();
+
+        let encode = |label, evals: &[E]| {
+            ExtensionFunction::from_evals(
+                label,
+                evals,
+                &domain_k,
+                Some(enforced_degree_bound),
+                Some(1),
+            )
+        };
+        let (f, g, h) = (
+            encode("f", &f_evals),
+            encode("g", &g_evals),
+            encode("h", &h_evals),
+        );
+        assert_eq!(f.evaluate::<E>(&domain_k.element(3)), f_evals[3]);
+
+        let (f_commit, f_rands) = f.commit::<C::PC, _>(&ck, Some(rng)).unwrap();
+        let (g_commit, g_rands) = g.commit::<C::PC, _>(&ck, Some(rng)).unwrap();
+        let (h_commit, h_rands) = h.commit::<C::PC, _>(&ck, Some(rng)).unwrap();
+
+        let mut fs_rng = C::FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = ExtensionProduct::<C, E>::prove(
+            &ck,
+            &domain_k,
+            (&h, &h_commit),
+            (&f, &f_commit),
+            (&g, &g_commit),
+            [&h_rands, &f_rands, &g_rands],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = C::FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        ExtensionProduct::<C, E>::verify(
+            &vk,
+            &domain_k,
+            &h_commit,
+            &f_commit,
+            &g_commit,
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )
+    }
+
+    #[test]
+    fn test_quadratic_extension_product() {
+        // Fq2 of MNT4-298 extends the scalar field of MNT6-298
+        assert_eq!(
+            run_extension_product::<Mnt6Kzg, Fq2>(|_| Fq2::one()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_cubic_extension_product() {
+        // Fq3 of MNT6-298 extends the scalar field of MNT4-298
+        assert_eq!(
+            run_extension_product::<Mnt4Kzg, Fq3>(|_| Fq3::one()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_wrong_extension_product() {
+        // a product off by u at a single point of K
+        let u = Fq2::from_coordinates(&[Default::default(), One::one()]);
+        assert!(
+            run_extension_product::<Mnt6Kzg, Fq2>(|i| if i == 5 { u } else { Fq2::one() }).is_err()
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::Error;
+use ark_ff::One;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, LinearCombination};
+use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use zero_over_k::config::{pc, ProtocolConfig};
+
+mod tests;
+
+/// An oracle committed to by a third party, under the committer's own label and degree bound, together with the
+/// polynomial and commitment randomness it handed over to the prover
+pub struct ExternalOracle<'a, C: ProtocolConfig> {
+    pub polynomial: &'a LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    pub commitment: &'a LabeledCommitment<pc::Commitment<C>>,
+    pub randomness: &'a pc::Randomness<C>,
+}
+
+/// External oracles brought to the labels and degree bound expected by a protocol
+pub struct NormalizedOracles<C: ProtocolConfig> {
+    pub polynomials: Vec<LabeledPolynomial<C::F, DensePolynomial<C::F>>>,
+    pub commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+    pub rands: Vec<pc::Randomness<C>>,
+}
+
+/// Relabel the external oracles with `labels` and bring them to `enforced_degree_bound`, so that they can be passed
+/// to the protocols unmodified. The commitments are derived homomorphically from the external ones, as the
+/// aggregation of a single commitment with coefficient 1, so that the verifier derives the very same commitments
+/// with [`normalize_external_commitments`].
+///
+/// A commitment keeps its degree bound or drops it: the commitment to a polynomial under a degree bound contains the
+/// commitment to the polynomial without it, as in Marlin KZG. A bound the external committer did not enforce cannot
+/// be added without the committer.
+pub fn normalize_external_oracles<C: ProtocolConfig>(
+    oracles: &[ExternalOracle<C>],
+    labels: &[&str],
+    enforced_degree_bound: Option<usize>,
+) -> Result<NormalizedOracles<C>, Error> {
+    check_labels(oracles.len(), labels)?;
+
+    let mut normalized = NormalizedOracles {
+        polynomials: Vec::with_capacity(oracles.len()),
+        commitments: Vec::with_capacity(oracles.len()),
+        rands: Vec::with_capacity(oracles.len()),
+    };
+
+    for (oracle, &label) in oracles.iter().zip(labels) {
+        if let Some(bound) = enforced_degree_bound {
+            if oracle.polynomial.degree() > bound {
+                return Err(Error::IncompatibleExternalOracle(format!(
+                    "{} has degree {}, above the enforced degree bound {}",
+                    oracle.polynomial.label(),
+                    oracle.polynomial.degree(),
+                    bound
+                )));
+            }
+        }
+
+        let (commitment, randomness) = normalize_commitment::<C>(
+            oracle.commitment,
+            Some(oracle.randomness.clone()),
+            label,
+            enforced_degree_bound,
+        )?;
+
+        normalized.polynomials.push(LabeledPolynomial::new(
+            String::from(label),
+            oracle.polynomial.polynomial().clone(),
+            enforced_degree_bound,
+            oracle.polynomial.hiding_bound(),
+        ));
+        normalized.commitments.push(commitment);
+        normalized.rands.push(randomness);
+    }
+
+    Ok(normalized)
+}
+
+/// The verifier side of [`normalize_external_oracles`]
+pub fn normalize_external_commitments<C: ProtocolConfig>(
+    commitments: &[LabeledCommitment<pc::Commitment<C>>],
+    labels: &[&str],
+    enforced_degree_bound: Option<usize>,
+) -> Result<Vec<LabeledCommitment<pc::Commitment<C>>>, Error> {
+    check_labels(commitments.len(), labels)?;
+
+    commitments
+        .iter()
+        .zip(labels)
+        .map(|(commitment, &label)| {
+            normalize_commitment::<C>(commitment, None, label, enforced_degree_bound)
+                .map(|(commitment, _)| commitment)
+        })
+        .collect()
+}
+
+fn check_labels(number_of_oracles: usize, labels: &[&str]) -> Result<(), Error> {
+    if number_of_oracles != labels.len() {
+        return Err(Error::IncompatibleExternalOracle(format!(
+            "Got {} oracles but {} labels",
+            number_of_oracles,
+            labels.len()
+        )));
+    }
+
+    Ok(())
+}
+
+fn normalize_commitment<C: ProtocolConfig>(
+    commitment: &LabeledCommitment<pc::Commitment<C>>,
+    randomness: Option<pc::Randomness<C>>,
+    label: &str,
+    enforced_degree_bound: Option<usize>,
+) -> Result<(LabeledCommitment<pc::Commitment<C>>, pc::Randomness<C>), Error> {
+    match (commitment.degree_bound(), enforced_degree_bound) {
+        (external, enforced) if external == enforced => {}
+        (Some(_), None) => {}
+        (external, enforced) => {
+            return Err(Error::IncompatibleExternalOracle(format!(
+                "{} was committed with degree bound {:?}, which cannot be turned into {:?}",
+                commitment.label(),
+                external,
+                enforced
+            )))
+        }
+    }
+
+    // aggregating under the enforced degree bound leaves out a shifted part that is no longer needed
+    let source = LabeledCommitment::new(
+        commitment.label().clone(),
+        commitment.commitment().clone(),
+        enforced_degree_bound,
+    );
+    let relabel = LinearCombination::new(label, vec![(C::F::one(), commitment.label().clone())]);
+
+    C::PC::aggregate_commitments(&[source], randomness.map(|rand| vec![rand]), &relabel)
+        .map_err(Error::from)
+}
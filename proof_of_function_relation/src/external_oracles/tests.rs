@@ -0,0 +1,179 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        equal_on_subdomain::EqualOnSubdomain,
+        error::Error,
+        external_oracles::{
+            normalize_external_commitments, normalize_external_oracles, ExternalOracle,
+        },
+        proof_of_knowledge::ProofOfKnowledge,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    };
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+    use zero_over_k::util::sample_vector;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
+
+    #[test]
+    fn test_external_oracles_with_clashing_labels() {
+        let m = 8;
+        let rng = &mut thread_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let enforced_degree_bound = m + 1;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        // two committers hand over a polynomial each, under the same label
+        let f_evals: Vec<F> = sample_vector(rng, m);
+        let external = |evals: &[F]| {
+            LabeledPolynomial::new(
+                String::from("poly"),
+                DensePolynomial::from_coefficients_vec(domain_k.ifft(evals)),
+                Some(enforced_degree_bound),
+                Some(1),
+            )
+        };
+        let f = external(&f_evals);
+        let g = external(&f_evals);
+        let (f_commit, f_rand) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let (g_commit, g_rand) = PC::commit(&ck, [&g], Some(rng)).unwrap();
+
+        let oracles = [
+            ExternalOracle::<C> {
+                polynomial: &f,
+                commitment: &f_commit[0],
+                randomness: &f_rand[0],
+            },
+            ExternalOracle::<C> {
+                polynomial: &g,
+                commitment: &g_commit[0],
+                randomness: &g_rand[0],
+            },
+        ];
+        let normalized =
+            normalize_external_oracles(&oracles, &["f", "g"], Some(enforced_degree_bound)).unwrap();
+
+        let subdomain = [0, 1, 2, 3];
+        let proof = EqualOnSubdomain::<C>::prove(
+            &ck,
+            &domain_k,
+            &subdomain,
+            &normalized.polynomials[0],
+            &normalized.commitments[0],
+            &normalized.rands[0],
+            &normalized.polynomials[1],
+            &normalized.commitments[1],
+            &normalized.rands[1],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        // the verifier only sees the external commitments
+        let commitments = normalize_external_commitments::<C>(
+            &[f_commit[0].clone(), g_commit[0].clone()],
+            &["f", "g"],
+            Some(enforced_degree_bound),
+        )
+        .unwrap();
+
+        assert_eq!(
+            EqualOnSubdomain::<C>::verify(
+                &vk,
+                &ck,
+                &domain_k,
+                &subdomain,
+                &commitments[0],
+                &commitments[1],
+                Some(enforced_degree_bound),
+                proof,
+                &Context::new(b"test"),
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_drop_external_degree_bound() {
+        let rng = &mut thread_rng();
+        let max_degree = 20;
+        let external_degree_bound = 9;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[external_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("external_f"),
+            DensePolynomial::rand(7, rng),
+            Some(external_degree_bound),
+            Some(1),
+        );
+        let (f_commit, f_rand) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        let oracles = [ExternalOracle::<C> {
+            polynomial: &f,
+            commitment: &f_commit[0],
+            randomness: &f_rand[0],
+        }];
+        let normalized = normalize_external_oracles(&oracles, &["f"], None).unwrap();
+        assert_eq!(normalized.commitments[0].degree_bound(), None);
+
+        let proof = ProofOfKnowledge::<C>::prove(
+            &ck,
+            &normalized.polynomials[0],
+            &normalized.commitments[0],
+            &normalized.rands[0],
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        let commitments = normalize_external_commitments::<C>(&f_commit, &["f"], None).unwrap();
+
+        assert_eq!(
+            ProofOfKnowledge::<C>::verify(
+                &vk,
+                &commitments[0],
+                None,
+                proof,
+                &Context::new(b"test"),
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_cannot_add_degree_bound() {
+        let rng = &mut thread_rng();
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, max_degree, 1, Some(&[9])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("external_f"),
+            DensePolynomial::rand(7, rng),
+            None,
+            Some(1),
+        );
+        let (f_commit, _) = PC::commit(&ck, &[f], Some(rng)).unwrap();
+
+        assert!(matches!(
+            normalize_external_commitments::<C>(&f_commit, &["f"], Some(9)),
+            Err(Error::IncompatibleExternalOracle(_))
+        ));
+    }
+}
@@ -1,67 +1,314 @@
 use crate::error::{to_pc_error, Error};
 use crate::geo_seq::proof::Proof;
-use ark_ff::{to_bytes, PrimeField};
-use ark_poly::{univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
-use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, QuerySet};
+use ark_ff::{to_bytes, Field, One};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::{LabeledCommitment, PolynomialCommitment, QuerySet};
 use ark_std::marker::PhantomData;
-use fiat_shamir_rng::FiatShamirRng;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
-use rand::Rng;
+use blake2::{Blake2s, Digest};
+use fiat_shamir_rng::{ChallengeSet, Context, ProtocolRng, Transcript};
+use homomorphic_poly_commit::{commit_policy::CommitPolicy, AdditivelyHomomorphicPCS};
 use rand_core::OsRng;
 use std::iter;
+use zero_over_k::config::{pc, ProtocolConfig};
 use zero_over_k::{
+    coset_domain::CosetDomain,
+    proof_shape::ProofShape,
+    trace_span,
     virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
     zero_over_k::ZeroOverK,
     {geometric_seq_check, vo_constant},
 };
+#[cfg(feature = "prover")]
+use {
+    ark_poly::univariate::DensePolynomial, ark_poly_commit::LabeledPolynomial, rand::Rng,
+    zero_over_k::cancellation::CancellationToken,
+};
 
 pub mod proof;
 mod tests;
 
-pub struct GeoSeqTest<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> {
-    _field: PhantomData<F>,
-    _pc: PhantomData<PC>,
-    _fs: PhantomData<FS>,
+pub struct GeoSeqTest<C: ProtocolConfig> {
+    _config: PhantomData<C>,
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTest<F, PC, FS> {
+impl<C: ProtocolConfig> GeoSeqTest<C> {
     pub const PROTOCOL_NAME: &'static [u8] = b"Geometric Sequence Test";
+
+    /// Check and encode the public description of the sequence: the common ratio, the initial values and lengths of
+    /// the sub-sequences, and the domain the sequence is laid over. Both lists are length-prefixed so that moving a
+    /// boundary between them changes the encoding.
+    fn sequence_description(
+        common_ratio: C::F,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+    ) -> Result<Vec<u8>, Error> {
+        if sequence_initial_values.len() != sequence_lengths.len() {
+            return Err(Error::InvalidSequenceDescription(format!(
+                "Got {} initial values but {} sub-sequence lengths",
+                sequence_initial_values.len(),
+                sequence_lengths.len()
+            )));
+        }
+        if sequence_lengths.contains(&0) {
+            return Err(Error::InvalidSequenceDescription(String::from(
+                "A sub-sequence is empty",
+            )));
+        }
+        let m = sequence_lengths
+            .iter()
+            .try_fold(0usize, |acc, length| acc.checked_add(*length))
+            .ok_or_else(|| {
+                Error::InvalidSequenceDescription(String::from(
+                    "The sub-sequence lengths overflow a usize",
+                ))
+            })?;
+        if m != domain.size() {
+            return Err(Error::InvalidSequenceDescription(format!(
+                "The sub-sequences cover {} elements of a domain of size {}",
+                m,
+                domain.size()
+            )));
+        }
+
+        to_bytes![
+            common_ratio,
+            sequence_initial_values.len() as u64,
+            sequence_initial_values,
+            sequence_lengths.len() as u64,
+            sequence_lengths
+                .iter()
+                .map(|&x| x as u64)
+                .collect::<Vec<_>>(),
+            domain.size() as u64,
+            domain.element(1)
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+
+    /// The zero over K sub-proof runs in its own transcript, so bind it to the sequence description as well
+    fn zero_over_k_context(context: &Context, description: &[u8]) -> Context {
+        context
+            .child(b"zero_over_k")
+            .child(&Blake2s::digest(description))
+    }
+
+    /// The queries of a proof that the polynomial labeled `f_label` holds the sequence: f at the start of each
+    /// sub-sequence, and the ZeroOverK showing that f(gamma * X) = r * f(X) within each of them
+    pub fn prove_dry_run(
+        common_ratio: C::F,
+        f_label: &str,
+        f_degree_bound: Option<usize>,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+    ) -> Result<ProofShape, Error> {
+        Self::sequence_description(
+            common_ratio,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+        )?;
+        let geo_seq_vo = GenericShiftingVO::new(
+            &[0, 0],
+            &[C::F::one(), domain.element(1)],
+            geometric_seq_check!(common_ratio, sequence_lengths, domain),
+        )?
+        .with_scaling_factor(2);
+
+        // the starting indices run up to the end of the last sub-sequence
+        let mut shape = ProofShape::new();
+        for i in 0..=sequence_lengths.len() {
+            shape.query(f_label, format!("gamma_pi_{}", i));
+        }
+        shape.challenge("separation_challenge");
+        shape.check(
+            "initial_values",
+            format!(
+                "{}(gamma^pi_i) = a_i for each sub-sequence i, as a batch opening under separation_challenge",
+                f_label
+            ),
+        );
+        shape.extend(
+            "zero_over_k",
+            ZeroOverK::<C>::prove_dry_run(f_degree_bound, &geo_seq_vo),
+        );
+        Ok(shape)
+    }
+
+    /// Serialized size in bytes of a proof that an f with degree bound `f_degree_bound`, hiding or not, holds the
+    /// sequence, with keys of degree `supported_degree`
+    #[allow(clippy::too_many_arguments)]
+    pub fn proof_size(
+        common_ratio: C::F,
+        f_degree_bound: Option<usize>,
+        f_hiding: bool,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        Self::sequence_description(
+            common_ratio,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+        )?;
+        let geo_seq_vo = GenericShiftingVO::new(
+            &[0, 0],
+            &[C::F::one(), domain.element(1)],
+            geometric_seq_check!(common_ratio, sequence_lengths, domain),
+        )?
+        .with_scaling_factor(2);
+
+        Ok(ZeroOverK::<C>::proof_size(
+            f_degree_bound,
+            &geo_seq_vo,
+            CommitPolicy::NonHiding,
+            supported_degree,
+        ) + C::PC::batch_proof_size(
+            supported_degree,
+            &vec![f_hiding; sequence_lengths.len() + 1],
+        ))
+    }
+
     // TODO: for both prove() and verify:
     // TODO: have an assertion that domain is large enough given m
     // TODO: move the padding outside and the check that the length is correct
-    // TODO: verifier should check that the size of the sequence is correct given the domain
+    #[cfg(feature = "prover")]
     pub fn prove<R: Rng>(
-        ck: &PC::CommitterKey,
-        common_ratio: F,
-        f: &LabeledPolynomial<F, DensePolynomial<F>>,
-        f_commit: &LabeledCommitment<PC::Commitment>,
-        f_rand: &PC::Randomness,
-        sequence_initial_values: &Vec<F>,
-        sequence_lengths: &Vec<usize>,
-        domain: &GeneralEvaluationDomain<F>,
+        ck: &pc::CommitterKey<C>,
+        common_ratio: C::F,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        context: &Context,
         rng: &mut R,
-    ) -> Result<Proof<F, PC>, Error> {
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            ck,
+            common_ratio,
+            f,
+            f_commit,
+            f_rand,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+            context,
+            None,
+            None,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove`], polling `cancellation` between the rounds, see [`zero_over_k::cancellation`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_cancellation<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        common_ratio: C::F,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        context: &Context,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            ck,
+            common_ratio,
+            f,
+            f_commit,
+            f_rand,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+            context,
+            None,
+            cancellation,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove`], on a transcript managed by the caller
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_transcript<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        common_ratio: C::F,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            ck,
+            common_ratio,
+            f,
+            f_commit,
+            f_rand,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+            context,
+            Some(transcript),
+            None,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    fn prove_in<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        common_ratio: C::F,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("geo_seq::prove");
         // Generate the GeoSequenceVO virtual oracle
-        let alphas = [F::one(), domain.element(1)];
+        let alphas = [C::F::one(), domain.element(1)];
         let geo_seq_vo = GenericShiftingVO::new(
             &[0, 0],
             &alphas,
             geometric_seq_check!(common_ratio, sequence_lengths, domain),
-        )?;
+        )?
+        .with_scaling_factor(2);
 
+        let description = Self::sequence_description(
+            common_ratio,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+        )?;
         let fs_bytes = &to_bytes![
             &Self::PROTOCOL_NAME,
-            sequence_initial_values,
-            sequence_lengths
-                .iter()
-                .map(|&x| x as u64)
-                .collect::<Vec<_>>(),
-            common_ratio,
+            context,
+            description,
             &[f_commit.clone()].to_vec(),
             &alphas.to_vec()
         ]
         .map_err(|_| Error::ToBytesError)?;
-        let mut fs_rng = FS::initialize(fs_bytes);
+        let mut fs_rng = ProtocolRng::<C::FS>::new(transcript, fs_bytes);
 
         let mut query_set = QuerySet::new();
         let sequence_starting_indices = iter::once(0)
@@ -80,8 +327,9 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
             ));
         }
 
-        let separation_challenge = F::rand(&mut fs_rng);
-        let opening_proof = PC::batch_open(
+        let separation_challenge: C::F =
+            ChallengeSet::new(&mut fs_rng, b"opening").challenge(b"separation_challenge");
+        let opening_proof = C::PC::batch_open(
             ck,
             &[f.clone()],
             &[f_commit.clone()],
@@ -90,20 +338,24 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
             &[f_rand.clone()],
             Some(rng),
         )
-        .map_err(to_pc_error::<F, PC>)?;
+        .map_err(to_pc_error::<C::F, C::PC>)?;
 
-        let z_proof = ZeroOverK::<F, PC, FS>::prove(
-            &[f.clone()],
+        let z_proof = ZeroOverK::<C>::prove_in(
+            &[f],
             &[f_commit.clone()],
             &[f_rand.clone()],
             f.degree_bound(),
             &geo_seq_vo,
-            &domain,
-            &ck,
+            &CosetDomain::subgroup(*domain),
+            ck,
+            CommitPolicy::NonHiding,
+            &Self::zero_over_k_context(context, &description),
+            fs_rng.sub_transcript(),
+            cancellation,
             rng,
         )?;
 
-        let proof = Proof::<F, PC> {
+        let proof = Proof::<C> {
             z_proof,
             opening_proof,
         };
@@ -111,27 +363,93 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
     }
 
     pub fn verify(
-        common_ratio: F,
-        sequence_initial_values: &Vec<F>,
-        sequence_lengths: &Vec<usize>,
-        domain: &GeneralEvaluationDomain<F>,
-        f_commit: &LabeledCommitment<PC::Commitment>,
+        common_ratio: C::F,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        Self::verify_in(
+            common_ratio,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+            f_commit,
+            enforced_degree_bound,
+            proof,
+            vk,
+            context,
+            None,
+        )
+    }
+
+    /// Verify a proof of [`Self::prove_with_transcript`], on a transcript in the state the prover's was in
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_transcript(
+        common_ratio: C::F,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+    ) -> Result<(), Error> {
+        Self::verify_in(
+            common_ratio,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+            f_commit,
+            enforced_degree_bound,
+            proof,
+            vk,
+            context,
+            Some(transcript),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn verify_in(
+        common_ratio: C::F,
+        sequence_initial_values: &[C::F],
+        sequence_lengths: &[usize],
+        domain: &GeneralEvaluationDomain<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
         enforced_degree_bound: Option<usize>,
-        proof: Proof<F, PC>,
-        vk: &PC::VerifierKey,
+        proof: Proof<C>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
     ) -> Result<(), Error> {
+        let _span = trace_span!("geo_seq::verify");
         let bounded_f_commit = LabeledCommitment::new(
             f_commit.label().clone(),
             f_commit.commitment().clone(),
             enforced_degree_bound,
         );
 
-        let alphas = [F::one(), domain.element(1)];
+        // check the description first: the starting indices below are only meaningful for a well-formed one
+        let description = Self::sequence_description(
+            common_ratio,
+            sequence_initial_values,
+            sequence_lengths,
+            domain,
+        )?;
+
+        let alphas = [C::F::one(), domain.element(1)];
         let geo_seq_vo = GenericShiftingVO::new(
             &[0, 0],
             &alphas,
             geometric_seq_check!(common_ratio, sequence_lengths, domain),
-        )?;
+        )?
+        .with_scaling_factor(2);
 
         // Test that for all i in n, check that f(gamma^p_i) = a_i
         let sequence_starting_indices = iter::once(0)
@@ -147,17 +465,13 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
 
         let fs_bytes = &to_bytes![
             &Self::PROTOCOL_NAME,
-            sequence_initial_values,
-            sequence_lengths
-                .iter()
-                .map(|&x| x as u64)
-                .collect::<Vec<_>>(),
-            common_ratio,
+            context,
+            description,
             &[f_commit.clone()].to_vec(),
             &alphas.to_vec()
         ]
         .map_err(|_| Error::ToBytesError)?;
-        let mut fs_rng = FS::initialize(fs_bytes);
+        let mut fs_rng = ProtocolRng::<C::FS>::new(transcript, fs_bytes);
 
         let mut query_set = QuerySet::new();
         for (i, &point_i) in points.iter().enumerate() {
@@ -171,8 +485,9 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
             evaluations.insert((bounded_f_commit.label().clone(), point_i), a_i);
         }
 
-        let separation_challenge = F::rand(&mut fs_rng);
-        match PC::batch_check(
+        let separation_challenge: C::F =
+            ChallengeSet::new(&mut fs_rng, b"opening").challenge(b"separation_challenge");
+        match C::PC::batch_check(
             vk,
             &[bounded_f_commit.clone()],
             &query_set,
@@ -183,7 +498,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
         ) {
             Ok(true) => Ok(()),
             Ok(false) => Err(Error::BatchCheckError),
-            Err(e) => panic!("{:?}", e),
+            Err(e) => Err(to_pc_error::<C::F, C::PC>(e)),
         }?;
 
         // TODO: is this check done or does the function return before it should if the above batch
@@ -193,13 +508,15 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
         // let f = DensePolynomial::<F>::from_coefficients_slice(&domain.ifft(&seq));
 
         // TODO: raise a different error?
-        ZeroOverK::<F, PC, FS>::verify(
+        ZeroOverK::<C>::verify_in(
             proof.z_proof,
             &[bounded_f_commit.clone()],
             enforced_degree_bound,
             &geo_seq_vo,
-            &domain,
+            &CosetDomain::subgroup(*domain),
             vk,
+            &Self::zero_over_k_context(context, &description),
+            fs_rng.sub_transcript(),
         )?;
 
         Ok(())
@@ -207,7 +524,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
 
     #[allow(dead_code)]
     /// Inefficiently verify that the sequence is valid
-    pub fn naive_verify(seq: &Vec<F>, r: F, a_s: &[F], c_s: &[usize]) -> bool {
+    pub fn naive_verify(seq: &[C::F], r: C::F, a_s: &[C::F], c_s: &[usize]) -> bool {
         if a_s.len() != c_s.len() {
             return false;
         }
@@ -218,7 +535,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> GeoSeqTe
 
         let mut i = 0;
         for (a_i, a) in a_s.iter().enumerate() {
-            let mut r_pow = F::from(1 as u64);
+            let mut r_pow = C::F::from(1 as u64);
             for _ in 0..c_s[a_i] {
                 let expected = *a * r_pow;
 
@@ -1,12 +1,24 @@
-use ark_ff::PrimeField;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
 use zero_over_k::zero_over_k::proof::Proof as ZProof;
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Write};
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
-    pub z_proof: ZProof<F, PC>,
-    pub opening_proof: PC::BatchProof,
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig> {
+    pub z_proof: ZProof<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub opening_proof: pc::BatchProof<C>,
 }
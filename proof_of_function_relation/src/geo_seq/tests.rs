@@ -1,20 +1,27 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod tests {
-    use crate::{error::Error, geo_seq::GeoSeqTest, util::generate_sequence};
+    use crate::config::Config;
+    use crate::{
+        error::Error,
+        geo_seq::GeoSeqTest,
+        util::{generate_sequence, interpolate_sequence, SEQUENCE_CHUNK_SIZE},
+    };
     use ark_bn254::{Bn254, Fr};
     use ark_poly::{
         univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
     };
-    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_poly_commit::{LabeledPolynomial, PCCommitterKey, PolynomialCommitment};
+    use ark_serialize::CanonicalSerialize;
     use ark_std::rand::thread_rng;
     use blake2::Blake2s;
-    use fiat_shamir_rng::SimpleHashFiatShamirRng;
+    use fiat_shamir_rng::{Context, FiatShamirTranscript, SimpleHashFiatShamirRng, Transcript};
     use homomorphic_poly_commit::marlin_kzg::KZG10;
     use rand_chacha::ChaChaRng;
 
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
     type F = Fr;
     type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
 
     /// Test that geometric_sequence() works correctly
     #[test]
@@ -33,7 +40,7 @@ mod tests {
             assert_eq!(&expected[i], s);
         }
 
-        assert!(GeoSeqTest::<F, KZG10<Bn254>, FS>::naive_verify(
+        assert!(GeoSeqTest::<C>::naive_verify(
             &seq,
             common_ratio,
             sequence_initial_values,
@@ -56,7 +63,7 @@ mod tests {
         for (i, s) in seq.iter().enumerate() {
             assert_eq!(&expected[i], s);
         }
-        assert!(GeoSeqTest::<F, KZG10<Bn254>, FS>::naive_verify(
+        assert!(GeoSeqTest::<C>::naive_verify(
             &seq,
             common_ratio,
             sequence_initial_values,
@@ -64,6 +71,22 @@ mod tests {
         ));
     }
 
+    /// Test that interpolate_sequence() agrees with generate_sequence() across chunk boundaries and with padding
+    #[test]
+    fn test_interpolate_sequence() {
+        let domain = GeneralEvaluationDomain::<F>::new(4 * SEQUENCE_CHUNK_SIZE).unwrap();
+        let common_ratio = domain.element(1);
+        let initial_values = &[F::from(3u64), F::from(5u64), F::from(7u64)];
+        let lengths = &[SEQUENCE_CHUNK_SIZE + 1, 2 * SEQUENCE_CHUNK_SIZE - 3, 5];
+
+        let seq = generate_sequence(common_ratio, initial_values, lengths);
+        let expected = DensePolynomial::from_coefficients_vec(domain.ifft(&seq));
+
+        assert_eq!(
+            interpolate_sequence(common_ratio, initial_values, lengths, &domain),
+            expected
+        );
+    }
     #[test]
     fn test_geo_seq_proof() {
         let rng = &mut thread_rng();
@@ -123,20 +146,33 @@ mod tests {
 
         let (commitment, rands) = PC::commit(&ck, &[f.clone()], Some(rng)).unwrap();
 
-        let proof = GeoSeqTest::<F, KZG10<Bn254>, FS>::prove(
+        let proof = GeoSeqTest::<C>::prove(
             &ck,
             common_ratio,
             &f,
             &commitment[0].clone(),
             &rands[0].clone(),
-            &mut sequence_initial_values,
-            &mut sequence_lengths,
+            &sequence_initial_values,
+            &sequence_lengths,
             &domain_k,
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
 
-        let res = GeoSeqTest::<F, KZG10<Bn254>, FS>::verify(
+        let size = GeoSeqTest::<C>::proof_size(
+            common_ratio,
+            Some(enforced_degree_bound),
+            true,
+            &sequence_initial_values,
+            &sequence_lengths,
+            &domain_k,
+            ck.supported_degree(),
+        )
+        .unwrap();
+        assert_eq!(size, proof.serialized_size());
+
+        let res = GeoSeqTest::<C>::verify(
             common_ratio,
             &sequence_initial_values,
             &sequence_lengths,
@@ -145,6 +181,7 @@ mod tests {
             Some(enforced_degree_bound),
             proof,
             &vk,
+            &Context::new(b"test"),
         )
         .unwrap();
 
@@ -212,20 +249,21 @@ mod tests {
 
         let wrong_common_ratio = Fr::from(2u64);
 
-        let proof = GeoSeqTest::<F, KZG10<Bn254>, FS>::prove(
+        let proof = GeoSeqTest::<C>::prove(
             &ck,
             wrong_common_ratio,
             &f,
             &commitment[0].clone(),
             &rands[0].clone(),
-            &mut sequence_initial_values,
-            &mut sequence_lengths,
+            &sequence_initial_values,
+            &sequence_lengths,
             &domain_k,
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
 
-        let res = GeoSeqTest::<F, KZG10<Bn254>, FS>::verify(
+        let res = GeoSeqTest::<C>::verify(
             common_ratio,
             &sequence_initial_values,
             &sequence_lengths,
@@ -234,6 +272,7 @@ mod tests {
             Some(enforced_degree_bound),
             proof,
             &vk,
+            &Context::new(b"test"),
         );
 
         assert!(common_ratio != wrong_common_ratio);
@@ -304,20 +343,21 @@ mod tests {
 
         let (commitment, rands) = PC::commit(&ck, &[f.clone()], Some(rng)).unwrap();
 
-        let proof = GeoSeqTest::<F, KZG10<Bn254>, FS>::prove(
+        let proof = GeoSeqTest::<C>::prove(
             &ck,
             common_ratio,
             &f,
             &commitment[0].clone(),
             &rands[0].clone(),
-            &mut sequence_initial_values,
-            &mut sequence_lengths,
+            &sequence_initial_values,
+            &sequence_lengths,
             &domain_k,
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
 
-        let res = GeoSeqTest::<F, KZG10<Bn254>, FS>::verify(
+        let res = GeoSeqTest::<C>::verify(
             common_ratio,
             &sequence_initial_values,
             &sequence_lengths,
@@ -326,10 +366,178 @@ mod tests {
             Some(enforced_degree_bound),
             proof,
             &vk,
+            &Context::new(b"test"),
         );
 
         assert!(res.is_err());
 
         assert_eq!(res.err().unwrap(), Error::BatchCheckError);
     }
+
+    /// A public description of a geometric sequence: the common ratio, the initial values and lengths of the
+    /// sub-sequences, and the size of the domain
+    type Description<'a> = (u64, &'a [u64], &'a [usize], usize);
+
+    /// Prove that the sequence given by `prover` is committed to, and verify the proof against `verifier`
+    fn run_with_descriptions(prover: Description, verifier: Description) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let to_field = |values: &[u64]| values.iter().map(|&x| F::from(x)).collect::<Vec<_>>();
+
+        let (prover_ratio, prover_values, prover_lengths, prover_size) = prover;
+        let prover_values = to_field(prover_values);
+        let prover_domain = GeneralEvaluationDomain::<F>::new(prover_size).unwrap();
+        let seq = generate_sequence(F::from(prover_ratio), &prover_values, prover_lengths);
+
+        let max_degree = 40;
+        let enforced_degree_bound = 17;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, 9, enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::from_coefficients_slice(&prover_domain.ifft(&seq)),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (commitment, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        let proof = GeoSeqTest::<C>::prove(
+            &ck,
+            F::from(prover_ratio),
+            &f,
+            &commitment[0],
+            &rands[0],
+            &prover_values,
+            prover_lengths,
+            &prover_domain,
+            &Context::new(b"test"),
+            rng,
+        )?;
+
+        let (verifier_ratio, verifier_values, verifier_lengths, verifier_size) = verifier;
+        GeoSeqTest::<C>::verify(
+            F::from(verifier_ratio),
+            &to_field(verifier_values),
+            verifier_lengths,
+            &GeneralEvaluationDomain::<F>::new(verifier_size).unwrap(),
+            &commitment[0],
+            Some(enforced_degree_bound),
+            proof,
+            &vk,
+            &Context::new(b"test"),
+        )
+    }
+
+    #[test]
+    fn test_matching_descriptions() {
+        let description = (3, &[1, 2][..], &[5, 3][..], 8);
+        assert_eq!(run_with_descriptions(description, description), Ok(()));
+    }
+
+    /// A constant sequence of ones fits each of the descriptions below, so only the transcript tells them apart
+    #[test]
+    fn test_reject_mismatched_ratio() {
+        assert!(run_with_descriptions((1, &[1], &[8], 8), (2, &[1], &[8], 8)).is_err());
+    }
+
+    #[test]
+    fn test_reject_mismatched_lengths() {
+        assert!(run_with_descriptions((1, &[1, 1], &[4, 4], 8), (1, &[1, 1], &[2, 6], 8)).is_err());
+    }
+
+    #[test]
+    fn test_reject_malformed_description() {
+        assert!(matches!(
+            run_with_descriptions((1, &[1], &[8], 8), (1, &[1, 1], &[8], 8)),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+        assert!(matches!(
+            run_with_descriptions((1, &[1], &[8], 8), (1, &[1], &[8], 16)),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+    }
+
+    #[test]
+    fn test_reject_overflowing_lengths() {
+        assert!(matches!(
+            run_with_descriptions((1, &[1], &[8], 8), (1, &[1, 1], &[usize::MAX, 9], 8)),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+        assert!(matches!(
+            run_with_descriptions((1, &[1], &[8], 8), (1, &[1, 1], &[0, 8], 8)),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+    }
+
+    #[test]
+    fn test_reject_mismatched_initial_values() {
+        assert!(run_with_descriptions((3, &[1, 2], &[5, 3], 8), (3, &[1, 5], &[5, 3], 8)).is_err());
+    }
+
+    #[test]
+    fn test_reject_mismatched_domain() {
+        assert!(run_with_descriptions((1, &[1], &[8], 8), (1, &[1], &[16], 16)).is_err());
+    }
+
+    #[test]
+    fn test_geo_seq_with_transcript() {
+        let rng = &mut thread_rng();
+        let common_ratio = Fr::from(9u64);
+        let sequence_initial_values = vec![Fr::from(2u64), Fr::from(5u64)];
+        let sequence_lengths = vec![3, 5];
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let seq = generate_sequence::<F>(common_ratio, &sequence_initial_values, &sequence_lengths);
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&seq)),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (commitment, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        // the transcript of a larger protocol, which absorbed its own statement before running the test
+        let outer_transcript = |statement: &[u8]| {
+            let mut transcript = FiatShamirTranscript::<FS>::new(b"outer");
+            transcript.append_message(b"outer_statement", statement);
+            transcript
+        };
+
+        let prove_and_verify = |verifier_statement: &[u8], rng: &mut _| {
+            let proof = GeoSeqTest::<C>::prove_with_transcript(
+                &ck,
+                common_ratio,
+                &f,
+                &commitment[0],
+                &rands[0],
+                &sequence_initial_values,
+                &sequence_lengths,
+                &domain_k,
+                &Context::new(b"test"),
+                &mut outer_transcript(b"statement"),
+                rng,
+            )
+            .unwrap();
+            GeoSeqTest::<C>::verify_with_transcript(
+                common_ratio,
+                &sequence_initial_values,
+                &sequence_lengths,
+                &domain_k,
+                &commitment[0],
+                Some(enforced_degree_bound),
+                proof,
+                &vk,
+                &Context::new(b"test"),
+                &mut outer_transcript(verifier_statement),
+            )
+        };
+
+        assert_eq!(prove_and_verify(b"statement", rng), Ok(()));
+        // the challenges depend on what the larger protocol absorbed before
+        assert!(prove_and_verify(b"another statement", rng).is_err());
+    }
 }
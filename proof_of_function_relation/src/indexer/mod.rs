@@ -0,0 +1,446 @@
+use crate::domains::{DomainH, DomainK};
+use crate::error::Error;
+use ark_ff::{PrimeField, ToBytes};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain, Evaluations as EvaluationsOnDomain};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PCCommitment};
+use derivative::Derivative;
+use std::{fmt::Debug, io::Write};
+
+#[cfg(all(test, feature = "prover"))]
+pub(crate) mod test_utils;
+mod tests;
+
+/// A sparse matrix, stored row by row as a list of (value, column index) pairs
+pub type Matrix<F> = Vec<Vec<(F, usize)>>;
+
+/// Evaluations over K of the polynomials encoding a sparse matrix
+#[derive(Clone, Debug)]
+pub struct MatrixEvals<F: PrimeField> {
+    pub row: EvaluationsOnDomain<F>,
+    pub col: EvaluationsOnDomain<F>,
+    pub val: EvaluationsOnDomain<F>,
+    pub row_col: EvaluationsOnDomain<F>,
+}
+
+/// The polynomials encoding a sparse matrix M over a domain K. For the i-th non-zero entry M[r][c] = v we have
+/// row(gamma^i) = omega^r, col(gamma^i) = omega^c and val(gamma^i) = v, where gamma generates K and omega generates H.
+#[derive(Clone, Debug)]
+pub struct MatrixArith<F: PrimeField> {
+    pub row: LabeledPolynomial<F, DensePolynomial<F>>,
+    pub col: LabeledPolynomial<F, DensePolynomial<F>>,
+    pub val: LabeledPolynomial<F, DensePolynomial<F>>,
+    /// The product row * col, as used in the Marlin inner sumcheck
+    pub row_col: LabeledPolynomial<F, DensePolynomial<F>>,
+
+    pub evals_on_k: MatrixEvals<F>,
+}
+
+impl<F: PrimeField> MatrixArith<F> {
+    /// Prefix every label with `prefix` and attach the given degree and hiding bounds to each polynomial
+    pub fn with_bounds(
+        self,
+        prefix: &str,
+        degree_bound: Option<usize>,
+        hiding_bound: Option<usize>,
+    ) -> Self {
+        let relabel = |p: LabeledPolynomial<F, DensePolynomial<F>>| {
+            LabeledPolynomial::new(
+                format!("{}_{}", prefix, p.label()),
+                p.polynomial().clone(),
+                degree_bound,
+                hiding_bound,
+            )
+        };
+
+        Self {
+            row: relabel(self.row),
+            col: relabel(self.col),
+            val: relabel(self.val),
+            row_col: relabel(self.row_col),
+            evals_on_k: self.evals_on_k,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LabeledPolynomial<F, DensePolynomial<F>>> {
+        vec![&self.row, &self.col, &self.val, &self.row_col].into_iter()
+    }
+}
+
+/// How the positions of K that are not occupied by a non-zero entry are filled. Padding entries always have a value
+/// of 0; the strategy decides which (row, col) pair they point to. Prover and verifier must agree on it, so the
+/// protocols consuming an encoding take it as part of their statement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaddingStrategy {
+    /// Repeat the last entry (or (0, 0) for an empty matrix). Padding never introduces new (row, col) pairs and
+    /// preserves (strict) triangularity.
+    #[default]
+    RepeatLast,
+    /// Point every padding entry to (0, 0), as in the Marlin encoding of a diagonal matrix
+    Origin,
+}
+
+impl PaddingStrategy {
+    /// The (row, col) pair of a padding entry, given the last non-padding entry of the encoding
+    pub fn padding_entry(&self, last_entry: Option<(usize, usize)>) -> (usize, usize) {
+        match self {
+            Self::RepeatLast => last_entry.unwrap_or((0, 0)),
+            Self::Origin => (0, 0),
+        }
+    }
+}
+
+impl ToBytes for PaddingStrategy {
+    fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        let tag: u8 = match self {
+            Self::RepeatLast => 0,
+            Self::Origin => 1,
+        };
+        tag.write(writer)
+    }
+}
+
+/// The row, col and val polynomials of a sparse matrix given as a list of (row, column, value) entries, with the
+/// i-th entry encoded at gamma^i. The unused positions of K are padded with entries of value 0 according to a
+/// [`PaddingStrategy`].
+#[derive(Clone, Debug)]
+pub struct SparseMatrixEncoding<F: PrimeField> {
+    pub row: LabeledPolynomial<F, DensePolynomial<F>>,
+    pub col: LabeledPolynomial<F, DensePolynomial<F>>,
+    pub val: LabeledPolynomial<F, DensePolynomial<F>>,
+
+    pub row_evals: EvaluationsOnDomain<F>,
+    pub col_evals: EvaluationsOnDomain<F>,
+    pub val_evals: EvaluationsOnDomain<F>,
+}
+
+impl<F: PrimeField> SparseMatrixEncoding<F> {
+    /// Encode the entries in the given order, mapping row and column indices to elements of `domain_h`
+    pub fn from_entries(
+        entries: &[(usize, usize, F)],
+        domain_h: &DomainH<F>,
+        domain_k: &DomainK<F>,
+        padding: PaddingStrategy,
+    ) -> Result<Self, Error> {
+        if entries.len() > domain_k.size() {
+            return Err(Error::MatrixTooLarge(format!(
+                "Matrix has {} non-zero entries but domain K only has {} elements",
+                entries.len(),
+                domain_k.size()
+            )));
+        }
+
+        let mut row_vec = Vec::with_capacity(domain_k.size());
+        let mut col_vec = Vec::with_capacity(domain_k.size());
+        let mut val_vec = Vec::with_capacity(domain_k.size());
+
+        for &(row_index, col_index, val) in entries {
+            for (name, index) in [("Row", row_index), ("Column", col_index)] {
+                if index >= domain_h.size() {
+                    return Err(Error::MatrixTooLarge(format!(
+                        "{} index {} is outside of domain H of size {}",
+                        name,
+                        index,
+                        domain_h.size()
+                    )));
+                }
+            }
+            row_vec.push(domain_h.element(row_index));
+            col_vec.push(domain_h.element(col_index));
+            val_vec.push(val);
+        }
+
+        let (pad_row, pad_col) =
+            padding.padding_entry(entries.last().map(|&(row, col, _)| (row, col)));
+        row_vec.resize(domain_k.size(), domain_h.element(pad_row));
+        col_vec.resize(domain_k.size(), domain_h.element(pad_col));
+        val_vec.resize(domain_k.size(), F::zero());
+
+        let row_evals = EvaluationsOnDomain::from_vec_and_domain(row_vec, domain_k.0);
+        let col_evals = EvaluationsOnDomain::from_vec_and_domain(col_vec, domain_k.0);
+        let val_evals = EvaluationsOnDomain::from_vec_and_domain(val_vec, domain_k.0);
+
+        Ok(Self {
+            row: interpolate("row", &row_evals),
+            col: interpolate("col", &col_evals),
+            val: interpolate("val", &val_evals),
+            row_evals,
+            col_evals,
+            val_evals,
+        })
+    }
+
+    /// Prefix every label with `prefix` and attach the given degree and hiding bounds to each polynomial
+    pub fn with_bounds(
+        self,
+        prefix: &str,
+        degree_bound: Option<usize>,
+        hiding_bound: Option<usize>,
+    ) -> Self {
+        let relabel = |p: LabeledPolynomial<F, DensePolynomial<F>>| {
+            LabeledPolynomial::new(
+                format!("{}_{}", prefix, p.label()),
+                p.polynomial().clone(),
+                degree_bound,
+                hiding_bound,
+            )
+        };
+
+        Self {
+            row: relabel(self.row),
+            col: relabel(self.col),
+            val: relabel(self.val),
+            ..self
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LabeledPolynomial<F, DensePolynomial<F>>> {
+        vec![&self.row, &self.col, &self.val].into_iter()
+    }
+
+    /// The row, col and val polynomials labelled `{prefix}_row`, `{prefix}_col` and `{prefix}_val` with the enforced
+    /// degree bound. Encodings made by the indexer share their labels, which a single ZeroOverK cannot tell apart.
+    pub fn oracles(
+        &self,
+        prefix: &str,
+        enforced_degree_bound: Option<usize>,
+    ) -> Vec<LabeledPolynomial<F, DensePolynomial<F>>> {
+        ENCODING_NAMES
+            .iter()
+            .zip(self.iter())
+            .map(|(name, poly)| {
+                LabeledPolynomial::new(
+                    format!("{}_{}", prefix, name),
+                    poly.polynomial().clone(),
+                    enforced_degree_bound,
+                    poly.hiding_bound(),
+                )
+            })
+            .collect()
+    }
+
+    /// Pack the row and col polynomials into row + gamma * col. For a challenge gamma drawn after row and col are
+    /// committed to, distinct (row, col) pairs of H x H get distinct packed values except with probability
+    /// |H|^4 / |F|, so the packed polynomial stands in for both. See
+    /// [`RowColPacking`](crate::row_col_packing::RowColPacking) for the proof that the packing is correct.
+    pub fn pack(&self, gamma: F) -> PackedEncoding<F> {
+        let row_col_evals = self
+            .row_evals
+            .evals
+            .iter()
+            .zip(self.col_evals.evals.iter())
+            .map(|(&row, &col)| row + gamma * col)
+            .collect();
+
+        PackedEncoding {
+            gamma,
+            row_col: LabeledPolynomial::new(
+                String::from("row_col"),
+                self.row.polynomial() + &(self.col.polynomial() * gamma),
+                self.row.degree_bound(),
+                self.row.hiding_bound(),
+            ),
+            val: self.val.clone(),
+            row_col_evals: EvaluationsOnDomain::from_vec_and_domain(
+                row_col_evals,
+                self.row_evals.domain(),
+            ),
+            val_evals: self.val_evals.clone(),
+        }
+    }
+}
+
+/// A [`SparseMatrixEncoding`] with its row and col polynomials packed into row + gamma * col, which halves the
+/// commitments and openings spent on the indices of the entries
+#[derive(Clone, Debug)]
+pub struct PackedEncoding<F: PrimeField> {
+    pub gamma: F,
+    pub row_col: LabeledPolynomial<F, DensePolynomial<F>>,
+    pub val: LabeledPolynomial<F, DensePolynomial<F>>,
+
+    pub row_col_evals: EvaluationsOnDomain<F>,
+    pub val_evals: EvaluationsOnDomain<F>,
+}
+
+impl<F: PrimeField> PackedEncoding<F> {
+    pub fn iter(&self) -> impl Iterator<Item = &LabeledPolynomial<F, DensePolynomial<F>>> {
+        vec![&self.row_col, &self.val].into_iter()
+    }
+}
+
+impl<F: PrimeField> From<MatrixArith<F>> for SparseMatrixEncoding<F> {
+    fn from(arith: MatrixArith<F>) -> Self {
+        Self {
+            row: arith.row,
+            col: arith.col,
+            val: arith.val,
+            row_evals: arith.evals_on_k.row,
+            col_evals: arith.evals_on_k.col,
+            val_evals: arith.evals_on_k.val,
+        }
+    }
+}
+
+/// The commitments to the row, col and val polynomials of a [`SparseMatrixEncoding`]
+#[derive(Clone, Derivative)]
+#[derivative(
+    Debug(bound = "C: Debug"),
+    PartialEq(bound = "C: PartialEq"),
+    Eq(bound = "C: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct CommittedEncoding<C: PCCommitment> {
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub row: LabeledCommitment<C>,
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub col: LabeledCommitment<C>,
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub val: LabeledCommitment<C>,
+}
+
+impl<C: PCCommitment> CommittedEncoding<C> {
+    pub fn iter(&self) -> impl Iterator<Item = &LabeledCommitment<C>> {
+        vec![&self.row, &self.col, &self.val].into_iter()
+    }
+
+    /// The commitments under the labels of [`SparseMatrixEncoding::oracles`]
+    pub fn oracles(
+        &self,
+        prefix: &str,
+        enforced_degree_bound: Option<usize>,
+    ) -> Vec<LabeledCommitment<C>> {
+        ENCODING_NAMES
+            .iter()
+            .zip(self.iter())
+            .map(|(name, commit)| {
+                LabeledCommitment::new(
+                    format!("{}_{}", prefix, name),
+                    commit.commitment().clone(),
+                    enforced_degree_bound,
+                )
+            })
+            .collect()
+    }
+}
+
+/// The commitments to the row_col and val polynomials of a [`PackedEncoding`]
+#[derive(Clone, Derivative)]
+#[derivative(
+    Debug(bound = "C: Debug"),
+    PartialEq(bound = "C: PartialEq"),
+    Eq(bound = "C: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct CommittedPackedEncoding<C: PCCommitment> {
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub row_col: LabeledCommitment<C>,
+    #[derivative(
+        Debug(format_with = "homomorphic_poly_commit::labeled::fmt"),
+        PartialEq(compare_with = "homomorphic_poly_commit::labeled::eq")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::labeled_commitment")
+    )]
+    pub val: LabeledCommitment<C>,
+}
+
+impl<C: PCCommitment> CommittedPackedEncoding<C> {
+    pub fn iter(&self) -> impl Iterator<Item = &LabeledCommitment<C>> {
+        vec![&self.row_col, &self.val].into_iter()
+    }
+}
+
+const ENCODING_NAMES: [&str; 3] = ["row", "col", "val"];
+
+/// Arithmetize a sparse matrix over `domain_k`, encoding row and column indices as elements of `domain_h`.
+/// Entries are enumerated row by row and padded according to `padding`.
+pub fn arithmetize_matrix<F: PrimeField>(
+    matrix: &Matrix<F>,
+    domain_h: DomainH<F>,
+    domain_k: DomainK<F>,
+    padding: PaddingStrategy,
+) -> Result<MatrixArith<F>, Error> {
+    if matrix.len() > domain_h.size() {
+        return Err(Error::MatrixTooLarge(format!(
+            "Matrix has {} rows but domain H only has {} elements",
+            matrix.len(),
+            domain_h.size()
+        )));
+    }
+
+    let entries = matrix
+        .iter()
+        .enumerate()
+        .flat_map(|(row_index, row)| {
+            row.iter()
+                .map(move |&(val, col_index)| (row_index, col_index, val))
+        })
+        .collect::<Vec<_>>();
+    let encoding = SparseMatrixEncoding::from_entries(&entries, &domain_h, &domain_k, padding)?;
+
+    let row_col_vec = encoding
+        .row_evals
+        .evals
+        .iter()
+        .zip(encoding.col_evals.evals.iter())
+        .map(|(&r, &c)| r * c)
+        .collect();
+
+    let evals_on_k = MatrixEvals {
+        row: encoding.row_evals,
+        col: encoding.col_evals,
+        val: encoding.val_evals,
+        row_col: EvaluationsOnDomain::from_vec_and_domain(row_col_vec, domain_k.0),
+    };
+
+    Ok(MatrixArith {
+        row: encoding.row,
+        col: encoding.col,
+        val: encoding.val,
+        row_col: interpolate("row_col", &evals_on_k.row_col),
+        evals_on_k,
+    })
+}
+
+fn interpolate<F: PrimeField>(
+    label: &str,
+    evals: &EvaluationsOnDomain<F>,
+) -> LabeledPolynomial<F, DensePolynomial<F>> {
+    LabeledPolynomial::new(label.to_string(), evals.clone().interpolate(), None, None)
+}
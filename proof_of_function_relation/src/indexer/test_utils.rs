@@ -0,0 +1,47 @@
+//! Fixtures shared by the tests of the protocols on sparse matrix encodings
+
+use crate::{
+    domains::{DomainH, DomainK},
+    indexer::{CommittedEncoding, PaddingStrategy, SparseMatrixEncoding},
+};
+use ark_ff::PrimeField;
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+use ark_poly_commit::PolynomialCommitment;
+use ark_std::rand::thread_rng;
+
+/// The smallest domains K and H of at least `size_k` and `size_h` elements
+pub(crate) fn domains<F: PrimeField>(size_k: usize, size_h: usize) -> (DomainK<F>, DomainH<F>) {
+    (DomainK::new(size_k).unwrap(), DomainH::new(size_h).unwrap())
+}
+
+/// Encode `entries` under the label prefix "m", with a degree bound of |K| + 1 and a hiding bound of 1
+pub(crate) fn encode<F: PrimeField>(
+    entries: &[(usize, usize, F)],
+    domain_k: &DomainK<F>,
+    domain_h: &DomainH<F>,
+    padding: PaddingStrategy,
+) -> SparseMatrixEncoding<F> {
+    SparseMatrixEncoding::from_entries(entries, domain_h, domain_k, padding)
+        .unwrap()
+        .with_bounds("m", Some(domain_k.size() + 1), Some(1))
+}
+
+/// The commitments to an encoding, with the randomness used for each of its polynomials
+pub(crate) type Committed<F, PC> = (
+    CommittedEncoding<<PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment>,
+    Vec<<PC as PolynomialCommitment<F, DensePolynomial<F>>>::Randomness>,
+);
+
+/// Commit to the row, col and val polynomials of `encoding`
+pub(crate) fn committed<F: PrimeField, PC: PolynomialCommitment<F, DensePolynomial<F>>>(
+    ck: &PC::CommitterKey,
+    encoding: &SparseMatrixEncoding<F>,
+) -> Committed<F, PC> {
+    let (commitments, rands) = PC::commit(ck, encoding.iter(), Some(&mut thread_rng())).unwrap();
+    let committed = CommittedEncoding {
+        row: commitments[0].clone(),
+        col: commitments[1].clone(),
+        val: commitments[2].clone(),
+    };
+    (committed, rands)
+}
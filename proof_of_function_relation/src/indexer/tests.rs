@@ -0,0 +1,167 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::{
+        domains::{DomainH, DomainK},
+        error::Error,
+        indexer::{arithmetize_matrix, Matrix, PaddingStrategy, SparseMatrixEncoding},
+    };
+    use ark_bn254::Fr;
+    use ark_ff::{Field, One, Zero};
+    use ark_poly::EvaluationDomain;
+
+    type F = Fr;
+
+    fn lower_triangular() -> Matrix<F> {
+        vec![
+            vec![],
+            vec![(F::from(2u64), 0)],
+            vec![(F::from(3u64), 0), (F::from(5u64), 1)],
+            vec![(F::from(7u64), 2)],
+        ]
+    }
+
+    #[test]
+    fn test_arithmetize_matrix() {
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let matrix = lower_triangular();
+
+        let arith =
+            arithmetize_matrix(&matrix, domain_h, domain_k, PaddingStrategy::RepeatLast).unwrap();
+
+        let omega = domain_h.element(1);
+        let expected_rows = [1, 2, 2, 3, 3, 3, 3, 3];
+        let expected_cols = [0, 0, 1, 2, 2, 2, 2, 2];
+        let expected_vals = [2u64, 3, 5, 7, 0, 0, 0, 0];
+
+        for (i, gamma_i) in domain_k.elements().enumerate() {
+            let row = omega.pow([expected_rows[i] as u64]);
+            let col = omega.pow([expected_cols[i] as u64]);
+            assert_eq!(arith.row.evaluate(&gamma_i), row);
+            assert_eq!(arith.col.evaluate(&gamma_i), col);
+            assert_eq!(arith.val.evaluate(&gamma_i), F::from(expected_vals[i]));
+            assert_eq!(arith.row_col.evaluate(&gamma_i), row * col);
+        }
+
+        let arith = arith.with_bounds("a", Some(domain_k.size() + 1), None);
+        assert_eq!(arith.row.label(), "a_row");
+        assert_eq!(arith.row_col.degree_bound(), Some(domain_k.size() + 1));
+    }
+
+    #[test]
+    fn test_empty_matrix() {
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let domain_k = DomainK::<F>::new(4).unwrap();
+        let matrix: Matrix<F> = vec![vec![]; 4];
+
+        let arith =
+            arithmetize_matrix(&matrix, domain_h, domain_k, PaddingStrategy::RepeatLast).unwrap();
+        for elem in domain_k.elements() {
+            assert_eq!(arith.row.evaluate(&elem), F::one());
+            assert_eq!(arith.val.evaluate(&elem), F::zero());
+        }
+    }
+
+    #[test]
+    fn test_too_many_non_zero_entries() {
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let domain_k = DomainK::<F>::new(2).unwrap();
+
+        let res = arithmetize_matrix(
+            &lower_triangular(),
+            domain_h,
+            domain_k,
+            PaddingStrategy::RepeatLast,
+        );
+        assert!(matches!(res, Err(Error::MatrixTooLarge(_))));
+    }
+
+    #[test]
+    fn test_encoding_from_entries() {
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+
+        // entries are encoded in the given order, not sorted
+        let entries = [
+            (3, 1, F::from(3u64)),
+            (2, 0, F::from(1u64)),
+            (3, 2, F::from(5u64)),
+        ];
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries,
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap()
+        .with_bounds("m", Some(domain_k.size() + 1), Some(1));
+
+        // the padding repeats the last entry with a value of 0
+        let omega = domain_h.element(1);
+        let expected_rows = [3, 2, 3, 3, 3, 3, 3, 3];
+        let expected_cols = [1, 0, 2, 2, 2, 2, 2, 2];
+        let expected_vals = [3u64, 1, 5, 0, 0, 0, 0, 0];
+
+        for (i, gamma_i) in domain_k.elements().enumerate() {
+            assert_eq!(encoding.row_evals[i], omega.pow([expected_rows[i] as u64]));
+            assert_eq!(encoding.row.evaluate(&gamma_i), encoding.row_evals[i]);
+            assert_eq!(
+                encoding.col.evaluate(&gamma_i),
+                omega.pow([expected_cols[i] as u64])
+            );
+            assert_eq!(encoding.val.evaluate(&gamma_i), F::from(expected_vals[i]));
+        }
+
+        assert_eq!(encoding.col.label(), "m_col");
+        assert_eq!(encoding.val.degree_bound(), Some(domain_k.size() + 1));
+        assert_eq!(encoding.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_origin_padding() {
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let domain_k = DomainK::<F>::new(4).unwrap();
+
+        let entries = [(2, 2, F::from(2u64)), (3, 3, F::from(2u64))];
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries,
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::Origin,
+        )
+        .unwrap();
+
+        // the padding points to (0, 0), i.e. omega^0 = 1, with a value of 0
+        let omega = domain_h.element(1);
+        let expected = [omega.pow([2u64]), omega.pow([3u64]), F::one(), F::one()];
+        assert_eq!(encoding.row_evals.evals, expected);
+        assert_eq!(encoding.col_evals.evals, expected);
+        assert_eq!(
+            encoding.val_evals.evals,
+            [F::from(2u64), F::from(2u64), F::zero(), F::zero()]
+        );
+    }
+
+    #[test]
+    fn test_padding_entry() {
+        let last = Some((3, 1));
+        assert_eq!(PaddingStrategy::RepeatLast.padding_entry(last), (3, 1));
+        assert_eq!(PaddingStrategy::RepeatLast.padding_entry(None), (0, 0));
+        assert_eq!(PaddingStrategy::Origin.padding_entry(last), (0, 0));
+        assert_eq!(PaddingStrategy::default(), PaddingStrategy::RepeatLast);
+    }
+
+    #[test]
+    fn test_entry_outside_of_domain_h() {
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+
+        let res = SparseMatrixEncoding::from_entries(
+            &[(4, 0, F::one())],
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        );
+        assert!(matches!(res, Err(Error::MatrixTooLarge(_))));
+    }
+}
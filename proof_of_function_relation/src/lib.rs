@@ -1,9 +1,37 @@
+pub mod band_matrix_test;
+pub mod block_diagonal_test;
+pub mod checked_key;
+pub mod config;
+pub mod cost_model;
 pub mod discrete_log_comparison;
+pub mod domains;
+pub mod equal_on_subdomain;
 pub mod error;
+pub mod extension_field;
+pub mod external_oracles;
 pub mod geo_seq;
+pub mod indexer;
+pub mod line_sum_test;
+pub mod matrix_product_test;
+pub mod matrix_sum_test;
 pub mod non_zero_over_k;
+pub mod permutation_matrix_test;
+pub mod pre_verified;
+pub mod proof_of_knowledge;
+pub mod proof_version;
+pub mod report;
+pub mod rotation_argument;
+pub mod row_col_packing;
+pub mod session;
+#[cfg(all(test, feature = "prover"))]
+mod soundness_oracle;
+pub mod spec;
 pub mod subset_over_k;
+pub mod symmetric_test;
 pub mod t_diag;
 pub mod t_functional_triple;
 pub mod t_strictly_lower_triangular_test;
+#[cfg(any(all(test, feature = "prover"), feature = "test-vectors"))]
+pub mod test_vectors;
+pub mod transpose_test;
 pub mod util;
@@ -0,0 +1,401 @@
+use crate::{
+    discrete_log_comparison::piop::PIOPforDLComparison,
+    domains::{DomainH, DomainK},
+    error::{Error, WitnessError},
+    line_sum_test::proof::Proof,
+    report::VerificationReport,
+};
+use ark_ff::{to_bytes, One, SquareRootField, ToBytes, Zero};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use blake2::{Blake2s, Digest};
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use std::{io::Write, marker::PhantomData};
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {
+    crate::{error::to_pc_error, util::accumulator},
+    ark_ff::batch_inversion,
+    rand::Rng,
+};
+
+pub mod proof;
+mod tests;
+
+/// The lines of a matrix whose sums are proven
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    /// Sums of the rows, proven against the row polynomial of the encoding
+    Rows,
+    /// Sums of the columns, proven against the col polynomial of the encoding
+    Columns,
+}
+
+impl ToBytes for Axis {
+    fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        let tag: u8 = match self {
+            Self::Rows => 0,
+            Self::Columns => 1,
+        };
+        tag.write(writer)
+    }
+}
+
+/// Proves that the lines (rows or columns) of the matrix encoded over K sum to a committed vector s over H: for every
+/// i, the values val(κ) of the entries with line(κ) = ω^i add up to s(ω^i), where line is the row or col polynomial
+/// of the encoding and ω generates H.
+///
+/// For a challenge x, both vectors are compared through the rational function Σ_i u_i * ω^i / (x - ω^i), which
+/// determines u. This gives two sums that must agree on a common value σ:
+///
+/// Σ_{κ in K} val(κ) * line(κ) / (x - line(κ)) = σ = Σ_{h in H} s(h) * h / (x - h)
+///
+/// Each sum is proven with an accumulator acc over its domain D, generated by δ, such that
+/// (acc(δ * X) - acc(X) + σ / |D|) * (x - p(X)) - v(X) * p(X) is zero over D: adding up over the cycle D gives
+/// Σ v(d) * p(d) / (x - p(d)) = σ.
+pub struct LineSumTest<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> LineSumTest<C>
+where
+    C::F: SquareRootField,
+{
+    pub const PROTOCOL_NAME: &'static [u8] = b"Line Sum Test";
+
+    /// Check that every line of the encoding sums to the corresponding evaluation of `sums` over H, failing on the
+    /// first line that does not before any work is done. With the `validate-witness` feature the prover runs this
+    /// check itself.
+    pub fn validate_witness(
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        line_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        val_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        sums_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    ) -> Result<(), Error> {
+        let lines = PIOPforDLComparison::exponents(domain_k, domain_h, line_poly)?;
+        let vals = domain_k.fft(val_poly.polynomial());
+
+        let mut line_sums = vec![C::F::zero(); domain_h.size()];
+        for (line, val) in lines.into_iter().zip(vals) {
+            line_sums[line] += val;
+        }
+
+        let sums = domain_h.fft(sums_poly.polynomial());
+        match line_sums
+            .iter()
+            .zip(sums.iter())
+            .position(|(line_sum, sum)| line_sum != sum)
+        {
+            Some(line) => Err(Error::InvalidWitness(WitnessError::WrongLineSum { line })),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        axis: Axis,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        line_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        line_commit: &LabeledCommitment<pc::Commitment<C>>,
+        line_random: &pc::Randomness<C>,
+        val_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        val_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_random: &pc::Randomness<C>,
+        sums_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        sums_commit: &LabeledCommitment<pc::Commitment<C>>,
+        sums_random: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("line_sum_test::prove");
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(domain_k, domain_h, line_poly, val_poly, sums_poly)?;
+
+        let fs_bytes = &to_bytes![
+            &Self::PROTOCOL_NAME,
+            context,
+            axis,
+            line_commit,
+            val_commit,
+            sums_commit
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let x: C::F = ChallengeSet::new(&mut *fs_rng, b"sums").challenge(b"x");
+
+        // Step 1: the terms of both sums. The denominators only vanish if x lands on a point of the encoding, which
+        // happens with negligible probability.
+        let k_terms = Self::terms(
+            x,
+            &domain_k.fft(line_poly.polynomial()),
+            &domain_k.fft(val_poly.polynomial()),
+        );
+        let h_terms = Self::terms(
+            x,
+            &domain_h.elements().collect::<Vec<_>>(),
+            &domain_h.fft(sums_poly.polynomial()),
+        );
+        let sum = k_terms.iter().sum::<C::F>();
+
+        // Step 2: commit to the accumulators of both sums
+        let k_acc = accumulator("k_acc", domain_k, &k_terms, sum, enforced_degree_bound);
+        let h_acc = accumulator("h_acc", domain_h, &h_terms, sum, enforced_degree_bound);
+
+        let (commitments, rands) =
+            C::PC::commit(ck, [&k_acc, &h_acc], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![commitments, sum].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        // Step 3: Zero over K for the sum over the encoding
+        let alphas = [C::F::one(), domain_k.element(1), C::F::one(), C::F::one()];
+        let k_sum_vo = GenericShiftingVO::new(
+            &[0, 0, 1, 2],
+            &alphas,
+            Self::k_sum_check(x, sum, domain_k.size()),
+        )?
+        .with_scaling_factor(2);
+
+        let k_sum_proof = ZeroOverK::<C>::prove(
+            &[&k_acc, line_poly, val_poly],
+            &[
+                commitments[0].clone(),
+                line_commit.clone(),
+                val_commit.clone(),
+            ],
+            &[rands[0].clone(), line_random.clone(), val_random.clone()],
+            enforced_degree_bound,
+            &k_sum_vo,
+            domain_k,
+            ck,
+            &Self::sum_context(context, b"k_sum", x, sum)?,
+            rng,
+        )?;
+
+        // Step 4: Zero over H for the sum over the committed vector
+        let alphas = [C::F::one(), domain_h.element(1), C::F::one()];
+        let h_sum_vo = GenericShiftingVO::new(
+            &[0, 0, 1],
+            &alphas,
+            Self::h_sum_check(x, sum, domain_h.size()),
+        )?
+        .with_scaling_factor(2);
+
+        let h_sum_proof = ZeroOverK::<C>::prove(
+            &[&h_acc, sums_poly],
+            &[commitments[1].clone(), sums_commit.clone()],
+            &[rands[1].clone(), sums_random.clone()],
+            enforced_degree_bound,
+            &h_sum_vo,
+            domain_h,
+            ck,
+            &Self::sum_context(context, b"h_sum", x, sum)?,
+            rng,
+        )?;
+
+        Ok(Proof {
+            k_acc_commit: commitments[0].commitment().clone(),
+            h_acc_commit: commitments[1].commitment().clone(),
+            sum,
+            k_sum_proof,
+            h_sum_proof,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        axis: Axis,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        line_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_commit: &LabeledCommitment<pc::Commitment<C>>,
+        sums_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(
+            vk,
+            axis,
+            domain_k,
+            domain_h,
+            line_commit,
+            val_commit,
+            sums_commit,
+            enforced_degree_bound,
+            proof,
+            context,
+            fs_rng,
+        )
+        .into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        axis: Axis,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        line_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_commit: &LabeledCommitment<pc::Commitment<C>>,
+        sums_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("line_sum_test::verify");
+        let mut report = VerificationReport::builder("line_sum_test");
+
+        // re-label the oracle commitments with the enforced degree bound
+        let relabel = |commit: &LabeledCommitment<pc::Commitment<C>>| {
+            LabeledCommitment::new(
+                commit.label().clone(),
+                commit.commitment().clone(),
+                enforced_degree_bound,
+            )
+        };
+        let commitments = vec![
+            LabeledCommitment::new(
+                String::from("k_acc"),
+                proof.k_acc_commit,
+                enforced_degree_bound,
+            ),
+            LabeledCommitment::new(
+                String::from("h_acc"),
+                proof.h_acc_commit,
+                enforced_degree_bound,
+            ),
+        ];
+        let sum = proof.sum;
+
+        let mut x = C::F::zero();
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![
+                &Self::PROTOCOL_NAME,
+                context,
+                axis,
+                line_commit,
+                val_commit,
+                sums_commit
+            ]
+            .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            x = ChallengeSet::new(&mut *fs_rng, b"sums").challenge(b"x");
+
+            let fs_bytes = &to_bytes![commitments, sum].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        report.check("k_sum", || {
+            let alphas = [C::F::one(), domain_k.element(1), C::F::one(), C::F::one()];
+            let k_sum_vo = GenericShiftingVO::new(
+                &[0, 0, 1, 2],
+                &alphas,
+                Self::k_sum_check(x, sum, domain_k.size()),
+            )?
+            .with_scaling_factor(2);
+
+            ZeroOverK::<C>::verify(
+                proof.k_sum_proof,
+                &[
+                    commitments[0].clone(),
+                    relabel(line_commit),
+                    relabel(val_commit),
+                ],
+                enforced_degree_bound,
+                &k_sum_vo,
+                domain_k,
+                vk,
+                &Self::sum_context(context, b"k_sum", x, sum)?,
+            )
+            .map_err(Error::from)
+        });
+
+        report.check("h_sum", || {
+            let alphas = [C::F::one(), domain_h.element(1), C::F::one()];
+            let h_sum_vo = GenericShiftingVO::new(
+                &[0, 0, 1],
+                &alphas,
+                Self::h_sum_check(x, sum, domain_h.size()),
+            )?
+            .with_scaling_factor(2);
+
+            ZeroOverK::<C>::verify(
+                proof.h_sum_proof,
+                &[commitments[1].clone(), relabel(sums_commit)],
+                enforced_degree_bound,
+                &h_sum_vo,
+                domain_h,
+                vk,
+                &Self::sum_context(context, b"h_sum", x, sum)?,
+            )
+            .map_err(Error::from)
+        });
+
+        report.finish()
+    }
+
+    #[cfg(feature = "prover")]
+    /// The terms v * p / (x - p) of a sum, given the evaluations of p and v over its domain
+    fn terms(x: C::F, points: &[C::F], values: &[C::F]) -> Vec<C::F> {
+        let mut denominators = points.iter().map(|&point| x - point).collect::<Vec<_>>();
+        batch_inversion(&mut denominators);
+
+        points
+            .iter()
+            .zip(values)
+            .zip(denominators)
+            .map(|((&point, &value), denominator)| value * point * denominator)
+            .collect()
+    }
+
+    /// (acc(gamma * X) - acc(X) + sum / |K|) * (x - line(X)) - val(X) * line(X), over the terms
+    /// [X, acc(X), acc(gamma * X), line(X), val(X)]
+    fn k_sum_check(x: C::F, sum: C::F, size: usize) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        let step = sum / C::F::from(size as u64);
+        move |terms: &[VOTerm<C::F>]| {
+            (terms[2].clone() - terms[1].clone() + vo_constant!(step))
+                * (vo_constant!(x) - terms[3].clone())
+                - terms[4].clone() * terms[3].clone()
+        }
+    }
+
+    /// (acc(omega * X) - acc(X) + sum / |H|) * (x - X) - s(X) * X, over the terms [X, acc(X), acc(omega * X), s(X)]
+    fn h_sum_check(x: C::F, sum: C::F, size: usize) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        let step = sum / C::F::from(size as u64);
+        move |terms: &[VOTerm<C::F>]| {
+            (terms[2].clone() - terms[1].clone() + vo_constant!(step))
+                * (vo_constant!(x) - terms[0].clone())
+                - terms[3].clone() * terms[0].clone()
+        }
+    }
+
+    /// The zero over K sub-proofs run in their own transcripts, so bind them to the challenge and the claimed sum
+    fn sum_context(context: &Context, label: &[u8], x: C::F, sum: C::F) -> Result<Context, Error> {
+        let bytes = to_bytes![x, sum].map_err(|_| Error::ToBytesError)?;
+        Ok(context.child(label).child(&Blake2s::digest(&bytes)))
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{domains, encode},
+            PaddingStrategy,
+        },
+        line_sum_test::{Axis, LineSumTest},
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::{univariate::DensePolynomial, EvaluationDomain, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // M values
+    /*
+        1, 2, 0, 0
+        0, 0, 0, 0
+        3, 0, 4, 0
+        0, 0, 0, 5
+    */
+    // row sums: 3, 0, 7, 5
+    // column sums: 4, 2, 4, 5
+    fn entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 0, F::from(1u64)),
+            (0, 1, F::from(2u64)),
+            (2, 0, F::from(3u64)),
+            (2, 2, F::from(4u64)),
+            (3, 3, F::from(5u64)),
+        ]
+    }
+
+    fn sums_poly(sums: &[u64]) -> LabeledPolynomial<F, DensePolynomial<F>> {
+        let (domain_k, domain_h) = domains::<F>(8, 4);
+        let sums = sums.iter().map(|&sum| F::from(sum)).collect::<Vec<_>>();
+        LabeledPolynomial::new(
+            String::from("sums"),
+            DensePolynomial::from_coefficients_vec(domain_h.ifft(&sums)),
+            Some(domain_k.size() + 1),
+            Some(1),
+        )
+    }
+
+    fn run_line_sum_test(axis: Axis, padding: PaddingStrategy, sums: &[u64]) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let encoding = encode(&entries(), &domain_k, &domain_h, padding);
+        let line_poly = match axis {
+            Axis::Rows => encoding.row,
+            Axis::Columns => encoding.col,
+        };
+        let sums_poly = sums_poly(sums);
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) =
+            PC::commit(&ck, [&line_poly, &encoding.val, &sums_poly], Some(rng)).unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = LineSumTest::<C>::prove(
+            &ck,
+            axis,
+            &domain_k,
+            &domain_h,
+            &line_poly,
+            &commitments[0],
+            &rands[0],
+            &encoding.val,
+            &commitments[1],
+            &rands[1],
+            &sums_poly,
+            &commitments[2],
+            &rands[2],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        LineSumTest::<C>::verify(
+            &vk,
+            axis,
+            &domain_k,
+            &domain_h,
+            &commitments[0],
+            &commitments[1],
+            &commitments[2],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )
+    }
+
+    #[test]
+    fn test_row_sums() {
+        assert_eq!(
+            run_line_sum_test(Axis::Rows, PaddingStrategy::RepeatLast, &[3, 0, 7, 5]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_column_sums() {
+        assert_eq!(
+            run_line_sum_test(Axis::Columns, PaddingStrategy::Origin, &[4, 2, 4, 5]),
+            Ok(())
+        );
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_wrong_row_sums() {
+        assert!(run_line_sum_test(Axis::Rows, PaddingStrategy::RepeatLast, &[3, 0, 7, 6]).is_err());
+
+        // the column sums are not the row sums
+        assert!(run_line_sum_test(Axis::Rows, PaddingStrategy::RepeatLast, &[4, 2, 4, 5]).is_err());
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let (domain_k, domain_h) = domains(8, 4);
+        let encoding = encode(&entries(), &domain_k, &domain_h, PaddingStrategy::Origin);
+        let validate = |line_poly, sums: &[u64]| {
+            LineSumTest::<C>::validate_witness(
+                &domain_k,
+                &domain_h,
+                line_poly,
+                &encoding.val,
+                &sums_poly(sums),
+            )
+        };
+
+        assert_eq!(validate(&encoding.row, &[3, 0, 7, 5]), Ok(()));
+        assert_eq!(validate(&encoding.col, &[4, 2, 4, 5]), Ok(()));
+        assert_eq!(
+            validate(&encoding.row, &[3, 1, 7, 5]),
+            Err(Error::InvalidWitness(WitnessError::WrongLineSum {
+                line: 1
+            }))
+        );
+    }
+}
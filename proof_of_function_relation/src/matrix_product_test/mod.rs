@@ -0,0 +1,572 @@
+use crate::{
+    discrete_log_comparison::piop::PIOPforDLComparison,
+    domains::{DomainH, DomainK},
+    error::{Error, WitnessError},
+    indexer::{CommittedEncoding, SparseMatrixEncoding},
+    matrix_product_test::proof::Proof,
+    report::VerificationReport,
+};
+use ark_ff::{to_bytes, One, SquareRootField, Zero};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::LabeledCommitment;
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use blake2::{Blake2s, Digest};
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use std::{collections::BTreeMap, marker::PhantomData};
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {
+    crate::{error::to_pc_error, util::accumulator},
+    ark_ff::batch_inversion,
+    ark_poly::{univariate::DensePolynomial, UVPolynomial},
+    ark_poly_commit::LabeledPolynomial,
+    rand::Rng,
+};
+
+pub mod proof;
+mod tests;
+
+/// The public data of a matrix product test: the committed encodings over K of the matrices A, B and M
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<C::F>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_h: DomainH<C::F>,
+    pub a: CommittedEncoding<pc::Commitment<C>>,
+    pub b: CommittedEncoding<pc::Commitment<C>>,
+    pub m: CommittedEncoding<pc::Commitment<C>>,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+impl<C: ProtocolConfig> Statement<C> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_bytes![
+            self.domain_k.size() as u64,
+            self.domain_h.size() as u64,
+            self.a.iter().collect::<Vec<_>>(),
+            self.b.iter().collect::<Vec<_>>(),
+            self.m.iter().collect::<Vec<_>>()
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+
+    /// The commitments of the three encodings under the labels of `MatrixProductTest::oracles`
+    fn commitments(&self) -> Vec<LabeledCommitment<pc::Commitment<C>>> {
+        [("m", &self.m), ("a", &self.a), ("b", &self.b)]
+            .iter()
+            .flat_map(|(prefix, encoding)| encoding.oracles(prefix, self.enforced_degree_bound))
+            .collect()
+    }
+}
+
+/// k_acc appears twice, followed by the row, col and val oracles of M, A and B
+const K_SUM_MAPPING: [usize; 11] = [0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+/// h_acc appears twice, followed by u_a and b_v
+const H_SUM_MAPPING: [usize; 4] = [0, 0, 1, 2];
+
+/// Proves that the matrix M is the product A * B of two matrices, which lets committed functions be composed. The
+/// three matrices are square over H and given as sparse encodings over the same domain K.
+///
+/// For verifier challenges x and y, let u and v be the vectors u_i = 1 / (x - ω^i) and v_j = 1 / (y - ω^j), where ω
+/// generates H. The bilinear form u^T * M * v is a rational function of x and y that determines M, so it is enough
+/// to show that u^T * M * v = (u^T * A) * (B * v) for random x and y. The prover commits to the vectors u_a = u^T * A
+/// and b_v = B * v over H, and three sums must agree:
+///
+/// Σ_K val_M / ((x - row_M) * (y - col_M)) = Σ_H u_a * b_v                             (the inner product)
+/// Σ_K val_A / ((x - row_A) * (z - col_A)) = Σ_H u_a / (z - X)                          (u_a is u^T * A)
+/// Σ_K val_B / ((z - row_B) * (y - col_B)) = Σ_H b_v / (z - X)                          (b_v is B * v)
+///
+/// where the last two determine u_a and b_v as rational functions of a later challenge z. The three equalities are
+/// batched with powers of a challenge η into a single sum σ over each domain, and each sum is proven with an
+/// accumulator as in [`crate::line_sum_test::LineSumTest`].
+pub struct MatrixProductTest<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> MatrixProductTest<C>
+where
+    C::F: SquareRootField,
+{
+    pub const PROTOCOL_NAME: &'static [u8] = b"Matrix Product Test";
+
+    /// Check that every entry of M is the entry of A * B at the same row and column, failing on the first offending
+    /// entry in row-major order before any work is done. With the `validate-witness` feature the prover runs this
+    /// check itself.
+    pub fn validate_witness(
+        statement: &Statement<C>,
+        a: &SparseMatrixEncoding<C::F>,
+        b: &SparseMatrixEncoding<C::F>,
+        m: &SparseMatrixEncoding<C::F>,
+    ) -> Result<(), Error> {
+        let entries = |encoding: &SparseMatrixEncoding<C::F>| {
+            let exponents_of = |poly| {
+                PIOPforDLComparison::exponents(&statement.domain_k, &statement.domain_h, poly)
+            };
+            let entries = exponents_of(&encoding.row)?
+                .into_iter()
+                .zip(exponents_of(&encoding.col)?)
+                .zip(statement.domain_k.fft(encoding.val.polynomial()))
+                .map(|((row, col), val)| (row, col, val))
+                .collect::<Vec<_>>();
+            Ok::<_, Error>(entries)
+        };
+
+        let mut b_rows = BTreeMap::<usize, Vec<(usize, C::F)>>::new();
+        for (row, col, val) in entries(b)? {
+            b_rows.entry(row).or_default().push((col, val));
+        }
+
+        let mut differences = BTreeMap::<(usize, usize), C::F>::new();
+        for (row, inner, a_val) in entries(a)? {
+            for &(col, b_val) in b_rows.get(&inner).into_iter().flatten() {
+                *differences.entry((row, col)).or_insert_with(C::F::zero) += a_val * b_val;
+            }
+        }
+        for (row, col, val) in entries(m)? {
+            *differences.entry((row, col)).or_insert_with(C::F::zero) -= val;
+        }
+
+        match differences
+            .into_iter()
+            .find(|(_, difference)| !difference.is_zero())
+        {
+            Some(((row, col), _)) => Err(Error::InvalidWitness(WitnessError::ProductMismatch {
+                row,
+                col,
+            })),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "prover")]
+    /// The randomness of each encoding is given in the order of [`SparseMatrixEncoding::iter`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        a: &SparseMatrixEncoding<C::F>,
+        a_rands: &[pc::Randomness<C>],
+        b: &SparseMatrixEncoding<C::F>,
+        b_rands: &[pc::Randomness<C>],
+        m: &SparseMatrixEncoding<C::F>,
+        m_rands: &[pc::Randomness<C>],
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("matrix_product_test::prove");
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(statement, a, b, m)?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let mut round = ChallengeSet::new(&mut *fs_rng, b"vectors");
+        let x: C::F = round.challenge(b"x");
+        let y: C::F = round.challenge(b"y");
+
+        let domain_k = &statement.domain_k;
+        let domain_h = &statement.domain_h;
+        let bound = statement.enforced_degree_bound;
+
+        // Step 1: commit to u_a = u^T * A and b_v = B * v over H
+        let u_a = Self::fold(
+            domain_h,
+            x,
+            &a.row_evals.evals,
+            &a.col_evals.evals,
+            &a.val_evals.evals,
+            a.col.label(),
+        )?;
+        let b_v = Self::fold(
+            domain_h,
+            y,
+            &b.col_evals.evals,
+            &b.row_evals.evals,
+            &b.val_evals.evals,
+            b.row.label(),
+        )?;
+        let interpolate = |label: &str, evals: &[C::F]| {
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::from_coefficients_vec(domain_h.ifft(evals)),
+                bound,
+                Some(1),
+            )
+        };
+        let u_a_poly = interpolate("u_a", &u_a);
+        let b_v_poly = interpolate("b_v", &b_v);
+
+        let (vector_commitments, vector_rands) =
+            C::PC::commit(ck, [&u_a_poly, &b_v_poly], Some(rng))
+                .map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![vector_commitments].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let mut round = ChallengeSet::new(&mut *fs_rng, b"sums");
+        let z: C::F = round.challenge(b"z");
+        let eta: C::F = round.challenge(b"eta");
+
+        // Step 2: the terms of both sums. The denominators only vanish if a challenge lands on a point of an
+        // encoding or of H, which happens with negligible probability.
+        let m_terms = Self::terms(x, y, m);
+        let a_terms = Self::terms(x, z, a);
+        let b_terms = Self::terms(z, y, b);
+        let k_terms = m_terms
+            .iter()
+            .zip(a_terms)
+            .zip(b_terms)
+            .map(|((m_term, a_term), b_term)| *m_term + eta * (a_term + eta * b_term))
+            .collect::<Vec<_>>();
+
+        let mut h_denominators = domain_h.elements().map(|h| z - h).collect::<Vec<_>>();
+        batch_inversion(&mut h_denominators);
+        let h_terms = u_a
+            .iter()
+            .zip(b_v.iter())
+            .zip(h_denominators)
+            .map(|((&u_a, &b_v), denominator)| u_a * b_v + eta * (u_a + eta * b_v) * denominator)
+            .collect::<Vec<_>>();
+
+        let sum = k_terms.iter().sum::<C::F>();
+
+        // Step 3: commit to the accumulators of both sums
+        let k_acc = accumulator("k_acc", domain_k, &k_terms, sum, bound);
+        let h_acc = accumulator("h_acc", domain_h, &h_terms, sum, bound);
+
+        let (acc_commitments, acc_rands) =
+            C::PC::commit(ck, [&k_acc, &h_acc], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![acc_commitments, sum].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let challenges = [x, y, z, eta];
+
+        // Step 4: Zero over K for the sum over the encodings
+        let k_sum_vo = GenericShiftingVO::new(
+            &K_SUM_MAPPING,
+            &Self::accumulator_alphas(domain_k, K_SUM_MAPPING.len()),
+            Self::k_sum_check(challenges, sum, domain_k.size()),
+        )?
+        .with_scaling_factor(7);
+
+        let oracles = Self::oracles([m, a, b], bound);
+        let k_sum_proof = ZeroOverK::<C>::prove(
+            &[&k_acc]
+                .into_iter()
+                .chain(oracles.iter())
+                .collect::<Vec<_>>(),
+            &[acc_commitments[0].clone()]
+                .into_iter()
+                .chain(statement.commitments())
+                .collect::<Vec<_>>(),
+            &[&acc_rands[..1], m_rands, a_rands, b_rands].concat(),
+            bound,
+            &k_sum_vo,
+            domain_k,
+            ck,
+            &Self::sum_context(context, b"k_sum", challenges, sum)?,
+            rng,
+        )?;
+
+        // Step 5: Zero over H for the sum over the committed vectors
+        let h_sum_vo = GenericShiftingVO::new(
+            &H_SUM_MAPPING,
+            &Self::accumulator_alphas(domain_h, H_SUM_MAPPING.len()),
+            Self::h_sum_check(z, eta, sum, domain_h.size()),
+        )?
+        .with_scaling_factor(3);
+
+        let h_sum_proof = ZeroOverK::<C>::prove(
+            &[&h_acc, &u_a_poly, &b_v_poly],
+            &[
+                acc_commitments[1].clone(),
+                vector_commitments[0].clone(),
+                vector_commitments[1].clone(),
+            ],
+            &[
+                acc_rands[1].clone(),
+                vector_rands[0].clone(),
+                vector_rands[1].clone(),
+            ],
+            bound,
+            &h_sum_vo,
+            domain_h,
+            ck,
+            &Self::sum_context(context, b"h_sum", challenges, sum)?,
+            rng,
+        )?;
+
+        Ok(Proof {
+            u_a_commit: vector_commitments[0].commitment().clone(),
+            b_v_commit: vector_commitments[1].commitment().clone(),
+            k_acc_commit: acc_commitments[0].commitment().clone(),
+            h_acc_commit: acc_commitments[1].commitment().clone(),
+            sum,
+            k_sum_proof,
+            h_sum_proof,
+        })
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(vk, statement, proof, context, fs_rng).into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("matrix_product_test::verify");
+        let mut report = VerificationReport::builder("matrix_product_test");
+
+        let domain_k = &statement.domain_k;
+        let domain_h = &statement.domain_h;
+        let bound = statement.enforced_degree_bound;
+
+        let labeled = |label: &str, commitment: pc::Commitment<C>| {
+            LabeledCommitment::new(String::from(label), commitment, bound)
+        };
+        let vector_commitments = vec![
+            labeled("u_a", proof.u_a_commit),
+            labeled("b_v", proof.b_v_commit),
+        ];
+        let acc_commitments = vec![
+            labeled("k_acc", proof.k_acc_commit),
+            labeled("h_acc", proof.h_acc_commit),
+        ];
+        let sum = proof.sum;
+
+        let mut challenges = [C::F::zero(); 4];
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+
+            let mut round = ChallengeSet::new(&mut *fs_rng, b"vectors");
+            challenges[0] = round.challenge(b"x");
+            challenges[1] = round.challenge(b"y");
+
+            let fs_bytes = &to_bytes![vector_commitments].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+
+            let mut round = ChallengeSet::new(&mut *fs_rng, b"sums");
+            challenges[2] = round.challenge(b"z");
+            challenges[3] = round.challenge(b"eta");
+
+            let fs_bytes = &to_bytes![acc_commitments, sum].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        report.check("k_sum", || {
+            let k_sum_vo = GenericShiftingVO::new(
+                &K_SUM_MAPPING,
+                &Self::accumulator_alphas(domain_k, K_SUM_MAPPING.len()),
+                Self::k_sum_check(challenges, sum, domain_k.size()),
+            )?
+            .with_scaling_factor(7);
+
+            ZeroOverK::<C>::verify(
+                proof.k_sum_proof,
+                &[acc_commitments[0].clone()]
+                    .into_iter()
+                    .chain(statement.commitments())
+                    .collect::<Vec<_>>(),
+                bound,
+                &k_sum_vo,
+                domain_k,
+                vk,
+                &Self::sum_context(context, b"k_sum", challenges, sum)?,
+            )
+            .map_err(Error::from)
+        });
+
+        report.check("h_sum", || {
+            let [_, _, z, eta] = challenges;
+            let h_sum_vo = GenericShiftingVO::new(
+                &H_SUM_MAPPING,
+                &Self::accumulator_alphas(domain_h, H_SUM_MAPPING.len()),
+                Self::h_sum_check(z, eta, sum, domain_h.size()),
+            )?
+            .with_scaling_factor(3);
+
+            ZeroOverK::<C>::verify(
+                proof.h_sum_proof,
+                &[
+                    acc_commitments[1].clone(),
+                    vector_commitments[0].clone(),
+                    vector_commitments[1].clone(),
+                ],
+                bound,
+                &h_sum_vo,
+                domain_h,
+                vk,
+                &Self::sum_context(context, b"h_sum", challenges, sum)?,
+            )
+            .map_err(Error::from)
+        });
+
+        report.finish()
+    }
+
+    #[cfg(feature = "prover")]
+    /// The vector over H whose entry at index i is Σ val / (challenge - point) over the entries with line = ω^i.
+    /// Folding A by rows gives u^T * A, folding B by columns gives B * v.
+    fn fold(
+        domain_h: &DomainH<C::F>,
+        challenge: C::F,
+        points: &[C::F],
+        lines: &[C::F],
+        vals: &[C::F],
+        label: &str,
+    ) -> Result<Vec<C::F>, Error> {
+        let lines = PIOPforDLComparison::exponents_of_evals(domain_h, label, lines)?;
+        let mut denominators = points
+            .iter()
+            .map(|&point| challenge - point)
+            .collect::<Vec<_>>();
+        batch_inversion(&mut denominators);
+
+        let mut folded = vec![C::F::zero(); domain_h.size()];
+        for ((line, val), denominator) in lines.into_iter().zip(vals).zip(denominators) {
+            folded[line] += *val * denominator;
+        }
+        Ok(folded)
+    }
+
+    #[cfg(feature = "prover")]
+    /// The terms val / ((p - row) * (q - col)) of an encoding over K
+    fn terms(p: C::F, q: C::F, encoding: &SparseMatrixEncoding<C::F>) -> Vec<C::F> {
+        let mut denominators = encoding
+            .row_evals
+            .evals
+            .iter()
+            .zip(encoding.col_evals.evals.iter())
+            .map(|(&row, &col)| (p - row) * (q - col))
+            .collect::<Vec<_>>();
+        batch_inversion(&mut denominators);
+
+        encoding
+            .val_evals
+            .evals
+            .iter()
+            .zip(denominators)
+            .map(|(&val, denominator)| val * denominator)
+            .collect()
+    }
+
+    #[cfg(feature = "prover")]
+    /// The row, col and val polynomials of M, A and B, under the labels of `Statement::commitments`
+    fn oracles(
+        encodings: [&SparseMatrixEncoding<C::F>; 3],
+        enforced_degree_bound: Option<usize>,
+    ) -> Vec<LabeledPolynomial<C::F, DensePolynomial<C::F>>> {
+        ["m", "a", "b"]
+            .iter()
+            .zip(encodings)
+            .flat_map(|(prefix, encoding)| encoding.oracles(prefix, enforced_degree_bound))
+            .collect()
+    }
+
+    /// The accumulator is shifted by the generator of its domain in the second term, every other oracle appears
+    /// unshifted
+    fn accumulator_alphas(domain: &GeneralEvaluationDomain<C::F>, len: usize) -> Vec<C::F> {
+        let mut alphas = vec![C::F::one(); len];
+        alphas[1] = domain.element(1);
+        alphas
+    }
+
+    /// (acc(gamma * X) - acc(X) + sum / |K|) * d_M * d_A * d_B - val_M * d_A * d_B - η * val_A * d_M * d_B -
+    /// η^2 * val_B * d_M * d_A, where d_M = (x - row_M) * (y - col_M), d_A = (x - row_A) * (z - col_A) and
+    /// d_B = (z - row_B) * (y - col_B), over the terms
+    /// [X, acc(X), acc(gamma * X), row_M(X), col_M(X), val_M(X), row_A(X), ..., val_B(X)]
+    fn k_sum_check(
+        challenges: [C::F; 4],
+        sum: C::F,
+        size: usize,
+    ) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        let [x, y, z, eta] = challenges;
+        let step = sum / C::F::from(size as u64);
+        move |terms: &[VOTerm<C::F>]| {
+            let denominator = |p: C::F, row: &VOTerm<C::F>, q: C::F, col: &VOTerm<C::F>| {
+                (vo_constant!(p) - row.clone()) * (vo_constant!(q) - col.clone())
+            };
+            let d_m = denominator(x, &terms[3], y, &terms[4]);
+            let d_a = denominator(x, &terms[6], z, &terms[7]);
+            let d_b = denominator(z, &terms[9], y, &terms[10]);
+
+            (terms[2].clone() - terms[1].clone() + vo_constant!(step))
+                * d_m.clone()
+                * d_a.clone()
+                * d_b.clone()
+                - (terms[5].clone() * d_a.clone() * d_b.clone()
+                    + vo_constant!(eta) * terms[8].clone() * d_m.clone() * d_b
+                    + vo_constant!(eta * eta) * terms[11].clone() * d_m * d_a)
+        }
+    }
+
+    /// (acc(omega * X) - acc(X) + sum / |H| - u_a(X) * b_v(X)) * (z - X) - η * u_a(X) - η^2 * b_v(X), over the terms
+    /// [X, acc(X), acc(omega * X), u_a(X), b_v(X)]
+    fn h_sum_check(
+        z: C::F,
+        eta: C::F,
+        sum: C::F,
+        size: usize,
+    ) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        let step = sum / C::F::from(size as u64);
+        move |terms: &[VOTerm<C::F>]| {
+            (terms[2].clone() - terms[1].clone() + vo_constant!(step)
+                - terms[3].clone() * terms[4].clone())
+                * (vo_constant!(z) - terms[0].clone())
+                - vo_constant!(eta) * terms[3].clone()
+                - vo_constant!(eta * eta) * terms[4].clone()
+        }
+    }
+
+    /// The zero over K sub-proofs run in their own transcripts, so bind them to the challenges and the claimed sum
+    fn sum_context(
+        context: &Context,
+        label: &[u8],
+        challenges: [C::F; 4],
+        sum: C::F,
+    ) -> Result<Context, Error> {
+        let bytes = to_bytes![challenges.to_vec(), sum].map_err(|_| Error::ToBytesError)?;
+        Ok(context.child(label).child(&Blake2s::digest(&bytes)))
+    }
+}
@@ -0,0 +1,224 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{committed, domains, encode},
+            PaddingStrategy,
+        },
+        matrix_product_test::{MatrixProductTest, Statement},
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // A and B values
+    /*
+        1, 2, 0, 0      0, 1, 0, 0
+        0, 0, 3, 0      2, 0, 0, 0
+        0, 0, 0, 0      0, 0, 0, 3
+        4, 0, 0, 5      0, 0, 4, 0
+    */
+    fn a_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 0, F::from(1u64)),
+            (0, 1, F::from(2u64)),
+            (1, 2, F::from(3u64)),
+            (3, 0, F::from(4u64)),
+            (3, 3, F::from(5u64)),
+        ]
+    }
+
+    fn b_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 1, F::from(1u64)),
+            (1, 0, F::from(2u64)),
+            (2, 3, F::from(3u64)),
+            (3, 2, F::from(4u64)),
+        ]
+    }
+
+    // M = A * B
+    /*
+        4, 1, 0,  0
+        0, 0, 0,  9
+        0, 0, 0,  0
+        0, 4, 20, 0
+    */
+    fn product_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 0, F::from(4u64)),
+            (0, 1, F::from(1u64)),
+            (1, 3, F::from(9u64)),
+            (3, 1, F::from(4u64)),
+            (3, 2, F::from(20u64)),
+        ]
+    }
+
+    fn identity_entries() -> Vec<(usize, usize, F)> {
+        (0..4).map(|i| (i, i, F::from(1u64))).collect()
+    }
+
+    fn run_matrix_product_test(
+        a_entries: &[(usize, usize, F)],
+        b_entries: &[(usize, usize, F)],
+        m_entries: &[(usize, usize, F)],
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let max_degree = 80;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (a, b, m) = (
+            encode(a_entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast),
+            encode(b_entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast),
+            encode(m_entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast),
+        );
+        let (a_commit, a_rands) = committed::<F, PC>(&ck, &a);
+        let (b_commit, b_rands) = committed::<F, PC>(&ck, &b);
+        let (m_commit, m_rands) = committed::<F, PC>(&ck, &m);
+
+        let statement = Statement::<C> {
+            domain_k,
+            domain_h,
+            a: a_commit,
+            b: b_commit,
+            m: m_commit,
+            enforced_degree_bound: Some(enforced_degree_bound),
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = MatrixProductTest::<C>::prove(
+            &ck,
+            &statement,
+            &a,
+            &a_rands,
+            &b,
+            &b_rands,
+            &m,
+            &m_rands,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        MatrixProductTest::<C>::verify(&vk, &statement, proof, &Context::new(b"test"), &mut fs_rng)
+    }
+
+    #[test]
+    fn test_matrix_product() {
+        assert_eq!(
+            run_matrix_product_test(&a_entries(), &b_entries(), &product_entries()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_identity() {
+        assert_eq!(
+            run_matrix_product_test(&a_entries(), &identity_entries(), &a_entries()),
+            Ok(())
+        );
+        assert_eq!(
+            run_matrix_product_test(&identity_entries(), &b_entries(), &b_entries()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_unordered_product() {
+        // (3, 2) split over two entries of M, listed out of order
+        let mut entries = product_entries();
+        entries[4].2 = F::from(15u64);
+        entries.push((3, 2, F::from(5u64)));
+        entries.reverse();
+        assert_eq!(
+            run_matrix_product_test(&a_entries(), &b_entries(), &entries),
+            Ok(())
+        );
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_wrong_product() {
+        let mut entries = product_entries();
+        entries[4].2 = F::from(21u64);
+        assert!(run_matrix_product_test(&a_entries(), &b_entries(), &entries).is_err());
+
+        // B * A
+        assert!(run_matrix_product_test(&b_entries(), &a_entries(), &product_entries()).is_err());
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[domain_k.size() + 1])).unwrap();
+
+        let validate = |m_entries: &[(usize, usize, F)]| {
+            let (a, b, m) = (
+                encode(
+                    &a_entries(),
+                    &domain_k,
+                    &domain_h,
+                    PaddingStrategy::RepeatLast,
+                ),
+                encode(
+                    &b_entries(),
+                    &domain_k,
+                    &domain_h,
+                    PaddingStrategy::RepeatLast,
+                ),
+                encode(m_entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast),
+            );
+            let statement = Statement::<C> {
+                domain_k,
+                domain_h,
+                a: committed::<F, PC>(&ck, &a).0,
+                b: committed::<F, PC>(&ck, &b).0,
+                m: committed::<F, PC>(&ck, &m).0,
+                enforced_degree_bound: None,
+            };
+            MatrixProductTest::<C>::validate_witness(&statement, &a, &b, &m)
+        };
+
+        assert_eq!(validate(&product_entries()), Ok(()));
+
+        let mut entries = product_entries();
+        entries[2].2 = F::from(8u64);
+        assert_eq!(
+            validate(&entries),
+            Err(Error::InvalidWitness(WitnessError::ProductMismatch {
+                row: 1,
+                col: 3
+            }))
+        );
+
+        // the first mismatch in row-major order
+        assert_eq!(
+            validate(&a_entries()),
+            Err(Error::InvalidWitness(WitnessError::ProductMismatch {
+                row: 0,
+                col: 0
+            }))
+        );
+    }
+}
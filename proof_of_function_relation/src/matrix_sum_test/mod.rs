@@ -0,0 +1,369 @@
+use crate::{
+    discrete_log_comparison::piop::PIOPforDLComparison,
+    domains::{DomainH, DomainK},
+    error::{Error, WitnessError},
+    indexer::{CommittedEncoding, SparseMatrixEncoding},
+    matrix_sum_test::proof::Proof,
+    report::VerificationReport,
+};
+use ark_ff::{to_bytes, One, SquareRootField, Zero};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::LabeledCommitment;
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use blake2::{Blake2s, Digest};
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use std::{collections::BTreeMap, marker::PhantomData};
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {
+    crate::error::to_pc_error,
+    ark_ff::batch_inversion,
+    ark_poly::{univariate::DensePolynomial, UVPolynomial},
+    ark_poly_commit::LabeledPolynomial,
+    rand::Rng,
+};
+
+pub mod proof;
+mod tests;
+
+/// The public data of a matrix sum test: the committed encodings over K of the matrices M, M1 and M2
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<C::F>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_h: DomainH<C::F>,
+    pub m: CommittedEncoding<pc::Commitment<C>>,
+    pub m1: CommittedEncoding<pc::Commitment<C>>,
+    pub m2: CommittedEncoding<pc::Commitment<C>>,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+impl<C: ProtocolConfig> Statement<C> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_bytes![
+            self.domain_k.size() as u64,
+            self.domain_h.size() as u64,
+            self.m.iter().collect::<Vec<_>>(),
+            self.m1.iter().collect::<Vec<_>>(),
+            self.m2.iter().collect::<Vec<_>>()
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+
+    /// The commitments of the three encodings under the labels of `MatrixSumTest::oracles`
+    fn commitments(&self) -> Vec<LabeledCommitment<pc::Commitment<C>>> {
+        [("m", &self.m), ("m1", &self.m1), ("m2", &self.m2)]
+            .iter()
+            .flat_map(|(prefix, encoding)| encoding.oracles(prefix, self.enforced_degree_bound))
+            .collect()
+    }
+}
+
+/// acc appears twice, followed by the row, col and val oracles of M, M1 and M2
+const SUM_CHECK_MAPPING: [usize; 11] = [0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+/// Proves that the matrix M is the sum of the matrices M1 and M2 entrywise, which lets a function be committed to
+/// as independent parts. The three matrices are given as sparse encodings over the same domain K, whose entries
+/// need not be aligned: M may list its non-zero entries in any order, split or merge entries that share a row and
+/// column, and omit the entries of M1 and M2 that cancel out.
+///
+/// The index pairs are aligned with a rational identity. For verifier challenges beta and gamma,
+///
+/// sum_K val / (beta - row - gamma * col) = sum_K val1 / (beta - row1 - gamma * col1) + sum_K val2 / (beta - ...)
+///
+/// holds if and only if every (row, col) pair accumulates the same value in M as in M1 and M2 together, up to a
+/// negligible probability. The prover commits to acc, the running sum of the terms of M minus those of M1 and M2
+/// over K, and a single ZeroOverK shows that acc(gamma_K * X) - acc(X) is the term at X. Since K is a cycle, the
+/// terms then add up to 0.
+pub struct MatrixSumTest<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> MatrixSumTest<C>
+where
+    C::F: SquareRootField,
+{
+    pub const PROTOCOL_NAME: &'static [u8] = b"Matrix Sum Test";
+
+    /// Check that every entry of M is the sum of the entries of M1 and M2 at the same row and column, failing on the
+    /// first offending entry in row-major order before any work is done. With the `validate-witness` feature the
+    /// prover runs this check itself.
+    pub fn validate_witness(
+        statement: &Statement<C>,
+        m: &SparseMatrixEncoding<C::F>,
+        m1: &SparseMatrixEncoding<C::F>,
+        m2: &SparseMatrixEncoding<C::F>,
+    ) -> Result<(), Error> {
+        let mut differences = BTreeMap::<(usize, usize), C::F>::new();
+        for (encoding, sign) in [(m, C::F::one()), (m1, -C::F::one()), (m2, -C::F::one())] {
+            let exponents_of = |poly| {
+                PIOPforDLComparison::exponents(&statement.domain_k, &statement.domain_h, poly)
+            };
+            let row_exponents = exponents_of(&encoding.row)?;
+            let col_exponents = exponents_of(&encoding.col)?;
+
+            for ((row, col), val) in row_exponents
+                .into_iter()
+                .zip(col_exponents)
+                .zip(statement.domain_k.fft(encoding.val.polynomial()))
+            {
+                *differences.entry((row, col)).or_insert_with(C::F::zero) += sign * val;
+            }
+        }
+
+        match differences
+            .into_iter()
+            .find(|(_, difference)| !difference.is_zero())
+        {
+            Some(((row, col), _)) => Err(Error::InvalidWitness(WitnessError::SumMismatch {
+                row,
+                col,
+            })),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "prover")]
+    /// The randomness of each encoding is given in the order of [`SparseMatrixEncoding::iter`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        m: &SparseMatrixEncoding<C::F>,
+        m_rands: &[pc::Randomness<C>],
+        m1: &SparseMatrixEncoding<C::F>,
+        m1_rands: &[pc::Randomness<C>],
+        m2: &SparseMatrixEncoding<C::F>,
+        m2_rands: &[pc::Randomness<C>],
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("matrix_sum_test::prove");
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(statement, m, m1, m2)?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let mut round = ChallengeSet::new(&mut *fs_rng, b"fingerprint");
+        let beta: C::F = round.challenge(b"beta");
+        let gamma: C::F = round.challenge(b"gamma");
+
+        let domain_k = &statement.domain_k;
+        let bound = statement.enforced_degree_bound;
+
+        // Step 1: interpolate the running sum acc, with acc(gamma_K^0) = 0. The denominators only vanish if beta hits
+        // the fingerprint of an entry, which happens with negligible probability.
+        let encodings = [m, m1, m2];
+        let mut denominators = encodings
+            .iter()
+            .flat_map(|encoding| {
+                encoding
+                    .row_evals
+                    .evals
+                    .iter()
+                    .zip(encoding.col_evals.evals.iter())
+                    .map(|(&row, &col)| beta - Self::fingerprint(gamma, row, col))
+            })
+            .collect::<Vec<_>>();
+        batch_inversion(&mut denominators);
+
+        let size = domain_k.size();
+        let mut acc_evals = Vec::with_capacity(size);
+        let mut acc = C::F::zero();
+        for i in 0..size {
+            acc_evals.push(acc);
+            acc += m.val_evals.evals[i] * denominators[i]
+                - m1.val_evals.evals[i] * denominators[size + i]
+                - m2.val_evals.evals[i] * denominators[2 * size + i];
+        }
+
+        let acc = DensePolynomial::from_coefficients_vec(domain_k.ifft(&acc_evals));
+        let acc = LabeledPolynomial::new(String::from("acc"), acc, bound, Some(1));
+
+        let (acc_commit, acc_rand) =
+            C::PC::commit(ck, [&acc], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![acc_commit].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        // Step 2: Zero over K for acc(gamma_K * X) - acc(X) = val / d - val1 / d1 - val2 / d2, with the denominators
+        // cleared
+        let oracles = Self::oracles(&encodings, bound);
+        let concrete_oracles = [&acc].into_iter().chain(oracles.iter()).collect::<Vec<_>>();
+        let commitments = [acc_commit[0].clone()]
+            .into_iter()
+            .chain(statement.commitments())
+            .collect::<Vec<_>>();
+        let rands = [&acc_rand[..], m_rands, m1_rands, m2_rands].concat();
+
+        let sum_check_vo = GenericShiftingVO::new(
+            &SUM_CHECK_MAPPING,
+            &Self::sum_check_alphas(domain_k),
+            Self::sum_check(beta, gamma),
+        )?
+        .with_scaling_factor(4);
+        let sum_check_proof = ZeroOverK::<C>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            bound,
+            &sum_check_vo,
+            domain_k,
+            ck,
+            &Self::sum_check_context(context, beta, gamma)?,
+            rng,
+        )?;
+
+        Ok(Proof {
+            acc_commit: acc_commit[0].commitment().clone(),
+            sum_check_proof,
+        })
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(vk, statement, proof, context, fs_rng).into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("matrix_sum_test::verify");
+        let mut report = VerificationReport::builder("matrix_sum_test");
+        let bound = statement.enforced_degree_bound;
+
+        let mut challenges = (C::F::zero(), C::F::zero());
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            let mut round = ChallengeSet::new(&mut *fs_rng, b"fingerprint");
+            challenges = (round.challenge(b"beta"), round.challenge(b"gamma"));
+            Ok::<(), Error>(())
+        });
+        let (beta, gamma) = challenges;
+
+        let acc_commit = LabeledCommitment::new(String::from("acc"), proof.acc_commit, bound);
+
+        report.check("acc_commit", || {
+            let fs_bytes =
+                &to_bytes![[acc_commit.clone()].to_vec()].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        report.check("sum_check", || {
+            let commitments = [acc_commit.clone()]
+                .into_iter()
+                .chain(statement.commitments())
+                .collect::<Vec<_>>();
+            let sum_check_vo = GenericShiftingVO::new(
+                &SUM_CHECK_MAPPING,
+                &Self::sum_check_alphas(&statement.domain_k),
+                Self::sum_check(beta, gamma),
+            )?
+            .with_scaling_factor(4);
+
+            ZeroOverK::<C>::verify(
+                proof.sum_check_proof,
+                &commitments,
+                bound,
+                &sum_check_vo,
+                &statement.domain_k,
+                vk,
+                &Self::sum_check_context(context, beta, gamma)?,
+            )
+            .map_err(Error::from)
+        });
+
+        report.finish()
+    }
+
+    #[cfg(feature = "prover")]
+    /// Random linear combination of the row and column of an entry
+    fn fingerprint(gamma: C::F, row: C::F, col: C::F) -> C::F {
+        row + gamma * col
+    }
+
+    #[cfg(feature = "prover")]
+    /// The row, col and val polynomials of the three encodings, under the labels of `Statement::commitments`
+    fn oracles(
+        encodings: &[&SparseMatrixEncoding<C::F>],
+        enforced_degree_bound: Option<usize>,
+    ) -> Vec<LabeledPolynomial<C::F, DensePolynomial<C::F>>> {
+        ["m", "m1", "m2"]
+            .iter()
+            .zip(encodings)
+            .flat_map(|(prefix, encoding)| encoding.oracles(prefix, enforced_degree_bound))
+            .collect()
+    }
+
+    /// acc is shifted by gamma_K in the second term, every other oracle appears once unshifted
+    fn sum_check_alphas(domain_k: &GeneralEvaluationDomain<C::F>) -> Vec<C::F> {
+        let mut alphas = vec![C::F::one(); SUM_CHECK_MAPPING.len()];
+        alphas[1] = domain_k.element(1);
+        alphas
+    }
+
+    /// (acc(gamma_K * X) - acc(X)) * d * d1 * d2 - (val * d1 * d2 - val1 * d * d2 - val2 * d * d1), where
+    /// d = beta - fingerprint(row, col), over the terms
+    /// [X, acc(X), acc(gamma_K * X), row(X), col(X), val(X), row1(X), col1(X), val1(X), row2(X), col2(X), val2(X)]
+    fn sum_check(beta: C::F, gamma: C::F) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        move |terms: &[VOTerm<C::F>]| {
+            let denominator = |row: &VOTerm<C::F>, col: &VOTerm<C::F>| {
+                vo_constant!(beta) - (row.clone() + vo_constant!(gamma) * col.clone())
+            };
+            let d = denominator(&terms[3], &terms[4]);
+            let d1 = denominator(&terms[6], &terms[7]);
+            let d2 = denominator(&terms[9], &terms[10]);
+
+            (terms[2].clone() - terms[1].clone()) * d.clone() * d1.clone() * d2.clone()
+                - (terms[5].clone() * d1.clone() * d2.clone()
+                    - terms[8].clone() * d.clone() * d2
+                    - terms[11].clone() * d * d1)
+        }
+    }
+
+    fn sum_check_context(context: &Context, beta: C::F, gamma: C::F) -> Result<Context, Error> {
+        let challenges = to_bytes![beta, gamma].map_err(|_| Error::ToBytesError)?;
+        Ok(context
+            .child(b"sum_check")
+            .child(&Blake2s::digest(&challenges)))
+    }
+}
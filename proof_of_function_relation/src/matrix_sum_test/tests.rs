@@ -0,0 +1,208 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{committed, domains, encode},
+            PaddingStrategy,
+        },
+        matrix_sum_test::{MatrixSumTest, Statement},
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // M1 and M2 values
+    /*
+        1, 0, 0, 0      0, 0, 0, 0
+        0, 2, 0, 0      0, 3, 0, 4
+        0, 0, 0, 0      0, 0, 0, 0
+        5, 0, 0, 6      0, 0, 0,-6
+    */
+    fn m1_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 0, F::from(1u64)),
+            (1, 1, F::from(2u64)),
+            (3, 0, F::from(5u64)),
+            (3, 3, F::from(6u64)),
+        ]
+    }
+
+    fn m2_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (1, 1, F::from(3u64)),
+            (1, 3, F::from(4u64)),
+            (3, 3, -F::from(6u64)),
+        ]
+    }
+
+    // M = M1 + M2, listed out of order and without the cancelled entry (3, 3)
+    fn sum_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (1, 3, F::from(4u64)),
+            (0, 0, F::from(1u64)),
+            (3, 0, F::from(5u64)),
+            (1, 1, F::from(5u64)),
+        ]
+    }
+
+    fn run_matrix_sum_test(
+        m_entries: &[(usize, usize, F)],
+        m1_entries: &[(usize, usize, F)],
+        m2_entries: &[(usize, usize, F)],
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let max_degree = 40;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (m, m1, m2) = (
+            encode(m_entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast),
+            encode(
+                m1_entries,
+                &domain_k,
+                &domain_h,
+                PaddingStrategy::RepeatLast,
+            ),
+            encode(
+                m2_entries,
+                &domain_k,
+                &domain_h,
+                PaddingStrategy::RepeatLast,
+            ),
+        );
+        let (m_commit, m_rands) = committed::<F, PC>(&ck, &m);
+        let (m1_commit, m1_rands) = committed::<F, PC>(&ck, &m1);
+        let (m2_commit, m2_rands) = committed::<F, PC>(&ck, &m2);
+
+        let statement = Statement::<C> {
+            domain_k,
+            domain_h,
+            m: m_commit,
+            m1: m1_commit,
+            m2: m2_commit,
+            enforced_degree_bound: Some(enforced_degree_bound),
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = MatrixSumTest::<C>::prove(
+            &ck,
+            &statement,
+            &m,
+            &m_rands,
+            &m1,
+            &m1_rands,
+            &m2,
+            &m2_rands,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        MatrixSumTest::<C>::verify(&vk, &statement, proof, &Context::new(b"test"), &mut fs_rng)
+    }
+
+    #[test]
+    fn test_matrix_sum() {
+        assert_eq!(
+            run_matrix_sum_test(&sum_entries(), &m1_entries(), &m2_entries()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_split_entries() {
+        // (1, 1) split over two entries of M
+        let mut entries = sum_entries();
+        entries[3].2 = F::from(1u64);
+        entries.push((1, 1, F::from(4u64)));
+        assert_eq!(
+            run_matrix_sum_test(&entries, &m1_entries(), &m2_entries()),
+            Ok(())
+        );
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_wrong_sum() {
+        let mut entries = sum_entries();
+        entries[3].2 = F::from(6u64);
+        assert!(run_matrix_sum_test(&entries, &m1_entries(), &m2_entries()).is_err());
+
+        // M1 alone
+        assert!(run_matrix_sum_test(&m1_entries(), &m1_entries(), &m2_entries()).is_err());
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[domain_k.size() + 1])).unwrap();
+
+        let validate = |m_entries: &[(usize, usize, F)]| {
+            let (m, m1, m2) = (
+                encode(m_entries, &domain_k, &domain_h, PaddingStrategy::RepeatLast),
+                encode(
+                    &m1_entries(),
+                    &domain_k,
+                    &domain_h,
+                    PaddingStrategy::RepeatLast,
+                ),
+                encode(
+                    &m2_entries(),
+                    &domain_k,
+                    &domain_h,
+                    PaddingStrategy::RepeatLast,
+                ),
+            );
+            let statement = Statement::<C> {
+                domain_k,
+                domain_h,
+                m: committed::<F, PC>(&ck, &m).0,
+                m1: committed::<F, PC>(&ck, &m1).0,
+                m2: committed::<F, PC>(&ck, &m2).0,
+                enforced_degree_bound: None,
+            };
+            MatrixSumTest::<C>::validate_witness(&statement, &m, &m1, &m2)
+        };
+
+        assert_eq!(validate(&sum_entries()), Ok(()));
+
+        let mut entries = sum_entries();
+        entries[0].2 = F::from(3u64);
+        assert_eq!(
+            validate(&entries),
+            Err(Error::InvalidWitness(WitnessError::SumMismatch {
+                row: 1,
+                col: 3
+            }))
+        );
+
+        // the first mismatch in row-major order
+        assert_eq!(
+            validate(&m1_entries()),
+            Err(Error::InvalidWitness(WitnessError::SumMismatch {
+                row: 1,
+                col: 1
+            }))
+        );
+    }
+}
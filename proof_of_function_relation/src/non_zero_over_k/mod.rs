@@ -1,36 +1,188 @@
-use crate::error::{to_pc_error, Error};
-use crate::non_zero_over_k::{piop::PIOPforNonZeroOverK, proof::Proof};
-use ark_ff::PrimeField;
-use ark_poly::{univariate::DensePolynomial, GeneralEvaluationDomain};
-use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
-use fiat_shamir_rng::FiatShamirRng;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
-use rand::Rng;
+use crate::error::Error;
+use crate::non_zero_over_k::proof::Proof;
+use ark_ff::One;
+use ark_poly::GeneralEvaluationDomain;
+use ark_poly_commit::LabeledCommitment;
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use fiat_shamir_rng::{Context, Transcript};
+use homomorphic_poly_commit::{commit_policy::CommitPolicy, AdditivelyHomomorphicPCS};
 use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
 use zero_over_k::{
-    virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
+    coset_domain::CosetDomain,
+    proof_shape::ProofShape,
+    trace_span,
+    virtual_oracle::{
+        generic_shifting_vo::{presets, GenericShiftingVO},
+        VirtualOracle,
+    },
     zero_over_k::ZeroOverK,
 };
+#[cfg(feature = "prover")]
+use {
+    crate::{error::to_pc_error, non_zero_over_k::piop::PIOPforNonZeroOverK},
+    ark_poly::univariate::DensePolynomial,
+    ark_poly_commit::LabeledPolynomial,
+    homomorphic_poly_commit::opening_hint::OpeningHint,
+    rand::Rng,
+    zero_over_k::cancellation::CancellationToken,
+};
 
 pub mod piop;
 pub mod proof;
 mod tests;
 
-pub struct NonZeroOverK<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> {
-    _field: PhantomData<F>,
-    _pc: PhantomData<PC>,
-    _fs_rng: PhantomData<FS>,
+pub struct NonZeroOverK<C: ProtocolConfig> {
+    _config: PhantomData<C>,
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> NonZeroOverK<F, PC, FS> {
+impl<C: ProtocolConfig> NonZeroOverK<C> {
+    /// The commitments and queries of a proof that an f with degree bound `degree_bound` does not vanish: g and
+    /// the ZeroOverK showing that f * g = 1
+    pub fn prove_dry_run(degree_bound: Option<usize>) -> Result<ProofShape, Error> {
+        let mut shape = ProofShape::new();
+        shape.commit("g", degree_bound);
+        shape.extend(
+            "inverse_check",
+            ZeroOverK::<C>::prove_dry_run(degree_bound, &Self::inverse_check_oracle()?),
+        );
+        Ok(shape)
+    }
+
+    /// Serialized size in bytes of a proof for an f with degree bound `degree_bound`, with keys of degree
+    /// `supported_degree`
+    pub fn proof_size(
+        degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        Ok(C::PC::commitment_size(degree_bound.is_some())
+            + ZeroOverK::<C>::proof_size(
+                degree_bound,
+                &Self::inverse_check_oracle()?,
+                CommitPolicy::NonHiding,
+                supported_degree,
+            ))
+    }
+
+    #[cfg(feature = "prover")]
     pub fn prove<R: Rng>(
-        ck: &PC::CommitterKey,
-        domain: &GeneralEvaluationDomain<F>,
-        f: &LabeledPolynomial<F, DensePolynomial<F>>,
-        f_commit: &LabeledCommitment<PC::Commitment>,
-        f_rand: &PC::Randomness,
+        ck: &pc::CommitterKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_over_coset(
+            ck,
+            &CosetDomain::subgroup(*domain),
+            f,
+            f_commit,
+            f_rand,
+            context,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove that f does not vanish over a coset of K rather than K itself
+    pub fn prove_over_coset<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &CosetDomain<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(ck, domain, f, f_commit, f_rand, context, None, None, rng)
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove`], polling `cancellation` between the rounds, see [`zero_over_k::cancellation`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_cancellation<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            ck,
+            &CosetDomain::subgroup(*domain),
+            f,
+            f_commit,
+            f_rand,
+            context,
+            None,
+            cancellation,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove_over_coset`], on a transcript managed by the caller
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_transcript<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &CosetDomain<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            ck,
+            domain,
+            f,
+            f_commit,
+            f_rand,
+            context,
+            Some(transcript),
+            None,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove that f does not vanish over K, given the [`OpeningHint`] of its owner rather than f itself, see
+    /// [`homomorphic_poly_commit::opening_hint`]
+    pub fn prove_from_hint<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        f_hint: &OpeningHint<C::F, C::PC>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        context: &Context,
         rng: &mut R,
-    ) -> Result<Proof<F, PC>, Error> {
+    ) -> Result<Proof<C>, Error> {
+        let f = f_hint.to_oracle()?;
+        ZeroOverK::<C>::check_oracle_labels(&[&f], ark_std::slice::from_ref(f_commit))?;
+
+        Self::prove(ck, domain, &f, f_commit, &f_hint.randomness, context, rng)
+    }
+
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    fn prove_in<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &CosetDomain<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("non_zero_over_k::prove");
         //-----------------------------------------------
         // INIT PROVER
         let prover_initial_state = PIOPforNonZeroOverK::prover_init(domain, f)?;
@@ -42,23 +194,25 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> NonZeroO
 
         //-----------------------------------------------
         // RUN SUBPROTOCOLS
-        let (commitments, rands) = PC::commit(ck, &[prover_first_oracles.g.clone()], Some(rng))
-            .map_err(to_pc_error::<F, PC>)?;
+        let (commitments, rands) = C::PC::commit(ck, prover_first_oracles.iter(), Some(rng))
+            .map_err(to_pc_error::<C::F, C::PC>)?;
 
-        let concrete_oracles = [f.clone(), prover_first_oracles.g.clone()];
+        let concrete_oracles = [f, &prover_first_oracles.g];
 
-        let alphas = vec![F::one(), F::one()];
-        let inverse_check_oracle =
-            GenericShiftingVO::new(&vec![0, 1], &alphas, presets::inverse_check)?;
+        let inverse_check_oracle = Self::inverse_check_oracle()?;
 
-        let zero_over_k_proof = ZeroOverK::<F, PC, FS>::prove(
+        let zero_over_k_proof = ZeroOverK::<C>::prove_in(
             &concrete_oracles,
             &[f_commit.clone(), commitments[0].clone()],
             &[f_rand.clone(), rands[0].clone()],
             f.degree_bound(),
             &inverse_check_oracle,
-            &domain,
+            domain,
             ck,
+            CommitPolicy::NonHiding,
+            &context.child(b"inverse_check"),
+            transcript,
+            cancellation,
             rng,
         )?;
 
@@ -71,12 +225,74 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> NonZeroO
     }
 
     pub fn verify(
-        vk: &PC::VerifierKey,
-        domain: &GeneralEvaluationDomain<F>,
-        f_commit: PC::Commitment,
+        vk: &pc::VerifierKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        f_commit: pc::Commitment<C>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        Self::verify_over_coset(
+            vk,
+            &CosetDomain::subgroup(*domain),
+            f_commit,
+            enforced_degree_bound,
+            proof,
+            context,
+        )
+    }
+
+    /// Verify that f does not vanish over a coset of K, see [`Self::prove_over_coset`]
+    pub fn verify_over_coset(
+        vk: &pc::VerifierKey<C>,
+        domain: &CosetDomain<C::F>,
+        f_commit: pc::Commitment<C>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        Self::verify_in(
+            vk,
+            domain,
+            f_commit,
+            enforced_degree_bound,
+            proof,
+            context,
+            None,
+        )
+    }
+
+    /// Verify a proof of [`Self::prove_with_transcript`], on a transcript in the state the prover's was in
+    pub fn verify_with_transcript(
+        vk: &pc::VerifierKey<C>,
+        domain: &CosetDomain<C::F>,
+        f_commit: pc::Commitment<C>,
         enforced_degree_bound: Option<usize>,
-        proof: Proof<F, PC>,
+        proof: Proof<C>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
     ) -> Result<(), Error> {
+        Self::verify_in(
+            vk,
+            domain,
+            f_commit,
+            enforced_degree_bound,
+            proof,
+            context,
+            Some(transcript),
+        )
+    }
+
+    fn verify_in(
+        vk: &pc::VerifierKey<C>,
+        domain: &CosetDomain<C::F>,
+        f_commit: pc::Commitment<C>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
+    ) -> Result<(), Error> {
+        let _span = trace_span!("non_zero_over_k::verify");
         let bounded_f_commit =
             LabeledCommitment::new(String::from("f"), f_commit, enforced_degree_bound);
         let g_commit = LabeledCommitment::new(
@@ -86,18 +302,26 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> NonZeroO
         );
 
         let concrete_oracles_commitments = [bounded_f_commit.clone(), g_commit];
-        let alphas = vec![F::one(), F::one()];
-        let inverse_check_oracle =
-            GenericShiftingVO::new(&vec![0, 1], &alphas, presets::inverse_check)?;
+        let inverse_check_oracle = Self::inverse_check_oracle()?;
 
-        ZeroOverK::<F, PC, FS>::verify(
+        ZeroOverK::<C>::verify_in(
             proof.zero_over_k_proof,
             &concrete_oracles_commitments,
             enforced_degree_bound,
             &inverse_check_oracle,
-            &domain,
+            domain,
             &vk,
+            &context.child(b"inverse_check"),
+            transcript,
         )
         .map_err(Error::from)
     }
+
+    /// f * g - 1, which vanishes over K when g is the inverse of f
+    fn inverse_check_oracle() -> Result<impl VirtualOracle<C::F>, Error> {
+        Ok(
+            GenericShiftingVO::new(&[0, 1], &[C::F::one(), C::F::one()], presets::inverse_check)?
+                .with_scaling_factor(2),
+        )
+    }
 }
@@ -1,6 +1,7 @@
 use ark_ff::{FftField, PrimeField};
 use ark_std::marker::PhantomData;
 
+#[cfg(feature = "prover")]
 pub mod prover;
 
 #[allow(dead_code)]
@@ -2,23 +2,23 @@ use crate::error::Error;
 use crate::non_zero_over_k::piop::PIOPforNonZeroOverK;
 use ark_ff::{FftField, PrimeField};
 use ark_marlin::ahp::prover::ProverMsg;
-use ark_poly::{
-    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
-};
+use ark_poly::univariate::DensePolynomial;
 use ark_poly_commit::LabeledPolynomial;
 use ark_std::rand::Rng;
+use derivative::Derivative;
+use zero_over_k::coset_domain::CosetDomain;
 
 pub struct ProverState<'a, F: PrimeField + FftField> {
-    domain_k: &'a GeneralEvaluationDomain<F>,
+    domain_k: &'a CosetDomain<F>,
 
     f: &'a LabeledPolynomial<F, DensePolynomial<F>>,
-
-    first_oracles: Option<ProverFirstOracles<F>>,
 }
 
 /// The first set of prover oracles
-#[derive(Clone)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
 pub struct ProverFirstOracles<F: PrimeField + FftField> {
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub g: LabeledPolynomial<F, DensePolynomial<F>>,
 }
 
@@ -30,18 +30,14 @@ impl<F: PrimeField + FftField> ProverFirstOracles<F> {
 
 impl<F: PrimeField + FftField> PIOPforNonZeroOverK<F> {
     pub fn prover_init<'a>(
-        domain_k: &'a GeneralEvaluationDomain<F>,
+        domain_k: &'a CosetDomain<F>,
         f: &'a LabeledPolynomial<F, DensePolynomial<F>>,
     ) -> Result<ProverState<'a, F>, Error> {
-        Ok(ProverState {
-            domain_k,
-            f,
-            first_oracles: None,
-        })
+        Ok(ProverState { domain_k, f })
     }
 
     pub fn prover_first_round<'a, R: Rng>(
-        mut state: ProverState<'a, F>,
+        state: ProverState<'a, F>,
         _rng: &mut R,
     ) -> Result<(ProverMsg<F>, ProverFirstOracles<F>, ProverState<'a, F>), Error> {
         let f_evals = state.domain_k.fft(state.f.polynomial());
@@ -59,13 +55,8 @@ impl<F: PrimeField + FftField> PIOPforNonZeroOverK<F> {
             .map(|x| x.inverse().unwrap())
             .collect::<Vec<_>>();
 
-        let g = DensePolynomial::<F>::from_coefficients_slice(&state.domain_k.ifft(&g_evals));
-        let g = LabeledPolynomial::new(
-            String::from("g"),
-            g.clone(),
-            state.f.degree_bound(),
-            Some(1),
-        );
+        let g = state.domain_k.ifft(&g_evals);
+        let g = LabeledPolynomial::new(String::from("g"), g, state.f.degree_bound(), Some(1));
 
         // create ProverFirstOracles struct
         let prover_oracles = ProverFirstOracles { g };
@@ -73,9 +64,6 @@ impl<F: PrimeField + FftField> PIOPforNonZeroOverK<F> {
         // Prover message
         let msg = ProverMsg::EmptyMessage;
 
-        // Update Prover state
-        state.first_oracles = Some(prover_oracles.clone());
-
         Ok((msg, prover_oracles, state))
     }
 }
@@ -1,11 +1,23 @@
+use ::zero_over_k::config::{pc, ProtocolConfig};
 use ::zero_over_k::zero_over_k;
-use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Write};
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use derivative::Derivative;
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
-    pub g_commit: PC::Commitment,
-    pub zero_over_k_proof: zero_over_k::proof::Proof<F, PC>,
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig> {
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub g_commit: pc::Commitment<C>,
+    pub zero_over_k_proof: zero_over_k::proof::Proof<C>,
 }
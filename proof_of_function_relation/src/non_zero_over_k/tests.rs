@@ -1,20 +1,23 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test {
+    use crate::config::Config;
     use crate::{error::Error, non_zero_over_k::NonZeroOverK};
     use ark_bn254::{Bn254, Fr};
-    use ark_ff::Field;
     use ark_ff::One;
     use ark_ff::Zero;
+    use ark_ff::{FftField, Field};
     use ark_poly::{
         univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
     };
-    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_poly_commit::{LabeledPolynomial, PCCommitterKey, PolynomialCommitment};
+    use ark_serialize::CanonicalSerialize;
     use ark_std::rand::thread_rng;
     use blake2::Blake2s;
-    use fiat_shamir_rng::SimpleHashFiatShamirRng;
-    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::{marlin_kzg::KZG10, opening_hint::OpeningHint};
     use rand_chacha::ChaChaRng;
     use zero_over_k::{
+        coset_domain::CosetDomain,
         util::sample_vector,
         virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
     };
@@ -22,6 +25,7 @@ mod test {
     type F = Fr;
     type PC = KZG10<Bn254>;
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
 
     // This test should pass because it the randomly generated virtual oracle should
     // not evalute to 0 - if it does, just rerun the test
@@ -56,22 +60,30 @@ mod test {
 
         let (f_commit, f_rand) = PC::commit(&ck, &[f.clone()], Some(rng)).unwrap();
 
-        let proof = NonZeroOverK::<F, PC, FS>::prove(
+        let proof = NonZeroOverK::<C>::prove(
             &ck,
             &domain_k,
             &f,
             &f_commit[0].clone(),
             &f_rand[0].clone(),
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
 
-        let res = NonZeroOverK::<F, PC, FS>::verify(
+        assert_eq!(
+            NonZeroOverK::<C>::proof_size(Some(enforced_degree_bound), ck.supported_degree())
+                .unwrap(),
+            proof.serialized_size()
+        );
+
+        let res = NonZeroOverK::<C>::verify(
             &vk,
             &domain_k,
             f_commit[0].commitment().clone(),
             Some(enforced_degree_bound),
             proof,
+            &Context::new(b"test"),
         )
         .unwrap();
 
@@ -113,12 +125,13 @@ mod test {
 
         let (f_commit, f_rand) = PC::commit(&ck, &[f.clone()], Some(rng)).unwrap();
 
-        let proof = NonZeroOverK::<F, PC, FS>::prove(
+        let proof = NonZeroOverK::<C>::prove(
             &ck,
             &domain_k,
             &f,
             &f_commit[0].clone(),
             &f_rand[0].clone(),
+            &Context::new(b"test"),
             rng,
         );
 
@@ -203,21 +216,23 @@ mod test {
 
         let (f_commit, f_rand) = PC::commit(&ck, &[f.clone()], Some(rng)).unwrap();
 
-        let proof = NonZeroOverK::<F, PC, FS>::prove(
+        let proof = NonZeroOverK::<C>::prove(
             &ck,
             &domain_k,
             &f,
             &f_commit[0].clone(),
             &f_rand[0].clone(),
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
-        let res = NonZeroOverK::<F, PC, FS>::verify(
+        let res = NonZeroOverK::<C>::verify(
             &vk,
             &domain_k,
             f_commit[0].commitment().clone(),
             Some(enforced_degree_bound),
             proof,
+            &Context::new(b"test"),
         );
 
         assert!(res.is_err());
@@ -227,4 +242,133 @@ mod test {
             Error::ZeroOverKError(String::from("Check2Failed"))
         );
     }
+
+    // X - 1 vanishes at 1, which is in K but not in the coset gK for g a generator of the multiplicative group
+    #[test]
+    fn test_non_zero_over_coset() {
+        let rng = &mut thread_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let coset = CosetDomain::new(domain_k, F::multiplicative_generator()).unwrap();
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::from_coefficients_slice(&[-F::one(), F::one()]),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (f_commit, f_rand) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        let proof = NonZeroOverK::<C>::prove_over_coset(
+            &ck,
+            &coset,
+            &f,
+            &f_commit[0],
+            &f_rand[0],
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+        assert_eq!(
+            NonZeroOverK::<C>::verify_over_coset(
+                &vk,
+                &coset,
+                *f_commit[0].commitment(),
+                Some(enforced_degree_bound),
+                proof,
+                &Context::new(b"test"),
+            ),
+            Ok(())
+        );
+
+        // the same polynomial vanishes over K
+        let proof = NonZeroOverK::<C>::prove(
+            &ck,
+            &domain_k,
+            &f,
+            &f_commit[0],
+            &f_rand[0],
+            &Context::new(b"test"),
+            rng,
+        );
+        assert_eq!(proof.err().unwrap(), Error::FEvalIsZero);
+
+        // a proof over the coset is not a proof over K
+        let proof = NonZeroOverK::<C>::prove_over_coset(
+            &ck,
+            &coset,
+            &f,
+            &f_commit[0],
+            &f_rand[0],
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+        assert!(NonZeroOverK::<C>::verify(
+            &vk,
+            &domain_k,
+            *f_commit[0].commitment(),
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_prove_from_hint() {
+        let rng = &mut thread_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        // X + 2 vanishes nowhere over K
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::from_coefficients_slice(&[F::from(2u64), F::one()]),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (f_commit, f_rand) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let hint = OpeningHint::<F, PC>::new(&f, &f_rand[0]).unwrap();
+
+        let proof = NonZeroOverK::<C>::prove_from_hint(
+            &ck,
+            &domain_k,
+            &hint,
+            &f_commit[0],
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+        assert_eq!(
+            NonZeroOverK::<C>::verify(
+                &vk,
+                &domain_k,
+                *f_commit[0].commitment(),
+                Some(enforced_degree_bound),
+                proof,
+                &Context::new(b"test"),
+            ),
+            Ok(())
+        );
+
+        // a hint with another degree bound than the commitment is rejected
+        let mut unbounded = hint.clone();
+        unbounded.degree_bound = None;
+        assert!(NonZeroOverK::<C>::prove_from_hint(
+            &ck,
+            &domain_k,
+            &unbounded,
+            &f_commit[0],
+            &Context::new(b"test"),
+            rng,
+        )
+        .is_err());
+    }
 }
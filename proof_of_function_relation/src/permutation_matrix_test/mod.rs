@@ -0,0 +1,298 @@
+use crate::{
+    domains::{DomainH, DomainK},
+    error::{to_pc_error, Error, WitnessError},
+    line_sum_test::{Axis, LineSumTest},
+    permutation_matrix_test::proof::Proof,
+    report::VerificationReport,
+};
+use ark_ff::{to_bytes, One, SquareRootField};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain, UVPolynomial};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use fiat_shamir_rng::{Absorb, Context};
+#[cfg(feature = "prover")]
+use rand::Rng;
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{presets::square_check, GenericShiftingVO},
+    zero_over_k::ZeroOverK,
+};
+
+pub mod proof;
+mod tests;
+
+/// Proves that the |H| x |H| matrix encoded over K is a permutation matrix: every value of the encoding is 0 or 1,
+/// and every row and every column sums to 1. Since at most |K| values are added up, the sums cannot wrap around the
+/// field, so each row and each column holds exactly one entry of value 1.
+pub struct PermutationMatrixTest<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> PermutationMatrixTest<C>
+where
+    C::F: SquareRootField,
+{
+    pub const PROTOCOL_NAME: &'static [u8] = b"Permutation Matrix Test";
+
+    /// Check that the values are all 0 or 1 and that every row and column sums to 1, failing on the first offending
+    /// element before any work is done. With the `validate-witness` feature the prover runs this check itself.
+    pub fn validate_witness(
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        val_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    ) -> Result<(), Error> {
+        if let Some(index) = domain_k
+            .fft(val_poly.polynomial())
+            .iter()
+            .position(|&val| val * val != val)
+        {
+            return Err(Error::InvalidWitness(WitnessError::NotBoolean { index }));
+        }
+
+        let one_poly = Self::one_poly(None);
+        LineSumTest::<C>::validate_witness(domain_k, domain_h, row_poly, val_poly, &one_poly)?;
+        LineSumTest::<C>::validate_witness(domain_k, domain_h, col_poly, val_poly, &one_poly)
+    }
+
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_random: &pc::Randomness<C>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_random: &pc::Randomness<C>,
+        val_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        val_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_random: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("permutation_matrix_test::prove");
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(domain_k, domain_h, row_poly, col_poly, val_poly)?;
+
+        let fs_bytes = &to_bytes![
+            &Self::PROTOCOL_NAME,
+            context,
+            row_commit,
+            col_commit,
+            val_commit
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        // Step 1: Zero over K for val = val^2
+        let alphas = [C::F::one(), C::F::one()];
+        let boolean_check_vo =
+            GenericShiftingVO::new(&[0, 0], &alphas, square_check)?.with_scaling_factor(2);
+        let val_boolean_proof = ZeroOverK::<C>::prove(
+            &[val_poly],
+            ark_std::slice::from_ref(val_commit),
+            ark_std::slice::from_ref(val_random),
+            enforced_degree_bound,
+            &boolean_check_vo,
+            domain_k,
+            ck,
+            &context.child(b"val_boolean"),
+            rng,
+        )?;
+
+        // Step 2: every row and every column sums to 1. The all-ones vector is public, so it is committed to without
+        // randomness and the verifier derives the same commitment.
+        let one_poly = Self::one_poly(enforced_degree_bound);
+        let (one_commit, one_rand) =
+            C::PC::commit(ck, [&one_poly], None).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let row_sum_proof = LineSumTest::<C>::prove(
+            ck,
+            Axis::Rows,
+            domain_k,
+            domain_h,
+            row_poly,
+            row_commit,
+            row_random,
+            val_poly,
+            val_commit,
+            val_random,
+            &one_poly,
+            &one_commit[0],
+            &one_rand[0],
+            enforced_degree_bound,
+            &context.child(b"row_sums"),
+            fs_rng,
+            rng,
+        )?;
+
+        let col_sum_proof = LineSumTest::<C>::prove(
+            ck,
+            Axis::Columns,
+            domain_k,
+            domain_h,
+            col_poly,
+            col_commit,
+            col_random,
+            val_poly,
+            val_commit,
+            val_random,
+            &one_poly,
+            &one_commit[0],
+            &one_rand[0],
+            enforced_degree_bound,
+            &context.child(b"col_sums"),
+            fs_rng,
+            rng,
+        )?;
+
+        Ok(Proof {
+            val_boolean_proof,
+            row_sum_proof,
+            col_sum_proof,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(
+            vk,
+            ck,
+            domain_k,
+            domain_h,
+            row_commit,
+            col_commit,
+            val_commit,
+            enforced_degree_bound,
+            proof,
+            context,
+            fs_rng,
+        )
+        .into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("permutation_matrix_test::verify");
+        let mut report = VerificationReport::builder("permutation_matrix_test");
+
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![
+                &Self::PROTOCOL_NAME,
+                context,
+                row_commit,
+                col_commit,
+                val_commit
+            ]
+            .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        report.check("val_boolean", || {
+            let alphas = [C::F::one(), C::F::one()];
+            let boolean_check_vo =
+                GenericShiftingVO::new(&[0, 0], &alphas, square_check)?.with_scaling_factor(2);
+
+            ZeroOverK::<C>::verify(
+                proof.val_boolean_proof,
+                &[LabeledCommitment::new(
+                    val_commit.label().clone(),
+                    val_commit.commitment().clone(),
+                    enforced_degree_bound,
+                )],
+                enforced_degree_bound,
+                &boolean_check_vo,
+                domain_k,
+                vk,
+                &context.child(b"val_boolean"),
+            )
+            .map_err(Error::from)
+        });
+
+        let one_commit = match C::PC::commit(ck, [&Self::one_poly(enforced_degree_bound)], None) {
+            Ok((one_commit, _)) => one_commit,
+            Err(e) => {
+                report.check("one_commit", || Err(to_pc_error::<C::F, C::PC>(e)));
+                return report.finish();
+            }
+        };
+
+        report.add(LineSumTest::<C>::verify_with_report(
+            vk,
+            Axis::Rows,
+            domain_k,
+            domain_h,
+            row_commit,
+            val_commit,
+            &one_commit[0],
+            enforced_degree_bound,
+            proof.row_sum_proof,
+            &context.child(b"row_sums"),
+            fs_rng,
+        ));
+
+        report.add(LineSumTest::<C>::verify_with_report(
+            vk,
+            Axis::Columns,
+            domain_k,
+            domain_h,
+            col_commit,
+            val_commit,
+            &one_commit[0],
+            enforced_degree_bound,
+            proof.col_sum_proof,
+            &context.child(b"col_sums"),
+            fs_rng,
+        ));
+
+        report.finish()
+    }
+
+    /// The all-ones vector over H, which every line must sum to
+    fn one_poly(
+        enforced_degree_bound: Option<usize>,
+    ) -> LabeledPolynomial<C::F, DensePolynomial<C::F>> {
+        LabeledPolynomial::new(
+            String::from("one"),
+            DensePolynomial::from_coefficients_slice(&[C::F::one()]),
+            enforced_degree_bound,
+            None,
+        )
+    }
+}
@@ -0,0 +1,28 @@
+use crate::line_sum_test::proof::Proof as LineSumProof;
+use ark_ff::SquareRootField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::io::{Read, Write};
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::zero_over_k::proof::Proof as ZeroProof;
+
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    pub val_boolean_proof: ZeroProof<C>,
+    pub row_sum_proof: LineSumProof<C>,
+    pub col_sum_proof: LineSumProof<C>,
+}
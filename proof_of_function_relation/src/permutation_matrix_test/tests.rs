@@ -0,0 +1,148 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{domains, encode},
+            PaddingStrategy,
+        },
+        permutation_matrix_test::PermutationMatrixTest,
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // M values
+    /*
+        0, 0, 1, 0
+        1, 0, 0, 0
+        0, 0, 0, 1
+        0, 1, 0, 0
+    */
+    fn permutation_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 2, F::from(1u64)),
+            (1, 0, F::from(1u64)),
+            (2, 3, F::from(1u64)),
+            (3, 1, F::from(1u64)),
+        ]
+    }
+
+    fn run_permutation_matrix_test(entries: &[(usize, usize, F)]) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let encoding = encode(entries, &domain_k, &domain_h, PaddingStrategy::Origin);
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) = PC::commit(&ck, encoding.iter(), Some(rng)).unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = PermutationMatrixTest::<C>::prove(
+            &ck,
+            &domain_k,
+            &domain_h,
+            &encoding.row,
+            &commitments[0],
+            &rands[0],
+            &encoding.col,
+            &commitments[1],
+            &rands[1],
+            &encoding.val,
+            &commitments[2],
+            &rands[2],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        PermutationMatrixTest::<C>::verify(
+            &vk,
+            &ck,
+            &domain_k,
+            &domain_h,
+            &commitments[0],
+            &commitments[1],
+            &commitments[2],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )
+    }
+
+    #[test]
+    fn test_permutation_matrix() {
+        assert_eq!(run_permutation_matrix_test(&permutation_entries()), Ok(()));
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_two_ones_in_a_column() {
+        let mut entries = permutation_entries();
+        entries[3].1 = 3;
+        assert!(run_permutation_matrix_test(&entries).is_err());
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_non_boolean_values() {
+        // rows and columns still sum to 1
+        let mut entries = permutation_entries();
+        entries[0].2 = F::from(2u64);
+        entries.push((0, 2, -F::from(1u64)));
+        assert!(run_permutation_matrix_test(&entries).is_err());
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let (domain_k, domain_h) = domains(8, 4);
+        let validate = |entries: &[(usize, usize, F)]| {
+            let encoding = encode(entries, &domain_k, &domain_h, PaddingStrategy::Origin);
+            PermutationMatrixTest::<C>::validate_witness(
+                &domain_k,
+                &domain_h,
+                &encoding.row,
+                &encoding.col,
+                &encoding.val,
+            )
+        };
+
+        assert_eq!(validate(&permutation_entries()), Ok(()));
+
+        let mut entries = permutation_entries();
+        entries[3].1 = 3;
+        assert_eq!(
+            validate(&entries),
+            Err(Error::InvalidWitness(WitnessError::WrongLineSum {
+                line: 1
+            }))
+        );
+
+        let mut entries = permutation_entries();
+        entries[2].2 = F::from(2u64);
+        assert_eq!(
+            validate(&entries),
+            Err(Error::InvalidWitness(WitnessError::NotBoolean { index: 2 }))
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Sub-proofs that were verified elsewhere, e.g. by an aggregator or inside a recursive proof, are identified by a
+//! digest of their statement and their bytes. A composed verifier given such digests skips the checks of the matching
+//! sub-proofs, but still replays their part of the transcript, so that the challenges of the other sub-proofs are
+//! those the prover used.
+//!
+//! ```ignore
+//! let digests = TFT::<C>::sub_proof_digests(&proof_bytes, &context)?;
+//! // the t-SLT test on A was verified by the aggregator
+//! let pre_verified = PreVerified::from_iter([digests[0].clone()]);
+//! TFT::<C>::verify_with_pre_verified(vk, ck, t, ..., proof_bytes, &pre_verified, &context, fs_rng);
+//! ```
+//!
+//! Skipping is opt-in: verifying with an empty set, as `verify` does, checks every sub-proof.
+
+use crate::error::Error;
+use ark_ff::to_bytes;
+use ark_serialize::CanonicalSerialize;
+use blake2::{Blake2s, Digest};
+use fiat_shamir_rng::Context;
+use std::{collections::BTreeSet, iter::FromIterator};
+
+/// Digests of the sub-proofs deemed valid by the caller
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreVerified {
+    digests: BTreeSet<Vec<u8>>,
+}
+
+impl PreVerified {
+    /// No sub-proof is pre-verified, so that everything is checked
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deem the sub-proof with this digest valid. Returns false if it already was.
+    pub fn insert(&mut self, digest: Vec<u8>) -> bool {
+        self.digests.insert(digest)
+    }
+
+    pub fn contains(&self, digest: &[u8]) -> bool {
+        self.digests.contains(digest)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+}
+
+impl FromIterator<Vec<u8>> for PreVerified {
+    fn from_iter<I: IntoIterator<Item = Vec<u8>>>(digests: I) -> Self {
+        Self {
+            digests: digests.into_iter().collect(),
+        }
+    }
+}
+
+/// Digest of a sub-proof of the protocol `protocol_name`, created under `context` as part of a proof of the statement
+/// with digest `statement_digest`. The same bytes under another statement or context have another digest.
+pub fn sub_proof_digest<P: CanonicalSerialize>(
+    protocol_name: &[u8],
+    statement_digest: &[u8],
+    context: &Context,
+    sub_proof: &P,
+) -> Result<Vec<u8>, Error> {
+    let mut bytes = to_bytes![
+        protocol_name,
+        statement_digest.len() as u64,
+        statement_digest,
+        context
+    ]
+    .map_err(|_| Error::ToBytesError)?;
+    sub_proof
+        .serialize(&mut bytes)
+        .map_err(|_| Error::ProofSerializationError)?;
+
+    Ok(Blake2s::digest(&bytes).to_vec())
+}
@@ -0,0 +1,154 @@
+use crate::error::{to_pc_error, Error};
+use crate::proof_of_knowledge::proof::Proof;
+use ark_ff::{to_bytes, One};
+use ark_poly_commit::{LabeledCommitment, PolynomialCommitment};
+use fiat_shamir_rng::{ChallengeSet, Context, ProtocolRng, Transcript};
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::trace_span;
+#[cfg(feature = "prover")]
+use {ark_poly::univariate::DensePolynomial, ark_poly_commit::LabeledPolynomial, rand::Rng};
+
+pub mod proof;
+mod tests;
+
+/// Proves knowledge of the polynomial behind a commitment by opening it at a point derived from the transcript.
+/// Verifiers can require this of f and g before running protocols such as the discrete-log comparison on them.
+pub struct ProofOfKnowledge<C: ProtocolConfig> {
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> ProofOfKnowledge<C> {
+    pub const PROTOCOL_NAME: &'static [u8] = b"Proof of Knowledge of Opening";
+
+    #[cfg(feature = "prover")]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(ck, f, f_commit, f_rand, context, None, rng)
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove`], on a transcript managed by the caller
+    pub fn prove_with_transcript<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(ck, f, f_commit, f_rand, context, Some(transcript), rng)
+    }
+
+    #[cfg(feature = "prover")]
+    fn prove_in<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("proof_of_knowledge::prove");
+        let mut fs_rng = Self::initialize_transcript(f_commit, context, transcript)?;
+        let point: C::F = ChallengeSet::new(&mut fs_rng, b"opening").challenge(b"point");
+
+        let evaluation = f.evaluate(&point);
+        let opening_proof = C::PC::open(
+            ck,
+            [f],
+            [f_commit],
+            &point,
+            C::F::one(),
+            [f_rand],
+            Some(rng),
+        )
+        .map_err(to_pc_error::<C::F, C::PC>)?;
+
+        Ok(Proof {
+            evaluation,
+            opening_proof,
+        })
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        Self::verify_in(vk, f_commit, enforced_degree_bound, proof, context, None)
+    }
+
+    /// Verify a proof of [`Self::prove_with_transcript`], on a transcript in the state the prover's was in
+    pub fn verify_with_transcript(
+        vk: &pc::VerifierKey<C>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+    ) -> Result<(), Error> {
+        Self::verify_in(
+            vk,
+            f_commit,
+            enforced_degree_bound,
+            proof,
+            context,
+            Some(transcript),
+        )
+    }
+
+    fn verify_in(
+        vk: &pc::VerifierKey<C>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
+    ) -> Result<(), Error> {
+        let _span = trace_span!("proof_of_knowledge::verify");
+        // re-label the commitment with the enforced degree bound
+        let f_commit = LabeledCommitment::new(
+            f_commit.label().clone(),
+            f_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
+
+        let mut fs_rng = Self::initialize_transcript(&f_commit, context, transcript)?;
+        let point: C::F = ChallengeSet::new(&mut fs_rng, b"opening").challenge(b"point");
+
+        match C::PC::check(
+            vk,
+            &[f_commit],
+            &point,
+            vec![proof.evaluation],
+            &proof.opening_proof,
+            C::F::one(),
+            None,
+        ) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::BatchCheckError),
+            Err(e) => Err(to_pc_error::<C::F, C::PC>(e)),
+        }
+    }
+
+    fn initialize_transcript<'a>(
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        context: &Context,
+        transcript: Option<&'a mut dyn Transcript>,
+    ) -> Result<ProtocolRng<'a, C::FS>, Error> {
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, f_commit.commitment()]
+            .map_err(|_| Error::ToBytesError)?;
+        Ok(ProtocolRng::new(transcript, fs_bytes))
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{error::Error, proof_of_knowledge::ProofOfKnowledge};
+    use ark_bn254::{Bn254, Fr};
+    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
+
+    #[test]
+    fn test_proof_of_knowledge() {
+        let rng = &mut thread_rng();
+        let max_degree = 20;
+        let enforced_degree_bound = 10;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::<F>::rand(7, rng),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        let proof = ProofOfKnowledge::<C>::prove(
+            &ck,
+            &f,
+            &commitments[0],
+            &rands[0],
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        let res = ProofOfKnowledge::<C>::verify(
+            &vk,
+            &commitments[0],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+        );
+
+        assert_eq!(res, Ok(()));
+    }
+
+    #[test]
+    fn test_wrong_polynomial() {
+        let rng = &mut thread_rng();
+        let max_degree = 20;
+        let enforced_degree_bound = 10;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::<F>::rand(7, rng),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let g = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::<F>::rand(7, rng),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, &[f, g.clone()], Some(rng)).unwrap();
+
+        // a prover who only knows g cannot open the commitment to f
+        let proof = ProofOfKnowledge::<C>::prove(
+            &ck,
+            &g,
+            &commitments[0],
+            &rands[1],
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        let res = ProofOfKnowledge::<C>::verify(
+            &vk,
+            &commitments[0],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+        );
+
+        assert_eq!(res, Err(Error::BatchCheckError));
+    }
+}
@@ -0,0 +1,56 @@
+//! Versions of the proof formats. A protocol whose proof format changes keeps the verifier of every earlier version,
+//! and its versioned proofs start with their [`ProofVersion`], so that a proof serialized before the change still
+//! deserializes and verifies after it.
+//!
+//! ```ignore
+//! let proof: VersionedProof<C> = DLComparison::<C>::prove_v2(...)?.into();
+//! let bytes = { let mut bytes = Vec::new(); proof.serialize(&mut bytes)?; bytes };
+//! // later, possibly after a newer version was introduced
+//! let proof = VersionedProof::<C>::deserialize(&bytes[..])?;
+//! DLComparison::<C>::verify_versioned(&vk, &ck, &domain_k, &domain_h, &f, &g, bound, proof, &context, fs_rng)?;
+//! ```
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::io::{Read, Write};
+
+/// Version of a proof format, serialized as a single byte. Bytes of unknown versions do not deserialize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProofVersion {
+    /// The original format of every protocol
+    V1,
+    /// The format of DLComparison with the square and product checks batched in one ZeroOverK
+    V2,
+}
+
+impl ProofVersion {
+    /// The version new proofs are produced in
+    pub const LATEST: Self = Self::V2;
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+        }
+    }
+}
+
+impl CanonicalSerialize for ProofVersion {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.to_u8().serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.to_u8().serialized_size()
+    }
+}
+
+impl CanonicalDeserialize for ProofVersion {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        match u8::deserialize(reader)? {
+            1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::error::Error;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+use zero_over_k::{trace_event, trace_span};
+
+/// The outcome of verifying a (possibly composed) proof. Each sub-protocol is verified even if a previous one
+/// failed, so that a report lists every check that passed or failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerificationReport {
+    /// Name of the protocol or sub-check
+    pub name: String,
+    /// Ok if this check and all of its sub-checks passed, otherwise the first error encountered
+    pub status: Result<(), Error>,
+    /// Time spent verifying, including sub-checks
+    pub duration: Duration,
+    pub sub_reports: Vec<VerificationReport>,
+}
+
+impl VerificationReport {
+    /// Start a report for a composed protocol. Sub-checks are added with `check` and `add`, and the report is
+    /// completed with `finish`.
+    pub fn builder(name: &str) -> ReportBuilder {
+        ReportBuilder {
+            name: name.to_string(),
+            start: Instant::now(),
+            sub_reports: Vec::new(),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status.is_ok()
+    }
+
+    /// Convert into the boolean-style result returned by `verify`
+    pub fn into_result(self) -> Result<(), Error> {
+        self.status
+    }
+
+    /// Iterate over the leaf checks that failed, together with their path in the report
+    pub fn failures(&self) -> Vec<(String, Error)> {
+        let mut failures = Vec::new();
+        self.collect_failures("", &mut failures);
+        failures
+    }
+
+    fn collect_failures(&self, prefix: &str, failures: &mut Vec<(String, Error)>) {
+        let path = if prefix.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}/{}", prefix, self.name)
+        };
+
+        if self.sub_reports.is_empty() {
+            if let Err(error) = &self.status {
+                failures.push((path, error.clone()));
+            }
+        } else {
+            for sub_report in &self.sub_reports {
+                sub_report.collect_failures(&path, failures);
+            }
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let status = match &self.status {
+            Ok(()) => String::from("ok"),
+            Err(error) => format!("FAILED ({:?})", error),
+        };
+        writeln!(
+            f,
+            "{}{}: {} [{:?}]",
+            "  ".repeat(depth),
+            self.name,
+            status,
+            self.duration
+        )?;
+        for sub_report in &self.sub_reports {
+            sub_report.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// Accumulates the sub-checks of a report while a composed proof is being verified
+pub struct ReportBuilder {
+    name: String,
+    start: Instant,
+    sub_reports: Vec<VerificationReport>,
+}
+
+impl ReportBuilder {
+    /// Run a sub-check and record its outcome and duration
+    pub fn check<E, C>(&mut self, name: &str, check: C)
+    where
+        E: Into<Error>,
+        C: FnOnce() -> Result<(), E>,
+    {
+        let _span = trace_span!("check", protocol = %self.name, check = name);
+        let start = Instant::now();
+        let status = check().map_err(Into::into);
+        trace_event!(ok = status.is_ok());
+        self.sub_reports.push(VerificationReport {
+            name: name.to_string(),
+            status,
+            duration: start.elapsed(),
+            sub_reports: Vec::new(),
+        });
+    }
+
+    /// Record the report of a nested composed protocol
+    pub fn add(&mut self, report: VerificationReport) {
+        self.sub_reports.push(report);
+    }
+
+    pub fn finish(self) -> VerificationReport {
+        let status = match self
+            .sub_reports
+            .iter()
+            .find(|report| report.status.is_err())
+        {
+            Some(failed) => failed.status.clone(),
+            None => Ok(()),
+        };
+
+        VerificationReport {
+            name: self.name,
+            status,
+            duration: self.start.elapsed(),
+            sub_reports: self.sub_reports,
+        }
+    }
+}
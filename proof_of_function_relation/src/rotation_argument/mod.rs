@@ -0,0 +1,101 @@
+use crate::error::Error;
+use crate::rotation_argument::proof::Proof;
+use ark_ff::One;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::LabeledCommitment;
+use fiat_shamir_rng::Context;
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {ark_poly::univariate::DensePolynomial, ark_poly_commit::LabeledPolynomial, rand::Rng};
+
+pub mod proof;
+mod tests;
+
+/// Proves that g is f rotated by one step of the domain K, i.e. f(gamma * X) = g(X) for all X in K, where gamma
+/// is the generator of K
+pub struct RotationArgument<C: ProtocolConfig> {
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> RotationArgument<C> {
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        g: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_rand: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("rotation_argument::prove");
+        // the virtual oracle f(gamma * X) - g(X)
+        let alphas = [domain.element(1), C::F::one()];
+        let rotation_vo = GenericShiftingVO::new(&[0, 1], &alphas, presets::rotation_check)?
+            .with_scaling_factor(1);
+
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
+            &[f, g],
+            &[f_commit.clone(), g_commit.clone()],
+            &[f_rand.clone(), g_rand.clone()],
+            enforced_degree_bound,
+            &rotation_vo,
+            domain,
+            ck,
+            &context.child(b"rotation_check"),
+            rng,
+        )?;
+
+        Ok(Proof { zero_over_k_proof })
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        domain: &GeneralEvaluationDomain<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        g_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        let _span = trace_span!("rotation_argument::verify");
+        // re-label the oracle commitments with the enforced degree bound
+        let f_commit = LabeledCommitment::new(
+            f_commit.label().clone(),
+            f_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
+        let g_commit = LabeledCommitment::new(
+            g_commit.label().clone(),
+            g_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
+
+        // the virtual oracle f(gamma * X) - g(X)
+        let alphas = [domain.element(1), C::F::one()];
+        let rotation_vo = GenericShiftingVO::new(&[0, 1], &alphas, presets::rotation_check)?
+            .with_scaling_factor(1);
+
+        ZeroOverK::<C>::verify(
+            proof.zero_over_k_proof,
+            &[f_commit, g_commit],
+            enforced_degree_bound,
+            &rotation_vo,
+            domain,
+            vk,
+            &context.child(b"rotation_check"),
+        )
+        .map_err(Error::from)
+    }
+}
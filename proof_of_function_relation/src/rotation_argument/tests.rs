@@ -0,0 +1,90 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{error::Error, rotation_argument::RotationArgument};
+    use ark_bn254::{Bn254, Fr};
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    };
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+    use zero_over_k::util::sample_vector;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
+
+    fn run_rotation_argument(rotate_by: usize) -> Result<(), Error> {
+        let m = 8;
+        let rng = &mut thread_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let enforced_degree_bound = m + 1;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        // g(gamma^i) = f(gamma^(i + rotate_by))
+        let f_evals: Vec<F> = sample_vector(rng, m);
+        let mut g_evals = f_evals.clone();
+        g_evals.rotate_left(rotate_by);
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&f_evals)),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&g_evals)),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+
+        let (commitments, rands) = PC::commit(&ck, &[f.clone(), g.clone()], Some(rng)).unwrap();
+
+        let proof = RotationArgument::<C>::prove(
+            &ck,
+            &domain_k,
+            &f,
+            &commitments[0],
+            &rands[0],
+            &g,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            &Context::new(b"test"),
+            rng,
+        )?;
+
+        RotationArgument::<C>::verify(
+            &vk,
+            &domain_k,
+            &commitments[0],
+            &commitments[1],
+            Some(enforced_degree_bound),
+            proof,
+            &Context::new(b"test"),
+        )
+    }
+
+    #[test]
+    fn test_rotation_by_one() {
+        assert_eq!(run_rotation_argument(1), Ok(()));
+    }
+
+    #[test]
+    fn test_wrong_rotation() {
+        assert_eq!(
+            run_rotation_argument(2),
+            Err(Error::ZeroOverKError(String::from("Check2Failed")))
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    domains::{DomainH, DomainK},
+    error::Error,
+    indexer::{CommittedEncoding, CommittedPackedEncoding},
+    row_col_packing::proof::Proof,
+};
+use ark_ff::{to_bytes, One};
+use ark_poly::EvaluationDomain;
+use ark_poly_commit::LabeledCommitment;
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use blake2::{Blake2s, Digest};
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {
+    crate::{
+        error::to_pc_error,
+        indexer::{PackedEncoding, SparseMatrixEncoding},
+    },
+    rand::Rng,
+};
+
+pub mod proof;
+mod tests;
+
+/// The public data of a packing: the committed encoding over K of a matrix, with indices in H
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<C::F>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_h: DomainH<C::F>,
+    pub encoding: CommittedEncoding<pc::Commitment<C>>,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+impl<C: ProtocolConfig> Statement<C> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_bytes![
+            self.domain_k.size() as u64,
+            self.domain_h.size() as u64,
+            self.encoding.iter().collect::<Vec<_>>()
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+}
+
+/// row_col, row and col
+const PACKING_MAPPING: [usize; 3] = [0, 1, 2];
+
+/// Packs the row and col polynomials of a committed encoding into the single polynomial row + gamma * col, for a
+/// challenge gamma drawn once row and col are bound to the transcript. Protocols that only need the (row, col) pair
+/// of each entry, e.g. to fingerprint it, can then take the packed encoding and commit to and open one polynomial
+/// instead of two.
+///
+/// The prover commits to row_col and a ZeroOverK shows that row_col - row - gamma * col vanishes over K. The
+/// verifier comes out of the protocol with the commitments of a [`CommittedPackedEncoding`].
+pub struct RowColPacking<C: ProtocolConfig> {
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> RowColPacking<C> {
+    pub const PROTOCOL_NAME: &'static [u8] = b"Row Col Packing";
+
+    #[cfg(feature = "prover")]
+    /// The randomness of the encoding is given in the order of [`SparseMatrixEncoding::iter`]. Returns the packed
+    /// encoding with the randomness of its commitments, in the order of [`PackedEncoding::iter`].
+    #[allow(clippy::type_complexity)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        encoding: &SparseMatrixEncoding<C::F>,
+        rands: &[pc::Randomness<C>],
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<(PackedEncoding<C::F>, Vec<pc::Randomness<C>>, Proof<C>), Error> {
+        let _span = trace_span!("row_col_packing::prove");
+        let gamma = Self::packing_challenge(statement, context, fs_rng)?;
+        let bound = statement.enforced_degree_bound;
+
+        let packed = encoding.pack(gamma);
+        let (row_col_commit, row_col_rand) =
+            C::PC::commit(ck, [&packed.row_col], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![row_col_commit].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let oracles = [&packed.row_col, &encoding.row, &encoding.col];
+        let commitments = [
+            row_col_commit[0].clone(),
+            statement.encoding.row.clone(),
+            statement.encoding.col.clone(),
+        ];
+        let packing_rands = [row_col_rand[0].clone(), rands[0].clone(), rands[1].clone()];
+
+        let packing_check_vo = GenericShiftingVO::new(
+            &PACKING_MAPPING,
+            &[C::F::one(); 3],
+            Self::packing_check(gamma),
+        )?
+        .with_scaling_factor(1);
+        let packing_check_proof = ZeroOverK::<C>::prove(
+            &oracles,
+            &commitments,
+            &packing_rands,
+            bound,
+            &packing_check_vo,
+            &statement.domain_k,
+            ck,
+            &Self::packing_check_context(context, gamma)?,
+            rng,
+        )?;
+
+        let packed_rands = vec![row_col_rand[0].clone(), rands[2].clone()];
+        let proof = Proof {
+            row_col_commit: row_col_commit[0].commitment().clone(),
+            packing_check_proof,
+        };
+
+        Ok((packed, packed_rands, proof))
+    }
+
+    /// Returns the packing challenge with the commitments of the packed encoding
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(C::F, CommittedPackedEncoding<pc::Commitment<C>>), Error> {
+        let _span = trace_span!("row_col_packing::verify");
+        let gamma = Self::packing_challenge(statement, context, fs_rng)?;
+        let bound = statement.enforced_degree_bound;
+
+        let row_col_commit =
+            LabeledCommitment::new(String::from("row_col"), proof.row_col_commit, bound);
+
+        let fs_bytes =
+            &to_bytes![[row_col_commit.clone()].to_vec()].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let relabel = |commitment: &LabeledCommitment<pc::Commitment<C>>| {
+            LabeledCommitment::new(
+                commitment.label().clone(),
+                commitment.commitment().clone(),
+                bound,
+            )
+        };
+        let commitments = [
+            row_col_commit.clone(),
+            relabel(&statement.encoding.row),
+            relabel(&statement.encoding.col),
+        ];
+
+        let packing_check_vo = GenericShiftingVO::new(
+            &PACKING_MAPPING,
+            &[C::F::one(); 3],
+            Self::packing_check(gamma),
+        )?
+        .with_scaling_factor(1);
+        ZeroOverK::<C>::verify(
+            proof.packing_check_proof,
+            &commitments,
+            bound,
+            &packing_check_vo,
+            &statement.domain_k,
+            vk,
+            &Self::packing_check_context(context, gamma)?,
+        )?;
+
+        Ok((
+            gamma,
+            CommittedPackedEncoding {
+                row_col: row_col_commit,
+                val: statement.encoding.val.clone(),
+            },
+        ))
+    }
+
+    fn packing_challenge(
+        statement: &Statement<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<C::F, Error> {
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        Ok(ChallengeSet::new(&mut *fs_rng, b"packing").challenge(b"gamma"))
+    }
+
+    /// row_col(X) - row(X) - gamma * col(X) over the terms [X, row_col(X), row(X), col(X)]
+    fn packing_check(gamma: C::F) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        move |terms: &[VOTerm<C::F>]| {
+            terms[1].clone() - terms[2].clone() - vo_constant!(gamma) * terms[3].clone()
+        }
+    }
+
+    fn packing_check_context(context: &Context, gamma: C::F) -> Result<Context, Error> {
+        let challenge = to_bytes![gamma].map_err(|_| Error::ToBytesError)?;
+        Ok(context
+            .child(b"packing_check")
+            .child(&Blake2s::digest(&challenge)))
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        domains::{DomainH, DomainK},
+        error::Error,
+        indexer::{CommittedEncoding, PaddingStrategy, SparseMatrixEncoding},
+        row_col_packing::{RowColPacking, Statement},
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::{univariate::DensePolynomial, EvaluationDomain, Polynomial};
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+    use std::collections::BTreeSet;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+    type Commitment = <PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment;
+
+    /*
+        1, 0, 0, 0
+        0, 2, 0, 4
+        0, 0, 0, 0
+        5, 0, 0, 6
+    */
+    fn entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 0, F::from(1u64)),
+            (1, 1, F::from(2u64)),
+            (1, 3, F::from(4u64)),
+            (3, 0, F::from(5u64)),
+            (3, 3, F::from(6u64)),
+        ]
+    }
+
+    fn run_row_col_packing(
+        tamper: impl Fn(&mut CommittedEncoding<Commitment>),
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries(),
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap()
+        .with_bounds("m", Some(enforced_degree_bound), Some(1));
+        let (commitments, rands) = PC::commit(&ck, encoding.iter(), Some(rng)).unwrap();
+        let mut statement = Statement::<C> {
+            domain_k,
+            domain_h,
+            encoding: CommittedEncoding {
+                row: commitments[0].clone(),
+                col: commitments[1].clone(),
+                val: commitments[2].clone(),
+            },
+            enforced_degree_bound: Some(enforced_degree_bound),
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let (packed, packed_rands, proof) = RowColPacking::<C>::prove(
+            &ck,
+            &statement,
+            &encoding,
+            &rands,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        // the packed values tell the (row, col) pairs apart, padding included
+        let pairs = entries()
+            .iter()
+            .map(|&(row, col, _)| (row, col))
+            .collect::<BTreeSet<_>>();
+        let packed_values = packed.row_col_evals.evals.iter().collect::<BTreeSet<_>>();
+        assert_eq!(packed_values.len(), pairs.len());
+        assert_eq!(packed_rands.len(), 2);
+
+        tamper(&mut statement.encoding);
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let (gamma, committed) = RowColPacking::<C>::verify(
+            &vk,
+            &statement,
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )?;
+
+        assert_eq!(gamma, packed.gamma);
+        assert_eq!(
+            committed.iter().map(|c| c.label()).collect::<Vec<_>>(),
+            ["row_col", "m_val"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_col_packing() {
+        assert_eq!(run_row_col_packing(|_| {}), Ok(()));
+    }
+
+    #[test]
+    fn test_packing_of_other_columns() {
+        // the packing of the original encoding does not hold for another col polynomial
+        assert!(run_row_col_packing(|encoding| encoding.col = encoding.val.clone()).is_err());
+    }
+
+    #[test]
+    fn test_statement_equality() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, None).unwrap();
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries(),
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap();
+        let (commitments, _) = PC::commit(&ck, encoding.iter(), None).unwrap();
+        let statement = Statement::<C> {
+            domain_k,
+            domain_h,
+            encoding: CommittedEncoding {
+                row: commitments[0].clone(),
+                col: commitments[1].clone(),
+                val: commitments[2].clone(),
+            },
+            enforced_degree_bound: None,
+        };
+
+        // labeled commitments compare by label, commitment and degree bound
+        let mut other = statement.clone();
+        assert_eq!(other, statement);
+        other.encoding.col = statement.encoding.row.clone();
+        assert_ne!(other, statement);
+        assert!(format!("{:?}", statement).contains("label: \"col\""));
+    }
+
+    #[test]
+    fn test_pack() {
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries(),
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap();
+
+        let gamma = F::from(7u64);
+        let packed = encoding.pack(gamma);
+        for (i, &(row, col, _)) in entries().iter().enumerate() {
+            let expected = domain_h.element(row) + gamma * domain_h.element(col);
+            assert_eq!(packed.row_col_evals.evals[i], expected);
+            assert_eq!(
+                packed.row_col.polynomial().evaluate(&domain_k.element(i)),
+                expected
+            );
+        }
+        assert_eq!(packed.val_evals.evals, encoding.val_evals.evals);
+    }
+}
@@ -0,0 +1,191 @@
+//! Commit once, prove many: a session commits to a set of oracles and hands out a fresh context for every proof
+//! about them.
+//!
+//! ```ignore
+//! let mut prover = ProverSession::<C>::commit(ck, vec![f], &context, rng)?;
+//! let fork = prover.fork(b"non_zero")?;
+//! let (f, f_commit, f_rand) = prover.oracle("f").unwrap();
+//! let proof = NonZeroOverK::<C>::prove(prover.ck(), &domain_k, f, f_commit, f_rand, &fork, rng)?;
+//!
+//! let mut verifier = VerifierSession::<C>::new(vk, prover.commitments().to_vec(), &context)?;
+//! let fork = verifier.fork(b"non_zero")?;
+//! NonZeroOverK::<C>::verify(verifier.vk(), &domain_k, f_commit, bound, proof, &fork)?;
+//! ```
+//!
+//! Both sides derive a base context from the context of the session and a transcript of the commitments, so that
+//! sessions over different commitments never share a context. Each fork is a child of the base context, and a
+//! session refuses to fork twice under the same label, so no two proofs of a session share their challenges.
+
+use crate::error::{to_pc_error, Error};
+use ark_ff::to_bytes;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use ark_std::rand::Rng;
+use fiat_shamir_rng::{Context, FiatShamirRng};
+use std::{collections::BTreeSet, marker::PhantomData};
+use zero_over_k::config::{pc, ProtocolConfig};
+
+mod tests;
+
+/// Contexts of a session are children of the session context under this label
+pub const SESSION_LABEL: &[u8] = b"session";
+
+/// Number of bytes drawn from the transcript of the commitments to tell sessions apart
+const DIGEST_SIZE: usize = 32;
+
+/// The contexts handed out by a session, shared by its prover and verifier sides
+struct Forks {
+    base: Context,
+    used: BTreeSet<Vec<u8>>,
+}
+
+impl Forks {
+    fn new<FS: FiatShamirRng, C: ark_ff::ToBytes>(
+        context: &Context,
+        commitments: &[C],
+    ) -> Result<Self, Error> {
+        let mut fs_rng = FS::initialize(
+            &to_bytes![context, SESSION_LABEL, commitments].map_err(|_| Error::ToBytesError)?,
+        );
+        let mut digest = [0u8; DIGEST_SIZE];
+        fs_rng.fill_bytes(&mut digest);
+
+        Ok(Self {
+            base: context.child(SESSION_LABEL).child(&digest),
+            used: BTreeSet::new(),
+        })
+    }
+
+    fn fork(&mut self, label: &[u8]) -> Result<Context, Error> {
+        // a separator in the label would let a fork collide with a sub-protocol context of another fork
+        if label.is_empty() || label.contains(&b'/') {
+            return Err(Error::InvalidFork(format!(
+                "Fork label {:?} must be non-empty and free of '/'",
+                String::from_utf8_lossy(label)
+            )));
+        }
+        if !self.used.insert(label.to_vec()) {
+            return Err(Error::InvalidFork(format!(
+                "Fork label {:?} was already used in this session",
+                String::from_utf8_lossy(label)
+            )));
+        }
+        Ok(self.base.child(label))
+    }
+}
+
+/// The prover side of a session: the committer key, the committed oracles and the forks handed out so far
+pub struct ProverSession<C: ProtocolConfig> {
+    ck: pc::CommitterKey<C>,
+    oracles: Vec<LabeledPolynomial<C::F, DensePolynomial<C::F>>>,
+    commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+    rands: Vec<pc::Randomness<C>>,
+    forks: Forks,
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> ProverSession<C> {
+    /// Commit to `oracles` and open a session over them under `context`
+    pub fn commit<R: Rng>(
+        ck: pc::CommitterKey<C>,
+        oracles: Vec<LabeledPolynomial<C::F, DensePolynomial<C::F>>>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        let (commitments, rands) =
+            C::PC::commit(&ck, oracles.iter(), Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+        let forks = Forks::new::<C::FS, _>(context, &commitments)?;
+
+        Ok(Self {
+            ck,
+            oracles,
+            commitments,
+            rands,
+            forks,
+            _config: PhantomData,
+        })
+    }
+
+    pub fn ck(&self) -> &pc::CommitterKey<C> {
+        &self.ck
+    }
+
+    pub fn oracles(&self) -> &[LabeledPolynomial<C::F, DensePolynomial<C::F>>] {
+        &self.oracles
+    }
+
+    pub fn commitments(&self) -> &[LabeledCommitment<pc::Commitment<C>>] {
+        &self.commitments
+    }
+
+    pub fn rands(&self) -> &[pc::Randomness<C>] {
+        &self.rands
+    }
+
+    /// The oracle labeled `label`, with its commitment and commitment randomness
+    #[allow(clippy::type_complexity)]
+    pub fn oracle(
+        &self,
+        label: &str,
+    ) -> Option<(
+        &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        &LabeledCommitment<pc::Commitment<C>>,
+        &pc::Randomness<C>,
+    )> {
+        let index = self.oracles.iter().position(|f| f.label() == label)?;
+        Some((
+            &self.oracles[index],
+            &self.commitments[index],
+            &self.rands[index],
+        ))
+    }
+
+    /// The context of a new proof about the committed oracles. Each label can be used once per session.
+    pub fn fork(&mut self, label: &[u8]) -> Result<Context, Error> {
+        self.forks.fork(label)
+    }
+}
+
+/// The verifier side of a session: the verifier key, the commitments and the forks handed out so far
+pub struct VerifierSession<C: ProtocolConfig> {
+    vk: pc::VerifierKey<C>,
+    commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+    forks: Forks,
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> VerifierSession<C> {
+    /// Open a session over `commitments` under `context`, as the prover did
+    pub fn new(
+        vk: pc::VerifierKey<C>,
+        commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+        context: &Context,
+    ) -> Result<Self, Error> {
+        let forks = Forks::new::<C::FS, _>(context, &commitments)?;
+
+        Ok(Self {
+            vk,
+            commitments,
+            forks,
+            _config: PhantomData,
+        })
+    }
+
+    pub fn vk(&self) -> &pc::VerifierKey<C> {
+        &self.vk
+    }
+
+    pub fn commitments(&self) -> &[LabeledCommitment<pc::Commitment<C>>] {
+        &self.commitments
+    }
+
+    /// The commitment labeled `label`
+    pub fn commitment(&self, label: &str) -> Option<&LabeledCommitment<pc::Commitment<C>>> {
+        self.commitments.iter().find(|c| c.label() == label)
+    }
+
+    /// The context of the proof the prover created under the same fork label
+    pub fn fork(&mut self, label: &[u8]) -> Result<Context, Error> {
+        self.forks.fork(label)
+    }
+}
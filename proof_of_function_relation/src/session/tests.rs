@@ -0,0 +1,104 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        error::Error,
+        non_zero_over_k::NonZeroOverK,
+        session::{ProverSession, VerifierSession},
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    };
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::{rand::thread_rng, UniformRand};
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
+
+    #[test]
+    fn test_forked_proofs() {
+        let rng = &mut thread_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let enforced_degree_bound = Some(14);
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, 14])).unwrap();
+
+        // a random polynomial of degree 7 does not vanish over K but with negligible probability
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::from_coefficients_vec((0..8).map(|_| F::rand(rng)).collect()),
+            enforced_degree_bound,
+            Some(1),
+        );
+        let context = Context::new(b"test");
+        let mut prover = ProverSession::<C>::commit(ck, vec![f], &context, rng).unwrap();
+
+        let mut prove = |label: &[u8], rng: &mut _| {
+            let fork = prover.fork(label)?;
+            let (f, f_commit, f_rand) = prover.oracle("f").unwrap();
+            NonZeroOverK::<C>::prove(prover.ck(), &domain_k, f, f_commit, f_rand, &fork, rng)
+        };
+        let proof_a = prove(b"a", rng).unwrap();
+        let proof_b = prove(b"b", rng).unwrap();
+        let proof_c = prove(b"c", rng).unwrap();
+
+        // a fork cannot be reused, nor nest into another one
+        assert!(matches!(prove(b"a", rng), Err(Error::InvalidFork(_))));
+        assert!(matches!(prove(b"a/b", rng), Err(Error::InvalidFork(_))));
+
+        let commitments = prover.commitments().to_vec();
+        let mut verifier = VerifierSession::<C>::new(vk.clone(), commitments, &context).unwrap();
+        let f_commit = *verifier.commitment("f").unwrap().commitment();
+
+        // the forks of the verifier match those of the prover in any order, and only those
+        let fork_b = verifier.fork(b"b").unwrap();
+        let fork_a = verifier.fork(b"a").unwrap();
+        assert!(matches!(verifier.fork(b"b"), Err(Error::InvalidFork(_))));
+        assert!(NonZeroOverK::<C>::verify(
+            verifier.vk(),
+            &domain_k,
+            f_commit,
+            enforced_degree_bound,
+            proof_c,
+            &fork_b
+        )
+        .is_err());
+        assert_eq!(
+            NonZeroOverK::<C>::verify(
+                verifier.vk(),
+                &domain_k,
+                f_commit,
+                enforced_degree_bound,
+                proof_a,
+                &fork_a
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            NonZeroOverK::<C>::verify(
+                verifier.vk(),
+                &domain_k,
+                f_commit,
+                enforced_degree_bound,
+                proof_b,
+                &fork_b
+            ),
+            Ok(())
+        );
+
+        // a session over other commitments forks other contexts
+        let other = VerifierSession::<C>::new(vk, Vec::new(), &context)
+            .unwrap()
+            .fork(b"a")
+            .unwrap();
+        assert_ne!(other, fork_a);
+    }
+}
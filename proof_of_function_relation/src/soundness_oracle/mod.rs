@@ -0,0 +1,115 @@
+//! Plaintext verifiers, for testing the cryptographic verifiers against. Given the witness polynomials, they check the
+//! statement of a protocol directly on their evaluations, without commitments or challenges. A verifier that accepts
+//! a proof whose plaintext statement is false is unsound, one that rejects an honest proof of a true statement is
+//! incomplete, and [`assert_agreement`] fails on either.
+//!
+//! They are written from the statements rather than from the `validate_witness` checks of the provers, so that they
+//! also catch a statement that one of those checks gets wrong.
+
+use crate::{
+    domains::{DomainH, DomainK},
+    error::Error,
+    util::generate_sequence,
+};
+use ark_ff::PrimeField;
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial,
+};
+use ark_poly_commit::LabeledPolynomial;
+
+mod tests;
+
+/// The evaluations of `p` over `domain`
+pub fn evaluations<F: PrimeField>(
+    domain: &GeneralEvaluationDomain<F>,
+    p: &LabeledPolynomial<F, DensePolynomial<F>>,
+) -> Vec<F> {
+    domain
+        .elements()
+        .map(|x| p.polynomial().evaluate(&x))
+        .collect()
+}
+
+/// The discrete log of `value` in base the generator of `domain_h`, if it is an element of H
+pub fn discrete_log<F: PrimeField>(domain_h: &DomainH<F>, value: F) -> Option<usize> {
+    domain_h.elements().position(|element| element == value)
+}
+
+/// f does not vanish anywhere on K
+pub fn non_zero_over_k<F: PrimeField>(
+    domain_k: &DomainK<F>,
+    f: &LabeledPolynomial<F, DensePolynomial<F>>,
+) -> bool {
+    evaluations(domain_k, f)
+        .iter()
+        .all(|value| !value.is_zero())
+}
+
+/// f takes the concatenation of the geometric sequences with `common_ratio`, `initial_values` and `lengths` over K
+pub fn geometric_sequence<F: PrimeField>(
+    domain_k: &DomainK<F>,
+    f: &LabeledPolynomial<F, DensePolynomial<F>>,
+    common_ratio: F,
+    initial_values: &[F],
+    lengths: &[usize],
+) -> bool {
+    lengths.iter().sum::<usize>() == domain_k.size()
+        && evaluations(domain_k, f) == generate_sequence(common_ratio, initial_values, lengths)
+}
+
+/// At every element of K, f and g take values in H and the discrete log of f is strictly greater than that of g
+pub fn discrete_log_greater<F: PrimeField>(
+    domain_k: &DomainK<F>,
+    domain_h: &DomainH<F>,
+    f: &LabeledPolynomial<F, DensePolynomial<F>>,
+    g: &LabeledPolynomial<F, DensePolynomial<F>>,
+) -> bool {
+    evaluations(domain_k, f)
+        .into_iter()
+        .zip(evaluations(domain_k, g))
+        .all(|(f_value, g_value)| {
+            match (
+                discrete_log(domain_h, f_value),
+                discrete_log(domain_h, g_value),
+            ) {
+                (Some(f_log), Some(g_log)) => f_log > g_log,
+                _ => false,
+            }
+        })
+}
+
+/// The matrix encoded by row and col has its non-zero entries strictly below the diagonal, in rows t and below
+pub fn t_strictly_lower_triangular<F: PrimeField>(
+    t: usize,
+    domain_k: &DomainK<F>,
+    domain_h: &DomainH<F>,
+    row: &LabeledPolynomial<F, DensePolynomial<F>>,
+    col: &LabeledPolynomial<F, DensePolynomial<F>>,
+) -> bool {
+    t <= domain_h.size()
+        && evaluations(domain_k, row)
+            .into_iter()
+            .all(|value| discrete_log(domain_h, value).is_some_and(|row_log| row_log >= t))
+        && discrete_log_greater(domain_k, domain_h, row, col)
+}
+
+/// Check that proving and verifying agree with the plaintext statement: a proof of a false statement must not verify,
+/// and an honest proof of a true one must be produced and verify
+pub fn assert_agreement<P>(
+    protocol: &str,
+    holds: bool,
+    proof: Result<P, Error>,
+    verify: impl FnOnce(P) -> Result<(), Error>,
+) {
+    match proof {
+        Ok(proof) => match verify(proof) {
+            Ok(()) => assert!(holds, "{} accepts a false statement", protocol),
+            Err(err) => assert!(!holds, "{} rejects a true statement: {:?}", protocol, err),
+        },
+        Err(err) => assert!(
+            !holds,
+            "{} fails to prove a true statement: {:?}",
+            protocol, err
+        ),
+    }
+}
@@ -0,0 +1,308 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        checked_key::CheckedCommitterKey,
+        discrete_log_comparison::DLComparison,
+        domains::{DomainH, DomainK},
+        geo_seq::GeoSeqTest,
+        indexer::PaddingStrategy,
+        non_zero_over_k::NonZeroOverK,
+        soundness_oracle::{
+            assert_agreement, discrete_log_greater, geometric_sequence, non_zero_over_k,
+            t_strictly_lower_triangular,
+        },
+        t_strictly_lower_triangular_test::TStrictlyLowerTriangular,
+        util::generate_sequence,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{to_bytes, One, UniformRand, Zero};
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    };
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::{thread_rng, Rng};
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    /// Witnesses drawn per protocol, about half of which break the statement in a single place
+    const TRIALS: usize = 8;
+
+    const M: usize = 8;
+    const N: usize = 4;
+    const ENFORCED_DEGREE_BOUND: usize = M + 1;
+
+    #[allow(clippy::type_complexity)]
+    fn setup() -> (
+        <PC as PolynomialCommitment<F, DensePolynomial<F>>>::CommitterKey,
+        <PC as PolynomialCommitment<F, DensePolynomial<F>>>::VerifierKey,
+    ) {
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, &mut thread_rng()).unwrap();
+        PC::trim(&pp, max_degree, 1, Some(&[2, ENFORCED_DEGREE_BOUND])).unwrap()
+    }
+
+    fn interpolate(
+        label: &str,
+        domain_k: &GeneralEvaluationDomain<F>,
+        evals: &[F],
+    ) -> LabeledPolynomial<F, DensePolynomial<F>> {
+        LabeledPolynomial::new(
+            String::from(label),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(evals)),
+            Some(ENFORCED_DEGREE_BOUND),
+            Some(1),
+        )
+    }
+
+    #[test]
+    fn test_non_zero_over_k_agrees() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(M).unwrap();
+        let (ck, vk) = setup();
+
+        for _ in 0..TRIALS {
+            let mut evals = (0..M).map(|_| F::rand(rng) + F::one()).collect::<Vec<_>>();
+            if rng.gen() {
+                evals[rng.gen_range(0..M)] = F::zero();
+            }
+            let f = interpolate("f", &domain_k, &evals);
+            let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+            let context = Context::new(b"non_zero_over_k");
+            assert_agreement(
+                "NonZeroOverK",
+                non_zero_over_k(&domain_k, &f),
+                NonZeroOverK::<C>::prove(
+                    &ck,
+                    &domain_k,
+                    &f,
+                    &commitments[0],
+                    &rands[0],
+                    &context,
+                    rng,
+                ),
+                |proof| {
+                    NonZeroOverK::<C>::verify(
+                        &vk,
+                        &domain_k,
+                        *commitments[0].commitment(),
+                        Some(ENFORCED_DEGREE_BOUND),
+                        proof,
+                        &context,
+                    )
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_geo_seq_agrees() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(M).unwrap();
+        let (ck, vk) = setup();
+
+        for _ in 0..TRIALS {
+            // cut K into sub-sequences at random
+            let mut lengths = vec![1];
+            for _ in 1..M {
+                match rng.gen() {
+                    true => *lengths.last_mut().unwrap() += 1,
+                    false => lengths.push(1),
+                }
+            }
+            let initial_values = (0..lengths.len()).map(|_| F::rand(rng)).collect::<Vec<_>>();
+            let common_ratio = F::rand(rng);
+
+            let mut evals = generate_sequence(common_ratio, &initial_values, &lengths);
+            if rng.gen() {
+                evals[rng.gen_range(0..M)] += F::one();
+            }
+            let f = interpolate("f", &domain_k, &evals);
+            let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+            let context = Context::new(b"geo_seq");
+            assert_agreement(
+                "GeoSeqTest",
+                geometric_sequence(&domain_k, &f, common_ratio, &initial_values, &lengths),
+                GeoSeqTest::<C>::prove(
+                    &ck,
+                    common_ratio,
+                    &f,
+                    &commitments[0],
+                    &rands[0],
+                    &initial_values,
+                    &lengths,
+                    &domain_k,
+                    &context,
+                    rng,
+                ),
+                |proof| {
+                    GeoSeqTest::<C>::verify(
+                        common_ratio,
+                        &initial_values,
+                        &lengths,
+                        &domain_k,
+                        &commitments[0],
+                        Some(ENFORCED_DEGREE_BOUND),
+                        proof,
+                        &vk,
+                        &context,
+                    )
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_discrete_log_comparison_agrees() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(M).unwrap();
+        let domain_h = DomainH::<F>::new(N).unwrap();
+        let (ck, vk) = setup();
+        let checked_ck = CheckedCommitterKey::new(&ck, Some(ENFORCED_DEGREE_BOUND)).unwrap();
+
+        for _ in 0..TRIALS {
+            let f_logs = (0..M).map(|_| rng.gen_range(1..N)).collect::<Vec<_>>();
+            let mut g_logs = f_logs
+                .iter()
+                .map(|&f_log| rng.gen_range(0..f_log))
+                .collect::<Vec<_>>();
+            if rng.gen() {
+                let i = rng.gen_range(0..M);
+                g_logs[i] = rng.gen_range(f_logs[i]..N);
+            }
+            let to_poly = |label, logs: &[usize]| {
+                let evals = logs
+                    .iter()
+                    .map(|&log| domain_h.element(log))
+                    .collect::<Vec<_>>();
+                interpolate(label, &domain_k, &evals)
+            };
+            let f = to_poly("f", &f_logs);
+            let g = to_poly("g", &g_logs);
+            let (commitments, rands) = PC::commit(&ck, [&f, &g], Some(rng)).unwrap();
+
+            let context = Context::new(b"discrete_log_comparison");
+            let fs_rng = || FS::initialize(&to_bytes!(b"soundness oracle").unwrap());
+            assert_agreement(
+                "DLComparison",
+                discrete_log_greater(&domain_k, &domain_h, &f, &g),
+                DLComparison::<C>::prove(
+                    &checked_ck,
+                    &domain_k,
+                    &domain_h,
+                    &f,
+                    &commitments[0],
+                    &rands[0],
+                    &g,
+                    &commitments[1],
+                    &rands[1],
+                    Some(ENFORCED_DEGREE_BOUND),
+                    &context,
+                    &mut fs_rng(),
+                    rng,
+                ),
+                |proof| {
+                    DLComparison::<C>::verify(
+                        &vk,
+                        &ck,
+                        &domain_k,
+                        &domain_h,
+                        &commitments[0],
+                        &commitments[1],
+                        Some(ENFORCED_DEGREE_BOUND),
+                        proof,
+                        &context,
+                        &mut fs_rng(),
+                    )
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_t_strictly_lower_triangular_agrees() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(M).unwrap();
+        let domain_h = DomainH::<F>::new(N).unwrap();
+        let (ck, vk) = setup();
+        let checked_ck = CheckedCommitterKey::new(&ck, Some(ENFORCED_DEGREE_BOUND)).unwrap();
+
+        for _ in 0..TRIALS {
+            let t = rng.gen_range(1..N);
+            let mut row_logs = (0..M).map(|_| rng.gen_range(t..N)).collect::<Vec<_>>();
+            let mut col_logs = row_logs
+                .iter()
+                .map(|&row_log| rng.gen_range(0..row_log))
+                .collect::<Vec<_>>();
+            // either an entry above row t but still below the diagonal, or one on or above the diagonal
+            let i = rng.gen_range(0..M);
+            match rng.gen_range(0..3) {
+                0 => {
+                    row_logs[i] = rng.gen_range(1..t.max(2));
+                    col_logs[i] = 0;
+                }
+                1 => col_logs[i] = rng.gen_range(row_logs[i]..N),
+                _ => {}
+            }
+            let to_poly = |label, logs: &[usize]| {
+                let evals = logs
+                    .iter()
+                    .map(|&log| domain_h.element(log))
+                    .collect::<Vec<_>>();
+                interpolate(label, &domain_k, &evals)
+            };
+            let row = to_poly("row", &row_logs);
+            let col = to_poly("col", &col_logs);
+            let (commitments, rands) = PC::commit(&ck, [&row, &col], Some(rng)).unwrap();
+
+            let context = Context::new(b"t_strictly_lower_triangular");
+            let fs_rng = || FS::initialize(&to_bytes!(b"soundness oracle").unwrap());
+            assert_agreement(
+                "TStrictlyLowerTriangular",
+                t_strictly_lower_triangular(t, &domain_k, &domain_h, &row, &col),
+                TStrictlyLowerTriangular::<C>::prove(
+                    &checked_ck,
+                    t,
+                    &domain_k,
+                    &domain_h,
+                    &row,
+                    &commitments[0],
+                    &rands[0],
+                    &col,
+                    &commitments[1],
+                    &rands[1],
+                    Some(ENFORCED_DEGREE_BOUND),
+                    PaddingStrategy::RepeatLast,
+                    &context,
+                    &mut fs_rng(),
+                    rng,
+                ),
+                |proof| {
+                    TStrictlyLowerTriangular::<C>::verify(
+                        &vk,
+                        &ck,
+                        t,
+                        &domain_k,
+                        &domain_h,
+                        &commitments[0],
+                        &commitments[1],
+                        Some(ENFORCED_DEGREE_BOUND),
+                        PaddingStrategy::RepeatLast,
+                        proof,
+                        &context,
+                        &mut fs_rng(),
+                    )
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Machine-readable descriptions of the protocols of this crate: the rounds of each proof, with the oracles committed
+//! to and their degree bounds and the challenges drawn, the openings, and the checks of the verifier.
+//!
+//! A spec is generated from the dry run of the prover, see [`zero_over_k::proof_shape`], so it follows the same code
+//! paths as the proofs themselves. Auditors and other implementations can diff it against theirs, e.g. as JSON with
+//! the `serde` feature.
+//!
+//! ```ignore
+//! let parameters = SpecParameters { t: 2, domain_k_size: 8, domain_h_size: 4, enforced_degree_bound: Some(9) };
+//! let spec = ProtocolSpec::generate::<C>(Protocol::TFT, &parameters)?;
+//! println!("{}", spec.to_json()?);
+//! ```
+
+use crate::{
+    cost_model::Protocol,
+    discrete_log_comparison::DLComparison,
+    domains::{DomainH, DomainK},
+    error::Error,
+    geo_seq::GeoSeqTest,
+    non_zero_over_k::NonZeroOverK,
+    subset_over_k::{SubsetOverK, Table},
+    t_diag::TDiag,
+    t_functional_triple::TFT,
+    t_strictly_lower_triangular_test::TStrictlyLowerTriangular,
+};
+use ark_ff::{One, SquareRootField};
+use ark_poly::EvaluationDomain;
+use zero_over_k::config::ProtocolConfig;
+use zero_over_k::{
+    proof_shape::{CheckShape, ProofShape, QueryShape},
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    zero_over_k::ZeroOverK,
+};
+
+mod tests;
+
+/// The public parameters a spec is generated for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpecParameters {
+    /// Number of input rows, for the t-SLT, t-Diag and t-FT tests
+    pub t: usize,
+    pub domain_k_size: usize,
+    pub domain_h_size: usize,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+/// An oracle committed to by the prover
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleSpec {
+    pub label: String,
+    pub degree_bound: Option<usize>,
+}
+
+/// The oracles the prover commits to in a round, and the challenges the verifier draws from them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundSpec {
+    pub oracles: Vec<OracleSpec>,
+    pub challenges: Vec<String>,
+}
+
+/// The description of a protocol. Labels of sub-protocols are prefixed with their context, as in a [`ProofShape`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProtocolSpec {
+    /// The name of the protocol, see [`Protocol::name`]
+    pub protocol: String,
+    pub parameters: SpecParameters,
+    pub rounds: Vec<RoundSpec>,
+    /// The openings of the proof, by polynomial and point
+    pub queries: Vec<QueryShape>,
+    pub checks: Vec<CheckShape>,
+}
+
+impl ProtocolSpec {
+    /// The spec of a protocol over domains of the given sizes.
+    ///
+    /// [`Protocol::ZeroOverK`] is described for a virtual oracle with the given number of terms, shifted by the first
+    /// elements of K, and [`Protocol::GeoSeq`] for a sequence with ratio the generator of K made of sub-sequences of
+    /// equal lengths.
+    pub fn generate<C: ProtocolConfig>(
+        protocol: Protocol,
+        parameters: &SpecParameters,
+    ) -> Result<Self, Error>
+    where
+        C::F: SquareRootField,
+    {
+        let domain_k = DomainK::<C::F>::new(parameters.domain_k_size)?;
+        let domain_h = DomainH::<C::F>::new(parameters.domain_h_size)?;
+        let (t, bound) = (parameters.t, parameters.enforced_degree_bound);
+
+        let shape = match protocol {
+            Protocol::ZeroOverK { terms, shifts } => {
+                let alphas = (0..terms)
+                    .map(|i| domain_k.element(if i < shifts { i } else { 0 }))
+                    .collect::<Vec<_>>();
+                // the dry run only depends on the shifts and the number of terms
+                let vo = GenericShiftingVO::new(
+                    &(0..terms).collect::<Vec<_>>(),
+                    &alphas,
+                    |terms: &[VOTerm<C::F>]| terms[1].clone(),
+                )?;
+                ZeroOverK::<C>::prove_dry_run(bound, &vo)
+            }
+            Protocol::NonZeroOverK => NonZeroOverK::<C>::prove_dry_run(bound)?,
+            Protocol::GeoSeq { sequences } => {
+                let length = domain_k.size() / sequences.max(1);
+                GeoSeqTest::<C>::prove_dry_run(
+                    domain_k.element(1),
+                    "f",
+                    bound,
+                    &vec![C::F::one(); sequences],
+                    &vec![length; sequences],
+                    &domain_k,
+                )?
+            }
+            Protocol::DLComparison => {
+                DLComparison::<C>::prove_dry_run(&domain_k, &domain_h, bound)?
+            }
+            Protocol::DLComparisonV2 => {
+                DLComparison::<C>::prove_v2_dry_run(&domain_k, &domain_h, bound)?
+            }
+            Protocol::SubsetOverK => {
+                SubsetOverK::<C>::prove_dry_run(&domain_k, Table::Values, bound)?
+            }
+            Protocol::TStrictlyLowerTriangular => TStrictlyLowerTriangular::<C>::prove_dry_run(
+                t,
+                &domain_k,
+                &domain_h,
+                bound,
+                TFT::<C>::AB_PADDING,
+            )?,
+            Protocol::TDiag => TDiag::<C>::prove_dry_run(
+                t,
+                bound,
+                &domain_k,
+                &domain_h,
+                domain_h.size(),
+                TFT::<C>::C_PADDING,
+            )?,
+            Protocol::TFT => TFT::<C>::prove_dry_run(t, &domain_k, &domain_h, bound)?,
+        };
+
+        Ok(Self::from_shape(protocol.name(), *parameters, shape))
+    }
+
+    /// The spec of the protocol whose dry run gave `shape`, with its commitments and challenges grouped by round
+    pub fn from_shape(protocol: &str, parameters: SpecParameters, shape: ProofShape) -> Self {
+        let mut rounds = vec![RoundSpec::default(); shape.num_rounds()];
+        for commitment in shape.commitments {
+            rounds[commitment.round].oracles.push(OracleSpec {
+                label: commitment.label,
+                degree_bound: commitment.degree_bound,
+            });
+        }
+        for challenge in shape.challenges {
+            rounds[challenge.round].challenges.push(challenge.label);
+        }
+
+        Self {
+            protocol: protocol.to_string(),
+            parameters,
+            rounds,
+            queries: shape.queries,
+            checks: shape.checks,
+        }
+    }
+
+    /// The spec as pretty-printed JSON
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self).map_err(|_| Error::ToBytesError)
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        cost_model::Protocol,
+        domains::{DomainH, DomainK},
+        error::Error,
+        spec::{ProtocolSpec, SpecParameters},
+        t_functional_triple::TFT,
+    };
+    use ark_bn254::{Bn254, Fr};
+    use blake2::Blake2s;
+    use fiat_shamir_rng::SimpleHashFiatShamirRng;
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
+
+    const PARAMETERS: SpecParameters = SpecParameters {
+        t: 2,
+        domain_k_size: 8,
+        domain_h_size: 4,
+        enforced_degree_bound: Some(9),
+    };
+
+    #[test]
+    fn test_zero_over_k_spec() {
+        let spec = ProtocolSpec::generate::<C>(
+            Protocol::ZeroOverK {
+                terms: 2,
+                shifts: 2,
+            },
+            &PARAMETERS,
+        )
+        .unwrap();
+
+        // r_i, m_i and q_1 give beta_1, beta_2 and c, and the evaluations the separation challenge
+        assert_eq!(spec.rounds.len(), 2);
+        let labels = |round: usize| {
+            spec.rounds[round]
+                .oracles
+                .iter()
+                .map(|oracle| oracle.label.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(0), ["r_0", "r_1", "m_0", "m_1", "q_1"]);
+        assert_eq!(spec.rounds[0].challenges, ["beta_1", "beta_2", "c"]);
+        assert!(spec.rounds[1].oracles.is_empty());
+        assert_eq!(spec.rounds[1].challenges, ["separation_challenge"]);
+        assert_eq!(spec.rounds[0].oracles[2].degree_bound, Some(9));
+        assert_eq!(
+            spec.checks
+                .iter()
+                .map(|check| check.label.as_str())
+                .collect::<Vec<_>>(),
+            ["check_1", "check_2", "opening"]
+        );
+    }
+
+    #[test]
+    fn test_tft_spec() {
+        let spec = ProtocolSpec::generate::<C>(Protocol::TFT, &PARAMETERS).unwrap();
+        assert_eq!(spec.protocol, "t_functional_triple");
+
+        // the spec lays out the dry run of the prover round by round
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let shape = TFT::<C>::prove_dry_run(2, &domain_k, &domain_h, Some(9)).unwrap();
+        assert_eq!(
+            spec.rounds
+                .iter()
+                .map(|round| round.oracles.len())
+                .sum::<usize>(),
+            shape.commitments.len()
+        );
+        assert_eq!(spec.queries, shape.queries);
+        assert!(spec.rounds.iter().all(|round| !round.challenges.is_empty()));
+
+        // the first round of the t-SLT test of A commits to h, then opens it at the start of its sequences
+        assert_eq!(spec.rounds[0].oracles[0].label, "a_slt/h");
+        assert_eq!(
+            spec.rounds[0].challenges,
+            ["a_slt/h_geo_seq/separation_challenge"]
+        );
+        for check in [
+            "a_slt/discrete_log_comparison/h_degree_bound",
+            "b_slt/h_geo_seq/initial_values",
+            "c_diag/val_m_plus_h2_non_zero/inverse_check/check_2",
+        ] {
+            assert!(spec.checks.iter().any(|c| c.label == check), "{}", check);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_domain() {
+        let parameters = SpecParameters {
+            domain_k_size: 1 << 40,
+            ..PARAMETERS
+        };
+        assert!(matches!(
+            ProtocolSpec::generate::<C>(Protocol::DLComparison, &parameters),
+            Err(Error::UnsupportedDomain(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_spec() {
+        let spec = ProtocolSpec::generate::<C>(Protocol::DLComparisonV2, &PARAMETERS).unwrap();
+        let json = spec.to_json().unwrap();
+        assert!(json.contains("\"protocol\": \"discrete_log_comparison_v2\""));
+        assert!(json.contains("\"combination\""));
+        assert_eq!(serde_json::from_str::<ProtocolSpec>(&json).unwrap(), spec);
+    }
+}
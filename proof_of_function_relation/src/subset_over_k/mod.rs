@@ -1,29 +1,515 @@
-use crate::{error::Error, subset_over_k::proof::Proof};
-use ark_ff::PrimeField;
-use ark_std::marker::PhantomData;
-use fiat_shamir_rng::FiatShamirRng;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use crate::{
+    domains::DomainK,
+    error::{Error, WitnessError},
+    subset_over_k::proof::Proof,
+};
+use ark_ff::{to_bytes, One, PrimeField, Zero};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use blake2::Blake2s;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use homomorphic_poly_commit::{commit_policy::CommitPolicy, AdditivelyHomomorphicPCS};
+use std::{collections::BTreeMap, marker::PhantomData};
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    proof_shape::ProofShape,
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {
+    crate::error::to_pc_error, ark_ff::batch_inversion, ark_poly::UVPolynomial, rand::Rng,
+    zero_over_k::cancellation::CancellationToken,
+};
 
 pub mod proof;
+mod tests;
 
-// TODO: implement SubsetOverK, currently just a placeholder
-pub struct SubsetOverK<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> {
-    _field: PhantomData<F>,
-    _pc: PhantomData<PC>,
-    _fs: PhantomData<FS>,
+/// z appears twice, followed by a, b, m, f and h
+const SUBSET_CHECK_MAPPING: [usize; 7] = [0, 0, 1, 2, 3, 4, 5];
+
+/// Labels of the polynomials committed by the prover
+const LABELS: [&str; 4] = ["subset_m", "subset_a", "subset_b", "subset_z"];
+
+/// The values over K that the evaluations of f are looked up in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Table {
+    /// The evaluations of h
+    Values,
+    /// The squares of the evaluations of h, e.g. the powers of a base from those of its square root
+    Squares,
+}
+
+impl Table {
+    /// The table over K, from the evaluations of h
+    fn evals<F: PrimeField>(self, mut h_evals: Vec<F>) -> Vec<F> {
+        if self == Table::Squares {
+            for h in h_evals.iter_mut() {
+                h.square_in_place();
+            }
+        }
+        h_evals
+    }
+
+    /// Bound on the degree of the lookup identity relative to the oracles: b * (beta - h^2) is of degree three
+    fn scaling_factor(self) -> usize {
+        match self {
+            Table::Values => 2,
+            Table::Squares => 3,
+        }
+    }
 }
 
-impl<F, PC, FS> SubsetOverK<F, PC, FS>
-where
-    F: PrimeField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
-{
-    pub fn prove() -> Proof {
-        Proof {}
+/// Proves that every evaluation of f over K is an evaluation of h over K, or the square of one, for committed f and h.
+///
+/// This is a logarithmic derivative lookup: the prover commits to the multiplicities m, where m(gamma_K^j) counts the
+/// evaluations of f equal to h(gamma_K^j) at the first index j holding that value and is 0 elsewhere. For a verifier
+/// challenge beta, the sum over K of 1 / (beta - f) equals that of m / (beta - h) if and only if f takes its values
+/// in those of h, but for a negligible fraction of beta. The prover commits to a = 1 / (beta - f), b = m / (beta - h)
+/// and their running difference z, with z(gamma_K * X) = z(X) + a(X) - b(X). z wraps around K, so both sums are equal
+/// without opening z, and for a challenge alpha a single zero over K checks
+///
+/// a * (beta - f) - 1 + alpha * (b * (beta - h) - m) + alpha^2 * (z(gamma_K * X) - z - a + b)
+///
+/// with h^2 in place of h for a [`Table::Squares`] lookup.
+pub struct SubsetOverK<C: ProtocolConfig> {
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> SubsetOverK<C> {
+    pub const PROTOCOL_NAME: &'static [u8] = b"Subset over K";
+
+    /// Check that every evaluation of f over K is in the table of h, failing on the first index of K where it is not
+    /// before any work is done. With the `validate-witness` feature the prover runs this check itself.
+    pub fn validate_witness(
+        domain_k: &DomainK<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        h: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        table: Table,
+    ) -> Result<(), Error> {
+        let h_evals = table.evals(domain_k.fft(h.polynomial()));
+        let f_evals = domain_k.fft(f.polynomial());
+        match Self::multiplicities(&f_evals, &h_evals) {
+            Ok(_) => Ok(()),
+            Err((index, _)) => Err(Error::InvalidWitness(WitnessError::NotInSubset { index })),
+        }
+    }
+
+    /// The commitments and queries of a proof over K with oracles of degree bound `enforced_degree_bound`, without
+    /// computing it
+    pub fn prove_dry_run(
+        domain_k: &DomainK<C::F>,
+        table: Table,
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<ProofShape, Error> {
+        let mut shape = ProofShape::new();
+        shape.commit(LABELS[0], enforced_degree_bound);
+        shape.challenge("beta");
+        for label in &LABELS[1..] {
+            shape.commit(*label, enforced_degree_bound);
+        }
+        shape.challenge("alpha");
+        shape.extend(
+            "subset_check",
+            ZeroOverK::<C>::prove_dry_run(
+                enforced_degree_bound,
+                &Self::subset_check_oracle(domain_k, table, C::F::one(), C::F::one())?,
+            ),
+        );
+        Ok(shape)
+    }
+
+    /// Serialized size in bytes of a proof over K with oracles of degree bound `enforced_degree_bound`, with keys of
+    /// degree `supported_degree`
+    pub fn proof_size(
+        domain_k: &DomainK<C::F>,
+        table: Table,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        Ok(
+            LABELS.len() * C::PC::commitment_size(enforced_degree_bound.is_some())
+                + ZeroOverK::<C>::proof_size(
+                    enforced_degree_bound,
+                    &Self::subset_check_oracle(domain_k, table, C::F::one(), C::F::one())?,
+                    CommitPolicy::NonHiding,
+                    supported_degree,
+                ),
+        )
     }
 
-    pub fn verify(_proof: Proof) -> Result<(), Error> {
+    #[cfg(feature = "prover")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        h: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_rand: &pc::Randomness<C>,
+        table: Table,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_with_cancellation(
+            ck,
+            domain_k,
+            f,
+            f_commit,
+            f_rand,
+            h,
+            h_commit,
+            h_rand,
+            table,
+            enforced_degree_bound,
+            context,
+            fs_rng,
+            None,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove`], polling `cancellation` between the rounds, see [`zero_over_k::cancellation`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_cancellation<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        f: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        f_rand: &pc::Randomness<C>,
+        h: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_rand: &pc::Randomness<C>,
+        table: Table,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("subset_over_k::prove");
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(domain_k, f, h, table)?;
+
+        Self::absorb_statement(domain_k, f_commit, h_commit, table, context, fs_rng)?;
+
+        // Step 1: commit to the multiplicities. Values of f missing from h are left out, so that the sums differ and
+        // the proof does not verify.
+        let f_evals = domain_k.fft(f.polynomial());
+        let h_evals = table.evals(domain_k.fft(h.polynomial()));
+        let m_evals =
+            Self::multiplicities(&f_evals, &h_evals).unwrap_or_else(|(_, partial)| partial);
+
+        let m = Self::interpolate(LABELS[0], &m_evals, domain_k, enforced_degree_bound);
+        let (m_commit, m_rand) =
+            C::PC::commit(ck, [&m], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![m_commit].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+        let beta: C::F = ChallengeSet::new(&mut *fs_rng, b"lookup").challenge(b"beta");
+
+        // Step 2: commit to a, b and their running difference z. The denominators only vanish if beta hits a value of
+        // f or h, which happens with negligible probability.
+        let mut a_evals = f_evals.iter().map(|&f| beta - f).collect::<Vec<_>>();
+        batch_inversion(&mut a_evals);
+        let mut b_evals = h_evals.iter().map(|&h| beta - h).collect::<Vec<_>>();
+        batch_inversion(&mut b_evals);
+        for (b, m) in b_evals.iter_mut().zip(&m_evals) {
+            *b *= m;
+        }
+
+        let mut z_evals = Vec::with_capacity(domain_k.size());
+        let mut acc = C::F::zero();
+        for (a, b) in a_evals.iter().zip(&b_evals) {
+            z_evals.push(acc);
+            acc += *a - b;
+        }
+
+        let a = Self::interpolate(LABELS[1], &a_evals, domain_k, enforced_degree_bound);
+        let b = Self::interpolate(LABELS[2], &b_evals, domain_k, enforced_degree_bound);
+        let z = Self::interpolate(LABELS[3], &z_evals, domain_k, enforced_degree_bound);
+        let (commitments, rands) =
+            C::PC::commit(ck, [&a, &b, &z], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![commitments].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+        let alpha: C::F = ChallengeSet::new(&mut *fs_rng, b"combination").challenge(b"alpha");
+
+        // Step 3: Zero over K for the lookup identity
+        let concrete_oracles = [&z, &a, &b, &m, f, h];
+        let oracle_commitments = [
+            commitments[2].clone(),
+            commitments[0].clone(),
+            commitments[1].clone(),
+            m_commit[0].clone(),
+            f_commit.clone(),
+            h_commit.clone(),
+        ];
+        let oracle_rands = [
+            rands[2].clone(),
+            rands[0].clone(),
+            rands[1].clone(),
+            m_rand[0].clone(),
+            f_rand.clone(),
+            h_rand.clone(),
+        ];
+
+        let subset_check_proof = ZeroOverK::<C>::prove_with_cancellation(
+            &concrete_oracles,
+            &oracle_commitments,
+            &oracle_rands,
+            enforced_degree_bound,
+            &Self::subset_check_oracle(domain_k, table, beta, alpha)?,
+            domain_k,
+            ck,
+            &context.bound_child::<Blake2s, _>(b"subset_check", &[beta, alpha]),
+            cancellation,
+            rng,
+        )?;
+
+        Ok(Proof {
+            m_commit: m_commit[0].commitment().clone(),
+            a_commit: commitments[0].commitment().clone(),
+            b_commit: commitments[1].commitment().clone(),
+            z_commit: commitments[2].commitment().clone(),
+            subset_check_proof,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        domain_k: &DomainK<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        table: Table,
+        enforced_degree_bound: Option<usize>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        let _span = trace_span!("subset_over_k::verify");
+        let (beta, alpha) = Self::challenges(
+            domain_k,
+            f_commit,
+            h_commit,
+            table,
+            enforced_degree_bound,
+            &proof,
+            context,
+            fs_rng,
+        )?;
+
+        let [m_commit, a_commit, b_commit, z_commit] =
+            Self::commitments(&proof, enforced_degree_bound);
+        let oracle_commitments = [
+            z_commit,
+            a_commit,
+            b_commit,
+            m_commit,
+            f_commit.clone(),
+            h_commit.clone(),
+        ];
+
+        ZeroOverK::<C>::verify(
+            proof.subset_check_proof,
+            &oracle_commitments,
+            enforced_degree_bound,
+            &Self::subset_check_oracle(domain_k, table, beta, alpha)?,
+            domain_k,
+            vk,
+            &context.bound_child::<Blake2s, _>(b"subset_check", &[beta, alpha]),
+        )
+        .map_err(Error::from)
+    }
+
+    /// Advance `fs_rng` as verifying `proof` would, without checking anything
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn replay_transcript(
+        domain_k: &DomainK<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        table: Table,
+        enforced_degree_bound: Option<usize>,
+        proof: &Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::challenges(
+            domain_k,
+            f_commit,
+            h_commit,
+            table,
+            enforced_degree_bound,
+            proof,
+            context,
+            fs_rng,
+        )
+        .map(|_| ())
+    }
+
+    fn absorb_statement(
+        domain_k: &DomainK<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        table: Table,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        let fs_bytes = &to_bytes![
+            &Self::PROTOCOL_NAME,
+            context,
+            domain_k.size() as u64,
+            table == Table::Squares,
+            f_commit.commitment(),
+            h_commit.commitment()
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
         Ok(())
     }
+
+    /// Absorb the statement and the commitments of `proof` as the prover did, and derive beta and alpha
+    #[allow(clippy::too_many_arguments)]
+    fn challenges(
+        domain_k: &DomainK<C::F>,
+        f_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        table: Table,
+        enforced_degree_bound: Option<usize>,
+        proof: &Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(C::F, C::F), Error> {
+        Self::absorb_statement(domain_k, f_commit, h_commit, table, context, fs_rng)?;
+        let [m_commit, a_commit, b_commit, z_commit] =
+            Self::commitments(proof, enforced_degree_bound);
+
+        let fs_bytes = &to_bytes![vec![m_commit]].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+        let beta = ChallengeSet::new(&mut *fs_rng, b"lookup").challenge(b"beta");
+
+        let fs_bytes =
+            &to_bytes![vec![a_commit, b_commit, z_commit]].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+        let alpha = ChallengeSet::new(&mut *fs_rng, b"combination").challenge(b"alpha");
+
+        Ok((beta, alpha))
+    }
+
+    /// The commitments of `proof` under the labels the prover committed them with
+    fn commitments(
+        proof: &Proof<C>,
+        enforced_degree_bound: Option<usize>,
+    ) -> [LabeledCommitment<pc::Commitment<C>>; 4] {
+        let commitment = |label: &str, commitment: &pc::Commitment<C>| {
+            LabeledCommitment::new(
+                String::from(label),
+                commitment.clone(),
+                enforced_degree_bound,
+            )
+        };
+        [
+            commitment(LABELS[0], &proof.m_commit),
+            commitment(LABELS[1], &proof.a_commit),
+            commitment(LABELS[2], &proof.b_commit),
+            commitment(LABELS[3], &proof.z_commit),
+        ]
+    }
+
+    /// The multiplicity of each evaluation of h among those of f, at the first index of K holding it. On an
+    /// evaluation of f missing from h, the index of the first one and the multiplicities of the others.
+    #[allow(clippy::type_complexity)]
+    fn multiplicities(f_evals: &[C::F], h_evals: &[C::F]) -> Result<Vec<C::F>, (usize, Vec<C::F>)> {
+        let mut first_index = BTreeMap::new();
+        for (index, value) in h_evals.iter().enumerate() {
+            first_index.entry(*value).or_insert(index);
+        }
+
+        let mut multiplicities = vec![C::F::zero(); h_evals.len()];
+        let mut missing = None;
+        for (index, value) in f_evals.iter().enumerate() {
+            match first_index.get(value) {
+                Some(&j) => multiplicities[j] += C::F::one(),
+                None => {
+                    missing.get_or_insert(index);
+                }
+            }
+        }
+
+        match missing {
+            None => Ok(multiplicities),
+            Some(index) => Err((index, multiplicities)),
+        }
+    }
+
+    #[cfg(feature = "prover")]
+    fn interpolate(
+        label: &str,
+        evals: &[C::F],
+        domain_k: &DomainK<C::F>,
+        enforced_degree_bound: Option<usize>,
+    ) -> LabeledPolynomial<C::F, DensePolynomial<C::F>> {
+        LabeledPolynomial::new(
+            String::from(label),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(evals)),
+            enforced_degree_bound,
+            Some(1),
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn subset_check_oracle(
+        domain_k: &DomainK<C::F>,
+        table: Table,
+        beta: C::F,
+        alpha: C::F,
+    ) -> Result<GenericShiftingVO<C::F, impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F>>, Error> {
+        // z is shifted by gamma_K in the second term, every other oracle appears once unshifted
+        let mut alphas = vec![C::F::one(); SUBSET_CHECK_MAPPING.len()];
+        alphas[1] = domain_k.element(1);
+
+        Ok(GenericShiftingVO::new(
+            &SUBSET_CHECK_MAPPING,
+            &alphas,
+            Self::subset_check(table, beta, alpha),
+        )?
+        .with_scaling_factor(table.scaling_factor()))
+    }
+
+    /// a * (beta - f) - 1 + alpha * (b * (beta - h) - m) + alpha^2 * (z(gamma_K * X) - z - a + b), over the terms
+    /// [X, z(X), z(gamma_K * X), a(X), b(X), m(X), f(X), h(X)], with h^2 in place of h for a table of squares
+    fn subset_check(
+        table: Table,
+        beta: C::F,
+        alpha: C::F,
+    ) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        move |terms: &[VOTerm<C::F>]| {
+            let (z, z_shifted, a, b, m, f, h) = (
+                terms[1].clone(),
+                terms[2].clone(),
+                terms[3].clone(),
+                terms[4].clone(),
+                terms[5].clone(),
+                terms[6].clone(),
+                terms[7].clone(),
+            );
+            let h = match table {
+                Table::Values => h,
+                Table::Squares => h.clone() * h,
+            };
+            a.clone() * (vo_constant!(beta) - f) - vo_constant!(C::F::one())
+                + vo_constant!(alpha) * (b.clone() * (vo_constant!(beta) - h) - m)
+                + vo_constant!(alpha * alpha) * (z_shifted - z - a + b)
+        }
+    }
 }
@@ -0,0 +1,162 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        domains::DomainK,
+        error::{Error, WitnessError},
+        subset_over_k::{SubsetOverK, Table},
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::{to_bytes, Field};
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    };
+    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // h holds three distinct values and repeats its padding, f draws from them with repetitions
+    fn h_evals() -> Vec<F> {
+        [5u64, 7, 9, 0, 0, 0, 0, 0]
+            .iter()
+            .map(|&v| F::from(v))
+            .collect()
+    }
+
+    fn f_evals() -> Vec<F> {
+        [9u64, 5, 5, 0, 9, 9, 7, 0]
+            .iter()
+            .map(|&v| F::from(v))
+            .collect()
+    }
+
+    fn interpolate(
+        label: &str,
+        evals: &[F],
+        domain_k: &GeneralEvaluationDomain<F>,
+        hiding_bound: Option<usize>,
+    ) -> LabeledPolynomial<F, DensePolynomial<F>> {
+        LabeledPolynomial::new(
+            String::from(label),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(evals)),
+            Some(domain_k.size() + 1),
+            hiding_bound,
+        )
+    }
+
+    fn run_subset_test(f_evals: &[F], h_evals: &[F], table: Table) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let enforced_degree_bound = Some(domain_k.size() + 1);
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, domain_k.size() + 1])).unwrap();
+
+        // f is private, h is public
+        let f = interpolate("f", f_evals, &domain_k, Some(1));
+        let h = interpolate("h", h_evals, &domain_k, None);
+        let (commitments, rands) = PC::commit(&ck, [&f, &h], Some(rng)).unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = SubsetOverK::<C>::prove(
+            &ck,
+            &domain_k,
+            &f,
+            &commitments[0],
+            &rands[0],
+            &h,
+            &commitments[1],
+            &rands[1],
+            table,
+            enforced_degree_bound,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        SubsetOverK::<C>::verify(
+            &vk,
+            &domain_k,
+            &commitments[0],
+            &commitments[1],
+            table,
+            enforced_degree_bound,
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        )
+    }
+
+    #[test]
+    fn test_subset() {
+        assert_eq!(
+            run_subset_test(&f_evals(), &h_evals(), Table::Values),
+            Ok(())
+        );
+
+        // every value of h need not be hit
+        let f_evals = vec![F::from(7u64); 8];
+        assert_eq!(run_subset_test(&f_evals, &h_evals(), Table::Values), Ok(()));
+    }
+
+    #[test]
+    fn test_subset_of_squares() {
+        let squares: Vec<F> = f_evals().iter().map(|v| v.square()).collect();
+        assert_eq!(
+            run_subset_test(&squares, &h_evals(), Table::Squares),
+            Ok(())
+        );
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_not_subset() {
+        let mut evals = f_evals();
+        evals[6] = F::from(8u64);
+        assert!(run_subset_test(&evals, &h_evals(), Table::Values).is_err());
+
+        // f takes 9 three times, but h does not hold it
+        let mut evals = h_evals();
+        evals[2] = F::from(0u64);
+        assert!(run_subset_test(&f_evals(), &evals, Table::Values).is_err());
+
+        // the values of f are in h, not among its squares
+        assert!(run_subset_test(&f_evals(), &h_evals(), Table::Squares).is_err());
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let validate = |f_evals: &[F]| {
+            SubsetOverK::<C>::validate_witness(
+                &domain_k,
+                &interpolate("f", f_evals, &domain_k, Some(1)),
+                &interpolate("h", &h_evals(), &domain_k, None),
+                Table::Values,
+            )
+        };
+
+        assert_eq!(validate(&f_evals()), Ok(()));
+
+        let mut evals = f_evals();
+        evals[4] = F::from(8u64);
+        evals[6] = F::from(8u64);
+        assert_eq!(
+            validate(&evals),
+            Err(Error::InvalidWitness(WitnessError::NotInSubset {
+                index: 4
+            }))
+        );
+    }
+}
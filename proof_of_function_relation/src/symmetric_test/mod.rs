@@ -0,0 +1,148 @@
+use crate::{
+    domains::DomainK,
+    error::{Error, WitnessError},
+    indexer::{CommittedEncoding, SparseMatrixEncoding},
+    report::VerificationReport,
+    symmetric_test::proof::Proof,
+    transpose_test::{self, TransposeTest},
+};
+use ark_ff::to_bytes;
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, Context};
+#[cfg(feature = "prover")]
+use rand::Rng;
+use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::trace_span;
+
+pub mod proof;
+mod tests;
+
+/// The public data of a symmetric test: the committed encoding over K of a matrix M
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<C::F>,
+    pub m: CommittedEncoding<pc::Commitment<C>>,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+impl<C: ProtocolConfig> Statement<C> {
+    /// M is symmetric if it is its own transpose
+    fn transpose_statement(&self) -> transpose_test::Statement<C> {
+        transpose_test::Statement {
+            domain_k: self.domain_k,
+            m: self.m.clone(),
+            m_t: self.m.clone(),
+            enforced_degree_bound: self.enforced_degree_bound,
+        }
+    }
+}
+
+/// Proves that the matrix encoded by (row, col, val) over K is symmetric: the multiset of entries (row, col, val) is
+/// equal to the multiset of swapped entries (col, row, val). This is the [`TransposeTest`] of M against itself, bound
+/// to its own protocol name. Padding entries are part of the multiset, so the encoding must be padded with
+/// `PaddingStrategy::Origin` unless its last entry lies on the diagonal.
+pub struct SymmetricTest<C: ProtocolConfig> {
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> SymmetricTest<C> {
+    pub const PROTOCOL_NAME: &'static [u8] = b"Symmetric Matrix Test";
+
+    /// Check that every entry of the encoding is paired with its mirror, failing on the first unpaired element of K
+    /// before any work is done. With the `validate-witness` feature the prover runs this check itself.
+    pub fn validate_witness(
+        statement: &Statement<C>,
+        m: &SparseMatrixEncoding<C::F>,
+    ) -> Result<(), Error> {
+        TransposeTest::<C>::validate_witness(&statement.transpose_statement(), m, m).map_err(|e| {
+            match e {
+                Error::InvalidWitness(WitnessError::NoTransposedEntry { index }) => {
+                    Error::InvalidWitness(WitnessError::NoMirrorEntry { index })
+                }
+                e => e,
+            }
+        })
+    }
+
+    #[cfg(feature = "prover")]
+    /// The randomness of the encoding is given in the order of [`SparseMatrixEncoding::iter`]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        m: &SparseMatrixEncoding<C::F>,
+        m_rands: &[pc::Randomness<C>],
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("symmetric_test::prove");
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(statement, m)?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        TransposeTest::<C>::prove(
+            ck,
+            &statement.transpose_statement(),
+            m,
+            m_rands,
+            m,
+            m_rands,
+            context,
+            fs_rng,
+            rng,
+        )
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(vk, statement, proof, context, fs_rng).into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("symmetric_test::verify");
+        let mut report = VerificationReport::builder("symmetric_test");
+
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        report.add(TransposeTest::<C>::verify_with_report(
+            vk,
+            &statement.transpose_statement(),
+            proof,
+            context,
+            fs_rng,
+        ));
+
+        report.finish()
+    }
+}
@@ -0,0 +1,2 @@
+/// A symmetric test is a transpose test of a matrix against itself, and has the same proof
+pub use crate::transpose_test::proof::Proof;
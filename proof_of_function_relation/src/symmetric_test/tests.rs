@@ -0,0 +1,161 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{committed, domains, encode},
+            PaddingStrategy,
+        },
+        symmetric_test::{Statement, SymmetricTest},
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // M values
+    /*
+        0, 1, 0, 0
+        1, 0, 0, 3
+        0, 0, 2, 0
+        0, 3, 0, 0
+    */
+    fn symmetric_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 1, F::from(1u64)),
+            (1, 0, F::from(1u64)),
+            (1, 3, F::from(3u64)),
+            (2, 2, F::from(2u64)),
+            (3, 1, F::from(3u64)),
+        ]
+    }
+
+    fn run_symmetric_test(
+        entries: &[(usize, usize, F)],
+        padding: PaddingStrategy,
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let m = encode(entries, &domain_k, &domain_h, padding);
+        let (m_commit, m_rands) = committed::<F, PC>(&ck, &m);
+
+        let statement = Statement::<C> {
+            domain_k,
+            m: m_commit,
+            enforced_degree_bound: Some(enforced_degree_bound),
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = SymmetricTest::<C>::prove(
+            &ck,
+            &statement,
+            &m,
+            &m_rands,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        SymmetricTest::<C>::verify(&vk, &statement, proof, &Context::new(b"test"), &mut fs_rng)
+    }
+
+    #[test]
+    fn test_symmetric_matrix() {
+        assert_eq!(
+            run_symmetric_test(&symmetric_entries(), PaddingStrategy::Origin),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_symmetric_matrix_with_diagonal_last_entry() {
+        // repeating a diagonal entry as padding keeps the encoding closed under swapping
+        let mut entries = symmetric_entries();
+        entries.swap(3, 4);
+        assert_eq!(
+            run_symmetric_test(&entries, PaddingStrategy::RepeatLast),
+            Ok(())
+        );
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_asymmetric_value() {
+        let mut entries = symmetric_entries();
+        entries[4].2 = F::from(4u64);
+        assert!(run_symmetric_test(&entries, PaddingStrategy::Origin).is_err());
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_missing_mirror_entry() {
+        let mut entries = symmetric_entries();
+        entries.remove(1);
+        assert!(run_symmetric_test(&entries, PaddingStrategy::Origin).is_err());
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_off_diagonal_padding() {
+        // padding repeats (3, 1), which has no mirror among the padding entries
+        assert!(run_symmetric_test(&symmetric_entries(), PaddingStrategy::RepeatLast).is_err());
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[domain_k.size() + 1])).unwrap();
+
+        let validate = |entries: &[(usize, usize, F)], padding| {
+            let m = encode(entries, &domain_k, &domain_h, padding);
+            let statement = Statement::<C> {
+                domain_k,
+                m: committed::<F, PC>(&ck, &m).0,
+                enforced_degree_bound: None,
+            };
+            SymmetricTest::<C>::validate_witness(&statement, &m)
+        };
+
+        assert_eq!(
+            validate(&symmetric_entries(), PaddingStrategy::Origin),
+            Ok(())
+        );
+
+        let mut entries = symmetric_entries();
+        entries[2].2 = F::from(4u64);
+        assert_eq!(
+            validate(&entries, PaddingStrategy::Origin),
+            Err(Error::InvalidWitness(WitnessError::NoMirrorEntry {
+                index: 2
+            }))
+        );
+        // the first padding entry repeats (3, 1) with a value of 0
+        assert_eq!(
+            validate(&symmetric_entries(), PaddingStrategy::RepeatLast),
+            Err(Error::InvalidWitness(WitnessError::NoMirrorEntry {
+                index: 5
+            }))
+        );
+    }
+}
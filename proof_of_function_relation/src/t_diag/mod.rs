@@ -1,26 +1,39 @@
 use crate::{
-    error::{to_pc_error, Error},
+    domains::{DomainH, DomainK},
+    error::Error,
     geo_seq::GeoSeqTest,
+    indexer::PaddingStrategy,
     non_zero_over_k::NonZeroOverK,
+    report::VerificationReport,
     t_diag::proof::Proof,
-    util::generate_sequence,
+    util::{padded_sequence, rows_from_t},
 };
-use ark_ff::{PrimeField, SquareRootField};
-use ark_poly::{
-    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
-};
-use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
-use fiat_shamir_rng::FiatShamirRng;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
-use rand::Rng;
+use ark_ff::{One, SquareRootField, Zero};
+use ark_poly::EvaluationDomain;
+use ark_poly_commit::LabeledCommitment;
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
+use fiat_shamir_rng::Context;
+use homomorphic_poly_commit::{commit_policy::CommitPolicy, AdditivelyHomomorphicPCS};
 use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
 use zero_over_k::{
+    proof_shape::ProofShape,
+    trace_span,
     virtual_oracle::generic_shifting_vo::{
         presets::{self, zero_product_check},
         GenericShiftingVO,
     },
+    virtual_oracle::VirtualOracle,
     zero_over_k::ZeroOverK,
 };
+#[cfg(feature = "prover")]
+use {
+    crate::{checked_key::CheckedCommitterKey, error::to_pc_error, util::interpolate_sequence},
+    ark_poly::univariate::DensePolynomial,
+    ark_poly_commit::LabeledPolynomial,
+    rand::Rng,
+};
 
 use self::piop::PIOPforTDiagTest;
 
@@ -28,82 +41,207 @@ pub mod piop;
 pub mod proof;
 mod tests;
 
-pub struct TDiag<
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
-> {
-    _field: PhantomData<F>,
-    _pc: PhantomData<PC>,
-    _fs: PhantomData<FS>,
+pub struct TDiag<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
 }
 
-impl<F, PC, FS> TDiag<F, PC, FS>
+impl<C: ProtocolConfig> TDiag<C>
 where
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
+    C::F: SquareRootField,
 {
     #[allow(dead_code)]
     pub const PROTOCOL_NAME: &'static [u8] = b"t-Diagonal Test";
 
+    /// The commitments and queries of a proof for row t over K and H, without computing it
+    pub fn prove_dry_run(
+        t: usize,
+        enforced_degree_bound: Option<usize>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        number_of_constraints: usize,
+        padding: PaddingStrategy,
+    ) -> Result<ProofShape, Error> {
+        let mut shape = ProofShape::new();
+        shape.commit("h1", enforced_degree_bound);
+        shape.commit("h2", enforced_degree_bound);
+        let sequences = Self::sequences(t, domain_k, domain_h, number_of_constraints, padding)?;
+        for ((context, label), (common_ratio, a_s, c_s)) in
+            [("h1_geo_seq", "h1"), ("h2_geo_seq", "h2")]
+                .into_iter()
+                .zip(sequences)
+        {
+            shape.extend(
+                context,
+                GeoSeqTest::<C>::prove_dry_run(
+                    common_ratio,
+                    label,
+                    enforced_degree_bound,
+                    &a_s,
+                    &c_s,
+                    domain_k,
+                )?,
+            );
+        }
+
+        let eq_vo = Self::equality_check_oracle()?;
+        for context in ["h_eq_row_m", "row_m_eq_col_m"] {
+            shape.extend(
+                context,
+                ZeroOverK::<C>::prove_dry_run(enforced_degree_bound, &eq_vo),
+            );
+        }
+        shape.extend(
+            "val_m_times_h2",
+            ZeroOverK::<C>::prove_dry_run(enforced_degree_bound, &Self::product_check_oracle()?),
+        );
+        shape.extend(
+            "val_m_plus_h2_non_zero",
+            NonZeroOverK::<C>::prove_dry_run(enforced_degree_bound)?,
+        );
+        Ok(shape)
+    }
+
+    /// Serialized size in bytes of a proof for row t over K and H, with keys of degree `supported_degree`
+    pub fn proof_size(
+        t: usize,
+        enforced_degree_bound: Option<usize>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        number_of_constraints: usize,
+        padding: PaddingStrategy,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        // h1 and h2 are hiding
+        let mut size = 2 * C::PC::commitment_size(enforced_degree_bound.is_some());
+        for (common_ratio, a_s, c_s) in
+            Self::sequences(t, domain_k, domain_h, number_of_constraints, padding)?
+        {
+            size += GeoSeqTest::<C>::proof_size(
+                common_ratio,
+                enforced_degree_bound,
+                true,
+                &a_s,
+                &c_s,
+                domain_k,
+                supported_degree,
+            )?;
+        }
+        size += 2 * ZeroOverK::<C>::proof_size(
+            enforced_degree_bound,
+            &Self::equality_check_oracle()?,
+            CommitPolicy::NonHiding,
+            supported_degree,
+        );
+        size += ZeroOverK::<C>::proof_size(
+            enforced_degree_bound,
+            &Self::product_check_oracle()?,
+            CommitPolicy::NonHiding,
+            supported_degree,
+        );
+        size += NonZeroOverK::<C>::proof_size(enforced_degree_bound, supported_degree)?;
+        Ok(size)
+    }
+
+    /// The common ratios and sequences of h1 and h2
+    #[allow(clippy::type_complexity)]
+    fn sequences(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        number_of_constraints: usize,
+        padding: PaddingStrategy,
+    ) -> Result<[(C::F, Vec<C::F>, Vec<usize>); 2], Error> {
+        if t > domain_h.size() {
+            return Err(Error::T2Large);
+        }
+        let rows = rows_from_t(t, number_of_constraints)?;
+        let (a_s_h1, c_s_h1) = padded_sequence(domain_h.element(t), rows, C::F::zero, domain_k)?;
+        let (a_s_h2, c_s_h2) = padded_sequence(
+            C::F::zero(),
+            rows,
+            || PIOPforTDiagTest::h2_padding_value(padding, t, number_of_constraints, domain_h),
+            domain_k,
+        )?;
+
+        Ok([
+            (domain_h.element(1), a_s_h1, c_s_h1),
+            (domain_h.element(0), a_s_h2, c_s_h2),
+        ])
+    }
+
+    fn equality_check_oracle() -> Result<impl VirtualOracle<C::F>, Error> {
+        Ok(GenericShiftingVO::new(
+            &[0, 1],
+            &[C::F::one(), C::F::one()],
+            presets::equality_check,
+        )?
+        .with_scaling_factor(1))
+    }
+
+    fn product_check_oracle() -> Result<impl VirtualOracle<C::F>, Error> {
+        Ok(
+            GenericShiftingVO::new(&[0, 1], &[C::F::one(), C::F::one()], zero_product_check)?
+                .with_scaling_factor(2),
+        )
+    }
+
+    #[cfg(feature = "prover")]
     pub fn prove<R: Rng>(
-        ck: &PC::CommitterKey,
+        ck: &CheckedCommitterKey<C, Self>,
         t: usize,
-        row_m: &LabeledPolynomial<F, DensePolynomial<F>>,
-        col_m: &LabeledPolynomial<F, DensePolynomial<F>>,
-        val_m: &LabeledPolynomial<F, DensePolynomial<F>>,
-        row_m_commitment: &LabeledCommitment<PC::Commitment>,
-        col_m_commitment: &LabeledCommitment<PC::Commitment>,
-        val_m_commitment: &LabeledCommitment<PC::Commitment>,
-        row_m_random: &PC::Randomness,
-        col_m_random: &PC::Randomness,
-        val_m_random: &PC::Randomness,
+        row_m: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_m: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        val_m: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        val_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_m_random: &pc::Randomness<C>,
+        col_m_random: &pc::Randomness<C>,
+        val_m_random: &pc::Randomness<C>,
         enforced_degree_bound: Option<usize>,
-        domain_k: &GeneralEvaluationDomain<F>,
-        domain_h: &GeneralEvaluationDomain<F>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
         number_of_constraints: usize,
+        padding: PaddingStrategy,
+        context: &Context,
         rng: &mut R,
-    ) -> Result<Proof<F, PC>, Error> {
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("t_diag::prove");
+        ck.check_bound(enforced_degree_bound)?;
+        let cancellation = ck.cancellation();
+        let ck = ck.ck();
         if t > domain_h.size() {
             return Err(Error::T2Large);
         }
 
         // Step 1a produce h1 = w^t, w^(t+1), ..., w^(n-1), 0, 0, ..., 0
         let r_h1 = domain_h.element(1);
-        let mut a_s_h1 = vec![domain_h.element(t)];
-        let mut c_s_h1 = vec![number_of_constraints - t];
-
-        let to_pad = domain_k.size() - (number_of_constraints - t);
-        if to_pad > 0 {
-            a_s_h1.push(F::zero());
-            c_s_h1.push(to_pad);
-        }
+        let rows = rows_from_t(t, number_of_constraints)?;
+        let (a_s_h1, c_s_h1) = padded_sequence(domain_h.element(t), rows, C::F::zero, domain_k)?;
 
-        let seq = generate_sequence::<F>(r_h1, &a_s_h1.as_slice(), &c_s_h1.as_slice());
-        let h1 = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&seq));
+        let h1 = interpolate_sequence(r_h1, &a_s_h1, &c_s_h1, domain_k);
         let h1 = LabeledPolynomial::new(String::from("h1"), h1, enforced_degree_bound, Some(1));
 
-        // Step 1b produce h2 = 0, 0, ..., 0, 1, 1, ..., 1
+        // Step 1b produce h2 = 0, 0, ..., 0, p, p, ..., p where p is rowM = colM on the padding entries
         let r_h2 = domain_h.element(0);
-        let mut a_s_h2 = vec![F::zero()];
-        let mut c_s_h2 = vec![number_of_constraints - t];
-
-        let to_pad = domain_k.size() - (number_of_constraints - t);
-        if to_pad > 0 {
-            a_s_h2.push(F::one());
-            c_s_h2.push(to_pad);
-        }
+        let (a_s_h2, c_s_h2) = padded_sequence(
+            C::F::zero(),
+            rows,
+            || PIOPforTDiagTest::h2_padding_value(padding, t, number_of_constraints, domain_h),
+            domain_k,
+        )?;
 
-        let seq = generate_sequence::<F>(r_h2, &a_s_h2.as_slice(), &c_s_h2.as_slice());
-        let h2 = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&seq));
+        let h2 = interpolate_sequence(r_h2, &a_s_h2, &c_s_h2, domain_k);
         let h2 = LabeledPolynomial::new(String::from("h2"), h2, enforced_degree_bound, Some(1));
 
         let (h_commitments, h_rands) =
-            PC::commit(ck, &[h1.clone(), h2.clone()], Some(rng)).map_err(to_pc_error::<F, PC>)?;
+            C::PC::commit(ck, [&h1, &h2], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
 
         // Step 2: Geometric Sequence Test on h1
-        let h1_seq_proof = GeoSeqTest::<F, PC, FS>::prove(
+        let h1_seq_proof = GeoSeqTest::<C>::prove_with_cancellation(
             ck,
             r_h1,
             &h1,
@@ -112,11 +250,13 @@ where
             &a_s_h1,
             &c_s_h1,
             domain_k,
+            &context.child(b"h1_geo_seq"),
+            cancellation,
             rng,
         )?;
 
         // Step 3: Geometric Sequence Test on h2
-        let h2_seq_proof = GeoSeqTest::<F, PC, FS>::prove(
+        let h2_seq_proof = GeoSeqTest::<C>::prove_with_cancellation(
             ck,
             r_h2,
             &h2,
@@ -125,55 +265,66 @@ where
             &a_s_h2,
             &c_s_h2,
             domain_k,
+            &context.child(b"h2_geo_seq"),
+            cancellation,
             rng,
         )?;
 
         // Step 4a: h = h1 + h2
         let h = h1.polynomial() + h2.polynomial();
         let h = LabeledPolynomial::new(String::from("h"), h, enforced_degree_bound, Some(1));
-        let alphas = vec![F::one(), F::one()];
+        let alphas = vec![C::F::one(), C::F::one()];
 
-        let (h_commitment, h_rand) = PC::aggregate_commitments(
+        let (h_commitment, h_rand) = C::PC::aggregate_commitments(
             &h_commitments,
             Some(h_rands.to_vec()),
             &PIOPforTDiagTest::generate_h_linear_combination(),
         )?;
 
         // Step 4b: Zero over K for h = rowM
-        let eq_vo = GenericShiftingVO::new(&vec![0, 1], &alphas, presets::equality_check)?;
-        let h_eq_row_m = ZeroOverK::<F, PC, FS>::prove(
-            &[h.clone(), row_m.clone()],
+        let eq_vo = GenericShiftingVO::new(&vec![0, 1], &alphas, presets::equality_check)?
+            .with_scaling_factor(1);
+        let h_eq_row_m = ZeroOverK::<C>::prove_with_cancellation(
+            &[&h, row_m],
             &[h_commitment.clone(), row_m_commitment.clone()],
             &[h_rand.clone(), row_m_random.clone()],
             enforced_degree_bound,
             &eq_vo,
             domain_k,
             ck,
+            &context.child(b"h_eq_row_m"),
+            cancellation,
             rng,
         )?;
 
         // Step 4c: Zero over K for rowM = colM
-        let row_m_eq_col_m = ZeroOverK::<F, PC, FS>::prove(
-            &[row_m.clone(), col_m.clone()],
+        let row_m_eq_col_m = ZeroOverK::<C>::prove_with_cancellation(
+            &[row_m, col_m],
             &[row_m_commitment.clone(), col_m_commitment.clone()],
             &[row_m_random.clone(), col_m_random.clone()],
             enforced_degree_bound,
             &eq_vo,
             domain_k,
             ck,
+            &context.child(b"row_m_eq_col_m"),
+            cancellation,
             rng,
         )?;
 
         // Step 5: Zero over K for valM * h2 = 0
-        let prod_vo = GenericShiftingVO::new(&[0, 1], &[F::one(), F::one()], zero_product_check)?;
-        let val_m_times_h2_proof = ZeroOverK::<F, PC, FS>::prove(
-            &[val_m.clone(), h2.clone()],
+        let prod_vo =
+            GenericShiftingVO::new(&[0, 1], &[C::F::one(), C::F::one()], zero_product_check)?
+                .with_scaling_factor(2);
+        let val_m_times_h2_proof = ZeroOverK::<C>::prove_with_cancellation(
+            &[val_m, &h2],
             &[val_m_commitment.clone(), h_commitments[1].clone()],
             &[val_m_random.clone(), h_rands[1].clone()],
             enforced_degree_bound,
             &prod_vo,
             domain_k,
             ck,
+            &context.child(b"val_m_times_h2"),
+            cancellation,
             rng,
         )?;
 
@@ -185,7 +336,7 @@ where
             enforced_degree_bound,
             Some(1),
         );
-        let (val_plus_h2_commit, val_plus_h2_rand) = PC::aggregate_commitments(
+        let (val_plus_h2_commit, val_plus_h2_rand) = C::PC::aggregate_commitments(
             &[h_commitments[1].clone(), val_m_commitment.clone()],
             Some(vec![h_rands[1].clone(), val_m_random.clone()]),
             &PIOPforTDiagTest::generate_valM_plus_h2_linear_combination(&String::from(
@@ -193,12 +344,14 @@ where
             )),
         )?;
 
-        let val_plus_h2_proof = NonZeroOverK::<F, PC, FS>::prove(
+        let val_plus_h2_proof = NonZeroOverK::<C>::prove_with_cancellation(
             ck,
             domain_k,
             &val_plus_h2,
             &val_plus_h2_commit,
             &val_plus_h2_rand,
+            &context.child(b"val_m_plus_h2_non_zero"),
+            cancellation,
             rng,
         )?;
 
@@ -217,17 +370,54 @@ where
     }
 
     pub fn verify(
-        vk: &PC::VerifierKey,
+        vk: &pc::VerifierKey<C>,
         t: usize,
-        row_m_commitment: &LabeledCommitment<PC::Commitment>,
-        col_m_commitment: &LabeledCommitment<PC::Commitment>,
-        val_m_commitment: &LabeledCommitment<PC::Commitment>,
+        row_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        val_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
         enforced_degree_bound: Option<usize>,
-        domain_h: &GeneralEvaluationDomain<F>,
-        domain_k: &GeneralEvaluationDomain<F>,
+        domain_h: &DomainH<C::F>,
+        domain_k: &DomainK<C::F>,
         number_of_constraints: usize,
-        proof: Proof<F, PC>,
+        padding: PaddingStrategy,
+        proof: Proof<C>,
+        context: &Context,
     ) -> Result<(), Error> {
+        Self::verify_with_report(
+            vk,
+            t,
+            row_m_commitment,
+            col_m_commitment,
+            val_m_commitment,
+            enforced_degree_bound,
+            domain_h,
+            domain_k,
+            number_of_constraints,
+            padding,
+            proof,
+            context,
+        )
+        .into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        t: usize,
+        row_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        val_m_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        domain_h: &DomainH<C::F>,
+        domain_k: &DomainK<C::F>,
+        number_of_constraints: usize,
+        padding: PaddingStrategy,
+        proof: Proof<C>,
+        context: &Context,
+    ) -> VerificationReport {
+        let _span = trace_span!("t_diag::verify");
+        let mut report = VerificationReport::builder("t_diag");
+
         // re-label the oracle commitments with the enforced degree bound
         let row_m_commitment = LabeledCommitment::new(
             row_m_commitment.label().clone(),
@@ -247,14 +437,6 @@ where
 
         // Step 2: Geometric Sequence Test on h1
         let r_h1 = domain_h.element(1);
-        let mut a_s_h1 = vec![domain_h.element(t)];
-        let mut c_s_h1 = vec![number_of_constraints - t];
-
-        let to_pad = domain_k.size() - (number_of_constraints - t);
-        if to_pad > 0 {
-            a_s_h1.push(F::zero());
-            c_s_h1.push(to_pad);
-        }
 
         let h_commitments = vec![
             LabeledCommitment::new(
@@ -269,95 +451,124 @@ where
             ),
         ];
 
-        GeoSeqTest::<F, PC, FS>::verify(
-            r_h1,
-            &a_s_h1,
-            &c_s_h1,
-            domain_k,
-            &h_commitments[0],
-            enforced_degree_bound,
-            proof.h1_seq_proof,
-            vk,
-        )?;
-
-        // h2 params
-        let r_h2 = F::one();
-        let mut a_s_h2 = vec![F::zero()];
-        let mut c_s_h2 = vec![number_of_constraints - t];
-
-        let to_pad = domain_k.size() - (number_of_constraints - t);
-        if to_pad > 0 {
-            a_s_h2.push(F::one());
-            c_s_h2.push(to_pad);
-        }
+        report.check("h1_geo_seq", || {
+            let rows = rows_from_t(t, number_of_constraints)?;
+            let (a_s_h1, c_s_h1) =
+                padded_sequence(domain_h.element(t), rows, C::F::zero, domain_k)?;
+
+            GeoSeqTest::<C>::verify(
+                r_h1,
+                &a_s_h1,
+                &c_s_h1,
+                domain_k,
+                &h_commitments[0],
+                enforced_degree_bound,
+                proof.h1_seq_proof,
+                vk,
+                &context.child(b"h1_geo_seq"),
+            )
+        });
 
         // Step 3: Geometric Sequence Test on h2
-        GeoSeqTest::<F, PC, FS>::verify(
-            r_h2,
-            &a_s_h2,
-            &c_s_h2,
-            domain_k,
-            &h_commitments[1],
-            enforced_degree_bound,
-            proof.h2_seq_proof,
-            vk,
-        )?;
+        let r_h2 = C::F::one();
+        report.check("h2_geo_seq", || {
+            let rows = rows_from_t(t, number_of_constraints)?;
+            let (a_s_h2, c_s_h2) = padded_sequence(
+                C::F::zero(),
+                rows,
+                || PIOPforTDiagTest::h2_padding_value(padding, t, number_of_constraints, domain_h),
+                domain_k,
+            )?;
+
+            GeoSeqTest::<C>::verify(
+                r_h2,
+                &a_s_h2,
+                &c_s_h2,
+                domain_k,
+                &h_commitments[1],
+                enforced_degree_bound,
+                proof.h2_seq_proof,
+                vk,
+                &context.child(b"h2_geo_seq"),
+            )
+        });
 
         // Step 4a: Verifier derives a commitment to h = h1 + h2
-        let alphas = [F::one(), F::one()];
-        let (h_commit, _) = PC::aggregate_commitments(
-            &h_commitments,
-            None,
-            &PIOPforTDiagTest::generate_h_linear_combination(),
-        )?;
+        let alphas = [C::F::one(), C::F::one()];
+        let eq_vo = GenericShiftingVO::new(&[0, 1], &alphas, presets::equality_check)
+            .unwrap()
+            .with_scaling_factor(1);
 
         // Step 4b: Zero over K for h = rowM
-        let eq_vo = GenericShiftingVO::new(&vec![0, 1], &alphas, presets::equality_check)?;
-        ZeroOverK::<F, PC, FS>::verify(
-            proof.h_eq_row_m,
-            vec![h_commit.clone(), row_m_commitment.clone()].as_slice(),
-            enforced_degree_bound,
-            &eq_vo,
-            domain_k,
-            vk,
-        )?;
+        report.check("h_eq_row_m", || {
+            let (h_commit, _) = C::PC::aggregate_commitments(
+                &h_commitments,
+                None,
+                &PIOPforTDiagTest::generate_h_linear_combination(),
+            )?;
+
+            ZeroOverK::<C>::verify(
+                proof.h_eq_row_m,
+                &[h_commit, row_m_commitment.clone()],
+                enforced_degree_bound,
+                &eq_vo,
+                domain_k,
+                vk,
+                &context.child(b"h_eq_row_m"),
+            )
+            .map_err(Error::from)
+        });
 
         // Step 4c: Zero over K for rowM = colM
-        ZeroOverK::<F, PC, FS>::verify(
-            proof.row_m_eq_col_m,
-            vec![row_m_commitment.clone(), col_m_commitment.clone()].as_slice(),
-            enforced_degree_bound,
-            &eq_vo,
-            domain_k,
-            vk,
-        )?;
+        report.check("row_m_eq_col_m", || {
+            ZeroOverK::<C>::verify(
+                proof.row_m_eq_col_m,
+                &[row_m_commitment.clone(), col_m_commitment.clone()],
+                enforced_degree_bound,
+                &eq_vo,
+                domain_k,
+                vk,
+                &context.child(b"row_m_eq_col_m"),
+            )
+        });
 
         // Step 5: Zero over K for valM * h2 = 0
-        let prod_vo = GenericShiftingVO::new(&[0, 1], &[F::one(), F::one()], zero_product_check)?;
-        ZeroOverK::<F, PC, FS>::verify(
-            proof.val_m_times_h2_proof,
-            vec![val_m_commitment.clone(), h_commitments[1].clone()].as_slice(),
-            enforced_degree_bound,
-            &prod_vo,
-            domain_k,
-            vk,
-        )?;
+        let prod_vo =
+            GenericShiftingVO::new(&[0, 1], &[C::F::one(), C::F::one()], zero_product_check)
+                .unwrap()
+                .with_scaling_factor(2);
+        report.check("val_m_times_h2", || {
+            ZeroOverK::<C>::verify(
+                proof.val_m_times_h2_proof,
+                &[val_m_commitment.clone(), h_commitments[1].clone()],
+                enforced_degree_bound,
+                &prod_vo,
+                domain_k,
+                vk,
+                &context.child(b"val_m_times_h2"),
+            )
+        });
 
         // Step 6: Non-zero over K for valM + h2 != 0
-        let (val_plus_h2_commit, _) = PC::aggregate_commitments(
-            &[val_m_commitment.clone(), h_commitments[1].clone()],
-            None,
-            &PIOPforTDiagTest::generate_valM_plus_h2_linear_combination(val_m_commitment.label()),
-        )?;
-
-        NonZeroOverK::<F, PC, FS>::verify(
-            vk,
-            domain_k,
-            val_plus_h2_commit.commitment().clone(),
-            enforced_degree_bound,
-            proof.val_plus_h2_proof,
-        )?;
+        report.check("val_m_plus_h2_non_zero", || {
+            let (val_plus_h2_commit, _) = C::PC::aggregate_commitments(
+                &[val_m_commitment.clone(), h_commitments[1].clone()],
+                None,
+                &PIOPforTDiagTest::generate_valM_plus_h2_linear_combination(
+                    val_m_commitment.label(),
+                ),
+            )?;
+
+            NonZeroOverK::<C>::verify(
+                vk,
+                domain_k,
+                val_plus_h2_commit.commitment().clone(),
+                enforced_degree_bound,
+                proof.val_plus_h2_proof,
+                &context.child(b"val_m_plus_h2_non_zero"),
+            )
+        });
 
-        Ok(())
+        report.finish()
     }
 }
@@ -1,4 +1,6 @@
+use crate::{domains::DomainH, indexer::PaddingStrategy};
 use ark_ff::PrimeField;
+use ark_poly::EvaluationDomain;
 use ark_poly_commit::{LinearCombination, PolynomialLabel};
 use ark_std::marker::PhantomData;
 
@@ -7,6 +9,21 @@ pub struct PIOPforTDiagTest<F: PrimeField> {
 }
 
 impl<F: PrimeField> PIOPforTDiagTest<F> {
+    /// The value taken by rowM = colM on the padding entries, i.e. the constant part of h2. The diagonal entries
+    /// are (t, t), ..., (n - 1, n - 1), so repeating the last one points to omega^(n - 1).
+    pub fn h2_padding_value(
+        padding: PaddingStrategy,
+        t: usize,
+        number_of_constraints: usize,
+        domain_h: &DomainH<F>,
+    ) -> F {
+        let last_entry = match t < number_of_constraints {
+            true => Some((number_of_constraints - 1, number_of_constraints - 1)),
+            false => None,
+        };
+        domain_h.element(padding.padding_entry(last_entry).0)
+    }
+
     pub fn generate_h_linear_combination() -> LinearCombination<F> {
         LinearCombination::new("h", vec![(F::one(), "h1"), (F::one(), "h2")])
     }
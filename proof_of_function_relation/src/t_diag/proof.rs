@@ -1,19 +1,32 @@
 use crate::{geo_seq::proof::Proof as GeoSeqProof, non_zero_over_k::proof::Proof as NonZeroProof};
-use ark_ff::PrimeField;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
 use zero_over_k::zero_over_k::proof::Proof as ZeroProof;
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Write};
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
-    pub h1_commit: PC::Commitment,
-    pub h2_commit: PC::Commitment,
-    pub h1_seq_proof: GeoSeqProof<F, PC>,
-    pub h2_seq_proof: GeoSeqProof<F, PC>,
-    pub h_eq_row_m: ZeroProof<F, PC>,
-    pub row_m_eq_col_m: ZeroProof<F, PC>,
-    pub val_m_times_h2_proof: ZeroProof<F, PC>,
-    pub val_plus_h2_proof: NonZeroProof<F, PC>,
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig> {
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h1_commit: pc::Commitment<C>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h2_commit: pc::Commitment<C>,
+    pub h1_seq_proof: GeoSeqProof<C>,
+    pub h2_seq_proof: GeoSeqProof<C>,
+    pub h_eq_row_m: ZeroProof<C>,
+    pub row_m_eq_col_m: ZeroProof<C>,
+    pub val_m_times_h2_proof: ZeroProof<C>,
+    pub val_plus_h2_proof: NonZeroProof<C>,
 }
@@ -1,19 +1,29 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test {
-    use crate::{error::Error, t_diag::TDiag, util::gen_t_diag_test_polys};
+    use crate::config::Config;
+    use crate::{
+        checked_key::CheckedCommitterKey,
+        domains::{DomainH, DomainK},
+        error::Error,
+        indexer::{PaddingStrategy, SparseMatrixEncoding},
+        t_diag::TDiag,
+        util::gen_t_diag_test_polys,
+    };
 
     use ark_bn254::{Bn254, Fr};
-    use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
-    use ark_poly_commit::PolynomialCommitment;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::{PCCommitterKey, PolynomialCommitment};
+    use ark_serialize::CanonicalSerialize;
     use ark_std::rand::thread_rng;
     use blake2::Blake2s;
-    use fiat_shamir_rng::SimpleHashFiatShamirRng;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
     use homomorphic_poly_commit::marlin_kzg::KZG10;
     use rand_chacha::ChaChaRng;
 
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
     type F = Fr;
     type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
 
     #[test]
     fn test_diag_matrix() {
@@ -22,8 +32,8 @@ mod test {
         let n = 4;
         let t = 2;
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let enforced_degree_bound = domain_k.size() + 1;
         let enforced_hiding_bound = 1;
@@ -50,8 +60,8 @@ mod test {
         )
         .unwrap();
 
-        let proof = TDiag::<F, PC, FS>::prove(
-            &ck,
+        let proof = TDiag::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
             t,
             &row_poly,
             &col_poly,
@@ -66,11 +76,25 @@ mod test {
             &domain_k,
             &domain_h,
             domain_h.size(),
+            PaddingStrategy::Origin,
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
 
-        let is_valid = TDiag::<F, PC, FS>::verify(
+        let size = TDiag::<C>::proof_size(
+            t,
+            Some(enforced_degree_bound),
+            &domain_k,
+            &domain_h,
+            domain_h.size(),
+            PaddingStrategy::Origin,
+            ck.supported_degree(),
+        )
+        .unwrap();
+        assert_eq!(size, proof.serialized_size());
+
+        let is_valid = TDiag::<C>::verify(
             &vk,
             t,
             &commitments[0],
@@ -80,7 +104,9 @@ mod test {
             &domain_h,
             &domain_k,
             domain_h.size(),
+            PaddingStrategy::Origin,
             proof,
+            &Context::new(b"test"),
         );
 
         assert!(is_valid.is_ok());
@@ -93,8 +119,8 @@ mod test {
         let n = 4;
         let t = 2;
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let enforced_degree_bound = domain_k.size() + 1;
         let enforced_hiding_bound = 1;
@@ -127,8 +153,8 @@ mod test {
         )
         .unwrap();
 
-        let proof = TDiag::<F, PC, FS>::prove(
-            &ck,
+        let proof = TDiag::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
             t,
             &row_poly,
             &col_poly,
@@ -143,13 +169,22 @@ mod test {
             &domain_k,
             &domain_h,
             domain_h.size(),
+            PaddingStrategy::Origin,
+            &Context::new(b"test"),
             rng,
         );
 
         assert!(proof.is_err());
 
         // Test for a specific error
+        #[cfg(not(feature = "strict-labels"))]
         assert_eq!(proof.err().unwrap(), Error::FEvalIsZero);
+
+        // val is row under the same label, which the strict label checks reject first
+        #[cfg(feature = "strict-labels")]
+        assert!(
+            matches!(proof.err().unwrap(), Error::ZeroOverKError(error) if error.starts_with("LabelMismatch"))
+        );
     }
 
     #[test]
@@ -159,8 +194,8 @@ mod test {
         let n = 4;
         let t = 2;
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let enforced_degree_bound = domain_k.size() + 1;
         let other_degree_bound = domain_k.size() + 5;
@@ -194,8 +229,8 @@ mod test {
         )
         .unwrap();
 
-        let proof = TDiag::<F, PC, FS>::prove(
-            &ck,
+        let proof = TDiag::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(other_degree_bound)).unwrap(),
             t,
             &row_poly,
             &col_poly,
@@ -210,11 +245,13 @@ mod test {
             &domain_k,
             &domain_h,
             domain_h.size(),
+            PaddingStrategy::Origin,
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
 
-        let is_valid = TDiag::<F, PC, FS>::verify(
+        let is_valid = TDiag::<C>::verify(
             &vk,
             t,
             &commitments[0],
@@ -224,11 +261,151 @@ mod test {
             &domain_h,
             &domain_k,
             domain_h.size(),
+            PaddingStrategy::Origin,
             proof,
+            &Context::new(b"test"),
         );
 
         assert!(is_valid.is_err());
 
         assert_eq!(is_valid.err().unwrap(), Error::BatchCheckError)
     }
+
+    /// Prove that the diagonal matrix with entries (2, 2) and (3, 3) encoded with `encoding_padding` is 2-diagonal,
+    /// and verify the proof under `statement_padding`
+    fn prove_and_verify_diag(
+        encoding_padding: PaddingStrategy,
+        statement_padding: PaddingStrategy,
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let t = 2;
+
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let entries = [(2, 2, F::from(2u64)), (3, 3, F::from(2u64))];
+        let encoding =
+            SparseMatrixEncoding::from_entries(&entries, &domain_h, &domain_k, encoding_padding)
+                .unwrap()
+                .with_bounds("c", Some(enforced_degree_bound), Some(1));
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) = PC::commit(
+            &ck,
+            &[
+                encoding.row.clone(),
+                encoding.col.clone(),
+                encoding.val.clone(),
+            ],
+            Some(rng),
+        )
+        .unwrap();
+
+        let proof = TDiag::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+            t,
+            &encoding.row,
+            &encoding.col,
+            &encoding.val,
+            &commitments[0],
+            &commitments[1],
+            &commitments[2],
+            &rands[0],
+            &rands[1],
+            &rands[2],
+            Some(enforced_degree_bound),
+            &domain_k,
+            &domain_h,
+            domain_h.size(),
+            encoding_padding,
+            &Context::new(b"test"),
+            rng,
+        )?;
+
+        TDiag::<C>::verify(
+            &vk,
+            t,
+            &commitments[0],
+            &commitments[1],
+            &commitments[2],
+            Some(enforced_degree_bound),
+            &domain_h,
+            &domain_k,
+            domain_h.size(),
+            statement_padding,
+            proof,
+            &Context::new(b"test"),
+        )
+    }
+
+    #[test]
+    fn test_reject_number_of_constraints_out_of_range() {
+        let rng = &mut thread_rng();
+        let t = 2;
+
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let polys = gen_t_diag_test_polys(domain_k, domain_h, Some(enforced_degree_bound), Some(1));
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+        let (commitments, rands) = PC::commit(&ck, &polys, Some(rng)).unwrap();
+
+        let mut prove = |number_of_constraints| {
+            TDiag::<C>::prove(
+                &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+                t,
+                &polys[0],
+                &polys[1],
+                &polys[2],
+                &commitments[0],
+                &commitments[1],
+                &commitments[2],
+                &rands[0],
+                &rands[1],
+                &rands[2],
+                Some(enforced_degree_bound),
+                &domain_k,
+                &domain_h,
+                number_of_constraints,
+                PaddingStrategy::Origin,
+                &Context::new(b"test"),
+                rng,
+            )
+        };
+
+        // fewer rows than t
+        assert_eq!(prove(1).err(), Some(Error::T2Large));
+
+        // more rows from t on than K has elements
+        assert!(matches!(
+            prove(domain_k.size() + t + 1),
+            Err(Error::InvalidSequenceDescription(_))
+        ));
+    }
+
+    #[test]
+    fn test_diag_matrix_padding_strategies() {
+        for padding in [PaddingStrategy::RepeatLast, PaddingStrategy::Origin] {
+            assert_eq!(prove_and_verify_diag(padding, padding), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_reject_padding_mismatch() {
+        assert!(
+            prove_and_verify_diag(PaddingStrategy::RepeatLast, PaddingStrategy::Origin).is_err()
+        );
+        assert!(
+            prove_and_verify_diag(PaddingStrategy::Origin, PaddingStrategy::RepeatLast).is_err()
+        );
+    }
 }
@@ -1,74 +1,264 @@
 use crate::{
-    error::Error, t_diag::TDiag, t_functional_triple::proof::Proof,
+    domains::{DomainH, DomainK},
+    error::Error,
+    indexer::PaddingStrategy,
+    pre_verified::{sub_proof_digest, PreVerified},
+    report::VerificationReport,
+    t_diag::TDiag,
+    t_functional_triple::proof::{AmortizedProof, Proof},
     t_strictly_lower_triangular_test::TStrictlyLowerTriangular,
 };
-use ark_poly::{univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
-use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
-use rand::Rng;
+use ark_poly::EvaluationDomain;
+use ark_poly_commit::LabeledCommitment;
 use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+#[cfg(feature = "prover")]
+use {
+    crate::checked_key::CheckedCommitterKey, ark_poly::univariate::DensePolynomial,
+    ark_poly_commit::LabeledPolynomial, rand::Rng,
+};
 
-use ark_ff::{PrimeField, SquareRootField};
+use ark_ff::{to_bytes, SquareRootField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use fiat_shamir_rng::FiatShamirRng;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use blake2::{Blake2s, Digest};
+use fiat_shamir_rng::{Absorb, Context};
+use homomorphic_poly_commit::LENGTH_PREFIX_SIZE;
 use std::io::BufReader;
+use zero_over_k::proof_shape::ProofShape;
+use zero_over_k::trace_span;
 
 pub mod proof;
 // mod tests;
 
-pub struct TFT<F: PrimeField + SquareRootField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng>
+pub struct TFT<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
 {
-    _field: PhantomData<F>,
-    _pc: PhantomData<PC>,
-    _digest: PhantomData<FS>,
+    _config: PhantomData<C>,
 }
 
-impl<F, PC, FS> TFT<F, PC, FS>
+impl<C: ProtocolConfig> TFT<C>
 where
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
+    C::F: SquareRootField,
 {
     pub const PROTOCOL_NAME: &'static [u8] = b"t-FT Test";
 
+    /// Version of the proof format, bound into the statement digest
+    pub const PROTOCOL_VERSION: u64 = 1;
+
+    /// Padding of the A and B encodings produced by the Marlin indexer
+    pub const AB_PADDING: PaddingStrategy = PaddingStrategy::RepeatLast;
+    /// Padding of the C encoding produced by the Marlin indexer, which treats C as a diagonal matrix
+    pub const C_PADDING: PaddingStrategy = PaddingStrategy::Origin;
+
+    /// Hash of the full public statement: the protocol version, t, the domains, the enforced degree bound and the
+    /// commitments of A, B and C in the order they are passed to `prove`. It is absorbed into the transcript and embedded in the proof, so that a proof cannot be replayed
+    /// against a different statement of the same shape.
+    pub fn statement_digest(
+        t: usize,
+        domain_h: &DomainH<C::F>,
+        domain_k: &DomainK<C::F>,
+        enforced_degree_bound: Option<usize>,
+        commitments: &[&LabeledCommitment<pc::Commitment<C>>],
+    ) -> Result<Vec<u8>, Error> {
+        let mut statement_bytes = to_bytes![
+            &Self::PROTOCOL_NAME,
+            Self::PROTOCOL_VERSION,
+            t as u64,
+            domain_h.size() as u64,
+            domain_h.element(1),
+            domain_k.size() as u64,
+            domain_k.element(1),
+            enforced_degree_bound.is_some(),
+            enforced_degree_bound.unwrap_or(0) as u64,
+            commitments.len() as u64
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        for commitment in commitments {
+            statement_bytes.extend(to_bytes![commitment].map_err(|_| Error::ToBytesError)?);
+        }
+
+        Ok(Blake2s::digest(&statement_bytes).to_vec())
+    }
+
+    /// The commitments and queries of a proof for row t over K and H, without computing it
+    pub fn prove_dry_run(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<ProofShape, Error> {
+        let mut shape = ProofShape::new();
+        for context in ["a_slt", "b_slt"] {
+            shape.extend(
+                context,
+                TStrictlyLowerTriangular::<C>::prove_dry_run(
+                    t,
+                    domain_k,
+                    domain_h,
+                    enforced_degree_bound,
+                    Self::AB_PADDING,
+                )?,
+            );
+        }
+        shape.extend(
+            "c_diag",
+            TDiag::<C>::prove_dry_run(
+                t,
+                enforced_degree_bound,
+                domain_k,
+                domain_h,
+                domain_h.size(),
+                Self::C_PADDING,
+            )?,
+        );
+        Ok(shape)
+    }
+
+    /// Serialized size in bytes of a proof for row t over K and H, with keys of degree `supported_degree`
+    pub fn proof_size(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        let slt_size = TStrictlyLowerTriangular::<C>::proof_size(
+            t,
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            Self::AB_PADDING,
+            supported_degree,
+        )?;
+        Ok(Self::statement_digest_size()
+            + 2 * slt_size
+            + Self::c_diag_proof_size(
+                t,
+                domain_k,
+                domain_h,
+                enforced_degree_bound,
+                supported_degree,
+            )?)
+    }
+
+    /// Serialized size in bytes of an amortized proof for row t over K and H, with keys of degree `supported_degree`
+    pub fn amortized_proof_size(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        let shared_h_size = TStrictlyLowerTriangular::<C>::shared_h_size(
+            t,
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            supported_degree,
+        )?;
+        let instance_size = TStrictlyLowerTriangular::<C>::instance_proof_size(
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            supported_degree,
+        )?;
+        Ok(Self::statement_digest_size()
+            + shared_h_size
+            + 2 * instance_size
+            + Self::c_diag_proof_size(
+                t,
+                domain_k,
+                domain_h,
+                enforced_degree_bound,
+                supported_degree,
+            )?)
+    }
+
+    fn statement_digest_size() -> usize {
+        LENGTH_PREFIX_SIZE + <Blake2s as Digest>::output_size()
+    }
+
+    fn c_diag_proof_size(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        TDiag::<C>::proof_size(
+            t,
+            enforced_degree_bound,
+            domain_k,
+            domain_h,
+            domain_h.size(),
+            Self::C_PADDING,
+            supported_degree,
+        )
+    }
+
     // TODO: change to use ark-marlin Index. (wait for a new release?)
+    #[cfg(feature = "prover")]
     pub fn prove<R: Rng>(
-        ck: &PC::CommitterKey,
+        ck: &CheckedCommitterKey<C, Self>,
         t: usize,
-        domain_k: &GeneralEvaluationDomain<F>,
-        domain_h: &GeneralEvaluationDomain<F>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
         enforced_degree_bound: Option<usize>,
         // a
-        row_a_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        col_a_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        row_a_commit: &LabeledCommitment<PC::Commitment>,
-        col_a_commit: &LabeledCommitment<PC::Commitment>,
-        row_a_random: &PC::Randomness,
-        col_a_random: &PC::Randomness,
+        row_a_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_a_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_a_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_a_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_a_random: &pc::Randomness<C>,
+        col_a_random: &pc::Randomness<C>,
         // b
-        row_b_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        col_b_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        row_b_commit: &LabeledCommitment<PC::Commitment>,
-        col_b_commit: &LabeledCommitment<PC::Commitment>,
-        row_b_random: &PC::Randomness,
-        col_b_random: &PC::Randomness,
+        row_b_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_b_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_b_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_b_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_b_random: &pc::Randomness<C>,
+        col_b_random: &pc::Randomness<C>,
         // c
-        row_c_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        col_c_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        val_c_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        row_c_commit: &LabeledCommitment<PC::Commitment>,
-        col_c_commit: &LabeledCommitment<PC::Commitment>,
-        val_c_commit: &LabeledCommitment<PC::Commitment>,
-        row_c_random: &PC::Randomness,
-        col_c_random: &PC::Randomness,
-        val_c_random: &PC::Randomness,
+        row_c_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_c_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        val_c_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_c_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_c_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_c_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_c_random: &pc::Randomness<C>,
+        col_c_random: &pc::Randomness<C>,
+        val_c_random: &pc::Randomness<C>,
+        context: &Context,
         //rands
-        fs_rng: &mut FS,
+        fs_rng: &mut C::FS,
         rng: &mut R,
     ) -> Result<Vec<u8>, Error> {
+        let _span = trace_span!("t_functional_triple::prove");
+        ck.check_bound(enforced_degree_bound)?;
+        let statement_digest = Self::statement_digest(
+            t,
+            domain_h,
+            domain_k,
+            enforced_degree_bound,
+            &[
+                row_a_commit,
+                col_a_commit,
+                row_b_commit,
+                col_b_commit,
+                row_c_commit,
+                col_c_commit,
+                val_c_commit,
+            ],
+        )?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement_digest]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
         // 1. t-SLT test on A
-        let a_slt_proof = TStrictlyLowerTriangular::<F, PC, FS>::prove(
-            ck,
+        let a_slt_proof = TStrictlyLowerTriangular::<C>::prove(
+            &ck.sub_protocol(),
             t,
             domain_k,
             domain_h,
@@ -79,13 +269,15 @@ where
             col_a_commit,
             col_a_random,
             enforced_degree_bound,
+            Self::AB_PADDING,
+            &context.child(b"a_slt"),
             fs_rng,
             rng,
         )?;
 
         // 2. t-SLT test on B
-        let b_slt_proof = TStrictlyLowerTriangular::<F, PC, FS>::prove(
-            ck,
+        let b_slt_proof = TStrictlyLowerTriangular::<C>::prove(
+            &ck.sub_protocol(),
             t,
             domain_k,
             domain_h,
@@ -96,13 +288,15 @@ where
             col_b_commit,
             col_b_random,
             enforced_degree_bound,
+            Self::AB_PADDING,
+            &context.child(b"b_slt"),
             fs_rng,
             rng,
         )?;
 
         // 3. t-Diag test on C
-        let c_diag_proof = TDiag::<F, PC, FS>::prove(
-            ck,
+        let c_diag_proof = TDiag::<C>::prove(
+            &ck.sub_protocol(),
             t,
             row_c_poly,
             col_c_poly,
@@ -117,10 +311,13 @@ where
             domain_k,
             domain_h,
             domain_h.size(),
+            Self::C_PADDING,
+            &context.child(b"c_diag"),
             rng,
         )?;
 
         let proof = Proof {
+            statement_digest,
             a_slt_proof,
             b_slt_proof,
             c_diag_proof,
@@ -135,27 +332,215 @@ where
         Ok(Vec::from(writer.as_slice()))
     }
 
-    pub fn verify(
-        vk: &PC::VerifierKey,
-        ck: &PC::CommitterKey,
+    #[cfg(feature = "prover")]
+    /// As [`Self::prove`], but with a single h and geometric sequence test for the t-SLT tests on A and B, which is
+    /// proven under its own context and absorbed into the transcript of both tests
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_amortized<R: Rng>(
+        ck: &CheckedCommitterKey<C, Self>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        // a
+        row_a_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_a_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_a_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_a_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_a_random: &pc::Randomness<C>,
+        col_a_random: &pc::Randomness<C>,
+        // b
+        row_b_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_b_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_b_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_b_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_b_random: &pc::Randomness<C>,
+        col_b_random: &pc::Randomness<C>,
+        // c
+        row_c_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_c_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        val_c_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_c_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_c_commit: &LabeledCommitment<pc::Commitment<C>>,
+        val_c_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_c_random: &pc::Randomness<C>,
+        col_c_random: &pc::Randomness<C>,
+        val_c_random: &pc::Randomness<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error> {
+        let _span = trace_span!("t_functional_triple::prove_amortized");
+        ck.check_bound(enforced_degree_bound)?;
+        let statement_digest = Self::statement_digest(
+            t,
+            domain_h,
+            domain_k,
+            enforced_degree_bound,
+            &[
+                row_a_commit,
+                col_a_commit,
+                row_b_commit,
+                col_b_commit,
+                row_c_commit,
+                col_c_commit,
+                val_c_commit,
+            ],
+        )?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement_digest]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        // 1. h and its geometric sequence test, shared by the t-SLT tests on A and B
+        let shared_h = TStrictlyLowerTriangular::<C>::prove_shared_h(
+            &ck.sub_protocol(),
+            t,
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            &context.child(b"shared_h"),
+            rng,
+        )?;
+
+        // 2. t-SLT tests on A and B
+        let a_slt_proof = TStrictlyLowerTriangular::<C>::prove_with_shared_h(
+            &ck.sub_protocol(),
+            t,
+            domain_k,
+            domain_h,
+            row_a_poly,
+            row_a_commit,
+            row_a_random,
+            col_a_poly,
+            col_a_commit,
+            col_a_random,
+            enforced_degree_bound,
+            Self::AB_PADDING,
+            &shared_h,
+            &context.child(b"a_slt"),
+            fs_rng,
+            rng,
+        )?;
+        let b_slt_proof = TStrictlyLowerTriangular::<C>::prove_with_shared_h(
+            &ck.sub_protocol(),
+            t,
+            domain_k,
+            domain_h,
+            row_b_poly,
+            row_b_commit,
+            row_b_random,
+            col_b_poly,
+            col_b_commit,
+            col_b_random,
+            enforced_degree_bound,
+            Self::AB_PADDING,
+            &shared_h,
+            &context.child(b"b_slt"),
+            fs_rng,
+            rng,
+        )?;
+
+        // 3. t-Diag test on C
+        let c_diag_proof = TDiag::<C>::prove(
+            &ck.sub_protocol(),
+            t,
+            row_c_poly,
+            col_c_poly,
+            val_c_poly,
+            row_c_commit,
+            col_c_commit,
+            val_c_commit,
+            row_c_random,
+            col_c_random,
+            val_c_random,
+            enforced_degree_bound,
+            domain_k,
+            domain_h,
+            domain_h.size(),
+            Self::C_PADDING,
+            &context.child(b"c_diag"),
+            rng,
+        )?;
+
+        let proof = AmortizedProof {
+            statement_digest,
+            shared_h,
+            a_slt_proof,
+            b_slt_proof,
+            c_diag_proof,
+        };
+
+        let mut writer = Vec::<u8>::new();
+        proof
+            .serialize(&mut writer)
+            .map_err(|_| Error::ProofSerializationError)?;
+
+        Ok(writer)
+    }
+
+    /// Verify a proof created with [`Self::prove_amortized`], checking the shared h once
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_amortized(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
         t: usize,
-        row_a_commitment: &LabeledCommitment<PC::Commitment>,
-        col_a_commitment: &LabeledCommitment<PC::Commitment>,
-        row_b_commitment: &LabeledCommitment<PC::Commitment>,
-        col_b_commitment: &LabeledCommitment<PC::Commitment>,
-        row_c_commitment: &LabeledCommitment<PC::Commitment>,
-        col_c_commitment: &LabeledCommitment<PC::Commitment>,
-        val_c_commitment: &LabeledCommitment<PC::Commitment>,
+        row_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        val_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
         enforced_degree_bound: Option<usize>,
-        domain_h: &GeneralEvaluationDomain<F>,
-        domain_k: &GeneralEvaluationDomain<F>,
+        domain_h: &DomainH<C::F>,
+        domain_k: &DomainK<C::F>,
         proof_bytes: Vec<u8>,
-        fs_rng: &mut FS,
-    ) -> Result<(), Error> {
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("t_functional_triple::verify_amortized");
+        let mut report = VerificationReport::builder("t_functional_triple");
+
         let reader = BufReader::new(proof_bytes.as_slice());
-        let proof: Proof<F, PC> = Proof::<F, PC>::deserialize(reader).unwrap();
+        let proof = match AmortizedProof::<C>::deserialize(reader) {
+            Ok(proof) => proof,
+            Err(_) => {
+                report.check("deserialize", || Err(Error::ProofDeserializationError));
+                return report.finish();
+            }
+        };
+
+        let statement_digest = Self::statement_digest(
+            t,
+            domain_h,
+            domain_k,
+            enforced_degree_bound,
+            &[
+                row_a_commitment,
+                col_a_commitment,
+                row_b_commitment,
+                col_b_commitment,
+                row_c_commitment,
+                col_c_commitment,
+                val_c_commitment,
+            ],
+        );
+
+        report.check("statement_digest", || match &statement_digest {
+            Ok(digest) if *digest == proof.statement_digest => Ok(()),
+            Ok(_) => Err(Error::StatementMismatch),
+            Err(error) => Err(error.clone()),
+        });
 
-        TStrictlyLowerTriangular::<F, PC, FS>::verify(
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement_digest.clone()?]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        report.add(TStrictlyLowerTriangular::<C>::verify_with_shared_h(
             vk,
             ck,
             t,
@@ -164,11 +549,13 @@ where
             row_a_commitment,
             col_a_commitment,
             enforced_degree_bound,
+            Self::AB_PADDING,
+            &proof.shared_h,
             proof.a_slt_proof,
+            &context.child(b"a_slt"),
             fs_rng,
-        )?;
-
-        TStrictlyLowerTriangular::<F, PC, FS>::verify(
+        ));
+        report.add(TStrictlyLowerTriangular::<C>::verify_with_shared_h(
             vk,
             ck,
             t,
@@ -177,11 +564,25 @@ where
             row_b_commitment,
             col_b_commitment,
             enforced_degree_bound,
+            Self::AB_PADDING,
+            &proof.shared_h,
             proof.b_slt_proof,
+            &context.child(b"b_slt"),
             fs_rng,
-        )?;
+        ));
+        report.check("shared_h", || {
+            TStrictlyLowerTriangular::<C>::verify_shared_h(
+                vk,
+                t,
+                domain_k,
+                domain_h,
+                enforced_degree_bound,
+                proof.shared_h,
+                &context.child(b"shared_h"),
+            )
+        });
 
-        TDiag::<F, PC, FS>::verify(
+        report.add(TDiag::<C>::verify_with_report(
             vk,
             t,
             row_c_commitment,
@@ -191,9 +592,324 @@ where
             domain_h,
             domain_k,
             domain_h.size(),
+            Self::C_PADDING,
             proof.c_diag_proof,
-        )?;
+            &context.child(b"c_diag"),
+        ));
+
+        report.finish()
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        row_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        val_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        domain_h: &DomainH<C::F>,
+        domain_k: &DomainK<C::F>,
+        proof_bytes: Vec<u8>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(
+            vk,
+            ck,
+            t,
+            row_a_commitment,
+            col_a_commitment,
+            row_b_commitment,
+            col_b_commitment,
+            row_c_commitment,
+            col_c_commitment,
+            val_c_commitment,
+            enforced_degree_bound,
+            domain_h,
+            domain_k,
+            proof_bytes,
+            context,
+            fs_rng,
+        )
+        .into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        row_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        val_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        domain_h: &DomainH<C::F>,
+        domain_k: &DomainK<C::F>,
+        proof_bytes: Vec<u8>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        Self::verify_with_pre_verified(
+            vk,
+            ck,
+            t,
+            row_a_commitment,
+            col_a_commitment,
+            row_b_commitment,
+            col_b_commitment,
+            row_c_commitment,
+            col_c_commitment,
+            val_c_commitment,
+            enforced_degree_bound,
+            domain_h,
+            domain_k,
+            proof_bytes,
+            &PreVerified::new(),
+            context,
+            fs_rng,
+        )
+    }
+
+    /// Digests of the t-SLT tests on A and B and of the t-Diag test on C of a proof created under `context`, in this
+    /// order, as [`Self::verify_with_pre_verified`] looks them up
+    pub fn sub_proof_digests(proof_bytes: &[u8], context: &Context) -> Result<[Vec<u8>; 3], Error> {
+        let proof = Proof::<C>::deserialize(BufReader::new(proof_bytes))
+            .map_err(|_| Error::ProofDeserializationError)?;
+
+        Ok([
+            sub_proof_digest(
+                TStrictlyLowerTriangular::<C>::PROTOCOL_NAME,
+                &proof.statement_digest,
+                &context.child(b"a_slt"),
+                &proof.a_slt_proof,
+            )?,
+            sub_proof_digest(
+                TStrictlyLowerTriangular::<C>::PROTOCOL_NAME,
+                &proof.statement_digest,
+                &context.child(b"b_slt"),
+                &proof.b_slt_proof,
+            )?,
+            sub_proof_digest(
+                TDiag::<C>::PROTOCOL_NAME,
+                &proof.statement_digest,
+                &context.child(b"c_diag"),
+                &proof.c_diag_proof,
+            )?,
+        ])
+    }
+
+    /// Verify the proof and report on each sub-protocol, skipping the checks of the sub-proofs whose digest is in
+    /// `pre_verified`. The transcript of a skipped sub-proof is still replayed. Sub-proof digests are matched against
+    /// the statement of the verifier, so that a digest is only honoured for the statement it was computed for.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_pre_verified(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        row_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_a_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_b_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        row_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        col_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        val_c_commitment: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        domain_h: &DomainH<C::F>,
+        domain_k: &DomainK<C::F>,
+        proof_bytes: Vec<u8>,
+        pre_verified: &PreVerified,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("t_functional_triple::verify");
+        let mut report = VerificationReport::builder("t_functional_triple");
+
+        let reader = BufReader::new(proof_bytes.as_slice());
+        let proof = match Proof::<C>::deserialize(reader) {
+            Ok(proof) => proof,
+            Err(_) => {
+                report.check("deserialize", || Err(Error::ProofDeserializationError));
+                return report.finish();
+            }
+        };
+
+        let statement_digest = Self::statement_digest(
+            t,
+            domain_h,
+            domain_k,
+            enforced_degree_bound,
+            &[
+                row_a_commitment,
+                col_a_commitment,
+                row_b_commitment,
+                col_b_commitment,
+                row_c_commitment,
+                col_c_commitment,
+                val_c_commitment,
+            ],
+        );
+
+        report.check("statement_digest", || match &statement_digest {
+            Ok(digest) if *digest == proof.statement_digest => Ok(()),
+            Ok(_) => Err(Error::StatementMismatch),
+            Err(error) => Err(error.clone()),
+        });
+
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement_digest.clone()?]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        let a_context = context.child(b"a_slt");
+        if Self::is_pre_verified(
+            pre_verified,
+            TStrictlyLowerTriangular::<C>::PROTOCOL_NAME,
+            &statement_digest,
+            &a_context,
+            &proof.a_slt_proof,
+        ) {
+            report.add(Self::pre_verified_report(
+                "t_strictly_lower_triangular_test",
+                || {
+                    TStrictlyLowerTriangular::<C>::replay_transcript(
+                        ck,
+                        t,
+                        domain_k,
+                        row_a_commitment,
+                        col_a_commitment,
+                        enforced_degree_bound,
+                        Self::AB_PADDING,
+                        &proof.a_slt_proof,
+                        &a_context,
+                        fs_rng,
+                    )
+                },
+            ));
+        } else {
+            report.add(TStrictlyLowerTriangular::<C>::verify_with_report(
+                vk,
+                ck,
+                t,
+                domain_k,
+                domain_h,
+                row_a_commitment,
+                col_a_commitment,
+                enforced_degree_bound,
+                Self::AB_PADDING,
+                proof.a_slt_proof,
+                &a_context,
+                fs_rng,
+            ));
+        }
+
+        let b_context = context.child(b"b_slt");
+        if Self::is_pre_verified(
+            pre_verified,
+            TStrictlyLowerTriangular::<C>::PROTOCOL_NAME,
+            &statement_digest,
+            &b_context,
+            &proof.b_slt_proof,
+        ) {
+            report.add(Self::pre_verified_report(
+                "t_strictly_lower_triangular_test",
+                || {
+                    TStrictlyLowerTriangular::<C>::replay_transcript(
+                        ck,
+                        t,
+                        domain_k,
+                        row_b_commitment,
+                        col_b_commitment,
+                        enforced_degree_bound,
+                        Self::AB_PADDING,
+                        &proof.b_slt_proof,
+                        &b_context,
+                        fs_rng,
+                    )
+                },
+            ));
+        } else {
+            report.add(TStrictlyLowerTriangular::<C>::verify_with_report(
+                vk,
+                ck,
+                t,
+                domain_k,
+                domain_h,
+                row_b_commitment,
+                col_b_commitment,
+                enforced_degree_bound,
+                Self::AB_PADDING,
+                proof.b_slt_proof,
+                &b_context,
+                fs_rng,
+            ));
+        }
+
+        // the t-Diag test does not share the transcript of the other sub-proofs, so there is nothing to replay
+        let c_context = context.child(b"c_diag");
+        if Self::is_pre_verified(
+            pre_verified,
+            TDiag::<C>::PROTOCOL_NAME,
+            &statement_digest,
+            &c_context,
+            &proof.c_diag_proof,
+        ) {
+            report.add(Self::pre_verified_report("t_diag", || Ok(())));
+        } else {
+            report.add(TDiag::<C>::verify_with_report(
+                vk,
+                t,
+                row_c_commitment,
+                col_c_commitment,
+                val_c_commitment,
+                enforced_degree_bound,
+                domain_h,
+                domain_k,
+                domain_h.size(),
+                Self::C_PADDING,
+                proof.c_diag_proof,
+                &c_context,
+            ));
+        }
+
+        report.finish()
+    }
+
+    fn is_pre_verified<P: CanonicalSerialize>(
+        pre_verified: &PreVerified,
+        protocol_name: &[u8],
+        statement_digest: &Result<Vec<u8>, Error>,
+        context: &Context,
+        sub_proof: &P,
+    ) -> bool {
+        match statement_digest {
+            Ok(statement_digest) if !pre_verified.is_empty() => {
+                sub_proof_digest(protocol_name, statement_digest, context, sub_proof)
+                    .map(|digest| pre_verified.contains(&digest))
+                    .unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
 
-        Ok(())
+    /// The report of a sub-proof verified elsewhere, of which only the transcript is replayed
+    fn pre_verified_report(
+        name: &str,
+        replay_transcript: impl FnOnce() -> Result<(), Error>,
+    ) -> VerificationReport {
+        let mut report = VerificationReport::builder(name);
+        report.check("pre_verified", || Ok::<(), Error>(()));
+        report.check("transcript", replay_transcript);
+        report.finish()
     }
 }
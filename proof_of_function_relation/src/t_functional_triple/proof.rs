@@ -1,14 +1,59 @@
 use crate::{
-    t_diag::proof::Proof as TDiagProof, t_strictly_lower_triangular_test::proof::Proof as TSLTProof,
+    t_diag::proof::Proof as TDiagProof,
+    t_strictly_lower_triangular_test::proof::{InstanceProof, Proof as TSLTProof, SharedH},
 };
-use ark_ff::{PrimeField, SquareRootField};
+use ark_ff::SquareRootField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Write};
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F: PrimeField + SquareRootField, PC: AdditivelyHomomorphicPCS<F>> {
-    pub a_slt_proof: TSLTProof<F, PC>,
-    pub b_slt_proof: TSLTProof<F, PC>,
-    pub c_diag_proof: TDiagProof<F, PC>,
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    /// Digest of the statement the proof was created for
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub statement_digest: Vec<u8>,
+    pub a_slt_proof: TSLTProof<C>,
+    pub b_slt_proof: TSLTProof<C>,
+    pub c_diag_proof: TDiagProof<C>,
+}
+
+/// A proof whose t-SLT tests on A and B reference the same h, since it only depends on t and the domains
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct AmortizedProof<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    /// Digest of the statement the proof was created for
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub statement_digest: Vec<u8>,
+    pub shared_h: SharedH<C>,
+    pub a_slt_proof: InstanceProof<C>,
+    pub b_slt_proof: InstanceProof<C>,
+    pub c_diag_proof: TDiagProof<C>,
 }
@@ -1,4 +1,4 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test {
     use crate::{error::Error, t_functional_triple::TFT, util::gen_t_diag_test_polys};
 
@@ -1,96 +1,268 @@
 use crate::{
-    discrete_log_comparison::DLComparison,
-    error::{to_pc_error, Error},
+    discrete_log_comparison::{piop::PIOPforDLComparison, DLComparison},
+    domains::{DomainH, DomainK},
+    error::{Error, WitnessError},
+    geo_seq::proof::Proof as GeoSeqProof,
     geo_seq::GeoSeqTest,
-    subset_over_k::SubsetOverK,
-    t_strictly_lower_triangular_test::proof::Proof,
-    util::generate_sequence,
-};
-use ark_ff::{to_bytes, PrimeField, SquareRootField};
-use ark_poly::{
-    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    indexer::PaddingStrategy,
+    report::{ReportBuilder, VerificationReport},
+    subset_over_k::{proof::Proof as SubsetProof, SubsetOverK, Table},
+    t_strictly_lower_triangular_test::proof::{InstanceProof, Proof, SharedH},
+    util::{padded_sequence, rows_from_t},
 };
+use ark_ff::{to_bytes, SquareRootField, Zero};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain};
+#[cfg(feature = "prover")]
+use ark_poly_commit::PolynomialCommitment;
 use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
-use fiat_shamir_rng::FiatShamirRng;
+use ark_serialize::CanonicalSerialize;
+use fiat_shamir_rng::{Absorb, Context};
 use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
-use rand::Rng;
 use std::marker::PhantomData;
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::proof_shape::ProofShape;
+use zero_over_k::trace_span;
+#[cfg(feature = "prover")]
+use {
+    crate::{checked_key::CheckedCommitterKey, error::to_pc_error, util::interpolate_sequence},
+    rand::Rng,
+    zero_over_k::cancellation::CancellationToken,
+};
 
 pub mod proof;
 mod tests;
 
-pub struct TStrictlyLowerTriangular<
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
-> {
-    _field: PhantomData<F>,
-    _pc: PhantomData<PC>,
-    _fs: PhantomData<FS>,
+pub struct TStrictlyLowerTriangular<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    _config: PhantomData<C>,
 }
 
-impl<F, PC, FS> TStrictlyLowerTriangular<F, PC, FS>
+impl<C: ProtocolConfig> TStrictlyLowerTriangular<C>
 where
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
-    FS: FiatShamirRng,
+    C::F: SquareRootField,
 {
     pub const PROTOCOL_NAME: &'static [u8] = b"t-Strictly Lower Triangular Test";
 
-    pub fn prove<R: Rng>(
-        ck: &PC::CommitterKey,
-        t: usize,
-        domain_k: &GeneralEvaluationDomain<F>,
-        domain_h: &GeneralEvaluationDomain<F>,
-        row_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        row_commit: &LabeledCommitment<PC::Commitment>,
-        row_random: &PC::Randomness,
-        col_poly: &LabeledPolynomial<F, DensePolynomial<F>>,
-        col_commit: &LabeledCommitment<PC::Commitment>,
-        col_random: &PC::Randomness,
-        enforced_degree_bound: Option<usize>,
-        fs_rng: &mut FS,
-        rng: &mut R,
-    ) -> Result<Proof<F, PC>, Error> {
-        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME].map_err(|_| Error::ToBytesError)?;
-        fs_rng.absorb(fs_bytes);
+    /// Label under which an instance proven against a shared h absorbs it
+    pub const SHARED_H_LABEL: &'static [u8] = b"shared h";
 
-        let r = domain_h.element(1);
+    /// Check that every entry lies at or below row t and strictly below the diagonal, failing on the first
+    /// offending element of K before any work is done. With the `validate-witness` feature the prover runs this check
+    /// itself.
+    pub fn validate_witness(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+    ) -> Result<(), Error> {
+        if t > domain_h.size() {
+            return Err(Error::T2Large);
+        }
+
+        let row_exponents = PIOPforDLComparison::exponents(domain_k, domain_h, row_poly)?;
+        if let Some((index, &row_exponent)) = row_exponents
+            .iter()
+            .enumerate()
+            .find(|(_, &row_exponent)| row_exponent < t)
+        {
+            return Err(Error::InvalidWitness(WitnessError::RowBelowT {
+                index,
+                row_exponent,
+                t,
+            }));
+        }
+
+        DLComparison::<C>::validate_witness(domain_k, domain_h, row_poly, col_poly)
+    }
 
+    /// The commitments and queries of a proof for row t over K and H, without computing it
+    pub fn prove_dry_run(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        padding: PaddingStrategy,
+    ) -> Result<ProofShape, Error> {
+        Self::check_padding(padding)?;
         if t > domain_h.size() {
             return Err(Error::T2Large);
         }
+        let (a_s, c_s) = padded_sequence(
+            domain_h.element(t),
+            rows_from_t(t, domain_h.size())?,
+            C::F::zero,
+            domain_k,
+        )?;
 
-        // Step 1: interpolate h
-        let mut a_s = vec![domain_h.element(t)];
-        let mut c_s = vec![domain_h.size() - t];
+        let mut shape = ProofShape::new();
+        shape.commit("h", enforced_degree_bound);
+        shape.extend(
+            "h_geo_seq",
+            GeoSeqTest::<C>::prove_dry_run(
+                domain_h.element(1),
+                "h",
+                enforced_degree_bound,
+                &a_s,
+                &c_s,
+                domain_k,
+            )?,
+        );
+        shape.extend(
+            "row_subset",
+            SubsetOverK::<C>::prove_dry_run(domain_k, Table::Values, enforced_degree_bound)?,
+        );
+        shape.extend(
+            "discrete_log_comparison",
+            DLComparison::<C>::prove_dry_run(domain_k, domain_h, enforced_degree_bound)?,
+        );
+        Ok(shape)
+    }
+
+    /// Serialized size in bytes of a proof for row t over K and H, with keys of degree `supported_degree`
+    pub fn proof_size(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        padding: PaddingStrategy,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        Self::check_padding(padding)?;
+        Ok(Self::shared_h_size(
+            t,
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            supported_degree,
+        )? + Self::instance_proof_size(
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            supported_degree,
+        )?)
+    }
 
-        let to_pad = domain_k.size() - (domain_h.size() - t);
-        if to_pad > 0 {
-            a_s.push(F::zero());
-            c_s.push(to_pad);
+    /// Serialized size in bytes of a [`SharedH`] for row t over K and H, with keys of degree `supported_degree`
+    pub fn shared_h_size(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        if t > domain_h.size() {
+            return Err(Error::T2Large);
         }
+        let (a_s, c_s) = padded_sequence(
+            domain_h.element(t),
+            rows_from_t(t, domain_h.size())?,
+            C::F::zero,
+            domain_k,
+        )?;
 
-        let seq = generate_sequence::<F>(r, &a_s.as_slice(), &c_s.as_slice());
-        let h = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&seq));
-        let h = LabeledPolynomial::new(String::from("h"), h, enforced_degree_bound, Some(1));
+        // h is public given t, so it is not hiding
+        Ok(C::PC::commitment_size(enforced_degree_bound.is_some())
+            + GeoSeqTest::<C>::proof_size(
+                domain_h.element(1),
+                enforced_degree_bound,
+                false,
+                &a_s,
+                &c_s,
+                domain_k,
+                supported_degree,
+            )?)
+    }
 
-        let (commitment, rands) =
-            PC::commit(&ck, &[h.clone()], Some(rng)).map_err(to_pc_error::<F, PC>)?;
+    /// Serialized size in bytes of an [`InstanceProof`] over K and H, with keys of degree `supported_degree`
+    pub fn instance_proof_size(
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        supported_degree: usize,
+    ) -> Result<usize, Error> {
+        Ok(SubsetOverK::<C>::proof_size(
+            domain_k,
+            Table::Values,
+            enforced_degree_bound,
+            supported_degree,
+        )? + DLComparison::<C>::proof_size(
+            domain_k,
+            domain_h,
+            enforced_degree_bound,
+            supported_degree,
+        )?)
+    }
+
+    #[cfg(feature = "prover")]
+    pub fn prove<R: Rng>(
+        ck: &CheckedCommitterKey<C, Self>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_random: &pc::Randomness<C>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_random: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        padding: PaddingStrategy,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("t_strictly_lower_triangular_test::prove");
+        ck.check_bound(enforced_degree_bound)?;
+        let cancellation = ck.cancellation();
+        let dl_ck = ck.sub_protocol();
+        let ck = ck.ck();
+        Self::check_padding(padding)?;
 
-        let h_commit = commitment[0].clone();
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(t, domain_k, domain_h, row_poly, col_poly)?;
 
-        // Step 2: Geometric sequence test on h
-        let geo_seq_proof = GeoSeqTest::<F, PC, FS>::prove(
-            ck, r, &h, &h_commit, &rands[0], &a_s, &c_s, domain_k, rng,
+        Self::absorb_statement(t, row_commit, col_commit, padding, None, context, fs_rng)?;
+
+        // Steps 1 and 2: interpolate h and test that it is a geometric sequence
+        let (h, h_commit, h_rand) =
+            Self::commit_h(ck, t, domain_k, domain_h, enforced_degree_bound)?;
+        let geo_seq_proof = Self::prove_h(
+            ck,
+            t,
+            domain_k,
+            domain_h,
+            &h,
+            &h_commit,
+            &h_rand,
+            context,
+            cancellation,
+            rng,
         )?;
 
         // Step 3: Subset over K between row_M and h
-        let subset_proof = SubsetOverK::<F, PC, FS>::prove();
+        let subset_proof = SubsetOverK::<C>::prove_with_cancellation(
+            ck,
+            domain_k,
+            row_poly,
+            row_commit,
+            row_random,
+            &h,
+            &h_commit,
+            &h_rand,
+            Table::Values,
+            enforced_degree_bound,
+            &context.child(b"row_subset"),
+            fs_rng,
+            cancellation,
+            rng,
+        )?;
 
         // Step 4: Discrete Log Comparison between row_M and col_M
-        let dl_proof = DLComparison::<F, PC, FS>::prove(
-            ck,
+        let dl_proof = DLComparison::<C>::prove(
+            &dl_ck,
             domain_k,
             domain_h,
             row_poly,
@@ -100,6 +272,7 @@ where
             col_commit,
             col_random,
             enforced_degree_bound,
+            &context.child(b"discrete_log_comparison"),
             fs_rng,
             rng,
         )?;
@@ -114,59 +287,468 @@ where
         Ok(proof)
     }
 
+    #[cfg(feature = "prover")]
+    /// Commit to h and test that it is a geometric sequence once, for every instance with row t over K and H. The
+    /// instances are then proven with [`Self::prove_with_shared_h`].
+    pub fn prove_shared_h<R: Rng>(
+        ck: &CheckedCommitterKey<C, Self>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<SharedH<C>, Error> {
+        ck.check_bound(enforced_degree_bound)?;
+        let cancellation = ck.cancellation();
+        let ck = ck.ck();
+        let (h, h_commit, h_rand) =
+            Self::commit_h(ck, t, domain_k, domain_h, enforced_degree_bound)?;
+        let geo_seq_proof = Self::prove_h(
+            ck,
+            t,
+            domain_k,
+            domain_h,
+            &h,
+            &h_commit,
+            &h_rand,
+            context,
+            cancellation,
+            rng,
+        )?;
+
+        Ok(SharedH {
+            h_commit: h_commit.commitment().clone(),
+            geo_seq_proof,
+        })
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove an instance without its own h, against `shared_h` proven for the same t and domains. The transcript
+    /// absorbs `shared_h`, so that the proof does not verify along with any other.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_shared_h<R: Rng>(
+        ck: &CheckedCommitterKey<C, Self>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        row_random: &pc::Randomness<C>,
+        col_poly: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_random: &pc::Randomness<C>,
+        enforced_degree_bound: Option<usize>,
+        padding: PaddingStrategy,
+        shared_h: &SharedH<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<InstanceProof<C>, Error> {
+        let _span = trace_span!("t_strictly_lower_triangular_test::prove_with_shared_h");
+        ck.check_bound(enforced_degree_bound)?;
+        let cancellation = ck.cancellation();
+        Self::check_padding(padding)?;
+        if t > domain_h.size() {
+            return Err(Error::T2Large);
+        }
+
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(t, domain_k, domain_h, row_poly, col_poly)?;
+
+        Self::absorb_statement(
+            t,
+            row_commit,
+            col_commit,
+            padding,
+            Some(shared_h),
+            context,
+            fs_rng,
+        )?;
+
+        // h is not hiding, so it is recomputed rather than handed over with its randomness
+        let dl_ck = ck.sub_protocol();
+        let ck = ck.ck();
+        let (h, h_commit, h_rand) =
+            Self::commit_h(ck, t, domain_k, domain_h, enforced_degree_bound)?;
+        if h_commit.commitment() != &shared_h.h_commit {
+            return Err(Error::StatementMismatch);
+        }
+
+        let subset_proof = SubsetOverK::<C>::prove_with_cancellation(
+            ck,
+            domain_k,
+            row_poly,
+            row_commit,
+            row_random,
+            &h,
+            &h_commit,
+            &h_rand,
+            Table::Values,
+            enforced_degree_bound,
+            &context.child(b"row_subset"),
+            fs_rng,
+            cancellation,
+            rng,
+        )?;
+        let dl_proof = DLComparison::<C>::prove(
+            &dl_ck,
+            domain_k,
+            domain_h,
+            row_poly,
+            row_commit,
+            row_random,
+            col_poly,
+            col_commit,
+            col_random,
+            enforced_degree_bound,
+            &context.child(b"discrete_log_comparison"),
+            fs_rng,
+            rng,
+        )?;
+
+        Ok(InstanceProof {
+            dl_proof,
+            subset_proof,
+        })
+    }
+
+    #[cfg(feature = "prover")]
+    /// Interpolate h over K, holding the rows from t on and padded with zeros, and commit to it. h only depends on t
+    /// and the domains, so it is not hiding.
+    fn commit_h(
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+    ) -> Result<
+        (
+            LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+            LabeledCommitment<pc::Commitment<C>>,
+            pc::Randomness<C>,
+        ),
+        Error,
+    > {
+        let (a_s, c_s) = Self::h_sequence(t, domain_k, domain_h)?;
+        let h = interpolate_sequence(domain_h.element(1), &a_s, &c_s, domain_k);
+        let h = LabeledPolynomial::new(String::from("h"), h, enforced_degree_bound, None);
+
+        let (commitment, rands) =
+            C::PC::commit(ck, [&h], None).map_err(to_pc_error::<C::F, C::PC>)?;
+        Ok((h, commitment[0].clone(), rands[0].clone()))
+    }
+
+    #[cfg(feature = "prover")]
+    /// Geometric sequence test on h
+    #[allow(clippy::too_many_arguments)]
+    fn prove_h<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        h: &LabeledPolynomial<C::F, DensePolynomial<C::F>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_rand: &pc::Randomness<C>,
+        context: &Context,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<GeoSeqProof<C>, Error> {
+        let _span = trace_span!("t_strictly_lower_triangular_test::prove_h");
+        let (a_s, c_s) = Self::h_sequence(t, domain_k, domain_h)?;
+
+        GeoSeqTest::<C>::prove_with_cancellation(
+            ck,
+            domain_h.element(1),
+            h,
+            h_commit,
+            h_rand,
+            &a_s,
+            &c_s,
+            domain_k,
+            &context.child(b"h_geo_seq"),
+            cancellation,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// The initial values and lengths of the sequence h holds over K
+    fn h_sequence(
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+    ) -> Result<(Vec<C::F>, Vec<usize>), Error> {
+        if t > domain_h.size() {
+            return Err(Error::T2Large);
+        }
+        padded_sequence(
+            domain_h.element(t),
+            rows_from_t(t, domain_h.size())?,
+            C::F::zero,
+            domain_k,
+        )
+    }
+
     pub fn verify(
-        vk: &PC::VerifierKey,
-        ck: &PC::CommitterKey,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
         t: usize,
-        domain_k: &GeneralEvaluationDomain<F>,
-        domain_h: &GeneralEvaluationDomain<F>,
-        row_commit: &LabeledCommitment<PC::Commitment>,
-        col_commit: &LabeledCommitment<PC::Commitment>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
         enforced_degree_bound: Option<usize>,
-        proof: Proof<F, PC>,
-        fs_rng: &mut FS,
+        padding: PaddingStrategy,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
     ) -> Result<(), Error> {
-        // re-label the oracle commitments with the enforced degree bound
-        let row_commit = LabeledCommitment::new(
-            row_commit.label().clone(),
-            row_commit.commitment().clone(),
+        Self::verify_with_report(
+            vk,
+            ck,
+            t,
+            domain_k,
+            domain_h,
+            row_commit,
+            col_commit,
             enforced_degree_bound,
-        );
-        let col_commit = LabeledCommitment::new(
-            col_commit.label().clone(),
-            col_commit.commitment().clone(),
+            padding,
+            proof,
+            context,
+            fs_rng,
+        )
+        .into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        padding: PaddingStrategy,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("t_strictly_lower_triangular_test::verify");
+        let mut report = VerificationReport::builder("t_strictly_lower_triangular_test");
+
+        report.check("padding", || Self::check_padding(padding));
+
+        report.check("transcript", || {
+            Self::absorb_statement(t, row_commit, col_commit, padding, None, context, fs_rng)
+        });
+
+        let h_commit = LabeledCommitment::new(
+            String::from("h"),
+            proof.h_commit.clone(),
             enforced_degree_bound,
         );
 
         // Step 2: Geometric sequence test on h
-        let mut a_s = vec![domain_h.element(t)];
-        let mut c_s = vec![domain_h.size() - t];
+        report.check("h_geo_seq", || {
+            Self::verify_h(
+                vk,
+                t,
+                domain_k,
+                domain_h,
+                proof.h_commit,
+                enforced_degree_bound,
+                proof.geo_seq_proof,
+                context,
+            )
+        });
 
-        let to_pad = domain_k.size() - (domain_h.size() - t);
-        if to_pad > 0 {
-            a_s.push(F::zero());
-            c_s.push(to_pad);
-        }
+        Self::verify_dl_and_subset(
+            &mut report,
+            vk,
+            ck,
+            domain_k,
+            domain_h,
+            row_commit,
+            col_commit,
+            &h_commit,
+            enforced_degree_bound,
+            proof.subset_proof,
+            proof.dl_proof,
+            context,
+            fs_rng,
+        );
 
-        let h_commit =
-            LabeledCommitment::new(String::from("h"), proof.h_commit, enforced_degree_bound);
+        report.finish()
+    }
 
-        GeoSeqTest::<F, PC, FS>::verify(
+    /// Verify h and its geometric sequence test once, after every instance proven against it
+    pub fn verify_shared_h(
+        vk: &pc::VerifierKey<C>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        enforced_degree_bound: Option<usize>,
+        shared_h: SharedH<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        Self::verify_h(
+            vk,
+            t,
+            domain_k,
+            domain_h,
+            shared_h.h_commit,
+            enforced_degree_bound,
+            shared_h.geo_seq_proof,
+            context,
+        )
+    }
+
+    /// Verify an instance proven against `shared_h`, which is not checked here but with [`Self::verify_shared_h`],
+    /// once for all the instances referencing it
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_shared_h(
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        padding: PaddingStrategy,
+        shared_h: &SharedH<C>,
+        proof: InstanceProof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("t_strictly_lower_triangular_test::verify_with_shared_h");
+        let mut report = VerificationReport::builder("t_strictly_lower_triangular_test");
+
+        report.check("padding", || Self::check_padding(padding));
+        report.check("t", || match t > domain_h.size() {
+            true => Err(Error::T2Large),
+            false => Ok(()),
+        });
+
+        report.check("transcript", || {
+            Self::absorb_statement(
+                t,
+                row_commit,
+                col_commit,
+                padding,
+                Some(shared_h),
+                context,
+                fs_rng,
+            )
+        });
+
+        let h_commit = LabeledCommitment::new(
+            String::from("h"),
+            shared_h.h_commit.clone(),
+            enforced_degree_bound,
+        );
+
+        Self::verify_dl_and_subset(
+            &mut report,
+            vk,
+            ck,
+            domain_k,
+            domain_h,
+            row_commit,
+            col_commit,
+            &h_commit,
+            enforced_degree_bound,
+            proof.subset_proof,
+            proof.dl_proof,
+            context,
+            fs_rng,
+        );
+
+        report.finish()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn verify_h(
+        vk: &pc::VerifierKey<C>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        h_commit: pc::Commitment<C>,
+        enforced_degree_bound: Option<usize>,
+        geo_seq_proof: GeoSeqProof<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        let _span = trace_span!("t_strictly_lower_triangular_test::verify_h");
+        let h_commit = LabeledCommitment::new(String::from("h"), h_commit, enforced_degree_bound);
+        let (a_s, c_s) = padded_sequence(
+            domain_h.element(t),
+            rows_from_t(t, domain_h.size())?,
+            C::F::zero,
+            domain_k,
+        )?;
+
+        GeoSeqTest::<C>::verify(
             domain_h.element(1),
             &a_s,
             &c_s,
             domain_k,
             &h_commit,
             enforced_degree_bound,
-            proof.geo_seq_proof,
+            geo_seq_proof,
             vk,
-        )?;
+            &context.child(b"h_geo_seq"),
+        )
+    }
+
+    /// Steps 3 and 4 of the verifier, against the h of the proof or the shared one
+    #[allow(clippy::too_many_arguments)]
+    fn verify_dl_and_subset(
+        report: &mut ReportBuilder,
+        vk: &pc::VerifierKey<C>,
+        ck: &pc::CommitterKey<C>,
+        domain_k: &DomainK<C::F>,
+        domain_h: &DomainH<C::F>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        h_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        subset_proof: SubsetProof<C>,
+        dl_proof: crate::discrete_log_comparison::proof::Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) {
+        // re-label the oracle commitments with the enforced degree bound
+        let row_commit = LabeledCommitment::new(
+            row_commit.label().clone(),
+            row_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
+        let col_commit = LabeledCommitment::new(
+            col_commit.label().clone(),
+            col_commit.commitment().clone(),
+            enforced_degree_bound,
+        );
 
         // Step 3: Subset over K between row_M and h
-        SubsetOverK::<F, PC, FS>::verify(proof.subset_proof)?;
+        report.check("row_subset", || {
+            SubsetOverK::<C>::verify(
+                vk,
+                domain_k,
+                &row_commit,
+                h_commit,
+                Table::Values,
+                enforced_degree_bound,
+                subset_proof,
+                &context.child(b"row_subset"),
+                fs_rng,
+            )
+        });
 
         // Step 4: Discrete Log Comparison between row_M and col_M
-        DLComparison::<F, PC, FS>::verify(
+        report.add(DLComparison::<C>::verify_with_report(
             vk,
             ck,
             domain_k,
@@ -174,10 +756,91 @@ where
             &row_commit,
             &col_commit,
             enforced_degree_bound,
-            proof.dl_proof,
+            dl_proof,
+            &context.child(b"discrete_log_comparison"),
+            fs_rng,
+        ));
+    }
+
+    /// Advance `fs_rng` as verifying `proof` would, without checking anything
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn replay_transcript(
+        ck: &pc::CommitterKey<C>,
+        t: usize,
+        domain_k: &DomainK<C::F>,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        enforced_degree_bound: Option<usize>,
+        padding: PaddingStrategy,
+        proof: &Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::absorb_statement(t, row_commit, col_commit, padding, None, context, fs_rng)?;
+
+        let h_commit = LabeledCommitment::new(
+            String::from("h"),
+            proof.h_commit.clone(),
+            enforced_degree_bound,
+        );
+        SubsetOverK::<C>::replay_transcript(
+            domain_k,
+            row_commit,
+            &h_commit,
+            Table::Values,
+            enforced_degree_bound,
+            &proof.subset_proof,
+            &context.child(b"row_subset"),
             fs_rng,
         )?;
 
+        DLComparison::<C>::replay_transcript(
+            ck,
+            domain_k,
+            row_commit,
+            col_commit,
+            &proof.dl_proof,
+            &context.child(b"discrete_log_comparison"),
+            fs_rng,
+        )
+    }
+
+    /// Absorb the statement of an instance, along with the shared h it is proven against if any
+    fn absorb_statement(
+        t: usize,
+        row_commit: &LabeledCommitment<pc::Commitment<C>>,
+        col_commit: &LabeledCommitment<pc::Commitment<C>>,
+        padding: PaddingStrategy,
+        shared_h: Option<&SharedH<C>>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        let mut fs_bytes = to_bytes![
+            &Self::PROTOCOL_NAME,
+            context,
+            row_commit,
+            col_commit,
+            t as u64,
+            padding
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        if let Some(shared_h) = shared_h {
+            fs_bytes.extend_from_slice(Self::SHARED_H_LABEL);
+            shared_h
+                .serialize(&mut fs_bytes)
+                .map_err(|_| Error::ProofSerializationError)?;
+        }
+        fs_rng.absorb(&fs_bytes);
         Ok(())
     }
+
+    /// Padding entries must be strictly lower triangular as well, which only holds when they repeat an existing entry
+    fn check_padding(padding: PaddingStrategy) -> Result<(), Error> {
+        match padding {
+            PaddingStrategy::RepeatLast => Ok(()),
+            PaddingStrategy::Origin => Err(Error::UnsupportedPadding(String::from(
+                "Padding entries at (0, 0) are not strictly lower triangular",
+            ))),
+        }
+    }
 }
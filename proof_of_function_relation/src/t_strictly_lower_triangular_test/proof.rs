@@ -2,19 +2,75 @@ use crate::{
     discrete_log_comparison::proof::Proof as DLProof, geo_seq::proof::Proof as GeoSeqProof,
     subset_over_k::proof::Proof as SubsetProof,
 };
-use ark_ff::{PrimeField, SquareRootField};
+use ark_ff::SquareRootField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Write};
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use derivative::Derivative;
+use zero_over_k::config::{pc, ProtocolConfig};
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F, PC>
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig>
 where
-    F: PrimeField + SquareRootField,
-    PC: AdditivelyHomomorphicPCS<F>,
+    C::F: SquareRootField,
 {
-    pub h_commit: PC::Commitment,
-    pub dl_proof: DLProof<F, PC>,
-    pub geo_seq_proof: GeoSeqProof<F, PC>,
-    pub subset_proof: SubsetProof,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_commit: pc::Commitment<C>,
+    pub dl_proof: DLProof<C>,
+    pub geo_seq_proof: GeoSeqProof<C>,
+    pub subset_proof: SubsetProof<C>,
+}
+
+/// The commitment to h and its geometric sequence test, which only depend on t and the domains, proven once for every
+/// instance with the same ones
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct SharedH<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub h_commit: pc::Commitment<C>,
+    pub geo_seq_proof: GeoSeqProof<C>,
+}
+
+/// A proof of one instance referencing a [`SharedH`] instead of holding its own h
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct InstanceProof<C: ProtocolConfig>
+where
+    C::F: SquareRootField,
+{
+    pub dl_proof: DLProof<C>,
+    pub subset_proof: SubsetProof<C>,
 }
@@ -1,22 +1,181 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test {
-    use crate::{error::Error, t_strictly_lower_triangular_test::TStrictlyLowerTriangular};
+    use crate::config::Config;
+    use crate::{
+        checked_key::CheckedCommitterKey,
+        domains::{DomainH, DomainK},
+        error::{Error, WitnessError},
+        indexer::{PaddingStrategy, SparseMatrixEncoding},
+        t_strictly_lower_triangular_test::{proof::SharedH, TStrictlyLowerTriangular},
+    };
 
     use ark_bn254::{Bn254, Fr};
     use ark_ff::to_bytes;
-    use ark_poly::{
-        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
-    };
-    use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+    use ark_poly::{univariate::DensePolynomial, EvaluationDomain, UVPolynomial};
+    use ark_poly_commit::{LabeledPolynomial, PCCommitterKey, PolynomialCommitment};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
     use ark_std::rand::thread_rng;
     use blake2::Blake2s;
-    use fiat_shamir_rng::{FiatShamirRng, SimpleHashFiatShamirRng};
-    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::{marlin_kzg::KZG10, LENGTH_PREFIX_SIZE};
     use rand_chacha::ChaChaRng;
+    use std::sync::{Arc, Mutex};
+    use zero_over_k::cancellation::CancellationToken;
 
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
     type F = Fr;
     type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    #[test]
+    fn test_shared_h() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(6).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+        let enforced_degree_bound = domain_k.size() + 1;
+        let t = 2;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, enforced_degree_bound])).unwrap();
+        let checked_ck = CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap();
+
+        // two strictly lower triangular matrices with the same t
+        let encode = |label: &str, entries: &[(usize, usize, F)]| {
+            SparseMatrixEncoding::from_entries(
+                entries,
+                &domain_h,
+                &domain_k,
+                PaddingStrategy::RepeatLast,
+            )
+            .unwrap()
+            .with_bounds(label, Some(enforced_degree_bound), Some(1))
+        };
+        let matrices = [
+            encode("a", &[(2, 0, F::from(1u64)), (3, 2, F::from(5u64))]),
+            encode("b", &[(2, 1, F::from(2u64)), (3, 0, F::from(3u64))]),
+        ];
+        let commits = matrices
+            .iter()
+            .map(|m| PC::commit(&ck, &[m.row.clone(), m.col.clone()], Some(rng)).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut shared_h = || {
+            TStrictlyLowerTriangular::<C>::prove_shared_h(
+                &checked_ck,
+                t,
+                &domain_k,
+                &domain_h,
+                Some(enforced_degree_bound),
+                &Context::new(b"shared_h"),
+                rng,
+            )
+            .unwrap()
+        };
+        let h = shared_h();
+        let other_h = shared_h();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let mut proofs = Vec::new();
+        for (i, (m, (commitments, rands))) in matrices.iter().zip(commits.iter()).enumerate() {
+            proofs.push(
+                TStrictlyLowerTriangular::<C>::prove_with_shared_h(
+                    &checked_ck,
+                    t,
+                    &domain_k,
+                    &domain_h,
+                    &m.row,
+                    &commitments[0],
+                    &rands[0],
+                    &m.col,
+                    &commitments[1],
+                    &rands[1],
+                    Some(enforced_degree_bound),
+                    PaddingStrategy::RepeatLast,
+                    &h,
+                    &Context::new(&[i as u8]),
+                    &mut fs_rng,
+                    rng,
+                )
+                .unwrap(),
+            );
+        }
+
+        // the instances only verify along with the h they were proven against
+        let verify = |shared_h, proofs: Vec<_>| {
+            let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+            proofs
+                .into_iter()
+                .zip(commits.iter())
+                .enumerate()
+                .map(|(i, (proof, (commitments, _)))| {
+                    TStrictlyLowerTriangular::<C>::verify_with_shared_h(
+                        &vk,
+                        &ck,
+                        t,
+                        &domain_k,
+                        &domain_h,
+                        &commitments[0],
+                        &commitments[1],
+                        Some(enforced_degree_bound),
+                        PaddingStrategy::RepeatLast,
+                        shared_h,
+                        proof,
+                        &Context::new(&[i as u8]),
+                        &mut fs_rng,
+                    )
+                    .into_result()
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let bytes = |proofs: &Vec<_>| {
+            let mut bytes = Vec::new();
+            proofs.serialize(&mut bytes).unwrap();
+            bytes
+        };
+        let proofs_bytes = bytes(&proofs);
+        let reread = || Vec::deserialize(proofs_bytes.as_slice()).unwrap();
+        assert!(verify(&h, proofs).is_ok());
+        assert!(verify(&other_h, reread()).is_err());
+
+        // h is checked once, for the t it was proven for
+        let h_bytes = {
+            let mut bytes = Vec::new();
+            h.serialize(&mut bytes).unwrap();
+            bytes
+        };
+        let verify_h = |t| {
+            TStrictlyLowerTriangular::<C>::verify_shared_h(
+                &vk,
+                t,
+                &domain_k,
+                &domain_h,
+                Some(enforced_degree_bound),
+                SharedH::deserialize(h_bytes.as_slice()).unwrap(),
+                &Context::new(b"shared_h"),
+            )
+        };
+        assert!(verify_h(t).is_ok());
+        assert!(verify_h(t + 1).is_err());
+
+        // each part has a size known before proving
+        let h_size = TStrictlyLowerTriangular::<C>::shared_h_size(
+            t,
+            &domain_k,
+            &domain_h,
+            Some(enforced_degree_bound),
+            ck.supported_degree(),
+        )
+        .unwrap();
+        let instance_size = TStrictlyLowerTriangular::<C>::instance_proof_size(
+            &domain_k,
+            &domain_h,
+            Some(enforced_degree_bound),
+            ck.supported_degree(),
+        )
+        .unwrap();
+        assert_eq!(h_size, h_bytes.len());
+        assert_eq!(LENGTH_PREFIX_SIZE + 2 * instance_size, proofs_bytes.len());
+    }
 
     #[test]
     fn test_valid_matrix() {
@@ -49,46 +208,35 @@ mod test {
         let m = 6;
         let n = 4;
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let enforced_degree_bound = domain_k.size() + 1;
         let enforced_hiding_bound = 1;
 
-        let _gamma = domain_k.element(1);
-
-        let omega_0 = domain_h.element(0);
-        let omega_1 = domain_h.element(1);
-        let omega_2 = domain_h.element(2);
-        let omega_3 = domain_h.element(3);
-
-        let row_m_evals = vec![
-            omega_2, omega_2, omega_3, omega_3, omega_3, omega_3, omega_3, omega_3,
-        ];
-        let col_m_evals = vec![
-            omega_0, omega_1, omega_2, omega_2, omega_2, omega_2, omega_2,
-            omega_2,
-            // or should it be:
-            //omega_0, omega_0, omega_0, omega_1, omega_1, omega_1, omega_1, omega_1,
+        let entries = [
+            (2, 0, F::from(1u64)),
+            (2, 1, F::from(2u64)),
+            (3, 1, F::from(3u64)),
+            (3, 2, F::from(5u64)),
         ];
-
-        let t = 2;
-        let row_poly = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&row_m_evals));
-        let col_poly = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&col_m_evals));
-
-        let row_poly = LabeledPolynomial::new(
-            String::from("row_poly"),
-            row_poly,
-            Some(enforced_degree_bound),
-            Some(enforced_hiding_bound),
-        );
-        let col_poly = LabeledPolynomial::new(
-            String::from("col_poly"),
-            col_poly,
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries,
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap()
+        .with_bounds(
+            "m",
             Some(enforced_degree_bound),
             Some(enforced_hiding_bound),
         );
 
+        let t = 2;
+        let row_poly = encoding.row;
+        let col_poly = encoding.col;
+
         let max_degree = 20;
         let pp = PC::setup(max_degree, None, rng).unwrap();
         let (ck, vk) = PC::trim(
@@ -104,8 +252,8 @@ mod test {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let proof = TStrictlyLowerTriangular::<F, PC, FS>::prove(
-            &ck,
+        let proof = TStrictlyLowerTriangular::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
             t,
             &domain_k,
             &domain_h,
@@ -116,15 +264,28 @@ mod test {
             &commitments[1].clone(),
             &rands[1].clone(),
             Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
+            &Context::new(b"test"),
             &mut fs_rng,
             rng,
         )
         .unwrap();
 
+        let size = TStrictlyLowerTriangular::<C>::proof_size(
+            t,
+            &domain_k,
+            &domain_h,
+            Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
+            ck.supported_degree(),
+        )
+        .unwrap();
+        assert_eq!(size, proof.serialized_size());
+
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
         assert_eq!(
-            TStrictlyLowerTriangular::<F, PC, FS>::verify(
+            TStrictlyLowerTriangular::<C>::verify(
                 &vk,
                 &ck,
                 t,
@@ -133,14 +294,210 @@ mod test {
                 &commitments[0].clone(),
                 &commitments[1].clone(),
                 Some(enforced_degree_bound),
+                PaddingStrategy::RepeatLast,
                 proof,
-                &mut fs_rng,
+                &Context::new(b"test"),
+                &mut fs_rng
             )
             .is_ok(),
             true
         );
     }
 
+    #[test]
+    fn test_cancellation() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(6).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+
+        let enforced_degree_bound = domain_k.size() + 1;
+        let enforced_hiding_bound = 1;
+
+        let entries = [
+            (2, 0, F::from(1u64)),
+            (2, 1, F::from(2u64)),
+            (3, 1, F::from(3u64)),
+            (3, 2, F::from(5u64)),
+        ];
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries,
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap()
+        .with_bounds(
+            "m",
+            Some(enforced_degree_bound),
+            Some(enforced_hiding_bound),
+        );
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, _) = PC::trim(
+            &pp,
+            max_degree,
+            enforced_hiding_bound,
+            Some(&[2, enforced_degree_bound]),
+        )
+        .unwrap();
+        let (commitments, rands) = PC::commit(
+            &ck,
+            &[encoding.row.clone(), encoding.col.clone()],
+            Some(rng),
+        )
+        .unwrap();
+
+        // the token fires within the DL comparison, after the row subset check was proven
+        let stages = Arc::new(Mutex::new(Vec::new()));
+        let token = CancellationToken::new();
+        let token = token.clone().with_progress({
+            let stages = stages.clone();
+            move |stage| {
+                stages.lock().unwrap().push(String::from(stage));
+                if stage == "discrete_log_comparison::fft" {
+                    token.cancel();
+                }
+            }
+        });
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let result = TStrictlyLowerTriangular::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound))
+                .unwrap()
+                .with_cancellation(&token),
+            2,
+            &domain_k,
+            &domain_h,
+            &encoding.row,
+            &commitments[0],
+            &rands[0],
+            &encoding.col,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        );
+
+        assert_eq!(
+            result.map(|_| ()),
+            Err(Error::Aborted(String::from("discrete_log_comparison::fft")))
+        );
+        let stages = stages.lock().unwrap();
+        assert!(stages.contains(&String::from("zero_over_k::open")));
+        assert_eq!(stages.last().unwrap(), "discrete_log_comparison::fft");
+    }
+
+    #[test]
+    fn test_origin_padding_unsupported() {
+        let rng = &mut thread_rng();
+        let domain_k = DomainK::<F>::new(6).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+
+        let enforced_degree_bound = domain_k.size() + 1;
+        let t = 2;
+
+        // padding entries at (0, 0) are on the diagonal
+        let entries = [(2, 0, F::from(1u64)), (3, 1, F::from(3u64))];
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries,
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::Origin,
+        )
+        .unwrap()
+        .with_bounds("m", Some(enforced_degree_bound), Some(1));
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (commitments, rands) = PC::commit(
+            &ck,
+            &[encoding.row.clone(), encoding.col.clone()],
+            Some(rng),
+        )
+        .unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let res = TStrictlyLowerTriangular::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+            t,
+            &domain_k,
+            &domain_h,
+            &encoding.row,
+            &commitments[0],
+            &rands[0],
+            &encoding.col,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            PaddingStrategy::Origin,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        );
+        assert!(matches!(res, Err(Error::UnsupportedPadding(_))));
+
+        // the verifier rejects the statement as well, whatever the proof
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries,
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap()
+        .with_bounds("m", Some(enforced_degree_bound), Some(1));
+        let (commitments, rands) = PC::commit(
+            &ck,
+            &[encoding.row.clone(), encoding.col.clone()],
+            Some(rng),
+        )
+        .unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = TStrictlyLowerTriangular::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
+            t,
+            &domain_k,
+            &domain_h,
+            &encoding.row,
+            &commitments[0],
+            &rands[0],
+            &encoding.col,
+            &commitments[1],
+            &rands[1],
+            Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )
+        .unwrap();
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let res = TStrictlyLowerTriangular::<C>::verify(
+            &vk,
+            &ck,
+            t,
+            &domain_k,
+            &domain_h,
+            &commitments[0],
+            &commitments[1],
+            Some(enforced_degree_bound),
+            PaddingStrategy::Origin,
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+        assert!(res.is_err());
+    }
+
+    // an invalid witness is rejected by the prover before the verifier sees it
+    #[cfg(not(feature = "validate-witness"))]
     #[test]
     fn test_outside_of_lower_triangle() {
         // M indices
@@ -170,8 +527,8 @@ mod test {
         let m = 6;
         let n = 4;
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let enforced_degree_bound = domain_k.size() + 1;
         let enforced_hiding_bound = 1;
@@ -222,8 +579,8 @@ mod test {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let proof = TStrictlyLowerTriangular::<F, PC, FS>::prove(
-            &ck,
+        let proof = TStrictlyLowerTriangular::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
             t,
             &domain_k,
             &domain_h,
@@ -234,6 +591,8 @@ mod test {
             &commitments[1].clone(),
             &rands[1].clone(),
             Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
+            &Context::new(b"test"),
             &mut fs_rng,
             rng,
         );
@@ -248,46 +607,31 @@ mod test {
         let m = 6;
         let n = 4;
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let enforced_degree_bound = domain_k.size() - 1;
         let other_degree_bound = domain_k.size() + 1;
         let enforced_hiding_bound = 1;
 
-        let _gamma = domain_k.element(1);
-
-        let omega_0 = domain_h.element(0);
-        let omega_1 = domain_h.element(1);
-        let omega_2 = domain_h.element(2);
-        let omega_3 = domain_h.element(3);
-
-        let row_m_evals = vec![
-            omega_2, omega_2, omega_3, omega_3, omega_3, omega_3, omega_3, omega_3,
-        ];
-        let col_m_evals = vec![
-            omega_0, omega_1, omega_2, omega_2, omega_2, omega_2, omega_2,
-            omega_2,
-            // or should it be:
-            //omega_0, omega_0, omega_0, omega_1, omega_1, omega_1, omega_1, omega_1,
+        let entries = [
+            (2, 0, F::from(1u64)),
+            (2, 1, F::from(2u64)),
+            (3, 1, F::from(3u64)),
+            (3, 2, F::from(5u64)),
         ];
+        let encoding = SparseMatrixEncoding::from_entries(
+            &entries,
+            &domain_h,
+            &domain_k,
+            PaddingStrategy::RepeatLast,
+        )
+        .unwrap()
+        .with_bounds("m", Some(other_degree_bound), Some(enforced_hiding_bound));
 
         let t = 2;
-        let row_poly = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&row_m_evals));
-        let col_poly = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&col_m_evals));
-
-        let row_poly = LabeledPolynomial::new(
-            String::from("row_poly"),
-            row_poly,
-            Some(other_degree_bound),
-            Some(enforced_hiding_bound),
-        );
-        let col_poly = LabeledPolynomial::new(
-            String::from("col_poly"),
-            col_poly,
-            Some(other_degree_bound),
-            Some(enforced_hiding_bound),
-        );
+        let row_poly = encoding.row;
+        let col_poly = encoding.col;
 
         let max_degree = 20;
         let pp = PC::setup(max_degree, None, rng).unwrap();
@@ -304,8 +648,8 @@ mod test {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let proof = TStrictlyLowerTriangular::<F, PC, FS>::prove(
-            &ck,
+        let proof = TStrictlyLowerTriangular::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(other_degree_bound)).unwrap(),
             t,
             &domain_k,
             &domain_h,
@@ -316,6 +660,8 @@ mod test {
             &commitments[1].clone(),
             &rands[1].clone(),
             Some(other_degree_bound),
+            PaddingStrategy::RepeatLast,
+            &Context::new(b"test"),
             &mut fs_rng,
             rng,
         )
@@ -323,7 +669,7 @@ mod test {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let res = TStrictlyLowerTriangular::<F, PC, FS>::verify(
+        let res = TStrictlyLowerTriangular::<C>::verify(
             &vk,
             &ck,
             t,
@@ -332,7 +678,9 @@ mod test {
             &commitments[0].clone(),
             &commitments[1].clone(),
             Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
             proof,
+            &Context::new(b"test"),
             &mut fs_rng,
         );
 
@@ -341,9 +689,9 @@ mod test {
         assert_eq!(res.err().unwrap(), Error::BatchCheckError)
     }
 
+    // the matrix is strictly lower triangular, but has an entry within the first t rows
+    #[cfg(not(feature = "validate-witness"))]
     #[test]
-    #[should_panic]
-    #[ignore] // TODO: include subset test and remove this ignore
     fn test_not_t() {
         // M indices
         /*
@@ -372,8 +720,8 @@ mod test {
         let m = 6;
         let n = 4;
 
-        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
-        let domain_h = GeneralEvaluationDomain::<F>::new(n).unwrap();
+        let domain_k = DomainK::<F>::new(m).unwrap();
+        let domain_h = DomainH::<F>::new(n).unwrap();
 
         let enforced_degree_bound = domain_k.size() + 1;
         let enforced_hiding_bound = 1;
@@ -392,7 +740,8 @@ mod test {
             omega_0, omega_0, omega_1, omega_1, omega_1, omega_1, omega_1, omega_1,
         ];
 
-        let t = 1;
+        // the entry in row 1 lies within the first t = 2 rows
+        let t = 2;
         let row_poly = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&row_m_evals));
         let col_poly = DensePolynomial::<F>::from_coefficients_slice(&domain_k.ifft(&col_m_evals));
 
@@ -424,8 +773,8 @@ mod test {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
-        let proof = TStrictlyLowerTriangular::<F, PC, FS>::prove(
-            &ck,
+        let proof = TStrictlyLowerTriangular::<C>::prove(
+            &CheckedCommitterKey::new(&ck, Some(enforced_degree_bound)).unwrap(),
             t,
             &domain_k,
             &domain_h,
@@ -436,6 +785,8 @@ mod test {
             &commitments[1].clone(),
             &rands[1].clone(),
             Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
+            &Context::new(b"test"),
             &mut fs_rng,
             rng,
         )
@@ -443,21 +794,70 @@ mod test {
 
         let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
 
+        let report = TStrictlyLowerTriangular::<C>::verify_with_report(
+            &vk,
+            &ck,
+            t,
+            &domain_k,
+            &domain_h,
+            &commitments[0].clone(),
+            &commitments[1].clone(),
+            Some(enforced_degree_bound),
+            PaddingStrategy::RepeatLast,
+            proof,
+            &Context::new(b"test"),
+            &mut fs_rng,
+        );
+        // only the subset check between the rows and h fails
         assert_eq!(
-            TStrictlyLowerTriangular::<F, PC, FS>::verify(
-                &vk,
-                &ck,
-                t,
-                &domain_k,
-                &domain_h,
-                &commitments[0].clone(),
-                &commitments[1].clone(),
-                Some(enforced_degree_bound),
-                proof,
-                &mut fs_rng,
+            report.failures(),
+            vec![(
+                String::from("t_strictly_lower_triangular_test/row_subset"),
+                Error::ZeroOverKError(String::from("Check2Failed"))
+            )]
+        );
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let domain_k = DomainK::<F>::new(8).unwrap();
+        let domain_h = DomainH::<F>::new(4).unwrap();
+
+        let to_poly = |label: &str, exponents: &[usize]| {
+            let evals = exponents
+                .iter()
+                .map(|&e| domain_h.element(e))
+                .collect::<Vec<_>>();
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::<F>::from_coefficients_vec(domain_k.ifft(&evals)),
+                None,
+                None,
             )
-            .is_ok(),
-            true
+        };
+
+        // non-zero entries at (1, 0), (2, 0), (2, 1) and (3, 1)
+        let row_poly = to_poly("row_poly", &[1, 2, 2, 3, 2, 3, 3, 3]);
+        let col_poly = to_poly("col_poly", &[0, 0, 1, 1, 1, 1, 1, 1]);
+
+        let validate = |t| {
+            TStrictlyLowerTriangular::<C>::validate_witness(
+                t, &domain_k, &domain_h, &row_poly, &col_poly,
+            )
+        };
+
+        assert_eq!(validate(1), Ok(()));
+
+        // the entry in row 1 lies within the first t = 2 rows
+        assert_eq!(
+            validate(2),
+            Err(Error::InvalidWitness(WitnessError::RowBelowT {
+                index: 0,
+                row_exponent: 1,
+                t: 2
+            }))
         );
+
+        assert_eq!(validate(5), Err(Error::T2Large));
     }
 }
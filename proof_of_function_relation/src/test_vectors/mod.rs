@@ -0,0 +1,650 @@
+//! Canonical proofs for cross-implementation compatibility.
+//!
+//! Every vector is produced over BN254 with KZG commitments, from an SRS, witnesses and prover randomness that are
+//! all drawn from a ChaCha RNG seeded with [`SEED`]. The serialized commitments to the concrete oracles and the
+//! serialized proof are snapshotted under `test_vectors/` at the root of this crate, in hex. An implementation in
+//! another language can deserialize those bytes and check them against its own verifier, or re-derive them from the
+//! same seed.
+//!
+//! Run `UPDATE_TEST_VECTORS=1 cargo test -p proof_of_function_relation test_vectors` to regenerate the snapshots
+//! after an intended change to a proof format.
+
+use crate::config::Config;
+use crate::{
+    checked_key::CheckedCommitterKey,
+    discrete_log_comparison::{proof::VersionedProof, DLComparison},
+    domains::{choose_domains, DomainH, DomainK},
+    equal_on_subdomain::EqualOnSubdomain,
+    error::{to_pc_error, Error},
+    geo_seq::GeoSeqTest,
+    indexer::{PaddingStrategy, SparseMatrixEncoding},
+    non_zero_over_k::NonZeroOverK,
+    proof_of_knowledge::ProofOfKnowledge,
+    proof_version::ProofVersion,
+    rotation_argument::RotationArgument,
+    t_diag::TDiag,
+    t_strictly_lower_triangular_test::TStrictlyLowerTriangular,
+    util::{gen_t_diag_test_polys, generate_sequence},
+};
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{to_bytes, One};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain, UVPolynomial};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+use ark_serialize::CanonicalSerialize;
+use blake2::Blake2s;
+use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+use homomorphic_poly_commit::marlin_kzg::KZG10;
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+use zero_over_k::{
+    util::sample_vector,
+    virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
+    zero_over_k::ZeroOverK,
+};
+
+mod tests;
+
+type F = Fr;
+type PC = KZG10<Bn254>;
+type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+type C = Config<F, PC, FS>;
+
+/// The seed of the RNG behind every test vector
+pub const SEED: [u8; 32] = *b"functional-commitment/vectors/v1";
+
+/// The context every test vector is proven under
+pub const CONTEXT: &[u8] = b"test vectors";
+
+/// The seed of the Fiat-Shamir RNG of the protocols that take one from the caller
+pub const FS_SEED: &[u8] = b"test vectors";
+
+/// Size of K and H in every test vector
+pub const DOMAIN_K_SIZE: usize = 8;
+pub const DOMAIN_H_SIZE: usize = 4;
+
+const MAX_DEGREE: usize = 20;
+const DEGREE_BOUND: usize = DOMAIN_K_SIZE + 1;
+
+/// A canonical proof, together with the commitments to the concrete oracles it is about
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestVector {
+    pub name: &'static str,
+    pub commitments: Vec<u8>,
+    pub proof: Vec<u8>,
+}
+
+impl TestVector {
+    fn new<P: CanonicalSerialize>(
+        name: &'static str,
+        commitments: &[LabeledCommitment<
+            <PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment,
+        >],
+        proof: &P,
+    ) -> Result<Self, Error> {
+        let commitments = commitments
+            .iter()
+            .map(|commitment| *commitment.commitment())
+            .collect::<Vec<_>>();
+
+        let mut commitment_bytes = Vec::new();
+        commitments
+            .serialize(&mut commitment_bytes)
+            .map_err(|_| Error::ToBytesError)?;
+        let mut proof_bytes = Vec::new();
+        proof
+            .serialize(&mut proof_bytes)
+            .map_err(|_| Error::ToBytesError)?;
+
+        Ok(Self {
+            name,
+            commitments: commitment_bytes,
+            proof: proof_bytes,
+        })
+    }
+
+    /// The snapshot format: one `key: hex` line for the commitments and one for the proof
+    pub fn to_hex(&self) -> String {
+        format!(
+            "commitments: {}\nproof: {}\n",
+            to_hex(&self.commitments),
+            to_hex(&self.proof)
+        )
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Keys, domains and RNG shared by all the vectors
+struct Fixture {
+    rng: ChaChaRng,
+    ck: <PC as PolynomialCommitment<F, DensePolynomial<F>>>::CommitterKey,
+    vk: <PC as PolynomialCommitment<F, DensePolynomial<F>>>::VerifierKey,
+    domain_k: DomainK<F>,
+    domain_h: DomainH<F>,
+}
+
+impl Fixture {
+    fn new() -> Result<Self, Error> {
+        let mut rng = ChaChaRng::from_seed(SEED);
+        let pp = PC::setup(MAX_DEGREE, None, &mut rng).map_err(to_pc_error::<F, PC>)?;
+        let (ck, vk) =
+            PC::trim(&pp, MAX_DEGREE, 1, Some(&[2, DEGREE_BOUND])).map_err(to_pc_error::<F, PC>)?;
+
+        let (domain_h, domain_k) = choose_domains(DOMAIN_H_SIZE, DOMAIN_K_SIZE)?;
+
+        Ok(Self {
+            rng,
+            ck,
+            vk,
+            domain_k,
+            domain_h,
+        })
+    }
+
+    /// A hiding oracle interpolating `evals` over K
+    fn oracle(&self, label: &str, evals: &[F]) -> LabeledPolynomial<F, DensePolynomial<F>> {
+        LabeledPolynomial::new(
+            String::from(label),
+            DensePolynomial::from_coefficients_vec(self.domain_k.ifft(evals)),
+            Some(DEGREE_BOUND),
+            Some(1),
+        )
+    }
+
+    /// An oracle taking the values omega^exponents[i] over K, where omega generates H
+    fn oracle_in_h(
+        &self,
+        label: &str,
+        exponents: &[usize],
+    ) -> LabeledPolynomial<F, DensePolynomial<F>> {
+        let evals = exponents
+            .iter()
+            .map(|&e| self.domain_h.element(e))
+            .collect::<Vec<_>>();
+        self.oracle(label, &evals)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn commit(
+        &mut self,
+        oracles: &[&LabeledPolynomial<F, DensePolynomial<F>>],
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<<PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment>>,
+            Vec<<PC as PolynomialCommitment<F, DensePolynomial<F>>>::Randomness>,
+        ),
+        Error,
+    > {
+        PC::commit(&self.ck, oracles.iter().copied(), Some(&mut self.rng))
+            .map_err(to_pc_error::<F, PC>)
+    }
+}
+
+/// Every test vector, in a fixed order. Each vector is checked against the verifier before being returned. t-FT is
+/// covered through its t-SLT and t-diag sub-proofs.
+pub fn all() -> Result<Vec<TestVector>, Error> {
+    Ok(vec![
+        zero_over_k()?,
+        non_zero_over_k()?,
+        geo_seq()?,
+        proof_of_knowledge()?,
+        rotation_argument()?,
+        equal_on_subdomain()?,
+        discrete_log_comparison()?,
+        discrete_log_comparison_v1()?,
+        discrete_log_comparison_v2()?,
+        t_strictly_lower_triangular()?,
+        t_diag()?,
+    ])
+}
+
+pub fn zero_over_k() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let f_evals: Vec<F> = sample_vector(&mut fixture.rng, DOMAIN_K_SIZE);
+    let f = fixture.oracle("f", &f_evals);
+    let g = fixture.oracle("g", &f_evals);
+    let (commitments, rands) = fixture.commit(&[&f, &g])?;
+
+    let alphas = [F::one(), F::one()];
+    let equality_vo =
+        GenericShiftingVO::new(&[0, 1], &alphas, presets::equality_check)?.with_scaling_factor(1);
+
+    let proof = ZeroOverK::<C>::prove(
+        &[&f, &g],
+        &commitments,
+        &rands,
+        Some(DEGREE_BOUND),
+        &equality_vo,
+        &fixture.domain_k,
+        &fixture.ck,
+        &Context::new(CONTEXT),
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("zero_over_k", &commitments, &proof)?;
+
+    ZeroOverK::<C>::verify(
+        proof,
+        &commitments,
+        Some(DEGREE_BOUND),
+        &equality_vo,
+        &fixture.domain_k,
+        &fixture.vk,
+        &Context::new(CONTEXT),
+    )?;
+
+    Ok(vector)
+}
+
+pub fn non_zero_over_k() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let f_evals: Vec<F> = sample_vector(&mut fixture.rng, DOMAIN_K_SIZE);
+    let f = fixture.oracle("f", &f_evals);
+    let (commitments, rands) = fixture.commit(&[&f])?;
+
+    let proof = NonZeroOverK::<C>::prove(
+        &fixture.ck,
+        &fixture.domain_k,
+        &f,
+        &commitments[0],
+        &rands[0],
+        &Context::new(CONTEXT),
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("non_zero_over_k", &commitments, &proof)?;
+
+    NonZeroOverK::<C>::verify(
+        &fixture.vk,
+        &fixture.domain_k,
+        *commitments[0].commitment(),
+        Some(DEGREE_BOUND),
+        proof,
+        &Context::new(CONTEXT),
+    )?;
+
+    Ok(vector)
+}
+
+pub fn geo_seq() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let common_ratio = F::from(9u64);
+    let initial_values = [F::from(2u64), F::from(5u64)];
+    let lengths = [3, 5];
+    let seq = generate_sequence(common_ratio, &initial_values, &lengths);
+
+    let f = fixture.oracle("f", &seq);
+    let (commitments, rands) = fixture.commit(&[&f])?;
+
+    let proof = GeoSeqTest::<C>::prove(
+        &fixture.ck,
+        common_ratio,
+        &f,
+        &commitments[0],
+        &rands[0],
+        &initial_values,
+        &lengths,
+        &fixture.domain_k,
+        &Context::new(CONTEXT),
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("geo_seq", &commitments, &proof)?;
+
+    GeoSeqTest::<C>::verify(
+        common_ratio,
+        &initial_values,
+        &lengths,
+        &fixture.domain_k,
+        &commitments[0],
+        Some(DEGREE_BOUND),
+        proof,
+        &fixture.vk,
+        &Context::new(CONTEXT),
+    )?;
+
+    Ok(vector)
+}
+
+pub fn proof_of_knowledge() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let f_evals: Vec<F> = sample_vector(&mut fixture.rng, DOMAIN_K_SIZE);
+    let f = fixture.oracle("f", &f_evals);
+    let (commitments, rands) = fixture.commit(&[&f])?;
+
+    let proof = ProofOfKnowledge::<C>::prove(
+        &fixture.ck,
+        &f,
+        &commitments[0],
+        &rands[0],
+        &Context::new(CONTEXT),
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("proof_of_knowledge", &commitments, &proof)?;
+
+    ProofOfKnowledge::<C>::verify(
+        &fixture.vk,
+        &commitments[0],
+        Some(DEGREE_BOUND),
+        proof,
+        &Context::new(CONTEXT),
+    )?;
+
+    Ok(vector)
+}
+
+pub fn rotation_argument() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    // g(gamma^i) = f(gamma^(i + 1))
+    let f_evals: Vec<F> = sample_vector(&mut fixture.rng, DOMAIN_K_SIZE);
+    let mut g_evals = f_evals.clone();
+    g_evals.rotate_left(1);
+    let f = fixture.oracle("f", &f_evals);
+    let g = fixture.oracle("g", &g_evals);
+    let (commitments, rands) = fixture.commit(&[&f, &g])?;
+
+    let proof = RotationArgument::<C>::prove(
+        &fixture.ck,
+        &fixture.domain_k,
+        &f,
+        &commitments[0],
+        &rands[0],
+        &g,
+        &commitments[1],
+        &rands[1],
+        Some(DEGREE_BOUND),
+        &Context::new(CONTEXT),
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("rotation_argument", &commitments, &proof)?;
+
+    RotationArgument::<C>::verify(
+        &fixture.vk,
+        &fixture.domain_k,
+        &commitments[0],
+        &commitments[1],
+        Some(DEGREE_BOUND),
+        proof,
+        &Context::new(CONTEXT),
+    )?;
+
+    Ok(vector)
+}
+
+pub fn equal_on_subdomain() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    // g updates f outside of the subdomain only
+    let subdomain = [0, 2, 3, 4, 5];
+    let f_evals: Vec<F> = sample_vector(&mut fixture.rng, DOMAIN_K_SIZE);
+    let mut g_evals = f_evals.clone();
+    for index in [1, 6, 7] {
+        g_evals[index] += F::one();
+    }
+    let f = fixture.oracle("f", &f_evals);
+    let g = fixture.oracle("g", &g_evals);
+    let (commitments, rands) = fixture.commit(&[&f, &g])?;
+
+    let proof = EqualOnSubdomain::<C>::prove(
+        &fixture.ck,
+        &fixture.domain_k,
+        &subdomain,
+        &f,
+        &commitments[0],
+        &rands[0],
+        &g,
+        &commitments[1],
+        &rands[1],
+        Some(DEGREE_BOUND),
+        &Context::new(CONTEXT),
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("equal_on_subdomain", &commitments, &proof)?;
+
+    EqualOnSubdomain::<C>::verify(
+        &fixture.vk,
+        &fixture.ck,
+        &fixture.domain_k,
+        &subdomain,
+        &commitments[0],
+        &commitments[1],
+        Some(DEGREE_BOUND),
+        proof,
+        &Context::new(CONTEXT),
+    )?;
+
+    Ok(vector)
+}
+
+pub fn discrete_log_comparison() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let f = fixture.oracle_in_h("f", &[1, 2, 3, 3, 1, 2, 3, 3]);
+    let g = fixture.oracle_in_h("g", &[0, 1, 1, 2, 0, 1, 1, 2]);
+    let (commitments, rands) = fixture.commit(&[&f, &g])?;
+
+    let mut fs_rng = FS::initialize(&to_bytes!(FS_SEED).map_err(|_| Error::ToBytesError)?);
+    let proof = DLComparison::<C>::prove(
+        &CheckedCommitterKey::new(&fixture.ck, Some(DEGREE_BOUND))?,
+        &fixture.domain_k,
+        &fixture.domain_h,
+        &f,
+        &commitments[0],
+        &rands[0],
+        &g,
+        &commitments[1],
+        &rands[1],
+        Some(DEGREE_BOUND),
+        &Context::new(CONTEXT),
+        &mut fs_rng,
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("discrete_log_comparison", &commitments, &proof)?;
+
+    let mut fs_rng = FS::initialize(&to_bytes!(FS_SEED).map_err(|_| Error::ToBytesError)?);
+    DLComparison::<C>::verify(
+        &fixture.vk,
+        &fixture.ck,
+        &fixture.domain_k,
+        &fixture.domain_h,
+        &commitments[0],
+        &commitments[1],
+        Some(DEGREE_BOUND),
+        proof,
+        &Context::new(CONTEXT),
+        &mut fs_rng,
+    )?;
+
+    Ok(vector)
+}
+
+/// A DLComparison proof in the first format, serialized after its version. Its snapshot pins the bytes that later
+/// verifiers must keep accepting.
+pub fn discrete_log_comparison_v1() -> Result<TestVector, Error> {
+    versioned_discrete_log_comparison("discrete_log_comparison_v1", ProofVersion::V1)
+}
+
+/// A DLComparison proof in the format with batched square and product checks, serialized after its version
+pub fn discrete_log_comparison_v2() -> Result<TestVector, Error> {
+    versioned_discrete_log_comparison("discrete_log_comparison_v2", ProofVersion::V2)
+}
+
+fn versioned_discrete_log_comparison(
+    name: &'static str,
+    version: ProofVersion,
+) -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let f = fixture.oracle_in_h("f", &[1, 2, 3, 3, 1, 2, 3, 3]);
+    let g = fixture.oracle_in_h("g", &[0, 1, 1, 2, 0, 1, 1, 2]);
+    let (commitments, rands) = fixture.commit(&[&f, &g])?;
+
+    let ck = CheckedCommitterKey::new(&fixture.ck, Some(DEGREE_BOUND))?;
+    let mut fs_rng = FS::initialize(&to_bytes!(FS_SEED).map_err(|_| Error::ToBytesError)?);
+    let proof: VersionedProof<C> = match version {
+        ProofVersion::V1 => DLComparison::<C>::prove(
+            &ck,
+            &fixture.domain_k,
+            &fixture.domain_h,
+            &f,
+            &commitments[0],
+            &rands[0],
+            &g,
+            &commitments[1],
+            &rands[1],
+            Some(DEGREE_BOUND),
+            &Context::new(CONTEXT),
+            &mut fs_rng,
+            &mut fixture.rng,
+        )?
+        .into(),
+        ProofVersion::V2 => DLComparison::<C>::prove_v2(
+            &ck,
+            &fixture.domain_k,
+            &fixture.domain_h,
+            &f,
+            &commitments[0],
+            &rands[0],
+            &g,
+            &commitments[1],
+            &rands[1],
+            Some(DEGREE_BOUND),
+            &Context::new(CONTEXT),
+            &mut fs_rng,
+            &mut fixture.rng,
+        )?
+        .into(),
+    };
+    let vector = TestVector::new(name, &commitments, &proof)?;
+
+    let mut fs_rng = FS::initialize(&to_bytes!(FS_SEED).map_err(|_| Error::ToBytesError)?);
+    DLComparison::<C>::verify_versioned(
+        &fixture.vk,
+        &fixture.ck,
+        &fixture.domain_k,
+        &fixture.domain_h,
+        &commitments[0],
+        &commitments[1],
+        Some(DEGREE_BOUND),
+        proof,
+        &Context::new(CONTEXT),
+        &mut fs_rng,
+    )?;
+
+    Ok(vector)
+}
+
+pub fn t_strictly_lower_triangular() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let t = 2;
+    let entries = [
+        (2, 0, F::from(1u64)),
+        (2, 1, F::from(2u64)),
+        (3, 1, F::from(3u64)),
+        (3, 2, F::from(5u64)),
+    ];
+    let encoding = SparseMatrixEncoding::from_entries(
+        &entries,
+        &fixture.domain_h,
+        &fixture.domain_k,
+        PaddingStrategy::RepeatLast,
+    )?
+    .with_bounds("m", Some(DEGREE_BOUND), Some(1));
+    let (commitments, rands) = fixture.commit(&[&encoding.row, &encoding.col])?;
+
+    let mut fs_rng = FS::initialize(&to_bytes!(FS_SEED).map_err(|_| Error::ToBytesError)?);
+    let proof = TStrictlyLowerTriangular::<C>::prove(
+        &CheckedCommitterKey::new(&fixture.ck, Some(DEGREE_BOUND))?,
+        t,
+        &fixture.domain_k,
+        &fixture.domain_h,
+        &encoding.row,
+        &commitments[0],
+        &rands[0],
+        &encoding.col,
+        &commitments[1],
+        &rands[1],
+        Some(DEGREE_BOUND),
+        PaddingStrategy::RepeatLast,
+        &Context::new(CONTEXT),
+        &mut fs_rng,
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("t_strictly_lower_triangular", &commitments, &proof)?;
+
+    let mut fs_rng = FS::initialize(&to_bytes!(FS_SEED).map_err(|_| Error::ToBytesError)?);
+    TStrictlyLowerTriangular::<C>::verify(
+        &fixture.vk,
+        &fixture.ck,
+        t,
+        &fixture.domain_k,
+        &fixture.domain_h,
+        &commitments[0],
+        &commitments[1],
+        Some(DEGREE_BOUND),
+        PaddingStrategy::RepeatLast,
+        proof,
+        &Context::new(CONTEXT),
+        &mut fs_rng,
+    )?;
+
+    Ok(vector)
+}
+
+pub fn t_diag() -> Result<TestVector, Error> {
+    let mut fixture = Fixture::new()?;
+
+    let t = 2;
+    let polys = gen_t_diag_test_polys(
+        fixture.domain_k,
+        fixture.domain_h,
+        Some(DEGREE_BOUND),
+        Some(1),
+    );
+    let (row, col, val) = (&polys[4], &polys[5], &polys[6]);
+    let (commitments, rands) = fixture.commit(&[row, col, val])?;
+
+    let proof = TDiag::<C>::prove(
+        &CheckedCommitterKey::new(&fixture.ck, Some(DEGREE_BOUND))?,
+        t,
+        row,
+        col,
+        val,
+        &commitments[0],
+        &commitments[1],
+        &commitments[2],
+        &rands[0],
+        &rands[1],
+        &rands[2],
+        Some(DEGREE_BOUND),
+        &fixture.domain_k,
+        &fixture.domain_h,
+        DOMAIN_H_SIZE,
+        PaddingStrategy::Origin,
+        &Context::new(CONTEXT),
+        &mut fixture.rng,
+    )?;
+    let vector = TestVector::new("t_diag", &commitments, &proof)?;
+
+    TDiag::<C>::verify(
+        &fixture.vk,
+        t,
+        &commitments[0],
+        &commitments[1],
+        &commitments[2],
+        Some(DEGREE_BOUND),
+        &fixture.domain_h,
+        &fixture.domain_k,
+        DOMAIN_H_SIZE,
+        PaddingStrategy::Origin,
+        proof,
+        &Context::new(CONTEXT),
+    )?;
+
+    Ok(vector)
+}
@@ -0,0 +1,143 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::{
+        discrete_log_comparison::{proof::VersionedProof, DLComparison},
+        proof_version::ProofVersion,
+        test_vectors::{all, Fixture, TestVector, C, CONTEXT, DEGREE_BOUND, F, FS, FS_SEED, PC},
+    };
+    use ark_ff::to_bytes;
+    use ark_poly::univariate::DensePolynomial;
+    use ark_poly_commit::{LabeledCommitment, PolynomialCommitment};
+    use ark_serialize::CanonicalDeserialize;
+    use blake2::{Blake2s, Digest};
+    use fiat_shamir_rng::{Context, FiatShamirRng};
+    use std::{env, fs, path::PathBuf};
+
+    /// Snapshots of released proof versions with the Blake2s digests of their files. Verifiers must keep accepting
+    /// these bytes, so they are never regenerated.
+    const PINNED: [(&str, &str); 2] = [
+        (
+            "discrete_log_comparison_v1",
+            "569896af70d81badf61511e3e1a6e776e37b62260566f0e8e1f5a87f8d5097db",
+        ),
+        (
+            "discrete_log_comparison_v2",
+            "dcf61ffec180750a9bc9e67961630ddfe4142bc99fda672bfa44f08e4f69bc50",
+        ),
+    ];
+
+    fn snapshot_path(vector: &TestVector) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_vectors")
+            .join(format!("{}.hex", vector.name))
+    }
+
+    /// The bytes of the `key: hex` line of a snapshot
+    fn snapshot_bytes(snapshot: &str, key: &str) -> Vec<u8> {
+        let hex = snapshot
+            .lines()
+            .find_map(|line| line.strip_prefix(&format!("{}: ", key)))
+            .unwrap();
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_vectors_are_deterministic() {
+        assert_eq!(all().unwrap(), all().unwrap());
+    }
+
+    #[test]
+    fn test_vectors_match_snapshots() {
+        let update = env::var_os("UPDATE_TEST_VECTORS").is_some();
+
+        for vector in all().unwrap() {
+            let path = snapshot_path(&vector);
+            if update && PINNED.iter().all(|(name, _)| *name != vector.name) {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, vector.to_hex()).unwrap();
+                continue;
+            }
+
+            let snapshot = fs::read_to_string(&path)
+                .unwrap_or_else(|_| panic!("Missing snapshot {}", path.display()));
+            assert_eq!(
+                snapshot,
+                vector.to_hex(),
+                "{} does not match its snapshot",
+                vector.name
+            );
+        }
+    }
+
+    fn read_snapshot(name: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test_vectors")
+            .join(format!("{}.hex", name));
+        fs::read_to_string(&path).unwrap()
+    }
+
+    #[test]
+    fn test_versioned_snapshots_verify() {
+        // deserialize the pinned bytes rather than a fresh proof, as a verifier receiving an old proof would
+        let fixture = Fixture::new().unwrap();
+        for (name, version) in [
+            ("discrete_log_comparison_v1", ProofVersion::V1),
+            ("discrete_log_comparison_v2", ProofVersion::V2),
+        ] {
+            let snapshot = read_snapshot(name);
+
+            let commitments = Vec::<
+                <PC as PolynomialCommitment<F, DensePolynomial<F>>>::Commitment,
+            >::deserialize(&snapshot_bytes(&snapshot, "commitments")[..])
+            .unwrap();
+            let proof_bytes = snapshot_bytes(&snapshot, "proof");
+            let proof = VersionedProof::<C>::deserialize(&proof_bytes[..]).unwrap();
+            assert_eq!(proof.version(), version);
+
+            let f = LabeledCommitment::new("f".into(), commitments[0], Some(DEGREE_BOUND));
+            let g = LabeledCommitment::new("g".into(), commitments[1], Some(DEGREE_BOUND));
+            let mut fs_rng = FS::initialize(&to_bytes!(FS_SEED).unwrap());
+            DLComparison::<C>::verify_versioned(
+                &fixture.vk,
+                &fixture.ck,
+                &fixture.domain_k,
+                &fixture.domain_h,
+                &f,
+                &g,
+                Some(DEGREE_BOUND),
+                proof,
+                &Context::new(CONTEXT),
+                &mut fs_rng,
+            )
+            .unwrap();
+
+            // bytes of an unknown version are rejected rather than read as another version
+            let mut unknown = proof_bytes.clone();
+            unknown[0] = 0;
+            assert!(VersionedProof::<C>::deserialize(&unknown[..]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_v1_is_versioned_unversioned_proof() {
+        // a proof serialized before versioning becomes a version 1 proof by prepending its version
+        let unversioned = snapshot_bytes(&read_snapshot("discrete_log_comparison"), "proof");
+        let versioned = snapshot_bytes(&read_snapshot("discrete_log_comparison_v1"), "proof");
+        assert_eq!(versioned[0], 1);
+        assert_eq!(versioned[1..], unversioned[..]);
+    }
+
+    #[test]
+    fn test_versioned_snapshots_are_pinned() {
+        for &(name, digest) in PINNED.iter() {
+            let actual = Blake2s::digest(read_snapshot(name).as_bytes())
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>();
+            assert_eq!(actual, digest, "{} changed", name);
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{
+    domains::DomainK,
+    error::{to_pc_error, Error, WitnessError},
+    indexer::{CommittedEncoding, SparseMatrixEncoding},
+    report::VerificationReport,
+    transpose_test::proof::Proof,
+};
+use ark_ff::{to_bytes, One, Zero};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::{LabeledCommitment, PolynomialCommitment};
+use blake2::Blake2s;
+use derivative::Derivative;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context};
+use std::{collections::BTreeMap, marker::PhantomData};
+use zero_over_k::config::{pc, ProtocolConfig};
+use zero_over_k::{
+    trace_span,
+    virtual_oracle::generic_shifting_vo::{vo_term::VOTerm, GenericShiftingVO},
+    vo_constant,
+    zero_over_k::ZeroOverK,
+};
+#[cfg(feature = "prover")]
+use {
+    ark_ff::batch_inversion,
+    ark_poly::{univariate::DensePolynomial, UVPolynomial},
+    ark_poly_commit::LabeledPolynomial,
+    rand::Rng,
+};
+
+pub mod proof;
+mod tests;
+
+/// The public data of a transpose test: the committed encodings over K of a matrix M and of its transpose
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Statement<C: ProtocolConfig> {
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub domain_k: DomainK<C::F>,
+    pub m: CommittedEncoding<pc::Commitment<C>>,
+    pub m_t: CommittedEncoding<pc::Commitment<C>>,
+    pub enforced_degree_bound: Option<usize>,
+}
+
+impl<C: ProtocolConfig> Statement<C> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        to_bytes![
+            self.domain_k.size() as u64,
+            self.m.iter().collect::<Vec<_>>(),
+            self.m_t.iter().collect::<Vec<_>>()
+        ]
+        .map_err(|_| Error::ToBytesError)
+    }
+
+    /// The commitments of both encodings under the labels of `TransposeTest::oracles`
+    fn commitments(&self) -> Vec<LabeledCommitment<pc::Commitment<C>>> {
+        [("m", &self.m), ("m_t", &self.m_t)]
+            .iter()
+            .flat_map(|(prefix, encoding)| encoding.oracles(prefix, self.enforced_degree_bound))
+            .collect()
+    }
+}
+
+/// z appears twice, followed by the row, col and val oracles of M and M^T
+const TRANSPOSE_CHECK_MAPPING: [usize; 8] = [0, 0, 1, 2, 3, 4, 5, 6];
+
+/// Proves that the matrix encoded by M^T is the transpose of the one encoded by M: the multiset of entries
+/// (row, col, val) of M is equal to the multiset of swapped entries (col_t, row_t, val_t) of M^T. A verifier who
+/// trusts the commitments to M can then use those to M^T, e.g. for functions of the transpose, without indexing it
+/// again.
+///
+/// This is a grand product argument, which [`SymmetricTest`](crate::symmetric_test::SymmetricTest) also runs with
+/// M^T = M: for verifier challenges beta and gamma, the prover commits to z with z(1) = 1 and
+///
+/// z(gamma_K * X) * (beta - fingerprint(col_t, row_t, val_t)) = z(X) * (beta - fingerprint(row, col, val))
+///
+/// over K, where fingerprint(row, col, val) = row + gamma * col + gamma^2 * val and gamma_K generates K. Padding
+/// entries are part of the multiset, so both encodings must be padded with `PaddingStrategy::Origin`, or with padding
+/// entries that are the transposes of each other.
+pub struct TransposeTest<C: ProtocolConfig> {
+    _config: PhantomData<C>,
+}
+
+impl<C: ProtocolConfig> TransposeTest<C> {
+    pub const PROTOCOL_NAME: &'static [u8] = b"Transpose Test";
+
+    /// Check that every entry of M is paired with a transposed entry of M^T, failing on the first unpaired element of
+    /// K in the encoding of M before any work is done. With the `validate-witness` feature the prover runs this check
+    /// itself.
+    pub fn validate_witness(
+        statement: &Statement<C>,
+        m: &SparseMatrixEncoding<C::F>,
+        m_t: &SparseMatrixEncoding<C::F>,
+    ) -> Result<(), Error> {
+        let entries = Self::entries(&statement.domain_k, m);
+
+        let mut multiplicities = BTreeMap::new();
+        for &(row, col, val) in Self::entries(&statement.domain_k, m_t).iter() {
+            *multiplicities.entry((col, row, val)).or_insert(0isize) += 1;
+        }
+        for entry in entries.iter() {
+            *multiplicities.entry(*entry).or_insert(0isize) -= 1;
+        }
+
+        // both encodings have |K| entries, so they are paired if no entry of M is left over
+        match entries.iter().position(|entry| multiplicities[entry] != 0) {
+            Some(index) => Err(Error::InvalidWitness(WitnessError::NoTransposedEntry {
+                index,
+            })),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "prover")]
+    /// The randomness of each encoding is given in the order of [`SparseMatrixEncoding::iter`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove<R: Rng>(
+        ck: &pc::CommitterKey<C>,
+        statement: &Statement<C>,
+        m: &SparseMatrixEncoding<C::F>,
+        m_rands: &[pc::Randomness<C>],
+        m_t: &SparseMatrixEncoding<C::F>,
+        m_t_rands: &[pc::Randomness<C>],
+        context: &Context,
+        fs_rng: &mut C::FS,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!("transpose_test::prove");
+        #[cfg(feature = "validate-witness")]
+        Self::validate_witness(statement, m, m_t)?;
+
+        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+            .map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        let mut round = ChallengeSet::new(&mut *fs_rng, b"fingerprint");
+        let beta: C::F = round.challenge(b"beta");
+        let gamma: C::F = round.challenge(b"gamma");
+
+        let domain_k = &statement.domain_k;
+        let bound = statement.enforced_degree_bound;
+
+        // Step 1: interpolate the running product z, with z(gamma_K^0) = 1 and each step multiplying by the ratio of
+        // the fingerprints of an entry of M and of a swapped entry of M^T. The denominators only vanish if beta hits
+        // the fingerprint of a swapped entry, which happens with negligible probability.
+        let entries = Self::entries(domain_k, m);
+        let mut denominators = Self::entries(domain_k, m_t)
+            .iter()
+            .map(|&(row, col, val)| beta - Self::fingerprint(gamma, col, row, val))
+            .collect::<Vec<_>>();
+        batch_inversion(&mut denominators);
+
+        let mut z_evals = Vec::with_capacity(domain_k.size());
+        let mut acc = C::F::one();
+        for (&(row, col, val), denominator) in entries.iter().zip(denominators) {
+            z_evals.push(acc);
+            acc *= (beta - Self::fingerprint(gamma, row, col, val)) * denominator;
+        }
+
+        let z = DensePolynomial::from_coefficients_vec(domain_k.ifft(&z_evals));
+        let z = LabeledPolynomial::new(String::from("z"), z, bound, Some(1));
+
+        let (z_commit, z_rand) =
+            C::PC::commit(ck, [&z], Some(rng)).map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let fs_bytes = &to_bytes![z_commit].map_err(|_| Error::ToBytesError)?;
+        fs_rng.absorb(fs_bytes);
+
+        // Step 2: open z at 1 = gamma_K^0, where the product starts
+        let z_opening_proof = C::PC::open(
+            ck,
+            [&z],
+            [&z_commit[0]],
+            &C::F::one(),
+            C::F::one(),
+            [&z_rand[0]],
+            Some(rng),
+        )
+        .map_err(to_pc_error::<C::F, C::PC>)?;
+
+        // Step 3: Zero over K for the product steps
+        let oracles = Self::oracles(&[m, m_t], bound);
+        let concrete_oracles = [&z].into_iter().chain(oracles.iter()).collect::<Vec<_>>();
+        let commitments = [z_commit[0].clone()]
+            .into_iter()
+            .chain(statement.commitments())
+            .collect::<Vec<_>>();
+        let rands = [&z_rand[..], m_rands, m_t_rands].concat();
+
+        let transpose_check_vo = GenericShiftingVO::new(
+            &TRANSPOSE_CHECK_MAPPING,
+            &Self::transpose_check_alphas(domain_k),
+            Self::transpose_check(beta, gamma),
+        )?
+        .with_scaling_factor(2);
+        let transpose_check_proof = ZeroOverK::<C>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            bound,
+            &transpose_check_vo,
+            domain_k,
+            ck,
+            &context.bound_child::<Blake2s, _>(b"transpose_check", &[beta, gamma]),
+            rng,
+        )?;
+
+        Ok(Proof {
+            z_commit: z_commit[0].commitment().clone(),
+            z_opening_proof,
+            transpose_check_proof,
+        })
+    }
+
+    pub fn verify(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> Result<(), Error> {
+        Self::verify_with_report(vk, statement, proof, context, fs_rng).into_result()
+    }
+
+    /// Verify every sub-protocol of the proof and report on each of them
+    pub fn verify_with_report(
+        vk: &pc::VerifierKey<C>,
+        statement: &Statement<C>,
+        proof: Proof<C>,
+        context: &Context,
+        fs_rng: &mut C::FS,
+    ) -> VerificationReport {
+        let _span = trace_span!("transpose_test::verify");
+        let mut report = VerificationReport::builder("transpose_test");
+        let bound = statement.enforced_degree_bound;
+
+        let mut challenges = (C::F::zero(), C::F::zero());
+        report.check("transcript", || {
+            let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, context, statement.to_bytes()?]
+                .map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            let mut round = ChallengeSet::new(&mut *fs_rng, b"fingerprint");
+            challenges = (round.challenge(b"beta"), round.challenge(b"gamma"));
+            Ok::<(), Error>(())
+        });
+        let (beta, gamma) = challenges;
+
+        let z_commit = LabeledCommitment::new(String::from("z"), proof.z_commit, bound);
+
+        report.check("z_commit", || {
+            let fs_bytes =
+                &to_bytes![[z_commit.clone()].to_vec()].map_err(|_| Error::ToBytesError)?;
+            fs_rng.absorb(fs_bytes);
+            Ok::<(), Error>(())
+        });
+
+        report.check("z_at_one", || {
+            match C::PC::check(
+                vk,
+                ark_std::slice::from_ref(&z_commit),
+                &C::F::one(),
+                vec![C::F::one()],
+                &proof.z_opening_proof,
+                C::F::one(),
+                None,
+            ) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(Error::BatchCheckError),
+                Err(e) => Err(to_pc_error::<C::F, C::PC>(e)),
+            }
+        });
+
+        report.check("transpose_check", || {
+            let commitments = [z_commit.clone()]
+                .into_iter()
+                .chain(statement.commitments())
+                .collect::<Vec<_>>();
+            let transpose_check_vo = GenericShiftingVO::new(
+                &TRANSPOSE_CHECK_MAPPING,
+                &Self::transpose_check_alphas(&statement.domain_k),
+                Self::transpose_check(beta, gamma),
+            )?
+            .with_scaling_factor(2);
+
+            ZeroOverK::<C>::verify(
+                proof.transpose_check_proof,
+                &commitments,
+                bound,
+                &transpose_check_vo,
+                &statement.domain_k,
+                vk,
+                &context.bound_child::<Blake2s, _>(b"transpose_check", &[beta, gamma]),
+            )
+            .map_err(Error::from)
+        });
+
+        report.finish()
+    }
+
+    #[cfg(feature = "prover")]
+    /// Random linear combination of the coordinates of an entry
+    fn fingerprint(gamma: C::F, row: C::F, col: C::F, val: C::F) -> C::F {
+        row + gamma * (col + gamma * val)
+    }
+
+    /// The (row, col, val) entries of an encoding, in the order of K
+    fn entries(
+        domain_k: &GeneralEvaluationDomain<C::F>,
+        encoding: &SparseMatrixEncoding<C::F>,
+    ) -> Vec<(C::F, C::F, C::F)> {
+        let row_evals = domain_k.fft(encoding.row.polynomial());
+        let col_evals = domain_k.fft(encoding.col.polynomial());
+        let val_evals = domain_k.fft(encoding.val.polynomial());
+
+        row_evals
+            .into_iter()
+            .zip(col_evals)
+            .zip(val_evals)
+            .map(|((row, col), val)| (row, col, val))
+            .collect()
+    }
+
+    #[cfg(feature = "prover")]
+    /// The row, col and val polynomials of both encodings, under the labels of `Statement::commitments`
+    fn oracles(
+        encodings: &[&SparseMatrixEncoding<C::F>],
+        enforced_degree_bound: Option<usize>,
+    ) -> Vec<LabeledPolynomial<C::F, DensePolynomial<C::F>>> {
+        ["m", "m_t"]
+            .iter()
+            .zip(encodings)
+            .flat_map(|(prefix, encoding)| encoding.oracles(prefix, enforced_degree_bound))
+            .collect()
+    }
+
+    /// z is shifted by gamma_K in the second term, every other oracle appears once unshifted
+    fn transpose_check_alphas(domain_k: &GeneralEvaluationDomain<C::F>) -> Vec<C::F> {
+        let mut alphas = vec![C::F::one(); TRANSPOSE_CHECK_MAPPING.len()];
+        alphas[1] = domain_k.element(1);
+        alphas
+    }
+
+    /// z(gamma_K * X) * (beta - fingerprint(col_t, row_t, val_t)) - z(X) * (beta - fingerprint(row, col, val)), over
+    /// the terms [X, z(X), z(gamma_K * X), row(X), col(X), val(X), row_t(X), col_t(X), val_t(X)]
+    fn transpose_check(beta: C::F, gamma: C::F) -> impl Fn(&[VOTerm<C::F>]) -> VOTerm<C::F> {
+        move |terms: &[VOTerm<C::F>]| {
+            let fingerprint = |row: &VOTerm<C::F>, col: &VOTerm<C::F>, val: &VOTerm<C::F>| {
+                row.clone()
+                    + vo_constant!(gamma) * (col.clone() + vo_constant!(gamma) * val.clone())
+            };
+            terms[2].clone() * (vo_constant!(beta) - fingerprint(&terms[7], &terms[6], &terms[8]))
+                - terms[1].clone()
+                    * (vo_constant!(beta) - fingerprint(&terms[3], &terms[4], &terms[5]))
+        }
+    }
+}
@@ -0,0 +1,164 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::config::Config;
+    use crate::{
+        error::{Error, WitnessError},
+        indexer::{
+            test_utils::{committed, domains, encode},
+            PaddingStrategy,
+        },
+        transpose_test::{Statement, TransposeTest},
+    };
+
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::to_bytes;
+    use ark_poly::EvaluationDomain;
+    use ark_poly_commit::PolynomialCommitment;
+    use ark_std::rand::thread_rng;
+    use blake2::Blake2s;
+    use fiat_shamir_rng::{Context, FiatShamirRng, SimpleHashFiatShamirRng};
+    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use rand_chacha::ChaChaRng;
+
+    type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type F = Fr;
+    type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
+
+    // M and M^T values
+    /*
+        1, 2, 0, 0      1, 0, 0, 0
+        0, 0, 0, 3      2, 0, 0, 4
+        0, 0, 0, 0      0, 0, 0, 5
+        0, 4, 5, 0      0, 3, 0, 0
+    */
+    fn m_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 0, F::from(1u64)),
+            (0, 1, F::from(2u64)),
+            (1, 3, F::from(3u64)),
+            (3, 1, F::from(4u64)),
+            (3, 2, F::from(5u64)),
+        ]
+    }
+
+    // listed in row-major order of M^T, which is not the order of M
+    fn transposed_entries() -> Vec<(usize, usize, F)> {
+        vec![
+            (0, 0, F::from(1u64)),
+            (1, 0, F::from(2u64)),
+            (1, 3, F::from(4u64)),
+            (2, 3, F::from(5u64)),
+            (3, 1, F::from(3u64)),
+        ]
+    }
+
+    fn run_transpose_test(
+        m_entries: &[(usize, usize, F)],
+        m_t_entries: &[(usize, usize, F)],
+        padding: PaddingStrategy,
+    ) -> Result<(), Error> {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let enforced_degree_bound = domain_k.size() + 1;
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let (m, m_t) = (
+            encode(m_entries, &domain_k, &domain_h, padding),
+            encode(m_t_entries, &domain_k, &domain_h, padding),
+        );
+        let (m_commit, m_rands) = committed::<F, PC>(&ck, &m);
+        let (m_t_commit, m_t_rands) = committed::<F, PC>(&ck, &m_t);
+
+        let statement = Statement::<C> {
+            domain_k,
+            m: m_commit,
+            m_t: m_t_commit,
+            enforced_degree_bound: Some(enforced_degree_bound),
+        };
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        let proof = TransposeTest::<C>::prove(
+            &ck,
+            &statement,
+            &m,
+            &m_rands,
+            &m_t,
+            &m_t_rands,
+            &Context::new(b"test"),
+            &mut fs_rng,
+            rng,
+        )?;
+
+        let mut fs_rng = FS::initialize(&to_bytes!(b"Testing :)").unwrap());
+        TransposeTest::<C>::verify(&vk, &statement, proof, &Context::new(b"test"), &mut fs_rng)
+    }
+
+    #[test]
+    fn test_transpose() {
+        assert_eq!(
+            run_transpose_test(&m_entries(), &transposed_entries(), PaddingStrategy::Origin),
+            Ok(())
+        );
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_not_transposed() {
+        // M is not symmetric
+        assert!(run_transpose_test(&m_entries(), &m_entries(), PaddingStrategy::Origin).is_err());
+
+        let mut entries = transposed_entries();
+        entries[2].2 = F::from(6u64);
+        assert!(run_transpose_test(&m_entries(), &entries, PaddingStrategy::Origin).is_err());
+    }
+
+    #[cfg(not(feature = "validate-witness"))]
+    #[test]
+    fn test_unpaired_padding() {
+        // the padding entries repeat (3, 2) in M and (3, 1) in M^T
+        assert!(run_transpose_test(
+            &m_entries(),
+            &transposed_entries(),
+            PaddingStrategy::RepeatLast
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_witness() {
+        let rng = &mut thread_rng();
+        let (domain_k, domain_h) = domains(8, 4);
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[domain_k.size() + 1])).unwrap();
+
+        let validate = |m_t_entries: &[(usize, usize, F)]| {
+            let (m, m_t) = (
+                encode(&m_entries(), &domain_k, &domain_h, PaddingStrategy::Origin),
+                encode(m_t_entries, &domain_k, &domain_h, PaddingStrategy::Origin),
+            );
+            let statement = Statement::<C> {
+                domain_k,
+                m: committed::<F, PC>(&ck, &m).0,
+                m_t: committed::<F, PC>(&ck, &m_t).0,
+                enforced_degree_bound: None,
+            };
+            TransposeTest::<C>::validate_witness(&statement, &m, &m_t)
+        };
+
+        assert_eq!(validate(&transposed_entries()), Ok(()));
+
+        // (1, 3, 3) of M is transposed to (3, 1, 6)
+        let mut entries = transposed_entries();
+        entries[4].2 = F::from(6u64);
+        assert_eq!(
+            validate(&entries),
+            Err(Error::InvalidWitness(WitnessError::NoTransposedEntry {
+                index: 2
+            }))
+        );
+    }
+}
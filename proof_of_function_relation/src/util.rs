@@ -2,7 +2,18 @@ use ark_ff::{FftField, Field, PrimeField};
 use ark_poly::{
     univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
 };
-use ark_poly_commit::LabeledPolynomial;
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, LinearCombination};
+use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    domains::{DomainH, DomainK},
+    error::Error,
+};
+
+/// Number of consecutive elements of a geometric sequence generated from a single power of its ratio
+pub const SEQUENCE_CHUNK_SIZE: usize = 1 << 10;
 
 #[inline]
 pub fn powers_of<F>(scalar: F) -> impl Iterator<Item = F>
@@ -33,9 +44,133 @@ pub fn generate_sequence<F: PrimeField>(
     concatenation
 }
 
+/// The polynomial taking the values of [`generate_sequence`] over `domain`, padded with zeros. The sequence is
+/// written in place in chunks of [`SEQUENCE_CHUNK_SIZE`] elements, each starting from its own power of the ratio, so
+/// that with the `parallel` feature both the chunks and the iFFT run on multiple threads.
+pub fn interpolate_sequence<F: PrimeField>(
+    common_ratio: F,
+    initial_terms: &[F],
+    subsequence_lengths: &[usize],
+    domain: &GeneralEvaluationDomain<F>,
+) -> DensePolynomial<F> {
+    assert_ne!(initial_terms.len(), 0);
+    assert_eq!(subsequence_lengths.len(), initial_terms.len());
+    assert!(subsequence_lengths.iter().sum::<usize>() <= domain.size());
+
+    let mut evals = vec![F::zero(); domain.size()];
+    let mut rest = evals.as_mut_slice();
+    for (term, &length) in initial_terms.iter().zip(subsequence_lengths) {
+        let (subsequence, tail) = rest.split_at_mut(length);
+        rest = tail;
+
+        #[cfg(feature = "parallel")]
+        let chunks = subsequence.par_chunks_mut(SEQUENCE_CHUNK_SIZE);
+        #[cfg(not(feature = "parallel"))]
+        let chunks = subsequence.chunks_mut(SEQUENCE_CHUNK_SIZE);
+
+        chunks.enumerate().for_each(|(i, chunk)| {
+            let first = *term * common_ratio.pow([(i * SEQUENCE_CHUNK_SIZE) as u64]);
+            for (value, ratio_power) in chunk.iter_mut().zip(powers_of(common_ratio)) {
+                *value = first * ratio_power;
+            }
+        });
+    }
+
+    domain.ifft_in_place(&mut evals);
+    DensePolynomial::from_coefficients_vec(evals)
+}
+
+/// Initial values and lengths of a sequence over K made of a geometric sequence of `length` elements starting at
+/// `first`, followed by `padding` on the remaining |K| - `length` elements. Empty sub-sequences are left out, and the
+/// padding is only evaluated when there is room for it. Fails rather than wrapping around when the sequence does not
+/// fit in K.
+pub fn padded_sequence<F: FftField>(
+    first: F,
+    length: usize,
+    padding: impl FnOnce() -> F,
+    domain_k: &DomainK<F>,
+) -> Result<(Vec<F>, Vec<usize>), Error> {
+    let to_pad = domain_k.size().checked_sub(length).ok_or_else(|| {
+        Error::InvalidSequenceDescription(format!(
+            "A sequence of {} elements does not fit in a domain K of size {}",
+            length,
+            domain_k.size()
+        ))
+    })?;
+
+    Ok(match (length, to_pad) {
+        (_, 0) => (vec![first], vec![length]),
+        (0, _) => (vec![padding()], vec![to_pad]),
+        _ => (vec![first, padding()], vec![length, to_pad]),
+    })
+}
+
+/// The number of rows from t to `number_of_rows` - 1, failing when t is past the last row
+pub fn rows_from_t(t: usize, number_of_rows: usize) -> Result<usize, Error> {
+    number_of_rows.checked_sub(t).ok_or(Error::T2Large)
+}
+
+/// factor * p, for a public factor. The result keeps the hiding bound of p and takes the enforced degree bound.
+pub fn scale_poly<F: Field>(
+    label: &str,
+    poly: &LabeledPolynomial<F, DensePolynomial<F>>,
+    factor: F,
+    enforced_degree_bound: Option<usize>,
+) -> LabeledPolynomial<F, DensePolynomial<F>> {
+    LabeledPolynomial::new(
+        String::from(label),
+        poly.polynomial() * factor,
+        enforced_degree_bound,
+        poly.hiding_bound(),
+    )
+}
+
+/// The commitment to factor * p and its randomness, derived homomorphically from those of p. The verifier passes no
+/// randomness and ignores the one returned.
+pub fn scale_commitment<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>>(
+    label: &str,
+    commit: &LabeledCommitment<PC::Commitment>,
+    random: Option<&PC::Randomness>,
+    factor: F,
+    enforced_degree_bound: Option<usize>,
+) -> Result<(LabeledCommitment<PC::Commitment>, PC::Randomness), Error> {
+    // all aggregated commitments must share the degree bound of the result
+    let bounded = LabeledCommitment::new(
+        commit.label().clone(),
+        commit.commitment().clone(),
+        enforced_degree_bound,
+    );
+    let lc = LinearCombination::new(label, vec![(factor, commit.label().clone())]);
+    let scaled = PC::aggregate_commitments(&[bounded], random.map(|r| vec![r.clone()]), &lc)?;
+    Ok(scaled)
+}
+
+/// The accumulator of a sum over a domain D: starts at 0 and moves by term - sum / |D| at every step, so that it
+/// cycles back to 0 when the terms add up to the sum
+pub fn accumulator<F: FftField>(
+    label: &str,
+    domain: &GeneralEvaluationDomain<F>,
+    terms: &[F],
+    sum: F,
+    enforced_degree_bound: Option<usize>,
+) -> LabeledPolynomial<F, DensePolynomial<F>> {
+    let step = sum / F::from(domain.size() as u64);
+    let evals = terms
+        .iter()
+        .scan(F::zero(), |acc, &term| {
+            let current = *acc;
+            *acc += term - step;
+            Some(current)
+        })
+        .collect::<Vec<_>>();
+
+    let acc = DensePolynomial::from_coefficients_vec(domain.ifft(&evals));
+    LabeledPolynomial::new(String::from(label), acc, enforced_degree_bound, Some(1))
+}
+
 pub fn gen_t_diag_test_polys<F: FftField>(
-    domain_k: GeneralEvaluationDomain<F>,
-    domain_h: GeneralEvaluationDomain<F>,
+    domain_k: DomainK<F>,
+    domain_h: DomainH<F>,
     degree_bound: Option<usize>,
     hiding_bound: Option<usize>,
 ) -> Vec<LabeledPolynomial<F, DensePolynomial<F>>> {
@@ -0,0 +1,77 @@
+//! FFTs of a prover round, one by one versus batched.
+//!
+//! Run with `cargo bench -p zero_over_k --bench batch_fft [--features parallel] -- <log2 of |K>`. The benchmark
+//! evaluates and interpolates seven polynomials over K, as many as the oracles of the first round of the discrete-log
+//! comparison prover, first sequentially and then with `batch_fft` and `batch_ifft`. Without the `parallel` feature
+//! both runs do the same work, which gives the baseline for the batched timings.
+
+use std::{env, time::Instant};
+
+use ark_bn254::Fr;
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+};
+use ark_std::test_rng;
+use zero_over_k::util::{batch_fft, batch_ifft};
+
+type F = Fr;
+
+const NUM_ORACLES: usize = 7;
+
+fn main() {
+    let log_size = env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<u32>().ok())
+        .unwrap_or(16);
+    let rng = &mut test_rng();
+
+    let domain_k = GeneralEvaluationDomain::<F>::new(1 << log_size).unwrap();
+    let n = domain_k.size();
+
+    let polys = (0..NUM_ORACLES)
+        .map(|_| DensePolynomial::<F>::rand(n - 1, rng))
+        .collect::<Vec<_>>();
+    let poly_refs = polys.iter().collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let evals = polys
+        .iter()
+        .map(|p| domain_k.fft(p.coeffs()))
+        .collect::<Vec<_>>();
+    let sequential_fft = start.elapsed();
+
+    let eval_refs = evals.iter().map(|e| e.as_slice()).collect::<Vec<_>>();
+    let start = Instant::now();
+    let interpolated = eval_refs
+        .iter()
+        .map(|e| DensePolynomial::from_coefficients_vec(domain_k.ifft(e)))
+        .collect::<Vec<_>>();
+    let sequential_ifft = start.elapsed();
+    assert_eq!(interpolated, polys);
+
+    let start = Instant::now();
+    let batched_evals = batch_fft(&domain_k, &poly_refs);
+    let batched_fft = start.elapsed();
+    assert_eq!(batched_evals, evals);
+
+    let start = Instant::now();
+    let batched_interpolated = batch_ifft(&domain_k, &eval_refs);
+    let batched_ifft = start.elapsed();
+    assert_eq!(batched_interpolated, polys);
+
+    println!(
+        "|K| = 2^{} ({} elements), {} oracles, parallel: {}",
+        log_size,
+        n,
+        NUM_ORACLES,
+        cfg!(feature = "parallel")
+    );
+    println!(
+        "FFT:  {:?} one by one, {:?} batched",
+        sequential_fft, batched_fft
+    );
+    println!(
+        "iFFT: {:?} one by one, {:?} batched",
+        sequential_ifft, batched_ifft
+    );
+}
@@ -0,0 +1,108 @@
+//! Peak memory usage of a Zero over K proof.
+//!
+//! Run with `cargo bench -p zero_over_k --bench memory -- <log2 of |K|>`. The benchmark reports the growth of the
+//! resident set size high-water mark (VmHWM, only available on Linux) while proving, next to the size of the
+//! concrete oracles. The prover borrows the concrete oracles, so the growth only accounts for the polynomials the
+//! protocol itself produces (maskings, masked oracles and quotients), not for copies of its inputs.
+
+use std::{env, fs, time::Instant};
+use zero_over_k::config::Config;
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::One;
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+};
+use ark_poly_commit::{LabeledPolynomial, PolynomialCommitment};
+use ark_std::test_rng;
+use blake2::Blake2s;
+use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
+use homomorphic_poly_commit::marlin_kzg::KZG10;
+use rand_chacha::ChaChaRng;
+use zero_over_k::{
+    virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
+    zero_over_k::ZeroOverK,
+};
+
+type F = Fr;
+type PC = KZG10<Bn254>;
+type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+type C = Config<F, PC, FS>;
+
+/// Resident set size high-water mark of the current process, in KiB
+fn peak_rss_kib() -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find(|line| line.starts_with("VmHWM:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kib| kib.parse().ok())
+}
+
+/// Reset the high-water mark to the current resident set size, so that it only reflects what happens afterwards
+fn reset_peak_rss() {
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+fn main() {
+    let log_size = env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<u32>().ok())
+        .unwrap_or(14);
+    let rng = &mut test_rng();
+
+    let domain_k = GeneralEvaluationDomain::<F>::new(1 << log_size).unwrap();
+    let n = domain_k.size();
+
+    let max_degree = 2 * n + 2;
+    let pp = PC::setup(max_degree, None, rng).unwrap();
+    let (ck, _) = PC::trim(&pp, max_degree, 1, Some(&[2])).unwrap();
+
+    let f = DensePolynomial::<F>::rand(n - 1, rng);
+    let f = LabeledPolynomial::new(String::from("f"), f, None, Some(1));
+    let g = LabeledPolynomial::new(String::from("g"), f.polynomial().clone(), None, Some(1));
+
+    let concrete_oracles = [&f, &g];
+    let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng)).unwrap();
+
+    let alphas = [F::one(), F::one()];
+    let equality_vo = GenericShiftingVO::new(&[0, 1], &alphas, presets::equality_check)
+        .unwrap()
+        .with_scaling_factor(1);
+
+    reset_peak_rss();
+    let rss_before = peak_rss_kib();
+    let start = Instant::now();
+    ZeroOverK::<C>::prove(
+        &concrete_oracles,
+        &commitments,
+        &rands,
+        None,
+        &equality_vo,
+        &domain_k,
+        &ck,
+        &Context::new(b"memory benchmark"),
+        rng,
+    )
+    .unwrap();
+    let elapsed = start.elapsed();
+
+    let oracle_kib = concrete_oracles
+        .iter()
+        .map(|oracle| oracle.polynomial().coeffs.len() * std::mem::size_of::<F>())
+        .sum::<usize>()
+        / 1024;
+
+    println!("|K| = 2^{} ({} elements)", log_size, n);
+    println!("proving time: {:?}", elapsed);
+    println!("concrete oracles: {} KiB", oracle_kib);
+    match (rss_before, peak_rss_kib()) {
+        (Some(before), Some(after)) => println!(
+            "peak RSS: {} KiB before proving, {} KiB after (+{} KiB)",
+            before,
+            after,
+            after - before
+        ),
+        _ => println!("peak RSS: unavailable on this platform"),
+    }
+}
@@ -0,0 +1,87 @@
+//! Cooperative cancellation of long-running provers. A [`CancellationToken`] is passed to a prover, which polls it
+//! at [`checkpoint`]s: between the rounds of ZeroOverK, before each batch of FFTs and between the sub-protocols of the
+//! composed provers. Once the token is cancelled, the next checkpoint returns [`Error::Aborted`] and the prover unwinds
+//! through `?` without finishing the proof.
+//!
+//! ```ignore
+//! let token = CancellationToken::new().with_progress(|stage| println!("reached {}", stage));
+//! let watchdog = token.clone();
+//! std::thread::spawn(move || {
+//!     std::thread::sleep(timeout);
+//!     watchdog.cancel();
+//! });
+//!
+//! let proof = ZeroOverK::<C>::prove_with_cancellation(..., Some(&token), rng)?;
+//! ```
+//!
+//! The composed provers take the token through their committer key, see
+//! `proof_of_function_relation::checked_key`, and hand it down to their sub-protocols. Checkpoints are only polled
+//! between batches of FFTs, so the FFTs of a batch run with the `parallel` feature are not interrupted midway.
+//! Without a token, checkpoints always succeed.
+
+use crate::error::Error;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Called with the name of each checkpoint a prover reaches, e.g. to report progress or to cancel after a deadline
+pub type ProgressCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// A flag shared by the clones of a token, which the provers it is passed to poll
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    progress: Option<ProgressCallback>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `progress` at each checkpoint, before the token is polled
+    pub fn with_progress(mut self, progress: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Have the provers polling this token or any of its clones abort at their next checkpoint
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn poll(&self, stage: &str) -> Result<(), Error> {
+        if let Some(progress) = &self.progress {
+            progress(stage);
+        }
+        if self.is_cancelled() {
+            return Err(Error::Aborted(String::from(stage)));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Poll `cancellation`, if any, failing with [`Error::Aborted`] at `stage` if it was cancelled
+pub fn checkpoint(cancellation: Option<&CancellationToken>, stage: &str) -> Result<(), Error> {
+    match cancellation {
+        Some(token) => token.poll(stage),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,62 @@
+use ark_ff::PrimeField;
+use fiat_shamir_rng::FiatShamirRng;
+use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use std::marker::PhantomData;
+
+/// The field, polynomial commitment scheme and Fiat-Shamir RNG a protocol is instantiated with. Protocols, their
+/// proofs and statements are parameterized by a config, so that the bounds on these three types are stated once here
+/// instead of on every struct and impl, e.g. `ZeroOverK<C>` in place of `ZeroOverK<F, PC, FS>`.
+///
+/// The field is only required to be prime, as ZeroOverK and Marlin need nothing more. Protocols that also take square
+/// roots, e.g. to compare discrete logs, require `C::F: SquareRootField` on top.
+pub trait ProtocolConfig {
+    type F: PrimeField;
+    type PC: AdditivelyHomomorphicPCS<Self::F>;
+    type FS: FiatShamirRng;
+}
+
+/// A [`ProtocolConfig`] assembled from its components
+pub struct Config<F, PC, FS> {
+    _field: PhantomData<F>,
+    _pc: PhantomData<PC>,
+    _fs: PhantomData<FS>,
+}
+
+impl<F, PC, FS> ProtocolConfig for Config<F, PC, FS>
+where
+    F: PrimeField,
+    PC: AdditivelyHomomorphicPCS<F>,
+    FS: FiatShamirRng,
+{
+    type F = F;
+    type PC = PC;
+    type FS = FS;
+}
+
+/// The types of the polynomial commitment scheme of a config. `C::PC::Commitment` is ambiguous to the compiler, as the
+/// scheme is a `PolynomialCommitment` for a single field and polynomial type only through the config's bounds.
+pub mod pc {
+    use super::ProtocolConfig;
+    use ark_poly::univariate::DensePolynomial;
+    use ark_poly_commit::PolynomialCommitment;
+
+    type Scheme<C> = <C as ProtocolConfig>::PC;
+    type Field<C> = <C as ProtocolConfig>::F;
+
+    pub type UniversalParams<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::UniversalParams;
+    pub type CommitterKey<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::CommitterKey;
+    pub type VerifierKey<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::VerifierKey;
+    pub type Commitment<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::Commitment;
+    pub type Randomness<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::Randomness;
+    pub type Proof<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::Proof;
+    pub type BatchProof<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::BatchProof;
+    pub type Error<C> =
+        <Scheme<C> as PolynomialCommitment<Field<C>, DensePolynomial<Field<C>>>>::Error;
+}
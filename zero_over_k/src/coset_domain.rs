@@ -0,0 +1,120 @@
+//! Multiplicative cosets c·K of an evaluation domain K, over which ZeroOverK (and the protocols built on it) can
+//! prove that a virtual oracle vanishes. The coset with offset 1 is K itself.
+
+use crate::util::shift_dense_poly;
+use ark_ff::{FftField, ToBytes};
+use ark_poly::{
+    univariate::{DensePolynomial, SparsePolynomial},
+    EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+};
+use rand::Rng;
+use std::io::{Result as IoResult, Write};
+
+/// The coset `offset`·K of a domain K
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CosetDomain<F: FftField> {
+    pub domain: GeneralEvaluationDomain<F>,
+    pub offset: F,
+}
+
+impl<F: FftField> CosetDomain<F> {
+    /// The coset `offset`·K, or `None` if the offset is zero
+    pub fn new(domain: GeneralEvaluationDomain<F>, offset: F) -> Option<Self> {
+        if offset.is_zero() {
+            return None;
+        }
+        Some(Self { domain, offset })
+    }
+
+    /// K itself
+    pub fn subgroup(domain: GeneralEvaluationDomain<F>) -> Self {
+        Self {
+            domain,
+            offset: F::one(),
+        }
+    }
+
+    pub fn is_subgroup(&self) -> bool {
+        self.offset.is_one()
+    }
+
+    pub fn size(&self) -> usize {
+        self.domain.size()
+    }
+
+    /// The i-th element offset·ω^i
+    pub fn element(&self, i: usize) -> F {
+        self.offset * self.domain.element(i)
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = F> + '_ {
+        self.domain.elements().map(move |x| self.offset * x)
+    }
+
+    /// The vanishing polynomial X^n - offset^n of the coset
+    pub fn vanishing_polynomial(&self) -> SparsePolynomial<F> {
+        let size = self.size();
+        SparsePolynomial::from_coefficients_vec(vec![
+            (0, -self.offset.pow([size as u64])),
+            (size, F::one()),
+        ])
+    }
+
+    pub fn evaluate_vanishing_polynomial(&self, point: F) -> F {
+        let size = [self.size() as u64];
+        point.pow(size) - self.offset.pow(size)
+    }
+
+    /// Evaluate a polynomial over the coset, in the order of [`Self::elements`]
+    pub fn fft(&self, poly: &DensePolynomial<F>) -> Vec<F> {
+        self.domain.fft(&shift_dense_poly(poly, &self.offset))
+    }
+
+    /// Interpolate the polynomial taking `evals` over the coset, in the order of [`Self::elements`]
+    pub fn ifft(&self, evals: &[F]) -> DensePolynomial<F> {
+        let shifted = DensePolynomial::from_coefficients_vec(self.domain.ifft(evals));
+        shift_dense_poly(&shifted, &self.offset.inverse().unwrap())
+    }
+
+    /// Split the coset into the cosets offset·K' and offset·ω·K' of the subgroup K' of half its size, which hold its
+    /// elements of even and odd index. The vanishing polynomial of the coset is the product of theirs. `None` when
+    /// the coset has a single element.
+    pub fn split(&self) -> Option<(Self, Self)> {
+        let half = GeneralEvaluationDomain::new(self.size() / 2).filter(|_| self.size() > 1)?;
+        let even = Self {
+            domain: half,
+            offset: self.offset,
+        };
+        let odd = Self {
+            domain: half,
+            offset: self.offset * self.domain.element(1),
+        };
+        Some((even, odd))
+    }
+
+    /// Sample an element at which the vanishing polynomial of the coset does not vanish
+    pub fn sample_element_outside_domain<R: Rng>(&self, rng: &mut R) -> F {
+        let mut element = F::rand(rng);
+        while self.evaluate_vanishing_polynomial(element).is_zero() {
+            element = F::rand(rng);
+        }
+        element
+    }
+}
+
+impl<F: FftField> From<GeneralEvaluationDomain<F>> for CosetDomain<F> {
+    fn from(domain: GeneralEvaluationDomain<F>) -> Self {
+        Self::subgroup(domain)
+    }
+}
+
+/// The coset is bound to transcripts by its offset, which K itself omits so that proofs over K are unchanged
+impl<F: FftField> ToBytes for CosetDomain<F> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.size() as u64).write(&mut writer)?;
+        if !self.is_subgroup() {
+            self.offset.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
@@ -22,9 +22,20 @@ pub enum Error {
     MissingCommitment(String),
     InputLengthError(String),
     MismatchedDegreeBounds(String),
+    /// A polynomial exceeds its degree bound, or a VO the degree allowed by its terms and scaling factor
+    DegreeBoundExceeded(String),
+    /// A label is not the one the protocol expects, see `zero_over_k::labels`
+    LabelMismatch(String),
 
     UnsupportedDegree(String),
 
+    /// A commitment does not hide its polynomial although the commit policy requires it
+    EmptyRandomness(String),
+
+    /// The prover was cancelled through its [`CancellationToken`](crate::cancellation::CancellationToken), at the
+    /// given checkpoint
+    Aborted(String),
+
     VOFailedToInstantiate,
     VOFailedToCompute,
 }
@@ -43,8 +54,13 @@ where
 
 impl From<homomorphic_poly_commit::error::Error> for Error {
     fn from(err: homomorphic_poly_commit::error::Error) -> Self {
-        Self::PCError {
-            error: format!("{:?}", err),
+        match err {
+            homomorphic_poly_commit::error::Error::EmptyRandomness(label) => {
+                Self::EmptyRandomness(label)
+            }
+            err => Self::PCError {
+                error: format!("{:?}", err),
+            },
         }
     }
 }
@@ -1,4 +1,14 @@
+#[cfg(feature = "prover")]
+pub mod cancellation;
+pub mod config;
+pub mod coset_domain;
 pub mod error;
+#[cfg(feature = "prover")]
+pub mod masking;
+pub mod proof_shape;
+#[cfg(feature = "prover")]
+pub mod quotient;
+pub mod trace;
 pub mod util;
 pub mod virtual_oracle;
 pub mod zero_over_k;
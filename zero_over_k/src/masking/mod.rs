@@ -0,0 +1,74 @@
+//! Masking polynomials: random polynomials m = r * Z that vanish over a domain K, or a subset of points, so that
+//! adding m to an oracle hides its evaluations outside the vanishing set without changing it over the set.
+//!
+//! With `k` random coefficients in r, the evaluations of h + m at any `k` points outside the set are uniformly random
+//! and independent of h, since Z is non-zero there and the evaluations of r at `k` distinct points are uniform. A
+//! prover that reveals at most `k` evaluations of a masked oracle, including the ones derived from its openings,
+//! therefore reveals nothing about h. ZeroOverK masks each oracle with [`MASKING_DEGREE_BOUND`] random coefficients.
+//!
+//! [`MASKING_DEGREE_BOUND`]: crate::zero_over_k::MASKING_DEGREE_BOUND
+
+use crate::coset_domain::CosetDomain;
+use ark_ff::{FftField, Zero};
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use rand::Rng;
+
+mod tests;
+
+/// A masking polynomial, with the random polynomial it is made of
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Masking<F: FftField> {
+    /// r, with the requested number of random coefficients
+    pub random: DensePolynomial<F>,
+    /// m = r * Z, where Z vanishes over the set
+    pub masking: DensePolynomial<F>,
+}
+
+/// The monic polynomial of least degree that vanishes over `points`, which must be distinct
+pub fn vanishing_on_subset<F: FftField>(points: &[F]) -> DensePolynomial<F> {
+    points.iter().fold(
+        DensePolynomial::from_coefficients_vec(vec![F::one()]),
+        |acc, point| &acc * &DensePolynomial::from_coefficients_vec(vec![-*point, F::one()]),
+    )
+}
+
+/// A masking polynomial that vanishes wherever `vanishing` does, with `num_random_coefficients` random coefficients.
+/// Without random coefficients the masking is zero, and hides nothing.
+pub fn mask<F: FftField, R: Rng>(
+    vanishing: &DensePolynomial<F>,
+    num_random_coefficients: usize,
+    rng: &mut R,
+) -> Masking<F> {
+    if num_random_coefficients == 0 {
+        return Masking {
+            random: DensePolynomial::zero(),
+            masking: DensePolynomial::zero(),
+        };
+    }
+
+    let random = DensePolynomial::rand(num_random_coefficients - 1, rng);
+    let masking = &random * vanishing;
+    Masking { random, masking }
+}
+
+/// A masking polynomial that vanishes over `domain`
+pub fn mask_domain<F: FftField, R: Rng>(
+    domain: &CosetDomain<F>,
+    num_random_coefficients: usize,
+    rng: &mut R,
+) -> Masking<F> {
+    mask(
+        &domain.vanishing_polynomial().into(),
+        num_random_coefficients,
+        rng,
+    )
+}
+
+/// A masking polynomial that vanishes over `points`, which must be distinct
+pub fn mask_subset<F: FftField, R: Rng>(
+    points: &[F],
+    num_random_coefficients: usize,
+    rng: &mut R,
+) -> Masking<F> {
+    mask(&vanishing_on_subset(points), num_random_coefficients, rng)
+}
@@ -0,0 +1,75 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::{
+        coset_domain::CosetDomain,
+        masking::{mask, mask_domain, mask_subset, vanishing_on_subset},
+    };
+    use ark_bn254::Fr;
+    use ark_ff::{One, UniformRand, Zero};
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial,
+    };
+    use ark_std::test_rng;
+
+    type F = Fr;
+
+    #[test]
+    fn test_mask_domain() {
+        let rng = &mut test_rng();
+        let domain = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        for domain in [
+            domain.into(),
+            CosetDomain::new(domain, F::from(3u64)).unwrap(),
+        ] {
+            for num_random_coefficients in [1, 2, 5] {
+                let masking = mask_domain(&domain, num_random_coefficients, rng);
+                assert_eq!(masking.random.degree(), num_random_coefficients - 1);
+                assert_eq!(
+                    masking.masking.degree(),
+                    domain.size() + num_random_coefficients - 1
+                );
+                for element in domain.elements() {
+                    assert!(masking.masking.evaluate(&element).is_zero());
+                }
+
+                // outside the domain, the masking is r scaled by the vanishing polynomial
+                let point = F::rand(rng);
+                assert_eq!(
+                    masking.masking.evaluate(&point),
+                    masking.random.evaluate(&point) * domain.evaluate_vanishing_polynomial(point)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mask_subset() {
+        let rng = &mut test_rng();
+        let domain = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let subset = domain.elements().step_by(3).collect::<Vec<_>>();
+
+        let vanishing = vanishing_on_subset(&subset);
+        assert_eq!(vanishing.degree(), subset.len());
+        let masking = mask_subset(&subset, 3, rng);
+        for (i, element) in domain.elements().enumerate() {
+            assert_eq!(
+                masking.masking.evaluate(&element).is_zero(),
+                i.is_multiple_of(3),
+                "{}",
+                i
+            );
+        }
+        assert_eq!(masking.masking, &masking.random * &vanishing);
+    }
+
+    #[test]
+    fn test_no_random_coefficients() {
+        let rng = &mut test_rng();
+        let vanishing = vanishing_on_subset(&[F::from(2u64)]);
+        let masking = mask(&vanishing, 0, rng);
+        assert_eq!(masking.masking, DensePolynomial::zero());
+        assert!(vanishing_on_subset::<F>(&[])
+            .evaluate(&F::rand(rng))
+            .is_one());
+    }
+}
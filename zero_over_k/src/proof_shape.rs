@@ -0,0 +1,183 @@
+//! The shape of a proof: every commitment the prover produces, with its label and degree bound, and every query of
+//! its openings, with the label of the point. A dry run of a prover computes it from the statement alone, without
+//! FFTs or MSMs, to count the elements of a proof or to trim an SRS to exactly the degree bounds it needs.
+//!
+//! ```ignore
+//! let shape = ZeroOverK::<C>::prove_dry_run(Some(degree_bound), &vo);
+//! let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&shape.degree_bounds()))?;
+//! ```
+//!
+//! The labels of a sub-protocol are prefixed with the label of its context, e.g. `inverse_check/q_1`.
+//!
+//! A shape also records the challenges the verifier draws and the checks it makes, in the order of the protocol. A
+//! round is a batch of commitments followed by the challenges derived from them, and sub-protocols are laid out one
+//! after the other. This is what the `spec` module of `proof_of_function_relation` describes protocols with.
+
+use ark_poly_commit::PolynomialLabel;
+use std::collections::BTreeSet;
+
+/// A commitment produced by the prover in round `round`
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommitmentShape {
+    pub label: PolynomialLabel,
+    pub degree_bound: Option<usize>,
+    pub round: usize,
+}
+
+/// A polynomial opened at a point, named since its value depends on the challenges
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueryShape {
+    pub label: PolynomialLabel,
+    pub point: String,
+}
+
+/// A challenge drawn by the verifier at the end of round `round`
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChallengeShape {
+    pub label: String,
+    pub round: usize,
+}
+
+/// A check of the verifier, with the relation it enforces
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CheckShape {
+    pub label: String,
+    pub relation: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofShape {
+    pub commitments: Vec<CommitmentShape>,
+    pub queries: Vec<QueryShape>,
+    pub challenges: Vec<ChallengeShape>,
+    pub checks: Vec<CheckShape>,
+    /// The current round
+    pub round: usize,
+}
+
+impl ProofShape {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a commitment produced by the prover, which starts a new round once the current one drew a challenge
+    pub fn commit(&mut self, label: impl Into<PolynomialLabel>, degree_bound: Option<usize>) {
+        self.round = self.next_round();
+        self.commitments.push(CommitmentShape {
+            label: label.into(),
+            degree_bound,
+            round: self.round,
+        });
+    }
+
+    /// Record a challenge drawn by the verifier in the current round
+    pub fn challenge(&mut self, label: impl Into<String>) {
+        self.challenges.push(ChallengeShape {
+            label: label.into(),
+            round: self.round,
+        });
+    }
+
+    /// Start a new round for challenges derived from a message other than commitments, such as evaluations
+    pub fn end_round(&mut self) {
+        self.round = self.next_round();
+    }
+
+    /// Record a check of the verifier
+    pub fn check(&mut self, label: impl Into<String>, relation: impl Into<String>) {
+        self.checks.push(CheckShape {
+            label: label.into(),
+            relation: relation.into(),
+        });
+    }
+
+    /// The round the next commitment goes to: the current one, unless it already drew a challenge
+    fn next_round(&self) -> usize {
+        match self.challenges.last() {
+            Some(challenge) if challenge.round == self.round => self.round + 1,
+            _ => self.round,
+        }
+    }
+
+    /// Record the opening of the polynomial `label` at the point `point`
+    pub fn query(&mut self, label: impl Into<PolynomialLabel>, point: impl Into<String>) {
+        self.queries.push(QueryShape {
+            label: label.into(),
+            point: point.into(),
+        });
+    }
+
+    /// Record the commitments, queries, challenges and checks of a sub-protocol run under the context label
+    /// `prefix`, starting in the round the next commitment would go to
+    pub fn extend(&mut self, prefix: &str, sub_protocol: ProofShape) {
+        let prefixed = |label: &str| format!("{}/{}", prefix, label);
+        let offset = self.next_round();
+        self.commitments.extend(
+            sub_protocol
+                .commitments
+                .into_iter()
+                .map(|c| CommitmentShape {
+                    label: prefixed(&c.label),
+                    round: offset + c.round,
+                    ..c
+                }),
+        );
+        self.queries
+            .extend(sub_protocol.queries.into_iter().map(|q| QueryShape {
+                label: prefixed(&q.label),
+                point: prefixed(&q.point),
+            }));
+        self.challenges
+            .extend(sub_protocol.challenges.into_iter().map(|c| ChallengeShape {
+                label: prefixed(&c.label),
+                round: offset + c.round,
+            }));
+        self.checks
+            .extend(sub_protocol.checks.into_iter().map(|c| CheckShape {
+                label: prefixed(&c.label),
+                ..c
+            }));
+        self.round = offset + sub_protocol.round;
+    }
+
+    /// The number of rounds
+    pub fn num_rounds(&self) -> usize {
+        let rounds = self
+            .commitments
+            .iter()
+            .map(|c| c.round)
+            .chain(self.challenges.iter().map(|c| c.round))
+            .max();
+        rounds.map_or(0, |round| round + 1)
+    }
+
+    /// The distinct degree bounds of the commitments, in increasing order, which the keys must be trimmed with
+    pub fn degree_bounds(&self) -> Vec<usize> {
+        self.commitments
+            .iter()
+            .filter_map(|c| c.degree_bound)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// The distinct points the polynomials are opened at, in increasing order of their labels
+    pub fn points(&self) -> Vec<&str> {
+        self.queries
+            .iter()
+            .map(|q| q.point.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// The number of distinct points the polynomials are opened at
+    pub fn num_points(&self) -> usize {
+        self.points().len()
+    }
+}
@@ -0,0 +1,90 @@
+//! Division by the vanishing polynomial of a domain K (or a coset of K), as ZeroOverK computes its quotient q_1. A
+//! polynomial vanishes over K if and only if the division leaves no remainder.
+
+use crate::coset_domain::CosetDomain;
+use ark_ff::{batch_inversion, FftField, Zero};
+use ark_poly::{
+    univariate::{DenseOrSparsePolynomial, DensePolynomial},
+    Polynomial,
+};
+
+mod tests;
+
+/// The polynomial does not vanish over the domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RemainderNonZero;
+
+/// The quotient of `f` by the vanishing polynomial of `domain`, or [`RemainderNonZero`] if `f` does not vanish over
+/// it. A [`GeneralEvaluationDomain`](ark_poly::GeneralEvaluationDomain) converts into `domain` with `.into()`.
+pub fn compute_quotient<F: FftField>(
+    f: &DensePolynomial<F>,
+    domain: &CosetDomain<F>,
+) -> Result<DensePolynomial<F>, RemainderNonZero> {
+    let (quotient, remainder) = divide_by_vanishing_polynomial(f, domain);
+    if remainder.is_zero() {
+        Ok(quotient)
+    } else {
+        Err(RemainderNonZero)
+    }
+}
+
+/// The quotient of the polynomial f by the vanishing polynomial of `domain`, given the evaluations of f over
+/// `evaluation_coset` in the order of [`CosetDomain::elements`]. f must have degree less than the size of the coset.
+///
+/// This skips the interpolation of f: the quotient is interpolated from f / Z_K over the coset, where Z_K only takes
+/// a few distinct values. Its degree then tells whether f vanishes over K, as q * Z_K and f agree over the coset and
+/// both have degree less than its size. Falls back to dividing the coefficients of f if the coset meets the domain.
+pub fn compute_quotient_from_evals<F: FftField>(
+    evals: &[F],
+    evaluation_coset: &CosetDomain<F>,
+    domain: &CosetDomain<F>,
+) -> Result<DensePolynomial<F>, RemainderNonZero> {
+    let size = evaluation_coset.size();
+    let domain_size = domain.size();
+    assert_eq!(evals.len(), size, "one evaluation per element of the coset");
+
+    // Z_K(offset * w^i) = offset^n * w^(i * n) - c^n only depends on i modulo size / gcd(size, n)
+    let period = size / gcd(size, domain_size);
+    let mut vanishing_evals = evaluation_coset
+        .elements()
+        .take(period)
+        .map(|x| domain.evaluate_vanishing_polynomial(x))
+        .collect::<Vec<_>>();
+    if vanishing_evals.iter().any(|z| z.is_zero()) {
+        return compute_quotient(&evaluation_coset.ifft(evals), domain);
+    }
+    batch_inversion(&mut vanishing_evals);
+
+    let quotient_evals = evals
+        .iter()
+        .enumerate()
+        .map(|(i, &eval)| eval * vanishing_evals[i % period])
+        .collect::<Vec<_>>();
+    let quotient = evaluation_coset.ifft(&quotient_evals);
+
+    if quotient.is_zero() || quotient.degree() + domain_size < size {
+        Ok(quotient)
+    } else {
+        Err(RemainderNonZero)
+    }
+}
+
+/// The quotient and remainder of `f` by the vanishing polynomial of `domain`
+pub(crate) fn divide_by_vanishing_polynomial<F: FftField>(
+    f: &DensePolynomial<F>,
+    domain: &CosetDomain<F>,
+) -> (DensePolynomial<F>, DensePolynomial<F>) {
+    DenseOrSparsePolynomial::from(f)
+        .divide_with_q_and_r(&DenseOrSparsePolynomial::from(
+            &domain.vanishing_polynomial(),
+        ))
+        .expect("the vanishing polynomial is not zero")
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(all(test, feature = "prover"))]
+mod test {
+    use crate::{
+        coset_domain::CosetDomain,
+        quotient::{compute_quotient, compute_quotient_from_evals, RemainderNonZero},
+    };
+    use ark_bn254::Fr;
+    use ark_ff::{FftField, One};
+    use ark_poly::{
+        univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, UVPolynomial,
+    };
+    use ark_std::test_rng;
+
+    type F = Fr;
+
+    fn domains() -> Vec<CosetDomain<F>> {
+        let domain = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        vec![
+            domain.into(),
+            CosetDomain::new(domain, F::from(3u64)).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_compute_quotient() {
+        let rng = &mut test_rng();
+        for domain in domains() {
+            let quotient = DensePolynomial::<F>::rand(10, rng);
+            let vanishing: DensePolynomial<F> = domain.vanishing_polynomial().into();
+            let f = &quotient * &vanishing;
+            assert_eq!(compute_quotient(&f, &domain), Ok(quotient));
+
+            let not_vanishing = &f + &DensePolynomial::from_coefficients_slice(&[F::one()]);
+            assert_eq!(
+                compute_quotient(&not_vanishing, &domain),
+                Err(RemainderNonZero)
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_quotient_from_evals() {
+        let rng = &mut test_rng();
+        let evaluation_domain = GeneralEvaluationDomain::<F>::new(32).unwrap();
+        let evaluation_coset =
+            CosetDomain::new(evaluation_domain, F::multiplicative_generator()).unwrap();
+
+        for domain in domains() {
+            let quotient = DensePolynomial::<F>::rand(10, rng);
+            let vanishing: DensePolynomial<F> = domain.vanishing_polynomial().into();
+            let f = &quotient * &vanishing;
+            let evals = evaluation_coset.fft(&f);
+            assert_eq!(
+                compute_quotient_from_evals(&evals, &evaluation_coset, &domain),
+                Ok(quotient.clone())
+            );
+
+            // a remainder of any degree below |K| is caught
+            for remainder_degree in [0, 7] {
+                let remainder = DensePolynomial::<F>::rand(remainder_degree, rng);
+                let evals = evaluation_coset.fft(&(&f + &remainder));
+                assert_eq!(
+                    compute_quotient_from_evals(&evals, &evaluation_coset, &domain),
+                    Err(RemainderNonZero)
+                );
+            }
+
+            // the subgroup contains K, for which the coefficients of f are divided instead, and misses its coset
+            let evaluation_subgroup = CosetDomain::from(evaluation_domain);
+            let evals = evaluation_subgroup.fft(&f);
+            assert_eq!(
+                compute_quotient_from_evals(&evals, &evaluation_subgroup, &domain),
+                Ok(quotient)
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Optional [`tracing`](https://docs.rs/tracing) instrumentation of provers and verifiers, enabled by the `trace`
+//! feature. Protocols enter a span for each proof and for each of its rounds (commit, challenge, open), and emit
+//! events with the sizes of what they commit to and send. Subscribers time the spans, e.g. `tracing-subscriber`
+//! with span close events.
+//!
+//! Without the feature, [`trace_span!`](crate::trace_span) and [`trace_event!`](crate::trace_event) expand to
+//! nothing and their fields are not evaluated. The macros are feature-gated in this crate, so the crates built on it
+//! enable tracing by forwarding `zero_over_k/trace` rather than depending on `tracing` themselves.
+
+#[cfg(feature = "trace")]
+pub use tracing;
+
+/// A span entered until it is exited or dropped, see [`trace_span!`](crate::trace_span)
+#[must_use]
+pub struct Span {
+    #[cfg(feature = "trace")]
+    _entered: tracing::span::EnteredSpan,
+}
+
+impl Span {
+    #[cfg(feature = "trace")]
+    pub fn entered(span: tracing::Span) -> Self {
+        Self {
+            _entered: span.entered(),
+        }
+    }
+
+    #[cfg(not(feature = "trace"))]
+    pub fn disabled() -> Self {
+        Self {}
+    }
+
+    /// Leave the span before the end of the scope, e.g. at the end of a round
+    pub fn exit(self) {}
+}
+
+/// Enter an info-level span with the arguments of [`tracing::info_span!`](https://docs.rs/tracing/latest/tracing/macro.info_span.html),
+/// returning a [`Span`]
+#[cfg(feature = "trace")]
+#[macro_export]
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        $crate::trace::Span::entered($crate::trace::tracing::info_span!($($args)*))
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+#[macro_export]
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        $crate::trace::Span::disabled()
+    };
+}
+
+/// Emit an info-level event with the arguments of [`tracing::info!`](https://docs.rs/tracing/latest/tracing/macro.info.html)
+#[cfg(feature = "trace")]
+#[macro_export]
+macro_rules! trace_event {
+    ($($args:tt)*) => {
+        $crate::trace::tracing::info!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+#[macro_export]
+macro_rules! trace_event {
+    ($($args:tt)*) => {};
+}
@@ -1,7 +1,14 @@
 use ark_ff::Field;
 use ark_poly::{univariate::DensePolynomial, UVPolynomial};
-use ark_std::UniformRand;
-use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "prover")]
+use {
+    ark_ff::FftField,
+    ark_poly::{EvaluationDomain, GeneralEvaluationDomain},
+    ark_std::UniformRand,
+    rand::Rng,
+};
 
 pub fn powers_of<F>(scalar: F) -> impl Iterator<Item = F>
 where
@@ -39,6 +46,7 @@ pub fn shift_dense_poly<F: Field>(
     DensePolynomial::from_coefficients_vec(coeffs)
 }
 
+#[cfg(feature = "prover")]
 /// Sample a vector of random elements of type T
 pub fn sample_vector<T: UniformRand, R: Rng>(seed: &mut R, length: usize) -> Vec<T> {
     (0..length)
@@ -47,3 +55,34 @@ pub fn sample_vector<T: UniformRand, R: Rng>(seed: &mut R, length: usize) -> Vec
         .map(|_| T::rand(seed))
         .collect::<Vec<_>>()
 }
+
+#[cfg(feature = "prover")]
+/// Evaluate several polynomials over the same domain. With the `parallel` feature, each FFT runs on its own thread.
+pub fn batch_fft<F: FftField>(
+    domain: &GeneralEvaluationDomain<F>,
+    polys: &[&DensePolynomial<F>],
+) -> Vec<Vec<F>> {
+    #[cfg(feature = "parallel")]
+    let polys = polys.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let polys = polys.iter();
+
+    polys.map(|p| domain.fft(p.coeffs())).collect()
+}
+
+#[cfg(feature = "prover")]
+/// Interpolate several vectors of evaluations over the same domain. With the `parallel` feature, each iFFT runs on its
+/// own thread.
+pub fn batch_ifft<F: FftField>(
+    domain: &GeneralEvaluationDomain<F>,
+    evals: &[&[F]],
+) -> Vec<DensePolynomial<F>> {
+    #[cfg(feature = "parallel")]
+    let evals = evals.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let evals = evals.iter();
+
+    evals
+        .map(|e| DensePolynomial::from_coefficients_vec(domain.ifft(e)))
+        .collect()
+}
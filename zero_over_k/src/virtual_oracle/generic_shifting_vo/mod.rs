@@ -1,11 +1,15 @@
 use crate::error::Error;
 use crate::util::shift_dense_poly;
 use crate::virtual_oracle::generic_shifting_vo::vo_term::VOTerm;
-use ark_ff::Field;
-use ark_poly::{univariate::DensePolynomial, UVPolynomial};
-use ark_poly_commit::{Evaluations, PolynomialLabel, QuerySet};
+use ark_ff::FftField;
+use ark_poly::{univariate::DensePolynomial, Polynomial, UVPolynomial};
+use ark_poly_commit::{Evaluations, LabeledPolynomial, PolynomialLabel, QuerySet};
 
-use super::VirtualOracle;
+use super::{
+    check_oracle_degree, degree_bound,
+    public_oracle::{PublicOracle, PublicTerm},
+    VirtualOracle,
+};
 
 pub mod presets;
 mod tests;
@@ -16,18 +20,20 @@ pub mod vo_term;
 /// available in this crate. See equation (2) in https://eprint.iacr.org/2021/1342 for an explicit definition.
 pub struct GenericShiftingVO<F, T>
 where
-    F: Field,
+    F: FftField,
     T: Fn(&[VOTerm<F>]) -> VOTerm<F>,
 {
     mapping_vector: Vec<usize>,
     shifting_coefficients: Vec<F>,
     combine_function: T,
     minimum_oracle_length: usize,
+    scaling_factor: Option<usize>,
+    public_terms: Vec<PublicTerm<F>>,
 }
 
 impl<F, T> GenericShiftingVO<F, T>
 where
-    F: Field,
+    F: FftField,
     T: Fn(&[VOTerm<F>]) -> VOTerm<F>,
 {
     /// Constructor for an input-shifting virtual oracle
@@ -57,15 +63,36 @@ where
             shifting_coefficients: shifting_coefficients.to_vec(),
             combine_function,
             minimum_oracle_length,
+            scaling_factor: None,
+            public_terms: Vec::new(),
         })
     }
 
-    /// Returns the polynomial that results from the combination of the given concrete oracles
+    /// Declare the degree of the combine function in its terms (e.g. 2 for a product of two terms). This allows
+    /// provers to compute with the VO in evaluation form.
+    pub fn with_scaling_factor(mut self, scaling_factor: usize) -> Self {
+        self.scaling_factor = Some(scaling_factor);
+        self
+    }
+
+    /// Append a term over an oracle known to the verifier, shifted by `shifting_coefficient`. Public terms come
+    /// after the terms of the mapping vector, in the order they are added.
+    pub fn with_public_oracle(mut self, oracle: PublicOracle<F>, shifting_coefficient: F) -> Self {
+        self.public_terms
+            .push(PublicTerm::new(oracle, shifting_coefficient));
+        self
+    }
+
+    /// Returns the polynomial that results from the combination of the given concrete oracles. The oracles must
+    /// respect their degree bounds, and the result the bound they imply, see [`Self::degree_bound`].
     pub fn compute_polynomial(
         &self,
-        concrete_oracles: &[ark_poly_commit::LabeledPolynomial<F, DensePolynomial<F>>],
+        concrete_oracles: &[LabeledPolynomial<F, DensePolynomial<F>>],
     ) -> Result<DensePolynomial<F>, Error> {
         self.check_conrete_oracle_length(concrete_oracles.len())?;
+        for &mapped_index in &self.mapping_vector {
+            check_oracle_degree(&concrete_oracles[mapped_index])?;
+        }
 
         let x_poly = DensePolynomial::from_coefficients_slice(&[F::zero(), F::one()]);
 
@@ -84,14 +111,54 @@ where
                 );
                 terms.push(VOTerm::Polynomial(shifted))
             });
+        terms.extend(
+            self.public_terms
+                .iter()
+                .map(|term| VOTerm::Polynomial(term.shifted_polynomial())),
+        );
 
-        let combined = (self.combine_function)(&terms);
-        match combined {
-            VOTerm::Evaluation(_) => Err(Error::VOFailedToInstantiate),
-            VOTerm::Polynomial(poly) => Ok(poly),
+        let combined = match (self.combine_function)(&terms) {
+            VOTerm::Evaluation(_) => return Err(Error::VOFailedToInstantiate),
+            VOTerm::Polynomial(poly) => poly,
+        };
+        match self.degree_bound(concrete_oracles) {
+            Some(bound) if combined.degree() > bound => Err(Error::DegreeBoundExceeded(format!(
+                "The VO has degree {} but its terms and scaling factor bound it to {}",
+                combined.degree(),
+                bound
+            ))),
+            _ => Ok(combined),
         }
     }
 
+    /// Returns the combination of the given concrete oracles, labeled and bounded by [`Self::degree_bound`]
+    pub fn instantiate(
+        &self,
+        label: &str,
+        concrete_oracles: &[LabeledPolynomial<F, DensePolynomial<F>>],
+    ) -> Result<LabeledPolynomial<F, DensePolynomial<F>>, Error> {
+        Ok(LabeledPolynomial::new(
+            label.to_string(),
+            self.compute_polynomial(concrete_oracles)?,
+            self.degree_bound(concrete_oracles),
+            None,
+        ))
+    }
+
+    /// Degree bound of the VO over the given concrete oracles, from their own degree bounds and the scaling factor.
+    /// None if the scaling factor or the bound of an oracle in the mapping vector is unknown.
+    pub fn degree_bound(
+        &self,
+        concrete_oracles: &[LabeledPolynomial<F, DensePolynomial<F>>],
+    ) -> Option<usize> {
+        let term_degree_bounds = self
+            .mapping_vector
+            .iter()
+            .map(|&mapped_index| concrete_oracles.get(mapped_index)?.degree_bound())
+            .collect::<Vec<_>>();
+        degree_bound(self, &term_degree_bounds)
+    }
+
     /// Check that enough oracles were provided.
     fn check_conrete_oracle_length(&self, input_length: usize) -> Result<(), Error> {
         if input_length < self.minimum_oracle_length {
@@ -106,7 +173,7 @@ where
 
 impl<F, T> VirtualOracle<F> for GenericShiftingVO<F, T>
 where
-    F: Field,
+    F: FftField,
     T: Fn(&[VOTerm<F>]) -> VOTerm<F>,
 {
     fn generate_query_set(
@@ -159,6 +226,11 @@ where
             .collect();
 
         terms.insert(0, VOTerm::Evaluation(eval_point.clone()));
+        terms.extend(
+            self.public_terms
+                .iter()
+                .map(|term| VOTerm::Evaluation(term.evaluate(eval_point))),
+        );
 
         let combined = (self.combine_function)(&terms);
         match combined {
@@ -182,4 +254,12 @@ where
     fn num_of_variable_terms(&self) -> usize {
         self.mapping_vector.len()
     }
+
+    fn scaling_factor(&self) -> Option<usize> {
+        self.scaling_factor
+    }
+
+    fn public_terms(&self) -> &[PublicTerm<F>] {
+        &self.public_terms
+    }
 }
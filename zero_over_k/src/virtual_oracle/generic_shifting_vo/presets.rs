@@ -34,6 +34,19 @@ pub fn abc_product_check<F: FftField>(terms: &[VOTerm<F>]) -> VOTerm<F> {
     terms[1].clone() - terms[2].clone() * terms[3].clone()
 }
 
+/// A function to be used in a virtual oracle. Should the VO evaluate to 0 for all points in a domain K,
+/// we can conclude that terms[2] and terms[3] are equal wherever the selector terms[1] is non-zero
+pub fn selected_equality_check<F: FftField>(terms: &[VOTerm<F>]) -> VOTerm<F> {
+    terms[1].clone() * (terms[2].clone() - terms[3].clone())
+}
+
+/// A function to be used in a virtual oracle whose first term is shifted by the generator gamma of a domain K,
+/// i.e. with shifting coefficients [gamma, 1]. Should the VO evaluate to 0 for all points in K, we can conclude that
+/// f(gamma * X) = g(X) over K, where f and g are the concrete oracles mapped to terms[1] and terms[2]
+pub fn rotation_check<F: Field>(terms: &[VOTerm<F>]) -> VOTerm<F> {
+    terms[1].clone() - terms[2].clone()
+}
+
 /// A closure to be used in a geometric sequence test virtual oracle.
 /// This specific construction expects terms[0] to be X (as enforced by default), terms[1] to be f(X) and
 /// terms[2] to be f(gamma*X); where X is an indeterminate variable, f is a polynomial for which we make
@@ -41,7 +54,7 @@ pub fn abc_product_check<F: FftField>(terms: &[VOTerm<F>]) -> VOTerm<F> {
 #[macro_export]
 macro_rules! geometric_seq_check {
     ($common_ratio:expr, $sequence_lengths:expr, $domain:expr) => {
-        |terms: &[VOTerm<F>]| {
+        |terms: &[VOTerm<_>]| {
             // construct (f(gamma * X) - r * f(X))
             let check_next_term = terms[2].clone() - vo_constant!($common_ratio) * terms[1].clone();
 
@@ -64,3 +77,56 @@ macro_rules! geometric_seq_check {
         }
     };
 }
+
+/// A closure to be used in the combined check of a discrete-log comparison, where terms[1] to terms[6] are f, f',
+/// g, g', s and s'. It batches the square checks f = f'^2, g = g'^2 and s = s'^2 and the product check f' = s' * g'
+/// with the powers of a verifier challenge, so that a single ZeroOverK covers all four constraints. Like each of
+/// them, it has a scaling factor of 2.
+#[macro_export]
+macro_rules! square_and_product_check {
+    ($challenge:expr) => {
+        |terms: &[VOTerm<_>]| {
+            let square = |x: &VOTerm<_>, root: &VOTerm<_>| x.clone() - root.clone() * root.clone();
+            let f_square = square(&terms[1], &terms[2]);
+            let g_square = square(&terms[3], &terms[4]);
+            let s_square = square(&terms[5], &terms[6]);
+            let product = terms[2].clone() - terms[6].clone() * terms[4].clone();
+
+            // f_square + c * g_square + c^2 * s_square + c^3 * product, in Horner form
+            [g_square, s_square, product].into_iter().rev().fold(
+                VOTerm::Evaluation(ark_ff::Zero::zero()),
+                |acc, constraint| constraint + vo_constant!($challenge) * acc,
+            ) * vo_constant!($challenge)
+                + f_square
+        }
+    };
+}
+
+/// A closure to be used in a virtual oracle over the coordinates of functions into the extension F[u]/(u^d - β) of
+/// degree d = $degree, with β = $nonresidue. terms[1..=d], terms[d + 1..=2d] and terms[2d + 1..=3d] are the
+/// coordinates of h, f and g, and the closure batches the d coordinate checks of h = f * g with the powers of a
+/// verifier challenge. It has a scaling factor of 2.
+#[macro_export]
+macro_rules! extension_product_check {
+    ($nonresidue:expr, $degree:expr, $challenge:expr) => {
+        move |terms: &[VOTerm<_>]| {
+            let (h, fg) = terms[1..].split_at($degree);
+            let (f, g) = fg.split_at($degree);
+
+            // h_k - sum_{i + j = k} f_i * g_j - β * sum_{i + j = k + d} f_i * g_j, in Horner form over k
+            (0..$degree)
+                .rev()
+                .fold(VOTerm::Evaluation(ark_ff::Zero::zero()), |acc, k| {
+                    let constraint = (0..$degree).fold(h[k].clone(), |constraint, i| {
+                        let product = f[i].clone() * g[(k + $degree - i) % $degree].clone();
+                        if i <= k {
+                            constraint - product
+                        } else {
+                            constraint - vo_constant!($nonresidue) * product
+                        }
+                    });
+                    acc * vo_constant!($challenge) + constraint
+                })
+        }
+    };
+}
@@ -1,5 +1,6 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test {
+    use crate::config::Config;
     use crate::util::sample_vector;
     use crate::virtual_oracle::generic_shifting_vo::presets;
     use crate::virtual_oracle::VirtualOracle;
@@ -14,7 +15,7 @@ mod test {
     use ark_poly_commit::{evaluate_query_set, LabeledPolynomial, PolynomialCommitment};
     use ark_std::{rand::thread_rng, test_rng};
     use blake2::Blake2s;
-    use fiat_shamir_rng::SimpleHashFiatShamirRng;
+    use fiat_shamir_rng::{Context, SimpleHashFiatShamirRng};
     use homomorphic_poly_commit::marlin_kzg::KZG10;
     use rand_chacha::ChaChaRng;
 
@@ -23,6 +24,7 @@ mod test {
     type F = Fr;
     type PC = KZG10<Bn254>;
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
+    type C = Config<F, PC, FS>;
 
     pub fn simple_addition(terms: &[VOTerm<F>]) -> VOTerm<F> {
         terms[1].clone() + vo_constant!(F::from(2u64)) * terms[2].clone()
@@ -119,6 +121,50 @@ mod test {
         assert_eq!(expected, prod);
     }
 
+    #[test]
+    fn test_degree_bounds() {
+        let rng = &mut thread_rng();
+        let mut bounded = |label: &str, degree_bound| {
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::<F>::rand(4, rng),
+                Some(degree_bound),
+                None,
+            )
+        };
+        let concrete_oracles = &[bounded("a", 4), bounded("b", 4), bounded("c", 4)];
+        let shifting_coefficients = [F::one(), F::one()];
+        let mul_oracle =
+            || GenericShiftingVO::new(&[2, 0], &shifting_coefficients, simple_mul).unwrap();
+
+        // the bounds of the oracles carry over to the product
+        let prod = mul_oracle()
+            .with_scaling_factor(2)
+            .instantiate("prod", concrete_oracles)
+            .unwrap();
+        assert_eq!(prod.degree_bound(), Some(8));
+        assert_eq!(prod.degree(), 8);
+
+        // without a scaling factor, the product is not bounded
+        let prod = mul_oracle().instantiate("prod", concrete_oracles).unwrap();
+        assert_eq!(prod.degree_bound(), None);
+
+        // a scaling factor too small for the combine function is caught
+        assert!(matches!(
+            mul_oracle()
+                .with_scaling_factor(1)
+                .compute_polynomial(concrete_oracles),
+            Err(Error::DegreeBoundExceeded(_))
+        ));
+
+        // as is an oracle above its own bound
+        let concrete_oracles = &[bounded("a", 3), bounded("b", 4), bounded("c", 4)];
+        assert!(matches!(
+            mul_oracle().compute_polynomial(concrete_oracles),
+            Err(Error::DegreeBoundExceeded(_))
+        ));
+    }
+
     #[test]
     fn test_short_input_vec() {
         // mapping vector expects there to be a concrete oracle with index 1; effectively expected at last 2 concrete oracles
@@ -248,8 +294,8 @@ mod test {
         );
 
         // Step 5: commit to the concrete oracles
-        let concrete_oracles = [f, g];
-        let (commitments, rands) = PC::commit(&ck, &concrete_oracles, Some(rng))
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng))
             .map_err(to_pc_error::<F, PC>)
             .unwrap();
 
@@ -259,7 +305,7 @@ mod test {
             GenericShiftingVO::new(&vec![0, 1], &alphas.clone(), presets::inverse_check).unwrap();
 
         // Step 7: prove
-        let zero_over_k_proof = ZeroOverK::<F, PC, FS>::prove(
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
             &concrete_oracles,
             &commitments,
             &rands,
@@ -267,17 +313,19 @@ mod test {
             &inverse_check_oracle,
             &domain_k,
             &ck,
+            &Context::new(b"test"),
             rng,
         );
 
         // Step 8: verify
-        let is_valid = ZeroOverK::<F, PC, FS>::verify(
+        let is_valid = ZeroOverK::<C>::verify(
             zero_over_k_proof.unwrap(),
             &commitments,
             Some(enforced_degree_bound),
             &inverse_check_oracle,
             &domain_k,
             &vk,
+            &Context::new(b"test"),
         );
 
         assert!(is_valid.is_ok());
@@ -1,12 +1,14 @@
 use crate::error::Error;
-use ark_ff::Field;
-use ark_poly_commit::{Evaluations, PolynomialLabel, QuerySet};
+use ark_ff::FftField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::{Evaluations, LabeledPolynomial, PolynomialLabel, QuerySet};
 
-use self::generic_shifting_vo::vo_term::VOTerm;
+use self::{generic_shifting_vo::vo_term::VOTerm, public_oracle::PublicTerm};
 
 pub mod generic_shifting_vo;
+pub mod public_oracle;
 
-pub trait VirtualOracle<F: Field> {
+pub trait VirtualOracle<F: FftField> {
     /// maps input concrete oracles to internal terms, e.g.:
     /// mapping_vector = [0, 0, 2] means h_0 = concrete_0, h_1 = concrete_0, h_2 = concrete_2
     fn mapping_vector(&self) -> Vec<usize>;
@@ -16,9 +18,20 @@ pub trait VirtualOracle<F: Field> {
 
     fn apply_evaluation_function(&self, terms: &[VOTerm<F>]) -> VOTerm<F>;
 
-    /// Gives a count of all the terms expected by the VO function excluding the X term
+    /// Gives a count of all the terms expected by the VO function excluding the X term and the public terms
     fn num_of_variable_terms(&self) -> usize;
 
+    /// Terms over oracles known to the verifier, which it evaluates itself. They come after the variable terms in
+    /// the terms given to the VO function.
+    fn public_terms(&self) -> &[PublicTerm<F>] {
+        &[]
+    }
+
+    /// Bound on the degree of the VO relative to its terms: the VO is at most `scaling_factor` times the largest
+    /// degree of its (shifted) terms, X included. When known, a prover can evaluate the VO pointwise over a domain
+    /// that is `scaling_factor` times larger instead of multiplying polynomials in coefficient form.
+    fn scaling_factor(&self) -> Option<usize>;
+
     /// Generate a query set that will allow to evaluate the VO at a requested (labeled) point
     fn generate_query_set(
         &self,
@@ -36,7 +49,7 @@ pub trait VirtualOracle<F: Field> {
 }
 
 /// Returns the list of concrete oracle labels ordered according to the mapping vector
-pub fn get_term_labels<F: Field, VO: VirtualOracle<F>>(
+pub fn get_term_labels<F: FftField, VO: VirtualOracle<F>>(
     virtual_oracle: &VO,
     concrete_oracle_labels: &[PolynomialLabel],
 ) -> Vec<PolynomialLabel> {
@@ -47,3 +60,37 @@ pub fn get_term_labels<F: Field, VO: VirtualOracle<F>>(
 
     h_labels
 }
+
+/// Degree bound of the VO over terms of the given degree bounds: `scaling_factor` times the largest of them, X and the
+/// public terms included. None if the VO has no scaling factor or a term is not bounded.
+pub fn degree_bound<F: FftField, VO: VirtualOracle<F>>(
+    virtual_oracle: &VO,
+    term_degree_bounds: &[Option<usize>],
+) -> Option<usize> {
+    let scaling_factor = virtual_oracle.scaling_factor()?;
+    let public_degrees = virtual_oracle
+        .public_terms()
+        .iter()
+        .map(|term| Some(term.oracle.degree()));
+    let max_bound = term_degree_bounds
+        .iter()
+        .cloned()
+        .chain(public_degrees)
+        .try_fold(1, |max, bound| bound.map(|bound| max.max(bound)))?;
+    Some(scaling_factor * max_bound)
+}
+
+/// Check that the degree of an oracle does not exceed its degree bound, if it has one
+pub fn check_oracle_degree<F: FftField>(
+    oracle: &LabeledPolynomial<F, DensePolynomial<F>>,
+) -> Result<(), Error> {
+    match oracle.degree_bound() {
+        Some(bound) if oracle.degree() > bound => Err(Error::DegreeBoundExceeded(format!(
+            "Oracle {} has degree {} but degree bound {}",
+            oracle.label(),
+            oracle.degree(),
+            bound
+        ))),
+        _ => Ok(()),
+    }
+}
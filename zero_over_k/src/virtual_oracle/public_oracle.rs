@@ -0,0 +1,181 @@
+//! Oracles known to both the prover and the verifier, such as the vanishing polynomial of a domain or the
+//! interpolation of a public sequence. A virtual oracle may use them as terms: the verifier evaluates them itself, so
+//! they are neither committed to, masked nor opened, and cost nothing in the proof.
+
+use crate::util::shift_dense_poly;
+use ark_ff::{batch_inversion, FftField, ToBytes};
+use ark_poly::{
+    univariate::{DensePolynomial, SparsePolynomial},
+    EvaluationDomain, Evaluations, GeneralEvaluationDomain, Polynomial,
+};
+use std::io::{Result as IoResult, Write};
+
+/// A polynomial that the verifier can evaluate on its own
+#[derive(Clone, Debug, PartialEq)]
+pub enum PublicOracle<F: FftField> {
+    /// A polynomial in coefficient form, evaluated in time linear in its degree
+    Dense(DensePolynomial<F>),
+    /// A polynomial with few non-zero coefficients, evaluated in time linear in their number
+    Sparse(SparsePolynomial<F>),
+    /// The polynomial taking the given non-zero values at the given indices of a domain, and zero elsewhere on it.
+    /// It is evaluated in time linear in the number of values, without an FFT.
+    Lagrange {
+        domain: GeneralEvaluationDomain<F>,
+        values: Vec<(usize, F)>,
+    },
+}
+
+impl<F: FftField> PublicOracle<F> {
+    /// The vanishing polynomial of `domain`
+    pub fn vanishing(domain: &GeneralEvaluationDomain<F>) -> Self {
+        Self::Sparse(domain.vanishing_polynomial())
+    }
+
+    /// The polynomial that takes the values of `sequence` over `domain`, padded with zeros. Only its non-zero values
+    /// are kept, its coefficients are computed by the prover when it needs them.
+    pub fn interpolate(sequence: &[F], domain: &GeneralEvaluationDomain<F>) -> Self {
+        let values = sequence
+            .iter()
+            .take(domain.size())
+            .enumerate()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(i, value)| (i, *value))
+            .collect();
+        Self::Lagrange {
+            domain: *domain,
+            values,
+        }
+    }
+
+    pub fn evaluate(&self, point: &F) -> F {
+        match self {
+            Self::Dense(poly) => poly.evaluate(point),
+            Self::Sparse(poly) => poly.evaluate(point),
+            Self::Lagrange { domain, values } => evaluate_lagrange(domain, values, point),
+        }
+    }
+
+    /// The degree of the oracle, or an upper bound on it for an interpolation
+    pub fn degree(&self) -> usize {
+        match self {
+            Self::Dense(poly) => poly.degree(),
+            Self::Sparse(poly) => poly.degree(),
+            Self::Lagrange { values, .. } if values.is_empty() => 0,
+            Self::Lagrange { domain, .. } => domain.size() - 1,
+        }
+    }
+
+    pub fn to_dense(&self) -> DensePolynomial<F> {
+        match self {
+            Self::Dense(poly) => poly.clone(),
+            Self::Sparse(poly) => poly.clone().into(),
+            Self::Lagrange { domain, values } => {
+                let mut evals = vec![F::zero(); domain.size()];
+                for (i, value) in values {
+                    evals[*i] = *value;
+                }
+                Evaluations::from_vec_and_domain(evals, *domain).interpolate()
+            }
+        }
+    }
+}
+
+/// Evaluate sum_i v_i L_i(x), where L_i(x) = w^i z_K(x) / (|K| (x - w^i)) is the Lagrange polynomial of the i-th
+/// element w^i of K
+fn evaluate_lagrange<F: FftField>(
+    domain: &GeneralEvaluationDomain<F>,
+    values: &[(usize, F)],
+    point: &F,
+) -> F {
+    let elements = values
+        .iter()
+        .map(|(i, _)| domain.element(*i))
+        .collect::<Vec<_>>();
+
+    // on K, only the Lagrange polynomial of the point itself does not vanish
+    let z_k = domain.evaluate_vanishing_polynomial(*point);
+    if z_k.is_zero() {
+        return elements
+            .iter()
+            .zip(values)
+            .find(|(element, _)| *element == point)
+            .map_or(F::zero(), |(_, (_, value))| *value);
+    }
+
+    let mut denominators = elements
+        .iter()
+        .map(|element| *point - element)
+        .collect::<Vec<_>>();
+    batch_inversion(&mut denominators);
+    let sum = values
+        .iter()
+        .zip(elements)
+        .zip(denominators)
+        .fold(F::zero(), |acc, (((_, value), element), inverse)| {
+            acc + *value * element * inverse
+        });
+    sum * z_k / F::from(domain.size() as u64)
+}
+
+/// Public oracles are bound to the transcript by their coefficients, or by their values for an interpolation
+impl<F: FftField> ToBytes for PublicOracle<F> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::Dense(poly) => {
+                0u8.write(&mut writer)?;
+                (poly.coeffs.len() as u64).write(&mut writer)?;
+                poly.coeffs.write(&mut writer)
+            }
+            Self::Sparse(poly) => {
+                1u8.write(&mut writer)?;
+                (poly.len() as u64).write(&mut writer)?;
+                poly.iter().try_for_each(|(degree, coeff)| {
+                    (*degree as u64).write(&mut writer)?;
+                    coeff.write(&mut writer)
+                })
+            }
+            Self::Lagrange { domain, values } => {
+                2u8.write(&mut writer)?;
+                (domain.size() as u64).write(&mut writer)?;
+                (values.len() as u64).write(&mut writer)?;
+                values.iter().try_for_each(|(i, value)| {
+                    (*i as u64).write(&mut writer)?;
+                    value.write(&mut writer)
+                })
+            }
+        }
+    }
+}
+
+/// A public oracle used as a term of a virtual oracle, shifted by its own coefficient
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicTerm<F: FftField> {
+    pub oracle: PublicOracle<F>,
+    pub shifting_coefficient: F,
+}
+
+impl<F: FftField> PublicTerm<F> {
+    pub fn new(oracle: PublicOracle<F>, shifting_coefficient: F) -> Self {
+        Self {
+            oracle,
+            shifting_coefficient,
+        }
+    }
+
+    /// The term as a polynomial in X, i.e. the oracle at alpha * X
+    pub fn shifted_polynomial(&self) -> DensePolynomial<F> {
+        shift_dense_poly(&self.oracle.to_dense(), &self.shifting_coefficient)
+    }
+
+    /// The term at `point`, i.e. the oracle at alpha * point
+    pub fn evaluate(&self, point: &F) -> F {
+        self.oracle.evaluate(&(self.shifting_coefficient * point))
+    }
+}
+
+impl<F: FftField> ToBytes for PublicTerm<F> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.shifting_coefficient.write(&mut writer)?;
+        self.oracle.write(&mut writer)
+    }
+}
@@ -0,0 +1,48 @@
+//! ZeroOverK proven by a delegate from the [`OpeningHint`]s of the owner of the concrete oracles, rather than from the
+//! oracles themselves, see [`homomorphic_poly_commit::opening_hint`]. The proof is the one the owner would produce and
+//! verifies with [`ZeroOverK::verify`] against the commitments of the owner.
+
+use crate::config::{pc, ProtocolConfig};
+use crate::{
+    error::Error,
+    virtual_oracle::VirtualOracle,
+    zero_over_k::{proof::Proof, ZeroOverK},
+};
+use ark_poly::GeneralEvaluationDomain;
+use ark_poly_commit::LabeledCommitment;
+use fiat_shamir_rng::Context;
+use homomorphic_poly_commit::opening_hint::{from_hints, OpeningHint};
+use rand::Rng;
+
+impl<C: ProtocolConfig> ZeroOverK<C> {
+    /// Prove that the virtual oracle vanishes over K, given one hint per concrete oracle in the order of the
+    /// commitments
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_from_hints<R: Rng, VO: VirtualOracle<C::F>>(
+        hints: &[OpeningHint<C::F, C::PC>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &GeneralEvaluationDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let (oracles, rands) = from_hints(hints)?;
+        let oracles = oracles.iter().collect::<Vec<_>>();
+        // the hints come from another party than the prover, check them against the commitments up front
+        Self::check_oracle_labels(&oracles, concrete_oracle_commitments)?;
+
+        Self::prove(
+            &oracles,
+            concrete_oracle_commitments,
+            &rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            ck,
+            context,
+            rng,
+        )
+    }
+}
@@ -0,0 +1,53 @@
+//! The labels under which ZeroOverK commits to and opens its polynomials. The prover labels its polynomials with
+//! them and the verifier relabels the bare commitments of a proof with them, so a batched opening only checks out
+//! when both sides take their labels from here.
+
+use std::collections::BTreeSet;
+
+use ark_poly_commit::PolynomialLabel;
+
+pub const Q_1: &str = "q_1";
+pub const Q_2: &str = "q_2";
+
+const RANDOM_PREFIX: &str = "r_";
+const MASKING_PREFIX: &str = "m_";
+const H_PRIME_PREFIX: &str = "h_prime_";
+
+/// Label of the random polynomial r_i
+pub fn random(i: usize) -> PolynomialLabel {
+    format!("{}{}", RANDOM_PREFIX, i)
+}
+
+/// Label of the masking polynomial m_i
+pub fn masking(i: usize) -> PolynomialLabel {
+    format!("{}{}", MASKING_PREFIX, i)
+}
+
+/// Label of the masked term h'_i = h_i + m_i
+pub fn h_prime(i: usize) -> PolynomialLabel {
+    format!("{}{}", H_PRIME_PREFIX, i)
+}
+
+/// Whether `label` is a label of this registry for some term index. Concrete oracles must not use such labels, or
+/// they would be confused with the internal polynomials of the protocol.
+pub fn is_reserved(label: &str) -> bool {
+    label == Q_1
+        || label == Q_2
+        || [RANDOM_PREFIX, MASKING_PREFIX, H_PRIME_PREFIX]
+            .iter()
+            .any(|prefix| {
+                matches!(
+                    label.strip_prefix(prefix),
+                    Some(index) if !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit())
+                )
+            })
+}
+
+/// The labels of the polynomials opened for a virtual oracle with `num_of_terms` variable terms: each h'_i and m_i,
+/// q_1 and q_2
+pub fn opened(num_of_terms: usize) -> BTreeSet<PolynomialLabel> {
+    (0..num_of_terms)
+        .flat_map(|i| [h_prime(i), masking(i)])
+        .chain([Q_1.to_string(), Q_2.to_string()])
+        .collect()
+}
@@ -1,50 +1,478 @@
 #![allow(dead_code)]
 
-use crate::error::{to_pc_error, Error};
+use crate::config::{pc, ProtocolConfig};
+use crate::coset_domain::CosetDomain;
+use crate::error::Error;
 use crate::get_labels;
+use crate::proof_shape::ProofShape;
+use crate::trace_span;
 use crate::util::powers_of;
 use crate::virtual_oracle::{generic_shifting_vo::vo_term::VOTerm, VirtualOracle};
 use crate::zero_over_k::piop::PIOPforZeroOverK;
-use crate::zero_over_k::proof::Proof;
-use ark_ff::to_bytes;
+use crate::zero_over_k::proof::{DeferredProof, Proof};
 use ark_ff::PrimeField;
-use ark_poly::{univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use ark_ff::{to_bytes, One, Zero};
+use ark_poly::{univariate::DensePolynomial, GeneralEvaluationDomain};
 use ark_poly_commit::Evaluations;
 use ark_poly_commit::{
-    data_structures::{PCCommitterKey, PCVerifierKey},
-    LabeledCommitment, LabeledPolynomial,
+    data_structures::PCVerifierKey, LabeledCommitment, LabeledPolynomial, PolynomialCommitment,
+    QuerySet,
 };
+use ark_serialize::CanonicalSerialize;
 use ark_std::marker::PhantomData;
-use fiat_shamir_rng::FiatShamirRng;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
-use rand::Rng;
+use fiat_shamir_rng::{Absorb, ChallengeSet, Context, ProtocolRng, Transcript};
+use homomorphic_poly_commit::{
+    commit_policy::CommitPolicy, opening_accumulator::VerifierAccumulator,
+    AdditivelyHomomorphicPCS, LENGTH_PREFIX_SIZE,
+};
 use rand_core::OsRng;
-use std::iter;
+use std::{collections::BTreeSet, iter};
+#[cfg(feature = "prover")]
+use {
+    crate::{
+        cancellation::{checkpoint, CancellationToken},
+        error::to_pc_error,
+        trace_event,
+    },
+    ark_poly_commit::data_structures::PCCommitterKey,
+    homomorphic_poly_commit::opening_accumulator::ProverAccumulator,
+    rand::Rng,
+};
 
+#[cfg(feature = "prover")]
+pub mod hints;
+pub mod labels;
 mod piop;
 pub mod proof;
+pub mod rounds;
 mod tests;
 
-/// zk-SNARK to prove that a virtual oracle evaluates to 0 over a given domain
-pub struct ZeroOverK<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> {
-    _field: PhantomData<F>,
-    _polynomial_commitment_scheme: PhantomData<PC>,
-    _fs: PhantomData<FS>,
+/// Degree bound of the random polynomials r_i and of q_2. Committer and verifier keys used with ZeroOverK must be
+/// trimmed with it, along with the degree bound enforced on the concrete oracles.
+pub const MASKING_DEGREE_BOUND: usize = 2;
+
+/// zk-SNARK to prove that a virtual oracle evaluates to 0 over a given domain K, or a coset of it
+pub struct ZeroOverK<C: ProtocolConfig> {
+    _config: PhantomData<C>,
 }
 
-impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOverK<F, PC, FS> {
+impl<C: ProtocolConfig> ZeroOverK<C> {
     pub const PROTOCOL_NAME: &'static [u8] = b"Zero Over K";
 
-    pub fn prove<R: Rng, VO: VirtualOracle<F>>(
-        concrete_oracles: &[LabeledPolynomial<F, DensePolynomial<F>>],
-        concrete_oracle_commitments: &[LabeledCommitment<PC::Commitment>],
-        concrete_oracle_commit_rands: &[PC::Randomness],
+    /// Check that each concrete oracle has the label of its commitment, and that the labels are fit for
+    /// [`Self::check_commitment_labels`]. Otherwise the commitments to the h'_i derived from the concrete commitments
+    /// are not those of the polynomials the prover opens. Degree bounds are not compared: an oracle may be committed
+    /// to without a bound, e.g. an index polynomial, and be passed with the bound the protocol enforces. With the
+    /// `strict-labels` feature the prover runs this check itself.
+    pub fn check_oracle_labels(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+    ) -> Result<(), Error> {
+        if concrete_oracles.len() != concrete_oracle_commitments.len() {
+            return Err(Error::InputLengthError(format!(
+                "Got {} concrete oracles but {} commitments",
+                concrete_oracles.len(),
+                concrete_oracle_commitments.len()
+            )));
+        }
+        for (oracle, commitment) in concrete_oracles.iter().zip(concrete_oracle_commitments) {
+            if oracle.label() != commitment.label() {
+                return Err(Error::LabelMismatch(format!(
+                    "Oracle {} is committed to under the label {}",
+                    oracle.label(),
+                    commitment.label()
+                )));
+            }
+        }
+        Self::check_commitment_labels(concrete_oracle_commitments)
+    }
+
+    /// Check that the labels of the concrete oracle commitments are distinct and that none of them is a label of the
+    /// [`labels`] registry, which would be confused with an internal polynomial in the batched opening. With the
+    /// `strict-labels` feature the verifier runs this check itself.
+    pub fn check_commitment_labels(
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+    ) -> Result<(), Error> {
+        let mut seen = BTreeSet::new();
+        for commitment in concrete_oracle_commitments {
+            if labels::is_reserved(commitment.label()) {
+                return Err(Error::LabelMismatch(format!(
+                    "Concrete oracle label {} is reserved by the protocol",
+                    commitment.label()
+                )));
+            }
+            if !seen.insert(commitment.label()) {
+                return Err(Error::LabelMismatch(format!(
+                    "Concrete oracle label {} is used twice",
+                    commitment.label()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The commitments and queries of a proof for `virtual_oracle`, without computing it: r_i, m_i and q_1 are
+    /// committed to, and h'_i, m_i, q_1 and q_2 opened at the shifts of beta_1 and beta_2 the verifier queries
+    pub fn prove_dry_run<VO: VirtualOracle<C::F>>(
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+    ) -> ProofShape {
+        let alphas = virtual_oracle.shifting_coefficients();
+        let mut shape = ProofShape::new();
+        for i in 0..virtual_oracle.num_of_variable_terms() {
+            shape.commit(labels::random(i), Some(MASKING_DEGREE_BOUND));
+        }
+        for i in 0..virtual_oracle.num_of_variable_terms() {
+            shape.commit(labels::masking(i), maximum_oracle_degree_bound);
+        }
+        shape.commit(labels::Q_1, None);
+        for challenge in ["beta_1", "beta_2", "c"] {
+            shape.challenge(challenge);
+        }
+
+        // as in the query set of the verifier, equal shifts share a point and a shift of 1 is beta itself
+        let point = |i: usize, beta: &str| match alphas[..i].iter().position(|a| *a == alphas[i]) {
+            _ if alphas[i].is_one() => beta.to_string(),
+            Some(j) => format!("alpha_{}_{}", j, beta),
+            None => format!("alpha_{}_{}", i, beta),
+        };
+        for i in 0..alphas.len() {
+            shape.query(labels::h_prime(i), point(i, "beta_1"));
+            shape.query(labels::masking(i), point(i, "beta_2"));
+        }
+        shape.query(labels::Q_1, "beta_1");
+        shape.query(labels::Q_2, "beta_2");
+
+        // the evaluations are absorbed before the openings are batched
+        shape.end_round();
+        shape.challenge("separation_challenge");
+        shape.check(
+            "check_1",
+            "sum_i c^i * m_i(beta_2) = q_2(beta_2) * Z_K(beta_2)",
+        );
+        shape.check(
+            "check_2",
+            "VO(h'_i(alpha_i * beta_1)) = q_1(beta_1) * Z_K(beta_1)",
+        );
+        shape.check(
+            "opening",
+            "batch opening of the queries under separation_challenge",
+        );
+
+        shape
+    }
+
+    /// Serialized size in bytes of a proof for `virtual_oracle` under `policy`, with keys of degree
+    /// `supported_degree`. Every point opens a masked oracle, which is hiding, but for beta_1 when it only opens q_1.
+    pub fn proof_size<VO: VirtualOracle<C::F>>(
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        policy: CommitPolicy,
+        supported_degree: usize,
+    ) -> usize {
+        let shape = Self::prove_dry_run(maximum_oracle_degree_bound, virtual_oracle);
+        let points_hiding = shape
+            .points()
+            .into_iter()
+            .map(|point| {
+                policy == CommitPolicy::Hiding
+                    || shape
+                        .queries
+                        .iter()
+                        .any(|q| q.point == point && q.label != labels::Q_1)
+            })
+            .collect::<Vec<_>>();
+
+        // the r_i and m_i, and the evaluations of h'_i and m_i, are length-prefixed vectors
+        policy.serialized_size()
+            + shape
+                .commitments
+                .iter()
+                .map(|c| C::PC::commitment_size(c.degree_bound.is_some()))
+                .sum::<usize>()
+            + shape.queries.len() * C::F::zero().serialized_size()
+            + 4 * LENGTH_PREFIX_SIZE
+            + C::PC::batch_proof_size(supported_degree, &points_hiding)
+    }
+
+    #[cfg(feature = "prover")]
+    pub fn prove<R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &GeneralEvaluationDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_with_policy(
+            concrete_oracles,
+            concrete_oracle_commitments,
+            concrete_oracle_commit_rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            ck,
+            CommitPolicy::NonHiding,
+            context,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove under a commit policy. With `CommitPolicy::Hiding`, q_1 is committed with hiding randomness and any
+    /// concrete or internal oracle committed with empty randomness makes the prover fail. The policy is recorded in
+    /// the proof and bound to the transcript.
+    pub fn prove_with_policy<R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &GeneralEvaluationDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        policy: CommitPolicy,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_over_coset(
+            concrete_oracles,
+            concrete_oracle_commitments,
+            concrete_oracle_commit_rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            &CosetDomain::subgroup(*domain),
+            ck,
+            policy,
+            context,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove under a commit policy that the virtual oracle vanishes over a coset of K rather than K itself. The
+    /// vanishing polynomial and the points outside the domain are those of the coset, whose offset is bound to the
+    /// transcript.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_over_coset<R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        policy: CommitPolicy,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            concrete_oracles,
+            concrete_oracle_commitments,
+            concrete_oracle_commit_rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            ck,
+            policy,
+            context,
+            None,
+            None,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove_over_coset`], on a transcript managed by the caller rather than one seeded by the
+    /// protocol. The statement is appended to the transcript, so the proof only verifies on a transcript in the same
+    /// state, see [`fiat_shamir_rng::transcript`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_transcript<R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        policy: CommitPolicy,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            concrete_oracles,
+            concrete_oracle_commitments,
+            concrete_oracle_commit_rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            ck,
+            policy,
+            context,
+            Some(transcript),
+            None,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove as [`Self::prove`], polling `cancellation` between the rounds, see [`crate::cancellation`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_cancellation<R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &GeneralEvaluationDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        context: &Context,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        Self::prove_in(
+            concrete_oracles,
+            concrete_oracle_commitments,
+            concrete_oracle_commit_rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            &CosetDomain::subgroup(*domain),
+            ck,
+            CommitPolicy::NonHiding,
+            context,
+            None,
+            cancellation,
+            rng,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove on the transcript of the caller if there is one, and on a transcript seeded by the protocol otherwise,
+    /// for protocols that run ZeroOverK as a sub-protocol of either. The checkpoints poll `cancellation`, if any.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_in<R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        policy: CommitPolicy,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
+        cancellation: Option<&CancellationToken>,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let _span = trace_span!(
+            "zero_over_k::prove",
+            domain_size = domain.size(),
+            oracles = concrete_oracles.len()
+        );
+        let (proof, openings, mut fs_rng) = Self::prove_rounds(
+            concrete_oracles,
+            concrete_oracle_commitments,
+            concrete_oracle_commit_rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            ck,
+            policy,
+            context,
+            transcript,
+            cancellation,
+            rng,
+        )?;
+
+        checkpoint(cancellation, "zero_over_k::open")?;
+        let round = trace_span!("open", polynomials = openings.polynomials.len());
+        let separation_challenge: C::F =
+            ChallengeSet::new(&mut fs_rng, b"opening").challenge(b"separation_challenge");
+
+        let batch_opening = C::PC::batch_open(
+            ck,
+            &openings.polynomials,
+            &openings.commitments,
+            &openings.query_set,
+            separation_challenge,
+            &openings.rands,
+            Some(rng),
+        )
+        .map_err(to_pc_error::<C::F, C::PC>)?;
+        round.exit();
+
+        let proof = Proof::from_deferred(proof, batch_opening);
+        trace_event!(proof_bytes = proof.serialized_size());
+        Ok(proof)
+    }
+
+    #[cfg(feature = "prover")]
+    /// Prove on the transcript of the caller, deferring the opening of the oracles to `accumulator`. Protocols that
+    /// compose several sub-proofs, possibly over different domains, then open all of their oracles at once, see
+    /// [`homomorphic_poly_commit::opening_accumulator`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_deferred<R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        policy: CommitPolicy,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+        accumulator: &mut ProverAccumulator<C::F, C::PC>,
+        rng: &mut R,
+    ) -> Result<DeferredProof<C>, Error> {
+        let _span = trace_span!(
+            "zero_over_k::prove_deferred",
+            domain_size = domain.size(),
+            oracles = concrete_oracles.len()
+        );
+        let (proof, openings, _) = Self::prove_rounds(
+            concrete_oracles,
+            concrete_oracle_commitments,
+            concrete_oracle_commit_rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            ck,
+            policy,
+            context,
+            Some(transcript),
+            None,
+            rng,
+        )?;
+        accumulator.add(
+            &openings.polynomials,
+            &openings.commitments,
+            &openings.rands,
+            &openings.query_set,
+        );
+        Ok(proof)
+    }
+
+    #[cfg(feature = "prover")]
+    /// The rounds of the prover up to the opening of the oracles, which are returned along with the transcript
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn prove_rounds<'a, R: Rng, VO: VirtualOracle<C::F>>(
+        concrete_oracles: &[&LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        concrete_oracle_commit_rands: &[pc::Randomness<C>],
         maximum_oracle_degree_bound: Option<usize>,
         virtual_oracle: &VO,
-        domain: &GeneralEvaluationDomain<F>,
-        ck: &PC::CommitterKey,
+        domain: &CosetDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        policy: CommitPolicy,
+        context: &Context,
+        transcript: Option<&'a mut dyn Transcript>,
+        cancellation: Option<&CancellationToken>,
         rng: &mut R,
-    ) -> Result<Proof<F, PC>, Error> {
+    ) -> Result<(DeferredProof<C>, Openings<C>, ProtocolRng<'a, C::FS>), Error> {
         if let Some(degree) = maximum_oracle_degree_bound {
             if degree > ck.supported_degree() {
                 return Err(Error::UnsupportedDegree(format!(
@@ -54,6 +482,9 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             }
         };
 
+        #[cfg(feature = "strict-labels")]
+        Self::check_oracle_labels(concrete_oracles, concrete_oracle_commitments)?;
+
         let alphas = virtual_oracle.shifting_coefficients();
 
         let prover_initial_state = PIOPforZeroOverK::prover_init(
@@ -62,53 +493,102 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             maximum_oracle_degree_bound,
             virtual_oracle,
             &alphas,
+            policy.hiding_bound(),
         )?;
-        let verifier_initial_state = PIOPforZeroOverK::<F, VO>::verifier_init(
+        for (oracle, rand) in concrete_oracles.iter().zip(concrete_oracle_commit_rands) {
+            policy.check::<C::F, C::PC>(oracle.label(), rand)?;
+        }
+
+        let verifier_initial_state = PIOPforZeroOverK::<C::F, VO>::verifier_init(
             virtual_oracle,
             maximum_oracle_degree_bound,
             domain,
         )?;
 
-        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, concrete_oracle_commitments, alphas]
-            .map_err(|_| Error::ToBytesError)?;
-        let mut fs_rng = FS::initialize(fs_bytes);
+        let fs_bytes = &to_bytes![
+            &Self::PROTOCOL_NAME,
+            context,
+            policy,
+            concrete_oracle_commitments,
+            alphas,
+            virtual_oracle.public_terms(),
+            domain
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        let mut fs_rng = ProtocolRng::<C::FS>::new(transcript, fs_bytes);
 
         //------------------------------------------------------------------
         // First Round
+        checkpoint(cancellation, "zero_over_k::commit")?;
+        let round = trace_span!("commit");
         let (_, prover_first_oracles, prover_state) =
             PIOPforZeroOverK::prover_first_round(prover_initial_state, rng)?;
 
-        let random_polynomials = prover_first_oracles.random_polynomials.clone();
-        let masking_polynomials = prover_first_oracles.masking_polynomials.clone();
-        let q_1 = prover_first_oracles.q_1.clone();
-
         // commit to the random polynomials
-        let (r_commitments, r_rands) =
-            PC::commit(ck, random_polynomials.iter(), Some(rng)).map_err(to_pc_error::<F, PC>)?;
+        let (r_commitments, r_rands) = C::PC::commit(
+            ck,
+            prover_first_oracles.random_polynomials.iter(),
+            Some(rng),
+        )
+        .map_err(to_pc_error::<C::F, C::PC>)?;
 
         // commit to the masking polynomials
-        let (m_commitments, m_rands) =
-            PC::commit(ck, masking_polynomials.iter(), Some(rng)).map_err(to_pc_error::<F, PC>)?;
+        let (m_commitments, m_rands) = C::PC::commit(
+            ck,
+            prover_first_oracles.masking_polynomials.iter(),
+            Some(rng),
+        )
+        .map_err(to_pc_error::<C::F, C::PC>)?;
 
         // commit to q_1
         let (q1_commit, q1_rand) =
-            PC::commit(ck, &[q_1], Some(rng)).map_err(to_pc_error::<F, PC>)?;
+            C::PC::commit(ck, iter::once(&prover_first_oracles.q_1), Some(rng))
+                .map_err(to_pc_error::<C::F, C::PC>)?;
+
+        let internal_oracles = prover_first_oracles
+            .random_polynomials
+            .iter()
+            .chain(prover_first_oracles.masking_polynomials.iter())
+            .chain(iter::once(&prover_first_oracles.q_1));
+        let internal_rands = r_rands.iter().chain(m_rands.iter()).chain(q1_rand.iter());
+        for (oracle, rand) in internal_oracles.zip(internal_rands) {
+            policy.check::<C::F, C::PC>(oracle.label(), rand)?;
+        }
 
+        trace_event!(
+            commitments = r_commitments.len() + m_commitments.len() + 1,
+            commitment_bytes = r_commitments
+                .iter()
+                .chain(m_commitments.iter())
+                .chain(q1_commit.iter())
+                .map(|c| c.commitment().serialized_size())
+                .sum::<usize>()
+        );
+        round.exit();
+
+        checkpoint(cancellation, "zero_over_k::challenge")?;
+        let round = trace_span!("challenge");
         let fs_bytes =
             &to_bytes![r_commitments, m_commitments, q1_commit].map_err(|_| Error::ToBytesError)?;
         fs_rng.absorb(fs_bytes);
 
         let (verifier_first_msg, verifier_state) =
-            PIOPforZeroOverK::<F, VO>::verifier_first_round(verifier_initial_state, &mut fs_rng)?;
+            PIOPforZeroOverK::<C::F, VO>::verifier_first_round(
+                verifier_initial_state,
+                &mut fs_rng,
+            )?;
+        round.exit();
         //------------------------------------------------------------------
 
         //------------------------------------------------------------------
         // Second Round
+        checkpoint(cancellation, "zero_over_k::evaluate")?;
+        let _round = trace_span!("evaluate");
 
         let (_prover_second_msg, prover_second_oracles, prover_state) =
             PIOPforZeroOverK::prover_second_round(&verifier_first_msg, prover_state, rng);
 
-        let query_set = PIOPforZeroOverK::<F, VO>::verifier_query_set(&verifier_state, &alphas)?;
+        let query_set = PIOPforZeroOverK::<C::F, VO>::verifier_query_set(&verifier_state, &alphas)?;
         //------------------------------------------------------------------
 
         let h_primes = prover_state
@@ -122,14 +602,21 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             .chain(iter::once(&prover_first_oracles.q_1))
             .chain(iter::once(&prover_second_oracles.q_2));
 
+        #[cfg(feature = "strict-labels")]
+        check_opened_labels(
+            polynomials.clone().map(|p| p.label()),
+            &query_set,
+            virtual_oracle.num_of_variable_terms(),
+        )?;
+
         // it gives us ((poly_label, point), evaluation)
         let evaluated_query_set =
             ark_poly_commit::evaluate_query_set(polynomials.clone(), &query_set);
 
         let mut h_prime_evals = Vec::new();
         let mut m_evals = Vec::new();
-        let mut q1_eval: Option<F> = None;
-        let mut q2_eval: Option<F> = None;
+        let mut q1_eval: Option<C::F> = None;
+        let mut q2_eval: Option<C::F> = None;
 
         // Extract evaluations from the `evaluated_query_set`
         for ((poly_label, _), &evaluation) in &evaluated_query_set {
@@ -137,23 +624,27 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
                 h_prime_evals.push((poly_label, evaluation));
             } else if poly_label.contains("m_") {
                 m_evals.push((poly_label, evaluation));
-            } else if poly_label == "q_1" {
+            } else if poly_label == labels::Q_1 {
                 q1_eval = Some(evaluation);
-            } else if poly_label == "q_2" {
+            } else if poly_label == labels::Q_2 {
                 q2_eval = Some(evaluation);
             }
         }
 
         // sort the evaluation by poly label name => h_prime_is, m_is, q_1, q_2
         h_prime_evals.sort_by(|a, b| a.0.cmp(b.0));
-        let h_prime_evals = h_prime_evals.into_iter().map(|x| x.1).collect::<Vec<F>>();
+        let h_prime_evals = h_prime_evals
+            .into_iter()
+            .map(|x| x.1)
+            .collect::<Vec<C::F>>();
         m_evals.sort_by(|a, b| a.0.cmp(b.0));
-        let m_evals = m_evals.into_iter().map(|x| x.1).collect::<Vec<F>>();
+        let m_evals = m_evals.into_iter().map(|x| x.1).collect::<Vec<C::F>>();
 
         // sanity checks
         let q1_eval = q1_eval.expect("q_1 was not evaluated");
         let q2_eval = q2_eval.expect("q_2 was not evaluated");
         assert_eq!(h_prime_evals.len(), m_evals.len());
+        trace_event!(evaluations = h_prime_evals.len() + m_evals.len() + 2);
 
         let fs_bytes = &to_bytes![h_prime_evals, m_evals, q1_eval, q2_eval]
             .map_err(|_| Error::ToBytesError)?;
@@ -163,7 +654,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
         let q2_linear_combination =
             PIOPforZeroOverK::generate_q2_linear_combination(virtual_oracle, verifier_first_msg.c);
 
-        let (q2_commit, q2_rand) = PC::aggregate_commitments(
+        let (q2_commit, q2_rand) = C::PC::aggregate_commitments(
             &r_commitments,
             Some(r_rands.to_vec()),
             &q2_linear_combination,
@@ -189,8 +680,11 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
         let mut h_prime_commitments = Vec::new();
         let mut h_prime_rands = Vec::new();
         for lc in h_prime_lcs {
-            let (comm, rand) =
-                PC::aggregate_commitments(&h_and_m_commitments, Some(h_and_m_rands.to_vec()), &lc)?;
+            let (comm, rand) = C::PC::aggregate_commitments(
+                &h_and_m_commitments,
+                Some(h_and_m_rands.to_vec()),
+                &lc,
+            )?;
             h_prime_commitments.push(comm);
             h_prime_rands.push(rand);
         }
@@ -211,20 +705,16 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             .chain(q1_rand.iter())
             .chain(iter::once(&q2_rand));
 
-        let separation_challenge = F::rand(&mut fs_rng);
+        let openings = Openings {
+            polynomials: polynomials.cloned().collect(),
+            commitments: commitments.cloned().collect(),
+            rands: rands.cloned().collect(),
+            query_set,
+        };
 
-        let batch_opening = PC::batch_open(
-            ck,
-            polynomials.clone(),
-            commitments,
-            &query_set,
-            separation_challenge,
-            rands,
-            Some(rng),
-        )
-        .map_err(to_pc_error::<F, PC>)?;
+        let proof = DeferredProof {
+            policy,
 
-        let proof = Proof {
             // commitments
             m_commitments: m_commitments
                 .iter()
@@ -241,21 +731,175 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             q2_eval,
             h_prime_evals,
             m_evals,
-
-            opening_proof: batch_opening,
         };
 
-        Ok(proof)
+        Ok((proof, openings, fs_rng))
     }
 
-    pub fn verify<VO: VirtualOracle<F>>(
-        proof: Proof<F, PC>,
-        concrete_oracle_commitments: &[LabeledCommitment<PC::Commitment>],
+    pub fn verify<VO: VirtualOracle<C::F>>(
+        proof: Proof<C>,
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
         maximum_oracle_degree_bound: Option<usize>,
         virtual_oracle: &VO,
-        domain: &GeneralEvaluationDomain<F>,
-        vk: &PC::VerifierKey,
+        domain: &GeneralEvaluationDomain<C::F>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
     ) -> Result<(), Error> {
+        Self::verify_over_coset(
+            proof,
+            concrete_oracle_commitments,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            &CosetDomain::subgroup(*domain),
+            vk,
+            context,
+        )
+    }
+
+    /// Verify that the virtual oracle vanishes over a coset of K, see [`Self::prove_over_coset`]
+    pub fn verify_over_coset<VO: VirtualOracle<C::F>>(
+        proof: Proof<C>,
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        Self::verify_in(
+            proof,
+            concrete_oracle_commitments,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            vk,
+            context,
+            None,
+        )
+    }
+
+    /// Verify a proof of [`Self::prove_with_transcript`], on a transcript in the state the prover's was in
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_transcript<VO: VirtualOracle<C::F>>(
+        proof: Proof<C>,
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+    ) -> Result<(), Error> {
+        Self::verify_in(
+            proof,
+            concrete_oracle_commitments,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            vk,
+            context,
+            Some(transcript),
+        )
+    }
+
+    /// Verify on the transcript of the caller if there is one, see [`Self::prove_in`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_in<VO: VirtualOracle<C::F>>(
+        proof: Proof<C>,
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+        transcript: Option<&mut dyn Transcript>,
+    ) -> Result<(), Error> {
+        let _span = trace_span!(
+            "zero_over_k::verify",
+            domain_size = domain.size(),
+            oracles = concrete_oracle_commitments.len()
+        );
+        let (proof, opening_proof) = proof.into_deferred();
+        let (claims, mut fs_rng) = Self::verify_rounds(
+            &proof,
+            concrete_oracle_commitments,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            vk,
+            context,
+            transcript,
+        )?;
+
+        let round = trace_span!("open", commitments = claims.commitments.len());
+        let separation_challenge: C::F =
+            ChallengeSet::new(&mut fs_rng, b"opening").challenge(b"separation_challenge");
+
+        match C::PC::batch_check(
+            vk,
+            &claims.commitments,
+            &claims.query_set,
+            &claims.evaluations,
+            &opening_proof,
+            separation_challenge,
+            &mut OsRng,
+        ) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::BatchCheckError),
+            Err(e) => panic!("{:?}", e),
+        }?;
+        round.exit();
+
+        Self::check_evaluations(&proof, virtual_oracle, domain, &claims)
+    }
+
+    /// Verify a proof of [`Self::prove_deferred`] on the transcript of the caller. The evaluations it claims are
+    /// only checked against the commitments once the caller checks `accumulator`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_deferred<VO: VirtualOracle<C::F>>(
+        proof: &DeferredProof<C>,
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+        transcript: &mut dyn Transcript,
+        accumulator: &mut VerifierAccumulator<C::F, C::PC>,
+    ) -> Result<(), Error> {
+        let _span = trace_span!(
+            "zero_over_k::verify_deferred",
+            domain_size = domain.size(),
+            oracles = concrete_oracle_commitments.len()
+        );
+        let (claims, _) = Self::verify_rounds(
+            proof,
+            concrete_oracle_commitments,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            domain,
+            vk,
+            context,
+            Some(transcript),
+        )?;
+        Self::check_evaluations(proof, virtual_oracle, domain, &claims)?;
+        accumulator.add(&claims.commitments, &claims.query_set, &claims.evaluations);
+        Ok(())
+    }
+
+    /// The rounds of the verifier up to the opening of the oracles, whose claimed evaluations are returned along
+    /// with the transcript
+    #[allow(clippy::too_many_arguments)]
+    fn verify_rounds<'a, VO: VirtualOracle<C::F>>(
+        proof: &DeferredProof<C>,
+        concrete_oracle_commitments: &[LabeledCommitment<pc::Commitment<C>>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+        transcript: Option<&'a mut dyn Transcript>,
+    ) -> Result<(Claims<C>, ProtocolRng<'a, C::FS>), Error> {
         if let Some(degree) = maximum_oracle_degree_bound {
             if degree > vk.supported_degree() {
                 return Err(Error::UnsupportedDegree(format!(
@@ -265,26 +909,42 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             }
         };
 
+        #[cfg(feature = "strict-labels")]
+        Self::check_commitment_labels(concrete_oracle_commitments)?;
+
         let alphas = virtual_oracle.shifting_coefficients();
 
-        let verifier_initial_state = PIOPforZeroOverK::<F, VO>::verifier_init(
+        let verifier_initial_state = PIOPforZeroOverK::<C::F, VO>::verifier_init(
             virtual_oracle,
             maximum_oracle_degree_bound,
             domain,
         )?;
 
-        let fs_bytes = &to_bytes![&Self::PROTOCOL_NAME, concrete_oracle_commitments, alphas]
-            .map_err(|_| Error::ToBytesError)?;
-        let mut fs_rng = FS::initialize(fs_bytes);
+        let fs_bytes = &to_bytes![
+            &Self::PROTOCOL_NAME,
+            context,
+            proof.policy,
+            concrete_oracle_commitments,
+            alphas,
+            virtual_oracle.public_terms(),
+            domain
+        ]
+        .map_err(|_| Error::ToBytesError)?;
+        let mut fs_rng = ProtocolRng::<C::FS>::new(transcript, fs_bytes);
 
         //------------------------------------------------------------------
         // First Round
+        let round = trace_span!("challenge");
         let fs_bytes = &to_bytes![proof.r_commitments, proof.m_commitments, proof.q1_commit]
             .map_err(|_| Error::ToBytesError)?;
         fs_rng.absorb(fs_bytes);
 
         let (verifier_first_msg, verifier_state) =
-            PIOPforZeroOverK::<F, VO>::verifier_first_round(verifier_initial_state, &mut fs_rng)?;
+            PIOPforZeroOverK::<C::F, VO>::verifier_first_round(
+                verifier_initial_state,
+                &mut fs_rng,
+            )?;
+        round.exit();
 
         //------------------------------------------------------------------
         // Second Round
@@ -297,7 +957,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
         .map_err(|_| Error::ToBytesError)?;
         fs_rng.absorb(fs_bytes);
 
-        let query_set = PIOPforZeroOverK::<F, VO>::verifier_query_set(&verifier_state, &alphas)?;
+        let query_set = PIOPforZeroOverK::<C::F, VO>::verifier_query_set(&verifier_state, &alphas)?;
 
         let beta_1 = verifier_state
             .beta_1
@@ -310,7 +970,9 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             .r_commitments
             .iter()
             .enumerate()
-            .map(|(i, c)| LabeledCommitment::new(format!("r_{}", i), c.clone(), Some(2)))
+            .map(|(i, c)| {
+                LabeledCommitment::new(labels::random(i), c.clone(), Some(MASKING_DEGREE_BOUND))
+            })
             .collect::<Vec<_>>();
 
         let m_commitments = proof
@@ -318,7 +980,7 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             .iter()
             .enumerate()
             .map(|(i, c)| {
-                LabeledCommitment::new(format!("m_{}", i), c.clone(), maximum_oracle_degree_bound)
+                LabeledCommitment::new(labels::masking(i), c.clone(), maximum_oracle_degree_bound)
             })
             .collect::<Vec<_>>();
 
@@ -335,20 +997,21 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
         );
         let (h_prime_commitments, _): (Vec<_>, Vec<_>) = h_prime_lcs
             .iter()
-            .map(|lc| PC::aggregate_commitments(&h_and_m_commitments, None, lc))
+            .map(|lc| C::PC::aggregate_commitments(&h_and_m_commitments, None, lc))
             .collect::<Result<Vec<_>, _>>()?
             .iter()
             .cloned()
             .unzip();
 
-        let q1_commit = LabeledCommitment::new(String::from("q_1"), proof.q1_commit, None);
+        let q1_commit =
+            LabeledCommitment::new(labels::Q_1.to_string(), proof.q1_commit.clone(), None);
 
         // derive commitment to q2 through additive homomorphism
         let q2_linear_combination =
             PIOPforZeroOverK::generate_q2_linear_combination(virtual_oracle, verifier_first_msg.c);
 
         let (q2_commit, _) =
-            PC::aggregate_commitments(&r_commitments, None, &q2_linear_combination)?;
+            C::PC::aggregate_commitments(&r_commitments, None, &q2_linear_combination)?;
 
         let fs_bytes =
             &to_bytes![h_prime_commitments, q2_commit].map_err(|_| Error::ToBytesError)?;
@@ -381,47 +1044,60 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
             .chain(iter::once(&q1_commit))
             .chain(iter::once(&q2_commit));
 
-        let separation_challenge = F::rand(&mut fs_rng);
-
-        match PC::batch_check(
-            vk,
-            commitments,
+        #[cfg(feature = "strict-labels")]
+        check_opened_labels(
+            commitments.clone().map(|c| c.label()),
             &query_set,
-            &evaluations,
-            &proof.opening_proof,
-            separation_challenge,
-            &mut OsRng,
-        ) {
-            Ok(true) => Ok(()),
-            Ok(false) => Err(Error::BatchCheckError),
-            Err(e) => panic!("{:?}", e),
-        }?;
+            virtual_oracle.num_of_variable_terms(),
+        )?;
+
+        let claims = Claims {
+            commitments: commitments.cloned().collect(),
+            query_set,
+            evaluations,
+            beta_1,
+            beta_2,
+            c: verifier_first_msg.c,
+        };
+        Ok((claims, fs_rng))
+    }
+
+    /// Check that the claimed evaluations satisfy the identities of the protocol at beta_1 and beta_2
+    fn check_evaluations<VO: VirtualOracle<C::F>>(
+        proof: &DeferredProof<C>,
+        virtual_oracle: &VO,
+        domain: &CosetDomain<C::F>,
+        claims: &Claims<C>,
+    ) -> Result<(), Error> {
+        // the evaluations come sorted by label, which puts h_prime_10 before h_prime_2: bring them back to the
+        // order of the terms
+        let h_prime_evals = in_term_order(&proof.h_prime_evals);
+        let m_evals = in_term_order(&proof.m_evals);
 
         // compute M(beta_2)
-        let big_m_at_beta_2 = &proof
-            .m_evals
+        let big_m_at_beta_2 = &m_evals
             .iter()
-            .zip(powers_of(verifier_first_msg.c))
-            .fold(F::zero(), |acc, (&m_eval, c_power)| {
+            .zip(powers_of(claims.c))
+            .fold(C::F::zero(), |acc, (&m_eval, c_power)| {
                 acc + (m_eval * c_power)
             });
 
         // evaluate z_k(beta_1), z_k(beta_2)
-        let z_k_at_beta_1 = domain.evaluate_vanishing_polynomial(beta_1);
-        let z_k_at_beta_2 = domain.evaluate_vanishing_polynomial(beta_2);
+        let z_k_at_beta_1 = domain.evaluate_vanishing_polynomial(claims.beta_1);
+        let z_k_at_beta_2 = domain.evaluate_vanishing_polynomial(claims.beta_2);
 
         // compute F_prime(beta_1)
-        let f_prime_eval = compute_f_prime_eval(virtual_oracle, &proof.h_prime_evals, &beta_1)?;
+        let f_prime_eval = compute_f_prime_eval(virtual_oracle, &h_prime_evals, &claims.beta_1)?;
 
         // check that M(beta_2) - q2(beta_2)*zK(beta_2) = 0
         let check_1 = *big_m_at_beta_2 - proof.q2_eval * z_k_at_beta_2;
-        if check_1 != F::zero() {
+        if check_1 != C::F::zero() {
             return Err(Error::Check1Failed);
         }
 
         // check that F_prime(beta_1) - q1(beta_1)*zK(beta_1) = 0
         let check_2 = f_prime_eval - proof.q1_eval * z_k_at_beta_1;
-        if check_2 != F::zero() {
+        if check_2 != C::F::zero() {
             return Err(Error::Check2Failed);
         }
 
@@ -429,15 +1105,79 @@ impl<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>, FS: FiatShamirRng> ZeroOver
     }
 }
 
+/// The polynomials a prover opens, with their commitments and randomness in the same order
+struct Openings<C: ProtocolConfig> {
+    polynomials: Vec<LabeledPolynomial<C::F, DensePolynomial<C::F>>>,
+    commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+    rands: Vec<pc::Randomness<C>>,
+    query_set: QuerySet<C::F>,
+}
+
+/// The evaluations a proof claims for the commitments the verifier derived, and the challenges they are checked at
+struct Claims<C: ProtocolConfig> {
+    commitments: Vec<LabeledCommitment<pc::Commitment<C>>>,
+    query_set: QuerySet<C::F>,
+    evaluations: Evaluations<C::F, C::F>,
+    beta_1: C::F,
+    beta_2: C::F,
+    c: C::F,
+}
+
+/// Check that the polynomials opened in the batch and those in the query set carry exactly the labels of the
+/// registry for the virtual oracle
+#[cfg(feature = "strict-labels")]
+fn check_opened_labels<'a, F: PrimeField>(
+    opened: impl Iterator<Item = &'a String>,
+    query_set: &ark_poly_commit::QuerySet<F>,
+    num_of_terms: usize,
+) -> Result<(), Error> {
+    let expected = labels::opened(num_of_terms);
+    let opened = opened.cloned().collect::<BTreeSet<_>>();
+    if opened != expected {
+        return Err(Error::LabelMismatch(format!(
+            "Opened polynomials {:?} but expected {:?}",
+            opened, expected
+        )));
+    }
+    let queried = query_set
+        .iter()
+        .map(|(label, _)| label.clone())
+        .collect::<BTreeSet<_>>();
+    if queried != expected {
+        return Err(Error::LabelMismatch(format!(
+            "Queried polynomials {:?} but expected {:?}",
+            queried, expected
+        )));
+    }
+    Ok(())
+}
+
+/// Reorder evaluations sorted by their labels `{prefix}_{i}` into the order of i
+fn in_term_order<F: Copy>(evals: &[F]) -> Vec<F> {
+    let mut indices = (0..evals.len()).collect::<Vec<_>>();
+    indices.sort_by_key(|i| i.to_string());
+
+    let mut ordered = evals.to_vec();
+    for (&eval, index) in evals.iter().zip(indices) {
+        ordered[index] = eval;
+    }
+    ordered
+}
+
+/// Evaluate F' at `point` from the evaluations of the h'_i, the public terms being evaluated by the verifier itself
 fn compute_f_prime_eval<F: PrimeField, VO: VirtualOracle<F>>(
     virtual_oracle: &VO,
     evals: &[F],
     point: &F,
 ) -> Result<F, Error> {
-    let terms: Vec<_> = vec![point.clone()]
+    let public_evals = virtual_oracle
+        .public_terms()
         .iter()
-        .chain(evals.iter())
-        .map(|e| VOTerm::Evaluation(e.clone()))
+        .map(|term| term.evaluate(point));
+    let terms: Vec<_> = iter::once(*point)
+        .chain(evals.iter().cloned())
+        .chain(public_evals)
+        .map(VOTerm::Evaluation)
         .collect();
 
     match virtual_oracle.apply_evaluation_function(&terms) {
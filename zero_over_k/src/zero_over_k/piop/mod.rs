@@ -1,12 +1,14 @@
 use crate::{
     util::powers_of,
     virtual_oracle::{get_term_labels, VirtualOracle},
+    zero_over_k::labels,
 };
 use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly_commit::{LinearCombination, PolynomialLabel};
 use ark_std::marker::PhantomData;
 
+#[cfg(feature = "prover")]
 mod prover;
 mod verifier;
 
@@ -23,13 +25,13 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
     pub fn get_h_prime_labels(virtual_oracle: &VO) -> impl Iterator<Item = PolynomialLabel> {
         (0..virtual_oracle.num_of_variable_terms())
             .enumerate()
-            .map(|(i, _)| format!("h_prime_{}", i))
+            .map(|(i, _)| labels::h_prime(i))
     }
 
     pub fn get_r_labels(virtual_oracle: &VO) -> impl Iterator<Item = PolynomialLabel> {
         (0..virtual_oracle.num_of_variable_terms())
             .enumerate()
-            .map(|(i, _)| format!("r_{}", i))
+            .map(|(i, _)| labels::random(i))
     }
 
     pub fn generate_h_prime_linear_combinations(
@@ -46,7 +48,7 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
                 label,
                 vec![
                     (F::one(), h_labels[i].clone()),
-                    (F::one(), format!("m_{}", i)),
+                    (F::one(), labels::masking(i)),
                 ],
             );
             linear_combinations.push(lc)
@@ -65,6 +67,6 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
             .zip(powers_of(q2_separation_challenge))
             .map(|(r_label, challenge_power)| (challenge_power, r_label))
             .collect();
-        LinearCombination::new("q_2", terms)
+        LinearCombination::new(labels::Q_2, terms)
     }
 }
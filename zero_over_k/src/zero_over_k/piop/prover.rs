@@ -1,21 +1,26 @@
 use super::PIOPforZeroOverK;
+use crate::coset_domain::CosetDomain;
 use crate::error::Error;
+use crate::masking::{mask_domain, Masking};
+use crate::quotient::divide_by_vanishing_polynomial;
 use crate::util::*;
 use crate::virtual_oracle::generic_shifting_vo::vo_term::VOTerm;
-use crate::virtual_oracle::VirtualOracle;
+use crate::virtual_oracle::{check_oracle_degree, VirtualOracle};
 use crate::zero_over_k::piop::{verifier::VerifierFirstMsg, LabeledPolynomial};
+use crate::zero_over_k::{labels, MASKING_DEGREE_BOUND};
 use ark_ff::{PrimeField, Zero};
 use ark_marlin::ahp::prover::ProverMsg;
 use ark_poly::{
-    univariate::DenseOrSparsePolynomial, univariate::DensePolynomial, EvaluationDomain,
-    GeneralEvaluationDomain, UVPolynomial,
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial,
+    UVPolynomial,
 };
 use ark_std::rand::Rng;
+use derivative::Derivative;
 use std::iter;
 
 // TODO: change to use the new VirtualOracle implementation
 pub struct ProverState<'a, F: PrimeField, VO: VirtualOracle<F>> {
-    all_concrete_oracles: &'a [LabeledPolynomial<F>],
+    all_concrete_oracles: &'a [&'a LabeledPolynomial<F>],
 
     maximum_oracle_degree_bound: Option<usize>,
 
@@ -23,29 +28,37 @@ pub struct ProverState<'a, F: PrimeField, VO: VirtualOracle<F>> {
 
     virtual_oracle: &'a VO,
 
-    /// domain K over which a virtual oracle should be equal to 0
-    domain_k: &'a GeneralEvaluationDomain<F>,
+    /// domain K, or a coset of it, over which a virtual oracle should be equal to 0
+    domain_k: &'a CosetDomain<F>,
 
-    masking_polynomials: Option<Vec<LabeledPolynomial<F>>>,
+    /// hiding bound of q_1, the only prover oracle whose hiding depends on the commit policy
+    q1_hiding_bound: Option<usize>,
 
     random_polynomials: Option<Vec<LabeledPolynomial<F>>>,
 
     // this variable is made public to avoid recomputing the masked oracles at the PIOP-to-SNARK compiler stage
     pub masked_oracles: Option<Vec<LabeledPolynomial<F>>>,
 
-    q_1: Option<LabeledPolynomial<F>>,
-
     q_2: Option<LabeledPolynomial<F>>,
 
     verifier_message: Option<VerifierFirstMsg<F>>,
 }
 
 /// The first set of prover oracles
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
 pub struct ProverFirstOracles<F: PrimeField> {
+    #[derivative(PartialEq(
+        compare_with = "homomorphic_poly_commit::labeled::polynomial::vec::eq"
+    ))]
     pub masking_polynomials: Vec<LabeledPolynomial<F>>,
 
+    #[derivative(PartialEq(
+        compare_with = "homomorphic_poly_commit::labeled::polynomial::vec::eq"
+    ))]
     pub random_polynomials: Vec<LabeledPolynomial<F>>,
 
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub q_1: LabeledPolynomial<F>,
 }
 
@@ -60,7 +73,10 @@ impl<F: PrimeField> ProverFirstOracles<F> {
 }
 
 /// The second set of prover oracles
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq(bound = ""), Eq(bound = ""))]
 pub struct ProverSecondOracles<F: PrimeField> {
+    #[derivative(PartialEq(compare_with = "homomorphic_poly_commit::labeled::polynomial::eq"))]
     pub q_2: LabeledPolynomial<F>,
 }
 
@@ -74,11 +90,12 @@ impl<F: PrimeField> ProverSecondOracles<F> {
 impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
     /// Return the initial prover state
     pub fn prover_init<'a>(
-        domain: &'a GeneralEvaluationDomain<F>,
-        all_concrete_oracles: &'a [LabeledPolynomial<F>],
+        domain: &'a CosetDomain<F>,
+        all_concrete_oracles: &'a [&'a LabeledPolynomial<F>],
         maximum_oracle_degree_bound: Option<usize>,
         virtual_oracle: &'a VO,
         alphas: &'a Vec<F>,
+        q1_hiding_bound: Option<usize>,
     ) -> Result<ProverState<'a, F, VO>, Error> {
         Ok(ProverState {
             all_concrete_oracles,
@@ -86,10 +103,9 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
             alphas,
             virtual_oracle,
             domain_k: domain,
-            masking_polynomials: None,
+            q1_hiding_bound,
             random_polynomials: None,
             masked_oracles: None,
-            q_1: None,
             q_2: None,
             verifier_message: None,
         })
@@ -116,8 +132,11 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
             .virtual_oracle
             .mapping_vector()
             .iter()
-            .map(|&mapped_index| state.all_concrete_oracles[mapped_index].clone())
+            .map(|&mapped_index| state.all_concrete_oracles[mapped_index])
             .collect();
+        for oracle in &h_polynomials {
+            check_oracle_degree(oracle)?;
+        }
 
         // compute the masked oracles
         let h_primes = h_polynomials
@@ -126,7 +145,7 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
             .enumerate()
             .map(|(i, (oracle, masking_poly))| {
                 LabeledPolynomial::new(
-                    format!("h_prime_{}", i),
+                    labels::h_prime(i),
                     oracle.polynomial() + masking_poly.polynomial(),
                     state.maximum_oracle_degree_bound,
                     Some(1),
@@ -134,32 +153,38 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
             })
             .collect::<Vec<_>>();
 
-        // Compute f_prime using the virtual oracle's function
-        let f_prime = compute_f_prime(state.virtual_oracle, &h_primes)?;
+        // Compute f_prime using the virtual oracle's function. When the VO's degree is known, it is evaluated
+        // pointwise over a large enough coset rather than in coefficient form.
+        let f_prime = match state.virtual_oracle.scaling_factor() {
+            Some(scaling_factor) => {
+                compute_f_prime_over_coset(state.virtual_oracle, &h_primes, scaling_factor)?
+            }
+            None => compute_f_prime(state.virtual_oracle, &h_primes)?,
+        };
 
         // divide by the vanishing polynomial
-        let (quotient, _r) = DenseOrSparsePolynomial::from(&f_prime)
-            .divide_with_q_and_r(&DenseOrSparsePolynomial::from(
-                &domain.vanishing_polynomial(),
-            ))
-            .unwrap();
+        let (quotient, _r) = divide_by_vanishing_polynomial(&f_prime, domain);
 
         // sanity check
         // assert_eq!(_r, DensePolynomial::<F>::zero());
 
         let msg = ProverMsg::EmptyMessage;
 
-        let q_1 = LabeledPolynomial::new(String::from("q_1"), quotient, None, None); // TODO: enforce degree bound on q1. Requires degree of the VO function
+        let q_1 = LabeledPolynomial::new(
+            labels::Q_1.to_string(),
+            quotient,
+            None,
+            state.q1_hiding_bound,
+        ); // TODO: enforce degree bound on q1. Requires degree of the VO function
 
+        // the random polynomials have degree 1, only they are kept in the state for the second round
+        state.random_polynomials = Some(random_polynomials.clone());
         let oracles = ProverFirstOracles {
-            masking_polynomials: masking_polynomials.clone(),
-            random_polynomials: random_polynomials.clone(),
-            q_1: q_1.clone(),
+            masking_polynomials,
+            random_polynomials,
+            q_1,
         };
 
-        state.q_1 = Some(q_1);
-        state.masking_polynomials = Some(masking_polynomials);
-        state.random_polynomials = Some(random_polynomials);
         state.masked_oracles = Some(h_primes);
 
         Ok((msg, oracles, state))
@@ -170,7 +195,7 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
         mut state: ProverState<'a, F, VO>,
         _r: &mut R,
     ) -> (ProverMsg<F>, ProverSecondOracles<F>, ProverState<'a, F, VO>) {
-        let random_polynomials = state.random_polynomials.clone()
+        let random_polynomials = state.random_polynomials.as_ref()
             .expect("ProverState should include the random polynomials that were used to create the maskings in round 1");
 
         // q_2 is defined as r1 + c*r2 + c^2r3 + ...
@@ -181,7 +206,14 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
                 acc_poly + (r.polynomial() * c_power)
             });
 
-        let q_2 = LabeledPolynomial::new(String::from("q_2"), q_2, Some(2), None);
+        // q_2 inherits the hiding bound of the r_i, so that schemes which only use the randomness of hiding
+        // polynomials open it against the aggregated commitment
+        let q_2 = LabeledPolynomial::new(
+            labels::Q_2.to_string(),
+            q_2,
+            Some(MASKING_DEGREE_BOUND),
+            Some(1),
+        );
 
         let msg = ProverMsg::EmptyMessage;
 
@@ -194,38 +226,34 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
     }
 }
 
-/// computes array of m_i = ri(alpha_i^-1) * zk(alpha_i^-1)
+/// computes array of m_i = ri(alpha_i^-1) * zk(alpha_i^-1), where zk vanishes over the coset of K
 fn compute_maskings<R: Rng, F: PrimeField, VO: VirtualOracle<F>>(
     virtual_oracle: &VO,
-    domain: &GeneralEvaluationDomain<F>,
+    domain: &CosetDomain<F>,
     alphas: &[F],
     masking_bound: Option<usize>,
     rng: &mut R,
 ) -> (Vec<LabeledPolynomial<F>>, Vec<LabeledPolynomial<F>>) {
     let num_of_concrete_oracles = virtual_oracle.num_of_variable_terms();
-    //r is defined as polynomial degree < 2
-    let degree = 1;
 
     let mut random_polynomials = Vec::with_capacity(num_of_concrete_oracles);
     let mut masking_polynomials = Vec::with_capacity(num_of_concrete_oracles);
 
     for i in 0..num_of_concrete_oracles {
-        let r = DensePolynomial::<F>::rand(degree, rng);
+        // r has degree < MASKING_DEGREE_BOUND, so that the two evaluations of h_i' revealed by the proof are
+        // independent of h_i
+        let Masking { random, masking } = mask_domain(domain, MASKING_DEGREE_BOUND, rng);
         let shifting_factor = alphas[i].inverse().unwrap();
-        let r_shifted = shift_dense_poly(&r, &shifting_factor);
-        let vanishing_shifted =
-            shift_dense_poly(&domain.vanishing_polynomial().into(), &shifting_factor);
 
         random_polynomials.push(LabeledPolynomial::new(
-            format!("r_{}", i),
-            r,
-            Some(2),
+            labels::random(i),
+            random,
+            Some(MASKING_DEGREE_BOUND),
             Some(1),
         ));
-        // random_polynomials.push(LabeledPolynomial::new(format!("r_{}", i), r, None, None));
         masking_polynomials.push(LabeledPolynomial::new(
-            format!("m_{}", i),
-            &r_shifted * &vanishing_shifted,
+            labels::masking(i),
+            shift_dense_poly(&masking, &shifting_factor),
             masking_bound,
             Some(1),
         ));
@@ -251,9 +279,16 @@ fn compute_f_prime<F: PrimeField, VO: VirtualOracle<F>>(
         })
         .collect();
 
-    let terms_for_eval_function = iter::once(&x_poly)
-        .chain(shifted_h_primes.iter())
-        .map(|poly| VOTerm::Polynomial(poly.clone()))
+    // public terms are not masked: the verifier evaluates them as they are
+    let shifted_publics = virtual_oracle
+        .public_terms()
+        .iter()
+        .map(|term| term.shifted_polynomial());
+
+    let terms_for_eval_function = iter::once(x_poly)
+        .chain(shifted_h_primes)
+        .chain(shifted_publics)
+        .map(VOTerm::Polynomial)
         .collect::<Vec<VOTerm<F>>>();
 
     let f_prime = match virtual_oracle.apply_evaluation_function(&terms_for_eval_function) {
@@ -263,3 +298,76 @@ fn compute_f_prime<F: PrimeField, VO: VirtualOracle<F>>(
 
     f_prime
 }
+
+/// Computes f_prime in evaluation form: the shifted terms, public terms included, are evaluated over a coset of a domain of size greater
+/// than `scaling_factor` times their maximum degree, the VO is applied pointwise and f_prime is interpolated back.
+/// This avoids multiplying polynomials in coefficient form.
+fn compute_f_prime_over_coset<F: PrimeField, VO: VirtualOracle<F>>(
+    virtual_oracle: &VO,
+    h_prime_polynomials: &[LabeledPolynomial<F>],
+    scaling_factor: usize,
+) -> Result<DensePolynomial<F>, Error> {
+    let public_terms = virtual_oracle.public_terms();
+    let max_degree = h_prime_polynomials
+        .iter()
+        .map(|h| h.degree())
+        .chain(public_terms.iter().map(|term| term.oracle.degree()))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let domain = GeneralEvaluationDomain::<F>::new(scaling_factor * max_degree + 1).ok_or(
+        Error::UnsupportedDegree(format!(
+            "No evaluation domain for a VO of degree {}",
+            scaling_factor * max_degree
+        )),
+    )?;
+
+    let shifted_evals: Vec<Vec<F>> = h_prime_polynomials
+        .iter()
+        .zip(virtual_oracle.shifting_coefficients().iter())
+        .map(|(h, alpha)| shift_dense_poly(h.polynomial(), alpha))
+        .chain(public_terms.iter().map(|term| term.shifted_polynomial()))
+        .map(|shifted| domain.coset_fft(shifted.coeffs()))
+        .collect();
+
+    let coset_offset = F::multiplicative_generator();
+    let f_prime_evals = domain
+        .elements()
+        .enumerate()
+        .map(|(j, x)| {
+            let terms: Vec<VOTerm<F>> = iter::once(coset_offset * x)
+                .chain(shifted_evals.iter().map(|evals| evals[j]))
+                .map(VOTerm::Evaluation)
+                .collect();
+
+            match virtual_oracle.apply_evaluation_function(&terms) {
+                VOTerm::Evaluation(f_prime_eval) => Ok(f_prime_eval),
+                VOTerm::Polynomial(_) => Err(Error::VOFailedToCompute),
+            }
+        })
+        .collect::<Result<Vec<F>, Error>>()?;
+
+    let f_prime = DensePolynomial::from_coefficients_vec(domain.coset_ifft(&f_prime_evals));
+
+    // f_prime is only the VO if the VO does not exceed `scaling_factor` times the degree of its terms. Otherwise
+    // q_1 would be computed from a wrong f_prime, so it is compared with the VO at a point outside of the coset.
+    let point = coset_offset.square();
+    let terms: Vec<VOTerm<F>> = iter::once(point)
+        .chain(
+            h_prime_polynomials
+                .iter()
+                .zip(virtual_oracle.shifting_coefficients())
+                .map(|(h, alpha)| h.evaluate(&(alpha * point))),
+        )
+        .chain(public_terms.iter().map(|term| term.evaluate(&point)))
+        .map(VOTerm::Evaluation)
+        .collect();
+    match virtual_oracle.apply_evaluation_function(&terms) {
+        VOTerm::Evaluation(eval) if eval == f_prime.evaluate(&point) => Ok(f_prime),
+        VOTerm::Evaluation(_) => Err(Error::DegreeBoundExceeded(format!(
+            "The VO exceeds {} times the degree of its terms",
+            scaling_factor
+        ))),
+        VOTerm::Polynomial(_) => Err(Error::VOFailedToCompute),
+    }
+}
@@ -1,11 +1,12 @@
 use super::PIOPforZeroOverK;
+use crate::coset_domain::CosetDomain;
 use crate::error::Error;
 use crate::virtual_oracle::VirtualOracle;
+use crate::zero_over_k::labels;
 use ark_ff::PrimeField;
-use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_poly_commit::QuerySet;
-use rand::Rng;
-use std::collections::HashMap;
+use fiat_shamir_rng::{Absorb, ChallengeSet};
+use std::collections::BTreeMap;
 
 #[derive(Copy, Clone)]
 pub struct VerifierState<'a, F: PrimeField, VO: VirtualOracle<F>> {
@@ -13,8 +14,8 @@ pub struct VerifierState<'a, F: PrimeField, VO: VirtualOracle<F>> {
 
     maximum_oracle_degree_bound: Option<usize>,
 
-    /// domain K over which a virtual oracle should be equal to 0
-    domain_k: &'a GeneralEvaluationDomain<F>,
+    /// domain K, or a coset of it, over which a virtual oracle should be equal to 0
+    domain_k: &'a CosetDomain<F>,
 
     verifier_first_message: Option<VerifierFirstMsg<F>>,
 
@@ -35,7 +36,7 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
     pub fn verifier_init<'a>(
         virtual_oracle: &'a VO,
         maximum_oracle_degree_bound: Option<usize>,
-        domain_k: &'a GeneralEvaluationDomain<F>,
+        domain_k: &'a CosetDomain<F>,
     ) -> Result<VerifierState<'a, F, VO>, Error> {
         Ok(VerifierState {
             virtual_oracle,
@@ -47,13 +48,22 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
         })
     }
 
-    pub fn verifier_first_round<'a, R: Rng>(
+    /// Derive beta_1, beta_2 and c outside of K, after the prover committed to the random and masking polynomials
+    /// and to q_1
+    pub fn verifier_first_round<'a, FS: Absorb>(
         mut state: VerifierState<'a, F, VO>,
-        rng: &mut R,
+        fs_rng: &mut FS,
     ) -> Result<(VerifierFirstMsg<F>, VerifierState<'a, F, VO>), Error> {
-        let beta_1 = state.domain_k.sample_element_outside_domain(rng);
-        let beta_2 = state.domain_k.sample_element_outside_domain(rng);
-        let c = state.domain_k.sample_element_outside_domain(rng);
+        let domain_k = state.domain_k;
+        let mut round = ChallengeSet::new(fs_rng, b"first_round");
+        let mut outside_k = |label: &[u8]| {
+            round.challenge_with(label, |fs_rng| {
+                domain_k.sample_element_outside_domain(fs_rng)
+            })
+        };
+        let beta_1 = outside_k(b"beta_1");
+        let beta_2 = outside_k(b"beta_2");
+        let c = outside_k(b"c");
 
         let msg = VerifierFirstMsg { c };
 
@@ -107,7 +117,7 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
         // query_set.extend(m_query_set);
 
         let mut query_set = QuerySet::<F>::new();
-        let mut point_evaluations: HashMap<F, String> = HashMap::new();
+        let mut point_evaluations: BTreeMap<F, String> = BTreeMap::new();
 
         point_evaluations.insert(beta_1, String::from("beta_1"));
         point_evaluations.insert(beta_2, String::from("beta_2"));
@@ -124,7 +134,7 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
                 }
             };
 
-            query_set.insert((format!("h_prime_{}", i), (label, *alpha * beta_1)));
+            query_set.insert((labels::h_prime(i), (label, *alpha * beta_1)));
 
             let test_point = *alpha * beta_2;
             let label = match point_evaluations.get(&test_point) {
@@ -137,11 +147,11 @@ impl<F: PrimeField, VO: VirtualOracle<F>> PIOPforZeroOverK<F, VO> {
                 }
             };
 
-            query_set.insert((format!("m_{}", i), (label, *alpha * beta_2)));
+            query_set.insert((labels::masking(i), (label, *alpha * beta_2)));
         }
 
-        query_set.insert((String::from("q_1"), (String::from("beta_1"), beta_1)));
-        query_set.insert((String::from("q_2"), (String::from("beta_2"), beta_2)));
+        query_set.insert((labels::Q_1.to_string(), (String::from("beta_1"), beta_1)));
+        query_set.insert((labels::Q_2.to_string(), (String::from("beta_2"), beta_2)));
 
         /*
          * What do we get with geo seq virtual oracle
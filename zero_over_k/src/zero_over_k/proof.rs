@@ -1,22 +1,136 @@
-use ark_ff::PrimeField;
-use homomorphic_poly_commit::AdditivelyHomomorphicPCS;
+use crate::config::{pc, ProtocolConfig};
+use derivative::Derivative;
+use homomorphic_poly_commit::commit_policy::CommitPolicy;
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Write};
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Proof<F: PrimeField, PC: AdditivelyHomomorphicPCS<F>> {
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = "pc::Proof<C>: PartialEq, pc::BatchProof<C>: PartialEq"),
+    Eq(bound = "pc::Proof<C>: Eq, pc::BatchProof<C>: Eq")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct Proof<C: ProtocolConfig> {
+    /// whether the prover was required to hide every oracle it committed to
+    pub policy: CommitPolicy,
+
     // commitments
-    pub m_commitments: Vec<PC::Commitment>,
-    pub r_commitments: Vec<PC::Commitment>,
-    pub q1_commit: PC::Commitment,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub m_commitments: Vec<pc::Commitment<C>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub r_commitments: Vec<pc::Commitment<C>>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub q1_commit: pc::Commitment<C>,
 
     // evaluations
-    pub q1_eval: F,
-    pub q2_eval: F,
-    pub h_prime_evals: Vec<F>,
-    pub m_evals: Vec<F>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub q1_eval: C::F,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub q2_eval: C::F,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub h_prime_evals: Vec<C::F>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub m_evals: Vec<C::F>,
 
     // opening proof
-    pub opening_proof: PC::BatchProof,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub opening_proof: pc::BatchProof<C>,
+}
+
+/// A proof whose opening was deferred to an accumulator shared with other sub-proofs, see
+/// [`homomorphic_poly_commit::opening_accumulator`]
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
+pub struct DeferredProof<C: ProtocolConfig> {
+    pub policy: CommitPolicy,
+
+    // commitments
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub m_commitments: Vec<pc::Commitment<C>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub r_commitments: Vec<pc::Commitment<C>>,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub q1_commit: pc::Commitment<C>,
+
+    // evaluations
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub q1_eval: C::F,
+    #[cfg_attr(feature = "serde", serde(with = "homomorphic_poly_commit::serde_hex"))]
+    pub q2_eval: C::F,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub h_prime_evals: Vec<C::F>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "homomorphic_poly_commit::serde_hex::vec")
+    )]
+    pub m_evals: Vec<C::F>,
+}
+
+impl<C: ProtocolConfig> Proof<C> {
+    pub fn from_deferred(proof: DeferredProof<C>, opening_proof: pc::BatchProof<C>) -> Self {
+        Self {
+            policy: proof.policy,
+            m_commitments: proof.m_commitments,
+            r_commitments: proof.r_commitments,
+            q1_commit: proof.q1_commit,
+            q1_eval: proof.q1_eval,
+            q2_eval: proof.q2_eval,
+            h_prime_evals: proof.h_prime_evals,
+            m_evals: proof.m_evals,
+            opening_proof,
+        }
+    }
+
+    /// Split the proof into its deferred part and its opening proof
+    pub fn into_deferred(self) -> (DeferredProof<C>, pc::BatchProof<C>) {
+        let deferred = DeferredProof {
+            policy: self.policy,
+            m_commitments: self.m_commitments,
+            r_commitments: self.r_commitments,
+            q1_commit: self.q1_commit,
+            q1_eval: self.q1_eval,
+            q2_eval: self.q2_eval,
+            h_prime_evals: self.h_prime_evals,
+            m_evals: self.m_evals,
+        };
+        (deferred, self.opening_proof)
+    }
 }
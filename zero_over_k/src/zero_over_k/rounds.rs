@@ -0,0 +1,140 @@
+//! ZeroOverK over concrete oracles committed to in several rounds, such as the oracles of an index, fixed once and
+//! for all, and the oracles of a witness committed to later. The terms of the virtual oracle index the oracles of
+//! all rounds, in order, so that a single check can relate oracles of different rounds.
+//!
+//! ```ignore
+//! let rounds = [
+//!     OracleRound::<C> { oracles: &[&row, &col], commitments: &index_commitments, rands: &index_rands },
+//!     OracleRound::<C> { oracles: &[&w], commitments: &witness_commitments, rands: &witness_rands },
+//! ];
+//! // terms 0 and 1 are row and col, term 2 is w
+//! let proof = ZeroOverK::<C>::prove_over_rounds(&rounds, degree_bound, &vo, &domain, &ck, policy, &context, rng)?;
+//! ```
+
+use crate::config::{pc, ProtocolConfig};
+use crate::{
+    coset_domain::CosetDomain,
+    error::Error,
+    virtual_oracle::VirtualOracle,
+    zero_over_k::{proof::Proof, ZeroOverK},
+};
+use ark_ff::to_bytes;
+use ark_poly::{univariate::DensePolynomial, GeneralEvaluationDomain};
+use ark_poly_commit::{LabeledCommitment, LabeledPolynomial};
+use fiat_shamir_rng::{
+    transcript::{MESSAGE_LABEL, STATEMENT_LABEL},
+    Context, FiatShamirTranscript, Transcript,
+};
+#[cfg(feature = "prover")]
+use {homomorphic_poly_commit::commit_policy::CommitPolicy, rand::Rng};
+
+/// The concrete oracles committed to in one round, with their commitments and commitment randomness
+pub struct OracleRound<'a, C: ProtocolConfig> {
+    pub oracles: &'a [&'a LabeledPolynomial<C::F, DensePolynomial<C::F>>],
+    pub commitments: &'a [LabeledCommitment<pc::Commitment<C>>],
+    pub rands: &'a [pc::Randomness<C>],
+}
+
+impl<C: ProtocolConfig> ZeroOverK<C> {
+    /// Label of the transcript the commitments of the rounds are appended to
+    pub const ROUNDS_LABEL: &'static [u8] = b"Zero Over K over rounds";
+
+    #[cfg(feature = "prover")]
+    /// Prove that the virtual oracle vanishes over K, for concrete oracles committed to over several rounds. The
+    /// commitments of each round are appended to the transcript in the order of the rounds, before the statement of
+    /// the proof, and the oracles of all rounds are opened in a single batch. Labels must be distinct across rounds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_over_rounds<R: Rng, VO: VirtualOracle<C::F>>(
+        rounds: &[OracleRound<C>],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &GeneralEvaluationDomain<C::F>,
+        ck: &pc::CommitterKey<C>,
+        policy: CommitPolicy,
+        context: &Context,
+        rng: &mut R,
+    ) -> Result<Proof<C>, Error> {
+        let mut oracles = Vec::new();
+        let mut commitments = Vec::new();
+        let mut rands = Vec::new();
+        for (i, round) in rounds.iter().enumerate() {
+            if round.rands.len() != round.commitments.len() {
+                return Err(Error::InputLengthError(format!(
+                    "Round {} has {} commitments but {} randomness",
+                    i,
+                    round.commitments.len(),
+                    round.rands.len()
+                )));
+            }
+            oracles.extend_from_slice(round.oracles);
+            commitments.extend_from_slice(round.commitments);
+            rands.extend_from_slice(round.rands);
+        }
+        // the oracles of all rounds are opened together, under their labels
+        Self::check_oracle_labels(&oracles, &commitments)?;
+
+        let mut transcript =
+            Self::rounds_transcript(rounds.iter().map(|round| round.commitments), context)?;
+        Self::prove_in(
+            &oracles,
+            &commitments,
+            &rands,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            &CosetDomain::subgroup(*domain),
+            ck,
+            policy,
+            context,
+            Some(&mut transcript),
+            None,
+            rng,
+        )
+    }
+
+    /// Verify a proof of [`Self::prove_over_rounds`], given the commitments of each round
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_over_rounds<VO: VirtualOracle<C::F>>(
+        proof: Proof<C>,
+        rounds: &[&[LabeledCommitment<pc::Commitment<C>>]],
+        maximum_oracle_degree_bound: Option<usize>,
+        virtual_oracle: &VO,
+        domain: &GeneralEvaluationDomain<C::F>,
+        vk: &pc::VerifierKey<C>,
+        context: &Context,
+    ) -> Result<(), Error> {
+        let commitments = rounds.concat();
+        Self::check_commitment_labels(&commitments)?;
+
+        let mut transcript = Self::rounds_transcript(rounds.iter().copied(), context)?;
+        Self::verify_in(
+            proof,
+            &commitments,
+            maximum_oracle_degree_bound,
+            virtual_oracle,
+            &CosetDomain::subgroup(*domain),
+            vk,
+            context,
+            Some(&mut transcript),
+        )
+    }
+
+    /// A transcript with the commitments of each round appended in order, as separate messages
+    fn rounds_transcript<'a>(
+        rounds: impl Iterator<Item = &'a [LabeledCommitment<pc::Commitment<C>>]>,
+        context: &Context,
+    ) -> Result<FiatShamirTranscript<C::FS>, Error>
+    where
+        pc::Commitment<C>: 'a,
+    {
+        let mut transcript = FiatShamirTranscript::<C::FS>::new(Self::ROUNDS_LABEL);
+        transcript.append_message(
+            STATEMENT_LABEL,
+            &to_bytes![context].map_err(|_| Error::ToBytesError)?,
+        );
+        for (i, commitments) in rounds.enumerate() {
+            let message = to_bytes![i as u64, commitments].map_err(|_| Error::ToBytesError)?;
+            transcript.append_message(MESSAGE_LABEL, &message);
+        }
+        Ok(transcript)
+    }
+}
@@ -1,27 +1,48 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test {
     use crate::{
+        config::{Config, ProtocolConfig},
+        coset_domain::CosetDomain,
         error::{to_pc_error, Error},
-        virtual_oracle::generic_shifting_vo::{presets, GenericShiftingVO},
-        zero_over_k::ZeroOverK,
+        virtual_oracle::{
+            generic_shifting_vo::{presets, vo_term::VOTerm, GenericShiftingVO},
+            public_oracle::{PublicOracle, PublicTerm},
+            VirtualOracle,
+        },
+        vo_constant,
+        zero_over_k::{proof::DeferredProof, rounds::OracleRound, ZeroOverK},
     };
-    use ark_bn254::{Bn254, Fr};
-    use ark_ff::Field;
-    use ark_ff::One;
+    use ark_bn254::{Bn254, Fr, G1Affine};
+    use ark_ff::{FftField, Field, PrimeField};
+    use ark_ff::{One, UniformRand, Zero};
     use ark_poly::{
         univariate::DensePolynomial, EvaluationDomain, Evaluations, GeneralEvaluationDomain,
-        UVPolynomial,
+        Polynomial, UVPolynomial,
     };
-    use ark_poly_commit::{LabeledCommitment, LabeledPolynomial, PolynomialCommitment};
+    use ark_poly_commit::{
+        LabeledCommitment, LabeledPolynomial, PCCommitterKey, PolynomialCommitment,
+        PolynomialLabel, QuerySet,
+    };
+    use ark_serialize::CanonicalSerialize;
     use ark_std::{rand::thread_rng, test_rng};
     use blake2::Blake2s;
-    use fiat_shamir_rng::SimpleHashFiatShamirRng;
-    use homomorphic_poly_commit::marlin_kzg::KZG10;
+    use fiat_shamir_rng::{
+        Context, FiatShamirTranscript, RewindableTranscript, SimpleHashFiatShamirRng, Transcript,
+        TranscriptError,
+    };
+    use homomorphic_poly_commit::{
+        commit_policy::CommitPolicy,
+        marlin_kzg::KZG10,
+        opening_accumulator::{ProverAccumulator, VerifierAccumulator},
+        opening_hint,
+        pedersen::PedersenPC,
+    };
     use rand_chacha::ChaChaRng;
     type FS = SimpleHashFiatShamirRng<Blake2s, ChaChaRng>;
 
     type F = Fr;
     type PC = KZG10<Bn254>;
+    type C = Config<F, PC, FS>;
 
     #[test]
     fn test_zero_over_k_inverse_check_oracle() {
@@ -74,8 +95,8 @@ mod test {
         );
 
         // Step 5: commit to the concrete oracles
-        let concrete_oracles = [f, g];
-        let (commitments, rands) = PC::commit(&ck, &concrete_oracles, Some(rng))
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng))
             .map_err(to_pc_error::<F, PC>)
             .unwrap();
 
@@ -85,7 +106,363 @@ mod test {
             GenericShiftingVO::new(&vec![0, 1], &alphas, presets::inverse_check).unwrap();
 
         // Step 7: prove
-        let zero_over_k_proof = ZeroOverK::<F, PC, FS>::prove(
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        );
+
+        // Step 8: verify
+        let is_valid = ZeroOverK::<C>::verify(
+            zero_over_k_proof.unwrap(),
+            &commitments,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        );
+
+        assert!(is_valid.is_ok());
+    }
+
+    #[test]
+    fn test_zero_over_k_with_many_oracles() {
+        // with more than 10 oracles, the labels of the evaluations in the proof no longer sort in the order of
+        // the terms (h_prime_10 < h_prime_2)
+        let m = 8;
+        let n = 12;
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        // the last oracle is a linear combination of the others with distinct coefficients
+        let mut polys = (0..n - 1)
+            .map(|_| DensePolynomial::<F>::rand(7, rng))
+            .collect::<Vec<_>>();
+        let last = polys
+            .iter()
+            .enumerate()
+            .fold(DensePolynomial::zero(), |acc, (i, poly)| {
+                &acc + &(poly * F::from(i as u64 + 1))
+            });
+        polys.push(last);
+
+        let oracles = polys
+            .into_iter()
+            .enumerate()
+            .map(|(i, poly)| {
+                LabeledPolynomial::new(
+                    format!("f_{}", i),
+                    poly,
+                    Some(enforced_degree_bound),
+                    Some(1),
+                )
+            })
+            .collect::<Vec<_>>();
+        let oracles = oracles.iter().collect::<Vec<_>>();
+        let (commitments, rands) = PC::commit(&ck, oracles.iter().copied(), Some(rng)).unwrap();
+
+        let combination_check = |terms: &[VOTerm<F>]| {
+            let mut check = terms[n].clone();
+            for i in 0..n - 1 {
+                check = check - vo_constant!(F::from(i as u64 + 1)) * terms[i + 1].clone();
+            }
+            check
+        };
+        let vo = GenericShiftingVO::new(
+            &(0..n).collect::<Vec<_>>(),
+            &vec![F::one(); n],
+            combination_check,
+        )
+        .unwrap();
+
+        let proof = ZeroOverK::<C>::prove(
+            &oracles,
+            &commitments,
+            &rands,
+            Some(enforced_degree_bound),
+            &vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ZeroOverK::<C>::verify(
+                proof,
+                &commitments,
+                Some(enforced_degree_bound),
+                &vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_zero_over_k_wrong_context() {
+        let m = 8;
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::rand(7, rng),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let g_evals = f
+            .evaluate_over_domain_by_ref(domain_k)
+            .evals
+            .iter()
+            .map(|&x| x.inverse().unwrap())
+            .collect::<Vec<_>>();
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            Evaluations::from_vec_and_domain(g_evals, domain_k).interpolate(),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng)).unwrap();
+
+        let alphas = [F::one(), F::one()];
+        let inverse_check_oracle =
+            GenericShiftingVO::new(&[0, 1], &alphas, presets::inverse_check).unwrap();
+
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &ck,
+            &Context::new(b"instance 1"),
+            rng,
+        )
+        .unwrap();
+
+        // a proof produced for one instance must not verify for another
+        let res = ZeroOverK::<C>::verify(
+            zero_over_k_proof,
+            &commitments,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &vk,
+            &Context::new(b"instance 2"),
+        );
+
+        assert!(res.is_err());
+    }
+
+    /// The configuration of the tests, named by a type of its own
+    struct Bn254Kzg;
+
+    impl ProtocolConfig for Bn254Kzg {
+        type F = F;
+        type PC = PC;
+        type FS = FS;
+    }
+
+    #[test]
+    fn test_zero_over_k_with_config() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::rand(7, rng),
+            None,
+            Some(1),
+        );
+        let g = LabeledPolynomial::new(String::from("g"), f.polynomial().clone(), None, Some(1));
+
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng)).unwrap();
+
+        let alphas = [F::one(), F::one()];
+        let equality_vo =
+            GenericShiftingVO::new(&[0, 1], &alphas, presets::equality_check).unwrap();
+
+        let proof = ZeroOverK::<Bn254Kzg>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            None,
+            &equality_vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        let res = ZeroOverK::<Bn254Kzg>::verify(
+            proof,
+            &commitments,
+            None,
+            &equality_vo,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_zero_over_k_with_pedersen() {
+        type PC = PedersenPC<G1Affine, Blake2s>;
+        type C = Config<F, PC, FS>;
+
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+
+        let max_degree = 31;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, None).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::rand(7, rng),
+            None,
+            Some(1),
+        );
+        let g = LabeledPolynomial::new(String::from("g"), f.polynomial().clone(), None, Some(1));
+
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng)).unwrap();
+
+        let alphas = [F::one(), F::one()];
+        let equality_vo =
+            GenericShiftingVO::new(&[0, 1], &alphas, presets::equality_check).unwrap();
+
+        let proof = ZeroOverK::<C>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            None,
+            &equality_vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+        assert_eq!(
+            ZeroOverK::<C>::proof_size(
+                None,
+                &equality_vo,
+                CommitPolicy::NonHiding,
+                ck.supported_degree()
+            ),
+            proof.serialized_size()
+        );
+
+        let res = ZeroOverK::<C>::verify(
+            proof,
+            &commitments,
+            None,
+            &equality_vo,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_zero_over_k_in_evaluation_form() {
+        let m = 8;
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let max_hiding = 1;
+
+        let enforced_hiding_bound = Some(1);
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(
+            &pp,
+            max_degree,
+            max_hiding,
+            Some(&[2, enforced_degree_bound]),
+        )
+        .unwrap();
+
+        // Step 1: choose a random polynomial
+        let f_unlabeled: DensePolynomial<F> = DensePolynomial::rand(7, rng);
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            f_unlabeled,
+            Some(enforced_degree_bound),
+            enforced_hiding_bound,
+        );
+
+        // Step 2: evaluate it
+        let f_evals = f.evaluate_over_domain_by_ref(domain_k);
+
+        // Step 3: find the inverse at each of these points
+        let desired_g_evals = f_evals
+            .evals
+            .iter()
+            .map(|&x| x.inverse().unwrap())
+            .collect::<Vec<_>>();
+        let desired_g_evals = Evaluations::from_vec_and_domain(desired_g_evals, domain_k);
+
+        // Step 4: interpolate a polynomial from the inverses
+        let g = desired_g_evals.clone().interpolate();
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            g.clone(),
+            Some(enforced_degree_bound),
+            enforced_hiding_bound,
+        );
+
+        // Step 5: commit to the concrete oracles
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng))
+            .map_err(to_pc_error::<F, PC>)
+            .unwrap();
+
+        // Step 6: Derive the desired virtual oracle, the prover will compute the quotient over a coset
+        let alphas = vec![F::one(), F::one()];
+        let inverse_check_oracle = GenericShiftingVO::new(&[0, 1], &alphas, presets::inverse_check)
+            .unwrap()
+            .with_scaling_factor(2);
+
+        // Step 7: prove
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
             &concrete_oracles,
             &commitments,
             &rands,
@@ -93,17 +470,19 @@ mod test {
             &inverse_check_oracle,
             &domain_k,
             &ck,
+            &Context::new(b"test"),
             rng,
         );
 
         // Step 8: verify
-        let is_valid = ZeroOverK::<F, PC, FS>::verify(
+        let is_valid = ZeroOverK::<C>::verify(
             zero_over_k_proof.unwrap(),
             &commitments,
             Some(enforced_degree_bound),
             &inverse_check_oracle,
             &domain_k,
             &vk,
+            &Context::new(b"test"),
         );
 
         assert!(is_valid.is_ok());
@@ -162,16 +541,16 @@ mod test {
             enforced_hiding_bound,
         );
 
-        let concrete_oracles = [f, g];
+        let concrete_oracles = [&f, &g];
         let alphas = vec![F::one(), F::one()];
-        let (commitments, rands) = PC::commit(&ck, &concrete_oracles, Some(rng))
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng))
             .map_err(to_pc_error::<F, PC>)
             .unwrap();
 
         let zero_over_k_vo =
             GenericShiftingVO::new(&[0, 1], &alphas, presets::equality_check).unwrap();
 
-        let zero_over_k_proof = ZeroOverK::<F, PC, FS>::prove(
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
             &concrete_oracles,
             &commitments,
             &rands,
@@ -179,17 +558,19 @@ mod test {
             &zero_over_k_vo,
             &domain_k,
             &ck,
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
 
-        let res = ZeroOverK::<F, PC, FS>::verify(
+        let res = ZeroOverK::<C>::verify(
             zero_over_k_proof,
             &commitments,
             Some(enforced_degree_bound),
             &zero_over_k_vo,
             &domain_k,
             &vk,
+            &Context::new(b"test"),
         );
 
         assert!(res.is_err());
@@ -250,8 +631,8 @@ mod test {
         );
 
         // Step 5: commit to the concrete oracles
-        let concrete_oracles = [f, g];
-        let (commitments, rands) = PC::commit(&ck, &concrete_oracles, Some(rng))
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng))
             .map_err(to_pc_error::<F, PC>)
             .unwrap();
 
@@ -261,7 +642,7 @@ mod test {
             GenericShiftingVO::new(&vec![0, 1], &alphas, presets::inverse_check).unwrap();
 
         // Step 7: prove
-        let zero_over_k_proof = ZeroOverK::<F, PC, FS>::prove(
+        let zero_over_k_proof = ZeroOverK::<C>::prove(
             &concrete_oracles,
             &commitments,
             &rands,
@@ -269,6 +650,7 @@ mod test {
             &inverse_check_oracle,
             &domain_k,
             &ck,
+            &Context::new(b"test"),
             rng,
         )
         .unwrap();
@@ -282,17 +664,1383 @@ mod test {
                 LabeledCommitment::new(label, comm, Some(verifier_degree_bound))
             })
             .collect();
-        let res = ZeroOverK::<F, PC, FS>::verify(
+        let res = ZeroOverK::<C>::verify(
             zero_over_k_proof,
             &verifier_commitments,
             Some(verifier_degree_bound),
             &inverse_check_oracle,
             &domain_k,
             &vk,
+            &Context::new(b"test"),
         );
 
         assert!(res.is_err());
 
         assert_eq!(res.unwrap_err(), Error::BatchCheckError);
     }
+
+    /// Prove the inverse check of f and g under `policy`, committing to both oracles with `hiding_bound`, and verify
+    /// the proof after replacing its recorded policy with `recorded_policy`
+    fn run_with_policy(
+        hiding_bound: Option<usize>,
+        policy: CommitPolicy,
+        recorded_policy: CommitPolicy,
+    ) -> Result<(), Error> {
+        let m = 8;
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::rand(7, rng),
+            Some(enforced_degree_bound),
+            hiding_bound,
+        );
+        let g_evals = f
+            .evaluate_over_domain_by_ref(domain_k)
+            .evals
+            .iter()
+            .map(|&x| x.inverse().unwrap())
+            .collect::<Vec<_>>();
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            Evaluations::from_vec_and_domain(g_evals, domain_k).interpolate(),
+            Some(enforced_degree_bound),
+            hiding_bound,
+        );
+
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng)).unwrap();
+
+        let alphas = [F::one(), F::one()];
+        let inverse_check_oracle =
+            GenericShiftingVO::new(&[0, 1], &alphas, presets::inverse_check).unwrap();
+
+        let mut proof = ZeroOverK::<C>::prove_with_policy(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &ck,
+            policy,
+            &Context::new(b"test"),
+            rng,
+        )?;
+        assert_eq!(proof.policy, policy);
+        proof.policy = recorded_policy;
+
+        ZeroOverK::<C>::verify(
+            proof,
+            &commitments,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        )
+    }
+
+    #[test]
+    fn test_hiding_policy() {
+        assert_eq!(
+            run_with_policy(Some(1), CommitPolicy::Hiding, CommitPolicy::Hiding),
+            Ok(())
+        );
+        assert_eq!(
+            run_with_policy(None, CommitPolicy::NonHiding, CommitPolicy::NonHiding),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_hiding_policy_rejects_empty_randomness() {
+        assert!(matches!(
+            run_with_policy(None, CommitPolicy::Hiding, CommitPolicy::Hiding),
+            Err(Error::EmptyRandomness(_))
+        ));
+    }
+
+    #[test]
+    fn test_recorded_policy_is_bound_to_the_proof() {
+        assert!(run_with_policy(Some(1), CommitPolicy::Hiding, CommitPolicy::NonHiding).is_err());
+        assert!(run_with_policy(Some(1), CommitPolicy::NonHiding, CommitPolicy::Hiding).is_err());
+    }
+
+    #[test]
+    fn test_public_oracles() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        // f agrees with a public sequence over K, without being its interpolation
+        let sequence = (1..=6u64).map(F::from).collect::<Vec<_>>();
+        let s = PublicOracle::interpolate(&sequence, &domain_k);
+        let v_k = PublicOracle::vanishing(&domain_k);
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            s.to_dense() + v_k.to_dense(),
+            Some(degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, &[f.clone()], Some(rng)).unwrap();
+
+        // f(X) - s(X) - v_K(X) over K, where s and v_K are evaluated by the verifier
+        let public_check = |sequence: &[F]| {
+            GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| {
+                terms[1].clone() - terms[2].clone() - terms[3].clone()
+            })
+            .unwrap()
+            .with_public_oracle(PublicOracle::interpolate(sequence, &domain_k), F::one())
+            .with_public_oracle(v_k.clone(), F::one())
+        };
+        let prove = |vo: &_, rng: &mut _| {
+            ZeroOverK::<C>::prove(
+                &[&f],
+                &commitments,
+                &rands,
+                Some(degree_bound),
+                vo,
+                &domain_k,
+                &ck,
+                &Context::new(b"test"),
+                rng,
+            )
+            .unwrap()
+        };
+        let verify = |proof, vo: &_| {
+            ZeroOverK::<C>::verify(
+                proof,
+                &commitments,
+                Some(degree_bound),
+                vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+            )
+        };
+
+        let vo = public_check(&sequence);
+        let proof = prove(&vo, rng);
+
+        // only the committed term is masked and opened
+        assert_eq!(proof.m_commitments.len(), 1);
+        assert_eq!(proof.h_prime_evals.len(), 1);
+        assert!(verify(proof, &vo).is_ok());
+
+        // a proof says nothing about another public sequence
+        let mut other_sequence = sequence.clone();
+        other_sequence[2] += F::one();
+        assert!(verify(prove(&vo, rng), &public_check(&other_sequence)).is_err());
+
+        // the prover may evaluate public terms over a coset as well
+        let vo = public_check(&sequence).with_scaling_factor(1);
+        assert!(verify(prove(&vo, rng), &vo).is_ok());
+    }
+
+    #[test]
+    fn test_interpolation_from_values() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let sequence = [F::zero(), F::from(3u64), F::zero(), F::from(7u64)];
+
+        // only the non-zero values are kept
+        let s = PublicOracle::interpolate(&sequence, &domain_k);
+        assert!(matches!(&s, PublicOracle::Lagrange { values, .. } if values.len() == 2));
+
+        let interpolation = DensePolynomial::from_coefficients_vec(domain_k.ifft(&sequence));
+        assert_eq!(s.to_dense(), interpolation);
+        let points = domain_k
+            .elements()
+            .chain((0..8).map(|_| F::rand(rng)))
+            .collect::<Vec<_>>();
+        for point in points {
+            assert_eq!(s.evaluate(&point), interpolation.evaluate(&point));
+        }
+    }
+
+    /// Delegates to a virtual oracle, refusing to combine terms in coefficient form
+    struct EvaluationsOnly<VO>(VO);
+
+    impl<VO: VirtualOracle<F>> VirtualOracle<F> for EvaluationsOnly<VO> {
+        fn mapping_vector(&self) -> Vec<usize> {
+            self.0.mapping_vector()
+        }
+
+        fn shifting_coefficients(&self) -> Vec<F> {
+            self.0.shifting_coefficients()
+        }
+
+        fn apply_evaluation_function(&self, terms: &[VOTerm<F>]) -> VOTerm<F> {
+            assert!(
+                terms
+                    .iter()
+                    .all(|term| matches!(term, VOTerm::Evaluation(_))),
+                "the VO was instantiated in coefficient form"
+            );
+            self.0.apply_evaluation_function(terms)
+        }
+
+        fn num_of_variable_terms(&self) -> usize {
+            self.0.num_of_variable_terms()
+        }
+
+        fn public_terms(&self) -> &[PublicTerm<F>] {
+            self.0.public_terms()
+        }
+
+        fn scaling_factor(&self) -> Option<usize> {
+            self.0.scaling_factor()
+        }
+
+        fn generate_query_set(
+            &self,
+            concrete_oracle_labels: &[PolynomialLabel],
+            query_point: &(String, F),
+        ) -> Result<QuerySet<F>, Error> {
+            self.0
+                .generate_query_set(concrete_oracle_labels, query_point)
+        }
+
+        fn evaluate_from_concrete_evals(
+            &self,
+            concrete_oracle_labels: &[PolynomialLabel],
+            eval_point: &F,
+            evaluations: &ark_poly_commit::Evaluations<F, F>,
+        ) -> Result<F, Error> {
+            self.0
+                .evaluate_from_concrete_evals(concrete_oracle_labels, eval_point, evaluations)
+        }
+    }
+
+    #[test]
+    fn test_verifier_is_succinct() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        // f agrees over K with a public sequence, which the verifier evaluates from its non-zero values
+        let mut sequence = vec![F::zero(); 8];
+        sequence[1] = F::from(5u64);
+        sequence[6] = F::from(9u64);
+        let s = PublicOracle::interpolate(&sequence, &domain_k);
+        let v_k = PublicOracle::vanishing(&domain_k);
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            s.to_dense() + v_k.to_dense(),
+            Some(degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| {
+            terms[1].clone() - terms[2].clone() - terms[3].clone()
+        })
+        .unwrap()
+        .with_public_oracle(s, F::one())
+        .with_public_oracle(v_k, F::one());
+        let proof = ZeroOverK::<C>::prove(
+            &[&f],
+            &commitments,
+            &rands,
+            Some(degree_bound),
+            &vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        // neither the VO nor its public terms are ever in coefficient form on the verifier side
+        assert!(vo
+            .public_terms()
+            .iter()
+            .all(|term| !matches!(term.oracle, PublicOracle::Dense(_))));
+        let res = ZeroOverK::<C>::verify(
+            proof,
+            &commitments,
+            Some(degree_bound),
+            &EvaluationsOnly(vo),
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_reject_understated_scaling_factor() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::from_coefficients_vec(domain_k.ifft(&[F::zero(), F::one()])),
+            Some(degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+
+        // f^2 is of degree twice that of f, the prover would compute a wrong quotient with a scaling factor of 1
+        let square = |scaling_factor| {
+            GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| {
+                terms[1].clone() * terms[1].clone()
+            })
+            .unwrap()
+            .with_scaling_factor(scaling_factor)
+        };
+        let prove = |scaling_factor, rng: &mut _| {
+            ZeroOverK::<C>::prove(
+                &[&f],
+                &commitments,
+                &rands,
+                Some(degree_bound),
+                &square(scaling_factor),
+                &domain_k,
+                &ck,
+                &Context::new(b"test"),
+                rng,
+            )
+        };
+        assert!(matches!(prove(1, rng), Err(Error::DegreeBoundExceeded(_))));
+
+        // an oracle above its own degree bound is rejected as well
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            DensePolynomial::rand(degree_bound + 1, rng),
+            Some(degree_bound),
+            Some(1),
+        );
+        // the committer key refuses g under its bound, so commit to it without one
+        let unbounded_g = LabeledPolynomial::new(
+            g.label().clone(),
+            g.polynomial().clone(),
+            None,
+            g.hiding_bound(),
+        );
+        let (g_commitments, g_rands) = PC::commit(&ck, [&unbounded_g], Some(rng)).unwrap();
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], presets::equality_check).unwrap();
+        assert!(matches!(
+            ZeroOverK::<C>::prove(
+                &[&g],
+                &g_commitments,
+                &g_rands,
+                Some(degree_bound),
+                &vo,
+                &domain_k,
+                &ck,
+                &Context::new(b"test"),
+                rng,
+            ),
+            Err(Error::DegreeBoundExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_over_coset() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let coset = CosetDomain::new(domain_k, F::multiplicative_generator()).unwrap();
+        let (even, odd) = coset.split().unwrap();
+        let degree_bound = 14;
+
+        // the halves hold the elements of even and odd index, and split the vanishing polynomial
+        for i in 0..4 {
+            assert_eq!(even.element(i), coset.element(2 * i));
+            assert_eq!(odd.element(i), coset.element(2 * i + 1));
+        }
+        let dense = |domain: &CosetDomain<F>| -> DensePolynomial<F> {
+            domain.vanishing_polynomial().into()
+        };
+        assert_eq!(&dense(&even) * &dense(&odd), dense(&coset));
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        // f vanishes over the even half only
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            &dense(&even) * &DensePolynomial::from_coefficients_slice(&[F::one(), F::one()]),
+            Some(degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| terms[1].clone())
+            .unwrap()
+            .with_scaling_factor(1);
+
+        let run = |domain: &CosetDomain<F>, rng: &mut _| {
+            let proof = ZeroOverK::<C>::prove_over_coset(
+                &[&f],
+                &commitments,
+                &rands,
+                Some(degree_bound),
+                &vo,
+                domain,
+                &ck,
+                CommitPolicy::NonHiding,
+                &Context::new(b"test"),
+                rng,
+            )
+            .unwrap();
+            ZeroOverK::<C>::verify_over_coset(
+                proof,
+                &commitments,
+                Some(degree_bound),
+                &vo,
+                domain,
+                &vk,
+                &Context::new(b"test"),
+            )
+        };
+
+        assert_eq!(run(&even, rng), Ok(()));
+        assert_eq!(run(&odd, rng), Err(Error::Check2Failed));
+        assert_eq!(run(&coset, rng), Err(Error::Check2Failed));
+        assert_eq!(run(&domain_k.into(), rng), Err(Error::Check2Failed));
+    }
+
+    #[cfg(feature = "sponge")]
+    #[test]
+    fn test_zero_over_k_with_sponge() {
+        use ark_std::rand::SeedableRng;
+        use fiat_shamir_rng::{
+            ark_sponge::poseidon::{PoseidonParameters, PoseidonSponge},
+            SpongeConfig, SpongeFiatShamirRng,
+        };
+
+        /// Poseidon over the scalar field with constants drawn from a fixed seed, which is fine for a test but not
+        /// for production parameters
+        struct TestPoseidon;
+
+        impl SpongeConfig for TestPoseidon {
+            type Sponge = PoseidonSponge<F>;
+
+            fn parameters() -> PoseidonParameters<F> {
+                let rng = &mut ChaChaRng::seed_from_u64(0);
+                let mut matrix = |rows: usize| {
+                    (0..rows)
+                        .map(|_| (0..3).map(|_| F::rand(rng)).collect())
+                        .collect()
+                };
+                let mds = matrix(3);
+                PoseidonParameters::new(8, 31, 17, mds, matrix(39))
+            }
+        }
+
+        type SpongeFS = SpongeFiatShamirRng<TestPoseidon>;
+        type C = Config<F, PC, SpongeFS>;
+
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+
+        let max_degree = 20;
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::rand(7, rng),
+            None,
+            Some(1),
+        );
+        let g = LabeledPolynomial::new(String::from("g"), f.polynomial().clone(), None, Some(1));
+
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng)).unwrap();
+
+        let alphas = [F::one(), F::one()];
+        let equality_vo =
+            GenericShiftingVO::new(&[0, 1], &alphas, presets::equality_check).unwrap();
+
+        let proof = ZeroOverK::<C>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            None,
+            &equality_vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        let verify = |proof| {
+            ZeroOverK::<C>::verify(
+                proof,
+                &commitments,
+                None,
+                &equality_vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+            )
+        };
+        assert!(verify(proof.clone()).is_ok());
+
+        // the challenges come from the sponge, not from the digest
+        let res = ZeroOverK::<C>::verify(
+            proof,
+            &commitments,
+            None,
+            &equality_vo,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        );
+        assert!(res.is_err());
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_trace_spans() {
+        use crate::trace::tracing::{
+            span::{Attributes, Id, Record},
+            subscriber::with_default,
+            Event, Metadata, Subscriber,
+        };
+        use std::sync::{Arc, Mutex};
+
+        /// Records the name of every span in the order they are created
+        #[derive(Clone, Default)]
+        struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+        impl Subscriber for SpanNames {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut names = self.0.lock().unwrap();
+                names.push(span.metadata().name());
+                Id::from_u64(names.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let rng = &mut test_rng();
+        let domain_k: CosetDomain<F> = GeneralEvaluationDomain::<F>::new(8).unwrap().into();
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2])).unwrap();
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            domain_k.vanishing_polynomial().into(),
+            None,
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| terms[1].clone())
+            .unwrap()
+            .with_scaling_factor(1);
+
+        let span_names = SpanNames::default();
+        with_default(span_names.clone(), || {
+            let proof = ZeroOverK::<C>::prove_over_coset(
+                &[&f],
+                &commitments,
+                &rands,
+                None,
+                &vo,
+                &domain_k,
+                &ck,
+                CommitPolicy::NonHiding,
+                &Context::new(b"test"),
+                rng,
+            )
+            .unwrap();
+            ZeroOverK::<C>::verify_over_coset(
+                proof,
+                &commitments,
+                None,
+                &vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+            )
+            .unwrap();
+        });
+
+        assert_eq!(
+            *span_names.0.lock().unwrap(),
+            [
+                "zero_over_k::prove",
+                "commit",
+                "challenge",
+                "evaluate",
+                "open",
+                "zero_over_k::verify",
+                "challenge",
+                "open"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cancellation() {
+        use crate::cancellation::CancellationToken;
+        use std::sync::{Arc, Mutex};
+
+        // the oracles cannot be sent to another thread, so the prover sets them up wherever it runs
+        let prove = |cancellation: Option<&CancellationToken>| {
+            let rng = &mut test_rng();
+            let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+            let pp = PC::setup(20, None, rng).unwrap();
+            let (ck, _) = PC::trim(&pp, 20, 1, Some(&[2])).unwrap();
+            let f = LabeledPolynomial::new(
+                String::from("f"),
+                domain_k.vanishing_polynomial().into(),
+                None,
+                Some(1),
+            );
+            let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+            let vo =
+                GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| terms[1].clone())
+                    .unwrap()
+                    .with_scaling_factor(1);
+
+            ZeroOverK::<C>::prove_with_cancellation(
+                &[&f],
+                &commitments,
+                &rands,
+                None,
+                &vo,
+                &domain_k,
+                &ck,
+                &Context::new(b"test"),
+                cancellation,
+                rng,
+            )
+        };
+
+        // the token fires once the prover has committed and drawn its challenge
+        let stages = Arc::new(Mutex::new(Vec::new()));
+        let token = CancellationToken::new();
+        let token = token.clone().with_progress({
+            let stages = stages.clone();
+            move |stage| {
+                stages.lock().unwrap().push(String::from(stage));
+                if stage == "zero_over_k::evaluate" {
+                    token.cancel();
+                }
+            }
+        });
+
+        // the token is polled wherever the prover runs, here on another thread than the one holding it
+        let result =
+            std::thread::scope(|scope| scope.spawn(|| prove(Some(&token))).join().unwrap());
+        assert_eq!(
+            result,
+            Err(Error::Aborted(String::from("zero_over_k::evaluate")))
+        );
+        assert_eq!(
+            *stages.lock().unwrap(),
+            [
+                "zero_over_k::commit",
+                "zero_over_k::challenge",
+                "zero_over_k::evaluate"
+            ]
+        );
+        assert!(token.is_cancelled());
+
+        // a prover that is not given the cancelled token runs to the end
+        assert!(prove(None).is_ok());
+    }
+
+    #[test]
+    fn test_zero_over_k_with_transcript() {
+        let rng = &mut test_rng();
+        let domain_k: CosetDomain<F> = GeneralEvaluationDomain::<F>::new(8).unwrap().into();
+        let degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            domain_k.vanishing_polynomial().into(),
+            Some(degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| terms[1].clone())
+            .unwrap()
+            .with_scaling_factor(1);
+
+        // the transcript of a larger protocol, which absorbed its own statement before running ZeroOverK
+        let outer_transcript = || {
+            let mut transcript = FiatShamirTranscript::<FS>::new(b"outer");
+            transcript.append_message(b"outer_statement", b"statement");
+            transcript
+        };
+        let prove = |transcript: &mut dyn Transcript, rng: &mut _| {
+            ZeroOverK::<C>::prove_with_transcript(
+                &[&f],
+                &commitments,
+                &rands,
+                Some(degree_bound),
+                &vo,
+                &domain_k,
+                &ck,
+                CommitPolicy::NonHiding,
+                &Context::new(b"test"),
+                transcript,
+                rng,
+            )
+            .unwrap()
+        };
+        let verify = |proof, transcript: &mut dyn Transcript| {
+            ZeroOverK::<C>::verify_with_transcript(
+                proof,
+                &commitments,
+                Some(degree_bound),
+                &vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+                transcript,
+            )
+        };
+
+        let mut prover_transcript = outer_transcript();
+        let mut verifier_transcript = outer_transcript();
+        let proof = prove(&mut prover_transcript, rng);
+        assert_eq!(verify(proof, &mut verifier_transcript), Ok(()));
+        // the transcripts of prover and verifier are in the same state afterwards
+        let mut challenges = ([0u8; 32], [0u8; 32]);
+        prover_transcript.challenge_bytes(b"next", &mut challenges.0);
+        verifier_transcript.challenge_bytes(b"next", &mut challenges.1);
+        assert_eq!(challenges.0, challenges.1);
+
+        // the verifier has to run on the same transcript as the prover
+        let proof = prove(&mut outer_transcript(), rng);
+        assert!(verify(proof, &mut FiatShamirTranscript::<FS>::new(b"outer")).is_err());
+        let proof = prove(&mut outer_transcript(), rng);
+        assert!(ZeroOverK::<C>::verify_over_coset(
+            proof,
+            &commitments,
+            Some(degree_bound),
+            &vo,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_retry_from_checkpoint() {
+        let rng = &mut test_rng();
+        let domain_k: CosetDomain<F> = GeneralEvaluationDomain::<F>::new(8).unwrap().into();
+        let degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            domain_k.vanishing_polynomial().into(),
+            Some(degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| terms[1].clone())
+            .unwrap()
+            .with_scaling_factor(1);
+
+        let outer_transcript = || {
+            let mut transcript = FiatShamirTranscript::<FS>::new(b"outer");
+            transcript.append_message(b"outer_statement", b"statement");
+            transcript
+        };
+        let prove = |transcript: &mut dyn Transcript, rng: &mut _| {
+            ZeroOverK::<C>::prove_with_transcript(
+                &[&f],
+                &commitments,
+                &rands,
+                Some(degree_bound),
+                &vo,
+                &domain_k,
+                &ck,
+                CommitPolicy::NonHiding,
+                &Context::new(b"test"),
+                transcript,
+                rng,
+            )
+            .unwrap()
+        };
+
+        // the prover discards a first attempt and its challenges, and retries from the checkpoint
+        let mut prover_transcript = RewindableTranscript::new(outer_transcript());
+        let checkpoint = prover_transcript.checkpoint().unwrap();
+        prove(&mut prover_transcript, rng);
+        let stale = prover_transcript.checkpoint().unwrap();
+        prover_transcript.restore(&checkpoint).unwrap();
+        let proof = prove(&mut prover_transcript, rng);
+
+        // the verifier never saw the discarded attempt
+        let mut verifier_transcript = outer_transcript();
+        ZeroOverK::<C>::verify_with_transcript(
+            proof,
+            &commitments,
+            Some(degree_bound),
+            &vo,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+            &mut verifier_transcript,
+        )
+        .unwrap();
+        let mut challenges = ([0u8; 32], [0u8; 32]);
+        prover_transcript.challenge_bytes(b"next", &mut challenges.0);
+        verifier_transcript.challenge_bytes(b"next", &mut challenges.1);
+        assert_eq!(challenges.0, challenges.1);
+
+        // a checkpoint of the discarded attempt cannot be spliced back in, nor one of another transcript
+        assert_eq!(
+            prover_transcript.restore(&stale),
+            Err(TranscriptError::DiscardedCheckpoint)
+        );
+        let foreign = RewindableTranscript::new(outer_transcript())
+            .checkpoint()
+            .unwrap();
+        assert_eq!(
+            prover_transcript.restore(&foreign),
+            Err(TranscriptError::ForeignCheckpoint)
+        );
+
+        // once sealed, nothing appended so far can be rolled back
+        prover_transcript.seal();
+        assert_eq!(
+            prover_transcript.restore(&checkpoint),
+            Err(TranscriptError::DiscardedCheckpoint)
+        );
+        assert_eq!(
+            outer_transcript().checkpoint(),
+            Err(TranscriptError::NotRewindable)
+        );
+    }
+
+    #[test]
+    fn test_oracles_over_rounds() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        // an index oracle f committed to first, and a witness oracle g equal to it over K committed to later
+        let f_poly = DensePolynomial::<F>::rand(7, rng);
+        let vanishing: DensePolynomial<F> = domain_k.vanishing_polynomial().into();
+        let g_poly = &f_poly + &vanishing;
+        let labeled = |label: &str, poly: &DensePolynomial<F>| {
+            LabeledPolynomial::new(
+                String::from(label),
+                poly.clone(),
+                Some(degree_bound),
+                Some(1),
+            )
+        };
+        let (f, g) = (labeled("f", &f_poly), labeled("g", &g_poly));
+        let (f_commitments, f_rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let (g_commitments, g_rands) = PC::commit(&ck, [&g], Some(rng)).unwrap();
+
+        // f - g, with a term from each round
+        let vo = GenericShiftingVO::new(&[0, 1], &[F::one(), F::one()], presets::equality_check)
+            .unwrap();
+        let prove = |g: &LabeledPolynomial<F, DensePolynomial<F>>, rng: &mut _| {
+            let f_oracles = [&f];
+            let g_oracles = [g];
+            let rounds = [
+                OracleRound::<C> {
+                    oracles: &f_oracles,
+                    commitments: &f_commitments,
+                    rands: &f_rands,
+                },
+                OracleRound::<C> {
+                    oracles: &g_oracles,
+                    commitments: &g_commitments,
+                    rands: &g_rands,
+                },
+            ];
+            ZeroOverK::<C>::prove_over_rounds(
+                &rounds,
+                Some(degree_bound),
+                &vo,
+                &domain_k,
+                &ck,
+                CommitPolicy::NonHiding,
+                &Context::new(b"test"),
+                rng,
+            )
+        };
+        let verify = |proof, rounds: &[&[LabeledCommitment<_>]]| {
+            ZeroOverK::<C>::verify_over_rounds(
+                proof,
+                rounds,
+                Some(degree_bound),
+                &vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+            )
+        };
+
+        let proof = prove(&g, rng).unwrap();
+        assert_eq!(
+            verify(proof.clone(), &[&f_commitments, &g_commitments]),
+            Ok(())
+        );
+
+        // the transcript binds the rounds the oracles were committed in
+        let all_commitments = [f_commitments.clone(), g_commitments.clone()].concat();
+        assert!(verify(proof.clone(), &[&all_commitments]).is_err());
+        assert!(ZeroOverK::<C>::verify(
+            proof,
+            &all_commitments,
+            Some(degree_bound),
+            &vo,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        )
+        .is_err());
+
+        // the oracles of all rounds are opened together, so their labels must be distinct
+        assert!(matches!(
+            prove(&labeled("f", &g_poly), rng),
+            Err(Error::LabelMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_deferred_openings_over_two_domains() {
+        let rng = &mut test_rng();
+        let domain_k: CosetDomain<F> = GeneralEvaluationDomain::<F>::new(8).unwrap().into();
+        let domain_h: CosetDomain<F> = GeneralEvaluationDomain::<F>::new(4).unwrap().into();
+        let degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, degree_bound])).unwrap();
+
+        // f vanishes over K and g over H, both labeled "f" as each sub-proof is namespaced in the accumulator
+        let vanishing = |domain: &CosetDomain<F>| {
+            LabeledPolynomial::new(
+                String::from("f"),
+                &Into::<DensePolynomial<F>>::into(domain.vanishing_polynomial())
+                    * &DensePolynomial::from_coefficients_slice(&[F::one(), F::one()]),
+                Some(degree_bound),
+                Some(1),
+            )
+        };
+        let f = vanishing(&domain_k);
+        let g = vanishing(&domain_h);
+        let (f_commitments, f_rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let (g_commitments, g_rands) = PC::commit(&ck, [&g], Some(rng)).unwrap();
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| terms[1].clone())
+            .unwrap();
+        let opening_challenge = |transcript: &mut dyn Transcript| {
+            let mut bytes = [0u8; 32];
+            transcript.challenge_bytes(b"opening", &mut bytes);
+            F::from_le_bytes_mod_order(&bytes)
+        };
+
+        let mut transcript = FiatShamirTranscript::<FS>::new(b"outer");
+        let mut accumulator = ProverAccumulator::<F, PC>::new();
+        let mut proofs = Vec::new();
+        for (oracle, commitments, rands, domain) in [
+            (&f, &f_commitments, &f_rands, &domain_k),
+            (&g, &g_commitments, &g_rands, &domain_h),
+        ] {
+            proofs.push(
+                ZeroOverK::<C>::prove_deferred(
+                    &[oracle],
+                    commitments,
+                    rands,
+                    Some(degree_bound),
+                    &vo,
+                    domain,
+                    &ck,
+                    CommitPolicy::NonHiding,
+                    &Context::new(b"test"),
+                    &mut transcript,
+                    &mut accumulator,
+                    rng,
+                )
+                .unwrap(),
+            );
+        }
+        assert_eq!(accumulator.len(), 2);
+        let opening_proof = accumulator
+            .open(&ck, opening_challenge(&mut transcript), rng)
+            .unwrap();
+
+        let mut verify = |proofs: &[DeferredProof<C>], commitments: [&Vec<_>; 2]| {
+            let mut transcript = FiatShamirTranscript::<FS>::new(b"outer");
+            let mut accumulator = VerifierAccumulator::<F, PC>::new();
+            for ((proof, commitments), domain) in
+                proofs.iter().zip(commitments).zip([&domain_k, &domain_h])
+            {
+                ZeroOverK::<C>::verify_deferred(
+                    proof,
+                    commitments,
+                    Some(degree_bound),
+                    &vo,
+                    domain,
+                    &vk,
+                    &Context::new(b"test"),
+                    &mut transcript,
+                    &mut accumulator,
+                )?;
+            }
+            let opening_challenge = opening_challenge(&mut transcript);
+            match accumulator.check(&vk, &opening_proof, opening_challenge, rng) {
+                Ok(true) => Ok(()),
+                _ => Err(Error::BatchCheckError),
+            }
+        };
+        assert_eq!(verify(&proofs, [&f_commitments, &g_commitments]), Ok(()));
+
+        // the sub-proofs are bound to their commitments, and their evaluations to the single opening
+        assert!(verify(&proofs, [&g_commitments, &f_commitments]).is_err());
+        proofs[1].q1_eval += F::one();
+        proofs[1].q2_eval += F::one();
+        assert!(verify(&proofs, [&f_commitments, &g_commitments]).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_matches_proof() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let degree_bound = 14;
+
+        // Z_K(gamma * X) = Z_K(X), so that the check holds with two distinct shifts
+        let vo = GenericShiftingVO::new(
+            &[0, 0],
+            &[F::one(), domain_k.element(1)],
+            |terms: &[VOTerm<F>]| terms[1].clone() - terms[2].clone(),
+        )
+        .unwrap();
+        let shape = ZeroOverK::<C>::prove_dry_run(Some(degree_bound), &vo);
+        assert_eq!(shape.num_points(), 4);
+        // the commitments give beta_1, beta_2 and c, and the evaluations the separation challenge
+        assert_eq!(shape.num_rounds(), 2);
+        assert_eq!(shape.challenges.len(), 4);
+
+        // keys trimmed to exactly the degree bounds of the shape
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&shape.degree_bounds())).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            domain_k.vanishing_polynomial().into(),
+            Some(degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let proof = ZeroOverK::<C>::prove(
+            &[&f],
+            &commitments,
+            &rands,
+            Some(degree_bound),
+            &vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        assert_eq!(
+            shape.commitments.len(),
+            proof.r_commitments.len() + proof.m_commitments.len() + 1
+        );
+        assert_eq!(
+            shape.queries.len(),
+            proof.h_prime_evals.len() + proof.m_evals.len() + 2
+        );
+        assert_eq!(shape.num_points(), proof.opening_proof.len());
+        assert_eq!(
+            ZeroOverK::<C>::proof_size(
+                Some(degree_bound),
+                &vo,
+                CommitPolicy::NonHiding,
+                ck.supported_degree()
+            ),
+            proof.serialized_size()
+        );
+
+        assert_eq!(
+            ZeroOverK::<C>::verify(
+                proof,
+                &commitments,
+                Some(degree_bound),
+                &vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_label_checks() {
+        let rng = &mut test_rng();
+        let pp = PC::setup(8, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 8, 0, None).unwrap();
+
+        let mut labeled = |label: &str| {
+            LabeledPolynomial::new(
+                String::from(label),
+                DensePolynomial::rand(3, rng),
+                None,
+                None,
+            )
+        };
+        let (f, g) = (labeled("f"), labeled("g"));
+        let (commitments, _) = PC::commit(&ck, [&f, &g], None).unwrap();
+        assert_eq!(
+            ZeroOverK::<C>::check_oracle_labels(&[&f, &g], &commitments),
+            Ok(())
+        );
+
+        // the oracles must be given in the order of their commitments
+        assert!(matches!(
+            ZeroOverK::<C>::check_oracle_labels(&[&g, &f], &commitments),
+            Err(Error::LabelMismatch(_))
+        ));
+
+        let relabel = |label: &str| {
+            let mut relabeled = commitments.clone();
+            relabeled[1] =
+                LabeledCommitment::new(String::from(label), *commitments[1].commitment(), None);
+            ZeroOverK::<C>::check_commitment_labels(&relabeled)
+        };
+        assert_eq!(relabel("m"), Ok(()));
+        assert_eq!(relabel("q_10"), Ok(()));
+        for label in ["f", "m_0", "r_1", "h_prime_12", "q_1", "q_2"] {
+            assert!(matches!(relabel(label), Err(Error::LabelMismatch(_))));
+        }
+    }
+
+    #[test]
+    fn test_prove_from_hints() {
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let enforced_degree_bound = 14;
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, 20, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        // f and g agree over K but not elsewhere
+        let f_poly = DensePolynomial::<F>::rand(7, rng);
+        let vanishing: DensePolynomial<F> = domain_k.vanishing_polynomial().into();
+        let g_poly = &f_poly + &vanishing;
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            f_poly,
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            g_poly,
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+
+        // the owner commits and hands out hints, the delegate proves from them alone
+        let (commitments, rands) = PC::commit(&ck, [&f, &g], Some(rng)).unwrap();
+        let hints = opening_hint::hints::<F, PC>([&f, &g], &rands).unwrap();
+
+        let vo = GenericShiftingVO::new(&[0, 1], &[F::one(); 2], presets::equality_check).unwrap();
+        let proof = ZeroOverK::<C>::prove_from_hints(
+            &hints,
+            &commitments,
+            Some(enforced_degree_bound),
+            &vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+        assert_eq!(
+            ZeroOverK::<C>::verify(
+                proof,
+                &commitments,
+                Some(enforced_degree_bound),
+                &vo,
+                &domain_k,
+                &vk,
+                &Context::new(b"test"),
+            ),
+            Ok(())
+        );
+
+        // hints out of the order of the commitments are rejected
+        let swapped = [hints[1].clone(), hints[0].clone()];
+        assert!(matches!(
+            ZeroOverK::<C>::prove_from_hints(
+                &swapped,
+                &commitments,
+                Some(enforced_degree_bound),
+                &vo,
+                &domain_k,
+                &ck,
+                &Context::new(b"test"),
+                rng,
+            ),
+            Err(Error::LabelMismatch(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        use crate::zero_over_k::proof::Proof;
+
+        let m = 8;
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(m).unwrap();
+
+        let max_degree = 20;
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(max_degree, None, rng).unwrap();
+        let (ck, vk) = PC::trim(&pp, max_degree, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            DensePolynomial::rand(7, rng),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let g_evals = f
+            .evaluate_over_domain_by_ref(domain_k)
+            .evals
+            .iter()
+            .map(|&x| x.inverse().unwrap())
+            .collect::<Vec<_>>();
+        let g = LabeledPolynomial::new(
+            String::from("g"),
+            Evaluations::from_vec_and_domain(g_evals, domain_k).interpolate(),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+
+        let concrete_oracles = [&f, &g];
+        let (commitments, rands) = PC::commit(&ck, concrete_oracles, Some(rng)).unwrap();
+
+        let alphas = [F::one(), F::one()];
+        let inverse_check_oracle =
+            GenericShiftingVO::new(&[0, 1], &alphas, presets::inverse_check).unwrap();
+
+        let proof = ZeroOverK::<C>::prove(
+            &concrete_oracles,
+            &commitments,
+            &rands,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        // group and field elements are hex strings
+        let json = serde_json::to_value(&proof).unwrap();
+        assert!(json["q1_eval"].is_string());
+        assert!(json["m_commitments"][0].is_string());
+
+        let proof: Proof<C> = serde_json::from_value(json).unwrap();
+        let res = ZeroOverK::<C>::verify(
+            proof,
+            &commitments,
+            Some(enforced_degree_bound),
+            &inverse_check_oracle,
+            &domain_k,
+            &vk,
+            &Context::new(b"test"),
+        );
+        assert_eq!(res, Ok(()));
+
+        // malformed hex is reported rather than silently decoded
+        let json = serde_json::to_string(&serde_json::json!({ "q1_eval": "zz" })).unwrap();
+        assert!(serde_json::from_str::<Proof<C>>(&json).is_err());
+    }
+
+    #[test]
+    fn test_proof_equality() {
+        use crate::zero_over_k::proof::Proof;
+        use ark_serialize::CanonicalDeserialize;
+
+        let rng = &mut test_rng();
+        let domain_k = GeneralEvaluationDomain::<F>::new(8).unwrap();
+        let enforced_degree_bound = 14;
+
+        let pp = PC::setup(20, None, rng).unwrap();
+        let (ck, _) = PC::trim(&pp, 20, 1, Some(&[2, enforced_degree_bound])).unwrap();
+
+        let f = LabeledPolynomial::new(
+            String::from("f"),
+            &Into::<DensePolynomial<F>>::into(domain_k.vanishing_polynomial())
+                * &DensePolynomial::rand(3, rng),
+            Some(enforced_degree_bound),
+            Some(1),
+        );
+        let (commitments, rands) = PC::commit(&ck, [&f], Some(rng)).unwrap();
+        let vo = GenericShiftingVO::new(&[0], &[F::one()], |terms: &[VOTerm<F>]| terms[1].clone())
+            .unwrap();
+
+        let proof = ZeroOverK::<C>::prove(
+            &[&f],
+            &commitments,
+            &rands,
+            Some(enforced_degree_bound),
+            &vo,
+            &domain_k,
+            &ck,
+            &Context::new(b"test"),
+            rng,
+        )
+        .unwrap();
+
+        // proofs compare field by field, whether cloned or read back from their serialization
+        let mut bytes = Vec::new();
+        proof.serialize(&mut bytes).unwrap();
+        assert_eq!(Proof::<C>::deserialize(&bytes[..]).unwrap(), proof);
+
+        let mut other = proof.clone();
+        assert_eq!(other, proof);
+        other.q1_eval += F::one();
+        assert_ne!(other, proof);
+
+        let (deferred, _) = proof.clone().into_deferred();
+        assert_eq!(deferred.clone(), deferred);
+        assert!(format!("{:?}", proof).contains("q1_eval"));
+    }
 }